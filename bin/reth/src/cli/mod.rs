@@ -2,13 +2,16 @@
 
 use crate::{
     args::{
-        utils::{chain_help, chain_value_parser, SUPPORTED_CHAINS},
+        utils::{chain_help, chain_value_parser, parse_duration_from_secs, SUPPORTED_CHAINS},
         LogArgs,
     },
     commands::{
-        config_cmd, db, debug_cmd, dump_genesis, import, init_cmd, init_state,
+        chainspec, cold_storage, config_cmd,
+        dashboard::TuiArgs,
+        datadir, db, debug_cmd, dev, doctor, dump_genesis, export, import, init_cmd, init_state,
+        install_service,
         node::{self, NoArgs},
-        p2p, recover, stage, test_vectors,
+        p2p, recover, stage, state, stats, test_vectors, unwind, verify,
     },
     version::{LONG_VERSION, SHORT_VERSION},
 };
@@ -17,8 +20,9 @@ use reth_chainspec::ChainSpec;
 use reth_cli_runner::CliRunner;
 use reth_db::DatabaseEnv;
 use reth_node_builder::{NodeBuilder, WithLaunchContext};
+use reth_node_core::crash_report;
 use reth_tracing::FileWorkerGuard;
-use std::{ffi::OsString, fmt, future::Future, sync::Arc};
+use std::{ffi::OsString, fmt, future::Future, sync::Arc, time::Duration};
 use tracing::info;
 
 /// Re-export of the `reth_node_core` types specifically in the `cli` module.
@@ -67,6 +71,15 @@ pub struct Cli<Ext: clap::Args + fmt::Debug = NoArgs> {
     #[arg(long, value_name = "INSTANCE", global = true, default_value_t = 1, value_parser = value_parser!(u16).range(..=200))]
     instance: u16,
 
+    /// How long to wait for the command to commit its progress after receiving `SIGINT` or
+    /// `SIGTERM`, in seconds.
+    ///
+    /// On a shutdown signal, the current batch of work is finished and committed. If this takes
+    /// longer than the timeout, the process exits without committing the batch, which is then
+    /// redone on the next start.
+    #[arg(long, value_name = "SECONDS", global = true, default_value = "60", value_parser = parse_duration_from_secs)]
+    shutdown_timeout: Duration,
+
     #[command(flatten)]
     logs: LogArgs,
 }
@@ -139,11 +152,16 @@ impl<Ext: clap::Args + fmt::Debug> Cli<Ext> {
         self.logs.log_file_directory =
             self.logs.log_file_directory.join(self.chain.chain.to_string());
 
-        let _guard = self.init_tracing()?;
+        // with the dashboard, the log output is shown by the dashboard instead of stdout
+        let _guard = match self.command.tui_mut().and_then(TuiArgs::init_log_buffer) {
+            Some(log_buffer) => self.logs.init_tracing_with_log_buffer(log_buffer)?,
+            None => self.init_tracing()?,
+        };
         info!(target: "reth::cli", "Initialized tracing, debug log directory: {}", self.logs.log_file_directory);
+        crash_report::install_panic_hook();
 
-        let runner = CliRunner::default();
-        match self.command {
+        let runner = CliRunner::default().with_shutdown_timeout(self.shutdown_timeout);
+        let res = match self.command {
             Commands::Node(command) => {
                 runner.run_command_until_exit(|ctx| command.execute(ctx, launcher))
             }
@@ -159,12 +177,34 @@ impl<Ext: clap::Args + fmt::Debug> Cli<Ext> {
             Commands::DumpGenesis(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::Db(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::Stage(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
+            Commands::Unwind(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::P2P(command) => runner.run_until_ctrl_c(command.execute()),
             Commands::TestVectors(command) => runner.run_until_ctrl_c(command.execute()),
             Commands::Config(command) => runner.run_until_ctrl_c(command.execute()),
+            Commands::Chainspec(command) => runner.run_until_ctrl_c(command.execute()),
             Commands::Debug(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
             Commands::Recover(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
+            Commands::Doctor(command) => runner.run_until_ctrl_c(command.execute()),
+            Commands::Datadir(command) => runner.run_until_ctrl_c(command.execute()),
+            Commands::InstallService(command) => runner.run_until_ctrl_c(command.execute()),
+            Commands::Dev(command) => runner.run_blocking_until_ctrl_c(command.execute()),
+            Commands::Verify(command) => runner.run_until_ctrl_c(command.execute()),
+            Commands::State(command) => runner.run_until_ctrl_c(command.execute()),
+            Commands::Stats(command) => runner.run_until_ctrl_c(command.execute()),
+            Commands::Export(command) => runner.run_until_ctrl_c(command.execute()),
+            Commands::PruneStaticFilesToColdStorage(command) => {
+                runner.run_until_ctrl_c(command.execute())
+            }
+        };
+        if let Err(err) = &res {
+            crash_report::write_error_report(err);
         }
+        res
+    }
+
+    /// Returns how long the command is given to commit its progress after a shutdown signal.
+    pub(crate) const fn shutdown_timeout(&self) -> Duration {
+        self.shutdown_timeout
     }
 
     /// Initializes tracing with the configured options.
@@ -208,6 +248,9 @@ pub enum Commands<Ext: clap::Args + fmt::Debug = NoArgs> {
     /// Manipulate individual stages.
     #[command(name = "stage")]
     Stage(stage::Command),
+    /// Unwind the database, static files and prune checkpoints to a block.
+    #[command(name = "unwind")]
+    Unwind(unwind::Command),
     /// P2P Debugging utilities
     #[command(name = "p2p")]
     P2P(p2p::Command),
@@ -217,18 +260,62 @@ pub enum Commands<Ext: clap::Args + fmt::Debug = NoArgs> {
     /// Write config to stdout
     #[command(name = "config")]
     Config(config_cmd::Command),
+    /// Validate and inspect chain specifications
+    #[command(name = "chainspec")]
+    Chainspec(chainspec::Command),
     /// Various debug routines
     #[command(name = "debug")]
     Debug(debug_cmd::Command),
     /// Scripts for node recovery
     #[command(name = "recover")]
     Recover(recover::Command),
+    /// Check the health of a datadir
+    #[command(name = "doctor")]
+    Doctor(doctor::Command),
+    /// Manage the layout of a datadir
+    #[command(name = "datadir")]
+    Datadir(datadir::Command),
+    /// Install a systemd or launchd service that runs the node
+    #[command(name = "install-service")]
+    InstallService(install_service::Command<Ext>),
+    /// Development utilities
+    #[command(name = "dev")]
+    Dev(dev::Command),
+    /// Verify the local chain against other sources
+    #[command(name = "verify")]
+    Verify(verify::Command),
+    /// Inspect the state stored in the datadir
+    #[command(name = "state")]
+    State(state::Command),
+    /// Report statistics of the data stored in the datadir
+    #[command(name = "stats")]
+    Stats(stats::Command),
+    /// Export data stored in the datadir to files
+    #[command(name = "export")]
+    Export(export::Command),
+    /// Archive old static files to cold storage and prune them
+    #[command(name = "prune-static-files-to-cold-storage")]
+    PruneStaticFilesToColdStorage(cold_storage::Command),
+}
+
+impl<Ext: clap::Args + fmt::Debug> Commands<Ext> {
+    /// Returns the live dashboard arguments of the command, if it can show the dashboard.
+    fn tui_mut(&mut self) -> Option<&mut TuiArgs> {
+        match self {
+            Self::Node(command) => Some(&mut command.tui),
+            Self::Import(command) => Some(&mut command.tui),
+            #[cfg(feature = "optimism")]
+            Self::ImportOp(command) => Some(&mut command.tui),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::args::ColorMode;
+    use reth_tracing::LogFormat;
     use clap::CommandFactory;
 
     #[test]
@@ -237,6 +324,49 @@ mod tests {
         assert_eq!(reth.logs.color, ColorMode::Always);
     }
 
+    #[test]
+    fn parse_log_format_and_filter() {
+        let reth = Cli::try_parse_args_from([
+            "reth",
+            "node",
+            "--log.format",
+            "json",
+            "--log.filter",
+            "reth::stages=debug,net=warn",
+            "--log.filter",
+            "rpc=trace",
+        ])
+        .unwrap();
+        assert_eq!(reth.logs.log_format, Some(LogFormat::Json));
+        assert_eq!(
+            reth.logs.log_filter.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            ["reth::stages=debug", "net=warn", "rpc=trace"]
+        );
+
+        // the format of both outputs can't be set at the same time as the format of one output
+        assert!(Cli::try_parse_args_from([
+            "reth",
+            "node",
+            "--log.format",
+            "json",
+            "--log.file.format",
+            "terminal"
+        ])
+        .is_err());
+        assert!(Cli::try_parse_args_from(["reth", "node", "--log.filter", "reth=nope"]).is_err());
+    }
+
+    #[test]
+    fn parse_shutdown_timeout() {
+        let reth = Cli::try_parse_args_from(["reth", "import", "blocks.rlp"]).unwrap();
+        assert_eq!(reth.shutdown_timeout, reth_cli_runner::DEFAULT_SHUTDOWN_TIMEOUT);
+
+        let reth =
+            Cli::try_parse_args_from(["reth", "import", "--shutdown-timeout", "5", "blocks.rlp"])
+                .unwrap();
+        assert_eq!(reth.shutdown_timeout, Duration::from_secs(5));
+    }
+
     /// Tests that the help message is parsed correctly. This ensures that clap args are configured
     /// correctly and no conflicts are introduced via attributes that would result in a panic at
     /// runtime
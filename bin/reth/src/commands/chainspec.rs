@@ -0,0 +1,202 @@
+//! `reth chainspec` command.
+
+use crate::args::utils::{chain_help, chain_value_parser, SUPPORTED_CHAINS};
+use clap::{Parser, Subcommand};
+use reth_chainspec::{ChainSpec, ForkCondition, ForkId, Hardfork};
+use reth_primitives::{hex, B256};
+use std::sync::Arc;
+
+/// `reth chainspec` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth chainspec` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands {
+    /// Checks the chain specification for internal consistency.
+    Validate(ValidateCommand),
+    /// Prints the genesis hash, the hardfork schedule and the fork ids of the chain specification.
+    Print(PrintCommand),
+}
+
+impl Command {
+    /// Execute `chainspec` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::Validate(command) => command.execute(),
+            Subcommands::Print(command) => command.execute(),
+        }
+    }
+}
+
+/// `reth chainspec validate` command
+#[derive(Debug, Parser)]
+pub struct ValidateCommand {
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = chain_help(),
+        default_value = SUPPORTED_CHAINS[0],
+        value_parser = chain_value_parser
+    )]
+    chain: Arc<ChainSpec>,
+
+    /// The expected genesis hash.
+    ///
+    /// If set, the computed genesis hash must match.
+    #[arg(long, value_name = "HASH")]
+    genesis_hash: Option<B256>,
+}
+
+impl ValidateCommand {
+    /// Execute `chainspec validate` command
+    pub fn execute(self) -> eyre::Result<()> {
+        let problems = validate(&self.chain, self.genesis_hash);
+        if problems.is_empty() {
+            println!("Chain specification is valid, genesis hash {}", self.chain.genesis_hash());
+            return Ok(())
+        }
+
+        for problem in &problems {
+            println!("error: {problem}");
+        }
+        eyre::bail!("chain specification has {} problem(s)", problems.len())
+    }
+}
+
+/// `reth chainspec print` command
+#[derive(Debug, Parser)]
+pub struct PrintCommand {
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = chain_help(),
+        default_value = SUPPORTED_CHAINS[0],
+        value_parser = chain_value_parser
+    )]
+    chain: Arc<ChainSpec>,
+}
+
+impl PrintCommand {
+    /// Execute `chainspec print` command
+    pub fn execute(self) -> eyre::Result<()> {
+        let chain = &self.chain;
+        println!("Chain:        {}", chain.chain);
+        println!("Genesis hash: {}", chain.genesis_header().hash_slow());
+        println!();
+        println!("{}", chain.display_hardforks());
+        println!("Fork ids:");
+        for (forks, fork_id) in fork_ids(chain) {
+            let forks = forks.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ");
+            println!("  0x{} next {:<12} {forks}", hex::encode(fork_id.hash.0), fork_id.next);
+        }
+        Ok(())
+    }
+}
+
+/// Returns the distinct fork ids of the chain, with the hardforks that activate each of them.
+fn fork_ids(chain: &ChainSpec) -> Vec<(Vec<Hardfork>, ForkId)> {
+    let mut fork_ids: Vec<(Vec<Hardfork>, ForkId)> = Vec::new();
+    for (fork, _) in chain.forks_iter() {
+        let Some(fork_id) = chain.hardfork_fork_id(fork) else { continue };
+        match fork_ids.last_mut() {
+            Some((forks, last)) if last.hash == fork_id.hash => forks.push(fork),
+            _ => fork_ids.push((vec![fork], fork_id)),
+        }
+    }
+    fork_ids
+}
+
+/// Checks the chain specification for internal consistency and returns the problems found.
+fn validate(chain: &ChainSpec, expected_genesis_hash: Option<B256>) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    if let Some((earlier, later)) = chain.misordered_hardforks() {
+        problems.push(format!(
+            "{later} ({:?}) activates before {earlier} ({:?})",
+            chain.fork(later),
+            chain.fork(earlier)
+        ));
+    }
+
+    let computed = chain.genesis_header().hash_slow();
+    if let Some(hardcoded) = chain.genesis_hash {
+        if hardcoded != computed {
+            problems.push(format!(
+                "genesis hash {hardcoded} does not match the hash of the genesis header {computed}"
+            ));
+        }
+    }
+    if let Some(expected) = expected_genesis_hash {
+        if expected != computed {
+            problems.push(format!("genesis hash {computed} does not match expected {expected}"));
+        }
+    }
+
+    // timestamp based hardforks are only supported after the merge
+    if chain.fork(Hardfork::Paris) == ForkCondition::Never {
+        for (fork, condition) in chain.forks_iter() {
+            if condition.is_timestamp() {
+                problems.push(format!(
+                    "{fork} is activated by timestamp, but the Paris hardfork is not configured"
+                ));
+            }
+        }
+    }
+
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chainspec::{ChainSpecBuilder, MAINNET};
+
+    #[test]
+    fn parse_chainspec_command() {
+        let command = Command::parse_from(["reth", "validate", "--chain", SUPPORTED_CHAINS[0]]);
+        assert!(matches!(command.command, Subcommands::Validate(_)));
+        let command = Command::parse_from(["reth", "print"]);
+        assert!(matches!(command.command, Subcommands::Print(_)));
+    }
+
+    #[test]
+    #[cfg(not(feature = "optimism"))]
+    fn validate_built_in_chains() {
+        for chain in SUPPORTED_CHAINS {
+            let chain = chain_value_parser(chain).unwrap();
+            assert_eq!(validate(&chain, None), Vec::<String>::new(), "{}", chain.chain);
+        }
+    }
+
+    #[test]
+    fn validate_problems() {
+        let chain = ChainSpecBuilder::mainnet().build();
+        let problems = validate(&chain, Some(B256::ZERO));
+        assert_eq!(problems.len(), 1, "{problems:?}");
+
+        let mut chain = ChainSpecBuilder::mainnet().build();
+        chain.set_fork_condition(Hardfork::Paris, ForkCondition::Never);
+        chain.set_fork_condition(Hardfork::Cancun, ForkCondition::Timestamp(0));
+        let problems = validate(&chain, None);
+        // Shanghai activates after Cancun, and both activate without Paris
+        assert_eq!(problems.len(), 3, "{problems:?}");
+    }
+
+    #[test]
+    fn mainnet_fork_ids() {
+        let fork_ids = fork_ids(&MAINNET);
+        assert_eq!(fork_ids.last().unwrap().1, MAINNET.latest_fork_id());
+        // Frontier and Homestead have distinct fork ids
+        assert_eq!(fork_ids[0].0, vec![Hardfork::Frontier]);
+    }
+}
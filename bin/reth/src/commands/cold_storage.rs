@@ -0,0 +1,444 @@
+//! Command that archives old static files to cold storage before pruning them.
+
+use crate::commands::common::{AccessRights, Environment, EnvironmentArgs};
+use clap::Parser;
+use eyre::WrapErr;
+use reth_db::static_file::receipt_topics_path;
+use reth_fs_util as fs;
+use reth_nippy_jar::NippyJar;
+use reth_primitives::{BlockNumber, B256};
+use reth_provider::{
+    providers::StaticFileProvider, PruneCheckpointReader, PruneCheckpointWriter,
+    StaticFileProviderFactory,
+};
+use reth_prune_types::{PruneCheckpoint, PruneMode, PruneSegment};
+use reth_static_file_types::{
+    find_fixed_range, SegmentHeader, SegmentRangeInclusive, StaticFileSegment,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+};
+use tracing::info;
+
+/// Name of the manifest at the root of the cold storage.
+const MANIFEST: &str = "manifest.json";
+
+/// Name of the local copy of the manifest in the datadir.
+const LOCAL_MANIFEST: &str = "cold-storage-manifest.json";
+
+/// `reth prune-static-files-to-cold-storage` command
+///
+/// Uploads the static files of old receipts and transactions to cold storage, records them in the
+/// `manifest.json` of the storage and deletes them locally. Only whole static files below the
+/// `--before` block are archived, the highest static file of a segment is always kept.
+///
+/// Archived data can be recovered by downloading the files listed in the manifest back into the
+/// `static_files` directory of the datadir.
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    /// The cold storage to upload the static files to.
+    ///
+    /// Either a directory, e.g. a mounted bucket, as a path or `file://` URL, or an `http(s)://`
+    /// URL of an endpoint accepting `PUT` requests for the files.
+    #[arg(long, value_name = "URL")]
+    storage: String,
+
+    /// Header sent with the requests to an HTTP cold storage, e.g. `Authorization: Bearer
+    /// <TOKEN>`.
+    #[arg(long = "storage.header", value_name = "HEADER", value_parser = parse_header)]
+    storage_headers: Vec<(String, String)>,
+
+    /// The segments to archive.
+    #[arg(
+        long,
+        value_delimiter = ',',
+        default_value = "receipts,transactions",
+        value_parser = parse_segment
+    )]
+    segments: Vec<StaticFileSegment>,
+
+    /// Archive the static files that end before this block.
+    #[arg(long, value_name = "BLOCK")]
+    before: BlockNumber,
+}
+
+/// Manifest of the static files archived in a cold storage.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ColdStorageManifest {
+    /// Chain ID of the archived static files.
+    chain_id: u64,
+    /// Genesis hash of the archived static files.
+    genesis_hash: B256,
+    /// The archived static files, sorted by segment and block range.
+    static_files: Vec<ArchivedStaticFile>,
+}
+
+impl ColdStorageManifest {
+    /// Records the static file, replacing a previous record of the same segment and block range.
+    fn record(&mut self, static_file: ArchivedStaticFile) {
+        self.static_files.retain(|archived| {
+            archived.segment != static_file.segment ||
+                archived.block_range != static_file.block_range
+        });
+        self.static_files.push(static_file);
+        self.static_files.sort_by_key(|archived| (archived.segment, archived.block_range.start()));
+    }
+}
+
+/// A static file archived in a cold storage.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ArchivedStaticFile {
+    segment: StaticFileSegment,
+    block_range: SegmentRangeInclusive,
+    tx_range: Option<SegmentRangeInclusive>,
+    /// The data file and its satellite files.
+    files: Vec<ArchivedFile>,
+}
+
+/// A file archived in a cold storage.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ArchivedFile {
+    name: String,
+    size: u64,
+    sha256: B256,
+}
+
+impl Command {
+    /// Execute `prune-static-files-to-cold-storage` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let Environment { provider_factory, data_dir, .. } = self.env.init(AccessRights::RW)?;
+        let storage = ColdStorage::new(&self.storage, &self.storage_headers)?;
+
+        let local_manifest = data_dir.data_dir().join(LOCAL_MANIFEST);
+        let mut manifest = match storage.get(MANIFEST).await? {
+            Some(manifest) => serde_json::from_slice(&manifest)?,
+            None => ColdStorageManifest {
+                chain_id: self.env.chain.chain.id(),
+                genesis_hash: self.env.chain.genesis_hash(),
+                ..Default::default()
+            },
+        };
+        if manifest.chain_id != self.env.chain.chain.id() ||
+            manifest.genesis_hash != self.env.chain.genesis_hash()
+        {
+            eyre::bail!(
+                "the cold storage holds static files of chain {} with genesis {}",
+                manifest.chain_id,
+                manifest.genesis_hash
+            )
+        }
+
+        let static_file_provider = provider_factory.static_file_provider();
+        for segment in self.segments.iter().copied() {
+            let prune_segment = match segment {
+                StaticFileSegment::Receipts => PruneSegment::Receipts,
+                StaticFileSegment::Transactions => PruneSegment::Transactions,
+                StaticFileSegment::Headers => eyre::bail!("headers can't be archived"),
+            };
+
+            while let Some(fixed_block_range) =
+                self.next_static_file(&static_file_provider, segment)
+            {
+                let path =
+                    static_file_provider.directory().join(segment.filename(&fixed_block_range));
+                let jar = NippyJar::<SegmentHeader>::load(&path)?;
+                let header = jar.user_header();
+
+                let mut files = Vec::new();
+                for file in [
+                    jar.data_path().to_path_buf(),
+                    jar.offsets_path(),
+                    jar.config_path(),
+                    jar.index_path(),
+                    receipt_topics_path(jar.data_path()),
+                ] {
+                    if !file.exists() {
+                        continue
+                    }
+                    let name = file_name(&file)?;
+                    let (size, sha256) = hash_file(&file)?;
+                    storage.put_file(&name, &file).await?;
+                    files.push(ArchivedFile { name, size, sha256 });
+                }
+                let archived = ArchivedStaticFile {
+                    segment,
+                    block_range: header.block_range().copied().unwrap_or(fixed_block_range),
+                    tx_range: header.tx_range().copied(),
+                    files,
+                };
+                let (block_end, tx_end) =
+                    (archived.block_range.end(), archived.tx_range.map(|range| range.end()));
+                manifest.record(archived);
+
+                // The manifest must be stored before the static file is deleted, so the archived
+                // data remains recoverable.
+                let manifest_json = serde_json::to_vec_pretty(&manifest)?;
+                storage.put(MANIFEST, manifest_json.clone()).await?;
+                fs::write(&local_manifest, manifest_json)?;
+
+                if static_file_provider.delete_lowest_jar(segment)? != Some(fixed_block_range) {
+                    eyre::bail!("static file {} is no longer the lowest", path.display())
+                }
+
+                let provider = provider_factory.provider_rw()?;
+                let checkpoint = provider.get_prune_checkpoint(prune_segment)?;
+                if checkpoint.and_then(|checkpoint| checkpoint.block_number) < Some(block_end) {
+                    provider.save_prune_checkpoint(
+                        prune_segment,
+                        PruneCheckpoint {
+                            block_number: Some(block_end),
+                            tx_number: tx_end,
+                            prune_mode: PruneMode::Before(block_end + 1),
+                        },
+                    )?;
+                }
+                provider.commit()?;
+
+                info!(
+                    target: "reth::cli",
+                    %segment,
+                    block_range = %fixed_block_range,
+                    storage = %self.storage,
+                    "Archived static file"
+                );
+            }
+        }
+
+        info!(target: "reth::cli", manifest = %local_manifest.display(), "Archived static files");
+        Ok(())
+    }
+
+    /// Returns the fixed block range of the lowest static file of the segment, if it ends before
+    /// the `--before` block and isn't the highest static file of the segment.
+    fn next_static_file(
+        &self,
+        static_file_provider: &StaticFileProvider,
+        segment: StaticFileSegment,
+    ) -> Option<SegmentRangeInclusive> {
+        next_static_file(
+            static_file_provider.get_lowest_local_static_file_range(segment)?.start(),
+            static_file_provider.get_highest_static_file_block(segment)?,
+            self.before,
+        )
+    }
+}
+
+/// Returns the fixed block range of the static file of the lowest block, if it ends before the
+/// `before` block and doesn't contain the highest block.
+fn next_static_file(
+    lowest_block: BlockNumber,
+    highest_block: BlockNumber,
+    before: BlockNumber,
+) -> Option<SegmentRangeInclusive> {
+    let fixed_block_range = find_fixed_range(lowest_block);
+    (fixed_block_range.end() < before && fixed_block_range.end() < highest_block)
+        .then_some(fixed_block_range)
+}
+
+/// A cold storage the static files are uploaded to.
+#[derive(Debug)]
+enum ColdStorage {
+    /// A directory, e.g. a mounted bucket.
+    Dir(PathBuf),
+    /// An HTTP endpoint accepting `PUT` requests for the files.
+    Http { client: reqwest::Client, url: String, headers: reqwest::header::HeaderMap },
+}
+
+impl ColdStorage {
+    fn new(storage: &str, headers: &[(String, String)]) -> eyre::Result<Self> {
+        if storage.starts_with("http://") || storage.starts_with("https://") {
+            let mut header_map = reqwest::header::HeaderMap::new();
+            for (name, value) in headers {
+                header_map.insert(
+                    reqwest::header::HeaderName::from_bytes(name.as_bytes())?,
+                    reqwest::header::HeaderValue::from_str(value)?,
+                );
+            }
+            return Ok(Self::Http {
+                client: reqwest::Client::new(),
+                url: storage.trim_end_matches('/').to_string(),
+                headers: header_map,
+            })
+        }
+
+        let dir = PathBuf::from(storage.strip_prefix("file://").unwrap_or(storage));
+        fs::create_dir_all(&dir)?;
+        Ok(Self::Dir(dir))
+    }
+
+    /// Returns the content of the file, or `None` if it doesn't exist.
+    async fn get(&self, name: &str) -> eyre::Result<Option<Vec<u8>>> {
+        match self {
+            Self::Dir(dir) => {
+                let path = dir.join(name);
+                if !path.exists() {
+                    return Ok(None)
+                }
+                Ok(Some(fs::read(path)?))
+            }
+            Self::Http { client, url, headers } => {
+                let response =
+                    client.get(format!("{url}/{name}")).headers(headers.clone()).send().await?;
+                if response.status() == reqwest::StatusCode::NOT_FOUND {
+                    return Ok(None)
+                }
+                Ok(Some(response.error_for_status()?.bytes().await?.to_vec()))
+            }
+        }
+    }
+
+    /// Stores the content as the file.
+    async fn put(&self, name: &str, content: Vec<u8>) -> eyre::Result<()> {
+        match self {
+            Self::Dir(dir) => {
+                let path = dir.join(name);
+                fs::write(&path, content)?;
+                std::fs::File::open(&path)?.sync_all()?;
+            }
+            Self::Http { client, url, headers } => {
+                client
+                    .put(format!("{url}/{name}"))
+                    .headers(headers.clone())
+                    .body(content)
+                    .send()
+                    .await?
+                    .error_for_status()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Uploads the local file as the file.
+    async fn put_file(&self, name: &str, path: &Path) -> eyre::Result<()> {
+        match self {
+            Self::Dir(dir) => {
+                let target = dir.join(name);
+                std::fs::copy(path, &target).wrap_err_with(|| {
+                    format!("failed to copy {} to {}", path.display(), target.display())
+                })?;
+                std::fs::File::open(&target)?.sync_all()?;
+            }
+            Self::Http { client, url, headers } => {
+                let file = tokio::fs::File::open(path).await?;
+                let size = file.metadata().await?.len();
+                client
+                    .put(format!("{url}/{name}"))
+                    .headers(headers.clone())
+                    .header(reqwest::header::CONTENT_LENGTH, size)
+                    .body(reqwest::Body::wrap_stream(tokio_util::io::ReaderStream::new(file)))
+                    .send()
+                    .await?
+                    .error_for_status()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the size and the SHA-256 hash of the file.
+fn hash_file(path: &Path) -> eyre::Result<(u64, B256)> {
+    let mut file =
+        std::fs::File::open(path).wrap_err_with(|| format!("failed to open {}", path.display()))?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0; 1 << 20];
+    let mut size = 0;
+    loop {
+        let read = file.read(&mut buf)?;
+        if read == 0 {
+            break
+        }
+        hasher.update(&buf[..read]);
+        size += read as u64;
+    }
+    Ok((size, B256::from_slice(&hasher.finalize())))
+}
+
+fn file_name(path: &Path) -> eyre::Result<String> {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .ok_or_else(|| eyre::eyre!("invalid static file path {}", path.display()))
+}
+
+fn parse_header(header: &str) -> Result<(String, String), &'static str> {
+    let (name, value) = header.split_once(':').ok_or("expected `<NAME>: <VALUE>`")?;
+    Ok((name.trim().to_string(), value.trim().to_string()))
+}
+
+fn parse_segment(segment: &str) -> Result<StaticFileSegment, &'static str> {
+    match segment.parse() {
+        Ok(StaticFileSegment::Headers) => Err("headers can't be archived"),
+        Ok(segment) => Ok(segment),
+        Err(_) => Err("expected `receipts` or `transactions`"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn archives_whole_static_files_below_highest() {
+        assert_eq!(
+            next_static_file(0, 1_200_000, 1_000_000),
+            Some(SegmentRangeInclusive::new(0, 499_999))
+        );
+        // the static file doesn't end before the block
+        assert_eq!(next_static_file(0, 1_200_000, 499_999), None);
+        assert_eq!(
+            next_static_file(500_000, 1_200_000, 1_000_000),
+            Some(SegmentRangeInclusive::new(500_000, 999_999))
+        );
+        // the highest static file is kept
+        assert_eq!(next_static_file(1_000_000, 1_200_000, 2_000_000), None);
+        assert_eq!(next_static_file(0, 499_999, 1_000_000), None);
+    }
+
+    #[test]
+    fn records_archived_static_files() {
+        let archived = |segment, start| ArchivedStaticFile {
+            segment,
+            block_range: SegmentRangeInclusive::new(start, start + 499_999),
+            tx_range: None,
+            files: Vec::new(),
+        };
+        let mut manifest = ColdStorageManifest::default();
+        manifest.record(archived(StaticFileSegment::Receipts, 500_000));
+        manifest.record(archived(StaticFileSegment::Transactions, 0));
+        manifest.record(archived(StaticFileSegment::Receipts, 0));
+        manifest.record(archived(StaticFileSegment::Receipts, 500_000));
+
+        let recorded = manifest
+            .static_files
+            .iter()
+            .map(|archived| (archived.segment, archived.block_range.start()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            recorded,
+            vec![
+                (StaticFileSegment::Transactions, 0),
+                (StaticFileSegment::Receipts, 0),
+                (StaticFileSegment::Receipts, 500_000),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_storage_header() {
+        assert_eq!(
+            parse_header("Authorization: Bearer token").unwrap(),
+            ("Authorization".to_string(), "Bearer token".to_string())
+        );
+        assert!(parse_header("Authorization").is_err());
+        assert!(parse_segment("headers").is_err());
+        assert_eq!(parse_segment("receipts").unwrap(), StaticFileSegment::Receipts);
+    }
+}
@@ -13,10 +13,14 @@ use reth_node_core::{
         utils::{chain_help, chain_value_parser, SUPPORTED_CHAINS},
         DatabaseArgs, DatadirArgs,
     },
+    crash_report,
     dirs::{ChainPath, DataDirPath},
+    layout::{check_layout_version, init_layout_version},
 };
 use reth_primitives::B256;
-use reth_provider::{providers::StaticFileProvider, ProviderFactory, StaticFileProviderFactory};
+use reth_provider::{
+    providers::StaticFileProvider, BlockHashReader, ProviderFactory, StaticFileProviderFactory,
+};
 use reth_stages::{sets::DefaultStages, Pipeline, PipelineTarget};
 use reth_static_file::StaticFileProducer;
 use std::{path::PathBuf, sync::Arc};
@@ -60,8 +64,11 @@ impl EnvironmentArgs {
         let sf_path = data_dir.static_files();
 
         if access.is_read_write() {
+            init_layout_version(&data_dir)?;
             reth_fs_util::create_dir_all(&db_path)?;
             reth_fs_util::create_dir_all(&sf_path)?;
+        } else {
+            check_layout_version(&data_dir)?;
         }
 
         let config_path = self.config.clone().unwrap_or_else(|| data_dir.config());
@@ -84,6 +91,14 @@ impl EnvironmentArgs {
             ),
         };
 
+        if access.is_read_write() {
+            crash_report::set_datadir(&data_dir, self.chain.clone());
+            crash_report::set_database(db.clone());
+        }
+
+        let sfp = sfp.with_read_only_dirs(data_dir.read_only_static_files().to_vec())?;
+        // the genesis header is the first header of the static files
+        data_dir.ensure_genesis(self.chain.genesis_hash(), sfp.block_hash(0).ok().flatten())?;
         let provider_factory = self.create_provider_factory(&config, db, sfp)?;
         if access.is_read_write() {
             debug!(target: "reth::cli", chain=%self.chain.chain, genesis=?self.chain.genesis_hash(), "Initializing genesis");
@@ -2,13 +2,17 @@
 
 use std::path::PathBuf;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use eyre::{bail, WrapErr};
-use reth_config::Config;
+use reth_config::{default_config_template, Config, ConfigDiff};
 
 /// `reth config` command
 #[derive(Debug, Parser)]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct Command {
+    #[command(subcommand)]
+    command: Option<Subcommands>,
+
     /// The path to the configuration file to use.
     #[arg(long, value_name = "FILE", verbatim_doc_comment)]
     config: Option<PathBuf>,
@@ -21,6 +25,12 @@ pub struct Command {
 impl Command {
     /// Execute `config` command
     pub async fn execute(&self) -> eyre::Result<()> {
+        match &self.command {
+            Some(Subcommands::Init(command)) => return command.execute(),
+            Some(Subcommands::Diff(command)) => return command.execute(),
+            None => {}
+        }
+
         let config = if self.default {
             Config::default()
         } else {
@@ -36,3 +46,92 @@ impl Command {
         Ok(())
     }
 }
+
+/// `reth config` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands {
+    /// Write the default config with a description of every setting
+    Init(InitCommand),
+    /// Show the settings of a config file that differ from the defaults
+    Diff(DiffCommand),
+}
+
+/// `reth config init` command
+#[derive(Debug, Parser)]
+pub struct InitCommand {
+    /// The file to write the config to. Written to stdout if not set.
+    #[arg(long, short, value_name = "FILE")]
+    output: Option<PathBuf>,
+
+    /// Overwrite the file if it already exists.
+    #[arg(long, requires = "output")]
+    force: bool,
+}
+
+impl InitCommand {
+    /// Execute `config init` command
+    pub fn execute(&self) -> eyre::Result<()> {
+        let template = default_config_template();
+        let Some(path) = &self.output else {
+            print!("{template}");
+            return Ok(())
+        };
+
+        if path.exists() && !self.force {
+            bail!("Config file already exists: {}, use --force to overwrite it", path.display());
+        }
+        reth_fs_util::write(path, template)?;
+        println!("Wrote default config to {}", path.display());
+        Ok(())
+    }
+}
+
+/// `reth config diff` command
+#[derive(Debug, Parser)]
+pub struct DiffCommand {
+    /// The config file to compare with the defaults.
+    #[arg(value_name = "FILE")]
+    path: PathBuf,
+}
+
+impl DiffCommand {
+    /// Execute `config diff` command
+    pub fn execute(&self) -> eyre::Result<()> {
+        let contents = reth_fs_util::read_to_string(&self.path)?;
+        let diff = ConfigDiff::new(&contents)
+            .wrap_err_with(|| format!("Could not load config file: {}", self.path.display()))?;
+
+        if diff.overrides.is_empty() {
+            println!("All settings are set to their default");
+        }
+        for setting in &diff.overrides {
+            println!("{setting}");
+        }
+        for warning in &diff.warnings {
+            println!("warning: {warning}");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_config_command() {
+        let command = Command::parse_from(["reth", "--default"]);
+        assert!(command.default && command.command.is_none());
+
+        let command = Command::parse_from(["reth", "init", "--output", "reth.toml", "--force"]);
+        assert!(matches!(
+            command.command,
+            Some(Subcommands::Init(InitCommand { force: true, .. }))
+        ));
+
+        let command = Command::parse_from(["reth", "diff", "reth.toml"]);
+        assert!(matches!(command.command, Some(Subcommands::Diff(_))));
+
+        assert!(Command::try_parse_from(["reth", "init", "--force"]).is_err());
+    }
+}
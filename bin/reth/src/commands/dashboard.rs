@@ -0,0 +1,517 @@
+//! Live dashboard of the sync, shown instead of the log output with `--tui`.
+
+use crate::version::SHORT_VERSION;
+use clap::Args;
+use crossterm::{
+    event::{self, Event, KeyCode, KeyModifiers},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use human_bytes::human_bytes;
+use metrics_exporter_prometheus::PrometheusHandle;
+use ratatui::{
+    backend::{Backend, CrosstermBackend},
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Gauge, Paragraph},
+    Frame, Terminal,
+};
+use reth_db::tables;
+use reth_db_api::{database::Database, transaction::DbTx};
+use reth_node_core::dirs::{ChainPath, DataDirPath};
+use reth_primitives::constants::MGAS_TO_GAS;
+use reth_stages::{StageCheckpoint, StageId};
+use reth_tracing::LogBuffer;
+use std::{
+    collections::VecDeque,
+    io,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+use tokio::sync::watch;
+use tracing::debug;
+
+/// Available keybindings for the [`Dashboard`]
+static CMDS: [(&str, &str); 1] = [("q", "Stop")];
+
+/// How long to wait for input before the dashboard is drawn again.
+const TICK_RATE: Duration = Duration::from_millis(250);
+
+/// How often the stage checkpoints, the storage size and the metrics are read.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The window over which the growth of the storage is measured.
+///
+/// The database file grows in steps, so the window is long enough to smooth them out.
+const STORAGE_GROWTH_WINDOW: Duration = Duration::from_secs(10 * 60);
+
+/// The window over which the execution throughput is measured.
+const GAS_WINDOW: Duration = Duration::from_secs(60);
+
+/// Name of the metric with the number of connected peers.
+const CONNECTED_PEERS_METRIC: &str = "reth_network_connected_peers";
+
+/// Name of the metric with the gas executed by the execution stage, in millions.
+const EXECUTED_MGAS_METRIC: &str = "reth_sync_execution_mgas_processed_total";
+
+/// Live dashboard arguments
+#[derive(Debug, Clone, Default, Args)]
+#[command(next_help_heading = "Dashboard")]
+pub struct TuiArgs {
+    /// Show a live dashboard instead of the log output.
+    ///
+    /// The dashboard shows the progress of the stages, the number of connected peers, the growth
+    /// of the datadir, the execution throughput and the most recent log lines. Press `q` or
+    /// `Ctrl+C` to stop.
+    #[arg(long)]
+    pub tui: bool,
+
+    /// The buffer the log output is written into while the dashboard is shown.
+    ///
+    /// Set up with the tracing of the cli, see [`TuiArgs::init_log_buffer`].
+    #[arg(skip)]
+    pub log_buffer: Option<LogBuffer>,
+}
+
+impl TuiArgs {
+    /// Creates the buffer for the log output, if the dashboard is enabled.
+    pub(crate) fn init_log_buffer(&mut self) -> Option<LogBuffer> {
+        if !self.tui {
+            return None
+        }
+        Some(self.log_buffer.get_or_insert_with(LogBuffer::default).clone())
+    }
+
+    /// Shows the dashboard on a separate thread, if it is enabled.
+    ///
+    /// The number of connected peers and the execution throughput are read from the given metrics,
+    /// if any. Without metrics, the throughput is derived from the checkpoint of the execution
+    /// stage.
+    pub(crate) fn spawn<DB>(
+        &self,
+        title: String,
+        db: DB,
+        data_dir: &ChainPath<DataDirPath>,
+        metrics: Option<PrometheusHandle>,
+    ) -> eyre::Result<Option<Dashboard>>
+    where
+        DB: Database + 'static,
+    {
+        if !self.tui {
+            return Ok(None)
+        }
+
+        let state = DashboardState {
+            title,
+            db,
+            db_path: data_dir.db(),
+            static_files_path: data_dir.static_files(),
+            metrics,
+            logs: self.log_buffer.clone().unwrap_or_default(),
+            refreshed_at: None,
+            checkpoints: Vec::new(),
+            peers: None,
+            storage_size: 0,
+            storage_growth: Rate::new(STORAGE_GROWTH_WINDOW),
+            gas: Rate::new(GAS_WINDOW),
+        };
+
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let (closed_tx, closed) = watch::channel(false);
+        let thread = std::thread::Builder::new().name("dashboard".to_string()).spawn({
+            let shutdown = shutdown.clone();
+            move || run(state, &shutdown, &closed_tx)
+        })?;
+
+        Ok(Some(Dashboard { shutdown, closed, thread: Some(thread) }))
+    }
+}
+
+/// A dashboard shown on a separate thread.
+///
+/// The terminal is restored and the log output is written to stdout again once the dashboard is
+/// closed by the user or dropped.
+#[derive(Debug)]
+pub(crate) struct Dashboard {
+    shutdown: Arc<AtomicBool>,
+    closed: watch::Receiver<bool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Dashboard {
+    /// Resolves once the user closed the dashboard.
+    pub(crate) async fn closed(&mut self) {
+        if self.closed.wait_for(|closed| *closed).await.is_err() {
+            // the dashboard failed, the log output is written to stdout instead
+            std::future::pending::<()>().await
+        }
+    }
+}
+
+impl Drop for Dashboard {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Resolves once the user closed the dashboard, never if no dashboard is shown.
+pub(crate) async fn closed(dashboard: Option<&mut Dashboard>) {
+    match dashboard {
+        Some(dashboard) => dashboard.closed().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// The data shown by the dashboard.
+struct DashboardState<DB> {
+    title: String,
+    db: DB,
+    db_path: PathBuf,
+    static_files_path: PathBuf,
+    metrics: Option<PrometheusHandle>,
+    logs: LogBuffer,
+    refreshed_at: Option<Instant>,
+    checkpoints: Vec<(StageId, StageCheckpoint)>,
+    peers: Option<u64>,
+    storage_size: u64,
+    storage_growth: Rate,
+    gas: Rate,
+}
+
+impl<DB: Database> DashboardState<DB> {
+    /// Reads the stage checkpoints, the storage size and the metrics, at most once per
+    /// [`REFRESH_INTERVAL`].
+    fn refresh(&mut self) {
+        let now = Instant::now();
+        if self.refreshed_at.is_some_and(|at| now.duration_since(at) < REFRESH_INTERVAL) {
+            return
+        }
+        self.refreshed_at = Some(now);
+
+        match self.read_checkpoints() {
+            Ok(checkpoints) => self.checkpoints = checkpoints,
+            Err(err) => debug!(target: "reth::cli", %err, "Failed to read stage checkpoints"),
+        }
+
+        self.storage_size = [&self.db_path, &self.static_files_path]
+            .into_iter()
+            .map(|path| reth_fs_util::dir_size(path).unwrap_or_default())
+            .sum();
+        self.storage_growth.update(now, self.storage_size);
+
+        let metrics = self.metrics.as_ref().map(PrometheusHandle::render);
+        self.peers = metrics
+            .as_deref()
+            .and_then(|metrics| metric_value(metrics, CONNECTED_PEERS_METRIC))
+            .map(|peers| peers as u64);
+        let gas = match metrics {
+            Some(metrics) => {
+                metric_value(&metrics, EXECUTED_MGAS_METRIC).map(|mgas| mgas as u64 * MGAS_TO_GAS)
+            }
+            None => self
+                .checkpoint(StageId::Execution)
+                .and_then(|checkpoint| checkpoint.entities())
+                .map(|entities| entities.processed),
+        };
+        if let Some(gas) = gas {
+            self.gas.update(now, gas);
+        }
+    }
+
+    /// Reads the checkpoints of all stages that have one.
+    fn read_checkpoints(&self) -> eyre::Result<Vec<(StageId, StageCheckpoint)>> {
+        let tx = self.db.tx()?;
+        let mut checkpoints = Vec::new();
+        for stage in StageId::ALL {
+            if let Some(checkpoint) = tx.get::<tables::StageCheckpoints>(stage.to_string())? {
+                checkpoints.push((stage, checkpoint));
+            }
+        }
+        Ok(checkpoints)
+    }
+
+    /// Returns the checkpoint of the stage.
+    fn checkpoint(&self, stage: StageId) -> Option<&StageCheckpoint> {
+        self.checkpoints.iter().find(|(id, _)| *id == stage).map(|(_, checkpoint)| checkpoint)
+    }
+}
+
+/// The rate of change of a growing value, measured over a sliding window of samples.
+#[derive(Debug)]
+struct Rate {
+    window: Duration,
+    samples: VecDeque<(Instant, u64)>,
+}
+
+impl Rate {
+    /// Creates a new rate measured over the given window.
+    const fn new(window: Duration) -> Self {
+        Self { window, samples: VecDeque::new() }
+    }
+
+    /// Adds a sample of the value.
+    ///
+    /// The samples are discarded if the value shrinks, e.g. after an unwind.
+    fn update(&mut self, now: Instant, value: u64) {
+        if self.samples.back().is_some_and(|(_, last)| value < *last) {
+            self.samples.clear();
+        }
+        self.samples.push_back((now, value));
+        while self.samples.front().is_some_and(|(at, _)| now.duration_since(*at) > self.window) {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Returns the change of the value per second, if there are enough samples.
+    fn per_second(&self) -> Option<f64> {
+        let (first_at, first) = self.samples.front()?;
+        let (last_at, last) = self.samples.back()?;
+        let elapsed = last_at.duration_since(*first_at).as_secs_f64();
+        (elapsed > 0.0).then(|| (last - first) as f64 / elapsed)
+    }
+}
+
+/// Returns the value of an unlabeled metric in the Prometheus text format.
+fn metric_value(metrics: &str, name: &str) -> Option<f64> {
+    metrics.lines().find_map(|line| line.strip_prefix(name)?.strip_prefix(' ')?.trim().parse().ok())
+}
+
+/// Returns the progress of a stage as a ratio.
+///
+/// The progress is taken from the entities of the checkpoint if the stage reports them, otherwise
+/// the block number of the checkpoint is compared with the given tip.
+fn stage_progress(checkpoint: Option<&StageCheckpoint>, tip: u64) -> f64 {
+    let Some(checkpoint) = checkpoint else { return 0.0 };
+    let ratio = match checkpoint.entities() {
+        Some(entities) if entities.total > 0 => entities.processed as f64 / entities.total as f64,
+        _ if tip > 0 => checkpoint.block_number as f64 / tip as f64,
+        _ => 0.0,
+    };
+    ratio.clamp(0.0, 1.0)
+}
+
+/// Shows the dashboard until it is closed by the user or shut down.
+///
+/// Afterwards, the most recent log lines are printed and the log output is written to stdout
+/// again.
+fn run<DB: Database>(
+    mut state: DashboardState<DB>,
+    shutdown: &AtomicBool,
+    closed: &watch::Sender<bool>,
+) {
+    let res = show(&mut state, shutdown);
+
+    let (height, _) = crossterm::terminal::size().unwrap_or((24, 0));
+    for line in state.logs.last_lines(height as usize / 2) {
+        println!("{line}");
+    }
+    state.logs.print_to_stdout();
+
+    match res {
+        Ok(true) => {
+            let _ = closed.send(true);
+        }
+        Ok(false) => {}
+        Err(err) => eprintln!("Failed to show the dashboard: {err}"),
+    }
+}
+
+/// Shows the dashboard in the terminal.
+///
+/// Returns `true` if the dashboard was closed by the user.
+fn show<DB: Database>(state: &mut DashboardState<DB>, shutdown: &AtomicBool) -> io::Result<bool> {
+    // Setup backend
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    // Run event loop
+    let res = event_loop(&mut terminal, state, shutdown);
+
+    // Restore terminal
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+
+    res
+}
+
+/// Run the event loop
+fn event_loop<B: Backend, DB: Database>(
+    terminal: &mut Terminal<B>,
+    state: &mut DashboardState<DB>,
+    shutdown: &AtomicBool,
+) -> io::Result<bool> {
+    while !shutdown.load(Ordering::Relaxed) {
+        state.refresh();
+
+        // Render
+        terminal.draw(|f| ui(f, state))?;
+
+        // Poll events, the terminal is in raw mode so `Ctrl+C` arrives as a key
+        if event::poll(TICK_RATE)? {
+            if let Event::Key(key) = event::read()? {
+                match key.code {
+                    KeyCode::Char('q') => return Ok(true),
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        return Ok(true)
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    Ok(false)
+}
+
+/// Render the UI
+fn ui<DB>(f: &mut Frame<'_>, state: &DashboardState<DB>) {
+    let outer_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(StageId::ALL.len() as u16 + 2),
+            Constraint::Length(3),
+            Constraint::Min(3),
+            Constraint::Length(3),
+        ])
+        .split(f.size());
+
+    // Stages
+    {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!("reth {SHORT_VERSION} | {} | Stages", state.title));
+        let area = block.inner(outer_chunks[0]);
+        f.render_widget(block, outer_chunks[0]);
+
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(StageId::ALL.map(|_| Constraint::Length(1)))
+            .split(area);
+
+        // stages after the headers stage don't get ahead of it
+        let tip =
+            state.checkpoint(StageId::Headers).map_or(0, |checkpoint| checkpoint.block_number);
+        for (stage, row) in StageId::ALL.into_iter().zip(rows.iter()) {
+            let checkpoint = state.checkpoint(stage);
+            let progress = stage_progress(checkpoint, tip);
+            let block_number = checkpoint
+                .map_or_else(|| "-".to_string(), |checkpoint| checkpoint.block_number.to_string());
+            // `StageId` ignores the width when formatted
+            let label = format!(
+                "{:<20} block {block_number:>10} {:>7.2}%",
+                stage.to_string(),
+                progress * 100.0
+            );
+            let color = if progress < 1.0 { Color::Cyan } else { Color::Green };
+            let gauge = Gauge::default()
+                .gauge_style(Style::default().fg(color))
+                .ratio(progress)
+                .label(label);
+            f.render_widget(gauge, *row);
+        }
+    }
+
+    // Stats
+    {
+        let peers = state.peers.map_or_else(|| "-".to_string(), |peers| peers.to_string());
+        let head = state
+            .checkpoint(StageId::Finish)
+            .map_or_else(|| "-".to_string(), |checkpoint| checkpoint.block_number.to_string());
+        let growth = state.storage_growth.per_second().map_or_else(
+            || "-".to_string(),
+            |per_second| format!("{}/h", human_bytes(per_second * 3600.0)),
+        );
+        let gas = state.gas.per_second().map_or_else(
+            || "-".to_string(),
+            |per_second| format!("{:.2} Mgas/s", per_second / MGAS_TO_GAS as f64),
+        );
+        let stats = Paragraph::new(format!(
+            "Peers: {peers} | Head: {head} | Datadir: {} ({growth}) | Execution: {gas}",
+            human_bytes(state.storage_size as f64)
+        ))
+        .block(Block::default().borders(Borders::ALL).title("Status"));
+        f.render_widget(stats, outer_chunks[1]);
+    }
+
+    // Logs
+    {
+        let height = outer_chunks[2].height.saturating_sub(2) as usize;
+        let lines = state.logs.last_lines(height).into_iter().map(Line::from).collect::<Vec<_>>();
+        let logs =
+            Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Logs"));
+        f.render_widget(logs, outer_chunks[2]);
+    }
+
+    // Footer
+    let footer = Paragraph::new(
+        CMDS.iter().map(|(k, v)| format!("[{k}] {v}")).collect::<Vec<_>>().join(" | "),
+    )
+    .block(Block::default().borders(Borders::ALL))
+    .alignment(Alignment::Center);
+    f.render_widget(footer, outer_chunks[3]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_stages::{EntitiesCheckpoint, ExecutionCheckpoint};
+
+    #[test]
+    fn rate() {
+        let start = Instant::now();
+        let mut rate = Rate::new(Duration::from_secs(60));
+        rate.update(start, 100);
+        assert_eq!(rate.per_second(), None);
+
+        rate.update(start + Duration::from_secs(10), 200);
+        assert_eq!(rate.per_second(), Some(10.0));
+
+        // samples outside of the window are dropped
+        rate.update(start + Duration::from_secs(80), 1000);
+        assert_eq!(rate.per_second(), Some(800.0 / 70.0));
+
+        // a shrinking value starts over
+        rate.update(start + Duration::from_secs(90), 500);
+        assert_eq!(rate.per_second(), None);
+    }
+
+    #[test]
+    fn parse_metric_value() {
+        let metrics = "\
+# TYPE reth_network_connected_peers gauge
+reth_network_connected_peers 12
+reth_network_connected_peers_max 100
+reth_sync_execution_mgas_processed_total 1234
+";
+        assert_eq!(metric_value(metrics, CONNECTED_PEERS_METRIC), Some(12.0));
+        assert_eq!(metric_value(metrics, EXECUTED_MGAS_METRIC), Some(1234.0));
+        assert_eq!(metric_value(metrics, "reth_network_tracked_peers"), None);
+    }
+
+    #[test]
+    fn progress_of_stages() {
+        assert_eq!(stage_progress(None, 100), 0.0);
+        assert_eq!(stage_progress(Some(&StageCheckpoint::new(50)), 100), 0.5);
+        assert_eq!(stage_progress(Some(&StageCheckpoint::new(50)), 0), 0.0);
+
+        let execution =
+            StageCheckpoint::new(10).with_execution_stage_checkpoint(ExecutionCheckpoint {
+                block_range: Default::default(),
+                progress: EntitiesCheckpoint { processed: 25, total: 100 },
+            });
+        assert_eq!(stage_progress(Some(&execution), 100), 0.25);
+    }
+}
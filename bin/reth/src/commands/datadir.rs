@@ -0,0 +1,136 @@
+//! `reth datadir` command, manages the layout of a datadir.
+
+use crate::commands::common::EnvironmentArgs;
+use clap::{Parser, Subcommand};
+use reth_db::{
+    lockfile::{StorageLock, StorageLockStatus},
+    open_db, tables,
+    version::{create_db_version_file, get_db_version, DB_VERSION},
+};
+use reth_db_api::{database::Database, transaction::DbTx};
+use reth_node_core::layout::{pending_migrations, upgrade_layout, LAYOUT_VERSION};
+use reth_provider::bundle_state::rebuild_bytecode_references;
+use std::path::Path;
+use tracing::info;
+
+/// The database version before contract bytecodes were reference counted.
+///
+/// Upgrading it backfills the reference counts, which must exist before pruning deletes any
+/// bytecode.
+const DB_VERSION_WITHOUT_BYTECODE_REFERENCES: u64 = 2;
+
+/// The number of accounts or account changes to count the bytecode references of per commit.
+const BYTECODE_REFERENCES_BATCH_SIZE: usize = 1_000_000;
+
+/// `reth datadir` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+impl Command {
+    /// Execute `datadir` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::Upgrade(command) => command.execute(),
+        }
+    }
+}
+
+/// `reth datadir` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands {
+    /// Upgrade the datadir to the latest layout and database versions
+    Upgrade(UpgradeCommand),
+}
+
+/// `reth datadir upgrade` command
+#[derive(Debug, Parser)]
+pub struct UpgradeCommand {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    /// Print the migrations without applying them.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+impl UpgradeCommand {
+    /// Execute `datadir upgrade` command
+    pub fn execute(self) -> eyre::Result<()> {
+        let data_dir = self.env.datadir.clone().resolve_datadir(self.env.chain.chain);
+        let db_path = data_dir.db();
+        let migrations = pending_migrations(&data_dir)?;
+        let upgrade_db =
+            matches!(get_db_version(&db_path), Ok(DB_VERSION_WITHOUT_BYTECODE_REFERENCES));
+
+        if migrations.is_empty() && !upgrade_db {
+            println!(
+                "The datadir {data_dir} has the latest layout (v{LAYOUT_VERSION}) and database \
+                 (v{DB_VERSION})"
+            );
+            if !self.dry_run {
+                upgrade_layout(&data_dir)?;
+            }
+            return Ok(())
+        }
+
+        println!("Upgrading the datadir {data_dir}:");
+        for migration in &migrations {
+            println!(
+                "  layout v{} -> v{}: {}",
+                migration.version,
+                migration.version + 1,
+                migration.description
+            );
+        }
+        if upgrade_db {
+            println!(
+                "  database v{DB_VERSION_WITHOUT_BYTECODE_REFERENCES} -> v{DB_VERSION}: count the \
+                 references to contract bytecodes from the accounts and account changesets, \
+                 continuing from the last commit if a previous upgrade was interrupted"
+            );
+        }
+        if self.dry_run {
+            return Ok(())
+        }
+
+        // the files must not be moved while a node is using them
+        if let StorageLockStatus::Taken(pid) = StorageLock::status(&db_path)? {
+            eyre::bail!(
+                "the datadir is in use by the running process {pid}, stop it before upgrading"
+            )
+        }
+
+        upgrade_layout(&data_dir)?;
+        if upgrade_db {
+            self.upgrade_db(&db_path)?;
+        }
+        println!(
+            "The datadir was upgraded to the latest layout (v{LAYOUT_VERSION}) and database \
+             (v{DB_VERSION})"
+        );
+        Ok(())
+    }
+
+    /// Backfills the bytecode reference counts, and records the latest database version once
+    /// they're committed.
+    ///
+    /// The counts are committed in batches along with a checkpoint, so an interrupted upgrade
+    /// continues where it left off. Until the version is recorded the node refuses to open the
+    /// database, so the partial counts are never used.
+    fn upgrade_db(&self, db_path: &Path) -> eyre::Result<()> {
+        let db = open_db(db_path, self.env.db.database_args())?;
+        db.create_tables()?;
+        while let Some(checkpoint) =
+            db.update(|tx| rebuild_bytecode_references(tx, BYTECODE_REFERENCES_BATCH_SIZE))??
+        {
+            info!(target: "reth::cli", ?checkpoint, "Committed bytecode references");
+        }
+        let references = db.view(|tx| tx.entries::<tables::BytecodeReferences>())??;
+        create_db_version_file(db_path)?;
+        println!("  counted the references to {references} contract bytecodes");
+        Ok(())
+    }
+}
@@ -0,0 +1,237 @@
+use clap::Parser;
+use reth_db::static_file::{
+    iter_static_files, load_receipt_topics, receipt_topics_path, ReceiptMask, StaticFileCursor,
+};
+use reth_db_api::database::Database;
+use reth_fs_util as fs;
+use reth_nippy_jar::{ConsistencyFailStrategy, NippyJar, NippyJarWriter};
+use reth_primitives::{
+    receipt_compaction::{TopicDictionary, DEFAULT_TOPIC_DICTIONARY_SIZE},
+    static_file::SegmentHeader,
+    Receipt, StaticFileSegment,
+};
+use reth_provider::{ProviderFactory, StaticFileProviderFactory};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::{info, warn};
+
+/// Extension of the original files kept around while a static file is being swapped.
+const BACKUP_EXTENSION: &str = "uncompacted";
+
+/// Suffix of the compacted static file before it replaces the original one.
+const COMPACTED_SUFFIX: &str = "compacted";
+
+/// The arguments for the `reth db compact-receipts` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Maximum number of topics kept in the dictionary of each receipts static file.
+    #[arg(long, default_value_t = DEFAULT_TOPIC_DICTIONARY_SIZE)]
+    max_topics: usize,
+
+    /// Only report the expected size reduction, without rewriting any static file.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+impl Command {
+    /// Execute `db compact-receipts` command
+    pub fn execute<DB: Database>(self, provider_factory: ProviderFactory<DB>) -> eyre::Result<()> {
+        warn!("This command should be run without the node running!");
+
+        let static_file_provider = provider_factory.static_file_provider();
+        let directory = static_file_provider.directory();
+        if !self.dry_run {
+            restore_interrupted(directory)?;
+        }
+
+        let static_files = iter_static_files(directory)?;
+        let Some(ranges) = static_files.get(&StaticFileSegment::Receipts) else {
+            info!(target: "reth::cli", "No receipts static files found");
+            return Ok(())
+        };
+
+        let (mut total_before, mut total_after) = (0u64, 0u64);
+        for (block_range, _) in ranges {
+            let path = directory.join(StaticFileSegment::Receipts.filename(block_range));
+
+            if load_receipt_topics(&path)?.is_some() {
+                info!(target: "reth::cli", ?block_range, "Receipts static file already compacted");
+                continue
+            }
+
+            let mut jar = NippyJar::<SegmentHeader>::load(&path)?;
+            if jar.index_path().exists() || jar.compressor_mut().is_some() {
+                warn!(
+                    target: "reth::cli",
+                    ?block_range,
+                    "Skipping receipts static file with filters or compression"
+                );
+                continue
+            }
+
+            let receipts = read_receipts(&jar)?;
+            let topics = TopicDictionary::from_receipts(&receipts, self.max_topics);
+
+            let mut encoded = Vec::with_capacity(receipts.len());
+            for receipt in &receipts {
+                let mut buf = Vec::with_capacity(128);
+                topics.encode_receipt(receipt, &mut buf);
+                encoded.push(buf);
+            }
+
+            let before = fs::metadata(jar.data_path())?.len();
+            let after = encoded.iter().map(|buf| buf.len() as u64).sum::<u64>() +
+                topics.to_bytes().len() as u64;
+            total_before += before;
+            total_after += after;
+            info!(
+                target: "reth::cli",
+                ?block_range,
+                receipts = receipts.len(),
+                topics = topics.len(),
+                before,
+                after,
+                "Compacted receipts static file"
+            );
+
+            if !self.dry_run {
+                swap_compacted(jar, &encoded, &topics)?;
+            }
+        }
+
+        let reduction = if total_before == 0 {
+            0.0
+        } else {
+            100.0 * (1.0 - total_after as f64 / total_before as f64)
+        };
+        println!(
+            "Receipts: {total_before} bytes -> {total_after} bytes ({reduction:.1}% reduction){}",
+            if self.dry_run { " [dry run]" } else { "" }
+        );
+
+        Ok(())
+    }
+}
+
+/// Reads every receipt of an uncompacted receipts static file.
+fn read_receipts(jar: &NippyJar<SegmentHeader>) -> eyre::Result<Vec<Receipt>> {
+    let Some(tx_range) = jar.user_header().tx_range().copied() else { return Ok(Vec::new()) };
+
+    let mut cursor = StaticFileCursor::new(jar, Arc::new(jar.open_data_reader()?))?;
+    let mut receipts = Vec::with_capacity(jar.rows());
+    for tx in tx_range.start()..=tx_range.end() {
+        let receipt = cursor
+            .get_one::<ReceiptMask<Receipt>>(tx.into())?
+            .ok_or_else(|| eyre::eyre!("missing receipt {tx} in {}", jar.data_path().display()))?;
+        receipts.push(receipt);
+    }
+
+    Ok(receipts)
+}
+
+/// Writes the compacted static file next to the original one and swaps them.
+///
+/// The original files are kept with the [`BACKUP_EXTENSION`] until the topic dictionary is
+/// written, so an interrupted swap is rolled back by [`restore_interrupted`] on the next run.
+fn swap_compacted(
+    jar: NippyJar<SegmentHeader>,
+    encoded: &[Vec<u8>],
+    topics: &TopicDictionary,
+) -> eyre::Result<()> {
+    let path = jar.data_path().to_path_buf();
+    let compacted_path = compacted_path(&path);
+    remove_existing(&jar_files(&compacted_path))?;
+
+    let compacted = NippyJar::new(jar.columns(), &compacted_path, jar.user_header().clone());
+    let mut writer = NippyJarWriter::new(compacted, ConsistencyFailStrategy::Heal)?;
+    for receipt in encoded {
+        writer.append_column(Some(Ok(receipt)))?;
+    }
+    writer.commit()?;
+
+    for (original, new) in jar_files(&path).into_iter().zip(jar_files(&compacted_path)) {
+        fs::rename(&original, backup_path(&original))?;
+        fs::rename(new, original)?;
+    }
+
+    fs::write(receipt_topics_path(&path), topics.to_bytes())?;
+
+    for original in jar_files(&path) {
+        fs::remove_file(backup_path(&original))?;
+    }
+
+    Ok(())
+}
+
+/// Cleans up after interrupted [`swap_compacted`] calls in the static files `directory`.
+///
+/// If the topic dictionary of a static file was written its swap is complete and only the
+/// backups are removed, otherwise the original files are restored.
+fn restore_interrupted(directory: &Path) -> eyre::Result<()> {
+    let mut interrupted = Vec::new();
+    for entry in fs::read_dir(directory)? {
+        let backup = entry?.path();
+        if backup.extension().map_or(false, |extension| extension == BACKUP_EXTENSION) {
+            // Strip the backup extension, and the offsets or configuration extension if any.
+            let path = backup.with_extension("").with_extension("");
+            if !interrupted.contains(&path) {
+                interrupted.push(path);
+            }
+        }
+    }
+
+    for path in interrupted {
+        let completed = receipt_topics_path(&path).exists();
+        warn!(target: "reth::cli", ?path, completed, "Cleaning up interrupted receipts compaction");
+
+        for original in jar_files(&path) {
+            let backup = backup_path(&original);
+            if !backup.exists() {
+                continue
+            }
+
+            if completed {
+                fs::remove_file(backup)?;
+            } else {
+                fs::rename(backup, original)?;
+            }
+        }
+
+        remove_existing(&jar_files(&compacted_path(&path)))?;
+    }
+
+    Ok(())
+}
+
+fn remove_existing(paths: &[PathBuf]) -> eyre::Result<()> {
+    for path in paths {
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns the data, offsets and configuration files of the static file at `path`.
+fn jar_files(path: &Path) -> [PathBuf; 3] {
+    let jar = NippyJar::new_without_header(StaticFileSegment::Receipts.columns(), path);
+    [jar.data_path().to_path_buf(), jar.offsets_path(), jar.config_path()]
+}
+
+/// Returns the path the compacted static file is written to before the swap. It can't be parsed
+/// as a static file name, so it's never picked up by the provider.
+fn compacted_path(path: &Path) -> PathBuf {
+    append_to_path(path, &format!("-{COMPACTED_SUFFIX}"))
+}
+
+fn backup_path(path: &Path) -> PathBuf {
+    append_to_path(path, &format!(".{BACKUP_EXTENSION}"))
+}
+
+fn append_to_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    name.into()
+}
@@ -9,7 +9,7 @@ use reth_db_api::{
     table::{Decompress, DupSort, Table},
 };
 use reth_primitives::{BlockHash, Header, StaticFileSegment};
-use reth_provider::StaticFileProviderFactory;
+use reth_provider::{ReceiptProvider, StaticFileProviderFactory};
 use tracing::error;
 
 /// The arguments for the `reth db get` command
@@ -107,9 +107,12 @@ impl Command {
                                     println!("{}", serde_json::to_string_pretty(&transaction)?);
                                 }
                                 StaticFileSegment::Receipts => {
-                                    let receipt = <<Receipts as Table>::Value>::decompress(
-                                        content[0].as_slice(),
-                                    )?;
+                                    // Compacted receipts static files can only be decoded with
+                                    // their topic dictionary.
+                                    let receipt = tool
+                                        .provider_factory
+                                        .static_file_provider()
+                                        .receipt(key)?;
                                     println!("{}", serde_json::to_string_pretty(&receipt)?);
                                 }
                             }
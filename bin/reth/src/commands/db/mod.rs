@@ -10,9 +10,12 @@ use std::io::{self, Write};
 
 mod checksum;
 mod clear;
+mod compact_receipts;
 mod diff;
 mod get;
 mod list;
+mod reorgs;
+mod snapshot_import;
 mod stats;
 /// DB List TUI
 mod tui;
@@ -40,6 +43,8 @@ pub enum Subcommands {
     Diff(diff::Command),
     /// Gets the content of a table for the given key
     Get(get::Command),
+    /// Lists the reorgs of the canonical chain recorded in the reorg journal
+    Reorgs(reorgs::Command),
     /// Deletes all database entries
     Drop {
         /// Bypasses the interactive confirmation and drops the database directly
@@ -48,6 +53,10 @@ pub enum Subcommands {
     },
     /// Deletes all table entries
     Clear(clear::Command),
+    /// Rewrites receipts static files with a compact encoding and a per-file topic dictionary
+    CompactReceipts(compact_receipts::Command),
+    /// Restores a backup of another node into an empty datadir
+    SnapshotImport(snapshot_import::Command),
     /// Lists current and local database versions
     Version,
     /// Returns the full database path
@@ -98,6 +107,11 @@ impl Command {
                     command.execute(&tool)?;
                 });
             }
+            Subcommands::Reorgs(command) => {
+                db_ro_exec!(self.env, tool, {
+                    command.execute(&tool)?;
+                });
+            }
             Subcommands::Drop { force } => {
                 if !force {
                     // Ask for confirmation
@@ -122,6 +136,13 @@ impl Command {
                 let Environment { provider_factory, .. } = self.env.init(AccessRights::RW)?;
                 command.execute(provider_factory)?;
             }
+            Subcommands::CompactReceipts(command) => {
+                let Environment { provider_factory, .. } = self.env.init(AccessRights::RW)?;
+                command.execute(provider_factory)?;
+            }
+            Subcommands::SnapshotImport(command) => {
+                command.execute(data_dir, self.env.chain.clone(), self.env.db.database_args())?;
+            }
             Subcommands::Version => {
                 let local_db_version = match get_db_version(&db_path) {
                     Ok(version) => Some(version),
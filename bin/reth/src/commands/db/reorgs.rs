@@ -0,0 +1,71 @@
+use crate::utils::DbTool;
+use clap::Parser;
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use reth_db::DatabaseEnv;
+use reth_provider::ReorgJournalReader;
+use serde_json::json;
+use std::sync::Arc;
+
+#[derive(Parser, Debug)]
+/// The arguments for the `reth db reorgs` command
+pub struct Command {
+    /// The sequence number of the first reorg to list
+    #[arg(long, default_value_t = 0)]
+    from: u64,
+    /// The maximum number of reorgs to list
+    #[arg(long, short, default_value_t = 100)]
+    len: usize,
+    /// Dump as JSON, including the hashes of all removed and added blocks
+    #[arg(long, short)]
+    json: bool,
+}
+
+impl Command {
+    /// Execute `db reorgs` command
+    pub fn execute(self, tool: &DbTool<Arc<DatabaseEnv>>) -> eyre::Result<()> {
+        let reorgs = tool.provider_factory.reorgs(self.from, self.len)?;
+
+        if self.json {
+            let reorgs = reorgs
+                .into_iter()
+                .map(|(id, reorg)| {
+                    json!({
+                        "id": id,
+                        "timestamp": reorg.timestamp,
+                        "depth": reorg.depth(),
+                        "forkBlockNumber": reorg.fork_block,
+                        "forkBlockHash": reorg.fork_hash,
+                        "oldBlocks": reorg.old_blocks,
+                        "newBlocks": reorg.new_blocks,
+                    })
+                })
+                .collect::<Vec<_>>();
+            println!("{}", serde_json::to_string_pretty(&reorgs)?);
+            return Ok(())
+        }
+
+        if reorgs.is_empty() {
+            println!("No reorgs recorded");
+            return Ok(())
+        }
+
+        let mut table = ComfyTable::new();
+        table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        table.set_header(["ID", "Timestamp", "Depth", "Fork Block", "Old Tip", "New Tip"]);
+        for (id, reorg) in reorgs {
+            let old_tip = reorg.old_blocks.last().map(ToString::to_string).unwrap_or_default();
+            let new_tip = reorg.new_blocks.last().map(ToString::to_string).unwrap_or_default();
+            let mut row = Row::new();
+            row.add_cell(Cell::new(id))
+                .add_cell(Cell::new(reorg.timestamp))
+                .add_cell(Cell::new(reorg.depth()))
+                .add_cell(Cell::new(format!("{} ({})", reorg.fork_block, reorg.fork_hash)))
+                .add_cell(Cell::new(old_tip))
+                .add_cell(Cell::new(new_tip));
+            table.add_row(row);
+        }
+        println!("{table}");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,241 @@
+use crate::{commands::db::checksum::ChecksumViewer, utils::DbTool};
+use clap::Parser;
+use eyre::WrapErr;
+use reth_chainspec::ChainSpec;
+use reth_db::{mdbx::DatabaseArguments, open_db_read_only, TableViewer, Tables};
+use reth_fs_util as fs;
+use reth_node_core::{
+    dirs::{ChainPath, DataDirPath},
+    layout::init_layout_version,
+};
+use reth_primitives::{BlockNumber, B256};
+use reth_provider::{
+    providers::StaticFileProvider, BlockHashReader, ProviderFactory, StageCheckpointReader,
+};
+use reth_stages::StageId;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashSet},
+    io::{self, Read, Write},
+    path::{Component, Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+};
+use tracing::{info, warn};
+
+/// Name of the manifest at the root of a backup archive.
+const MANIFEST: &str = "manifest.json";
+
+/// Name of the directory in the datadir the backup is restored into before it's validated.
+const STAGING_DIR: &str = "snapshot-import";
+
+/// Name of the file in the staging directory listing the entries that are already restored.
+const PROGRESS_FILE: &str = "restored-entries";
+
+/// The arguments for the `reth db snapshot-import` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Path to the backup tar archive, or `-` to read it from stdin.
+    ///
+    /// The archive contains the `db` and `static_files` directories and a `manifest.json` with
+    /// the checkpoint and the table checksums of the backup.
+    #[arg(value_name = "ARCHIVE")]
+    archive: PathBuf,
+
+    /// Restore the backup without verifying the table checksums of the manifest.
+    #[arg(long)]
+    skip_checksums: bool,
+}
+
+/// Manifest of a backup.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BackupManifest {
+    /// Chain ID of the backed up node.
+    chain_id: u64,
+    /// Genesis hash of the backed up node.
+    genesis_hash: B256,
+    /// Block number of the [`StageId::Finish`] checkpoint at the time of the backup.
+    checkpoint: BlockNumber,
+    /// Checksum of every table, as computed by `reth db checksum`.
+    checksums: BTreeMap<String, u64>,
+}
+
+impl Command {
+    /// Execute `db snapshot-import` command
+    pub fn execute(
+        self,
+        data_dir: ChainPath<DataDirPath>,
+        chain: Arc<ChainSpec>,
+        db_args: DatabaseArguments,
+    ) -> eyre::Result<()> {
+        let (db_path, sf_path) = (data_dir.db(), data_dir.static_files());
+        if db_path.join("mdbx.dat").exists() {
+            eyre::bail!("a database already exists at {}, drop it first", db_path.display())
+        }
+
+        let staging = data_dir.data_dir().join(STAGING_DIR);
+        fs::create_dir_all(&staging)?;
+
+        if self.archive.as_os_str() == "-" {
+            self.unpack(io::stdin().lock(), &staging)?;
+        } else {
+            let archive = std::fs::File::open(&self.archive)
+                .wrap_err_with(|| format!("failed to open {}", self.archive.display()))?;
+            self.unpack(archive, &staging)?;
+        }
+
+        let manifest: BackupManifest = serde_json::from_slice(&fs::read(staging.join(MANIFEST))?)?;
+        self.validate(&manifest, &staging, chain, db_args)?;
+
+        fs::rename(staging.join("db"), &db_path)?;
+        if staging.join("static_files").exists() {
+            fs::rename(staging.join("static_files"), &sf_path)?;
+        } else {
+            fs::create_dir_all(&sf_path)?;
+        }
+        init_layout_version(&data_dir)?;
+        fs::remove_dir_all(&staging)?;
+
+        info!(target: "reth::cli", checkpoint = manifest.checkpoint, %data_dir, "Restored backup");
+        Ok(())
+    }
+
+    /// Unpacks the archive into the staging directory.
+    ///
+    /// Entries restored by an interrupted run are recorded in the [`PROGRESS_FILE`] and skipped.
+    fn unpack<R: Read>(&self, archive: R, staging: &Path) -> eyre::Result<()> {
+        let progress_path = staging.join(PROGRESS_FILE);
+        let restored = match fs::read_to_string(&progress_path) {
+            Ok(restored) => restored.lines().map(str::to_string).collect(),
+            Err(_) => HashSet::new(),
+        };
+        if !restored.is_empty() {
+            info!(target: "reth::cli", entries = restored.len(), "Resuming interrupted restore");
+        }
+        let mut progress =
+            std::fs::OpenOptions::new().create(true).append(true).open(&progress_path)?;
+
+        let mut archive = tar::Archive::new(archive);
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            let path = entry.path()?.into_owned();
+            let name = path.to_string_lossy().into_owned();
+
+            if !is_backup_entry(&path) {
+                warn!(target: "reth::cli", entry = %name, "Skipping unexpected archive entry");
+                continue
+            }
+            if restored.contains(&name) {
+                continue
+            }
+
+            if !entry.unpack_in(staging)? {
+                eyre::bail!("invalid archive entry {name}")
+            }
+            if entry.header().entry_type().is_file() {
+                std::fs::File::open(staging.join(&path))?.sync_all()?;
+                info!(target: "reth::cli", entry = %name, size = entry.size(), "Restored entry");
+            }
+            writeln!(progress, "{name}")?;
+            progress.sync_data()?;
+        }
+
+        Ok(())
+    }
+
+    /// Validates the restored database against the manifest.
+    fn validate(
+        &self,
+        manifest: &BackupManifest,
+        staging: &Path,
+        chain: Arc<ChainSpec>,
+        db_args: DatabaseArguments,
+    ) -> eyre::Result<()> {
+        if manifest.chain_id != chain.chain.id() || manifest.genesis_hash != chain.genesis_hash() {
+            eyre::bail!(
+                "the backup is of chain {} with genesis {}, expected chain {} with genesis {}",
+                manifest.chain_id,
+                manifest.genesis_hash,
+                chain.chain.id(),
+                chain.genesis_hash()
+            )
+        }
+
+        let sf_path = staging.join("static_files");
+        fs::create_dir_all(&sf_path)?;
+        let db = open_db_read_only(&staging.join("db"), db_args)?;
+        let provider_factory = ProviderFactory::new(
+            Arc::new(db),
+            chain.clone(),
+            StaticFileProvider::read_only(sf_path)?,
+        );
+
+        let provider = provider_factory.provider()?;
+        if provider.block_hash(0)? != Some(chain.genesis_hash()) {
+            eyre::bail!("the restored database does not contain the genesis block of the chain")
+        }
+        let checkpoint =
+            provider.get_stage_checkpoint(StageId::Finish)?.unwrap_or_default().block_number;
+        if checkpoint != manifest.checkpoint {
+            eyre::bail!(
+                "the restored database is at block {checkpoint}, but the backup was taken at block {}",
+                manifest.checkpoint
+            )
+        }
+        drop(provider);
+
+        if self.skip_checksums {
+            warn!(target: "reth::cli", "Skipping table checksums");
+            return Ok(())
+        }
+
+        let tool = DbTool::new(provider_factory)?;
+        for table in Tables::ALL {
+            let Some(expected) = manifest.checksums.get(table.name()) else {
+                warn!(target: "reth::cli", table = table.name(), "Table missing from the manifest");
+                continue
+            };
+            let (checksum, _) = table.view(&ChecksumViewer::new(&tool))?;
+            if checksum != *expected {
+                eyre::bail!(
+                    "checksum mismatch for table {}: expected {expected:#x}, got {checksum:#x}",
+                    table.name()
+                )
+            }
+        }
+        for name in manifest.checksums.keys() {
+            if Tables::from_str(name).is_err() {
+                warn!(target: "reth::cli", table = %name, "Unknown table in the manifest");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns `true` if the archive entry is part of a backup: the manifest, or an entry of the `db`
+/// or `static_files` directories.
+fn is_backup_entry(path: &Path) -> bool {
+    let mut components = path.components().filter(|c| !matches!(c, Component::CurDir));
+    match components.next() {
+        Some(Component::Normal(first)) if first == MANIFEST => components.next().is_none(),
+        Some(Component::Normal(first)) => first == "db" || first == "static_files",
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backup_entries() {
+        assert!(is_backup_entry(Path::new("manifest.json")));
+        assert!(is_backup_entry(Path::new("./db/mdbx.dat")));
+        assert!(is_backup_entry(Path::new("static_files/static_file_headers_0_499999")));
+        assert!(!is_backup_entry(Path::new("reth.toml")));
+        assert!(!is_backup_entry(Path::new("../db/mdbx.dat")));
+        assert!(!is_backup_entry(Path::new("/db/mdbx.dat")));
+    }
+}
@@ -252,7 +252,10 @@ impl Command {
         .compute_pending_block();
 
         #[cfg(not(feature = "optimism"))]
-        let payload_builder = reth_ethereum_payload_builder::EthereumPayloadBuilder::default();
+        let payload_builder = reth_ethereum_payload_builder::EthereumPayloadBuilder::new(
+            reth_node_ethereum::EthEvmConfig::default()
+                .with_chain_rules(&provider_factory.chain_spec()),
+        );
 
         match payload_builder.try_build(args)? {
             BuildOutcome::Better { payload, .. } => {
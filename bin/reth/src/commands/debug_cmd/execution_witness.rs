@@ -0,0 +1,91 @@
+//! Command for exporting the execution witnesses of blocks.
+
+use crate::{
+    commands::common::{AccessRights, Environment, EnvironmentArgs},
+    macros::block_executor,
+};
+use clap::Parser;
+use reth_cli_runner::CliContext;
+use reth_evm::execute::{BlockExecutorProvider, Executor};
+use reth_provider::{
+    BlockRangeIter, ChainSpecProvider, HeaderProvider, ProviderError, StateProvider,
+};
+use reth_revm::{database::StateProviderDatabase, witness::RecordingDatabase};
+use reth_rpc_types::ExecutionWitness;
+use std::path::PathBuf;
+use tracing::info;
+
+/// `reth debug execution-witness` command
+///
+/// Re-executes the blocks on top of the state of their parents and writes the witness of each
+/// block as `<number>_<hash>.json` into the output directory, in the format of
+/// `debug_executionWitness`.
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    /// The first block to export the witness of
+    #[arg(long)]
+    from: u64,
+
+    /// The last block to export the witness of, defaults to `--from`
+    #[arg(long)]
+    to: Option<u64>,
+
+    /// The directory to write the witnesses to
+    #[arg(long, value_name = "DIR")]
+    output: PathBuf,
+}
+
+impl Command {
+    /// Execute `debug execution-witness` command
+    pub async fn execute(self, _ctx: CliContext) -> eyre::Result<()> {
+        let Environment { provider_factory, .. } = self.env.init(AccessRights::RO)?;
+
+        let to = self.to.unwrap_or(self.from);
+        eyre::ensure!(self.from > 0, "the genesis block has no execution witness");
+        eyre::ensure!(self.from <= to, "--from must not be greater than --to");
+
+        reth_fs_util::create_dir_all(&self.output)?;
+        let executor = block_executor!(provider_factory.chain_spec());
+        let provider = provider_factory.provider()?;
+
+        let mut blocks = BlockRangeIter::new(&provider, self.from..=to).without_receipts();
+        for number in self.from..=to {
+            let block =
+                blocks.next().transpose()?.ok_or(ProviderError::BlockNotFound(number.into()))?;
+            let (hash, block) = (block.block.hash(), block.block.unseal());
+            let total_difficulty = provider
+                .header_td_by_number(number)?
+                .ok_or(ProviderError::TotalDifficultyNotFound(number))?;
+
+            // re-execute the block and record all state that is read from the parent state
+            let state = provider_factory.history_by_block_number(number - 1)?;
+            let mut db = RecordingDatabase::new(StateProviderDatabase::new(&state));
+            executor.executor(&mut db).execute((&block, total_difficulty).into())?;
+            let accessed = db.into_accessed();
+
+            // the parent header is needed for the pre-state root, older headers for the accessed
+            // block hashes
+            let first = accessed.block_hashes.keys().next().copied().unwrap_or(number - 1);
+            let headers = provider
+                .headers_range(first.min(number - 1)..number)?
+                .into_iter()
+                .map(|header| alloy_rlp::encode(header).into())
+                .collect();
+
+            let witness = ExecutionWitness {
+                state: state.witness(&accessed.proof_targets().collect())?,
+                codes: accessed.codes.into_values().map(|code| code.original_bytes()).collect(),
+                headers,
+            };
+
+            let path = self.output.join(format!("{number}_{hash}.json"));
+            reth_fs_util::write(&path, serde_json::to_vec_pretty(&witness)?)?;
+            info!(target: "reth::cli", number, %hash, ?path, "Exported execution witness");
+        }
+
+        Ok(())
+    }
+}
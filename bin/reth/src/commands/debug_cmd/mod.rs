@@ -5,6 +5,7 @@ use reth_cli_runner::CliContext;
 
 mod build_block;
 mod execution;
+mod execution_witness;
 mod in_memory_merkle;
 mod merkle;
 mod replay_engine;
@@ -29,6 +30,8 @@ pub enum Subcommands {
     BuildBlock(build_block::Command),
     /// Debug engine API by replaying stored messages.
     ReplayEngine(replay_engine::Command),
+    /// Export the execution witnesses of blocks for stateless verification.
+    ExecutionWitness(execution_witness::Command),
 }
 
 impl Command {
@@ -40,6 +43,7 @@ impl Command {
             Subcommands::InMemoryMerkle(command) => command.execute(ctx).await,
             Subcommands::BuildBlock(command) => command.execute(ctx).await,
             Subcommands::ReplayEngine(command) => command.execute(ctx).await,
+            Subcommands::ExecutionWitness(command) => command.execute(ctx).await,
         }
     }
 }
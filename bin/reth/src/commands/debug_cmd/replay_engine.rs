@@ -6,7 +6,9 @@ use crate::{
 use clap::Parser;
 use eyre::Context;
 use reth_basic_payload_builder::{BasicPayloadJobGenerator, BasicPayloadJobGeneratorConfig};
-use reth_beacon_consensus::{hooks::EngineHooks, BeaconConsensusEngine, EthBeaconConsensus};
+use reth_beacon_consensus::{
+    hooks::EngineHooks, BeaconConsensusEngine, EthBeaconConsensus, ForkchoiceStatus,
+};
 use reth_blockchain_tree::{
     BlockchainTree, BlockchainTreeConfig, ShareableBlockchainTree, TreeExternals,
 };
@@ -17,7 +19,9 @@ use reth_db::DatabaseEnv;
 use reth_fs_util as fs;
 use reth_network::NetworkHandle;
 use reth_network_api::NetworkInfo;
-use reth_node_core::engine::engine_store::{EngineMessageStore, StoredEngineApiMessage};
+use reth_node_core::engine::engine_store::{
+    EngineMessageStore, StoredEngineApiMessage, StoredEngineApiResponse,
+};
 use reth_payload_builder::{PayloadBuilderHandle, PayloadBuilderService};
 use reth_provider::{
     providers::BlockchainProvider, CanonStateSubscriptions, ChainSpecProvider, ProviderFactory,
@@ -33,6 +37,8 @@ use tracing::*;
 
 /// `reth debug replay-engine` command
 /// This script will read stored engine API messages and replay them by the timestamp.
+/// If the responses of the engine were recorded with the messages, the responses of the replay
+/// are compared to them.
 /// It does not require
 #[derive(Debug, Parser)]
 pub struct Command {
@@ -111,7 +117,10 @@ impl Command {
 
         // Set up payload builder
         #[cfg(not(feature = "optimism"))]
-        let payload_builder = reth_ethereum_payload_builder::EthereumPayloadBuilder::default();
+        let payload_builder = reth_ethereum_payload_builder::EthereumPayloadBuilder::new(
+            reth_node_ethereum::EthEvmConfig::default()
+                .with_chain_rules(&provider_factory.chain_spec()),
+        );
 
         // Optimism's payload builder is implemented on the OptimismPayloadBuilder type.
         #[cfg(feature = "optimism")]
@@ -171,29 +180,53 @@ impl Command {
         });
 
         let engine_api_store = EngineMessageStore::new(self.engine_api_store.clone());
+        let (mut compared, mut mismatched) = (0usize, 0usize);
         for filepath in engine_api_store.engine_messages_iter()? {
             let contents =
                 fs::read(&filepath).wrap_err(format!("failed to read: {}", filepath.display()))?;
             let message = serde_json::from_slice(&contents)
                 .wrap_err(format!("failed to parse: {}", filepath.display()))?;
             debug!(target: "reth::cli", filepath = %filepath.display(), ?message, "Forwarding Engine API message");
-            match message {
+            let response = match message {
                 StoredEngineApiMessage::ForkchoiceUpdated { state, payload_attrs } => {
                     let response =
-                        beacon_engine_handle.fork_choice_updated(state, payload_attrs).await?;
+                        beacon_engine_handle.fork_choice_updated(state, payload_attrs).await;
                     debug!(target: "reth::cli", ?response, "Received for forkchoice updated");
+                    match response {
+                        Ok(updated) => StoredEngineApiResponse::ForkchoiceUpdated {
+                            status: ForkchoiceStatus::from(updated.payload_status.status).into(),
+                        },
+                        Err(err) => StoredEngineApiResponse::Error { message: err.to_string() },
+                    }
                 }
                 StoredEngineApiMessage::NewPayload { payload, cancun_fields } => {
-                    let response = beacon_engine_handle.new_payload(payload, cancun_fields).await?;
+                    let response = beacon_engine_handle.new_payload(payload, cancun_fields).await;
                     debug!(target: "reth::cli", ?response, "Received for new payload");
+                    match response {
+                        Ok(status) => StoredEngineApiResponse::NewPayload { status },
+                        Err(err) => StoredEngineApiResponse::Error { message: err.to_string() },
+                    }
                 }
             };
 
+            if let Some(recorded) = engine_api_store.response(&filepath)? {
+                compared += 1;
+                if recorded != response {
+                    mismatched += 1;
+                    warn!(target: "reth::cli",
+                        filepath = %filepath.display(),
+                        ?recorded,
+                        replayed = ?response,
+                        "Replayed response differs from the recorded one"
+                    );
+                }
+            }
+
             // Pause before next message
             tokio::time::sleep(Duration::from_millis(self.interval)).await;
         }
 
-        info!(target: "reth::cli", "Finished replaying engine API messages");
+        info!(target: "reth::cli", compared, mismatched, "Finished replaying engine API messages");
 
         match rx.await? {
             Ok(()) => info!("Beacon consensus engine exited successfully"),
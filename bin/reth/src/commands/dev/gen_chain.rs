@@ -0,0 +1,466 @@
+//! Command that generates a deterministic chain for benchmarks.
+
+use crate::{
+    args::{
+        utils::{chain_help, chain_value_parser},
+        DatabaseArgs, DatadirArgs,
+    },
+    commands::common::{AccessRights, Environment, EnvironmentArgs},
+};
+use clap::Parser;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use reth_chainspec::{ChainSpec, ForkCondition, Hardfork};
+use reth_db::DatabaseEnv;
+use reth_evm::execute::{BlockExecutionOutput, BlockExecutorProvider, Executor};
+use reth_execution_types::ExecutionOutcome;
+use reth_fs_util as fs;
+use reth_node_ethereum::EthExecutorProvider;
+use reth_primitives::{
+    constants::EMPTY_OMMER_ROOT_HASH, keccak256, proofs, sign_message, Address, Block, Bloom,
+    Bytes, Genesis, GenesisAccount, Header, SealedHeader, Transaction, TransactionSigned,
+    TxEip1559, TxKind, Withdrawals, B256, U256,
+};
+use reth_provider::{
+    BlockNumReader, BlockWriter, HeaderProvider, ProviderFactory, StageCheckpointWriter,
+    StateProviderFactory,
+};
+use reth_revm::database::StateProviderDatabase;
+use reth_stages::{StageCheckpoint, StageId};
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::PathBuf,
+    sync::Arc,
+};
+use tracing::info;
+
+/// The address of the token contract that is deployed in the genesis block.
+const TOKEN_ADDRESS: Address = Address::new([
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x0e, 0x2c, 0x20,
+]);
+
+/// Runtime code of a minimal ERC-20 like token.
+///
+/// Called with `abi.encode(to, amount)`, it moves `amount` from the balance of the caller to the
+/// balance of `to` and emits a `Transfer(from, to)` log. Balances are stored at the slot of the
+/// account address. There are no balance checks, senders are funded generously in the genesis.
+#[rustfmt::skip]
+const TOKEN_RUNTIME: &[u8] = &[
+    // sstore(caller, sload(caller) - amount)
+    0x60, 0x20, 0x35, 0x33, 0x54, 0x03, 0x33, 0x55,
+    // sstore(to, sload(to) + amount)
+    0x60, 0x20, 0x35, 0x60, 0x00, 0x35, 0x54, 0x01, 0x60, 0x00, 0x35, 0x55,
+    // log3(0, 0, Transfer, caller, to)
+    0x60, 0x00, 0x35, 0x33, 0x7f, 0xdd, 0xf2, 0x52, 0xad, 0x1b, 0xe2, 0xc8, 0x9b, 0x69, 0xc2, 0xb0,
+    0x68, 0xfc, 0x37, 0x8d, 0xaa, 0x95, 0x2b, 0xa7, 0xf1, 0x63, 0xc4, 0xa1, 0x16, 0x28, 0xf5, 0x5a,
+    0x4d, 0xf5, 0x23, 0xb3, 0xef, 0x60, 0x00, 0x60, 0x00, 0xa3, 0x00,
+];
+
+/// Gas limit of plain ETH transfers.
+const TRANSFER_GAS_LIMIT: u64 = 21_000;
+
+/// Gas limit of token transfers.
+const TOKEN_TRANSFER_GAS_LIMIT: u64 = 100_000;
+
+/// Gas limit of contract creations.
+const CREATE_GAS_LIMIT: u64 = 200_000;
+
+/// The priority fee of all generated transactions: 1 gwei.
+const PRIORITY_FEE: u128 = 1_000_000_000;
+
+/// Seconds between generated blocks.
+const BLOCK_TIME: u64 = 12;
+
+/// `reth dev gen-chain` command
+///
+/// Generates blocks with a configurable mix of ETH transfers, token transfers and contract
+/// creations and writes them into the datadir. The transaction senders and a token contract are
+/// added to the genesis of the chain, so the datadir must not have been initialized yet.
+///
+/// The same seed and options always generate the same chain.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The number of blocks to generate.
+    #[arg(long, default_value_t = 1000)]
+    blocks: u64,
+
+    /// The number of transactions per block.
+    ///
+    /// Blocks contain fewer transactions if the transactions don't fit into the block gas limit.
+    #[arg(long, default_value_t = 100)]
+    txs_per_block: usize,
+
+    /// The number of accounts that send transactions.
+    #[arg(long, default_value_t = 1000)]
+    senders: u64,
+
+    /// The relative weight of ETH transfers to new accounts in the transaction mix.
+    #[arg(long = "mix.transfers", default_value_t = 60)]
+    transfers: u32,
+
+    /// The relative weight of token transfers in the transaction mix.
+    #[arg(long = "mix.erc20", default_value_t = 30)]
+    erc20: u32,
+
+    /// The relative weight of contract creations in the transaction mix.
+    #[arg(long = "mix.creates", default_value_t = 10)]
+    creates: u32,
+
+    /// The seed for the sender keys and the transaction mix.
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+
+    /// Also writes the generated chain to this directory.
+    ///
+    /// The directory will contain the blocks as `chain.rlp`, for `reth import`, and the genesis
+    /// with the funded senders as `genesis.json`, for `--chain`.
+    #[arg(long, value_name = "DIR")]
+    output: Option<PathBuf>,
+
+    /// Parameters for datadir configuration
+    #[command(flatten)]
+    datadir: DatadirArgs,
+
+    /// The chain to generate blocks for. The chain must be post-merge at genesis.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = chain_help(),
+        default_value = "dev",
+        value_parser = chain_value_parser
+    )]
+    chain: Arc<ChainSpec>,
+
+    /// All database related arguments
+    #[command(flatten)]
+    db: DatabaseArgs,
+}
+
+/// The kind of a generated transaction.
+#[derive(Debug, Clone, Copy)]
+enum TxMix {
+    Transfer,
+    Erc20,
+    Create,
+}
+
+impl Command {
+    /// Execute `dev gen-chain` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        eyre::ensure!(self.senders > 0, "at least one sender is required");
+        eyre::ensure!(
+            self.transfers + self.erc20 + self.creates > 0,
+            "at least one transaction kind must have a weight"
+        );
+        eyre::ensure!(
+            matches!(self.chain.paris_block_and_final_difficulty, Some((0, _))),
+            "only chains that are post-merge at genesis are supported"
+        );
+
+        let keys = (0..self.senders)
+            .map(|idx| keccak256([self.seed.to_be_bytes(), idx.to_be_bytes()].concat()))
+            .collect::<Vec<_>>();
+        let senders =
+            keys.iter().map(|key| signer_address(*key)).collect::<eyre::Result<Vec<_>>>()?;
+
+        let chain_spec = Arc::new(self.fund_senders(&senders));
+        let mut output = None;
+        if let Some(dir) = &self.output {
+            fs::create_dir_all(dir)?;
+            fs::write(
+                dir.join("genesis.json"),
+                serde_json::to_vec_pretty(&genesis_with_config(&chain_spec))?,
+            )?;
+            output = Some(BufWriter::new(File::create(dir.join("chain.rlp"))?));
+        }
+
+        let env = EnvironmentArgs {
+            datadir: self.datadir.clone(),
+            config: None,
+            chain: chain_spec.clone(),
+            db: self.db,
+        };
+        let Environment { provider_factory, .. } = env.init(AccessRights::RW)?;
+        eyre::ensure!(
+            provider_factory.last_block_number()? == 0,
+            "the datadir already contains blocks"
+        );
+
+        let executor = EthExecutorProvider::ethereum(chain_spec.clone());
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut nonces = vec![0u64; senders.len()];
+        let mut parent = provider_factory
+            .sealed_header(0)?
+            .ok_or_else(|| eyre::eyre!("genesis header is missing"))?;
+
+        for _ in 0..self.blocks {
+            let timestamp = parent.timestamp + BLOCK_TIME;
+            let base_fee = parent
+                .next_block_base_fee(chain_spec.base_fee_params_at_timestamp(timestamp))
+                .unwrap_or_default();
+
+            let mut transactions = Vec::with_capacity(self.txs_per_block);
+            let mut block_senders = Vec::with_capacity(self.txs_per_block);
+            let mut gas = 0;
+            for _ in 0..self.txs_per_block {
+                let sender = rng.gen_range(0..senders.len());
+                let (to, value, input, gas_limit) = match self.next_tx(&mut rng) {
+                    TxMix::Transfer => (
+                        TxKind::Call(Address::from(rng.gen::<[u8; 20]>())),
+                        U256::from(rng.gen_range(1..1_000_000_000u64)),
+                        Bytes::new(),
+                        TRANSFER_GAS_LIMIT,
+                    ),
+                    TxMix::Erc20 => {
+                        let to = senders[rng.gen_range(0..senders.len())];
+                        let amount = U256::from(rng.gen_range(1..1_000_000u64));
+                        let mut input = B256::left_padding_from(to.as_slice()).to_vec();
+                        input.extend_from_slice(&amount.to_be_bytes::<32>());
+                        (
+                            TxKind::Call(TOKEN_ADDRESS),
+                            U256::ZERO,
+                            input.into(),
+                            TOKEN_TRANSFER_GAS_LIMIT,
+                        )
+                    }
+                    TxMix::Create => {
+                        (TxKind::Create, U256::ZERO, token_init_code(), CREATE_GAS_LIMIT)
+                    }
+                };
+                if gas + gas_limit > parent.gas_limit {
+                    break
+                }
+                gas += gas_limit;
+
+                let transaction = Transaction::Eip1559(TxEip1559 {
+                    chain_id: chain_spec.chain.id(),
+                    nonce: nonces[sender],
+                    gas_limit,
+                    max_fee_per_gas: u128::from(base_fee) * 2 + PRIORITY_FEE,
+                    max_priority_fee_per_gas: PRIORITY_FEE,
+                    to,
+                    value,
+                    access_list: Default::default(),
+                    input,
+                });
+                let signature = sign_message(keys[sender], transaction.signature_hash())?;
+                transactions.push(TransactionSigned::from_transaction_and_signature(
+                    transaction,
+                    signature,
+                ));
+                block_senders.push(senders[sender]);
+                nonces[sender] += 1;
+            }
+
+            let header = self.build_and_insert_block(
+                &provider_factory,
+                &executor,
+                &parent,
+                timestamp,
+                base_fee,
+                transactions,
+                block_senders,
+                output.as_mut(),
+            )?;
+            info!(target: "reth::cli", number = header.number, hash = %header.hash(), gas_used = header.gas_used, "Generated block");
+            parent = header;
+        }
+
+        if let Some(mut output) = output {
+            output.flush()?;
+        }
+
+        // mark the generated blocks as fully synced
+        let provider_rw = provider_factory.provider_rw()?;
+        for stage in StageId::ALL {
+            provider_rw.save_stage_checkpoint(stage, StageCheckpoint::new(parent.number))?;
+        }
+        provider_rw.commit()?;
+
+        info!(target: "reth::cli", blocks = self.blocks, "Generated chain");
+        Ok(())
+    }
+
+    /// Returns the chain spec with the senders and the token contract added to the genesis.
+    fn fund_senders(&self, senders: &[Address]) -> ChainSpec {
+        let mut spec = (*self.chain).clone();
+        let balance = U256::from(10).pow(U256::from(24));
+
+        for sender in senders {
+            spec.genesis.alloc.insert(*sender, GenesisAccount { balance, ..Default::default() });
+        }
+        spec.genesis.alloc.insert(
+            TOKEN_ADDRESS,
+            GenesisAccount {
+                code: Some(TOKEN_RUNTIME.to_vec().into()),
+                storage: Some(
+                    senders
+                        .iter()
+                        .map(|sender| (B256::left_padding_from(sender.as_slice()), balance.into()))
+                        .collect(),
+                ),
+                ..Default::default()
+            },
+        );
+        // the genesis state changed, so the genesis hash has to be recomputed
+        spec.genesis_hash = None;
+
+        spec
+    }
+
+    /// Picks the kind of the next transaction according to the configured weights.
+    fn next_tx(&self, rng: &mut StdRng) -> TxMix {
+        let pick = rng.gen_range(0..self.transfers + self.erc20 + self.creates);
+        if pick < self.transfers {
+            TxMix::Transfer
+        } else if pick < self.transfers + self.erc20 {
+            TxMix::Erc20
+        } else {
+            TxMix::Create
+        }
+    }
+
+    /// Executes the transactions on top of the parent block, completes the header with the
+    /// execution results and inserts the block into the database.
+    #[allow(clippy::too_many_arguments)]
+    fn build_and_insert_block(
+        &self,
+        provider_factory: &ProviderFactory<Arc<DatabaseEnv>>,
+        executor: &EthExecutorProvider,
+        parent: &SealedHeader,
+        timestamp: u64,
+        base_fee: u64,
+        transactions: Vec<TransactionSigned>,
+        senders: Vec<Address>,
+        output: Option<&mut BufWriter<File>>,
+    ) -> eyre::Result<SealedHeader> {
+        let chain_spec = &self.chain;
+        let withdrawals =
+            chain_spec.is_shanghai_active_at_timestamp(timestamp).then_some(Withdrawals::default());
+        eyre::ensure!(
+            !chain_spec.is_prague_active_at_timestamp(timestamp),
+            "generating Prague blocks is not supported"
+        );
+
+        let mut header = Header {
+            parent_hash: parent.hash(),
+            ommers_hash: EMPTY_OMMER_ROOT_HASH,
+            transactions_root: proofs::calculate_transaction_root(&transactions),
+            withdrawals_root: withdrawals.as_ref().map(|w| proofs::calculate_withdrawals_root(w)),
+            number: parent.number + 1,
+            gas_limit: parent.gas_limit,
+            timestamp,
+            mix_hash: keccak256((parent.number + 1).to_be_bytes()),
+            base_fee_per_gas: Some(base_fee),
+            ..Default::default()
+        };
+        if chain_spec.is_cancun_active_at_timestamp(timestamp) {
+            header.blob_gas_used = Some(0);
+            header.excess_blob_gas = Some(parent.next_block_excess_blob_gas().unwrap_or_default());
+            header.parent_beacon_block_root = Some(B256::ZERO);
+        }
+
+        let block =
+            Block { header, body: transactions, ommers: Vec::new(), withdrawals, requests: None }
+                .with_senders_unchecked(senders);
+
+        let db = StateProviderDatabase::new(provider_factory.latest()?);
+        let BlockExecutionOutput { state, receipts, requests, gas_used } =
+            executor.executor(db).execute((&block, U256::ZERO).into())?;
+        let execution_outcome =
+            ExecutionOutcome::new(state, receipts.into(), block.number, vec![requests.into()]);
+
+        let hashed_post_state = execution_outcome.hash_state_slow();
+        let (state_root, trie_updates) =
+            hashed_post_state.state_root_with_updates(provider_factory.provider()?.tx_ref())?;
+
+        let mut block = block;
+        let receipts = execution_outcome.receipts_by_block(block.number);
+        block.block.header.state_root = state_root;
+        block.block.header.gas_used = gas_used;
+        block.block.header.logs_bloom = receipts
+            .iter()
+            .flatten()
+            .fold(Bloom::ZERO, |bloom, receipt| bloom | receipt.bloom_slow());
+        block.block.header.receipts_root = execution_outcome
+            .receipts_root_slow(block.number)
+            .ok_or_else(|| eyre::eyre!("receipts are missing"))?;
+
+        let block = block.seal_slow();
+        if let Some(output) = output {
+            output.write_all(&alloy_rlp::encode(block.block.clone().unseal()))?;
+        }
+
+        let header = block.header.clone();
+        let provider_rw = provider_factory.provider_rw()?;
+        provider_rw.append_blocks_with_state(
+            vec![block],
+            execution_outcome,
+            hashed_post_state,
+            trie_updates,
+            None,
+        )?;
+        provider_rw.commit()?;
+
+        Ok(header)
+    }
+}
+
+/// Returns the address of the given private key.
+fn signer_address(key: B256) -> eyre::Result<Address> {
+    let signature = sign_message(key, B256::ZERO)?;
+    signature.recover_signer(B256::ZERO).ok_or_else(|| eyre::eyre!("invalid sender key"))
+}
+
+/// Returns the init code that deploys another instance of the token contract.
+fn token_init_code() -> Bytes {
+    let len = TOKEN_RUNTIME.len() as u8;
+    // codecopy(0, 12, len) return(0, len)
+    let mut code = vec![0x60, len, 0x60, 0x0c, 0x60, 0x00, 0x39, 0x60, len, 0x60, 0x00, 0xf3];
+    code.extend_from_slice(TOKEN_RUNTIME);
+    code.into()
+}
+
+/// Returns the genesis of the chain spec with its hardfork schedule written to the chain config,
+/// so the chain spec can be recreated from the genesis file alone.
+fn genesis_with_config(chain_spec: &ChainSpec) -> Genesis {
+    let block = |fork| match chain_spec.fork(fork) {
+        ForkCondition::Block(block) => Some(block),
+        _ => None,
+    };
+    let timestamp = |fork| match chain_spec.fork(fork) {
+        ForkCondition::Timestamp(timestamp) => Some(timestamp),
+        _ => None,
+    };
+
+    let mut genesis = chain_spec.genesis.clone();
+    let config = &mut genesis.config;
+    config.chain_id = chain_spec.chain.id();
+    config.homestead_block = block(Hardfork::Homestead);
+    config.dao_fork_block = block(Hardfork::Dao);
+    config.eip150_block = block(Hardfork::Tangerine);
+    config.eip155_block = block(Hardfork::SpuriousDragon);
+    config.eip158_block = block(Hardfork::SpuriousDragon);
+    config.byzantium_block = block(Hardfork::Byzantium);
+    config.constantinople_block = block(Hardfork::Constantinople);
+    config.petersburg_block = block(Hardfork::Petersburg);
+    config.istanbul_block = block(Hardfork::Istanbul);
+    config.muir_glacier_block = block(Hardfork::MuirGlacier);
+    config.berlin_block = block(Hardfork::Berlin);
+    config.london_block = block(Hardfork::London);
+    config.arrow_glacier_block = block(Hardfork::ArrowGlacier);
+    config.gray_glacier_block = block(Hardfork::GrayGlacier);
+    if let ForkCondition::TTD { fork_block, total_difficulty } = chain_spec.fork(Hardfork::Paris) {
+        config.terminal_total_difficulty = Some(total_difficulty);
+        config.terminal_total_difficulty_passed = true;
+        config.merge_netsplit_block = fork_block;
+    }
+    config.shanghai_time = timestamp(Hardfork::Shanghai);
+    config.cancun_time = timestamp(Hardfork::Cancun);
+    config.prague_time = timestamp(Hardfork::Prague);
+
+    genesis
+}
@@ -0,0 +1,28 @@
+//! `reth dev` command. Collection of development utilities.
+
+use clap::{Parser, Subcommand};
+
+mod gen_chain;
+
+/// `reth dev` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth dev` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands {
+    /// Generate a deterministic chain for benchmarks.
+    GenChain(gen_chain::Command),
+}
+
+impl Command {
+    /// Execute `dev` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::GenChain(command) => command.execute().await,
+        }
+    }
+}
@@ -0,0 +1,596 @@
+//! `reth doctor` command, checks the health of a datadir without modifying it.
+
+use crate::commands::common::EnvironmentArgs;
+use clap::Parser;
+use reth_config::Config;
+use reth_db::{
+    lockfile::{StorageLock, StorageLockStatus},
+    open_db_read_only,
+    version::{check_db_version_file, DatabaseVersionError, DB_VERSION},
+    DatabaseEnv,
+};
+use reth_node_core::layout::{check_layout_version, LayoutVersionError, LAYOUT_VERSION};
+use reth_primitives::StaticFileSegment;
+use reth_provider::{
+    providers::StaticFileProvider, BlockHashReader, BlockNumReader, HeaderProvider,
+    ProviderFactory, PruneCheckpointReader, StageCheckpointReader, StaticFileProviderFactory,
+};
+use reth_prune_types::{PruneModes, PruneSegment};
+use reth_stages::StageId;
+use std::{
+    fmt,
+    path::Path,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// `reth doctor` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    /// The number of days of chain growth the free disk space should be sufficient for.
+    #[arg(long = "disk.days", value_name = "DAYS", default_value_t = 30)]
+    disk_days: u64,
+}
+
+impl Command {
+    /// Execute `doctor` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let findings = self.diagnose();
+
+        for finding in &findings {
+            println!("{finding}");
+        }
+
+        let errors = findings.iter().filter(|f| f.severity == Severity::Error).count();
+        let warnings = findings.iter().filter(|f| f.severity == Severity::Warning).count();
+        println!();
+        println!("{errors} error(s), {warnings} warning(s)");
+
+        if errors > 0 {
+            eyre::bail!("the datadir has {errors} problem(s)")
+        }
+        Ok(())
+    }
+
+    /// Runs all checks and returns their findings.
+    ///
+    /// Checks that depend on the database are skipped if it can't be opened.
+    fn diagnose(&self) -> Vec<Finding> {
+        let chain = &self.env.chain;
+        let data_dir = self.env.datadir.clone().resolve_datadir(chain.chain);
+        let (db_path, sf_path) = (data_dir.db(), data_dir.static_files());
+        let mut findings = Vec::new();
+
+        findings.push(check_lock("database lock", &db_path));
+        findings.push(check_lock("static files lock", &sf_path));
+
+        match check_layout_version(&data_dir) {
+            Ok(()) => findings.push(Finding::ok("layout", format!("v{LAYOUT_VERSION}"))),
+            Err(err) => {
+                let fix = match &err {
+                    LayoutVersionError::Outdated { .. } => format!(
+                        "upgrade the datadir with `reth datadir upgrade --chain {}`",
+                        chain.chain
+                    ),
+                    LayoutVersionError::Unsupported { .. } => {
+                        "use the version of reth that created the datadir".to_string()
+                    }
+                    _ => format!("check the permissions and the contents of {data_dir}"),
+                };
+                findings.push(Finding::error("layout", err.to_string(), fix));
+                return findings
+            }
+        }
+
+        if !db_path.exists() {
+            findings.push(Finding::error(
+                "database",
+                format!("no database at {}", db_path.display()),
+                format!(
+                    "initialize the datadir with `reth init --chain {}` or start the node",
+                    chain.chain
+                ),
+            ));
+            return findings
+        }
+
+        match check_db_version_file(&db_path) {
+            Ok(()) => {}
+            Err(err @ DatabaseVersionError::VersionMismatch { version }) => {
+                let fix = if version == DB_VERSION - 1 {
+                    format!(
+                        "upgrade the datadir with `reth datadir upgrade --chain {}`",
+                        chain.chain
+                    )
+                } else {
+                    "the database was created by an incompatible version of reth, resync the node \
+                     into an empty datadir"
+                        .to_string()
+                };
+                findings.push(Finding::error("database", err.to_string(), fix));
+                return findings
+            }
+            Err(err) => findings.push(Finding::warning(
+                "database",
+                err.to_string(),
+                "the version file is recreated the next time the node opens the database",
+            )),
+        }
+
+        let factory = match self.open(&db_path, &sf_path) {
+            Ok(factory) => factory,
+            Err(err) => {
+                findings.push(Finding::error(
+                    "database",
+                    format!("{err:#}"),
+                    "check the permissions of the datadir, or restore it from a backup or snapshot",
+                ));
+                return findings
+            }
+        };
+        findings.push(Finding::ok("database", "opened read-only"));
+
+        type Check = fn(&Command, &ProviderFactory<Arc<DatabaseEnv>>) -> eyre::Result<Vec<Finding>>;
+        let checks: [(&str, Check); 4] = [
+            ("genesis", Self::check_genesis),
+            ("storage consistency", Self::check_consistency),
+            ("checkpoints", Self::check_checkpoints),
+            ("disk space", Self::check_disk_space),
+        ];
+        for (check, run) in checks {
+            match run(self, &factory) {
+                Ok(mut check_findings) => findings.append(&mut check_findings),
+                Err(err) => findings.push(Finding::error(
+                    check,
+                    format!("the check failed: {err:#}"),
+                    "the database may be corrupted, restore it from a backup or snapshot",
+                )),
+            }
+        }
+
+        findings
+    }
+
+    /// Opens the database and static files read-only.
+    fn open(
+        &self,
+        db_path: &Path,
+        sf_path: &Path,
+    ) -> eyre::Result<ProviderFactory<Arc<DatabaseEnv>>> {
+        let db = Arc::new(open_db_read_only(db_path, self.env.db.database_args())?);
+        Ok(ProviderFactory::new(
+            db,
+            self.env.chain.clone(),
+            StaticFileProvider::read_only(sf_path)?,
+        ))
+    }
+
+    /// Loads the configuration of the node, falling back to the default.
+    fn config(&self) -> Config {
+        let data_dir = self.env.datadir.clone().resolve_datadir(self.env.chain.chain);
+        let config_path = self.env.config.clone().unwrap_or_else(|| data_dir.config());
+        confy::load_path(config_path).unwrap_or_default()
+    }
+
+    /// Checks that the datadir was initialized with the genesis of the selected chain.
+    fn check_genesis(
+        &self,
+        factory: &ProviderFactory<Arc<DatabaseEnv>>,
+    ) -> eyre::Result<Vec<Finding>> {
+        let chain = &self.env.chain;
+        let finding = match factory.block_hash(0)? {
+            Some(hash) if hash == chain.genesis_hash() => {
+                Finding::ok("genesis", format!("matches chain {}", chain.chain))
+            }
+            Some(hash) => Finding::error(
+                "genesis",
+                format!(
+                    "the datadir was initialized with genesis {hash}, chain {} has genesis {}",
+                    chain.chain,
+                    chain.genesis_hash()
+                ),
+                "pass the chain the datadir was created for with `--chain`, or use another \
+                 `--datadir`",
+            ),
+            None => Finding::error(
+                "genesis",
+                "the database has no genesis block",
+                format!("initialize the datadir with `reth init --chain {}`", chain.chain),
+            ),
+        };
+        Ok(vec![finding])
+    }
+
+    /// Checks that the database and the static files agree with each other.
+    fn check_consistency(
+        &self,
+        factory: &ProviderFactory<Arc<DatabaseEnv>>,
+    ) -> eyre::Result<Vec<Finding>> {
+        let has_receipt_pruning =
+            self.config().prune.as_ref().map_or(false, |prune| prune.has_receipts_pruning());
+        let finding = match factory
+            .static_file_provider()
+            .check_consistency(&factory.provider()?, has_receipt_pruning)?
+        {
+            None => Finding::ok("storage consistency", "database and static files are consistent"),
+            Some(target) => Finding::error(
+                "storage consistency",
+                format!("database and static files are inconsistent, a {target} is required"),
+                "start the node, it heals the storage on startup, or run `reth stage unwind`",
+            ),
+        };
+        Ok(vec![finding])
+    }
+
+    /// Checks the stage and prune checkpoints against the synced headers and the prune
+    /// configuration.
+    fn check_checkpoints(
+        &self,
+        factory: &ProviderFactory<Arc<DatabaseEnv>>,
+    ) -> eyre::Result<Vec<Finding>> {
+        let stages = StageId::ALL
+            .into_iter()
+            .map(|stage| Ok((stage, factory.get_stage_checkpoint(stage)?.map(|c| c.block_number))))
+            .collect::<eyre::Result<Vec<_>>>()?;
+        let headers_tip = factory
+            .static_file_provider()
+            .get_highest_static_file_block(StaticFileSegment::Headers)
+            .unwrap_or_default();
+        let mut findings = check_stage_checkpoints(&stages, headers_tip);
+
+        let finished = stages
+            .iter()
+            .find(|(stage, _)| *stage == StageId::Finish)
+            .and_then(|(_, block)| *block)
+            .unwrap_or_default();
+        let prune_modes = self.config().prune.map(|prune| prune.segments).unwrap_or_default();
+        let segments = [
+            PruneSegment::SenderRecovery,
+            PruneSegment::TransactionLookup,
+            PruneSegment::Receipts,
+            PruneSegment::ContractLogs,
+            PruneSegment::AccountHistory,
+            PruneSegment::StorageHistory,
+        ]
+        .into_iter()
+        .map(|segment| {
+            Ok((segment, factory.get_prune_checkpoint(segment)?.and_then(|c| c.block_number)))
+        })
+        .collect::<eyre::Result<Vec<_>>>()?;
+        findings.extend(check_prune_checkpoints(&segments, &prune_modes, finished));
+
+        Ok(findings)
+    }
+
+    /// Checks that the free disk space is sufficient for the projected growth of the datadir.
+    fn check_disk_space(
+        &self,
+        factory: &ProviderFactory<Arc<DatabaseEnv>>,
+    ) -> eyre::Result<Vec<Finding>> {
+        let data_dir = self.env.datadir.clone().resolve_datadir(self.env.chain.chain);
+        let size = reth_fs_util::dir_size(data_dir.db())? +
+            reth_fs_util::dir_size(data_dir.static_files())?;
+
+        let Some(available) = reth_fs_util::available_space(data_dir.data_dir()) else {
+            return Ok(vec![Finding::warning(
+                "disk space",
+                format!("the disk of {} could not be determined", data_dir.data_dir().display()),
+                "make sure the disk has enough free space for the chain to grow",
+            )])
+        };
+
+        let tip = factory.last_block_number()?;
+        let tip_timestamp =
+            factory.header_by_number(tip)?.map(|header| header.timestamp).unwrap_or_default();
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let projected = projected_growth(
+            size,
+            tip_timestamp.saturating_sub(self.env.chain.genesis.timestamp),
+            now.saturating_sub(tip_timestamp),
+            self.disk_days,
+        );
+
+        let finding = if available < projected {
+            Finding::warning(
+                "disk space",
+                format!(
+                    "{} free, the datadir uses {} and is projected to grow by {} within {} days",
+                    human_bytes(available),
+                    human_bytes(size),
+                    human_bytes(projected),
+                    self.disk_days
+                ),
+                "free up disk space, move the datadir to a larger disk or enable pruning in \
+                 reth.toml",
+            )
+        } else {
+            Finding::ok(
+                "disk space",
+                format!(
+                    "{} free, projected growth within {} days is {}",
+                    human_bytes(available),
+                    self.disk_days,
+                    human_bytes(projected)
+                ),
+            )
+        };
+        Ok(vec![finding])
+    }
+}
+
+/// Severity of a [`Finding`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Severity {
+    Ok,
+    Warning,
+    Error,
+}
+
+/// The outcome of a single check.
+#[derive(Debug)]
+struct Finding {
+    severity: Severity,
+    check: &'static str,
+    message: String,
+    /// What to do about the finding.
+    fix: Option<String>,
+}
+
+impl Finding {
+    fn ok(check: &'static str, message: impl Into<String>) -> Self {
+        Self { severity: Severity::Ok, check, message: message.into(), fix: None }
+    }
+
+    fn warning(check: &'static str, message: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self { severity: Severity::Warning, check, message: message.into(), fix: Some(fix.into()) }
+    }
+
+    fn error(check: &'static str, message: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self { severity: Severity::Error, check, message: message.into(), fix: Some(fix.into()) }
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Ok => "ok",
+            Severity::Warning => "warn",
+            Severity::Error => "error",
+        };
+        write!(f, "{severity:<6} {:<20} {}", self.check, self.message)?;
+        if let Some(fix) = &self.fix {
+            write!(f, "\n{:28}fix: {fix}", "")?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks whether the storage directory is locked by another process.
+fn check_lock(check: &'static str, path: &Path) -> Finding {
+    match StorageLock::status(path) {
+        Ok(StorageLockStatus::Unlocked) => Finding::ok(check, "not held by any process"),
+        Ok(StorageLockStatus::Taken(pid)) => Finding::warning(
+            check,
+            format!("held by the running process {pid}, the results reflect a live datadir"),
+            format!("stop process {pid} before running commands that write to the datadir"),
+        ),
+        Ok(StorageLockStatus::Stale) => Finding::warning(
+            check,
+            "left behind by a process that is no longer running, the node was not shut down \
+             gracefully",
+            "no action needed, the lock is replaced on the next start; check the storage \
+             consistency below",
+        ),
+        Err(err) => Finding::error(
+            check,
+            format!("the lock file could not be read: {err}"),
+            format!("check the permissions of {}", path.display()),
+        ),
+    }
+}
+
+/// Checks the stage checkpoints against each other and the highest header in the static files.
+fn check_stage_checkpoints(stages: &[(StageId, Option<u64>)], headers_tip: u64) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for (stage, block) in stages {
+        if block.is_some_and(|block| block > headers_tip) {
+            findings.push(Finding::error(
+                "checkpoints",
+                format!(
+                    "{stage} checkpoint {} is ahead of the highest header {headers_tip}",
+                    block.unwrap_or_default()
+                ),
+                format!(
+                    "unwind to the highest header with `reth stage unwind to-block {headers_tip}`"
+                ),
+            ));
+        }
+    }
+
+    let finished = stages
+        .iter()
+        .find(|(stage, _)| *stage == StageId::Finish)
+        .and_then(|(_, block)| *block)
+        .unwrap_or_default();
+    let behind = stages
+        .iter()
+        .filter(|(stage, block)| *stage != StageId::Finish && block.unwrap_or_default() < finished)
+        .map(|(stage, _)| stage.to_string())
+        .collect::<Vec<_>>();
+    if !behind.is_empty() {
+        findings.push(Finding::error(
+            "checkpoints",
+            format!("{} are behind the finished block {finished}", behind.join(", ")),
+            "run the lagging stages with `reth stage run`, or resync the node",
+        ));
+    }
+
+    let ahead = stages
+        .iter()
+        .filter(|(_, block)| block.unwrap_or_default() > finished)
+        .map(|(stage, _)| stage.to_string())
+        .collect::<Vec<_>>();
+    if !ahead.is_empty() {
+        findings.push(Finding::warning(
+            "checkpoints",
+            format!("the sync was interrupted, {} are ahead of block {finished}", ahead.join(", ")),
+            "no action needed, the node resumes the sync on the next start",
+        ));
+    }
+
+    if findings.is_empty() {
+        findings.push(Finding::ok("checkpoints", format!("all stages finished block {finished}")));
+    }
+    findings
+}
+
+/// Checks the prune checkpoints against the finished block and the prune configuration.
+fn check_prune_checkpoints(
+    segments: &[(PruneSegment, Option<u64>)],
+    prune_modes: &PruneModes,
+    finished: u64,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for (segment, block) in segments {
+        let Some(block) = *block else { continue };
+
+        if block > finished {
+            findings.push(Finding::error(
+                "prune checkpoints",
+                format!(
+                    "{segment} is pruned up to block {block}, ahead of the finished block \
+                     {finished}"
+                ),
+                "the pruned data can't be restored, resync the node",
+            ));
+        }
+
+        let configured = match segment {
+            PruneSegment::SenderRecovery => prune_modes.sender_recovery.is_some(),
+            PruneSegment::TransactionLookup => prune_modes.transaction_lookup.is_some(),
+            PruneSegment::Receipts => prune_modes.receipts.is_some(),
+            PruneSegment::ContractLogs => !prune_modes.receipts_log_filter.is_empty(),
+            PruneSegment::AccountHistory => prune_modes.account_history.is_some(),
+            PruneSegment::StorageHistory => prune_modes.storage_history.is_some(),
+            PruneSegment::Headers | PruneSegment::Transactions => true,
+        };
+        if !configured {
+            findings.push(Finding::warning(
+                "prune checkpoints",
+                format!(
+                    "{segment} was pruned up to block {block}, but pruning it is not configured"
+                ),
+                "data below the checkpoint stays unavailable, restore the prune configuration in \
+                 reth.toml or resync the node to serve it",
+            ));
+        }
+    }
+
+    if findings.is_empty() {
+        findings.push(Finding::ok("prune checkpoints", "consistent with the prune configuration"));
+    }
+    findings
+}
+
+/// Returns the projected growth in bytes of a datadir of the given size over the given number of
+/// days.
+///
+/// The datadir is assumed to grow at the same rate as it did from genesis up to the synced tip.
+/// The chain time the node is behind the current time is added to the projection.
+fn projected_growth(size: u64, chain_age: u64, behind: u64, days: u64) -> u64 {
+    if chain_age == 0 {
+        return 0
+    }
+    let seconds = behind.saturating_add(days.saturating_mul(24 * 60 * 60));
+    (size as u128 * seconds as u128 / chain_age as u128).try_into().unwrap_or(u64::MAX)
+}
+
+/// Formats a number of bytes with a binary unit.
+fn human_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_prune_types::PruneMode;
+
+    fn stages(blocks: &[(StageId, u64)]) -> Vec<(StageId, Option<u64>)> {
+        blocks.iter().map(|(stage, block)| (*stage, Some(*block))).collect()
+    }
+
+    #[test]
+    fn parse_doctor_command() {
+        let command = Command::parse_from(["reth", "--disk.days", "7"]);
+        assert_eq!(command.disk_days, 7);
+    }
+
+    #[test]
+    fn stage_checkpoints() {
+        let synced =
+            stages(&[(StageId::Headers, 100), (StageId::Execution, 100), (StageId::Finish, 100)]);
+        let findings = check_stage_checkpoints(&synced, 100);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Ok);
+
+        // an interrupted sync is resumed by the node
+        let interrupted =
+            stages(&[(StageId::Headers, 150), (StageId::Execution, 120), (StageId::Finish, 100)]);
+        let findings = check_stage_checkpoints(&interrupted, 150);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Warning);
+
+        // checkpoints ahead of the headers can't be resumed
+        let findings = check_stage_checkpoints(&interrupted, 110);
+        assert_eq!(findings.iter().filter(|f| f.severity == Severity::Error).count(), 2);
+
+        let lagging =
+            stages(&[(StageId::Headers, 100), (StageId::Execution, 90), (StageId::Finish, 100)]);
+        let findings = check_stage_checkpoints(&lagging, 100);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn prune_checkpoints() {
+        let modes =
+            PruneModes { receipts: Some(PruneMode::Distance(10_064)), ..PruneModes::none() };
+        let segments = [(PruneSegment::Receipts, Some(90)), (PruneSegment::AccountHistory, None)];
+        let findings = check_prune_checkpoints(&segments, &modes, 100);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Ok);
+
+        // pruned, but pruning was disabled since
+        let findings = check_prune_checkpoints(&segments, &PruneModes::none(), 100);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Warning);
+
+        let findings = check_prune_checkpoints(&[(PruneSegment::Receipts, Some(120))], &modes, 100);
+        assert_eq!(findings[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn disk_growth() {
+        const DAY: u64 = 24 * 60 * 60;
+        // 100 days of chain history in 100 GiB, synced to the current time
+        assert_eq!(projected_growth(100 << 30, 100 * DAY, 0, 30), 30 << 30);
+        // 10 days behind the current time
+        assert_eq!(projected_growth(100 << 30, 100 * DAY, 10 * DAY, 30), 40 << 30);
+        assert_eq!(projected_growth(100 << 30, 0, 0, 30), 0);
+
+        assert_eq!(human_bytes(1536), "1.5 KiB");
+        assert_eq!(human_bytes(30 << 30), "30.0 GiB");
+    }
+}
@@ -0,0 +1,198 @@
+//! Command that exports the derivation inputs of the blocks of an OP stack chain.
+
+use crate::commands::common::{AccessRights, Environment, EnvironmentArgs};
+use clap::Parser;
+use reth_evm_optimism::{extract_l1_origin, L1Origin};
+use reth_primitives::{hex, BlockNumber, Transaction};
+use reth_provider::{BlockNumReader, BlockRangeIter};
+use std::{fs::File, path::PathBuf};
+use tracing::info;
+
+/// Number of blocks between progress logs.
+const LOG_INTERVAL: u64 = 100_000;
+
+/// `reth export batches` command
+///
+/// Reconstructs the L1 inputs the L2 blocks of a range were derived from, as far as they are
+/// stored locally, and writes them as CSV tables to the output directory:
+///
+/// - `blocks.csv`: the L1 origin of every L2 block, its position in the sequencing epoch of the
+///   origin and the batcher its batch was posted by
+/// - `epochs.csv`: the range of L2 blocks of every sequencing epoch
+/// - `deposits.csv`: the raw deposit transactions of every L2 block, including its L1 info
+///   transaction
+///
+/// The batcher transactions are only posted to L1, so the frames of the batches can't be exported.
+/// Blocks without an L1 info transaction, like the blocks before Bedrock, are skipped.
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    /// The first block of the range
+    #[arg(long, value_name = "NUMBER", default_value_t = 0)]
+    from: BlockNumber,
+
+    /// The last block of the range, defaults to the local tip
+    #[arg(long, value_name = "NUMBER")]
+    to: Option<BlockNumber>,
+
+    /// The directory to write the tables to
+    #[arg(long, value_name = "DIR")]
+    output: PathBuf,
+}
+
+impl Command {
+    /// Execute `export batches` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let Environment { provider_factory, .. } = self.env.init(AccessRights::RO)?;
+        let provider = provider_factory.provider()?;
+
+        let tip = provider.best_block_number()?;
+        let to = self.to.unwrap_or(tip);
+        eyre::ensure!(to <= tip, "block {to} is above the local tip {tip}");
+        eyre::ensure!(self.from <= to, "--from must not be greater than --to");
+
+        reth_fs_util::create_dir_all(&self.output)?;
+        let mut blocks = csv::Writer::from_path(self.output.join("blocks.csv"))?;
+        blocks.write_record([
+            "l2_block_number",
+            "l2_block_hash",
+            "l2_timestamp",
+            "l1_origin_number",
+            "l1_origin_hash",
+            "l1_origin_timestamp",
+            "sequence_number",
+            "batcher",
+            "transactions",
+            "deposits",
+        ])?;
+        let mut deposits = csv::Writer::from_path(self.output.join("deposits.csv"))?;
+        deposits.write_record([
+            "l2_block_number",
+            "transaction_index",
+            "transaction_hash",
+            "source_hash",
+            "from",
+            "mint",
+            "is_system_transaction",
+            "raw",
+        ])?;
+        let mut epochs = EpochsTable::new(csv::Writer::from_path(self.output.join("epochs.csv"))?)?;
+
+        let (mut exported, mut skipped) = (0u64, 0u64);
+        for block in BlockRangeIter::new(&provider, self.from..=to).without_receipts() {
+            let block = block?.block.block;
+            let (hash, block) = (block.hash(), block.unseal());
+            if !block.body.first().is_some_and(|tx| tx.is_deposit()) {
+                skipped += 1;
+                continue
+            }
+            let origin = extract_l1_origin(&block)?;
+
+            let mut deposit_count = 0u64;
+            for (index, tx) in block.body.iter().enumerate() {
+                let Transaction::Deposit(deposit) = &tx.transaction else { continue };
+                deposits.write_record([
+                    block.number.to_string(),
+                    index.to_string(),
+                    tx.hash().to_string(),
+                    deposit.source_hash.to_string(),
+                    deposit.from.to_string(),
+                    deposit.mint.unwrap_or_default().to_string(),
+                    deposit.is_system_transaction.to_string(),
+                    hex::encode_prefixed(tx.envelope_encoded()),
+                ])?;
+                deposit_count += 1;
+            }
+
+            blocks.write_record([
+                block.number.to_string(),
+                hash.to_string(),
+                block.timestamp.to_string(),
+                origin.number.to_string(),
+                origin.hash.to_string(),
+                origin.timestamp.to_string(),
+                origin.sequence_number.to_string(),
+                origin.batcher().to_string(),
+                block.body.len().to_string(),
+                deposit_count.to_string(),
+            ])?;
+            epochs.add_block(block.number, origin)?;
+            exported += 1;
+
+            if block.number % LOG_INTERVAL == 0 && block.number != self.from {
+                info!(target: "reth::cli", block = block.number, to, "Exporting batches");
+            }
+        }
+        blocks.flush()?;
+        deposits.flush()?;
+        epochs.finish()?;
+
+        info!(
+            target: "reth::cli",
+            blocks = exported,
+            skipped,
+            output = %self.output.display(),
+            "Exported derivation inputs"
+        );
+
+        Ok(())
+    }
+}
+
+/// The table of the sequencing epochs, written as the blocks of the epochs are added.
+#[derive(Debug)]
+struct EpochsTable {
+    writer: csv::Writer<File>,
+    /// The L1 origin of the current epoch, and its first and last L2 blocks.
+    current: Option<(L1Origin, BlockNumber, BlockNumber)>,
+}
+
+impl EpochsTable {
+    fn new(mut writer: csv::Writer<File>) -> eyre::Result<Self> {
+        writer.write_record([
+            "l1_origin_number",
+            "l1_origin_hash",
+            "l1_origin_timestamp",
+            "batcher",
+            "first_l2_block",
+            "last_l2_block",
+            "l2_blocks",
+        ])?;
+        Ok(Self { writer, current: None })
+    }
+
+    /// Adds the next L2 block, writing the current epoch if the block starts a new one.
+    fn add_block(&mut self, number: BlockNumber, origin: L1Origin) -> eyre::Result<()> {
+        match &mut self.current {
+            Some((current, _, last)) if current.hash == origin.hash => *last = number,
+            _ => {
+                self.write_current()?;
+                self.current = Some((origin, number, number));
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the last epoch, which may be incomplete.
+    fn finish(mut self) -> eyre::Result<()> {
+        self.write_current()?;
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    fn write_current(&mut self) -> eyre::Result<()> {
+        let Some((origin, first, last)) = self.current.take() else { return Ok(()) };
+        self.writer.write_record([
+            origin.number.to_string(),
+            origin.hash.to_string(),
+            origin.timestamp.to_string(),
+            origin.batcher().to_string(),
+            first.to_string(),
+            last.to_string(),
+            (last - first + 1).to_string(),
+        ])?;
+        Ok(())
+    }
+}
@@ -0,0 +1,386 @@
+//! Command that exports the logs of a range of blocks as CSV tables.
+
+use crate::commands::common::{AccessRights, Environment, EnvironmentArgs};
+use alloy_dyn_abi::{DynSolValue, EventExt};
+use alloy_json_abi::{Event, JsonAbi};
+use clap::Parser;
+use reth_primitives::{hex, Address, BlockHash, BlockNumber, LogData, TxHash, B256};
+use reth_provider::{BlockNumReader, BlockRangeIter, BlockWithReceipts};
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    fs::File,
+    path::{Path, PathBuf},
+};
+use tracing::{info, warn};
+
+/// Number of blocks between progress logs.
+const LOG_INTERVAL: u64 = 100_000;
+
+/// The columns of the table of all logs.
+const LOG_COLUMNS: [&str; 11] = [
+    "block_number",
+    "block_hash",
+    "transaction_hash",
+    "transaction_index",
+    "log_index",
+    "address",
+    "topic0",
+    "topic1",
+    "topic2",
+    "topic3",
+    "data",
+];
+
+/// The columns of the tables of decoded events that precede the event parameters.
+const DECODED_COLUMNS: [&str; 3] = ["block_number", "transaction_hash", "log_index"];
+
+/// `reth export logs` command
+///
+/// Writes the logs of the receipts of a range of blocks to `logs.csv` in the output directory.
+///
+/// With `--abi-dir`, the logs of the contracts with an ABI are also decoded: the logs of each
+/// event are written to `decoded/<address>/<event>.csv`, with a column per event parameter.
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    /// The first block of the range
+    #[arg(long, value_name = "NUMBER", default_value_t = 0)]
+    from: BlockNumber,
+
+    /// The last block of the range, defaults to the local tip
+    #[arg(long, value_name = "NUMBER")]
+    to: Option<BlockNumber>,
+
+    /// Only export the logs of the given contract, can be given multiple times
+    #[arg(long = "address", value_name = "ADDRESS")]
+    addresses: Vec<Address>,
+
+    /// The directory with the ABIs of the contracts whose logs are decoded.
+    ///
+    /// Every file is named after the address of its contract, e.g. `0xdAC1...1ec7.json`, and
+    /// contains either a JSON ABI or a compiler artifact with an `abi` field.
+    #[arg(long, value_name = "DIR")]
+    abi_dir: Option<PathBuf>,
+
+    /// The directory to write the tables to
+    #[arg(long, value_name = "DIR")]
+    output: PathBuf,
+}
+
+impl Command {
+    /// Execute `export logs` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let Environment { provider_factory, .. } = self.env.init(AccessRights::RO)?;
+        let provider = provider_factory.provider()?;
+
+        let tip = provider.best_block_number()?;
+        let to = self.to.unwrap_or(tip);
+        eyre::ensure!(to <= tip, "block {to} is above the local tip {tip}");
+        eyre::ensure!(self.from <= to, "--from must not be greater than --to");
+
+        let contracts = match &self.abi_dir {
+            Some(dir) => load_abis(dir)?,
+            None => HashMap::new(),
+        };
+
+        reth_fs_util::create_dir_all(&self.output)?;
+        let mut logs = csv::Writer::from_path(self.output.join("logs.csv"))?;
+        logs.write_record(LOG_COLUMNS)?;
+        let mut decoded = DecodedTables::new(self.output.join("decoded"), contracts);
+
+        let mut exported = 0u64;
+        for block in BlockRangeIter::new(&provider, self.from..=to) {
+            let BlockWithReceipts { block, receipts } = block?;
+            let Some(receipts) = receipts else {
+                eyre::bail!("the receipts of block {} are pruned", block.number)
+            };
+
+            let mut log_index = 0u64;
+            for (tx_index, (tx, receipt)) in block.body.iter().zip(&receipts).enumerate() {
+                for log in &receipt.logs {
+                    let position = LogPosition {
+                        block_number: block.number,
+                        block_hash: block.hash(),
+                        tx_hash: tx.hash(),
+                        tx_index: tx_index as u64,
+                        log_index,
+                    };
+                    log_index += 1;
+                    if !self.addresses.is_empty() && !self.addresses.contains(&log.address) {
+                        continue
+                    }
+
+                    logs.write_record(position.log_record(log.address, &log.data))?;
+                    decoded.write(&position, log.address, &log.data)?;
+                    exported += 1;
+                }
+            }
+
+            if block.number % LOG_INTERVAL == 0 && block.number != self.from {
+                info!(target: "reth::cli", block = block.number, to, "Exporting logs");
+            }
+        }
+        logs.flush()?;
+        decoded.flush()?;
+
+        if decoded.failed > 0 {
+            warn!(
+                target: "reth::cli",
+                logs = decoded.failed,
+                "Some logs matched an event of their contract's ABI but couldn't be decoded"
+            );
+        }
+        info!(
+            target: "reth::cli",
+            logs = exported,
+            decoded = decoded.decoded,
+            output = %self.output.display(),
+            "Exported logs"
+        );
+
+        Ok(())
+    }
+}
+
+/// The position of a log in the chain.
+#[derive(Debug)]
+struct LogPosition {
+    block_number: BlockNumber,
+    block_hash: BlockHash,
+    tx_hash: TxHash,
+    tx_index: u64,
+    log_index: u64,
+}
+
+impl LogPosition {
+    /// Returns the row of the log in the table of all logs.
+    fn log_record(&self, address: Address, log: &LogData) -> Vec<String> {
+        let topics = log.topics();
+        let mut record = vec![
+            self.block_number.to_string(),
+            self.block_hash.to_string(),
+            self.tx_hash.to_string(),
+            self.tx_index.to_string(),
+            self.log_index.to_string(),
+            address.to_string(),
+        ];
+        record.extend((0..4).map(|i| topics.get(i).map(ToString::to_string).unwrap_or_default()));
+        record.push(log.data.to_string());
+        record
+    }
+}
+
+/// An event of a contract ABI.
+#[derive(Debug)]
+struct ContractEvent {
+    /// The name of the table of the event, which is the event name, followed by the selector for
+    /// overloaded events.
+    table: String,
+    event: Event,
+}
+
+/// The tables of the decoded events, written as their logs are found.
+#[derive(Debug)]
+struct DecodedTables {
+    dir: PathBuf,
+    /// The events of each contract by selector.
+    contracts: HashMap<Address, HashMap<B256, ContractEvent>>,
+    writers: HashMap<(Address, B256), csv::Writer<File>>,
+    /// The number of decoded logs.
+    decoded: u64,
+    /// The number of logs that matched an event but couldn't be decoded.
+    failed: u64,
+}
+
+impl DecodedTables {
+    fn new(dir: PathBuf, contracts: HashMap<Address, HashMap<B256, ContractEvent>>) -> Self {
+        Self { dir, contracts, writers: HashMap::new(), decoded: 0, failed: 0 }
+    }
+
+    /// Decodes the log with the ABI of its contract and writes it to the table of its event.
+    ///
+    /// Logs of contracts without an ABI and of unknown events are skipped.
+    fn write(
+        &mut self,
+        position: &LogPosition,
+        address: Address,
+        log: &LogData,
+    ) -> eyre::Result<()> {
+        let Some(selector) = log.topics().first() else { return Ok(()) };
+        let Some(event) = self.contracts.get(&address).and_then(|events| events.get(selector))
+        else {
+            return Ok(())
+        };
+        let Some(values) = decode_log(&event.event, log) else {
+            self.failed += 1;
+            return Ok(())
+        };
+
+        let writer = match self.writers.entry((address, *selector)) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let dir = self.dir.join(address.to_string());
+                reth_fs_util::create_dir_all(&dir)?;
+                let mut writer = csv::Writer::from_path(dir.join(format!("{}.csv", event.table)))?;
+                writer.write_record(columns(&event.event))?;
+                entry.insert(writer)
+            }
+        };
+        let mut record = vec![
+            position.block_number.to_string(),
+            position.tx_hash.to_string(),
+            position.log_index.to_string(),
+        ];
+        record.extend(values);
+        writer.write_record(record)?;
+        self.decoded += 1;
+
+        Ok(())
+    }
+
+    fn flush(&mut self) -> eyre::Result<()> {
+        for writer in self.writers.values_mut() {
+            writer.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// Loads the events of the ABIs in the directory, by contract address and selector.
+fn load_abis(dir: &Path) -> eyre::Result<HashMap<Address, HashMap<B256, ContractEvent>>> {
+    let mut contracts = HashMap::new();
+    for entry in reth_fs_util::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().map_or(true, |extension| extension != "json") {
+            continue
+        }
+        let Some(address) =
+            path.file_stem().and_then(|stem| stem.to_str()).and_then(|stem| stem.parse().ok())
+        else {
+            warn!(
+                target: "reth::cli",
+                path = %path.display(),
+                "Skipping ABI not named after a contract address"
+            );
+            continue
+        };
+
+        let json: serde_json::Value = serde_json::from_str(&reth_fs_util::read_to_string(&path)?)?;
+        // compiler artifacts have the ABI in their `abi` field
+        let json = json.get("abi").cloned().unwrap_or(json);
+        let abi: JsonAbi = serde_json::from_value(json)
+            .map_err(|err| eyre::eyre!("invalid ABI {}: {err}", path.display()))?;
+        contracts.insert(address, contract_events(abi));
+    }
+    Ok(contracts)
+}
+
+/// Returns the events of the ABI that can be matched to logs, by selector.
+fn contract_events(abi: JsonAbi) -> HashMap<B256, ContractEvent> {
+    let mut events = HashMap::new();
+    for (name, overloads) in abi.events {
+        let overloaded = overloads.len() > 1;
+        // anonymous events have no selector
+        for event in overloads.into_iter().filter(|event| !event.anonymous) {
+            let selector = event.selector();
+            let table = if overloaded {
+                format!("{name}_{}", hex::encode(&selector[..4]))
+            } else {
+                name.clone()
+            };
+            events.insert(selector, ContractEvent { table, event });
+        }
+    }
+    events
+}
+
+/// Returns the columns of the table of the event.
+fn columns(event: &Event) -> Vec<String> {
+    DECODED_COLUMNS
+        .iter()
+        .map(ToString::to_string)
+        .chain(event.inputs.iter().enumerate().map(|(index, input)| {
+            if input.name.is_empty() {
+                format!("arg{index}")
+            } else {
+                input.name.clone()
+            }
+        }))
+        .collect()
+}
+
+/// Decodes the log with the event, and returns the values of its parameters in their declaration
+/// order.
+///
+/// Indexed parameters of dynamic types are only stored as their hash, so the hash is returned.
+fn decode_log(event: &Event, log: &LogData) -> Option<Vec<String>> {
+    let decoded = event.decode_log(log, true).ok()?;
+    let (mut indexed, mut body) = (decoded.indexed.into_iter(), decoded.body.into_iter());
+    event
+        .inputs
+        .iter()
+        .map(|input| if input.indexed { indexed.next() } else { body.next() })
+        .map(|value| value.map(|value| format_value(&value)))
+        .collect()
+}
+
+/// Formats a decoded value for a table cell.
+fn format_value(value: &DynSolValue) -> String {
+    let join =
+        |values: &[DynSolValue]| values.iter().map(format_value).collect::<Vec<_>>().join(",");
+    match value {
+        DynSolValue::Bool(value) => value.to_string(),
+        DynSolValue::Int(value, _) => value.to_string(),
+        DynSolValue::Uint(value, _) => value.to_string(),
+        DynSolValue::FixedBytes(word, size) => hex::encode_prefixed(&word[..*size]),
+        DynSolValue::Address(address) => address.to_string(),
+        DynSolValue::Function(function) => function.to_string(),
+        DynSolValue::Bytes(bytes) => hex::encode_prefixed(bytes),
+        DynSolValue::String(value) => value.clone(),
+        DynSolValue::Array(values) | DynSolValue::FixedArray(values) => {
+            format!("[{}]", join(values))
+        }
+        DynSolValue::Tuple(values) | DynSolValue::CustomStruct { tuple: values, .. } => {
+            format!("({})", join(values))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::U256;
+
+    #[test]
+    fn decodes_event_log() {
+        let event = Event::parse(
+            "event Transfer(address indexed from, address indexed to, uint256 value, string)",
+        )
+        .unwrap();
+        assert_eq!(
+            columns(&event),
+            ["block_number", "transaction_hash", "log_index", "from", "to", "value", "arg3"]
+        );
+
+        let (from, to) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let data = DynSolValue::Tuple(vec![
+            DynSolValue::Uint(U256::from(1000), 256),
+            DynSolValue::String("memo".to_string()),
+        ])
+        .abi_encode_params();
+        let log = LogData::new_unchecked(
+            vec![event.selector(), from.into_word(), to.into_word()],
+            data.into(),
+        );
+        assert_eq!(
+            decode_log(&event, &log),
+            Some(vec![from.to_string(), to.to_string(), "1000".to_string(), "memo".to_string()])
+        );
+
+        // a log of another event with the same selector but other parameters
+        let log = LogData::new_unchecked(vec![event.selector()], Default::default());
+        assert_eq!(decode_log(&event, &log), None);
+    }
+}
@@ -0,0 +1,36 @@
+//! `reth export` command. Exports data stored in the datadir to files for offline processing.
+
+use clap::{Parser, Subcommand};
+
+#[cfg(feature = "optimism")]
+mod batches;
+mod logs;
+
+/// `reth export` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth export` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands {
+    /// Export the logs of a range of blocks as CSV, optionally decoded with the ABIs of their
+    /// contracts.
+    Logs(logs::Command),
+    /// Export the L1 origins and deposits the blocks of an OP stack chain were derived from.
+    #[cfg(feature = "optimism")]
+    Batches(batches::Command),
+}
+
+impl Command {
+    /// Execute `export` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::Logs(command) => command.execute().await,
+            #[cfg(feature = "optimism")]
+            Subcommands::Batches(command) => command.execute().await,
+        }
+    }
+}
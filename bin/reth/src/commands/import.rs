@@ -1,28 +1,39 @@
 //! Command that initializes the node by importing a chain from a file.
 
 use crate::{
-    commands::common::{AccessRights, Environment, EnvironmentArgs},
+    args::utils::block_range_value_parser,
+    commands::{
+        common::{AccessRights, Environment, EnvironmentArgs},
+        dashboard::{self, TuiArgs},
+    },
     macros::block_executor,
     version::SHORT_VERSION,
 };
 use clap::Parser;
 use futures::{Stream, StreamExt};
 use reth_beacon_consensus::EthBeaconConsensus;
+use reth_cli_runner::shutdown_signal;
 use reth_config::Config;
-use reth_consensus::Consensus;
+use reth_consensus::{Consensus, ConsensusError, HeaderConsensusError, PostExecutionInput};
 use reth_db::tables;
-use reth_db_api::{database::Database, transaction::DbTx};
+use reth_db_api::{database::Database, database_metrics::DatabaseMetadata, transaction::DbTx};
 use reth_downloaders::{
+    block_source::BlockSource,
     bodies::bodies::BodiesDownloaderBuilder,
-    file_client::{ChunkedFileReader, FileClient, DEFAULT_BYTE_LEN_CHUNK_CHAIN_FILE},
+    file_client::{FileClient, DEFAULT_BYTE_LEN_CHUNK_CHAIN_FILE},
     headers::reverse_headers::ReverseHeadersDownloaderBuilder,
+    import_format::{ImportFormat, ImportSource},
 };
+use reth_evm::execute::BlockExecutorProvider;
 use reth_network_p2p::{
     bodies::downloader::BodyDownloader,
     headers::downloader::{HeaderDownloader, SyncTarget},
 };
+use reth_node_core::args::ExecutionArgs;
 use reth_node_events::node::NodeEvent;
-use reth_primitives::B256;
+use reth_primitives::{
+    BlockNumber, BlockWithSenders, Header, SealedBlock, SealedHeader, B256, U256,
+};
 use reth_provider::{
     BlockNumReader, ChainSpecProvider, HeaderProvider, ProviderError, ProviderFactory,
     StageCheckpointReader,
@@ -30,9 +41,9 @@ use reth_provider::{
 use reth_prune_types::PruneModes;
 use reth_stages::{prelude::*, Pipeline, StageId, StageSet};
 use reth_static_file::StaticFileProducer;
-use std::{path::PathBuf, sync::Arc};
-use tokio::sync::watch;
-use tracing::{debug, error, info};
+use std::{future::Future, ops::RangeInclusive, path::PathBuf, pin::pin, sync::Arc};
+use tokio::sync::{mpsc, watch};
+use tracing::{debug, error, info, warn};
 
 /// Syncs RLP encoded blocks from a file.
 #[derive(Debug, Parser)]
@@ -48,12 +59,48 @@ pub struct ImportCommand {
     #[arg(long, value_name = "CHUNK_LEN", verbatim_doc_comment)]
     chunk_len: Option<u64>,
 
-    /// The path to a block file for import.
+    /// The format of the import path, detected from its contents by default.
+    ///
+    /// One of: rlp, op-legacy, era1, geth-freezer
+    ///
+    /// OVM block files of OP Mainnet below Bedrock (op-legacy) are imported like with the
+    /// 'import-op' command.
+    #[arg(long, value_name = "FORMAT", verbatim_doc_comment)]
+    format: Option<ImportFormat>,
+
+    /// Number of chunks decoded ahead while the pipeline imports the current one.
+    ///
+    /// Each decoded chunk is held in memory, so this bounds the memory used in addition to the
+    /// chunk being imported.
+    #[arg(long, value_name = "CHUNKS", default_value_t = DEFAULT_DECODE_AHEAD, verbatim_doc_comment)]
+    decode_ahead: usize,
+
+    /// Block range whose bodies are imported even if they don't match their headers, e.g.
+    /// 100-200. Can be given multiple times.
+    ///
+    /// Before a batch is imported, the transactions root, ommers hash and withdrawals root of
+    /// every block are checked against its body, and the import fails on the first mismatch.
+    /// Mismatches within the given ranges are logged as warnings instead.
+    #[arg(
+        long,
+        value_name = "RANGE",
+        value_parser = block_range_value_parser,
+        verbatim_doc_comment
+    )]
+    lenient_bodies: Vec<RangeInclusive<BlockNumber>>,
+
+    #[command(flatten)]
+    execution: ExecutionArgs,
+
+    /// The path to a block file, a directory of era1 archives or a geth datadir for import.
     ///
     /// The online stages (headers and bodies) are replaced by a file import, after which the
     /// remaining stages are executed.
     #[arg(value_name = "IMPORT_PATH", verbatim_doc_comment)]
     path: PathBuf,
+
+    #[command(flatten)]
+    pub(crate) tui: TuiArgs,
 }
 
 impl ImportCommand {
@@ -61,6 +108,40 @@ impl ImportCommand {
     pub async fn execute(self) -> eyre::Result<()> {
         info!(target: "reth::cli", "reth {} starting", SHORT_VERSION);
 
+        // OVM block files are only told apart from RLP block files by the Bedrock block
+        #[cfg(feature = "optimism")]
+        let legacy_below = self.env.chain.fork(reth_primitives::Hardfork::Bedrock).as_block();
+        #[cfg(not(feature = "optimism"))]
+        let legacy_below = None;
+        let format = match self.format {
+            Some(format) => format,
+            None => ImportFormat::detect(&self.path, legacy_below)?,
+        };
+        info!(target: "reth::cli", %format, path = ?self.path, "Importing blocks");
+
+        #[cfg(not(feature = "optimism"))]
+        if format == ImportFormat::OpLegacy {
+            eyre::bail!(
+                "importing OVM blocks of the OP Mainnet legacy chain requires the optimism feature"
+            )
+        }
+        #[cfg(feature = "optimism")]
+        if format == ImportFormat::OpLegacy {
+            if self.execution.trace_to.is_some() {
+                tracing::warn!(target: "reth::cli", "Execution tracing is not supported on optimism, no traces are written");
+            }
+            return crate::commands::import_op::ImportOpCommand::new(
+                self.env,
+                self.chunk_len,
+                self.decode_ahead,
+                self.lenient_bodies,
+                self.path,
+                self.tui,
+            )
+            .execute()
+            .await
+        }
+
         if self.no_state {
             info!(target: "reth::cli", "Disabled stages requiring state");
         }
@@ -70,36 +151,239 @@ impl ImportCommand {
             "Chunking chain import"
         );
 
-        let Environment { provider_factory, config, .. } = self.env.init(AccessRights::RW)?;
+        let Environment { provider_factory, mut config, data_dir } =
+            self.env.init(AccessRights::RW)?;
+        if let Some(size) = self.execution.state_cache_size {
+            config.stages.execution.state_cache_size = Some(size);
+        }
+
+        let mut dashboard = self.tui.spawn(
+            format!("import | {}", self.env.chain.chain),
+            provider_factory.db_ref().clone(),
+            &data_dir,
+            None,
+        )?;
 
         let consensus = Arc::new(EthBeaconConsensus::new(self.env.chain.clone()));
         info!(target: "reth::cli", "Consensus engine initialized");
 
+        let executor = block_executor!(provider_factory.chain_spec());
+        #[cfg(not(feature = "optimism"))]
+        let executor = executor.with_parallel_execution(self.execution.parallel);
+        #[cfg(feature = "optimism")]
+        if self.execution.parallel {
+            tracing::warn!(target: "reth::cli", "Parallel execution is not supported on optimism, executing sequentially");
+        }
+
+        #[cfg(not(feature = "optimism"))]
+        let executor = match &self.execution.trace_to {
+            Some(dir) => {
+                let kind = self.execution.trace_kind;
+                info!(target: "reth::cli", ?dir, %kind, "Writing execution traces");
+                executor.with_tracer(reth_node_ethereum::evm::BlockTracer::new(dir, kind))
+            }
+            None => executor,
+        };
+        #[cfg(feature = "optimism")]
+        if self.execution.trace_to.is_some() {
+            tracing::warn!(target: "reth::cli", "Execution tracing is not supported on optimism, no traces are written");
+        }
+
+        #[cfg(not(feature = "optimism"))]
+        let profiler = self.execution.profile.then(reth_node_ethereum::evm::ExecutionProfiler::new);
+        #[cfg(not(feature = "optimism"))]
+        let executor = match profiler.clone() {
+            Some(profiler) => executor.with_profiler(profiler),
+            None => executor,
+        };
+        #[cfg(feature = "optimism")]
+        if self.execution.profile {
+            tracing::warn!(target: "reth::cli", "Execution profiling is not supported on optimism");
+        }
+
+        #[cfg(not(feature = "optimism"))]
+        let executor = if self.execution.access_lists {
+            executor.with_access_list_recorder(
+                reth_node_ethereum::evm::BlockAccessListRecorder::default(),
+            )
+        } else {
+            executor
+        };
+        #[cfg(feature = "optimism")]
+        if self.execution.access_lists {
+            tracing::warn!(target: "reth::cli", "Recording access lists is not supported on optimism");
+        }
+
+        #[cfg(not(feature = "optimism"))]
+        let executor = match self.execution.slow_block_threshold {
+            Some(threshold) => {
+                executor.with_slow_block_detector(reth_node_ethereum::evm::SlowBlockDetector::new(
+                    threshold,
+                    data_dir.slow_blocks(),
+                    self.execution.slow_block_quota_bytes(),
+                ))
+            }
+            None => executor,
+        };
+        #[cfg(feature = "optimism")]
+        if self.execution.slow_block_threshold.is_some() {
+            tracing::warn!(target: "reth::cli", "Capturing slow blocks is not supported on optimism");
+        }
+
         // open file
-        let mut reader = ChunkedFileReader::new(&self.path, self.chunk_len).await?;
+        let mut source = ImportSource::open(format, &self.path, self.chunk_len).await?;
+
+        // on shutdown, the pipeline is stopped once its current batch is committed
+        let stop = async {
+            tokio::select! {
+                res = shutdown_signal() => res?,
+                _ = dashboard::closed(dashboard.as_mut()) => {}
+            }
+            Ok::<_, eyre::Report>(())
+        };
+        let ImportStats {
+            decoded_blocks: total_decoded_blocks,
+            decoded_txns: total_decoded_txns,
+            interrupted,
+        } = import_blocks(
+            &mut source,
+            &config,
+            provider_factory.clone(),
+            &consensus,
+            self.no_state,
+            executor,
+            self.decode_ahead,
+            &self.lenient_bodies,
+            stop,
+        )
+        .await?;
 
-        let mut total_decoded_blocks = 0;
-        let mut total_decoded_txns = 0;
+        // the summary is printed to stdout
+        drop(dashboard);
+
+        let provider = provider_factory.provider()?;
 
-        while let Some(file_client) = reader.next_chunk::<FileClient>().await? {
-            // create a new FileClient from chunk read from file
+        if interrupted {
+            let block =
+                provider.get_stage_checkpoint(StageId::Finish)?.unwrap_or_default().block_number;
             info!(target: "reth::cli",
-                "Importing chain file chunk"
+                block,
+                "Import interrupted, all blocks up to the given block are imported. Run the same command again to resume the import"
             );
+            return Ok(())
+        }
 
-            let tip = file_client.tip().ok_or(eyre::eyre!("file client has no tip"))?;
-            info!(target: "reth::cli", "Chain file chunk read");
+        let total_imported_blocks = provider.tx_ref().entries::<tables::HeaderNumbers>()?;
+        let total_imported_txns = provider.tx_ref().entries::<tables::TransactionHashNumbers>()?;
 
-            total_decoded_blocks += file_client.headers_len();
-            total_decoded_txns += file_client.total_transactions();
+        if total_decoded_blocks != total_imported_blocks ||
+            total_decoded_txns != total_imported_txns
+        {
+            error!(target: "reth::cli",
+                total_decoded_blocks,
+                total_imported_blocks,
+                total_decoded_txns,
+                total_imported_txns,
+                "Chain was partially imported"
+            );
+        }
+
+        info!(target: "reth::cli",
+            total_imported_blocks,
+            total_imported_txns,
+            "Chain file imported"
+        );
+
+        #[cfg(not(feature = "optimism"))]
+        if let Some(profiler) = profiler {
+            info!(target: "reth::cli", "\n{}", profiler.profile());
+        }
+
+        Ok(())
+    }
+}
+
+/// Default number of batches decoded ahead of the pipeline by [`import_blocks`].
+pub const DEFAULT_DECODE_AHEAD: usize = 1;
+
+/// The result of importing the blocks of a [`BlockSource`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ImportStats {
+    /// The number of blocks read from the source.
+    pub decoded_blocks: usize,
+    /// The number of transactions read from the source.
+    pub decoded_txns: usize,
+    /// Whether the import was stopped before the source was exhausted.
+    pub interrupted: bool,
+}
+
+/// Imports all blocks of the given source, running the import pipeline for each batch of blocks.
+///
+/// Decoding and importing overlap: the pipeline of a batch runs on its own task while up to
+/// `decode_ahead` following batches are read from the source, handed over through a bounded
+/// channel. Within a batch, the stages still run one after the other.
+///
+/// Before a batch is imported, its bodies are checked against their headers. Mismatches of
+/// blocks within `lenient_bodies` are logged, and these blocks are imported as they are. Any other
+/// mismatch fails the import.
+///
+/// Once `stop` resolves, the pipeline is stopped after its current batch is committed.
+#[allow(clippy::too_many_arguments)]
+pub async fn import_blocks<DB, S, C, E>(
+    source: &mut S,
+    config: &Config,
+    provider_factory: ProviderFactory<DB>,
+    consensus: &Arc<C>,
+    disable_exec: bool,
+    executor: E,
+    decode_ahead: usize,
+    lenient_bodies: &[RangeInclusive<BlockNumber>],
+    stop: impl Future<Output = eyre::Result<()>>,
+) -> eyre::Result<ImportStats>
+where
+    DB: Database + DatabaseMetadata + Clone + Unpin + 'static,
+    S: BlockSource,
+    C: Consensus + 'static,
+    E: BlockExecutorProvider,
+{
+    let (batches_tx, mut batches_rx) = mpsc::channel(decode_ahead.max(1));
+    let consensus = Arc::new(LenientBodiesConsensus {
+        inner: consensus.clone(),
+        lenient_bodies: lenient_bodies.to_vec(),
+    });
+
+    // Reads batches until the source is exhausted, waiting while `decode_ahead` batches are
+    // queued. Dropping the receiver ends it.
+    let decode = async move {
+        while let Some(file_client) = source.next_batch().await? {
+            debug!(target: "reth::cli", blocks = file_client.headers_len(), "Decoded batch of blocks");
+            validate_bodies(&file_client, lenient_bodies)?;
+            if batches_tx.send(file_client).await.is_err() {
+                break
+            }
+        }
+        Ok::<_, eyre::Report>(())
+    };
+
+    let import = async move {
+        let mut stats = ImportStats::default();
+        let mut stop = pin!(stop);
+
+        while let Some(file_client) = batches_rx.recv().await {
+            info!(target: "reth::cli", "Importing batch of blocks");
+
+            let tip = file_client.tip().ok_or(eyre::eyre!("file client has no tip"))?;
+            stats.decoded_blocks += file_client.headers_len();
+            stats.decoded_txns += file_client.total_transactions();
 
             let (mut pipeline, events) = build_import_pipeline(
-                &config,
+                config,
                 provider_factory.clone(),
                 &consensus,
                 Arc::new(file_client),
                 StaticFileProducer::new(provider_factory.clone(), PruneModes::default()),
-                self.no_state,
+                disable_exec,
+                executor.clone(),
             )
             .await?;
 
@@ -107,10 +391,10 @@ impl ImportCommand {
             pipeline.set_tip(tip);
             debug!(target: "reth::cli", ?tip, "Tip manually set");
 
-            let provider = provider_factory.provider()?;
-
-            let latest_block_number =
-                provider.get_stage_checkpoint(StageId::Finish)?.map(|ch| ch.block_number);
+            let latest_block_number = provider_factory
+                .provider()?
+                .get_stage_checkpoint(StageId::Finish)?
+                .map(|ch| ch.block_number);
             tokio::spawn(reth_node_events::node::handle_events(
                 None,
                 latest_block_number,
@@ -118,38 +402,98 @@ impl ImportCommand {
                 provider_factory.db_ref().clone(),
             ));
 
-            // Run pipeline
+            // Run pipeline on its own task, so the next batches are decoded meanwhile
             info!(target: "reth::cli", "Starting sync pipeline");
+            let mut run = tokio::spawn(async move { pipeline.run().await });
             tokio::select! {
-                res = pipeline.run() => res?,
-                _ = tokio::signal::ctrl_c() => {},
+                res = &mut run => res??,
+                res = &mut stop => {
+                    run.abort();
+                    res?;
+                    stats.interrupted = true;
+                    break
+                }
             }
         }
 
-        let provider = provider_factory.provider()?;
-
-        let total_imported_blocks = provider.tx_ref().entries::<tables::HeaderNumbers>()?;
-        let total_imported_txns = provider.tx_ref().entries::<tables::TransactionHashNumbers>()?;
+        Ok::<_, eyre::Report>(stats)
+    };
 
-        if total_decoded_blocks != total_imported_blocks ||
-            total_decoded_txns != total_imported_txns
-        {
-            error!(target: "reth::cli",
-                total_decoded_blocks,
-                total_imported_blocks,
-                total_decoded_txns,
-                total_imported_txns,
-                "Chain was partially imported"
-            );
+    let (mut decode, mut import) = (pin!(decode), pin!(import));
+    tokio::select! {
+        res = &mut import => res,
+        res = &mut decode => {
+            // the source is exhausted, import the queued batches
+            res?;
+            import.await
         }
+    }
+}
 
-        info!(target: "reth::cli",
-            total_imported_blocks,
-            total_imported_txns,
-            "Chain file imported"
+/// Checks the bodies of the batch against their headers, see [`import_blocks`].
+fn validate_bodies(
+    file_client: &FileClient,
+    lenient_bodies: &[RangeInclusive<BlockNumber>],
+) -> eyre::Result<()> {
+    for (number, err) in file_client.validate_bodies()? {
+        if !lenient_bodies.iter().any(|range| range.contains(&number)) {
+            eyre::bail!("body of block {number} doesn't match its header: {err}")
+        }
+        warn!(target: "reth::cli",
+            number,
+            %err,
+            "Importing block whose body doesn't match its header"
         );
+    }
+    Ok(())
+}
 
-        Ok(())
+/// Consensus of the import pipeline, skipping the pre-execution checks of the blocks whose bodies
+/// are imported even if they don't match their headers.
+#[derive(Debug)]
+struct LenientBodiesConsensus<C> {
+    inner: Arc<C>,
+    lenient_bodies: Vec<RangeInclusive<BlockNumber>>,
+}
+
+impl<C: Consensus> Consensus for LenientBodiesConsensus<C> {
+    fn validate_header(&self, header: &SealedHeader) -> Result<(), ConsensusError> {
+        self.inner.validate_header(header)
+    }
+
+    fn validate_header_against_parent(
+        &self,
+        header: &SealedHeader,
+        parent: &SealedHeader,
+    ) -> Result<(), ConsensusError> {
+        self.inner.validate_header_against_parent(header, parent)
+    }
+
+    fn validate_header_range(&self, headers: &[SealedHeader]) -> Result<(), HeaderConsensusError> {
+        self.inner.validate_header_range(headers)
+    }
+
+    fn validate_header_with_total_difficulty(
+        &self,
+        header: &Header,
+        total_difficulty: U256,
+    ) -> Result<(), ConsensusError> {
+        self.inner.validate_header_with_total_difficulty(header, total_difficulty)
+    }
+
+    fn validate_block_pre_execution(&self, block: &SealedBlock) -> Result<(), ConsensusError> {
+        if self.lenient_bodies.iter().any(|range| range.contains(&block.number)) {
+            return Ok(())
+        }
+        self.inner.validate_block_pre_execution(block)
+    }
+
+    fn validate_block_post_execution(
+        &self,
+        block: &BlockWithSenders,
+        input: PostExecutionInput<'_>,
+    ) -> Result<(), ConsensusError> {
+        self.inner.validate_block_post_execution(block, input)
     }
 }
 
@@ -157,17 +501,19 @@ impl ImportCommand {
 ///
 /// If configured to execute, all stages will run. Otherwise, only stages that don't require state
 /// will run.
-pub async fn build_import_pipeline<DB, C>(
+pub async fn build_import_pipeline<DB, C, E>(
     config: &Config,
     provider_factory: ProviderFactory<DB>,
     consensus: &Arc<C>,
     file_client: Arc<FileClient>,
     static_file_producer: StaticFileProducer<DB>,
     disable_exec: bool,
+    executor: E,
 ) -> eyre::Result<(Pipeline<DB>, impl Stream<Item = NodeEvent>)>
 where
     DB: Database + Clone + Unpin + 'static,
     C: Consensus + 'static,
+    E: BlockExecutorProvider,
 {
     if !file_client.has_canonical_blocks() {
         eyre::bail!("unable to import non canonical blocks");
@@ -197,7 +543,6 @@ where
         .expect("failed to set download range");
 
     let (tip_tx, tip_rx) = watch::channel(B256::ZERO);
-    let executor = block_executor!(provider_factory.chain_spec());
 
     let max_block = file_client.max_block().unwrap_or(0);
 
@@ -1,27 +1,50 @@
 //! Command that initializes the node by importing OP Mainnet chain segment below Bedrock, from a
 //! file.
+//!
+//! The import stops at the Bedrock transition block. If a state dump at the transition block is
+//! given, the state is initialized right after, so that the node can continue syncing post-Bedrock
+//! blocks from the network.
 
 use crate::{
+    args::utils::block_range_value_parser,
     commands::{
         common::{AccessRights, Environment, EnvironmentArgs},
-        import::build_import_pipeline,
+        dashboard::{self, TuiArgs},
+        import::{import_blocks, DEFAULT_DECODE_AHEAD},
+        init_state::init_at_state,
     },
+    macros::block_executor,
     version::SHORT_VERSION,
 };
 use clap::Parser;
-use reth_consensus::noop::NoopConsensus;
+use reth_cli_runner::shutdown_signal;
 use reth_db::tables;
-use reth_db_api::transaction::DbTx;
-use reth_downloaders::file_client::{
-    ChunkedFileReader, FileClient, DEFAULT_BYTE_LEN_CHUNK_CHAIN_FILE,
+use reth_db_api::{database::Database, models::StoredLegacyTxMeta, transaction::DbTx};
+use reth_downloaders::{
+    block_source::BlockSource,
+    file_client::{
+        ChunkedFileReader, DecodeMode, FileClient, FileClientError,
+        DEFAULT_BYTE_LEN_CHUNK_CHAIN_FILE,
+    },
+    file_codec_ovm_tx_meta::{TxMetaEntry, TxMetaFileReader},
+};
+use reth_optimism_consensus::OpLegacyConsensus;
+use reth_optimism_primitives::bedrock_import::{is_dup_tx, BLOCK_NUMS_REPLAYED_TX};
+use reth_primitives::{BlockNumber, Hardfork};
+use reth_provider::{
+    BlockNumReader, ChainSpecProvider, LegacyTxMetaWriter, ProviderFactory, StageCheckpointReader,
+    TransactionsProvider,
 };
-use reth_optimism_primitives::bedrock_import::is_dup_tx;
-use reth_provider::StageCheckpointReader;
-use reth_prune_types::PruneModes;
 use reth_stages::StageId;
-use reth_static_file::StaticFileProducer;
-use std::{path::PathBuf, sync::Arc};
-use tracing::{debug, error, info};
+use std::{
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::{debug, error, info, warn};
+
+/// Number of legacy transaction metadata entries inserted per database transaction.
+const TX_META_COMMIT_THRESHOLD: usize = 100_000;
 
 /// Syncs RLP encoded blocks from a file.
 #[derive(Debug, Parser)]
@@ -33,15 +56,84 @@ pub struct ImportOpCommand {
     #[arg(long, value_name = "CHUNK_LEN", verbatim_doc_comment)]
     chunk_len: Option<u64>,
 
+    /// Number of chunks decoded ahead while the pipeline imports the current one.
+    ///
+    /// Each decoded chunk is held in memory, so this bounds the memory used in addition to the
+    /// chunk being imported.
+    #[arg(long, value_name = "CHUNKS", default_value_t = DEFAULT_DECODE_AHEAD, verbatim_doc_comment)]
+    decode_ahead: usize,
+
+    /// JSONL file with the state dump at the Bedrock transition block.
+    ///
+    /// If the import reaches the Bedrock transition block, the state is initialized from this
+    /// file, after which the node continues syncing post-Bedrock blocks from the network. See
+    /// 'init-state' command for the file format.
+    #[arg(long, value_name = "STATE_DUMP_FILE", verbatim_doc_comment)]
+    state: Option<PathBuf>,
+
+    /// Abort on the first malformed block of the file.
+    ///
+    /// The error contains the byte offset and number of the block, the malformed field and a
+    /// hexdump of the bytes around it. By default, malformed blocks are skipped with a warning
+    /// and the import stops below the first of them.
+    #[arg(long, verbatim_doc_comment)]
+    strict: bool,
+
+    /// File with the size and metadata of the legacy transactions, as exported by l2geth.
+    ///
+    /// The metadata is not part of the exported blocks. It's stored for the imported
+    /// transactions once the blocks are imported, and served by 'reth_getLegacyTxMeta'. The
+    /// file holds one RLP entry '[tx_hash, size, [timestamp, rest]]' per transaction.
+    #[arg(long, value_name = "TX_META_FILE", verbatim_doc_comment)]
+    tx_meta: Option<PathBuf>,
+
+    /// Block range whose bodies are imported even if they don't match their headers, e.g.
+    /// 100-200. Can be given multiple times.
+    ///
+    /// The blocks with replayed transactions are always included, since their duplicate
+    /// transactions are filtered out.
+    #[arg(
+        long,
+        value_name = "RANGE",
+        value_parser = block_range_value_parser,
+        verbatim_doc_comment
+    )]
+    lenient_bodies: Vec<RangeInclusive<BlockNumber>>,
+
     /// The path to a block file for import.
     ///
     /// The online stages (headers and bodies) are replaced by a file import, after which the
     /// remaining stages are executed.
     #[arg(value_name = "IMPORT_PATH", verbatim_doc_comment)]
     path: PathBuf,
+
+    #[command(flatten)]
+    pub(crate) tui: TuiArgs,
 }
 
 impl ImportOpCommand {
+    /// Creates the command importing the OVM block file at `path`, without a state dump.
+    pub(crate) const fn new(
+        env: EnvironmentArgs,
+        chunk_len: Option<u64>,
+        decode_ahead: usize,
+        lenient_bodies: Vec<RangeInclusive<BlockNumber>>,
+        path: PathBuf,
+        tui: TuiArgs,
+    ) -> Self {
+        Self {
+            env,
+            chunk_len,
+            decode_ahead,
+            state: None,
+            strict: false,
+            tx_meta: None,
+            lenient_bodies,
+            path,
+            tui,
+        }
+    }
+
     /// Execute `import` command
     pub async fn execute(self) -> eyre::Result<()> {
         info!(target: "reth::cli", "reth {} starting", SHORT_VERSION);
@@ -55,75 +147,86 @@ impl ImportOpCommand {
             "Chunking chain import"
         );
 
-        let Environment { provider_factory, config, .. } = self.env.init(AccessRights::RW)?;
+        let Environment { provider_factory, config, data_dir } = self.env.init(AccessRights::RW)?;
 
-        // we use noop here because we expect the inputs to be valid
-        let consensus = Arc::new(NoopConsensus::default());
-
-        // open file
-        let mut reader = ChunkedFileReader::new(&self.path, self.chunk_len).await?;
+        let mut dashboard = self.tui.spawn(
+            format!("import-op | {}", self.env.chain.chain),
+            provider_factory.db_ref().clone(),
+            &data_dir,
+            None,
+        )?;
 
-        let mut total_decoded_blocks = 0;
-        let mut total_decoded_txns = 0;
-        let mut total_filtered_out_dup_txns = 0;
+        // legacy OVM blocks carry no valid seal, so they are checked against the pre-Bedrock rules
+        let consensus = Arc::new(OpLegacyConsensus::new(provider_factory.chain_spec()));
 
-        while let Some(mut file_client) = reader.next_chunk::<FileClient>().await? {
-            // create a new FileClient from chunk read from file
+        // the legacy chain segment ends at the Bedrock transition block, anything above must be
+        // synced with the post-Bedrock rules
+        let bedrock_block = provider_factory.chain_spec().fork(Hardfork::Bedrock).as_block();
+        if let Some(bedrock_block) = bedrock_block {
             info!(target: "reth::cli",
-                "Importing chain file chunk"
+                bedrock_block,
+                "Importing legacy chain up to Bedrock transition"
             );
+        }
 
-            let tip = file_client.tip().ok_or(eyre::eyre!("file client has no tip"))?;
-            info!(target: "reth::cli", "Chain file chunk read");
-
-            total_decoded_blocks += file_client.headers_len();
-            total_decoded_txns += file_client.total_transactions();
-
-            for (block_number, body) in file_client.bodies_iter_mut() {
-                body.transactions.retain(|_| {
-                    if is_dup_tx(block_number) {
-                        total_filtered_out_dup_txns += 1;
-                        return false
-                    }
-                    true
-                })
-            }
+        // open file
+        let decode_mode = if self.strict { DecodeMode::Strict } else { DecodeMode::Lenient };
+        let mut source = OvmBlockSource {
+            reader: ChunkedFileReader::new(&self.path, self.chunk_len)
+                .await?
+                .with_decode_mode(decode_mode),
+            bedrock_block,
+            filtered_dup_txns: 0,
+            post_bedrock_blocks: 0,
+        };
 
-            let (mut pipeline, events) = build_import_pipeline(
-                &config,
-                provider_factory.clone(),
-                &consensus,
-                Arc::new(file_client),
-                StaticFileProducer::new(provider_factory.clone(), PruneModes::default()),
-                true,
-            )
-            .await?;
-
-            // override the tip
-            pipeline.set_tip(tip);
-            debug!(target: "reth::cli", ?tip, "Tip manually set");
-
-            let provider = provider_factory.provider()?;
-
-            let latest_block_number =
-                provider.get_stage_checkpoint(StageId::Finish)?.map(|ch| ch.block_number);
-            tokio::spawn(reth_node_events::node::handle_events(
-                None,
-                latest_block_number,
-                events,
-                provider_factory.db_ref().clone(),
-            ));
-
-            // Run pipeline
-            info!(target: "reth::cli", "Starting sync pipeline");
+        // on shutdown, the pipeline is stopped once its current batch is committed
+        let stop = async {
             tokio::select! {
-                res = pipeline.run() => res?,
-                _ = tokio::signal::ctrl_c() => {},
+                res = shutdown_signal() => res?,
+                _ = dashboard::closed(dashboard.as_mut()) => {}
             }
-        }
+            Ok::<_, eyre::Report>(())
+        };
+        // the blocks with replayed transactions no longer match their transactions root once the
+        // duplicates are filtered out
+        let mut lenient_bodies = self.lenient_bodies.clone();
+        lenient_bodies.extend(BLOCK_NUMS_REPLAYED_TX.map(|number| number..=number));
+
+        let stats = import_blocks(
+            &mut source,
+            &config,
+            provider_factory.clone(),
+            &consensus,
+            true,
+            block_executor!(provider_factory.chain_spec()),
+            self.decode_ahead,
+            &lenient_bodies,
+            stop,
+        )
+        .await?;
+
+        let total_decoded_blocks = stats.decoded_blocks;
+        let total_filtered_out_dup_txns = source.filtered_dup_txns;
+        let total_decoded_txns = stats.decoded_txns + total_filtered_out_dup_txns;
+        let total_post_bedrock_blocks = source.post_bedrock_blocks;
+        let total_malformed_blocks = source.reader.malformed_blocks();
+
+        // the summary is printed to stdout
+        drop(dashboard);
 
         let provider = provider_factory.provider()?;
 
+        if stats.interrupted {
+            let block =
+                provider.get_stage_checkpoint(StageId::Finish)?.unwrap_or_default().block_number;
+            info!(target: "reth::cli",
+                block,
+                "Import interrupted, all blocks up to the given block are imported. Run the same command again to resume the import"
+            );
+            return Ok(())
+        }
+
         let total_imported_blocks = provider.tx_ref().entries::<tables::HeaderNumbers>()?;
         let total_imported_txns = provider.tx_ref().entries::<tables::TransactionHashNumbers>()?;
 
@@ -146,9 +249,141 @@ impl ImportOpCommand {
             total_decoded_blocks,
             total_decoded_txns,
             total_filtered_out_dup_txns,
+            total_post_bedrock_blocks,
+            total_malformed_blocks,
             "Chain file imported"
         );
+        if total_malformed_blocks > 0 {
+            warn!(target: "reth::cli",
+                total_malformed_blocks,
+                "Import stopped below a malformed block, run with --strict for a hexdump of the first malformed block"
+            );
+        }
+
+        let last_block = provider.last_block_number()?;
+        drop(provider);
+
+        if let Some(tx_meta) = &self.tx_meta {
+            let (total_imported_tx_meta, total_unknown_tx_meta) =
+                import_tx_meta(&provider_factory, tx_meta).await?;
+            info!(target: "reth::cli",
+                total_imported_tx_meta,
+                total_unknown_tx_meta,
+                "Legacy transaction metadata imported"
+            );
+        }
+
+        let reached_bedrock = bedrock_block.is_some_and(|bedrock_block| last_block == bedrock_block);
+        match (reached_bedrock, self.state) {
+            (true, Some(state)) => {
+                let hash = init_at_state(state, provider_factory, config.stages.etl)?;
+                info!(target: "reth::cli",
+                    block = last_block,
+                    ?hash,
+                    "Initialized state at Bedrock transition, post-Bedrock blocks are synced from the network"
+                );
+            }
+            (true, None) => {
+                info!(target: "reth::cli",
+                    block = last_block,
+                    "Legacy chain imported up to Bedrock transition, initialize the state at the transition with 'init-state' to continue syncing"
+                );
+            }
+            (false, Some(_)) => {
+                warn!(target: "reth::cli",
+                    last_block,
+                    ?bedrock_block,
+                    "Import did not reach Bedrock transition, state is not initialized"
+                );
+            }
+            (false, None) => {}
+        }
 
         Ok(())
     }
 }
+
+/// Stores the legacy transaction metadata of the file at `path` for the imported transactions.
+///
+/// Returns the number of stored entries, and the number of entries of transactions that are not
+/// imported.
+async fn import_tx_meta<DB: Database>(
+    provider_factory: &ProviderFactory<DB>,
+    path: &Path,
+) -> eyre::Result<(usize, usize)> {
+    let mut reader = TxMetaFileReader::open(path).await?;
+    let mut provider = provider_factory.provider_rw()?;
+
+    let (mut imported, mut unknown, mut uncommitted) = (0, 0, 0);
+    while let Some(TxMetaEntry { tx_hash, size, meta }) = reader.next_entry().await? {
+        let Some(id) = provider.transaction_id(tx_hash)? else {
+            unknown += 1;
+            continue
+        };
+        provider.insert_legacy_tx_meta(
+            id,
+            StoredLegacyTxMeta { size, timestamp: meta.timestamp, rest: meta.rest },
+        )?;
+        imported += 1;
+
+        uncommitted += 1;
+        if uncommitted == TX_META_COMMIT_THRESHOLD {
+            provider.commit()?;
+            provider = provider_factory.provider_rw()?;
+            uncommitted = 0;
+        }
+    }
+    provider.commit()?;
+
+    Ok((imported, unknown))
+}
+
+/// Reads the legacy chain segment below Bedrock from a file.
+///
+/// Blocks above the Bedrock transition block are skipped, they are synced from the network.
+/// Duplicate transactions of the legacy chain are filtered out.
+#[derive(Debug)]
+struct OvmBlockSource {
+    reader: ChunkedFileReader,
+    bedrock_block: Option<BlockNumber>,
+    /// The number of filtered out duplicate transactions.
+    filtered_dup_txns: usize,
+    /// The number of skipped blocks above the Bedrock transition block.
+    post_bedrock_blocks: usize,
+}
+
+impl BlockSource for OvmBlockSource {
+    type Error = FileClientError;
+
+    async fn next_batch(&mut self) -> Result<Option<FileClient>, Self::Error> {
+        let Some(mut file_client) = self.reader.next_blocks().await? else { return Ok(None) };
+
+        if let Some(bedrock_block) = self.bedrock_block {
+            let post_bedrock_blocks = file_client.truncate_above(bedrock_block);
+            if post_bedrock_blocks > 0 {
+                warn!(target: "reth::cli",
+                    post_bedrock_blocks,
+                    bedrock_block,
+                    "Skipping blocks above Bedrock transition, they are synced from the network"
+                );
+                self.post_bedrock_blocks += post_bedrock_blocks;
+            }
+            if file_client.headers_len() == 0 {
+                return Ok(None)
+            }
+        }
+
+        let filtered_dup_txns = &mut self.filtered_dup_txns;
+        for (block_number, body) in file_client.bodies_iter_mut() {
+            body.transactions.retain(|_| {
+                if is_dup_tx(block_number) {
+                    *filtered_dup_txns += 1;
+                    return false
+                }
+                true
+            })
+        }
+
+        Ok(Some(file_client))
+    }
+}
@@ -4,16 +4,20 @@
 use crate::commands::common::{AccessRights, Environment, EnvironmentArgs};
 use clap::Parser;
 use reth_db::tables;
-use reth_db_api::{database::Database, transaction::DbTx};
+use reth_db_api::{
+    database::Database,
+    models::StoredLegacyL1Fee,
+    transaction::{DbTx, DbTxMut},
+};
 use reth_downloaders::{
     file_client::{ChunkedFileReader, DEFAULT_BYTE_LEN_CHUNK_CHAIN_FILE},
     file_codec_ovm_receipt::HackReceiptFileCodec,
-    receipt_file_client::ReceiptFileClient,
+    receipt_file_client::{ReceiptFileClient, ReceiptL1Fee},
 };
 use reth_execution_types::ExecutionOutcome;
 use reth_node_core::version::SHORT_VERSION;
 use reth_optimism_primitives::bedrock_import::is_dup_tx;
-use reth_primitives::{Receipts, StaticFileSegment};
+use reth_primitives::{BlockNumber, Receipts, StaticFileSegment};
 use reth_provider::{
     OriginalValuesKnown, ProviderFactory, StageCheckpointReader, StateWriter,
     StaticFileProviderFactory, StaticFileWriter, StatsReader,
@@ -124,6 +128,7 @@ where
             mut receipts,
             first_block,
             total_receipts: total_receipts_chunk,
+            mut l1_fees,
             ..
         } = file_client;
 
@@ -132,6 +137,13 @@ where
 
         total_filtered_out_dup_txns += filter(first_block, &mut receipts);
 
+        // drop the L1 fees of the receipts that were filtered out
+        for (fees, receipts) in l1_fees.iter_mut().zip(receipts.iter()) {
+            if receipts.is_empty() {
+                fees.clear();
+            }
+        }
+
         info!(target: "reth::cli",
             first_receipts_block=?first_block,
             total_receipts_chunk,
@@ -141,6 +153,8 @@ where
         // We're reusing receipt writing code internal to
         // `ExecutionOutcome::write_to_storage`, so we just use a default empty
         // `BundleState`.
+        write_l1_fees(&tx, first_block, l1_fees)?;
+
         let execution_outcome =
             ExecutionOutcome::new(Default::default(), receipts, first_block, Default::default());
 
@@ -207,3 +221,30 @@ where
 
     Ok(())
 }
+
+/// Writes the L1 fees recorded in the receipts of the blocks starting at `first_block`, served
+/// by `eth_getTransactionReceipt` for the legacy range.
+fn write_l1_fees<TX: DbTxMut + DbTx>(
+    tx: &TX,
+    first_block: BlockNumber,
+    l1_fees: Vec<Vec<Option<ReceiptL1Fee>>>,
+) -> eyre::Result<()> {
+    for (number, fees) in (first_block..).zip(l1_fees) {
+        if fees.iter().all(Option::is_none) {
+            continue
+        }
+        let body = tx
+            .get::<tables::BlockBodyIndices>(number)?
+            .ok_or_else(|| eyre::eyre!("block {number} must be imported before its receipts"))?;
+        for (tx_num, fee) in body.tx_num_range().zip(fees) {
+            let Some(ReceiptL1Fee { l1_gas_price, l1_gas_used, l1_fee, fee_scalar }) = fee else {
+                continue
+            };
+            tx.put::<tables::LegacyL1Fees>(
+                tx_num,
+                StoredLegacyL1Fee { l1_gas_price, l1_gas_used, l1_fee, fee_scalar },
+            )?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,342 @@
+//! `reth install-service` command, installs a service that runs the node with the given arguments.
+
+use crate::cli::Cli;
+use clap::{Parser, ValueEnum};
+use eyre::{bail, WrapErr};
+use reth_node_core::dirs::logs_dir;
+use std::{
+    fmt, iter,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// The time the service manager waits for the node to stop, in addition to its shutdown timeout.
+const STOP_TIMEOUT_MARGIN: Duration = Duration::from_secs(30);
+
+/// `reth install-service` command
+#[derive(Debug, Parser)]
+pub struct Command<Ext: clap::Args + fmt::Debug> {
+    /// The service manager to install the service for.
+    ///
+    /// Defaults to launchd on macOS and systemd otherwise.
+    #[arg(long, value_enum, verbatim_doc_comment)]
+    manager: Option<ServiceManager>,
+
+    /// The name of the service.
+    #[arg(long, default_value = "reth")]
+    name: String,
+
+    /// The user that runs the node, only used by systemd.
+    ///
+    /// Defaults to the user that invoked the command, also when it's run with sudo.
+    #[arg(long, verbatim_doc_comment)]
+    user: Option<String>,
+
+    /// The file to write the service definition to, or `-` for stdout.
+    ///
+    /// Defaults to:
+    /// - systemd: `/etc/systemd/system/<NAME>.service`
+    /// - launchd: `$HOME/Library/LaunchAgents/xyz.paradigm.<NAME>.plist`
+    #[arg(long, short, value_name = "FILE", verbatim_doc_comment)]
+    output: Option<PathBuf>,
+
+    /// Overwrite the file if it already exists.
+    #[arg(long)]
+    force: bool,
+
+    /// The arguments of `reth node` the service runs with, e.g. `-- --chain sepolia --http`.
+    #[arg(last = true, value_name = "NODE_ARGS")]
+    node_args: Vec<String>,
+
+    #[arg(skip)]
+    _ext: PhantomData<Ext>,
+}
+
+impl<Ext: clap::Args + fmt::Debug> Command<Ext> {
+    /// Execute `install-service` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        // the service would fail on every start if the arguments are invalid
+        let cli = Cli::<Ext>::try_parse_from(
+            ["reth", "node"].into_iter().map(String::from).chain(self.node_args.iter().cloned()),
+        )
+        .map_err(|err| eyre::eyre!("invalid node arguments: {err}"))?;
+
+        let service = Service {
+            name: self.name.clone(),
+            program: std::env::current_exe().wrap_err("could not determine the reth binary")?,
+            args: iter::once("node".to_string()).chain(self.node_args).collect(),
+            working_dir: std::env::current_dir()
+                .wrap_err("could not determine the working directory")?,
+            user: self
+                .user
+                .or_else(|| std::env::var("SUDO_USER").ok())
+                .or_else(|| std::env::var("USER").ok()),
+            stop_timeout: cli.shutdown_timeout() + STOP_TIMEOUT_MARGIN,
+        };
+
+        let manager = self.manager.unwrap_or_default();
+        let definition = match manager {
+            ServiceManager::Systemd => service.systemd_unit(),
+            ServiceManager::Launchd => service.launchd_plist(logs_dir()),
+        };
+
+        let path = match self.output {
+            Some(path) if path.as_os_str() == "-" => {
+                print!("{definition}");
+                return Ok(())
+            }
+            Some(path) => path,
+            None => manager.default_path(&service.name)?,
+        };
+
+        if path.exists() && !self.force {
+            bail!("{} already exists, use --force to overwrite it", path.display());
+        }
+        if let Some(parent) = path.parent() {
+            reth_fs_util::create_dir_all(parent)?;
+        }
+        reth_fs_util::write(&path, definition)?;
+
+        println!("Installed the {manager} service at {}", path.display());
+        println!("Start it with:");
+        match manager {
+            ServiceManager::Systemd => {
+                println!("  sudo systemctl daemon-reload");
+                println!("  sudo systemctl enable --now {}", service.name);
+            }
+            ServiceManager::Launchd => println!("  launchctl load -w {}", path.display()),
+        }
+        Ok(())
+    }
+}
+
+/// A service manager the service can be installed for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ServiceManager {
+    /// systemd, the service manager of most Linux distributions.
+    Systemd,
+    /// launchd, the service manager of macOS.
+    Launchd,
+}
+
+impl ServiceManager {
+    /// Returns the path the service definition is installed at by default.
+    fn default_path(&self, name: &str) -> eyre::Result<PathBuf> {
+        match self {
+            Self::Systemd => Ok(Path::new("/etc/systemd/system").join(format!("{name}.service"))),
+            Self::Launchd => {
+                let Some(home) = std::env::var_os("HOME") else {
+                    bail!("could not determine the home directory, set the file with --output")
+                };
+                let file = format!("{}.plist", launchd_label(name));
+                Ok(Path::new(&home).join("Library/LaunchAgents").join(file))
+            }
+        }
+    }
+}
+
+impl Default for ServiceManager {
+    fn default() -> Self {
+        if cfg!(target_os = "macos") {
+            Self::Launchd
+        } else {
+            Self::Systemd
+        }
+    }
+}
+
+impl fmt::Display for ServiceManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Systemd => f.write_str("systemd"),
+            Self::Launchd => f.write_str("launchd"),
+        }
+    }
+}
+
+/// A service that runs reth.
+#[derive(Debug)]
+struct Service {
+    name: String,
+    program: PathBuf,
+    args: Vec<String>,
+    working_dir: PathBuf,
+    user: Option<String>,
+    /// How long the service manager waits for the node to shut down before killing it.
+    stop_timeout: Duration,
+}
+
+impl Service {
+    /// Returns the systemd unit of the service.
+    ///
+    /// The node is stopped with `SIGINT`, after which it commits its progress before exiting.
+    fn systemd_unit(&self) -> String {
+        let exec_start = iter::once(self.program.display().to_string())
+            .chain(self.args.iter().cloned())
+            .map(|arg| systemd_quote(&arg))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let user = self.user.as_ref().map(|user| format!("User={user}\n")).unwrap_or_default();
+
+        format!(
+            "[Unit]
+Description=reth Ethereum execution client ({name})
+Wants=network-online.target
+After=network-online.target
+
+[Service]
+Type=simple
+{user}WorkingDirectory={working_dir}
+ExecStart={exec_start}
+Restart=on-failure
+RestartSec=5
+KillSignal=SIGINT
+TimeoutStopSec={stop_timeout}
+LimitNOFILE=1048576
+
+[Install]
+WantedBy=multi-user.target
+",
+            name = self.name,
+            // paths are not unquoted by systemd, only specifiers are expanded
+            working_dir = self.working_dir.display().to_string().replace('%', "%%"),
+            stop_timeout = self.stop_timeout.as_secs(),
+        )
+    }
+
+    /// Returns the launchd property list of the service.
+    ///
+    /// The output of the node is written to the given logs directory, if any.
+    fn launchd_plist(&self, logs_dir: Option<PathBuf>) -> String {
+        let args = iter::once(self.program.display().to_string())
+            .chain(self.args.iter().cloned())
+            .map(|arg| format!("        <string>{}</string>\n", xml_escape(&arg)))
+            .collect::<String>();
+        let logs = logs_dir
+            .map(|dir| {
+                let log = |stream: &str| {
+                    xml_escape(
+                        &dir.join(format!("{}.{stream}.log", self.name)).display().to_string(),
+                    )
+                };
+                format!(
+                    "    <key>StandardOutPath</key>
+    <string>{}</string>
+    <key>StandardErrorPath</key>
+    <string>{}</string>
+",
+                    log("out"),
+                    log("err")
+                )
+            })
+            .unwrap_or_default();
+
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{label}</string>
+    <key>ProgramArguments</key>
+    <array>
+{args}    </array>
+    <key>WorkingDirectory</key>
+    <string>{working_dir}</string>
+    <key>RunAtLoad</key>
+    <true/>
+    <key>KeepAlive</key>
+    <dict>
+        <key>SuccessfulExit</key>
+        <false/>
+    </dict>
+    <key>ExitTimeOut</key>
+    <integer>{stop_timeout}</integer>
+{logs}</dict>
+</plist>
+"#,
+            label = xml_escape(&launchd_label(&self.name)),
+            working_dir = xml_escape(&self.working_dir.display().to_string()),
+            stop_timeout = self.stop_timeout.as_secs(),
+        )
+    }
+}
+
+/// Returns the launchd label of the service with the given name.
+fn launchd_label(name: &str) -> String {
+    format!("xyz.paradigm.{name}")
+}
+
+/// Quotes an argument of a systemd command line.
+///
+/// Specifiers (`%`) and variables (`$`) are escaped, so the argument is passed as is.
+fn systemd_quote(arg: &str) -> String {
+    let escaped =
+        arg.replace('\\', "\\\\").replace('"', "\\\"").replace('%', "%%").replace('$', "$$");
+    if escaped.is_empty() || escaped.contains(char::is_whitespace) || escaped != arg {
+        format!("\"{escaped}\"")
+    } else {
+        escaped
+    }
+}
+
+/// Escapes the special characters of an XML string.
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service() -> Service {
+        Service {
+            name: "reth".to_string(),
+            program: PathBuf::from("/usr/local/bin/reth"),
+            args: ["node", "--chain", "sepolia", "--datadir", "/data/my reth"]
+                .map(String::from)
+                .to_vec(),
+            working_dir: PathBuf::from("/home/reth"),
+            user: Some("reth".to_string()),
+            stop_timeout: Duration::from_secs(90),
+        }
+    }
+
+    #[test]
+    fn systemd_unit() {
+        let unit = service().systemd_unit();
+        assert!(unit.contains(
+            "ExecStart=/usr/local/bin/reth node --chain sepolia --datadir \"/data/my reth\"\n"
+        ));
+        assert!(unit.contains("User=reth\nWorkingDirectory=/home/reth\n"));
+        assert!(unit.contains("TimeoutStopSec=90\n"));
+    }
+
+    #[test]
+    fn launchd_plist() {
+        let plist = service().launchd_plist(Some(PathBuf::from("/Users/reth/Library/Logs/reth")));
+        assert!(plist.contains("<string>xyz.paradigm.reth</string>"));
+        assert!(plist.contains("        <string>/data/my reth</string>\n"));
+        assert!(plist.contains("<string>/Users/reth/Library/Logs/reth/reth.err.log</string>"));
+        assert!(plist.contains("<integer>90</integer>"));
+    }
+
+    #[test]
+    fn quote_systemd_args() {
+        assert_eq!(systemd_quote("--http"), "--http");
+        assert_eq!(systemd_quote(""), "\"\"");
+        assert_eq!(systemd_quote("100%"), "\"100%%\"");
+        assert_eq!(systemd_quote("say \"hi\""), "\"say \\\"hi\\\"\"");
+    }
+
+    #[test]
+    fn escape_xml() {
+        assert_eq!(xml_escape("a<b>&\"c'"), "a&lt;b&gt;&amp;&quot;c&apos;");
+    }
+}
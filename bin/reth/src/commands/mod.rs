@@ -1,20 +1,33 @@
 //! This contains all of the `reth` commands
 
+pub mod chainspec;
+pub mod cold_storage;
 pub mod config_cmd;
+pub mod dashboard;
+pub mod datadir;
 pub mod db;
 pub mod debug_cmd;
+pub mod dev;
+pub mod doctor;
 pub mod dump_genesis;
+pub mod export;
 pub mod import;
+#[cfg(feature = "optimism")]
 pub mod import_op;
 pub mod import_receipts_op;
 
 pub mod init_cmd;
 pub mod init_state;
+pub mod install_service;
 
 pub mod node;
 pub mod p2p;
 pub mod recover;
 pub mod stage;
+pub mod state;
+pub mod stats;
 pub mod test_vectors;
+pub mod unwind;
+pub mod verify;
 
 pub mod common;
@@ -1,18 +1,30 @@
 //! Main node command for launching a node
 
-use crate::args::{
-    utils::{chain_help, chain_value_parser, parse_socket_address, SUPPORTED_CHAINS},
-    DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, NetworkArgs, PayloadBuilderArgs, PruningArgs,
-    RpcServerArgs, TxPoolArgs,
+use crate::{
+    args::{
+        utils::{chain_help, chain_value_parser, parse_socket_address, SUPPORTED_CHAINS},
+        AlertArgs, DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, ExecutionArgs,
+        HardforkOverrideArgs, HeaderSyncArgs, MemoryArgs, NetworkArgs, PayloadBuilderArgs,
+        PruningArgs, PublisherArgs, ResourceArgs, RpcServerArgs, TxPoolArgs,
+    },
+    commands::dashboard::{self, TuiArgs},
 };
-use clap::{value_parser, Args, Parser};
+use clap::{builder::ArgPredicate, value_parser, Args, Parser};
 use reth_chainspec::ChainSpec;
 use reth_cli_runner::CliContext;
 use reth_db::{init_db, DatabaseEnv};
 use reth_node_builder::{NodeBuilder, WithLaunchContext};
-use reth_node_core::{node_config::NodeConfig, version};
+use reth_node_core::{
+    crash_report,
+    layout::{check_layout_version, init_layout_version},
+    node_config::NodeConfig,
+    version,
+};
+use reth_primitives::BlockNumber;
 use std::{ffi::OsString, fmt, future::Future, net::SocketAddr, path::PathBuf, sync::Arc};
 
+pub(crate) mod read_only;
+
 /// Start the node
 #[derive(Debug, Parser)]
 pub struct NodeCommand<Ext: clap::Args + fmt::Debug = NoArgs> {
@@ -29,11 +41,30 @@ pub struct NodeCommand<Ext: clap::Args + fmt::Debug = NoArgs> {
         long_help = chain_help(),
         default_value = SUPPORTED_CHAINS[0],
         default_value_if("dev", "true", "dev"),
+        default_value_if("fork_url", ArgPredicate::IsPresent, "dev"),
         value_parser = chain_value_parser,
         required = false,
     )]
     pub chain: Arc<ChainSpec>,
 
+    /// The chain specification file to reload when the node receives SIGHUP or an
+    /// `admin_reloadChainSpec` request.
+    ///
+    /// Usually the same file that is passed to `--chain`. Changed hardfork activations are applied
+    /// to the running node if they are in the future of the current head, any other change is
+    /// rejected.
+    #[arg(long = "chain.reload", value_name = "FILE")]
+    pub chain_reload: Option<PathBuf>,
+
+    /// Halt canonicalization on a reorg of more than this many canonical blocks.
+    ///
+    /// The halt is persisted and raises a critical alert. The node keeps serving the chain as it
+    /// was before the reorg, until the operator resumes canonicalization with
+    /// `reth unwind --force`. Protects archive and indexer deployments from long-range
+    /// attacks or a misconfigured consensus client.
+    #[arg(long, value_name = "BLOCKS")]
+    pub max_reorg_depth: Option<u64>,
+
     /// Enable Prometheus metrics.
     ///
     /// The metrics will be served at the given interface and port.
@@ -63,6 +94,21 @@ pub struct NodeCommand<Ext: clap::Args + fmt::Debug = NoArgs> {
     #[arg(long, conflicts_with = "instance", global = true)]
     pub with_unused_ports: bool,
 
+    /// Open the database read-only and only serve RPC from it.
+    ///
+    /// No network, transaction pool, pipeline or Engine API is started, the node serves the chain
+    /// as it is in the datadir, e.g. a snapshot or the result of `import-op`.
+    #[arg(long, conflicts_with_all = ["dev", "tui"])]
+    pub read_only: bool,
+
+    /// Serve the chain as if this block was its tip, requires `--read-only`.
+    ///
+    /// Later blocks, transactions and receipts aren't served and the latest state is the state
+    /// after the block, reconstructed from the state history. Useful for reproducible research
+    /// environments. The state history of the block must not be pruned.
+    #[arg(long, value_name = "BLOCK", requires = "read_only")]
+    pub pin_block: Option<BlockNumber>,
+
     /// All datadir related arguments
     #[command(flatten)]
     pub datadir: DatadirArgs,
@@ -87,6 +133,10 @@ pub struct NodeCommand<Ext: clap::Args + fmt::Debug = NoArgs> {
     #[command(flatten)]
     pub debug: DebugArgs,
 
+    /// All header sync related arguments with --header-sync prefix
+    #[command(flatten)]
+    pub header_sync: HeaderSyncArgs,
+
     /// All database related arguments
     #[command(flatten)]
     pub db: DatabaseArgs,
@@ -99,6 +149,34 @@ pub struct NodeCommand<Ext: clap::Args + fmt::Debug = NoArgs> {
     #[command(flatten)]
     pub pruning: PruningArgs,
 
+    /// All block execution related arguments with --execution prefix
+    #[command(flatten)]
+    pub execution: ExecutionArgs,
+
+    /// All memory budget related arguments with --memory prefix
+    #[command(flatten)]
+    pub memory: MemoryArgs,
+
+    /// All resource limit related arguments with --resources prefix
+    #[command(flatten)]
+    pub resources: ResourceArgs,
+
+    /// All hardfork override related arguments with --override prefix
+    #[command(flatten)]
+    pub overrides: HardforkOverrideArgs,
+
+    /// All health alert related arguments with --alert prefix
+    #[command(flatten)]
+    pub alerts: AlertArgs,
+
+    /// All block publisher related arguments with --publisher prefix
+    #[command(flatten)]
+    pub publisher: PublisherArgs,
+
+    /// Live dashboard arguments
+    #[command(flatten)]
+    pub tui: TuiArgs,
+
     /// Additional cli arguments
     #[command(flatten, next_help_heading = "Extension")]
     pub ext: Ext,
@@ -136,25 +214,42 @@ impl<Ext: clap::Args + fmt::Debug> NodeCommand<Ext> {
             datadir,
             config,
             chain,
+            chain_reload,
+            max_reorg_depth,
             metrics,
             instance,
             with_unused_ports,
+            read_only,
+            pin_block,
             network,
             rpc,
             txpool,
             builder,
             debug,
+            header_sync,
             db,
             dev,
             pruning,
+            execution,
+            memory,
+            resources,
+            overrides,
+            alerts,
+            publisher,
+            tui,
             ext,
         } = self;
 
+        // apply hardfork activation overrides before anything derives state from the chain spec
+        let chain = dev.fund_accounts(overrides.apply(chain)?);
+
         // set up node config
         let mut node_config = NodeConfig {
             datadir,
             config,
             chain,
+            chain_reload,
+            max_reorg_depth,
             metrics,
             instance,
             network,
@@ -162,20 +257,55 @@ impl<Ext: clap::Args + fmt::Debug> NodeCommand<Ext> {
             txpool,
             builder,
             debug,
+            header_sync,
             db,
             dev,
             pruning,
+            execution,
+            memory,
+            resources,
+            alerts,
+            publisher,
         };
 
         // Register the prometheus recorder before creating the database,
         // because database init needs it to register metrics.
-        let _ = node_config.install_prometheus_recorder()?;
+        let prometheus_handle = node_config.install_prometheus_recorder()?;
 
         let data_dir = node_config.datadir();
+        if read_only {
+            check_layout_version(&data_dir)?;
+            if with_unused_ports {
+                node_config = node_config.with_unused_ports();
+            } else {
+                node_config.adjust_instance_ports();
+            }
+            return read_only::launch(node_config, pin_block, ctx.task_executor).await
+        }
+
+        // refuse datadirs with an outdated layout before anything is written to them
+        init_layout_version(&data_dir)?;
         let db_path = data_dir.db();
 
+        let limits = node_config.resources.limits();
+        if limits.is_limited() {
+            tracing::info!(target: "reth::cli", cpus = ?limits.cpus, memory = ?limits.memory, "Detected resource limits");
+        }
+        // read ahead if the database fits into the memory limit, unless set with --db.readahead
+        let readahead = db.readahead.or_else(|| limits.db_readahead(&db_path));
+        let db_args = db.database_args().with_readahead(readahead);
+
         tracing::info!(target: "reth::cli", path = ?db_path, "Opening database");
-        let database = Arc::new(init_db(db_path.clone(), self.db.database_args())?.with_metrics());
+        let database = Arc::new(init_db(db_path.clone(), db_args)?.with_metrics());
+        crash_report::set_datadir(&data_dir, node_config.chain.clone());
+        crash_report::set_database(database.clone());
+
+        let mut dashboard = tui.spawn(
+            format!("node | {}", node_config.chain.chain),
+            database.clone(),
+            &data_dir,
+            Some(prometheus_handle),
+        )?;
 
         if with_unused_ports {
             node_config = node_config.with_unused_ports();
@@ -185,7 +315,13 @@ impl<Ext: clap::Args + fmt::Debug> NodeCommand<Ext> {
             .with_database(database)
             .with_launch_context(ctx.task_executor);
 
-        launcher(builder, ext).await
+        tokio::select! {
+            res = launcher(builder, ext) => res,
+            _ = dashboard::closed(dashboard.as_mut()) => {
+                tracing::info!(target: "reth::cli", "Dashboard closed, shutting down");
+                Ok(())
+            }
+        }
     }
 }
 
@@ -320,6 +456,18 @@ mod tests {
         assert!(cmd.dev.dev);
     }
 
+    #[test]
+    #[cfg(not(feature = "optimism"))] // dev mode not yet supported in op-reth
+    fn parse_fork() {
+        let cmd =
+            NodeCommand::<NoArgs>::parse_from(["reth", "--fork-url", "http://localhost:8545"]);
+        assert_eq!(cmd.chain.chain, reth_chainspec::DEV.chain);
+        assert!(cmd.rpc.http);
+        assert!(cmd.network.discovery.disable_discovery);
+        assert!(cmd.dev.dev);
+        assert_eq!(cmd.dev.fork_url.as_deref(), Some("http://localhost:8545"));
+    }
+
     #[test]
     fn parse_instance() {
         let mut cmd = NodeCommand::<NoArgs>::parse_from(["reth"]);
@@ -385,4 +533,45 @@ mod tests {
         // make sure the ipc path is not the default
         assert_ne!(cmd.rpc.ipcpath, String::from("/tmp/reth.ipc"));
     }
+
+    #[test]
+    fn read_only_conflicts_with_dev() {
+        let cmd = NodeCommand::try_parse_args_from(["reth", "--read-only"]).unwrap();
+        assert!(cmd.read_only);
+
+        let err = NodeCommand::try_parse_args_from(["reth", "--read-only", "--dev"]).unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    fn pin_block_requires_read_only() {
+        let cmd = NodeCommand::try_parse_args_from(["reth", "--read-only", "--pin-block", "100"])
+            .unwrap();
+        assert_eq!(cmd.pin_block, Some(100));
+
+        let err = NodeCommand::try_parse_args_from(["reth", "--pin-block", "100"]).unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn parse_tui() {
+        let cmd = NodeCommand::try_parse_args_from(["reth"]).unwrap();
+        assert!(!cmd.tui.tui);
+
+        let cmd = NodeCommand::try_parse_args_from(["reth", "--tui"]).unwrap();
+        assert!(cmd.tui.tui);
+        assert!(cmd.tui.log_buffer.is_none());
+
+        let err = NodeCommand::try_parse_args_from(["reth", "--tui", "--read-only"]).unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    fn parse_max_reorg_depth() {
+        let cmd = NodeCommand::try_parse_args_from(["reth"]).unwrap();
+        assert_eq!(cmd.max_reorg_depth, None);
+
+        let cmd = NodeCommand::try_parse_args_from(["reth", "--max-reorg-depth", "64"]).unwrap();
+        assert_eq!(cmd.max_reorg_depth, Some(64));
+    }
 }
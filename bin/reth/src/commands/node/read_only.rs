@@ -0,0 +1,111 @@
+//! Serving the RPC of a node from a datadir that is opened read-only.
+
+use eyre::WrapErr;
+use reth_blockchain_tree::noop::NoopBlockchainTree;
+use reth_db::{open_db_read_only, DatabaseEnv};
+use reth_node_core::node_config::NodeConfig;
+use reth_primitives::BlockNumber;
+use reth_provider::{
+    providers::{BlockchainProvider, StaticFileProvider},
+    BlockHashReader, ProviderFactory, StaticFileProviderFactory,
+};
+use reth_rpc_builder::{config::RethRpcServerConfig, RpcModuleBuilder, RpcServerHandle};
+use reth_tasks::TaskExecutor;
+use std::sync::Arc;
+use tracing::info;
+
+/// Opens the database and static files of the node read-only and serves the configured RPC
+/// modules from them until the node is shut down.
+///
+/// No network, transaction pool, pipeline or Engine API is started. The node serves the chain as
+/// it was when the datadir was opened, e.g. a snapshot or the result of `import-op`, or up to the
+/// pinned block.
+pub(crate) async fn launch(
+    config: NodeConfig,
+    pin_block: Option<BlockNumber>,
+    executor: TaskExecutor,
+) -> eyre::Result<()> {
+    let (_provider, handle) = serve(config, pin_block, executor).await?;
+
+    // the servers stop once the handle is dropped, which happens when the node shuts down and
+    // drops this future
+    std::future::pending::<()>().await;
+    drop(handle);
+    Ok(())
+}
+
+/// Opens the storage of the node read-only and starts the configured RPC servers on top of it.
+///
+/// If a block is pinned, the chain is served as if it was the tip.
+///
+/// Returns the provider over the storage and the handle of the RPC servers, which stop once the
+/// handle is dropped.
+pub(crate) async fn serve(
+    config: NodeConfig,
+    pin_block: Option<BlockNumber>,
+    executor: TaskExecutor,
+) -> eyre::Result<(BlockchainProvider<Arc<DatabaseEnv>>, RpcServerHandle)> {
+    let data_dir = config.datadir();
+    let (db_path, sf_path) = (data_dir.db(), data_dir.static_files());
+
+    info!(target: "reth::cli", ?db_path, ?sf_path, "Opening storage read-only");
+    let db = Arc::new(open_db_read_only(&db_path, config.db.database_args())?.with_metrics());
+    let static_file_provider = StaticFileProvider::read_only(sf_path)?
+        .with_read_only_dirs(data_dir.read_only_static_files().to_vec())?;
+    let mut provider_factory = ProviderFactory::new(db, config.chain.clone(), static_file_provider);
+
+    let genesis_hash = provider_factory.block_hash(0)?;
+    if genesis_hash != Some(config.chain.genesis_hash()) {
+        eyre::bail!(
+            "the datadir is not initialized for chain {} (expected genesis {}, found {:?})",
+            config.chain.chain,
+            config.chain.genesis_hash(),
+            genesis_hash
+        )
+    }
+
+    if let Some(block) = pin_block {
+        provider_factory = provider_factory
+            .pinned_at(block)
+            .wrap_err_with(|| format!("failed to pin the chain to block {block}"))?;
+        info!(target: "reth::cli", block, "Serving the chain pinned to a historical block");
+    }
+
+    let prometheus_handle = config.install_prometheus_recorder()?;
+    config
+        .start_metrics_endpoint(
+            prometheus_handle,
+            provider_factory.db_ref().clone(),
+            provider_factory.static_file_provider(),
+            executor.clone(),
+        )
+        .await?;
+
+    let blockchain_db =
+        BlockchainProvider::new(provider_factory, Arc::new(NoopBlockchainTree::default()))?;
+
+    #[cfg(not(feature = "optimism"))]
+    let evm_config = reth_node_ethereum::EthEvmConfig::default().with_chain_rules(&config.chain);
+    #[cfg(feature = "optimism")]
+    let evm_config = reth_node_optimism::OptimismEvmConfig::default();
+
+    let modules = RpcModuleBuilder::default()
+        .with_provider(blockchain_db.clone())
+        .with_noop_pool()
+        .with_noop_network()
+        .with_executor(executor)
+        .with_events(blockchain_db.clone())
+        .with_evm_config(evm_config)
+        .build(config.rpc.transport_rpc_module_config());
+
+    let handle = modules.start_server(config.rpc.rpc_server_config()).await?;
+    info!(
+        target: "reth::cli",
+        http = ?handle.http_local_addr(),
+        ws = ?handle.ws_local_addr(),
+        ipc = ?handle.ipc_endpoint(),
+        "Serving RPC from read-only datadir"
+    );
+
+    Ok((blockchain_db, handle))
+}
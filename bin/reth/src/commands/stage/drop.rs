@@ -65,6 +65,7 @@ impl Command {
                 tx.clear::<tables::Headers>()?;
                 tx.clear::<tables::HeaderTerminalDifficulties>()?;
                 tx.clear::<tables::HeaderNumbers>()?;
+                tx.clear::<tables::HeaderAccumulators>()?;
                 tx.put::<tables::StageCheckpoints>(
                     StageId::Headers.to_string(),
                     Default::default(),
@@ -78,6 +79,8 @@ impl Command {
                 tx.clear::<tables::BlockOmmers>()?;
                 tx.clear::<tables::BlockWithdrawals>()?;
                 tx.clear::<tables::BlockRequests>()?;
+                tx.clear::<tables::LegacyTxMeta>()?;
+                tx.clear::<tables::LegacyL1Fees>()?;
                 tx.put::<tables::StageCheckpoints>(
                     StageId::Bodies.to_string(),
                     Default::default(),
@@ -97,7 +100,9 @@ impl Command {
                 tx.clear::<tables::AccountChangeSets>()?;
                 tx.clear::<tables::StorageChangeSets>()?;
                 tx.clear::<tables::Bytecodes>()?;
+                tx.clear::<tables::BytecodeReferences>()?;
                 tx.clear::<tables::Receipts>()?;
+                tx.clear::<tables::BlockAccessLists>()?;
                 tx.put::<tables::StageCheckpoints>(
                     StageId::Execution.to_string(),
                     Default::default(),
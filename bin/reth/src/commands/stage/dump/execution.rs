@@ -145,6 +145,7 @@ async fn unwind_and_copy<DB: Database>(
         .update(|tx| tx.import_dupsort::<tables::PlainStorageState, _>(&unwind_inner_tx))??;
     output_db.update(|tx| tx.import_table::<tables::PlainAccountState, _>(&unwind_inner_tx))??;
     output_db.update(|tx| tx.import_table::<tables::Bytecodes, _>(&unwind_inner_tx))??;
+    output_db.update(|tx| tx.import_table::<tables::BytecodeReferences, _>(&unwind_inner_tx))??;
 
     Ok(())
 }
@@ -1,12 +1,12 @@
 //! `reth stage` command
 
+use crate::commands::unwind;
 use clap::{Parser, Subcommand};
 use reth_cli_runner::CliContext;
 
 pub mod drop;
 pub mod dump;
 pub mod run;
-pub mod unwind;
 
 /// `reth stage` command
 #[derive(Debug, Parser)]
@@ -30,6 +30,8 @@ pub enum Subcommands {
     /// Dumps a stage from a range into a new database.
     Dump(dump::Command),
     /// Unwinds a certain block range, deleting it from the database.
+    ///
+    /// Same as `reth unwind`.
     Unwind(unwind::Command),
 }
 
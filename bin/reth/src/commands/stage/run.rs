@@ -216,7 +216,9 @@ impl Command {
                     Box::new(IndexAccountHistoryStage::new(
                         config.stages.index_account_history,
                         etl_config,
-                        prune_modes.account_history,
+                        prune_modes
+                            .account_history
+                            .filter(|_| prune_modes.history_address_allowlist.is_empty()),
                     )),
                     None,
                 ),
@@ -224,7 +226,9 @@ impl Command {
                     Box::new(IndexStorageHistoryStage::new(
                         config.stages.index_storage_history,
                         etl_config,
-                        prune_modes.storage_history,
+                        prune_modes
+                            .storage_history
+                            .filter(|_| prune_modes.history_address_allowlist.is_empty()),
                     )),
                     None,
                 ),
@@ -0,0 +1,95 @@
+//! Command that reads an account and its storage from the datadir.
+
+use crate::commands::common::{AccessRights, Environment, EnvironmentArgs};
+use clap::Parser;
+use reth_primitives::{Address, BlockNumber, Bytes, StorageKey, B256, U256};
+use reth_provider::{AccountReader, BlockNumReader, StateProvider};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// `reth state get` command
+///
+/// Reads the balance, nonce, code and storage slots of an account at a stored block, directly from
+/// the datadir. Historical state is read through the history indexes, so it is only available for
+/// blocks that have not been pruned.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The address of the account.
+    address: Address,
+
+    /// The block to read the state at, defaults to the local tip.
+    #[arg(long, value_name = "NUMBER")]
+    block: Option<BlockNumber>,
+
+    /// A storage slot to read, can be passed multiple times.
+    #[arg(long = "slot", value_name = "SLOT")]
+    slots: Vec<StorageKey>,
+
+    /// Also output the bytecode of the account.
+    #[arg(long)]
+    code: bool,
+
+    #[command(flatten)]
+    env: EnvironmentArgs,
+}
+
+impl Command {
+    /// Execute `state get` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let Environment { provider_factory, .. } = self.env.init(AccessRights::RO)?;
+
+        let tip = provider_factory.best_block_number()?;
+        let block = self.block.unwrap_or(tip);
+        eyre::ensure!(block <= tip, "block {block} is above the local tip {tip}");
+
+        let state = provider_factory.history_by_block_number(block)?;
+        let account = match state.basic_account(self.address)? {
+            Some(account) => {
+                let code = if self.code {
+                    state.account_code(self.address)?.map(|code| code.original_bytes())
+                } else {
+                    None
+                };
+                Some(AccountState {
+                    balance: account.balance,
+                    nonce: account.nonce,
+                    code_hash: account.get_bytecode_hash(),
+                    code,
+                })
+            }
+            None => None,
+        };
+
+        let mut storage = BTreeMap::new();
+        for slot in self.slots {
+            let value = state.storage(self.address, slot)?.unwrap_or_default();
+            storage.insert(slot, value);
+        }
+
+        let output = Output { address: self.address, block, account, storage };
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        Ok(())
+    }
+}
+
+/// The state of an account at a block.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Output {
+    address: Address,
+    block: BlockNumber,
+    /// `None` if the account doesn't exist at the block.
+    account: Option<AccountState>,
+    storage: BTreeMap<StorageKey, U256>,
+}
+
+/// The fields of an existing account.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AccountState {
+    balance: U256,
+    nonce: u64,
+    code_hash: B256,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<Bytes>,
+}
@@ -0,0 +1,28 @@
+//! `reth state` command. Inspects the state stored in the datadir.
+
+use clap::{Parser, Subcommand};
+
+mod get;
+
+/// `reth state` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth state` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands {
+    /// Get an account and its storage slots at a block.
+    Get(get::Command),
+}
+
+impl Command {
+    /// Execute `state` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::Get(command) => command.execute().await,
+        }
+    }
+}
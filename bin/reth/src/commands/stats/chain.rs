@@ -0,0 +1,298 @@
+//! Command that reports statistics of a range of stored blocks.
+
+use crate::commands::common::{AccessRights, Environment, EnvironmentArgs};
+use clap::Parser;
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use reth_primitives::{Address, BlockNumber, SealedBlockWithSenders};
+use reth_provider::{BlockNumReader, BlockRangeIter};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use tracing::info;
+
+/// Number of blocks scanned by default, up to `--to`.
+const DEFAULT_RANGE_LEN: u64 = 10_000;
+
+/// Number of blocks between progress logs.
+const LOG_INTERVAL: u64 = 100_000;
+
+/// `reth stats chain` command
+///
+/// Scans the stored blocks of a range and reports the distribution of their gas used, the number
+/// of transactions by type, the average fullness of the blocks and the number of unique senders.
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    /// The first block of the range, defaults to 10000 blocks below the last block.
+    #[arg(long, value_name = "NUMBER")]
+    from: Option<BlockNumber>,
+
+    /// The last block of the range, defaults to the local tip.
+    #[arg(long, value_name = "NUMBER")]
+    to: Option<BlockNumber>,
+
+    /// The number of buckets of the histogram of the block fullness.
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u64).range(1..=100))]
+    buckets: u64,
+
+    /// Output the statistics as JSON.
+    #[arg(long)]
+    json: bool,
+}
+
+impl Command {
+    /// Execute `stats chain` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let Environment { provider_factory, .. } = self.env.init(AccessRights::RO)?;
+        let provider = provider_factory.provider()?;
+
+        let tip = provider.best_block_number()?;
+        let to = self.to.unwrap_or(tip);
+        let from = self.from.unwrap_or_else(|| to.saturating_sub(DEFAULT_RANGE_LEN - 1));
+        eyre::ensure!(to <= tip, "block {to} is above the local tip {tip}");
+        eyre::ensure!(from <= to, "--from must not be greater than --to");
+
+        let mut collector = StatsCollector::new(self.buckets);
+        for block in BlockRangeIter::new(&provider, from..=to).without_receipts() {
+            let block = block?.block;
+            collector.add_block(&block);
+            if block.number % LOG_INTERVAL == 0 && block.number != from {
+                info!(target: "reth::cli", block = block.number, to, "Scanning blocks");
+            }
+        }
+        let stats = collector.finish(from, to);
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&stats)?);
+        } else {
+            stats.print();
+        }
+
+        Ok(())
+    }
+}
+
+/// Aggregates the statistics of blocks.
+#[derive(Debug)]
+struct StatsCollector {
+    gas_used: Vec<u64>,
+    /// The sum of the ratios of the gas used to the gas limit of the blocks.
+    fullness: f64,
+    histogram: Vec<u64>,
+    transactions: u64,
+    tx_types: BTreeMap<String, u64>,
+    senders: HashSet<Address>,
+}
+
+impl StatsCollector {
+    fn new(buckets: u64) -> Self {
+        Self {
+            gas_used: Vec::new(),
+            fullness: 0.0,
+            histogram: vec![0; buckets as usize],
+            transactions: 0,
+            tx_types: BTreeMap::new(),
+            senders: HashSet::new(),
+        }
+    }
+
+    fn add_block(&mut self, block: &SealedBlockWithSenders) {
+        self.gas_used.push(block.gas_used);
+
+        let fullness = fullness(block.gas_used, block.gas_limit);
+        self.fullness += fullness;
+        self.histogram[bucket(fullness, self.histogram.len())] += 1;
+
+        self.transactions += block.body.len() as u64;
+        for tx in &block.body {
+            *self.tx_types.entry(format!("{:?}", tx.tx_type())).or_default() += 1;
+        }
+        self.senders.extend(&block.senders);
+    }
+
+    fn finish(mut self, from: BlockNumber, to: BlockNumber) -> ChainStats {
+        self.gas_used.sort_unstable();
+        let blocks = self.gas_used.len() as u64;
+        let total_gas_used = self.gas_used.iter().sum::<u64>();
+        let buckets = self.histogram.len() as f64;
+
+        ChainStats {
+            from,
+            // the scan stops at the first missing block
+            to: from + blocks.saturating_sub(1),
+            requested_to: to,
+            blocks,
+            transactions: self.transactions,
+            total_gas_used,
+            avg_fullness_percent: if blocks == 0 {
+                0.0
+            } else {
+                self.fullness / blocks as f64 * 100.0
+            },
+            gas_used: GasUsedStats {
+                min: self.gas_used.first().copied().unwrap_or_default(),
+                mean: if blocks == 0 { 0 } else { total_gas_used / blocks },
+                p50: percentile(&self.gas_used, 50),
+                p90: percentile(&self.gas_used, 90),
+                p99: percentile(&self.gas_used, 99),
+                max: self.gas_used.last().copied().unwrap_or_default(),
+            },
+            fullness_histogram: self
+                .histogram
+                .iter()
+                .enumerate()
+                .map(|(index, &blocks)| FullnessBucket {
+                    from_percent: index as f64 / buckets * 100.0,
+                    to_percent: (index + 1) as f64 / buckets * 100.0,
+                    blocks,
+                })
+                .collect(),
+            tx_types: self.tx_types,
+            unique_senders: self.senders.len() as u64,
+        }
+    }
+}
+
+/// Returns the ratio of the gas used to the gas limit of a block.
+fn fullness(gas_used: u64, gas_limit: u64) -> f64 {
+    if gas_limit == 0 {
+        return 0.0
+    }
+    (gas_used as f64 / gas_limit as f64).min(1.0)
+}
+
+/// Returns the bucket of the histogram a block with the given fullness falls into, full blocks are
+/// in the last bucket.
+fn bucket(fullness: f64, buckets: usize) -> usize {
+    ((fullness * buckets as f64) as usize).min(buckets - 1)
+}
+
+/// Returns the nearest-rank percentile of the sorted values.
+fn percentile(sorted: &[u64], percent: u64) -> u64 {
+    if sorted.is_empty() {
+        return 0
+    }
+    let rank = (sorted.len() as u64 * percent).div_ceil(100).max(1);
+    sorted[rank as usize - 1]
+}
+
+/// Statistics of a range of blocks.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ChainStats {
+    from: BlockNumber,
+    to: BlockNumber,
+    requested_to: BlockNumber,
+    blocks: u64,
+    transactions: u64,
+    total_gas_used: u64,
+    avg_fullness_percent: f64,
+    gas_used: GasUsedStats,
+    fullness_histogram: Vec<FullnessBucket>,
+    tx_types: BTreeMap<String, u64>,
+    unique_senders: u64,
+}
+
+/// The distribution of the gas used by the blocks.
+#[derive(Debug, Serialize)]
+struct GasUsedStats {
+    min: u64,
+    mean: u64,
+    p50: u64,
+    p90: u64,
+    p99: u64,
+    max: u64,
+}
+
+/// The number of blocks with a gas used to gas limit ratio in a range.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FullnessBucket {
+    from_percent: f64,
+    to_percent: f64,
+    blocks: u64,
+}
+
+impl ChainStats {
+    fn print(&self) {
+        if self.to < self.requested_to {
+            println!(
+                "Blocks above {} are not available, scanned blocks {} to {}",
+                self.to, self.from, self.to
+            );
+        }
+
+        let mut table = ComfyTable::new();
+        table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        table.set_header(["Statistic", "Value"]);
+        let rows = [
+            ("Blocks", format!("{} ({} to {})", self.blocks, self.from, self.to)),
+            ("Transactions", self.transactions.to_string()),
+            ("Unique senders", self.unique_senders.to_string()),
+            ("Total gas used", self.total_gas_used.to_string()),
+            ("Average fullness", format!("{:.2}%", self.avg_fullness_percent)),
+            ("Gas used min", self.gas_used.min.to_string()),
+            ("Gas used mean", self.gas_used.mean.to_string()),
+            ("Gas used p50", self.gas_used.p50.to_string()),
+            ("Gas used p90", self.gas_used.p90.to_string()),
+            ("Gas used p99", self.gas_used.p99.to_string()),
+            ("Gas used max", self.gas_used.max.to_string()),
+        ];
+        for (name, value) in rows {
+            let mut row = Row::new();
+            row.add_cell(Cell::new(name)).add_cell(Cell::new(value));
+            table.add_row(row);
+        }
+        println!("{table}");
+
+        let mut table = ComfyTable::new();
+        table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        table.set_header(["Fullness", "Blocks", "Share"]);
+        for bucket in &self.fullness_histogram {
+            let share = if self.blocks == 0 {
+                0.0
+            } else {
+                bucket.blocks as f64 / self.blocks as f64 * 100.0
+            };
+            let mut row = Row::new();
+            row.add_cell(Cell::new(format!(
+                "{:.0}% - {:.0}%",
+                bucket.from_percent, bucket.to_percent
+            )))
+            .add_cell(Cell::new(bucket.blocks))
+            .add_cell(Cell::new(format!("{share:.2}%")));
+            table.add_row(row);
+        }
+        println!("\n{table}");
+
+        let mut table = ComfyTable::new();
+        table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        table.set_header(["Transaction Type", "Transactions"]);
+        for (tx_type, count) in &self.tx_types {
+            let mut row = Row::new();
+            row.add_cell(Cell::new(tx_type)).add_cell(Cell::new(count));
+            table.add_row(row);
+        }
+        println!("\n{table}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gas_used_distribution() {
+        let sorted = (1..=200).collect::<Vec<u64>>();
+        assert_eq!(percentile(&sorted, 50), 100);
+        assert_eq!(percentile(&sorted, 99), 198);
+        assert_eq!(percentile(&sorted[..1], 90), 1);
+        assert_eq!(percentile(&[], 50), 0);
+
+        assert_eq!(bucket(fullness(0, 30_000_000), 10), 0);
+        assert_eq!(bucket(fullness(15_000_000, 30_000_000), 10), 5);
+        assert_eq!(bucket(fullness(30_000_000, 30_000_000), 10), 9);
+        assert_eq!(bucket(fullness(1, 0), 4), 0);
+    }
+}
@@ -0,0 +1,36 @@
+//! `reth stats` command. Reports statistics of the data stored in the datadir.
+
+use clap::{Parser, Subcommand};
+
+mod chain;
+mod state;
+mod sync_history;
+
+/// `reth stats` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth stats` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands {
+    /// Report gas, transaction and sender statistics of a range of stored blocks.
+    Chain(chain::Command),
+    /// Report the number of accounts, storage slots, bytecodes and trie nodes of the state.
+    State(state::Command),
+    /// Print the timeline of the sync progress recorded by the node.
+    SyncHistory(sync_history::Command),
+}
+
+impl Command {
+    /// Execute `stats` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::Chain(command) => command.execute().await,
+            Subcommands::State(command) => command.execute().await,
+            Subcommands::SyncHistory(command) => command.execute().await,
+        }
+    }
+}
@@ -0,0 +1,333 @@
+//! Command that reports the size of the state stored in the datadir.
+
+use crate::commands::common::{AccessRights, Environment, EnvironmentArgs};
+use clap::Parser;
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use reth_db::tables;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    transaction::DbTx,
+};
+use reth_primitives::Address;
+use serde::Serialize;
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BinaryHeap},
+};
+use tracing::info;
+
+/// Number of entries of a table between progress logs.
+const LOG_INTERVAL: u64 = 10_000_000;
+
+/// `reth stats state` command
+///
+/// Walks the plain state, the bytecodes and the merkle tries and reports the number of accounts
+/// and storage slots, the size of the bytecodes, the contracts with the most storage slots and the
+/// depth of the trie nodes.
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    /// The number of contracts with the most storage slots to report.
+    #[arg(long, default_value_t = 20)]
+    top: usize,
+
+    /// Skip walking the merkle tries.
+    #[arg(long)]
+    skip_trie: bool,
+
+    /// Output the statistics as JSON.
+    #[arg(long)]
+    json: bool,
+}
+
+impl Command {
+    /// Execute `stats state` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let Environment { provider_factory, .. } = self.env.init(AccessRights::RO)?;
+        let provider = provider_factory.provider()?;
+        let tx = provider.tx_ref();
+
+        info!(target: "reth::cli", "Walking accounts");
+        let mut accounts = AccountStats::default();
+        for entry in tx.cursor_read::<tables::PlainAccountState>()?.walk(None)? {
+            let (_, account) = entry?;
+            accounts.accounts += 1;
+            if account.has_bytecode() {
+                accounts.contracts += 1;
+            }
+            log_progress("PlainAccountState", accounts.accounts);
+        }
+
+        info!(target: "reth::cli", "Walking storage");
+        let mut storage = StorageCollector::new(self.top);
+        for entry in tx.cursor_dup_read::<tables::PlainStorageState>()?.walk(None)? {
+            let (address, _) = entry?;
+            storage.add_slot(address);
+            log_progress("PlainStorageState", storage.slots);
+        }
+
+        info!(target: "reth::cli", "Walking bytecodes");
+        let mut bytecodes = BytecodeStats::default();
+        for entry in tx.cursor_read::<tables::Bytecodes>()?.walk(None)? {
+            let (_, bytecode) = entry?;
+            let len = bytecode.original_bytes().len() as u64;
+            bytecodes.bytecodes += 1;
+            bytecodes.total_bytes += len;
+            bytecodes.max_bytes = bytecodes.max_bytes.max(len);
+        }
+
+        let (accounts_trie, storages_trie) = if self.skip_trie {
+            (None, None)
+        } else {
+            info!(target: "reth::cli", "Walking accounts trie");
+            let mut accounts_trie = TrieDepthStats::default();
+            for entry in tx.cursor_read::<tables::AccountsTrie>()?.walk(None)? {
+                let (nibbles, _) = entry?;
+                accounts_trie.add_node(nibbles.0.len());
+                log_progress("AccountsTrie", accounts_trie.nodes);
+            }
+
+            info!(target: "reth::cli", "Walking storages trie");
+            let mut storages_trie = TrieDepthStats::default();
+            for entry in tx.cursor_dup_read::<tables::StoragesTrie>()?.walk(None)? {
+                let (_, node) = entry?;
+                storages_trie.add_node(node.nibbles.0.len());
+                log_progress("StoragesTrie", storages_trie.nodes);
+            }
+
+            (Some(accounts_trie), Some(storages_trie))
+        };
+
+        let stats = StateStats {
+            accounts,
+            storage: storage.finish(),
+            bytecodes,
+            accounts_trie,
+            storages_trie,
+        };
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&stats)?);
+        } else {
+            stats.print();
+        }
+
+        Ok(())
+    }
+}
+
+fn log_progress(table: &str, entries: u64) {
+    if entries % LOG_INTERVAL == 0 {
+        info!(target: "reth::cli", table, entries, "Walking state");
+    }
+}
+
+/// Collects the number of storage slots of each contract, keeping the `top` contracts with the
+/// most slots.
+///
+/// The entries of the storage table are sorted by address, so the slots of a contract are counted
+/// one contract at a time.
+#[derive(Debug)]
+struct StorageCollector {
+    top: usize,
+    slots: u64,
+    contracts: u64,
+    current: Option<(Address, u64)>,
+    /// The contracts with the most slots, as a min-heap so the smallest one is replaced first.
+    largest: BinaryHeap<Reverse<(u64, Address)>>,
+}
+
+impl StorageCollector {
+    fn new(top: usize) -> Self {
+        Self { top, slots: 0, contracts: 0, current: None, largest: BinaryHeap::new() }
+    }
+
+    fn add_slot(&mut self, address: Address) {
+        self.slots += 1;
+        match &mut self.current {
+            Some((current, slots)) if *current == address => *slots += 1,
+            _ => {
+                self.finish_contract();
+                self.current = Some((address, 1));
+            }
+        }
+    }
+
+    fn finish_contract(&mut self) {
+        let Some((address, slots)) = self.current.take() else { return };
+        self.contracts += 1;
+        if self.top == 0 {
+            return
+        }
+        if self.largest.len() < self.top {
+            self.largest.push(Reverse((slots, address)));
+        } else if self.largest.peek().is_some_and(|Reverse((min, _))| slots > *min) {
+            self.largest.pop();
+            self.largest.push(Reverse((slots, address)));
+        }
+    }
+
+    fn finish(mut self) -> StorageStats {
+        self.finish_contract();
+        // sorted by descending slots, since the entries are reversed
+        let largest = self.largest.into_sorted_vec();
+        StorageStats {
+            slots: self.slots,
+            contracts_with_storage: self.contracts,
+            largest_contracts: largest
+                .into_iter()
+                .map(|Reverse((slots, address))| ContractStorage { address, slots })
+                .collect(),
+        }
+    }
+}
+
+/// The size of the state.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StateStats {
+    accounts: AccountStats,
+    storage: StorageStats,
+    bytecodes: BytecodeStats,
+    /// `None` if the tries were not walked.
+    accounts_trie: Option<TrieDepthStats>,
+    storages_trie: Option<TrieDepthStats>,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AccountStats {
+    accounts: u64,
+    /// The number of accounts with bytecode.
+    contracts: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StorageStats {
+    slots: u64,
+    contracts_with_storage: u64,
+    /// The contracts with the most storage slots, in descending order.
+    largest_contracts: Vec<ContractStorage>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ContractStorage {
+    address: Address,
+    slots: u64,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BytecodeStats {
+    bytecodes: u64,
+    total_bytes: u64,
+    max_bytes: u64,
+}
+
+/// The number of stored nodes of a trie by the length of their path.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TrieDepthStats {
+    nodes: u64,
+    max_depth: usize,
+    /// The number of nodes by depth.
+    depths: BTreeMap<usize, u64>,
+}
+
+impl TrieDepthStats {
+    fn add_node(&mut self, depth: usize) {
+        self.nodes += 1;
+        self.max_depth = self.max_depth.max(depth);
+        *self.depths.entry(depth).or_default() += 1;
+    }
+
+    fn avg_depth(&self) -> f64 {
+        if self.nodes == 0 {
+            return 0.0
+        }
+        let total = self.depths.iter().map(|(depth, nodes)| *depth as u64 * nodes).sum::<u64>();
+        total as f64 / self.nodes as f64
+    }
+}
+
+impl StateStats {
+    fn print(&self) {
+        let mut table = ComfyTable::new();
+        table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        table.set_header(["Statistic", "Value"]);
+        let mut rows = vec![
+            ("Accounts", self.accounts.accounts.to_string()),
+            ("Contracts", self.accounts.contracts.to_string()),
+            ("Storage slots", self.storage.slots.to_string()),
+            ("Contracts with storage", self.storage.contracts_with_storage.to_string()),
+            ("Bytecodes", self.bytecodes.bytecodes.to_string()),
+            ("Bytecode size", human_bytes::human_bytes(self.bytecodes.total_bytes as f64)),
+            ("Largest bytecode", human_bytes::human_bytes(self.bytecodes.max_bytes as f64)),
+        ];
+        for (name, trie) in
+            [("Accounts trie", &self.accounts_trie), ("Storages trie", &self.storages_trie)]
+        {
+            if let Some(trie) = trie {
+                rows.push((
+                    name,
+                    format!(
+                        "{} nodes, average depth {:.2}, max depth {}",
+                        trie.nodes,
+                        trie.avg_depth(),
+                        trie.max_depth
+                    ),
+                ));
+            }
+        }
+        for (name, value) in rows {
+            let mut row = Row::new();
+            row.add_cell(Cell::new(name)).add_cell(Cell::new(value));
+            table.add_row(row);
+        }
+        println!("{table}");
+
+        if !self.storage.largest_contracts.is_empty() {
+            let mut table = ComfyTable::new();
+            table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+            table.set_header(["Contract", "Storage Slots", "Share"]);
+            for contract in &self.storage.largest_contracts {
+                let share = contract.slots as f64 / self.storage.slots as f64 * 100.0;
+                let mut row = Row::new();
+                row.add_cell(Cell::new(contract.address))
+                    .add_cell(Cell::new(contract.slots))
+                    .add_cell(Cell::new(format!("{share:.2}%")));
+                table.add_row(row);
+            }
+            println!("\n{table}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_contracts_with_most_slots() {
+        let mut collector = StorageCollector::new(2);
+        for (address, slots) in [(1, 3), (2, 1), (3, 5), (4, 2)] {
+            for _ in 0..slots {
+                collector.add_slot(Address::with_last_byte(address));
+            }
+        }
+
+        let stats = collector.finish();
+        assert_eq!(stats.slots, 11);
+        assert_eq!(stats.contracts_with_storage, 4);
+        let largest = stats
+            .largest_contracts
+            .iter()
+            .map(|contract| (contract.address, contract.slots))
+            .collect::<Vec<_>>();
+        assert_eq!(largest, [(Address::with_last_byte(3), 5), (Address::with_last_byte(1), 3)]);
+    }
+}
@@ -0,0 +1,126 @@
+//! Command that prints the timeline of the sync progress recorded by the node.
+
+use crate::commands::common::{AccessRights, Environment, EnvironmentArgs};
+use clap::Parser;
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use human_bytes::human_bytes;
+use reth_db_api::models::SyncSnapshot;
+use reth_provider::SyncHistoryReader;
+use serde_json::json;
+
+/// `reth stats sync-history` command
+///
+/// Prints the snapshots of the sync progress the node records every few minutes: the synced block,
+/// the running pipeline stage with its checkpoint and the disk usage of the database and the
+/// static files. The rate is the progress of the running stage, or of the synced block if the
+/// pipeline was idle, since the previous snapshot.
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    /// The unix timestamp in seconds of the first snapshot to print
+    #[arg(long, value_name = "TIMESTAMP", default_value_t = 0)]
+    from: u64,
+
+    /// Output the snapshots as JSON.
+    #[arg(long)]
+    json: bool,
+}
+
+impl Command {
+    /// Execute `stats sync-history` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let Environment { provider_factory, .. } = self.env.init(AccessRights::RO)?;
+        let history = provider_factory.sync_history(self.from)?;
+
+        if self.json {
+            let history = history
+                .iter()
+                .enumerate()
+                .map(|(index, (timestamp, snapshot))| {
+                    json!({
+                        "timestamp": timestamp,
+                        "blockNumber": snapshot.block_number,
+                        "stage": (!snapshot.stage.is_empty()).then_some(&snapshot.stage),
+                        "stageCheckpoint": snapshot.stage_checkpoint,
+                        "diskUsage": snapshot.disk_usage,
+                        "blocksPerHour": index
+                            .checked_sub(1)
+                            .and_then(|prev| blocks_per_hour(&history[prev], &history[index])),
+                    })
+                })
+                .collect::<Vec<_>>();
+            println!("{}", serde_json::to_string_pretty(&history)?);
+            return Ok(())
+        }
+
+        if history.is_empty() {
+            println!("No sync progress recorded");
+            return Ok(())
+        }
+
+        let mut table = ComfyTable::new();
+        table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        table.set_header([
+            "Timestamp",
+            "Block",
+            "Stage",
+            "Stage Checkpoint",
+            "Blocks/h",
+            "Disk Usage",
+        ]);
+        for (index, (timestamp, snapshot)) in history.iter().enumerate() {
+            let rate = index
+                .checked_sub(1)
+                .and_then(|prev| blocks_per_hour(&history[prev], &history[index]))
+                .map(|rate| rate.to_string())
+                .unwrap_or_default();
+            let stage = if snapshot.stage.is_empty() { "Idle" } else { &snapshot.stage };
+            let mut row = Row::new();
+            row.add_cell(Cell::new(timestamp))
+                .add_cell(Cell::new(snapshot.block_number))
+                .add_cell(Cell::new(stage))
+                .add_cell(Cell::new(snapshot.stage_checkpoint))
+                .add_cell(Cell::new(rate))
+                .add_cell(Cell::new(human_bytes(snapshot.disk_usage as f64)));
+            table.add_row(row);
+        }
+        println!("{table}");
+
+        Ok(())
+    }
+}
+
+/// Returns the blocks per hour the running stage progressed between the snapshots, or `None` if
+/// a different stage was running at the previous snapshot.
+fn blocks_per_hour(
+    (prev_timestamp, prev): &(u64, SyncSnapshot),
+    (timestamp, current): &(u64, SyncSnapshot),
+) -> Option<u64> {
+    if prev.stage != current.stage {
+        return None
+    }
+    let elapsed = timestamp.checked_sub(*prev_timestamp).filter(|elapsed| *elapsed > 0)?;
+    let blocks = current.stage_checkpoint.checked_sub(prev.stage_checkpoint)?;
+    Some(blocks * 3600 / elapsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(stage: &str, stage_checkpoint: u64) -> SyncSnapshot {
+        SyncSnapshot { stage: stage.to_string(), stage_checkpoint, ..Default::default() }
+    }
+
+    #[test]
+    fn rate_of_running_stage() {
+        let prev = (0, snapshot("Execution", 1_000));
+        assert_eq!(blocks_per_hour(&prev, &(300, snapshot("Execution", 6_000))), Some(60_000));
+        assert_eq!(blocks_per_hour(&prev, &(300, snapshot("MerkleExecute", 6_000))), None);
+        assert_eq!(blocks_per_hour(&prev, &(0, snapshot("Execution", 6_000))), None);
+        // unwound
+        assert_eq!(blocks_per_hour(&prev, &(300, snapshot("Execution", 500))), None);
+    }
+}
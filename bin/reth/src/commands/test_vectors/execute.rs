@@ -0,0 +1,126 @@
+//! Command for running execution spec test fixtures.
+
+use ef_tests::{
+    cases::{blockchain_test::BlockchainTestCase, state_test::StateTestCase},
+    models::ForkSpec,
+    Case, Error, TestResult,
+};
+use eyre::Result;
+use reth_fs_util as fs;
+use std::{
+    collections::{BTreeMap, HashSet},
+    ffi::OsStr,
+    path::{Path, PathBuf},
+};
+use tracing::debug;
+use walkdir::WalkDir;
+
+/// The kind of a test fixture file.
+#[derive(Debug, Clone, Copy)]
+enum FixtureKind {
+    /// A blockchain test, run through the execution stage.
+    Blockchain,
+    /// A state test, a single transaction run with the EVM configuration.
+    State,
+}
+
+/// The number of tests that passed, failed and were skipped on a fork.
+#[derive(Debug, Default)]
+struct ForkResults {
+    passed: usize,
+    failed: usize,
+    skipped: usize,
+}
+
+/// Runs all blockchain and state test fixtures in the given files and directories and prints the
+/// results per fork. If `forks` is not empty, only tests for these forks are reported.
+///
+/// Returns an error if any test failed.
+pub(crate) fn execute_fixtures(paths: Vec<PathBuf>, forks: Vec<ForkSpec>) -> Result<()> {
+    let forks = forks.into_iter().collect::<HashSet<_>>();
+    let mut results = BTreeMap::<ForkSpec, ForkResults>::new();
+    let mut failed_fixtures = 0;
+
+    for path in paths {
+        for entry in WalkDir::new(path).sort_by_file_name() {
+            let entry = entry?;
+            let path = entry.path();
+            if !entry.file_type().is_file() || path.extension() != Some(OsStr::new("json")) {
+                continue
+            }
+
+            let Some(kind) = fixture_kind(path)? else {
+                debug!(target: "reth::cli", path = %path.display(), "Not a test fixture, skipping");
+                continue
+            };
+            let test_results = match kind {
+                FixtureKind::Blockchain => BlockchainTestCase::load(path).map(|c| c.run_each()),
+                FixtureKind::State => StateTestCase::load(path).map(|c| c.run_each()),
+            };
+            let test_results = match test_results {
+                Ok(test_results) => test_results,
+                Err(err) => {
+                    println!("[!] {} could not be loaded: {err}", path.display());
+                    failed_fixtures += 1;
+                    continue
+                }
+            };
+
+            for TestResult { name, fork, result } in test_results {
+                if !forks.is_empty() && !forks.contains(&fork) {
+                    continue
+                }
+                let fork_results = results.entry(fork.clone()).or_default();
+                match result {
+                    Ok(()) => fork_results.passed += 1,
+                    Err(Error::Skipped) => fork_results.skipped += 1,
+                    Err(err) => {
+                        println!("[!] {} {name} ({fork:?}) failed: {err}", path.display());
+                        fork_results.failed += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    println!("{:<36} {:>8} {:>8} {:>8}", "Fork", "Passed", "Failed", "Skipped");
+    for (fork, fork_results) in &results {
+        println!(
+            "{:<36} {:>8} {:>8} {:>8}",
+            format!("{fork:?}"),
+            fork_results.passed,
+            fork_results.failed,
+            fork_results.skipped
+        );
+    }
+
+    let failed = results.values().map(|r| r.failed).sum::<usize>() + failed_fixtures;
+    eyre::ensure!(failed == 0, "{failed} tests failed");
+    Ok(())
+}
+
+/// Returns the kind of the test fixture at the given path, or `None` if it is not a test fixture.
+fn fixture_kind(path: &Path) -> Result<Option<FixtureKind>> {
+    let Ok(fixture) =
+        serde_json::from_str::<BTreeMap<String, serde_json::Value>>(&fs::read_to_string(path)?)
+    else {
+        return Ok(None)
+    };
+    let Some(test) = fixture.values().next() else { return Ok(None) };
+
+    if test.get("blocks").is_some() {
+        Ok(Some(FixtureKind::Blockchain))
+    } else if test.get("transaction").is_some() && test.get("post").is_some() {
+        Ok(Some(FixtureKind::State))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Parses a fork name as it appears in test fixtures.
+pub(crate) fn parse_fork(value: &str) -> Result<ForkSpec> {
+    match serde_json::from_value(serde_json::Value::String(value.to_string()))? {
+        ForkSpec::Unknown => eyre::bail!("unknown fork: {value}"),
+        fork => Ok(fork),
+    }
+}
@@ -2,6 +2,8 @@
 
 use clap::{Parser, Subcommand};
 
+#[cfg(feature = "ef-tests")]
+mod execute;
 mod tables;
 
 /// Generate test-vectors for different data types.
@@ -19,6 +21,19 @@ pub enum Subcommands {
         /// List of table names. Case-sensitive.
         names: Vec<String>,
     },
+    /// Runs execution spec test fixtures and reports the results per fork.
+    ///
+    /// Blockchain tests are imported and run through the execution stage, state tests execute
+    /// their transaction with the EVM configuration. Both use the Ethereum executor.
+    #[cfg(feature = "ef-tests")]
+    Execute {
+        /// Test fixture files or directories containing them.
+        #[arg(required = true)]
+        paths: Vec<std::path::PathBuf>,
+        /// Only report tests for the given forks, e.g. `Cancun`.
+        #[arg(long = "fork", value_parser = execute::parse_fork)]
+        forks: Vec<ef_tests::models::ForkSpec>,
+    },
 }
 
 impl Command {
@@ -28,6 +43,10 @@ impl Command {
             Subcommands::Tables { names } => {
                 tables::generate_vectors(names)?;
             }
+            #[cfg(feature = "ef-tests")]
+            Subcommands::Execute { paths, forks } => {
+                execute::execute_fixtures(paths, forks)?;
+            }
         }
         Ok(())
     }
@@ -0,0 +1,549 @@
+//! Unwinding the chain to a target block
+
+use clap::{Parser, Subcommand};
+use reth_beacon_consensus::EthBeaconConsensus;
+use reth_config::Config;
+use reth_consensus::Consensus;
+use reth_db::tables;
+use reth_db_api::{cursor::DbCursorRO, database::Database, transaction::DbTx};
+use reth_downloaders::{bodies::noop::NoopBodiesDownloader, headers::noop::NoopHeaderDownloader};
+use reth_exex::ExExManagerHandle;
+use reth_node_core::args::NetworkArgs;
+use reth_primitives::{BlockHashOrNumber, BlockNumber, StaticFileSegment, TxNumber, B256};
+use reth_provider::{
+    BlockExecutionWriter, BlockNumReader, BlockReader, ChainSpecProvider, FinalizedBlockReader,
+    FinalizedBlockWriter, PendingUnwindReader, PendingUnwindWriter, ProviderFactory,
+    PruneCheckpointWriter, ReorgHaltReader, ReorgHaltWriter, StageCheckpointReader,
+    StaticFileProviderFactory,
+};
+use reth_prune_types::{PruneCheckpoint, PruneModes, PruneSegment};
+use reth_stages::{
+    sets::DefaultStages,
+    stages::{ExecutionStage, ExecutionStageThresholds},
+    Pipeline, StageId, StageSet,
+};
+use reth_static_file::StaticFileProducer;
+use std::{ops::RangeInclusive, sync::Arc};
+use tokio::sync::watch;
+use tracing::info;
+
+use crate::{
+    commands::common::{AccessRights, Environment, EnvironmentArgs},
+    macros::block_executor,
+};
+
+/// `reth unwind` command
+///
+/// Unwinds the database, the static files and the prune checkpoints to a target block, refusing
+/// to unwind a datadir whose stages and static files disagree on the blocks they hold.
+///
+/// A database unwind is done in a single transaction. A pipeline unwind commits the unwind of
+/// every stage on its own, so its target is persisted before the first stage is unwound and
+/// cleared with the last checkpoint reset. If it's interrupted, the node refuses to start until
+/// the unwind is completed by running the command again.
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    #[command(flatten)]
+    network: NetworkArgs,
+
+    /// Print the blocks, transactions, static files and checkpoints the unwind would delete or
+    /// reset, without changing the datadir.
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Unwind even though canonicalization is halted by a reorg deeper than `--max-reorg-depth`,
+    /// and resume canonicalization.
+    ///
+    /// Also unwinds a datadir whose merkle trie or static files are ahead of the stages that
+    /// produce them.
+    #[arg(long)]
+    force: bool,
+
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+impl Command {
+    /// Execute `unwind` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let access = if self.dry_run { AccessRights::RO } else { AccessRights::RW };
+        let Environment { provider_factory, config, .. } = self.env.init(access)?;
+
+        let range = self.command.unwind_range(provider_factory.clone())?;
+        if *range.start() == 0 {
+            eyre::bail!("Cannot unwind genesis block")
+        }
+
+        let plan = UnwindPlan::new(&provider_factory, *range.start() - 1, *range.end())?;
+        if self.dry_run {
+            plan.print();
+            return Ok(())
+        }
+        plan.check(self.force)?;
+        if let Some(pending) = plan.pending {
+            info!(target: "reth::cli", pending, "Resuming an interrupted unwind");
+        }
+
+        // Only execute a pipeline unwind if the start of the range overlaps the existing static
+        // files, or if the stages are not all at the tip. If that's the case, then copy all
+        // available data from MDBX to static files, and only then, proceed with the unwind.
+        if plan.needs_pipeline() {
+            info!(target: "reth::cli", ?range, "Executing a pipeline unwind.");
+            let mut pipeline = self.build_pipeline(config, provider_factory.clone()).await?;
+
+            // The stages commit their unwinds one after another, so the target is persisted first
+            // for an interrupted unwind to be resumed.
+            let provider = provider_factory.provider_rw()?;
+            provider.save_pending_unwind(plan.target)?;
+            provider.commit()?;
+
+            // Move all applicable data from database to static files.
+            pipeline.move_to_static_files()?;
+
+            pipeline.unwind(plan.target, None)?;
+
+            // The checkpoints are reset and the pending unwind is cleared once all stages are
+            // unwound.
+            let provider = provider_factory.provider_rw()?;
+            plan.reset_checkpoints(&*provider)?;
+            provider.commit()?;
+        } else {
+            info!(target: "reth::cli", ?range, "Executing a database unwind.");
+            let provider = provider_factory.provider_rw()?;
+
+            let _ = provider
+                .take_block_and_execution_range(range.clone())
+                .map_err(|err| eyre::eyre!("Transaction error on unwind: {err}"))?;
+
+            plan.reset_checkpoints(&*provider)?;
+            provider.commit()?;
+        }
+
+        if let Some(fork_block) = plan.reorg_halt {
+            info!(target: "reth::cli", fork_block, "Resumed canonicalization");
+        }
+
+        println!("Unwound {} blocks", range.count());
+
+        Ok(())
+    }
+
+    async fn build_pipeline<DB: Database + 'static>(
+        self,
+        config: Config,
+        provider_factory: ProviderFactory<Arc<DB>>,
+    ) -> Result<Pipeline<Arc<DB>>, eyre::Error> {
+        let consensus: Arc<dyn Consensus> =
+            Arc::new(EthBeaconConsensus::new(provider_factory.chain_spec()));
+        let stage_conf = &config.stages;
+        let prune_modes = config.prune.clone().map(|prune| prune.segments).unwrap_or_default();
+
+        let (tip_tx, tip_rx) = watch::channel(B256::ZERO);
+        let executor = block_executor!(provider_factory.chain_spec());
+
+        let pipeline = Pipeline::builder()
+            .with_tip_sender(tip_tx)
+            .add_stages(
+                DefaultStages::new(
+                    provider_factory.clone(),
+                    tip_rx,
+                    Arc::clone(&consensus),
+                    NoopHeaderDownloader::default(),
+                    NoopBodiesDownloader::default(),
+                    executor.clone(),
+                    stage_conf.clone(),
+                    prune_modes.clone(),
+                )
+                .set(ExecutionStage::new(
+                    executor,
+                    ExecutionStageThresholds {
+                        max_blocks: None,
+                        max_changes: None,
+                        max_cumulative_gas: None,
+                        max_duration: None,
+                    },
+                    stage_conf.execution_external_clean_threshold(),
+                    prune_modes,
+                    ExExManagerHandle::empty(),
+                )),
+            )
+            .build(
+                provider_factory.clone(),
+                StaticFileProducer::new(provider_factory, PruneModes::default()),
+            );
+        Ok(pipeline)
+    }
+}
+
+/// `reth unwind` subcommand
+#[derive(Subcommand, Debug, Eq, PartialEq)]
+enum Subcommands {
+    /// Unwinds the database from the latest block, until the given block number or hash has been
+    /// reached, that block is not included.
+    #[command(name = "to-block")]
+    ToBlock { target: BlockHashOrNumber },
+    /// Unwinds the database from the latest block, until the given number of blocks have been
+    /// reached.
+    #[command(name = "num-blocks")]
+    NumBlocks { amount: u64 },
+}
+
+impl Subcommands {
+    /// Returns the block range to unwind.
+    ///
+    /// This returns an inclusive range: [target..=latest]
+    fn unwind_range<DB: Database>(
+        &self,
+        factory: ProviderFactory<DB>,
+    ) -> eyre::Result<RangeInclusive<u64>> {
+        let provider = factory.provider()?;
+        let last = provider.last_block_number()?;
+        let target = match self {
+            Self::ToBlock { target } => match target {
+                BlockHashOrNumber::Hash(hash) => provider
+                    .block_number(*hash)?
+                    .ok_or_else(|| eyre::eyre!("Block hash not found in database: {hash:?}"))?,
+                BlockHashOrNumber::Number(num) => *num,
+            },
+            Self::NumBlocks { amount } => last.saturating_sub(*amount),
+        } + 1;
+        if target > last {
+            eyre::bail!("Target block number is higher than the latest block number")
+        }
+        Ok(target..=last)
+    }
+}
+
+/// The data an unwind to a target block deletes and the checkpoints it resets.
+#[derive(Debug, Default)]
+struct UnwindPlan {
+    /// The block to unwind to, it's kept.
+    target: BlockNumber,
+    /// The latest block.
+    tip: BlockNumber,
+    /// The transactions of the unwound blocks, `None` if they have none.
+    transactions: Option<RangeInclusive<TxNumber>>,
+    /// The checkpoints of all stages.
+    stages: Vec<(StageId, BlockNumber)>,
+    /// The highest block of each static file segment that has static files.
+    static_files: Vec<(StaticFileSegment, BlockNumber)>,
+    /// The prune checkpoints, with the checkpoints they're reset to if they're above the target.
+    prune_checkpoints: Vec<(PruneSegment, PruneCheckpoint, Option<PruneCheckpoint>)>,
+    /// The finalized block.
+    finalized: BlockNumber,
+    /// The fork block of the reorg that halted canonicalization.
+    reorg_halt: Option<BlockNumber>,
+    /// The target block of an interrupted unwind.
+    pending: Option<BlockNumber>,
+}
+
+impl UnwindPlan {
+    /// Reads the tips of the stages, static files and prune segments of the datadir.
+    fn new<DB: Database>(
+        factory: &ProviderFactory<DB>,
+        target: BlockNumber,
+        tip: BlockNumber,
+    ) -> eyre::Result<Self> {
+        let provider = factory.provider()?;
+
+        let target_indices = provider.block_body_indices(target)?;
+        let transactions = target_indices.and_then(|target_indices| {
+            let last = provider.block_body_indices(tip).ok().flatten()?.last_tx_num();
+            let first = target_indices.next_tx_num();
+            (first <= last).then_some(first..=last)
+        });
+
+        let mut stages = Vec::with_capacity(StageId::ALL.len());
+        for stage in StageId::ALL {
+            let checkpoint = provider.get_stage_checkpoint(stage)?.unwrap_or_default();
+            stages.push((stage, checkpoint.block_number));
+        }
+
+        let highest_static_files = factory.static_file_provider().get_highest_static_files();
+        let static_files = [
+            StaticFileSegment::Headers,
+            StaticFileSegment::Transactions,
+            StaticFileSegment::Receipts,
+        ]
+        .into_iter()
+        .filter_map(|segment| Some((segment, highest_static_files.highest(segment)?)))
+        .collect();
+
+        let mut prune_checkpoints = Vec::new();
+        for entry in provider.tx_ref().cursor_read::<tables::PruneCheckpoints>()?.walk(None)? {
+            let (segment, checkpoint) = entry?;
+            let reset =
+                checkpoint.block_number.filter(|block| *block > target).map(|_| PruneCheckpoint {
+                    block_number: Some(target),
+                    tx_number: checkpoint.tx_number.and(target_indices.map(|i| i.last_tx_num())),
+                    prune_mode: checkpoint.prune_mode,
+                });
+            prune_checkpoints.push((segment, checkpoint, reset));
+        }
+
+        Ok(Self {
+            target,
+            tip,
+            transactions,
+            stages,
+            static_files,
+            prune_checkpoints,
+            finalized: provider.last_finalized_block_number()?,
+            reorg_halt: provider.reorg_halt()?,
+            pending: provider.pending_unwind()?,
+        })
+    }
+
+    fn stage_checkpoint(&self, stage: StageId) -> BlockNumber {
+        self.stages.iter().find(|(id, _)| *id == stage).map(|(_, block)| *block).unwrap_or_default()
+    }
+
+    /// Returns `true` if the unwind deletes data from the static files, or if a stage isn't at the
+    /// tip, so each stage has to unwind from its own checkpoint.
+    ///
+    /// The database unwind unwinds the state and the trie of all blocks in one go, which is only
+    /// consistent if every stage has processed them.
+    fn needs_pipeline(&self) -> bool {
+        self.static_files.iter().any(|(_, block)| *block > self.target) ||
+            self.stages.iter().any(|(_, block)| *block != self.tip)
+    }
+
+    /// Returns the reasons why the stages and static files of the datadir don't agree on the
+    /// blocks they hold.
+    fn inconsistencies(&self) -> Vec<String> {
+        let mut inconsistencies = Vec::new();
+
+        let execution = self.stage_checkpoint(StageId::Execution);
+        let merkle = self.stage_checkpoint(StageId::MerkleExecute);
+        if merkle > execution {
+            inconsistencies.push(format!(
+                "the merkle trie is at block {merkle}, above the executed state at block \
+                 {execution}"
+            ));
+        }
+
+        for (segment, block) in &self.static_files {
+            let stage = match segment {
+                StaticFileSegment::Headers => StageId::Headers,
+                StaticFileSegment::Transactions => StageId::Bodies,
+                StaticFileSegment::Receipts => StageId::Execution,
+            };
+            let checkpoint = self.stage_checkpoint(stage);
+            if *block > checkpoint {
+                inconsistencies.push(format!(
+                    "the {segment} static files end at block {block}, above the {stage} stage \
+                     at block {checkpoint}"
+                ));
+            }
+        }
+
+        inconsistencies
+    }
+
+    /// Checks that the unwind can be done.
+    ///
+    /// The state can't be unwound below the blocks whose changesets were pruned. Unless forced,
+    /// halted canonicalization and an inconsistent datadir are refused as well. An interrupted
+    /// unwind has to be completed before unwinding to a higher block, and leaves the stages at
+    /// different blocks, so resuming it is not refused for being inconsistent.
+    fn check(&self, force: bool) -> eyre::Result<()> {
+        if let Some(pending) = self.pending.filter(|pending| *pending < self.target) {
+            eyre::bail!(
+                "An unwind to block {pending} was interrupted, run `reth unwind to-block \
+                 {pending}` to complete it"
+            )
+        }
+
+        for (segment, checkpoint, _) in &self.prune_checkpoints {
+            if !matches!(segment, PruneSegment::AccountHistory | PruneSegment::StorageHistory) {
+                continue
+            }
+            if let Some(pruned) = checkpoint.block_number.filter(|block| *block > self.target) {
+                eyre::bail!(
+                    "The changesets of {segment} are pruned up to block {pruned}, the state can't \
+                     be unwound to block {}",
+                    self.target
+                )
+            }
+        }
+
+        if force || self.pending.is_some() {
+            return Ok(())
+        }
+
+        if let Some(fork_block) = self.reorg_halt {
+            eyre::bail!(
+                "Canonicalization is halted by a reorg to block {fork_block}, pass --force to \
+                 unwind and resume it"
+            )
+        }
+
+        let inconsistencies = self.inconsistencies();
+        if !inconsistencies.is_empty() {
+            eyre::bail!(
+                "Refusing to unwind an inconsistent datadir: {}. Run `reth doctor` to inspect it, \
+                 or pass --force to unwind anyway",
+                inconsistencies.join(", ")
+            )
+        }
+
+        Ok(())
+    }
+
+    /// Resets the prune checkpoints and the finalized block above the target, resumes halted
+    /// canonicalization and clears the pending unwind.
+    fn reset_checkpoints<P>(&self, provider: &P) -> eyre::Result<()>
+    where
+        P: PruneCheckpointWriter + FinalizedBlockWriter + ReorgHaltWriter + PendingUnwindWriter,
+    {
+        for (segment, _, reset) in &self.prune_checkpoints {
+            if let Some(reset) = reset {
+                provider.save_prune_checkpoint(*segment, *reset)?;
+            }
+        }
+        if self.finalized > self.target {
+            provider.save_finalized_block_number(self.target)?;
+        }
+        if self.reorg_halt.is_some() {
+            provider.clear_reorg_halt()?;
+        }
+        provider.clear_pending_unwind()?;
+        Ok(())
+    }
+
+    /// Prints what the unwind deletes and resets.
+    fn print(&self) {
+        println!(
+            "Unwinding to block {} deletes {} blocks ({} to {})",
+            self.target,
+            self.tip - self.target,
+            self.target + 1,
+            self.tip
+        );
+        match &self.transactions {
+            Some(range) => println!(
+                "Transactions: {} ({} to {})",
+                range.end() - range.start() + 1,
+                range.start(),
+                range.end()
+            ),
+            None => println!("Transactions: none"),
+        }
+        println!(
+            "Method: {}",
+            if self.needs_pipeline() { "pipeline unwind" } else { "database unwind" }
+        );
+        if let Some(pending) = self.pending {
+            println!("Resumes the interrupted unwind to block {pending}");
+        }
+
+        for (stage, block) in self.stages.iter().filter(|(_, block)| *block > self.target) {
+            println!("Stage {stage}: checkpoint {block} -> {}", self.target);
+        }
+        for (segment, block) in self.static_files.iter().filter(|(_, block)| *block > self.target) {
+            println!("Static files {segment}: blocks {} to {block} are deleted", self.target + 1);
+        }
+        for (segment, checkpoint, reset) in &self.prune_checkpoints {
+            if let Some(reset) = reset {
+                println!(
+                    "Prune checkpoint {segment}: block {:?} -> {:?}",
+                    checkpoint.block_number, reset.block_number
+                );
+            }
+        }
+        if self.finalized > self.target {
+            println!("Finalized block: {} -> {}", self.finalized, self.target);
+        }
+        if let Some(fork_block) = self.reorg_halt {
+            println!("Canonicalization halted by a reorg to block {fork_block} is resumed");
+        }
+
+        if let Err(err) = self.check(false) {
+            println!("\n{err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_prune_types::PruneMode;
+
+    #[test]
+    fn parse_unwind() {
+        let cmd = Command::parse_from(["reth", "--datadir", "dir", "to-block", "100"]);
+        assert_eq!(cmd.command, Subcommands::ToBlock { target: BlockHashOrNumber::Number(100) });
+        assert!(!cmd.dry_run);
+
+        let cmd =
+            Command::parse_from(["reth", "--datadir", "dir", "--dry-run", "num-blocks", "100"]);
+        assert_eq!(cmd.command, Subcommands::NumBlocks { amount: 100 });
+        assert!(cmd.dry_run);
+    }
+
+    #[test]
+    fn refuses_inconsistent_unwinds() {
+        let mut plan = UnwindPlan {
+            target: 90,
+            tip: 100,
+            stages: StageId::ALL.into_iter().map(|stage| (stage, 100)).collect(),
+            static_files: vec![(StaticFileSegment::Headers, 100)],
+            ..Default::default()
+        };
+        assert!(plan.check(false).is_ok());
+        assert!(plan.needs_pipeline());
+
+        // the headers static files are ahead of the headers stage
+        plan.static_files = vec![(StaticFileSegment::Headers, 105)];
+        assert_eq!(plan.inconsistencies().len(), 1);
+        assert!(plan.check(false).is_err());
+        assert!(plan.check(true).is_ok());
+
+        // the changesets below the target are kept
+        plan.static_files.clear();
+        plan.prune_checkpoints = vec![(
+            PruneSegment::AccountHistory,
+            PruneCheckpoint {
+                block_number: Some(80),
+                tx_number: None,
+                prune_mode: PruneMode::Distance(10),
+            },
+            None,
+        )];
+        assert!(plan.check(false).is_ok());
+        assert!(!plan.needs_pipeline());
+
+        // the changesets of the unwound blocks are pruned
+        plan.prune_checkpoints[0].1.block_number = Some(95);
+        assert!(plan.check(true).is_err());
+    }
+
+    #[test]
+    fn resumes_interrupted_unwinds() {
+        // the unwind to block 90 was interrupted with the stages at different blocks
+        let mut plan = UnwindPlan {
+            target: 90,
+            tip: 100,
+            stages: StageId::ALL
+                .into_iter()
+                .map(|stage| (stage, if stage == StageId::Execution { 90 } else { 100 }))
+                .collect(),
+            pending: Some(90),
+            ..Default::default()
+        };
+        assert!(!plan.inconsistencies().is_empty());
+        assert!(plan.needs_pipeline());
+        assert!(plan.check(false).is_ok());
+
+        // unwinding further completes it as well
+        plan.target = 80;
+        assert!(plan.check(false).is_ok());
+
+        // a higher target would leave the blocks above it half unwound
+        plan.target = 95;
+        assert!(plan.check(true).is_err());
+    }
+}
@@ -0,0 +1,252 @@
+//! Command that compares the local chain with the chain of a trusted RPC node.
+
+use crate::commands::common::{AccessRights, Environment, EnvironmentArgs};
+use clap::Parser;
+use futures::{stream, StreamExt, TryStreamExt};
+use jsonrpsee::http_client::HttpClientBuilder;
+use reth_chainspec::ChainSpec;
+use reth_primitives::{BlockNumber, Receipt, Receipts, SealedHeader, B256};
+use reth_provider::{
+    BlockNumReader, BlockReader, HeaderProvider, ProviderError, ReceiptProvider,
+    StageCheckpointReader, StateRootProvider,
+};
+use reth_revm::db::BundleState;
+use reth_rpc_api::EthApiClient;
+use reth_rpc_types::Header as RpcHeader;
+use reth_stages::StageId;
+use std::fmt;
+use tracing::info;
+
+/// Interval in blocks at which the progress is logged.
+const PROGRESS_INTERVAL: u64 = 10_000;
+
+/// `reth verify against-rpc` command
+///
+/// Compares the local chain with the chain of a trusted RPC node, e.g. after importing a chain
+/// segment with `import` or `import-op`. For every block of the range, this verifies:
+///  - the header hash, which covers all header fields
+///  - the receipts root, recomputed from the local receipts if they are available
+///
+/// Additionally, the state root is recomputed from the local state at selected blocks.
+///
+/// The command stops at the first divergence.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The HTTP URL of the trusted RPC node.
+    #[arg(long = "rpc-url", value_name = "URL")]
+    rpc_url: String,
+
+    /// The first block to verify.
+    #[arg(long, default_value_t = 0)]
+    from: BlockNumber,
+
+    /// The last block to verify, defaults to the local tip.
+    #[arg(long)]
+    to: Option<BlockNumber>,
+
+    /// Also recompute the state root every this many blocks.
+    ///
+    /// The state root at the end of the range is always recomputed, if the state of the block is
+    /// available locally. Recomputing the state root of older blocks requires their state
+    /// history.
+    #[arg(long = "state-root-interval", value_name = "BLOCKS")]
+    state_root_interval: Option<u64>,
+
+    /// The maximum number of concurrent requests to the RPC node.
+    #[arg(long, default_value_t = 16)]
+    concurrency: usize,
+
+    #[command(flatten)]
+    env: EnvironmentArgs,
+}
+
+impl Command {
+    /// Execute `verify against-rpc` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let Environment { provider_factory, .. } = self.env.init(AccessRights::RO)?;
+        let chain_spec = self.env.chain.clone();
+        let provider = provider_factory.provider()?;
+
+        let to = match self.to {
+            Some(to) => to,
+            None => provider.best_block_number()?,
+        };
+        eyre::ensure!(self.from <= to, "--from must not be greater than --to");
+
+        // the state is only available up to the last block the state root was computed for
+        let state_tip = provider
+            .get_stage_checkpoint(StageId::MerkleExecute)?
+            .map(|checkpoint| checkpoint.block_number)
+            .unwrap_or_default();
+
+        let client = HttpClientBuilder::default().build(&self.rpc_url)?;
+        let mut remote_headers = stream::iter(self.from..=to)
+            .map(|number| {
+                let client = &client;
+                async move {
+                    let block = EthApiClient::block_by_number(client, number.into(), false)
+                        .await?
+                        .ok_or_else(|| eyre::eyre!("block {number} not found on the RPC node"))?;
+                    Ok::<_, eyre::Report>(block.inner.header)
+                }
+            })
+            .buffered(self.concurrency.max(1));
+
+        let state_root_interval = self.state_root_interval.filter(|interval| *interval > 0);
+        let mut summary = Summary::default();
+        let mut number = self.from;
+        while let Some(remote) = remote_headers.try_next().await? {
+            let local = provider
+                .sealed_header(number)?
+                .ok_or(ProviderError::HeaderNotFound(number.into()))?;
+
+            let mut divergences = compare_headers(&local, &remote);
+
+            match provider.receipts_by_block(number.into())? {
+                Some(receipts) if receipts_available(&provider, number, &receipts)? => {
+                    let receipts_root = receipts_root(&chain_spec, &local, receipts);
+                    if receipts_root != remote.receipts_root {
+                        divergences.push(Divergence::new(
+                            "receipts root of the local receipts",
+                            receipts_root,
+                            remote.receipts_root,
+                        ));
+                    }
+                    summary.receipts_roots += 1;
+                }
+                _ => summary.receipts_unavailable += 1,
+            }
+
+            let is_selected =
+                number == to || state_root_interval.is_some_and(|interval| number % interval == 0);
+            if is_selected && number <= state_tip {
+                let state_root = provider_factory
+                    .history_by_block_number(number)?
+                    .state_root(&BundleState::default())?;
+                if state_root != remote.state_root {
+                    divergences.push(Divergence::new(
+                        "state root of the local state",
+                        state_root,
+                        remote.state_root,
+                    ));
+                }
+                summary.state_roots += 1;
+            }
+
+            if !divergences.is_empty() {
+                println!("Block {number} diverges from the RPC node:");
+                for divergence in &divergences {
+                    println!("  {divergence}");
+                }
+                eyre::bail!("the local chain diverges from the RPC node at block {number}")
+            }
+
+            summary.headers += 1;
+            if number % PROGRESS_INTERVAL == 0 {
+                info!(target: "reth::cli", number, to, "Verified blocks");
+            }
+            number += 1;
+        }
+
+        println!("Verified blocks {}..={to} against {}:", self.from, self.rpc_url);
+        println!("{summary}");
+        Ok(())
+    }
+}
+
+/// Returns the differences between the local and the remote header.
+///
+/// If the hashes match, the headers are equal and no fields are compared.
+fn compare_headers(local: &SealedHeader, remote: &RpcHeader) -> Vec<Divergence> {
+    let remote_hash = remote.hash.unwrap_or_default();
+    if local.hash() == remote_hash {
+        return Vec::new()
+    }
+
+    let mut divergences = vec![Divergence::new("hash", local.hash(), remote_hash)];
+    for (field, local, remote) in [
+        ("parent hash", local.parent_hash, remote.parent_hash),
+        ("state root", local.state_root, remote.state_root),
+        ("transactions root", local.transactions_root, remote.transactions_root),
+        ("receipts root", local.receipts_root, remote.receipts_root),
+    ] {
+        if local != remote {
+            divergences.push(Divergence::new(field, local, remote));
+        }
+    }
+    if u128::from(local.gas_used) != remote.gas_used {
+        divergences.push(Divergence::new("gas used", local.gas_used, remote.gas_used));
+    }
+    divergences
+}
+
+/// Returns whether the local receipts of the block are complete, receipts may have been pruned or
+/// not been imported.
+fn receipts_available(
+    provider: &impl BlockReader,
+    number: BlockNumber,
+    receipts: &[Receipt],
+) -> eyre::Result<bool> {
+    let tx_count = provider
+        .block_body_indices(number)?
+        .map(|indices| indices.tx_count)
+        .ok_or(ProviderError::BlockBodyIndicesNotFound(number))?;
+    Ok(receipts.len() as u64 == tx_count)
+}
+
+/// Computes the receipts root of the block from its receipts.
+fn receipts_root(chain_spec: &ChainSpec, header: &SealedHeader, receipts: Vec<Receipt>) -> B256 {
+    let receipts = Receipts::from(receipts);
+
+    #[cfg(feature = "optimism")]
+    let receipts_root = receipts.optimism_root_slow(0, chain_spec, header.timestamp);
+
+    #[cfg(not(feature = "optimism"))]
+    let receipts_root = {
+        let _ = (chain_spec, header);
+        receipts.root_slow(0)
+    };
+
+    receipts_root.expect("receipts are present")
+}
+
+/// A field of a block that differs between the local and the remote chain.
+#[derive(Debug)]
+struct Divergence {
+    field: &'static str,
+    local: String,
+    remote: String,
+}
+
+impl Divergence {
+    fn new(field: &'static str, local: impl fmt::Display, remote: impl fmt::Display) -> Self {
+        Self { field, local: local.to_string(), remote: remote.to_string() }
+    }
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: local {}, remote {}", self.field, self.local, self.remote)
+    }
+}
+
+/// The number of verified items.
+#[derive(Debug, Default)]
+struct Summary {
+    headers: u64,
+    receipts_roots: u64,
+    receipts_unavailable: u64,
+    state_roots: u64,
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "  headers:        {}", self.headers)?;
+        writeln!(
+            f,
+            "  receipts roots: {} ({} blocks without local receipts)",
+            self.receipts_roots, self.receipts_unavailable
+        )?;
+        write!(f, "  state roots:    {}", self.state_roots)
+    }
+}
@@ -0,0 +1,28 @@
+//! `reth verify` command. Verifies the local chain against other sources.
+
+use clap::{Parser, Subcommand};
+
+mod against_rpc;
+
+/// `reth verify` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth verify` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands {
+    /// Compare a range of local blocks with the blocks of a trusted RPC node.
+    AgainstRpc(against_rpc::Command),
+}
+
+impl Command {
+    /// Execute `verify` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::AgainstRpc(command) => command.execute().await,
+        }
+    }
+}
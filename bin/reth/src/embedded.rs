@@ -0,0 +1,333 @@
+//! Running the node from another Rust program.
+//!
+//! [`EmbeddedNode`] launches the same components as the `reth` binary, but returns handles instead
+//! of blocking until the process is signaled:
+//!
+//! - [`EmbeddedNode::launch`] runs a full node,
+//! - [`EmbeddedNode::launch_rpc`] serves the RPC from a datadir opened read-only, without network,
+//!   transaction pool or pipeline,
+//! - [`EmbeddedNode::import`] imports a chain from a file into the datadir.
+//!
+//! ```no_run
+//! use reth::embedded::EmbeddedNode;
+//! use reth_node_core::node_config::NodeConfig;
+//! use reth_node_ethereum::EthereumNode;
+//! use std::time::Duration;
+//!
+//! # async fn run() -> eyre::Result<()> {
+//! let handle = EmbeddedNode::new(NodeConfig::default()).launch(EthereumNode::default()).await?;
+//! let mut blocks = handle.subscribe_to_canonical_state();
+//! while let Ok(notification) = blocks.recv().await {
+//!     println!("new tip: {}", notification.tip().number);
+//! }
+//! handle.shutdown(Duration::from_secs(10));
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    builder::{
+        FullNode, FullNodeComponents, Node, NodeAdapter, NodeBuilder, NodeComponentsBuilder,
+        NodeConfig, RethFullAdapter,
+    },
+    commands::{import::build_import_pipeline, node::read_only},
+    macros::block_executor,
+};
+use futures::StreamExt;
+use reth_beacon_consensus::EthBeaconConsensus;
+use reth_config::{config::EtlConfig, Config};
+use reth_db::{init_db, DatabaseEnv};
+use reth_db_common::init::init_genesis;
+use reth_downloaders::file_client::ChunkedFileReader;
+use reth_node_core::{
+    exit::NodeExitFuture,
+    layout::{check_layout_version, init_layout_version},
+};
+use reth_node_events::node::NodeEvent;
+use reth_primitives::BlockNumber;
+use reth_provider::{
+    providers::{BlockchainProvider, StaticFileProvider},
+    CanonStateNotifications, CanonStateSubscriptions, ProviderFactory, StageCheckpointReader,
+};
+use reth_prune_types::PruneModes;
+use reth_rpc_builder::RpcServerHandle;
+use reth_stages::StageId;
+use reth_static_file::StaticFileProducer;
+use reth_tasks::{PanickedTaskError, TaskManager};
+use std::{fmt, path::PathBuf, sync::Arc, time::Duration};
+use tokio::{
+    sync::{mpsc, oneshot},
+    task::JoinHandle,
+};
+use tracing::info;
+
+/// Launches the node, or parts of it, from the given [`NodeConfig`].
+///
+/// The tasks of the node are spawned on the tokio runtime this is created on and are owned by the
+/// returned handles: they are shut down once the handle is shut down or dropped.
+#[derive(Debug)]
+pub struct EmbeddedNode {
+    config: NodeConfig,
+    tasks: TaskManager,
+}
+
+impl EmbeddedNode {
+    /// Creates a new embedded node with the given config.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if called outside the context of a Tokio runtime.
+    pub fn new(config: NodeConfig) -> Self {
+        Self { config, tasks: TaskManager::current() }
+    }
+
+    /// Returns the config the node is launched with.
+    pub const fn config(&self) -> &NodeConfig {
+        &self.config
+    }
+
+    /// Launches a full node of the given type.
+    ///
+    /// This initializes the datadir if needed, opens the database read-write and launches all
+    /// components of the node, like the `reth node` command does.
+    pub async fn launch<N>(
+        self,
+        node: N,
+    ) -> eyre::Result<
+        EmbeddedNodeHandle<
+            NodeAdapter<
+                RethFullAdapter<Arc<DatabaseEnv>, N>,
+                <N::ComponentsBuilder as NodeComponentsBuilder<
+                    RethFullAdapter<Arc<DatabaseEnv>, N>,
+                >>::Components,
+            >,
+        >,
+    >
+    where
+        N: Node<RethFullAdapter<Arc<DatabaseEnv>, N>>,
+    {
+        let Self { config, tasks } = self;
+
+        let data_dir = config.datadir();
+        init_layout_version(&data_dir)?;
+        let db_path = data_dir.db();
+        info!(target: "reth::embedded", path = ?db_path, "Opening database");
+        let database = Arc::new(init_db(db_path, config.db.database_args())?.with_metrics());
+
+        let handle = NodeBuilder::new(config)
+            .with_database(database)
+            .with_launch_context(tasks.executor())
+            .launch_node(node)
+            .await?;
+
+        Ok(EmbeddedNodeHandle {
+            node: handle.node,
+            node_exit_future: handle.node_exit_future,
+            tasks,
+        })
+    }
+
+    /// Serves the configured RPC modules from the datadir, which is opened read-only.
+    ///
+    /// No network, transaction pool, pipeline or Engine API is started, like with
+    /// `reth node --read-only`. The datadir can be in use by another node.
+    pub async fn launch_rpc(self) -> eyre::Result<EmbeddedRpcHandle> {
+        let Self { config, tasks } = self;
+
+        check_layout_version(&config.datadir())?;
+        let (provider, rpc) = read_only::serve(config, None, tasks.executor()).await?;
+
+        Ok(EmbeddedRpcHandle { provider, rpc, tasks })
+    }
+
+    /// Imports the RLP encoded blocks in the given file into the datadir.
+    ///
+    /// The import runs in the background. If `no_state` is set, stages that require state are
+    /// skipped, like with `reth import --no-state`.
+    pub async fn import(
+        self,
+        path: impl Into<PathBuf>,
+        no_state: bool,
+    ) -> eyre::Result<EmbeddedImportHandle> {
+        let Self { config: node_config, tasks } = self;
+        let path = path.into();
+
+        let data_dir = node_config.datadir();
+        init_layout_version(&data_dir)?;
+        let (db_path, sf_path) = (data_dir.db(), data_dir.static_files());
+        reth_fs_util::create_dir_all(&db_path)?;
+        reth_fs_util::create_dir_all(&sf_path)?;
+
+        let mut config: Config =
+            confy::load_path(node_config.config.clone().unwrap_or_else(|| data_dir.config()))
+                .unwrap_or_default();
+        if config.stages.etl.dir.is_none() {
+            config.stages.etl.dir = Some(EtlConfig::from_datadir(data_dir.data_dir()));
+        }
+
+        info!(target: "reth::embedded", ?db_path, ?sf_path, "Opening storage");
+        let provider_factory = ProviderFactory::new(
+            Arc::new(init_db(db_path, node_config.db.database_args())?),
+            node_config.chain.clone(),
+            StaticFileProvider::read_write(sf_path)?,
+        );
+        init_genesis(provider_factory.clone())?;
+
+        let consensus = Arc::new(EthBeaconConsensus::new(node_config.chain.clone()));
+        let executor = block_executor!(node_config.chain.clone());
+        let mut reader = ChunkedFileReader::new(&path, None).await?;
+
+        let (events_tx, events) = mpsc::unbounded_channel();
+        let (shutdown_tx, mut shutdown_rx) = oneshot::channel::<()>();
+
+        let task = tokio::spawn(async move {
+            while let Some(file_client) = reader.next_blocks().await? {
+                let tip = file_client.tip().ok_or(eyre::eyre!("file client has no tip"))?;
+
+                let (mut pipeline, mut pipeline_events) = build_import_pipeline(
+                    &config,
+                    provider_factory.clone(),
+                    &consensus,
+                    Arc::new(file_client),
+                    StaticFileProducer::new(provider_factory.clone(), PruneModes::default()),
+                    no_state,
+                    executor.clone(),
+                )
+                .await?;
+                pipeline.set_tip(tip);
+
+                let events_tx = events_tx.clone();
+                tokio::spawn(async move {
+                    while let Some(event) = pipeline_events.next().await {
+                        let _ = events_tx.send(event);
+                    }
+                });
+
+                // on shutdown, the pipeline is stopped once its current batch is committed
+                tokio::select! {
+                    res = pipeline.run() => res?,
+                    _ = &mut shutdown_rx => break,
+                }
+            }
+
+            Ok::<_, eyre::Report>(
+                provider_factory
+                    .provider()?
+                    .get_stage_checkpoint(StageId::Finish)?
+                    .unwrap_or_default()
+                    .block_number,
+            )
+        });
+
+        Ok(EmbeddedImportHandle { events, shutdown: shutdown_tx, task, tasks })
+    }
+}
+
+/// A handle to a full node launched with [`EmbeddedNode::launch`].
+pub struct EmbeddedNodeHandle<Node: FullNodeComponents> {
+    /// All components of the node.
+    pub node: FullNode<Node>,
+    node_exit_future: NodeExitFuture,
+    tasks: TaskManager,
+}
+
+impl<Node: FullNodeComponents> EmbeddedNodeHandle<Node> {
+    /// Returns a receiver of the canonical chain updates of the node, i.e. new canonical blocks
+    /// and reorgs.
+    pub fn subscribe_to_canonical_state(&self) -> CanonStateNotifications {
+        self.node.provider.subscribe_to_canonical_state()
+    }
+
+    /// Waits until the node exits.
+    ///
+    /// Returns once the node reached the configured `--debug.max-block`, `--debug.tip` or
+    /// `--debug.terminate` target, or with an error once a critical task of the node panicked.
+    /// Otherwise, this never returns.
+    pub async fn wait_for_exit(&mut self) -> eyre::Result<()> {
+        tokio::select! {
+            res = &mut self.node_exit_future => res,
+            err = &mut self.tasks => Err(err.into()),
+        }
+    }
+
+    /// Shuts down all tasks of the node and waits for them to finish, up to the given timeout.
+    ///
+    /// Returns true if all tasks finished before the timeout elapsed.
+    pub fn shutdown(self, timeout: Duration) -> bool {
+        self.tasks.graceful_shutdown_with_timeout(timeout)
+    }
+}
+
+impl<Node: FullNodeComponents> fmt::Debug for EmbeddedNodeHandle<Node> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EmbeddedNodeHandle")
+            .field("node", &"...")
+            .field("node_exit_future", &self.node_exit_future)
+            .field("tasks", &self.tasks)
+            .finish()
+    }
+}
+
+/// A handle to the RPC servers launched with [`EmbeddedNode::launch_rpc`].
+#[derive(Debug)]
+pub struct EmbeddedRpcHandle {
+    /// The provider over the read-only storage of the node.
+    pub provider: BlockchainProvider<Arc<DatabaseEnv>>,
+    /// The handle of the RPC servers.
+    pub rpc: RpcServerHandle,
+    tasks: TaskManager,
+}
+
+impl EmbeddedRpcHandle {
+    /// Waits until a critical task of the node panicked.
+    pub async fn wait_for_exit(&mut self) -> PanickedTaskError {
+        (&mut self.tasks).await
+    }
+
+    /// Stops the RPC servers and shuts down all tasks of the node, waiting for them to finish up
+    /// to the given timeout.
+    ///
+    /// Returns true if all tasks finished before the timeout elapsed.
+    pub fn shutdown(self, timeout: Duration) -> bool {
+        let _ = self.rpc.stop();
+        self.tasks.graceful_shutdown_with_timeout(timeout)
+    }
+}
+
+/// A handle to a chain import started with [`EmbeddedNode::import`].
+#[derive(Debug)]
+pub struct EmbeddedImportHandle {
+    events: mpsc::UnboundedReceiver<NodeEvent>,
+    shutdown: oneshot::Sender<()>,
+    task: JoinHandle<eyre::Result<BlockNumber>>,
+    tasks: TaskManager,
+}
+
+impl EmbeddedImportHandle {
+    /// Returns the next event of the import pipeline, or `None` once the import is done.
+    pub async fn next_event(&mut self) -> Option<NodeEvent> {
+        self.events.recv().await
+    }
+
+    /// Waits until all blocks of the file are imported.
+    ///
+    /// Returns the number of the last imported block.
+    pub async fn wait(self) -> eyre::Result<BlockNumber> {
+        let Self { task, tasks, .. } = self;
+        let res = task.await?;
+        tasks.graceful_shutdown();
+        res
+    }
+
+    /// Stops the import once the current batch is committed.
+    ///
+    /// Returns the number of the last imported block. Importing the same file again resumes the
+    /// import.
+    pub async fn shutdown(self) -> eyre::Result<BlockNumber> {
+        let Self { shutdown, task, tasks, .. } = self;
+        let _ = shutdown.send(());
+        let res = task.await?;
+        tasks.graceful_shutdown();
+        res
+    }
+}
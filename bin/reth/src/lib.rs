@@ -31,6 +31,7 @@
 
 pub mod cli;
 pub mod commands;
+pub mod embedded;
 mod macros;
 pub mod utils;
 
@@ -28,9 +28,11 @@ fn main() {
             .extend_rpc_modules(move |ctx| {
                 // register sequencer tx forwarder
                 if let Some(sequencer_http) = rollup_args.sequencer_http {
-                    ctx.registry.set_eth_raw_transaction_forwarder(Arc::new(SequencerClient::new(
-                        sequencer_http,
-                    )));
+                    let client = match &ctx.config().network.proxy {
+                        Some(proxy) => SequencerClient::with_proxy(sequencer_http, proxy)?,
+                        None => SequencerClient::new(sequencer_http),
+                    };
+                    ctx.registry.set_eth_raw_transaction_forwarder(Arc::new(client));
                 }
 
                 Ok(())
@@ -70,6 +70,18 @@ pub enum CanonicalError {
     /// Error indicating that a previous optimistic sync target was re-orged
     #[error("transaction error on revert: {0}")]
     OptimisticTargetRevert(BlockNumber),
+    /// Error indicating that canonicalization is halted by a reorg deeper than the configured
+    /// maximum.
+    #[error(
+        "canonicalization is halted by a reorg of {depth} blocks to block #{fork_block}, \
+         resume it with `reth unwind --force`"
+    )]
+    ReorgHalted {
+        /// The fork block of the reorg.
+        fork_block: BlockNumber,
+        /// The number of canonical blocks the reorg reverts.
+        depth: u64,
+    },
 }
 
 impl CanonicalError {
@@ -84,6 +96,11 @@ impl CanonicalError {
         matches!(self, Self::BlockchainTree(BlockchainTreeError::BlockHashNotFoundInChain { .. }))
     }
 
+    /// Returns `true` if the error matches [`CanonicalError::ReorgHalted`].
+    pub const fn is_reorg_halted(&self) -> bool {
+        matches!(self, Self::ReorgHalted { .. })
+    }
+
     /// Returns `Some(BlockNumber)` if the underlying error matches
     /// [`CanonicalError::OptimisticTargetRevert`].
     pub const fn optimistic_revert_block_number(&self) -> Option<BlockNumber> {
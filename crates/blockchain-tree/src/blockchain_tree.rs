@@ -10,7 +10,7 @@ use reth_blockchain_tree_api::{
     BlockAttachment, BlockStatus, BlockValidationKind, CanonicalOutcome, InsertPayloadOk,
 };
 use reth_consensus::{Consensus, ConsensusError};
-use reth_db_api::database::Database;
+use reth_db_api::{database::Database, models::StoredReorg};
 use reth_evm::execute::BlockExecutorProvider;
 use reth_execution_errors::{BlockExecutionError, BlockValidationError};
 use reth_execution_types::{Chain, ExecutionOutcome};
@@ -19,9 +19,10 @@ use reth_primitives::{
     SealedBlockWithSenders, SealedHeader, StaticFileSegment, B256, U256,
 };
 use reth_provider::{
-    BlockExecutionWriter, BlockNumReader, BlockWriter, CanonStateNotification,
-    CanonStateNotificationSender, CanonStateNotifications, ChainSpecProvider, ChainSplit,
-    ChainSplitTarget, DisplayBlocksChain, HeaderProvider, ProviderError, StaticFileProviderFactory,
+    BlockAccessListWriter, BlockExecutionWriter, BlockNumReader, BlockWriter,
+    CanonStateNotification, CanonStateNotificationSender, CanonStateNotifications,
+    ChainSpecProvider, ChainSplit, ChainSplitTarget, DisplayBlocksChain, HeaderProvider,
+    ProviderError, ReorgJournalWriter, StaticFileProviderFactory,
 };
 use reth_prune_types::PruneModes;
 use reth_stages_api::{MetricEvent, MetricEventsSender};
@@ -29,6 +30,7 @@ use reth_storage_errors::provider::{ProviderResult, RootMismatch};
 use std::{
     collections::{btree_map::Entry, BTreeMap, HashSet},
     sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
 };
 use tracing::{debug, error, info, instrument, trace, warn};
 
@@ -73,6 +75,10 @@ pub struct BlockchainTree<DB, E> {
     sync_metrics_tx: Option<MetricEventsSender>,
     /// Metrics for the blockchain tree.
     metrics: TreeMetrics,
+    /// The fork block of the reorg that halted canonicalization, if it's halted.
+    ///
+    /// See [`BlockchainTreeConfig::reorg_halt_depth`].
+    reorg_halt: Option<BlockNumber>,
 }
 
 impl<DB, E> BlockchainTree<DB, E> {
@@ -130,6 +136,11 @@ where
 
         let last_finalized_block_number = externals.fetch_latest_finalized_block_number()?;
 
+        let reorg_halt = externals.fetch_reorg_halt()?;
+        if let Some(fork_block) = reorg_halt {
+            error!(target: "blockchain_tree", fork_block, "Canonicalization is halted by a deep reorg, resume it with `reth unwind --force`");
+        }
+
         Ok(Self {
             externals,
             state: TreeState::new(
@@ -142,6 +153,7 @@ where
             canon_state_notification_sender,
             sync_metrics_tx: None,
             metrics: Default::default(),
+            reorg_halt,
         })
     }
 
@@ -1021,6 +1033,14 @@ where
     ) -> Result<CanonicalOutcome, CanonicalError> {
         let mut durations_recorder = MakeCanonicalDurationsRecorder::default();
 
+        if let Some(fork_block) = self.reorg_halt {
+            let tip = self.block_indices().canonical_tip();
+            return Err(CanonicalError::ReorgHalted {
+                fork_block,
+                depth: tip.number.saturating_sub(fork_block),
+            })
+        }
+
         let old_block_indices = self.block_indices().clone();
         let old_buffered_blocks = self.state.buffered_blocks.parent_to_child.clone();
         durations_recorder.record_relative(MakeCanonicalAction::CloneOldBlocks);
@@ -1062,6 +1082,8 @@ where
             }))
         };
 
+        self.check_reorg_depth(chain_id)?;
+
         // we are splitting chain at the block hash that we want to make canonical
         let Some(canonical) = self.remove_and_split_chain(chain_id, block_hash.into()) else {
             debug!(target: "blockchain_tree", ?block_hash, ?chain_id, "Chain not present");
@@ -1161,6 +1183,7 @@ where
 
             if let Some(old_canon_chain) = old_canon_chain {
                 self.update_reorg_metrics(old_canon_chain.len() as f64);
+                self.record_reorg(&old_canon_chain, &new_canon_chain);
 
                 // Insert old canonical chain back into tree.
                 self.insert_unwound_chain(AppendableChain::new(old_canon_chain.clone()));
@@ -1262,6 +1285,14 @@ where
             )
             .map_err(|e| CanonicalError::CanonicalCommit(e.to_string()))?;
 
+        if let Some(access_lists) = self.externals.executor_factory.access_list_recorder() {
+            for (number, hash) in block_hash_numbers {
+                if let Some(access_list) = access_lists.take(*number, hash) {
+                    provider_rw.insert_block_access_list(*number, access_list)?;
+                }
+            }
+        }
+
         provider_rw.commit()?;
         recorder.record_relative(MakeCanonicalAction::CommitCanonicalChainToDatabase);
 
@@ -1337,11 +1368,61 @@ where
         }
     }
 
+    /// Halts canonicalization if making the chain canonical reverts more canonical blocks than
+    /// the configured [`BlockchainTreeConfig::reorg_halt_depth`].
+    ///
+    /// The halt is persisted, so it survives restarts until the operator clears it.
+    fn check_reorg_depth(&mut self, chain_id: BlockchainId) -> Result<(), CanonicalError> {
+        let Some(max_depth) = self.config.reorg_halt_depth() else { return Ok(()) };
+        let Some(fork) = self.canonical_fork(chain_id) else { return Ok(()) };
+
+        let depth = self.block_indices().canonical_tip().number.saturating_sub(fork.number);
+        if depth <= max_depth {
+            return Ok(())
+        }
+
+        error!(
+            target: "blockchain_tree",
+            fork_block = fork.number,
+            depth,
+            max_depth,
+            "Reorg is deeper than the maximum reorg depth, halting canonicalization"
+        );
+        self.externals.save_reorg_halt(fork.number)?;
+        self.reorg_halt = Some(fork.number);
+        Err(CanonicalError::ReorgHalted { fork_block: fork.number, depth })
+    }
+
     fn update_reorg_metrics(&self, reorg_depth: f64) {
         self.metrics.reorgs.increment(1);
         self.metrics.latest_reorg_depth.set(reorg_depth);
     }
 
+    /// Appends the reorg from the old to the new canonical chain to the reorg journal.
+    ///
+    /// The reorg is already committed at this point, so failing to record it is only logged.
+    fn record_reorg(&self, old_chain: &Chain, new_chain: &Chain) {
+        let fork_block = new_chain.fork_block();
+        let reorg = StoredReorg {
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs(),
+            fork_block: fork_block.number,
+            fork_hash: fork_block.hash,
+            old_blocks: old_chain.blocks_iter().map(|block| block.hash()).collect(),
+            new_blocks: new_chain.blocks_iter().map(|block| block.hash()).collect(),
+        };
+        let depth = reorg.depth();
+
+        let result = self.externals.provider_factory.provider_rw().and_then(|provider| {
+            let id = provider.append_reorg(reorg)?;
+            provider.commit()?;
+            Ok(id)
+        });
+        match result {
+            Ok(id) => debug!(target: "blockchain_tree", id, depth, "Recorded reorg"),
+            Err(err) => error!(target: "blockchain_tree", %err, depth, "Failed to record reorg"),
+        }
+    }
+
     /// Update blockchain tree chains (canonical and sidechains) and sync metrics.
     ///
     /// NOTE: this method should not be called during the pipeline sync, because otherwise the sync
@@ -1390,7 +1471,7 @@ mod tests {
     };
     use reth_provider::{
         test_utils::{blocks::BlockchainTestData, create_test_provider_factory_with_chain_spec},
-        ProviderFactory,
+        ProviderFactory, ReorgHaltWriter, ReorgJournalReader,
     };
     use reth_stages_api::StageCheckpoint;
     use reth_trie::{root::state_root_unhashed, StateRoot};
@@ -2154,6 +2235,14 @@ mod tests {
             if *old.blocks() == BTreeMap::from([(block2.number,block2.clone())])
                 && *new.blocks() == BTreeMap::from([(block2a.number,block2a.clone())]));
 
+        // check the reorg journal.
+        let reorgs = tree.externals.provider_factory.reorgs(0, 10).unwrap();
+        assert_eq!(reorgs.len(), 1);
+        assert_eq!(reorgs[0].0, 0);
+        assert_eq!(reorgs[0].1.fork_hash, block1.hash());
+        assert_eq!(reorgs[0].1.old_blocks, vec![block2.hash()]);
+        assert_eq!(reorgs[0].1.new_blocks, vec![block2a_hash]);
+
         // Trie state:
         // b2a   b2 (side chain)
         // |   /
@@ -2421,4 +2510,59 @@ mod tests {
 
         assert_eq!(tree.block_indices().last_finalized_block(), block1a.number);
     }
+
+    #[test]
+    fn deep_reorg_halts_canonicalization() {
+        let data = BlockchainTestData::default_from_number(11);
+        let (block1, exec1) = data.blocks[0].clone();
+        let (block2, exec2) = data.blocks[1].clone();
+        let genesis = data.genesis;
+
+        // test pops execution results from vector, so order is from last to first.
+        let externals = setup_externals(vec![exec1.clone(), exec2, exec1]);
+        let cloned_externals_1 = TreeExternals {
+            provider_factory: externals.provider_factory.clone(),
+            executor_factory: externals.executor_factory.clone(),
+            consensus: externals.consensus.clone(),
+        };
+        let cloned_externals_2 = TreeExternals {
+            provider_factory: externals.provider_factory.clone(),
+            executor_factory: externals.executor_factory.clone(),
+            consensus: externals.consensus.clone(),
+        };
+        let provider_factory = externals.provider_factory.clone();
+
+        setup_genesis(&externals.provider_factory, genesis);
+
+        let config = BlockchainTreeConfig::new(1, 2, 3, 2).with_reorg_halt_depth(1);
+        let mut tree = BlockchainTree::new(externals, config, None).expect("failed to create tree");
+
+        tree.insert_block(block1.clone(), BlockValidationKind::Exhaustive).unwrap();
+        tree.insert_block(block2.clone(), BlockValidationKind::Exhaustive).unwrap();
+        tree.make_canonical(block2.hash()).unwrap();
+
+        // a fork of the genesis block reverts two canonical blocks
+        let mut block1a = block1;
+        block1a.set_hash(B256::new([0x33; 32]));
+        tree.insert_block(block1a.clone(), BlockValidationKind::Exhaustive).unwrap();
+        let halted = CanonicalError::ReorgHalted { fork_block: block1a.number - 1, depth: 2 };
+        assert_eq!(tree.make_canonical(block1a.hash()), Err(halted.clone()));
+        assert_eq!(tree.block_indices().canonical_tip().hash, block2.hash());
+
+        // the halt survives a restart
+        let mut tree =
+            BlockchainTree::new(cloned_externals_1, config, None).expect("failed to create tree");
+        assert_eq!(tree.make_canonical(block2.hash()), Err(halted));
+
+        // until the operator clears it
+        let provider_rw = provider_factory.provider_rw().unwrap();
+        assert_eq!(provider_rw.clear_reorg_halt().unwrap(), Some(block1a.number - 1));
+        provider_rw.commit().unwrap();
+        let mut tree =
+            BlockchainTree::new(cloned_externals_2, config, None).expect("failed to create tree");
+        assert_matches!(
+            tree.make_canonical(block2.hash()),
+            Ok(CanonicalOutcome::AlreadyCanonical { .. })
+        );
+    }
 }
@@ -201,6 +201,7 @@ impl AppendableChain {
             // will be open in parallel. See https://github.com/paradigmxyz/reth/issues/7509.
             .disable_long_read_transaction_safety()
             .state_provider_by_block_number(canonical_fork.number)?;
+        let state_provider = externals.provider_factory.with_fork_overlay(state_provider);
 
         let provider = BundleStateProvider::new(state_provider, bundle_state_data_provider);
 
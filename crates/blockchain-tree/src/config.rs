@@ -19,6 +19,9 @@ pub struct BlockchainTreeConfig {
     /// be 256. It covers both number of blocks required for reorg, and number of blocks
     /// required for `BLOCKHASH` EVM opcode.
     num_of_additional_canonical_block_hashes: u64,
+    /// The number of canonical blocks a reorg may revert before canonicalization is halted, if
+    /// limited.
+    reorg_halt_depth: Option<u64>,
 }
 
 impl Default for BlockchainTreeConfig {
@@ -33,6 +36,8 @@ impl Default for BlockchainTreeConfig {
             num_of_additional_canonical_block_hashes: 256,
             // max unconnected blocks.
             max_unconnected_blocks: 200,
+            // reorgs of any depth are canonicalized
+            reorg_halt_depth: None,
         }
     }
 }
@@ -54,9 +59,21 @@ impl BlockchainTreeConfig {
             max_reorg_depth,
             num_of_additional_canonical_block_hashes,
             max_unconnected_blocks,
+            reorg_halt_depth: None,
         }
     }
 
+    /// Halt canonicalization on reorgs that revert more than the given number of canonical
+    /// blocks.
+    ///
+    /// Unlike `max_reorg_depth`, which bounds the reorgs the tree keeps enough history for, this is
+    /// a safety limit: once exceeded, the halt is persisted and no block is canonicalized until
+    /// it's cleared by the operator.
+    pub const fn with_reorg_halt_depth(mut self, reorg_halt_depth: u64) -> Self {
+        self.reorg_halt_depth = Some(reorg_halt_depth);
+        self
+    }
+
     /// Return the maximum reorg depth.
     pub const fn max_reorg_depth(&self) -> u64 {
         self.max_reorg_depth
@@ -84,6 +101,12 @@ impl BlockchainTreeConfig {
         self.max_reorg_depth.max(self.num_of_additional_canonical_block_hashes)
     }
 
+    /// Return the number of canonical blocks a reorg may revert before canonicalization is
+    /// halted, if limited.
+    pub const fn reorg_halt_depth(&self) -> Option<u64> {
+        self.reorg_halt_depth
+    }
+
     /// Return max number of unconnected blocks that we are buffering
     pub const fn max_unconnected_blocks(&self) -> u32 {
         self.max_unconnected_blocks
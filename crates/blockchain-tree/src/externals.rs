@@ -5,8 +5,8 @@ use reth_db::{static_file::HeaderMask, tables};
 use reth_db_api::{cursor::DbCursorRO, database::Database, transaction::DbTx};
 use reth_primitives::{BlockHash, BlockNumber, StaticFileSegment};
 use reth_provider::{
-    FinalizedBlockReader, FinalizedBlockWriter, ProviderFactory, StaticFileProviderFactory,
-    StatsReader,
+    FinalizedBlockReader, FinalizedBlockWriter, ProviderFactory, ReorgHaltReader, ReorgHaltWriter,
+    StaticFileProviderFactory, StatsReader,
 };
 use reth_storage_errors::provider::ProviderResult;
 use std::{collections::BTreeMap, sync::Arc};
@@ -98,4 +98,15 @@ impl<DB: Database, E> TreeExternals<DB, E> {
         provider_rw.commit()?;
         Ok(())
     }
+
+    pub(crate) fn fetch_reorg_halt(&self) -> ProviderResult<Option<BlockNumber>> {
+        self.provider_factory.provider()?.reorg_halt()
+    }
+
+    pub(crate) fn save_reorg_halt(&self, fork_block: BlockNumber) -> ProviderResult<()> {
+        let provider_rw = self.provider_factory.provider_rw()?;
+        provider_rw.save_reorg_halt(fork_block)?;
+        provider_rw.commit()?;
+        Ok(())
+    }
 }
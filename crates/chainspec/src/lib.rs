@@ -12,11 +12,13 @@
 pub use alloy_chains::{Chain, ChainKind, NamedChain};
 pub use info::ChainInfo;
 pub use spec::{
-    BaseFeeParams, BaseFeeParamsKind, ChainSpec, ChainSpecBuilder, DepositContract,
-    ForkBaseFeeParams, DEV, GOERLI, HOLESKY, MAINNET, SEPOLIA,
+    BaseFeeParams, BaseFeeParamsKind, BlockHashHistory, BlockRewards, ChainSpec, ChainSpecBuilder,
+    DepositContract, ForkBaseFeeParams, GenesisConfigError, TreasuryPayout, DEV, GOERLI, HOLESKY,
+    MAINNET, SEPOLIA,
 };
 #[cfg(feature = "optimism")]
 pub use spec::{BASE_MAINNET, BASE_SEPOLIA, OP_MAINNET, OP_SEPOLIA};
+pub use update::{ChainSpecUpdateError, HardforkUpdate, ScheduledHardforks};
 
 #[cfg(not(feature = "std"))]
 extern crate alloc;
@@ -32,6 +34,9 @@ pub mod net;
 /// The chain spec module.
 mod spec;
 
+/// Changes of the hardfork schedule.
+mod update;
+
 /// Chain specific constants
 pub(crate) mod constants;
 
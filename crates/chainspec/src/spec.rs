@@ -1,6 +1,7 @@
-use crate::constants::MAINNET_DEPOSIT_CONTRACT;
+use crate::{constants::MAINNET_DEPOSIT_CONTRACT, ScheduledHardforks};
 #[cfg(not(feature = "std"))]
 use alloc::{
+    borrow::Cow,
     collections::BTreeMap,
     format,
     string::{String, ToString},
@@ -27,7 +28,7 @@ use reth_primitives_traits::{
 };
 use reth_trie_common::root::state_root_ref_unhashed;
 #[cfg(feature = "std")]
-use std::{collections::BTreeMap, sync::Arc};
+use std::{borrow::Cow, collections::BTreeMap, sync::Arc};
 
 #[cfg(feature = "optimism")]
 use crate::constants::optimism::{
@@ -65,6 +66,9 @@ pub static MAINNET: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
         )),
         base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
         prune_delete_limit: 3500,
+        block_rewards: BlockRewards::default(),
+        block_hash_history: BlockHashHistory::default(),
+        scheduled_hardforks: ScheduledHardforks::default(),
     }
     .into()
 });
@@ -89,6 +93,9 @@ pub static GOERLI: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
         )),
         base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
         prune_delete_limit: 1700,
+        block_rewards: BlockRewards::default(),
+        block_hash_history: BlockHashHistory::default(),
+        scheduled_hardforks: ScheduledHardforks::default(),
     }
     .into()
 });
@@ -113,6 +120,9 @@ pub static SEPOLIA: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
         )),
         base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
         prune_delete_limit: 1700,
+        block_rewards: BlockRewards::default(),
+        block_hash_history: BlockHashHistory::default(),
+        scheduled_hardforks: ScheduledHardforks::default(),
     }
     .into()
 });
@@ -135,6 +145,9 @@ pub static HOLESKY: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
         )),
         base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
         prune_delete_limit: 1700,
+        block_rewards: BlockRewards::default(),
+        block_hash_history: BlockHashHistory::default(),
+        scheduled_hardforks: ScheduledHardforks::default(),
     }
     .into()
 });
@@ -359,6 +372,16 @@ pub struct ChainSpec {
     /// the amount of blocks between pruner runs to account for the difference in amount of new
     /// data coming in.
     pub prune_delete_limit: usize,
+
+    /// The issuance rules applied after a block is executed
+    pub block_rewards: BlockRewards,
+
+    /// The ancestors whose hashes the `BLOCKHASH` opcode returns
+    pub block_hash_history: BlockHashHistory,
+
+    /// Hardfork activations that changed while the chain spec is in use, which take precedence
+    /// over the `hardforks`
+    pub scheduled_hardforks: ScheduledHardforks,
 }
 
 impl Default for ChainSpec {
@@ -372,6 +395,9 @@ impl Default for ChainSpec {
             deposit_contract: Default::default(),
             base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
             prune_delete_limit: MAINNET.prune_delete_limit,
+            block_rewards: Default::default(),
+            block_hash_history: Default::default(),
+            scheduled_hardforks: Default::default(),
         }
     }
 }
@@ -571,15 +597,21 @@ impl ChainSpec {
         }
     }
 
-    /// Returns the forks in this specification and their activation conditions.
-    pub const fn hardforks(&self) -> &BTreeMap<Hardfork, ForkCondition> {
-        &self.hardforks
+    /// Returns the forks in this specification and their activation conditions, including the
+    /// [scheduled](ChainSpec::schedule_hardforks) changes.
+    pub fn hardforks(&self) -> Cow<'_, BTreeMap<Hardfork, ForkCondition>> {
+        if self.scheduled_hardforks.is_empty() {
+            return Cow::Borrowed(&self.hardforks)
+        }
+        let mut hardforks = self.hardforks.clone();
+        self.scheduled_hardforks.apply(&mut hardforks);
+        Cow::Owned(hardforks)
     }
 
     /// Returns the hardfork display helper.
     pub fn display_hardforks(&self) -> DisplayHardforks {
         DisplayHardforks::new(
-            self.hardforks(),
+            &self.hardforks(),
             self.paris_block_and_final_difficulty.map(|(block, _)| block),
         )
     }
@@ -614,12 +646,66 @@ impl ChainSpec {
 
     /// Get the fork condition for the given fork.
     pub fn fork(&self, fork: Hardfork) -> ForkCondition {
-        self.hardforks.get(&fork).copied().unwrap_or(ForkCondition::Never)
+        self.scheduled_hardforks
+            .get(fork)
+            .or_else(|| self.hardforks.get(&fork).copied())
+            .unwrap_or(ForkCondition::Never)
     }
 
     /// Get an iterator of all hardforks with their respective activation conditions.
     pub fn forks_iter(&self) -> impl Iterator<Item = (Hardfork, ForkCondition)> + '_ {
-        self.hardforks.iter().map(|(f, b)| (*f, *b))
+        let (configured, scheduled) = match self.hardforks() {
+            Cow::Borrowed(hardforks) => (Some(hardforks.iter().map(|(f, b)| (*f, *b))), None),
+            Cow::Owned(hardforks) => (None, Some(hardforks.into_iter())),
+        };
+        configured.into_iter().flatten().chain(scheduled.into_iter().flatten())
+    }
+
+    /// Overrides the activation condition of the given hardfork.
+    ///
+    /// The genesis header depends on the hardforks active at genesis, so if the override changes
+    /// whether the fork is active at genesis, the cached genesis hash is cleared and recomputed
+    /// from the genesis on the next access.
+    pub fn set_fork_condition(&mut self, fork: Hardfork, condition: ForkCondition) {
+        let active_at_genesis = |condition: ForkCondition| {
+            condition.active_at_block(0) || condition.active_at_timestamp(self.genesis.timestamp)
+        };
+        if active_at_genesis(self.fork(fork)) != active_at_genesis(condition) {
+            self.genesis_hash = None;
+        }
+
+        if condition == ForkCondition::Never {
+            self.hardforks.remove(&fork);
+        } else {
+            self.hardforks.insert(fork, condition);
+        }
+    }
+
+    /// Returns the first pair of hardforks that activate out of order, if any.
+    ///
+    /// Hardforks are expected to activate in the order they are declared in [`Hardfork`]. Only
+    /// forks activated by the same kind of condition, block number or timestamp, are compared.
+    /// The returned tuple is `(earlier fork, later fork)`, where the later fork activates before
+    /// the earlier one.
+    pub fn misordered_hardforks(&self) -> Option<(Hardfork, Hardfork)> {
+        let mut last_block: Option<(Hardfork, u64)> = None;
+        let mut last_timestamp: Option<(Hardfork, u64)> = None;
+
+        for (fork, condition) in self.forks_iter() {
+            let (last, activation) = match condition {
+                ForkCondition::Block(block) => (&mut last_block, block),
+                ForkCondition::Timestamp(timestamp) => (&mut last_timestamp, timestamp),
+                ForkCondition::TTD { .. } | ForkCondition::Never => continue,
+            };
+            if let Some((previous, previous_activation)) = *last {
+                if activation < previous_activation {
+                    return Some((previous, fork))
+                }
+            }
+            *last = Some((fork, activation));
+        }
+
+        None
     }
 
     /// Convenience method to check if a fork is active at a given timestamp.
@@ -832,8 +918,55 @@ impl ChainSpec {
     }
 }
 
-impl From<Genesis> for ChainSpec {
-    fn from(genesis: Genesis) -> Self {
+/// Error returned when a genesis can't be converted into a [`ChainSpec`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror_no_std::Error)]
+pub enum GenesisConfigError {
+    /// A field of the genesis `config` that changes the rules of the chain is invalid.
+    #[error("invalid genesis config field {field}: {message}")]
+    InvalidField {
+        /// The name of the field.
+        field: &'static str,
+        /// Why the field is invalid.
+        message: String,
+    },
+}
+
+/// Reads an optional field of the genesis `config` that changes the rules of the chain, an invalid
+/// field is an error rather than ignored.
+#[cfg(feature = "serde")]
+fn genesis_config_field<T: serde::de::DeserializeOwned + Default>(
+    genesis: &Genesis,
+    field: &'static str,
+) -> Result<T, GenesisConfigError> {
+    genesis
+        .config
+        .extra_fields
+        .get_deserialized::<T>(field)
+        .transpose()
+        .map(Option::unwrap_or_default)
+        .map_err(|err| GenesisConfigError::InvalidField { field, message: err.to_string() })
+}
+
+/// Reads an optional field of the genesis `config` that changes the rules of the chain, which
+/// can't be parsed without the `serde` feature.
+#[cfg(not(feature = "serde"))]
+fn genesis_config_field<T: Default>(
+    genesis: &Genesis,
+    field: &'static str,
+) -> Result<T, GenesisConfigError> {
+    if genesis.config.extra_fields.contains_key(field) {
+        return Err(GenesisConfigError::InvalidField {
+            field,
+            message: "parsing it requires the serde feature of reth-chainspec".to_string(),
+        })
+    }
+    Ok(T::default())
+}
+
+impl TryFrom<Genesis> for ChainSpec {
+    type Error = GenesisConfigError;
+
+    fn try_from(genesis: Genesis) -> Result<Self, Self::Error> {
         #[cfg(feature = "optimism")]
         let optimism_genesis_info = OptimismGenesisInfo::extract_from(&genesis);
 
@@ -908,17 +1041,26 @@ impl From<Genesis> for ChainSpec {
             DepositContract { address, block: 0, topic: MAINNET_DEPOSIT_CONTRACT.topic }
         });
 
-        Self {
+        // Issuance rules of chains that deviate from mainnet
+        let block_rewards = genesis_config_field::<BlockRewards>(&genesis, "blockRewards")?;
+
+        // Chains serving more block hashes than the last 256
+        let block_hash_history =
+            genesis_config_field::<BlockHashHistory>(&genesis, "blockHashHistory")?;
+
+        Ok(Self {
             chain: genesis.config.chain_id.into(),
             genesis,
             genesis_hash: None,
             hardforks,
             paris_block_and_final_difficulty,
             deposit_contract,
+            block_rewards,
+            block_hash_history,
             #[cfg(feature = "optimism")]
             base_fee_params: optimism_genesis_info.base_fee_params,
             ..Default::default()
-        }
+        })
     }
 }
 
@@ -928,6 +1070,8 @@ pub struct ChainSpecBuilder {
     chain: Option<Chain>,
     genesis: Option<Genesis>,
     hardforks: BTreeMap<Hardfork, ForkCondition>,
+    block_rewards: BlockRewards,
+    block_hash_history: BlockHashHistory,
 }
 
 impl ChainSpecBuilder {
@@ -937,6 +1081,8 @@ impl ChainSpecBuilder {
             chain: Some(MAINNET.chain),
             genesis: Some(MAINNET.genesis.clone()),
             hardforks: MAINNET.hardforks.clone(),
+            block_rewards: MAINNET.block_rewards.clone(),
+            block_hash_history: MAINNET.block_hash_history,
         }
     }
 
@@ -946,6 +1092,18 @@ impl ChainSpecBuilder {
         self
     }
 
+    /// Set the issuance rules applied after a block is executed
+    pub fn block_rewards(mut self, block_rewards: BlockRewards) -> Self {
+        self.block_rewards = block_rewards;
+        self
+    }
+
+    /// Set the ancestors whose hashes the `BLOCKHASH` opcode returns
+    pub const fn block_hash_history(mut self, block_hash_history: BlockHashHistory) -> Self {
+        self.block_hash_history = block_hash_history;
+        self
+    }
+
     /// Set the genesis block.
     pub fn genesis(mut self, genesis: Genesis) -> Self {
         self.genesis = Some(genesis);
@@ -1133,6 +1291,8 @@ impl ChainSpecBuilder {
             hardforks: self.hardforks,
             paris_block_and_final_difficulty,
             deposit_contract: None,
+            block_rewards: self.block_rewards,
+            block_hash_history: self.block_hash_history,
             ..Default::default()
         }
     }
@@ -1143,7 +1303,9 @@ impl From<&Arc<ChainSpec>> for ChainSpecBuilder {
         Self {
             chain: Some(value.chain),
             genesis: Some(value.genesis.clone()),
-            hardforks: value.hardforks.clone(),
+            hardforks: value.hardforks().into_owned(),
+            block_rewards: value.block_rewards.clone(),
+            block_hash_history: value.block_hash_history,
         }
     }
 }
@@ -1166,6 +1328,207 @@ impl DepositContract {
     }
 }
 
+/// Issuance rules applied after the transactions of a block are executed.
+///
+/// The default rules are the ones of Ethereum mainnet. Chains with a different issuance can
+/// configure them in the `blockRewards` field of the genesis `config`, e.g.
+///
+/// ```json
+/// "blockRewards": {
+///     "blockReward": "2000000000000000000",
+///     "ommerRewards": false,
+///     "feeRecipient": "0x4200000000000000000000000000000000000011",
+///     "treasury": [{ "address": "0x...", "amount": "0x16345785d8a0000", "fromBlock": 100 }]
+/// }
+/// ```
+///
+/// Amounts of wei are decimal or `0x` prefixed hex strings, since JSON numbers above 2^64 lose
+/// precision.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+pub struct BlockRewards {
+    /// Fixed reward paid for every block, replacing the Ethash reward schedule that ends at the
+    /// merge. A reward of zero disables block rewards.
+    #[cfg_attr(feature = "serde", serde(with = "wei_amount::opt"))]
+    pub block_reward: Option<u128>,
+    /// Whether ommers and their inclusion are rewarded.
+    pub ommer_rewards: bool,
+    /// Recipient of the block reward, instead of the block beneficiary.
+    pub reward_recipient: Option<Address>,
+    /// Recipient of the priority fees of the transactions, instead of the block beneficiary.
+    ///
+    /// The `COINBASE` opcode still returns the block beneficiary.
+    pub fee_recipient: Option<Address>,
+    /// Fixed payouts credited after every block.
+    pub treasury: Vec<TreasuryPayout>,
+}
+
+impl BlockRewards {
+    /// Returns the treasury payouts active at the given block.
+    pub fn treasury_payouts_at(
+        &self,
+        block_number: BlockNumber,
+    ) -> impl Iterator<Item = &TreasuryPayout> + '_ {
+        self.treasury.iter().filter(move |payout| payout.is_active_at(block_number))
+    }
+}
+
+impl Default for BlockRewards {
+    fn default() -> Self {
+        Self {
+            block_reward: None,
+            ommer_rewards: true,
+            reward_recipient: None,
+            fee_recipient: None,
+            treasury: Vec::new(),
+        }
+    }
+}
+
+/// The ancestors of a block whose hashes the `BLOCKHASH` opcode returns.
+///
+/// By default these are the last 256 blocks, as on Ethereum mainnet. The hashes are read from the
+/// stored headers, so chains can serve a longer window, e.g. the 8192 blocks of the
+/// [EIP-2935](https://eips.ethereum.org/EIPS/eip-2935) history storage contract once Prague is
+/// active, by configuring it in the `blockHashHistory` field of the genesis `config`:
+///
+/// ```json
+/// "blockHashHistory": { "window": 256, "pragueWindow": 8192 }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+pub struct BlockHashHistory {
+    /// The number of ancestors whose hashes are returned.
+    pub window: u64,
+    /// The number of ancestors whose hashes are returned once Prague is active, instead of
+    /// `window`.
+    pub prague_window: Option<u64>,
+}
+
+impl BlockHashHistory {
+    /// The number of ancestors whose hashes are returned on Ethereum mainnet.
+    pub const DEFAULT_WINDOW: u64 = 256;
+
+    /// Returns the number of ancestors whose hashes are returned, depending on whether Prague is
+    /// active.
+    pub const fn window(&self, is_prague: bool) -> u64 {
+        match self.prague_window {
+            Some(window) if is_prague => window,
+            _ => self.window,
+        }
+    }
+
+    /// Returns `true` if the hashes of more or fewer ancestors than on Ethereum mainnet are
+    /// returned.
+    pub fn is_custom(&self) -> bool {
+        *self != Self::default()
+    }
+}
+
+impl Default for BlockHashHistory {
+    fn default() -> Self {
+        Self { window: Self::DEFAULT_WINDOW, prague_window: None }
+    }
+}
+
+/// A fixed amount credited to an address after every block in a range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct TreasuryPayout {
+    /// The address credited.
+    pub address: Address,
+    /// The amount credited per block, in wei.
+    #[cfg_attr(feature = "serde", serde(with = "wei_amount"))]
+    pub amount: u128,
+    /// The first block of the payout.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub from_block: BlockNumber,
+    /// The last block of the payout, if it ends.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub until_block: Option<BlockNumber>,
+}
+
+impl TreasuryPayout {
+    /// Returns `true` if the payout is made for the given block.
+    pub fn is_active_at(&self, block_number: BlockNumber) -> bool {
+        block_number >= self.from_block &&
+            self.until_block.map_or(true, |until_block| block_number <= until_block)
+    }
+}
+
+/// (De)serializes an amount of wei as a decimal or `0x` prefixed hex string.
+///
+/// JSON numbers are accepted as long as they fit in a `u64`, larger numbers are usually parsed as
+/// floats and lose precision.
+#[cfg(feature = "serde")]
+mod wei_amount {
+    use super::*;
+    use core::fmt;
+    use serde::{de, Deserializer, Serializer};
+
+    pub(super) fn serialize<S: Serializer>(
+        amount: &u128,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&amount.to_string())
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<u128, D::Error> {
+        deserializer.deserialize_any(WeiAmountVisitor)
+    }
+
+    struct WeiAmountVisitor;
+
+    impl<'de> de::Visitor<'de> for WeiAmountVisitor {
+        type Value = u128;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("an amount of wei as a decimal or 0x prefixed hex string")
+        }
+
+        fn visit_u64<E: de::Error>(self, amount: u64) -> Result<u128, E> {
+            Ok(amount.into())
+        }
+
+        fn visit_str<E: de::Error>(self, amount: &str) -> Result<u128, E> {
+            let parsed = amount
+                .parse::<U256>()
+                .map_err(|err| E::custom(format!("invalid amount {amount}: {err}")))?;
+            parsed.try_into().map_err(|_| E::custom(format!("amount {amount} exceeds 128 bits")))
+        }
+    }
+
+    /// (De)serializes an optional amount of wei.
+    pub(super) mod opt {
+        use super::{Deserializer, Serializer};
+        use serde::Deserialize;
+
+        pub(in super::super) fn serialize<S: Serializer>(
+            amount: &Option<u128>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match amount {
+                Some(amount) => super::serialize(amount, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub(in super::super) fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<u128>, D::Error> {
+            #[derive(serde::Deserialize)]
+            struct Amount(#[serde(with = "super")] u128);
+
+            Ok(Option::<Amount>::deserialize(deserializer)?.map(|Amount(amount)| amount))
+        }
+    }
+}
+
 /// Genesis info for Optimism.
 #[cfg(feature = "optimism")]
 #[derive(Default, Debug, serde::Deserialize)]
@@ -2276,7 +2639,7 @@ Post-merge hard forks (timestamp based):
         "#;
 
         let genesis: Genesis = serde_json::from_str(geth_genesis).unwrap();
-        let chainspec = ChainSpec::from(genesis);
+        let chainspec = ChainSpec::try_from(genesis).unwrap();
 
         // assert a bunch of hardforks that should be set
         assert_eq!(
@@ -2417,7 +2780,7 @@ Post-merge hard forks (timestamp based):
         "#;
 
         let genesis = serde_json::from_str::<Genesis>(hive_json).unwrap();
-        let chainspec: ChainSpec = genesis.into();
+        let chainspec: ChainSpec = genesis.try_into().unwrap();
         assert_eq!(chainspec.genesis_hash, None);
         assert_eq!(chainspec.chain, Chain::from_named(NamedChain::Optimism));
         let expected_state_root: B256 =
@@ -2560,7 +2923,7 @@ Post-merge hard forks (timestamp based):
 
         // check that it deserializes properly
         let genesis: Genesis = serde_json::from_str(hive_paris).unwrap();
-        let chainspec = ChainSpec::from(genesis);
+        let chainspec = ChainSpec::try_from(genesis).unwrap();
 
         // make sure we are at ForkHash("bc0c2605") with Head post-cancun
         let expected_forkid = ForkId { hash: ForkHash([0xbc, 0x0c, 0x26, 0x05]), next: 0 };
@@ -2866,7 +3229,7 @@ Post-merge hard forks (timestamp based):
             })
         );
 
-        let chain_spec: ChainSpec = genesis.into();
+        let chain_spec: ChainSpec = genesis.try_into().unwrap();
 
         assert_eq!(
             chain_spec.base_fee_params,
@@ -2928,7 +3291,7 @@ Post-merge hard forks (timestamp based):
             })
         );
 
-        let chain_spec: ChainSpec = genesis.into();
+        let chain_spec: ChainSpec = genesis.try_into().unwrap();
 
         assert_eq!(
             chain_spec.base_fee_params,
@@ -2987,7 +3350,7 @@ Post-merge hard forks (timestamp based):
     }
     "#;
         let genesis: Genesis = serde_json::from_str(geth_genesis).unwrap();
-        let chainspec = ChainSpec::from(genesis.clone());
+        let chainspec = ChainSpec::try_from(genesis.clone()).unwrap();
 
         let actual_chain_id = genesis.config.chain_id;
         assert_eq!(actual_chain_id, 8453);
@@ -3025,4 +3388,82 @@ Post-merge hard forks (timestamp based):
 
         assert!(chainspec.is_fork_active_at_timestamp(Hardfork::Regolith, 20));
     }
+
+    #[test]
+    fn override_fork_condition() {
+        let mut spec = (*MAINNET).clone();
+        assert!(spec.genesis_hash.is_some());
+
+        // moving a fork that is not active at genesis keeps the genesis hash
+        spec.set_fork_condition(Hardfork::Cancun, ForkCondition::Timestamp(1_800_000_000));
+        assert_eq!(spec.fork(Hardfork::Cancun), ForkCondition::Timestamp(1_800_000_000));
+        assert_eq!(spec.genesis_hash(), MAINNET.genesis_hash());
+
+        // activating a fork at genesis invalidates the cached genesis hash
+        spec.set_fork_condition(Hardfork::Shanghai, ForkCondition::Timestamp(0));
+        assert!(spec.genesis_hash.is_none());
+        assert_ne!(spec.genesis_hash(), MAINNET.genesis_hash());
+
+        spec.set_fork_condition(Hardfork::Prague, ForkCondition::Never);
+        assert!(!spec.hardforks().contains_key(&Hardfork::Prague));
+    }
+
+    #[test]
+    fn detect_misordered_hardforks() {
+        assert_eq!(MAINNET.misordered_hardforks(), None);
+        assert_eq!(SEPOLIA.misordered_hardforks(), None);
+        assert_eq!(HOLESKY.misordered_hardforks(), None);
+
+        let mut spec = (*MAINNET).clone();
+        spec.set_fork_condition(Hardfork::Cancun, ForkCondition::Timestamp(1));
+        assert_eq!(spec.misordered_hardforks(), Some((Hardfork::Shanghai, Hardfork::Cancun)));
+
+        let mut spec = (*MAINNET).clone();
+        spec.set_fork_condition(Hardfork::Berlin, ForkCondition::Block(20_000_000));
+        assert_eq!(spec.misordered_hardforks(), Some((Hardfork::Berlin, Hardfork::London)));
+    }
+
+    #[test]
+    fn parse_genesis_block_rewards() {
+        let s = r#"{"config":{"chainId":1337,"homesteadBlock":0,"blockRewards":{"blockReward":"1000000000000000000","ommerRewards":false,"feeRecipient":"0x4200000000000000000000000000000000000011","treasury":[{"address":"0x00000000000000000000000000000000000000aa","amount":"0x56bc75e2d63100000","fromBlock":10,"untilBlock":20}]}},"nonce":"0x0","timestamp":"0x0","extraData":"0x","gasLimit":"0x4c4b40","difficulty":"0x1","mixHash":"0x0000000000000000000000000000000000000000000000000000000000000000","coinbase":"0x0000000000000000000000000000000000000000","alloc":{}}"#;
+        let genesis: Genesis = serde_json::from_str(s).unwrap();
+        let chainspec = ChainSpec::try_from(genesis).unwrap();
+
+        let rules = &chainspec.block_rewards;
+        assert_eq!(rules.block_reward, Some(1_000_000_000_000_000_000));
+        assert!(!rules.ommer_rewards);
+        assert_eq!(rules.reward_recipient, None);
+        assert_eq!(rules.fee_recipient, Some(address!("4200000000000000000000000000000000000011")));
+        assert_eq!(rules.treasury_payouts_at(9).count(), 0);
+        assert_eq!(rules.treasury_payouts_at(10).count(), 1);
+        assert_eq!(rules.treasury_payouts_at(20).count(), 1);
+        assert_eq!(rules.treasury_payouts_at(21).count(), 0);
+        assert_eq!(rules.treasury[0].amount, 100_000_000_000_000_000_000);
+
+        // chains without custom rules follow mainnet issuance
+        assert_eq!(MAINNET.block_rewards, BlockRewards::default());
+        assert!(BlockRewards::default().ommer_rewards);
+
+        // invalid rules are rejected instead of falling back to mainnet issuance
+        let s = s.replace(r#""blockReward":"1000000000000000000""#, r#""blockReward":"ten""#);
+        let genesis: Genesis = serde_json::from_str(&s).unwrap();
+        let err = ChainSpec::try_from(genesis).unwrap_err();
+        assert!(matches!(err, GenesisConfigError::InvalidField { field: "blockRewards", .. }));
+    }
+
+    #[test]
+    fn parse_genesis_block_hash_history() {
+        let s = r#"{"config":{"chainId":1337,"homesteadBlock":0,"blockHashHistory":{"pragueWindow":8192}},"nonce":"0x0","timestamp":"0x0","extraData":"0x","gasLimit":"0x4c4b40","difficulty":"0x1","mixHash":"0x0000000000000000000000000000000000000000000000000000000000000000","coinbase":"0x0000000000000000000000000000000000000000","alloc":{}}"#;
+        let genesis: Genesis = serde_json::from_str(s).unwrap();
+        let chainspec = ChainSpec::try_from(genesis).unwrap();
+
+        let history = chainspec.block_hash_history;
+        assert!(history.is_custom());
+        assert_eq!(history.window(false), 256);
+        assert_eq!(history.window(true), 8192);
+
+        // chains without a custom window serve the last 256 block hashes
+        assert!(!MAINNET.block_hash_history.is_custom());
+        assert_eq!(MAINNET.block_hash_history.window(true), 256);
+    }
 }
@@ -0,0 +1,302 @@
+use crate::ChainSpec;
+#[cfg(not(feature = "std"))]
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+use alloy_chains::Chain;
+use alloy_primitives::{BlockNumber, B256};
+use reth_ethereum_forks::{ForkCondition, Hardfork, Head};
+#[cfg(feature = "std")]
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::{Arc, PoisonError, RwLock, RwLockReadGuard},
+};
+
+/// A changed activation condition of a hardfork.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HardforkUpdate {
+    /// The updated hardfork.
+    pub fork: Hardfork,
+    /// The previous activation condition.
+    pub from: ForkCondition,
+    /// The new activation condition.
+    pub to: ForkCondition,
+}
+
+/// Reason for rejecting an updated chain spec.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror_no_std::Error)]
+pub enum ChainSpecUpdateError {
+    /// The updated chain spec is for a different chain.
+    #[error("chain changed from {current} to {updated}")]
+    ChainMismatch {
+        /// The chain of the current chain spec.
+        current: Chain,
+        /// The chain of the updated chain spec.
+        updated: Chain,
+    },
+    /// The updated chain spec has a different genesis block.
+    #[error("genesis hash changed from {current} to {updated}")]
+    GenesisMismatch {
+        /// The genesis hash of the current chain spec.
+        current: B256,
+        /// The genesis hash of the updated chain spec.
+        updated: B256,
+    },
+    /// A part of the chain spec other than the hardfork activations changed.
+    #[error("{0} changed, only hardfork activations can be updated")]
+    Unsupported(&'static str),
+    /// The activation of a hardfork that is already active, or would become active, changed.
+    #[error("{fork} can't be changed from {from:?} to {to:?}, it is active at block {head}")]
+    ActiveHardfork {
+        /// The changed hardfork.
+        fork: Hardfork,
+        /// The current activation condition.
+        from: ForkCondition,
+        /// The updated activation condition.
+        to: ForkCondition,
+        /// The block number of the head the update was checked against.
+        head: BlockNumber,
+    },
+    /// The updated chain spec activates hardforks out of order.
+    #[error("{later} activates before {earlier}")]
+    MisorderedHardforks {
+        /// The hardfork that is expected to activate first.
+        earlier: Hardfork,
+        /// The hardfork that activates before `earlier`.
+        later: Hardfork,
+    },
+}
+
+/// Hardfork activations that changed while the chain spec is in use, see
+/// [`ChainSpec::schedule_hardforks`].
+///
+/// The schedule is shared by all clones of a chain spec, so every component of a node that holds
+/// the chain spec of the node, or a copy of it, observes the changed activations. Without the
+/// `std` feature the schedule is always empty.
+#[derive(Debug, Clone, Default)]
+pub struct ScheduledHardforks {
+    /// The changed activations, a fork that was removed is scheduled as [`ForkCondition::Never`].
+    #[cfg(feature = "std")]
+    forks: Arc<RwLock<BTreeMap<Hardfork, ForkCondition>>>,
+}
+
+#[cfg(feature = "std")]
+impl ScheduledHardforks {
+    fn read(&self) -> RwLockReadGuard<'_, BTreeMap<Hardfork, ForkCondition>> {
+        self.forks.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns `true` if no activation changed.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    /// Returns the changed activation of the given hardfork, if any.
+    pub(crate) fn get(&self, fork: Hardfork) -> Option<ForkCondition> {
+        self.read().get(&fork).copied()
+    }
+
+    /// Applies the changed activations to the given hardforks.
+    pub(crate) fn apply(&self, hardforks: &mut BTreeMap<Hardfork, ForkCondition>) {
+        for (fork, condition) in self.read().iter() {
+            if *condition == ForkCondition::Never {
+                hardforks.remove(fork);
+            } else {
+                hardforks.insert(*fork, *condition);
+            }
+        }
+    }
+
+    /// Records the given activations, replacing earlier changes of the same hardforks.
+    fn extend(&self, forks: impl IntoIterator<Item = (Hardfork, ForkCondition)>) {
+        self.forks.write().unwrap_or_else(PoisonError::into_inner).extend(forks);
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl ScheduledHardforks {
+    /// Returns `true` if no activation changed.
+    pub(crate) const fn is_empty(&self) -> bool {
+        true
+    }
+
+    /// Returns the changed activation of the given hardfork, if any.
+    pub(crate) const fn get(&self, _fork: Hardfork) -> Option<ForkCondition> {
+        None
+    }
+
+    /// Applies the changed activations to the given hardforks.
+    pub(crate) fn apply(&self, _hardforks: &mut BTreeMap<Hardfork, ForkCondition>) {}
+}
+
+impl PartialEq for ScheduledHardforks {
+    #[cfg(feature = "std")]
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.forks, &other.forks) || *self.read() == *other.read()
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for ScheduledHardforks {}
+
+impl ChainSpec {
+    /// Returns the hardfork activations that differ between this chain spec and the updated one.
+    ///
+    /// An update is only accepted if it changes activations that are in the future of the given
+    /// head, under both the current and the updated condition. Any other difference between the
+    /// two chain specs is rejected.
+    pub fn hardfork_updates(
+        &self,
+        updated: &Self,
+        head: &Head,
+    ) -> Result<Vec<HardforkUpdate>, ChainSpecUpdateError> {
+        if self.chain != updated.chain {
+            return Err(ChainSpecUpdateError::ChainMismatch {
+                current: self.chain,
+                updated: updated.chain,
+            })
+        }
+
+        let (current_genesis, updated_genesis) = (self.genesis_hash(), updated.genesis_hash());
+        if current_genesis != updated_genesis {
+            return Err(ChainSpecUpdateError::GenesisMismatch {
+                current: current_genesis,
+                updated: updated_genesis,
+            })
+        }
+
+        for (field, changed) in [
+            (
+                "paris block",
+                self.paris_block_and_final_difficulty != updated.paris_block_and_final_difficulty,
+            ),
+            ("deposit contract", self.deposit_contract != updated.deposit_contract),
+            ("base fee params", self.base_fee_params != updated.base_fee_params),
+            ("block rewards", self.block_rewards != updated.block_rewards),
+            ("block hash history", self.block_hash_history != updated.block_hash_history),
+            ("prune delete limit", self.prune_delete_limit != updated.prune_delete_limit),
+        ] {
+            if changed {
+                return Err(ChainSpecUpdateError::Unsupported(field))
+            }
+        }
+
+        let (current_forks, updated_forks) = (self.hardforks(), updated.hardforks());
+        let forks = current_forks.keys().chain(updated_forks.keys()).collect::<BTreeSet<_>>();
+        let mut updates = Vec::new();
+        for fork in forks {
+            let (from, to) = (self.fork(*fork), updated.fork(*fork));
+            if from == to {
+                continue
+            }
+            if from.active_at_head(head) || to.active_at_head(head) {
+                return Err(ChainSpecUpdateError::ActiveHardfork {
+                    fork: *fork,
+                    from,
+                    to,
+                    head: head.number,
+                })
+            }
+            updates.push(HardforkUpdate { fork: *fork, from, to });
+        }
+
+        if let Some((earlier, later)) = updated.misordered_hardforks() {
+            return Err(ChainSpecUpdateError::MisorderedHardforks { earlier, later })
+        }
+
+        Ok(updates)
+    }
+
+    /// Applies the given hardfork updates to this chain spec, and all of its clones, while it is
+    /// in use.
+    ///
+    /// The updates are expected to be checked with [`ChainSpec::hardfork_updates`] first, so that
+    /// only activations in the future of the chain change. They replace the activation conditions
+    /// the chain spec was created with, including overrides made with
+    /// [`ChainSpec::set_fork_condition`].
+    #[cfg(feature = "std")]
+    pub fn schedule_hardforks(&self, updates: &[HardforkUpdate]) {
+        self.scheduled_hardforks.extend(updates.iter().map(|update| (update.fork, update.to)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ChainSpecBuilder, MAINNET};
+
+    /// A head after the Cancun activation on mainnet.
+    fn cancun_head() -> Head {
+        Head { number: 20_000_000, timestamp: 1_720_000_000, ..Default::default() }
+    }
+
+    #[test]
+    fn schedule_future_hardfork() {
+        let mut updated = (**MAINNET).clone();
+        updated.set_fork_condition(Hardfork::Prague, ForkCondition::Timestamp(1_800_000_000));
+
+        let updates = MAINNET.hardfork_updates(&updated, &cancun_head()).unwrap();
+        assert_eq!(
+            updates,
+            vec![HardforkUpdate {
+                fork: Hardfork::Prague,
+                from: ForkCondition::Never,
+                to: ForkCondition::Timestamp(1_800_000_000),
+            }]
+        );
+        assert_eq!(MAINNET.hardfork_updates(&MAINNET, &cancun_head()), Ok(vec![]));
+    }
+
+    #[test]
+    fn reject_past_activation_changes() {
+        let mut updated = (**MAINNET).clone();
+        updated.set_fork_condition(Hardfork::Cancun, ForkCondition::Timestamp(1_800_000_000));
+        assert!(matches!(
+            MAINNET.hardfork_updates(&updated, &cancun_head()),
+            Err(ChainSpecUpdateError::ActiveHardfork { fork: Hardfork::Cancun, .. })
+        ));
+
+        // scheduling a fork in the past of the head is rejected as well
+        let mut updated = (**MAINNET).clone();
+        updated.set_fork_condition(Hardfork::Prague, ForkCondition::Timestamp(1_710_000_000));
+        assert!(MAINNET.hardfork_updates(&updated, &cancun_head()).is_err());
+
+        let updated = ChainSpecBuilder::mainnet().chain(Chain::sepolia()).build();
+        assert!(matches!(
+            MAINNET.hardfork_updates(&updated, &cancun_head()),
+            Err(ChainSpecUpdateError::ChainMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn scheduled_hardforks_are_shared_by_clones() {
+        let spec = ChainSpecBuilder::mainnet().build();
+        let running = Arc::new(spec.clone());
+        let prague = ForkCondition::Timestamp(1_800_000_000);
+        let fork_id = running.fork_id(&cancun_head());
+
+        let mut updated = spec.clone();
+        updated.set_fork_condition(Hardfork::Prague, prague);
+        let updates = spec.hardfork_updates(&updated, &cancun_head()).unwrap();
+        spec.schedule_hardforks(&updates);
+
+        assert_eq!(running.fork(Hardfork::Prague), prague);
+        assert!(running.is_prague_active_at_timestamp(1_800_000_000));
+        assert_eq!(running.hardforks().get(&Hardfork::Prague), Some(&prague));
+        // the next fork announced to peers is the scheduled one
+        assert_eq!(running.fork_id(&cancun_head()).hash, fork_id.hash);
+        assert_eq!(running.fork_id(&cancun_head()).next, 1_800_000_000);
+
+        // a spec built from scratch has its own schedule
+        assert_eq!(
+            ChainSpecBuilder::mainnet().build().fork(Hardfork::Prague),
+            ForkCondition::Never
+        );
+    }
+}
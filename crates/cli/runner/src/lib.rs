@@ -11,19 +11,40 @@
 //! Entrypoint for running commands.
 
 use reth_tasks::{TaskExecutor, TaskManager};
-use std::{future::Future, pin::pin, sync::mpsc, time::Duration};
-use tracing::{debug, error, trace};
+use std::{
+    future::Future,
+    pin::pin,
+    sync::mpsc,
+    time::{Duration, Instant},
+};
+use tracing::{debug, error, info, trace, warn};
+
+/// The default time a command is given to shut down after receiving a shutdown signal.
+pub const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(60);
 
 /// Executes CLI commands.
 ///
 /// Provides utilities for running a cli command to completion.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 #[non_exhaustive]
-pub struct CliRunner;
+pub struct CliRunner {
+    /// How long the command is given to commit its progress after a shutdown signal.
+    shutdown_timeout: Duration,
+}
 
 // === impl CliRunner ===
 
 impl CliRunner {
+    /// Sets how long a command is given to shut down after receiving `SIGINT` or `SIGTERM`.
+    ///
+    /// Within the timeout, the command finishes its current batch of work and commits it. After
+    /// the timeout, the process exits without waiting for the command. This is safe, because the
+    /// uncommitted work is discarded and redone the next time the command runs.
+    pub const fn with_shutdown_timeout(mut self, shutdown_timeout: Duration) -> Self {
+        self.shutdown_timeout = shutdown_timeout;
+        self
+    }
+
     /// Executes the given _async_ command on the tokio runtime until the command future resolves or
     /// until the process receives a `SIGINT` or `SIGTERM` signal.
     ///
@@ -44,6 +65,13 @@ impl CliRunner {
             &mut task_manager,
             run_until_ctrl_c(command(context)),
         ));
+        let interrupted = matches!(command_res, Ok(true));
+        let deadline = Instant::now() + self.shutdown_timeout;
+
+        if interrupted {
+            info!(target: "reth::cli", timeout = ?self.shutdown_timeout, "Received shutdown signal, committing progress. Press Ctrl+C again to exit immediately");
+            force_exit_on_ctrl_c(tokio_runtime.handle());
+        }
 
         if command_res.is_err() {
             error!(target: "reth::cli", "shutting down due to error");
@@ -52,13 +80,14 @@ impl CliRunner {
             // after the command has finished or exit signal was received we shutdown the task
             // manager which fires the shutdown signal to all tasks spawned via the task
             // executor and awaiting on tasks spawned with graceful shutdown
-            task_manager.graceful_shutdown_with_timeout(Duration::from_secs(5));
+            task_manager.graceful_shutdown_with_timeout(self.shutdown_timeout);
         }
 
         // `drop(tokio_runtime)` would block the current thread until its pools
         // (including blocking pool) are shutdown. Since we want to exit as soon as possible, drop
-        // it on a separate thread and wait for up to 5 seconds for this operation to
-        // complete.
+        // it on a separate thread and wait until the shutdown timeout for this operation to
+        // complete. Tasks that are still running, e.g. a pipeline finishing its current batch,
+        // run on the blocking pool.
         let (tx, rx) = mpsc::channel();
         std::thread::Builder::new()
             .name("tokio-runtime-shutdown".to_string())
@@ -68,11 +97,14 @@ impl CliRunner {
             })
             .unwrap();
 
-        let _ = rx.recv_timeout(Duration::from_secs(5)).inspect_err(|err| {
-            debug!(target: "reth::cli", %err, "tokio runtime shutdown timed out");
-        });
+        let shut_down = rx.recv_timeout(deadline.saturating_duration_since(Instant::now())).is_ok();
+        if interrupted {
+            log_resume_instructions(shut_down, self.shutdown_timeout);
+        } else if !shut_down {
+            debug!(target: "reth::cli", "tokio runtime shutdown timed out");
+        }
 
-        command_res
+        command_res.map(drop)
     }
 
     /// Executes a regular future until completion or until external signal received.
@@ -89,6 +121,10 @@ impl CliRunner {
     /// Executes a regular future as a spawned blocking task until completion or until external
     /// signal received.
     ///
+    /// After the signal, the command is given until the shutdown timeout to finish, so commands
+    /// that listen for the signal themselves can commit their progress. See
+    /// [`CliRunner::with_shutdown_timeout`].
+    ///
     /// See [`Runtime::spawn_blocking`](tokio::runtime::Runtime::spawn_blocking) .
     pub fn run_blocking_until_ctrl_c<F, E>(self, fut: F) -> Result<(), E>
     where
@@ -97,9 +133,28 @@ impl CliRunner {
     {
         let tokio_runtime = tokio_runtime()?;
         let handle = tokio_runtime.handle().clone();
-        let fut = tokio_runtime.handle().spawn_blocking(move || handle.block_on(fut));
-        tokio_runtime
-            .block_on(run_until_ctrl_c(async move { fut.await.expect("Failed to join task") }))?;
+        let mut fut = tokio_runtime.handle().spawn_blocking(move || handle.block_on(fut));
+        let shutdown_timeout = self.shutdown_timeout;
+        tokio_runtime.block_on(async move {
+            tokio::select! {
+                res = &mut fut => return res.expect("Failed to join task"),
+                res = shutdown_signal() => res?,
+            }
+
+            info!(target: "reth::cli", timeout = ?shutdown_timeout, "Received shutdown signal, committing progress. Press Ctrl+C again to exit immediately");
+            let res = tokio::select! {
+                res = tokio::time::timeout(shutdown_timeout, fut) => res,
+                res = shutdown_signal() => {
+                    res?;
+                    std::process::exit(SIGINT_EXIT_CODE)
+                }
+            };
+            log_resume_instructions(res.is_ok(), shutdown_timeout);
+            match res {
+                Ok(res) => res.expect("Failed to join task"),
+                Err(_) => Ok(()),
+            }
+        })?;
 
         // drop the tokio runtime on a separate thread because drop blocks until its pools
         // (including blocking pool) are shutdown. In other words `drop(tokio_runtime)` would block
@@ -113,6 +168,12 @@ impl CliRunner {
     }
 }
 
+impl Default for CliRunner {
+    fn default() -> Self {
+        Self { shutdown_timeout: DEFAULT_SHUTDOWN_TIMEOUT }
+    }
+}
+
 /// [`CliRunner`] configuration when executing commands asynchronously
 struct AsyncCliRunner {
     context: CliContext,
@@ -149,31 +210,46 @@ pub fn tokio_runtime() -> Result<tokio::runtime::Runtime, std::io::Error> {
 /// Runs the given future to completion or until a critical task panicked.
 ///
 /// Returns the error if a task panicked, or the given future returned an error.
-async fn run_to_completion_or_panic<F, E>(tasks: &mut TaskManager, fut: F) -> Result<(), E>
+async fn run_to_completion_or_panic<F, T, E>(tasks: &mut TaskManager, fut: F) -> Result<T, E>
 where
-    F: Future<Output = Result<(), E>>,
+    F: Future<Output = Result<T, E>>,
     E: Send + Sync + From<reth_tasks::PanickedTaskError> + 'static,
 {
-    {
-        let fut = pin!(fut);
-        tokio::select! {
-            err = tasks => {
-                return Err(err.into())
-            },
-            res = fut => res?,
-        }
+    let fut = pin!(fut);
+    tokio::select! {
+        err = tasks => Err(err.into()),
+        res = fut => res,
     }
-    Ok(())
 }
 
 /// Runs the future to completion or until:
 /// - `ctrl-c` is received.
 /// - `SIGTERM` is received (unix only).
-async fn run_until_ctrl_c<F, E>(fut: F) -> Result<(), E>
+///
+/// Returns `true` if the future was interrupted by a signal.
+async fn run_until_ctrl_c<F, E>(fut: F) -> Result<bool, E>
 where
     F: Future<Output = Result<(), E>>,
     E: Send + Sync + 'static + From<std::io::Error>,
 {
+    let fut = pin!(fut);
+
+    tokio::select! {
+        res = shutdown_signal() => {
+            res?;
+            Ok(true)
+        },
+        res = fut => res.map(|_| false),
+    }
+}
+
+/// Resolves once the process receives:
+/// - `ctrl-c`.
+/// - `SIGTERM` (unix only).
+///
+/// Commands that work in batches can listen for this signal to stop after committing their
+/// current batch.
+pub async fn shutdown_signal() -> std::io::Result<()> {
     let ctrl_c = tokio::signal::ctrl_c();
 
     #[cfg(unix)]
@@ -182,31 +258,47 @@ where
         let sigterm = stream.recv();
         let sigterm = pin!(sigterm);
         let ctrl_c = pin!(ctrl_c);
-        let fut = pin!(fut);
 
         tokio::select! {
-            _ = ctrl_c => {
+            res = ctrl_c => {
                 trace!(target: "reth::cli", "Received ctrl-c");
+                res
             },
             _ = sigterm => {
                 trace!(target: "reth::cli", "Received SIGTERM");
+                Ok(())
             },
-            res = fut => res?,
         }
     }
 
     #[cfg(not(unix))]
     {
-        let ctrl_c = pin!(ctrl_c);
-        let fut = pin!(fut);
+        let res = ctrl_c.await;
+        trace!(target: "reth::cli", "Received ctrl-c");
+        res
+    }
+}
 
-        tokio::select! {
-            _ = ctrl_c => {
-                trace!(target: "reth::cli", "Received ctrl-c");
-            },
-            res = fut => res?,
+/// The exit code of a process that was interrupted by `SIGINT`.
+const SIGINT_EXIT_CODE: i32 = 130;
+
+/// Exits the process immediately once another shutdown signal is received.
+///
+/// The database and the static files stay consistent, uncommitted progress is discarded.
+fn force_exit_on_ctrl_c(handle: &tokio::runtime::Handle) {
+    handle.spawn(async {
+        if shutdown_signal().await.is_ok() {
+            warn!(target: "reth::cli", "Received second shutdown signal, exiting immediately");
+            std::process::exit(SIGINT_EXIT_CODE)
         }
-    }
+    });
+}
 
-    Ok(())
+/// Logs how to resume the interrupted command.
+fn log_resume_instructions(shut_down: bool, shutdown_timeout: Duration) {
+    if shut_down {
+        info!(target: "reth::cli", "Shut down cleanly, all progress is committed. Run the same command again to resume");
+    } else {
+        warn!(target: "reth::cli", timeout = ?shutdown_timeout, "Shutdown timed out, exiting. The current batch is discarded and redone once the same command runs again");
+    }
 }
@@ -201,6 +201,9 @@ pub struct ExecutionConfig {
         deserialize_with = "deserialize_duration"
     )]
     pub max_duration: Option<Duration>,
+    /// The maximum size in megabytes of the state kept in memory across commits, disabled if
+    /// `None`.
+    pub state_cache_size: Option<u64>,
 }
 
 impl Default for ExecutionConfig {
@@ -212,6 +215,7 @@ impl Default for ExecutionConfig {
             max_cumulative_gas: Some(30_000_000 * 50_000),
             // 10 minutes
             max_duration: Some(Duration::from_secs(10 * 60)),
+            state_cache_size: None,
         }
     }
 }
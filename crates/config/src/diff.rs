@@ -0,0 +1,219 @@
+//! Comparison of a configuration file with the default configuration.
+
+use crate::Config;
+use std::{collections::BTreeMap, fmt};
+
+/// Keys that are no longer read, or read under a new name, with what to do about them.
+const DEPRECATED_KEYS: &[(&str, &str)] = &[
+    ("prune.parts", "renamed to `prune.segments`"),
+    ("peers.connect_trusted_nodes_only", "renamed to `peers.trusted_nodes_only`"),
+    (
+        "stages.transaction_lookup.commit_threshold",
+        "replaced by `stages.transaction_lookup.chunk_size`, the value is ignored",
+    ),
+    ("stages.total_difficulty", "the total difficulty stage was removed, the table is ignored"),
+    ("stages.execution.max_duration.secs", "write the duration as a string, e.g. '10m'"),
+    ("stages.execution.max_duration.nanos", "write the duration as a string, e.g. '10m'"),
+];
+
+/// A setting of a configuration file that differs from the default.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigOverride {
+    /// The dotted path of the setting.
+    pub key: String,
+    /// The default value, if the setting is set by default.
+    pub default: Option<toml::Value>,
+    /// The configured value.
+    pub value: toml::Value,
+}
+
+impl fmt::Display for ConfigOverride {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} = {}", self.key, self.value)?;
+        match &self.default {
+            Some(default) => write!(f, " (default: {default})"),
+            None => write!(f, " (default: unset)"),
+        }
+    }
+}
+
+/// A setting of a configuration file that is deprecated or not read at all.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigWarning {
+    /// The dotted path of the setting.
+    pub key: String,
+    /// Why the setting is deprecated and what to do about it.
+    pub message: String,
+}
+
+impl fmt::Display for ConfigWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.key, self.message)
+    }
+}
+
+/// The differences between a configuration file and the default configuration.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfigDiff {
+    /// Settings with values other than the default.
+    pub overrides: Vec<ConfigOverride>,
+    /// Settings that are deprecated or ignored.
+    pub warnings: Vec<ConfigWarning>,
+}
+
+impl ConfigDiff {
+    /// Compares the contents of a configuration file with the default configuration.
+    ///
+    /// Values are compared as they are read by the node, so a setting that is written differently
+    /// than the default, e.g. a duration of `'60s'` instead of `'1m'`, is not an override.
+    pub fn new(contents: &str) -> Result<Self, toml::de::Error> {
+        let raw: toml::Value = toml::from_str(contents)?;
+        let config: Config = toml::from_str(contents)?;
+
+        let read = flatten(&toml::Value::try_from(&config).expect("config is serializable"));
+        let default =
+            flatten(&toml::Value::try_from(Config::default()).expect("config is serializable"));
+
+        let overrides = read
+            .iter()
+            .filter(|(key, value)| default.get(*key) != Some(value))
+            .map(|(key, value)| ConfigOverride {
+                key: key.clone(),
+                default: default.get(key).cloned(),
+                value: value.clone(),
+            })
+            .collect();
+
+        let mut warnings: Vec<ConfigWarning> = Vec::new();
+        for key in flatten(&raw).into_keys() {
+            let deprecated = DEPRECATED_KEYS.iter().find(|(deprecated, _)| {
+                key == *deprecated || key.starts_with(&format!("{deprecated}."))
+            });
+            let warning = match deprecated {
+                Some((deprecated, message)) => {
+                    ConfigWarning { key: deprecated.to_string(), message: message.to_string() }
+                }
+                // addresses are read as checksummed
+                None if !read.keys().any(|read| read.eq_ignore_ascii_case(&key)) => ConfigWarning {
+                    key,
+                    message: "unknown setting, the value is ignored".to_string(),
+                },
+                None => continue,
+            };
+            if !warnings.contains(&warning) {
+                warnings.push(warning);
+            }
+        }
+
+        Ok(Self { overrides, warnings })
+    }
+
+    /// Returns `true` if the configuration file matches the default configuration.
+    pub fn is_empty(&self) -> bool {
+        self.overrides.is_empty() && self.warnings.is_empty()
+    }
+}
+
+/// Returns the values of the table by their dotted path.
+///
+/// Empty tables are left out.
+fn flatten(value: &toml::Value) -> BTreeMap<String, toml::Value> {
+    fn collect(prefix: &str, value: &toml::Value, out: &mut BTreeMap<String, toml::Value>) {
+        match value {
+            toml::Value::Table(table) => {
+                for (key, value) in table {
+                    let path =
+                        if prefix.is_empty() { key.clone() } else { format!("{prefix}.{key}") };
+                    collect(&path, value, out);
+                }
+            }
+            value => {
+                out.insert(prefix.to_string(), value.clone());
+            }
+        }
+    }
+
+    let mut out = BTreeMap::new();
+    collect("", value, &mut out);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_default_config() {
+        let default = toml::to_string(&Config::default()).unwrap();
+        assert!(ConfigDiff::new(&default).unwrap().is_empty());
+        assert!(ConfigDiff::new("").unwrap().is_empty());
+
+        // the same value written differently
+        assert!(ConfigDiff::new("[peers]\nban_duration = '720m'").unwrap().is_empty());
+    }
+
+    #[test]
+    fn diff_overrides() {
+        let diff = ConfigDiff::new(
+            r"
+[stages.execution]
+max_blocks = 1000
+
+[stages.etl]
+dir = '/tmp/etl'
+",
+        )
+        .unwrap();
+        assert_eq!(
+            diff.overrides,
+            vec![
+                ConfigOverride {
+                    key: "stages.etl.dir".to_string(),
+                    default: None,
+                    value: "/tmp/etl".into(),
+                },
+                ConfigOverride {
+                    key: "stages.execution.max_blocks".to_string(),
+                    default: Some(500_000.into()),
+                    value: 1000.into(),
+                },
+            ]
+        );
+        assert!(diff.warnings.is_empty());
+    }
+
+    #[test]
+    fn diff_deprecated_keys() {
+        let diff = ConfigDiff::new(
+            r"
+[peers]
+connect_trusted_nodes_only = true
+max_backof_count = 3
+
+[stages.execution.max_duration]
+secs = 600
+nanos = 0
+
+[prune.parts]
+sender_recovery = 'full'
+transaction_lookup = 'full'
+",
+        )
+        .unwrap();
+
+        let keys = diff.warnings.iter().map(|warning| warning.key.as_str()).collect::<Vec<_>>();
+        assert_eq!(
+            keys,
+            [
+                "peers.connect_trusted_nodes_only",
+                "peers.max_backof_count",
+                "prune.parts",
+                "stages.execution.max_duration.nanos",
+                "stages.execution.max_duration.secs",
+            ]
+        );
+        // deprecated keys are still read
+        assert!(diff.overrides.iter().any(|o| o.key == "peers.trusted_nodes_only"));
+        assert!(diff.overrides.iter().any(|o| o.key == "prune.segments.sender_recovery"));
+    }
+}
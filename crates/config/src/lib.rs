@@ -10,3 +10,9 @@
 
 pub mod config;
 pub use config::{BodiesConfig, Config, PruneConfig};
+
+pub mod diff;
+pub use diff::{ConfigDiff, ConfigOverride, ConfigWarning};
+
+pub mod template;
+pub use template::default_config_template;
@@ -0,0 +1,411 @@
+//! A documented default configuration file.
+
+use crate::Config;
+use std::collections::BTreeSet;
+
+/// The introduction of the configuration file.
+const HEADER: &str = "\
+# Configuration of a reth node.
+#
+# Every setting is listed with its default value. Settings that are removed from this file keep
+# their default value. Optional settings are commented out.
+
+";
+
+/// Documentation of the tables and keys of the configuration file, by their dotted path.
+///
+/// Durations are either written as strings like `'10m'`, or as tables with `secs` and `nanos`.
+const DOCS: &[(&str, &str)] = &[
+    ("stages", "Configuration of the stages of the sync pipeline."),
+    ("stages.headers", "Headers stage."),
+    (
+        "stages.headers.downloader_max_concurrent_requests",
+        "The maximum number of requests to send concurrently.",
+    ),
+    (
+        "stages.headers.downloader_min_concurrent_requests",
+        "The minimum number of requests to send concurrently.",
+    ),
+    (
+        "stages.headers.downloader_max_buffered_responses",
+        "The maximum number of responses to buffer internally, a response contains multiple headers.",
+    ),
+    (
+        "stages.headers.downloader_request_limit",
+        "The maximum number of headers to request from a peer at a time.",
+    ),
+    (
+        "stages.headers.commit_threshold",
+        "The maximum number of headers to download before committing progress to the database.",
+    ),
+    ("stages.bodies", "Bodies stage."),
+    (
+        "stages.bodies.downloader_request_limit",
+        "The number of non-empty blocks to request from a peer at a time.",
+    ),
+    (
+        "stages.bodies.downloader_stream_batch_size",
+        "The maximum number of block bodies returned at once from the stream.",
+    ),
+    (
+        "stages.bodies.downloader_max_buffered_blocks_size_bytes",
+        "The size of the internal block buffer in bytes.",
+    ),
+    (
+        "stages.bodies.downloader_min_concurrent_requests",
+        "The minimum number of requests to send concurrently.",
+    ),
+    (
+        "stages.bodies.downloader_max_concurrent_requests",
+        "The maximum number of requests to send concurrently.",
+    ),
+    ("stages.sender_recovery", "Sender recovery stage."),
+    (
+        "stages.sender_recovery.commit_threshold",
+        "The maximum number of transactions to process before committing progress to the database.",
+    ),
+    (
+        "stages.execution",
+        "Execution stage. The stage commits as soon as any of the thresholds is reached.",
+    ),
+    ("stages.execution.max_blocks", "The maximum number of blocks to process before committing."),
+    (
+        "stages.execution.max_changes",
+        "The maximum number of state changes to keep in memory before committing.",
+    ),
+    (
+        "stages.execution.max_cumulative_gas",
+        "The maximum cumulative amount of gas to process before committing.",
+    ),
+    (
+        "stages.execution.max_duration",
+        "The maximum time spent on processing blocks before committing.",
+    ),
+    ("stages.account_hashing", "Account hashing stage."),
+    (
+        "stages.account_hashing.clean_threshold",
+        "The number of blocks above which all accounts are hashed again instead of only the changed ones.",
+    ),
+    (
+        "stages.account_hashing.commit_threshold",
+        "The maximum number of accounts to process before committing progress to the database.",
+    ),
+    ("stages.storage_hashing", "Storage hashing stage."),
+    (
+        "stages.storage_hashing.clean_threshold",
+        "The number of blocks above which all storage slots are hashed again instead of only the changed ones.",
+    ),
+    (
+        "stages.storage_hashing.commit_threshold",
+        "The maximum number of storage slots to process before committing progress to the database.",
+    ),
+    ("stages.merkle", "Merkle stage."),
+    (
+        "stages.merkle.clean_threshold",
+        "The number of blocks above which the state trie is rebuilt instead of updated incrementally.",
+    ),
+    ("stages.transaction_lookup", "Transaction lookup stage."),
+    (
+        "stages.transaction_lookup.chunk_size",
+        "The maximum number of transactions to process before writing to disk.",
+    ),
+    ("stages.index_account_history", "Account history indexing stage."),
+    (
+        "stages.index_account_history.commit_threshold",
+        "The maximum number of blocks to process before committing progress to the database.",
+    ),
+    ("stages.index_storage_history", "Storage history indexing stage."),
+    (
+        "stages.index_storage_history.commit_threshold",
+        "The maximum number of blocks to process before committing progress to the database.",
+    ),
+    (
+        "stages.etl",
+        "Temporary files of the stages that collect data before writing it to the database.",
+    ),
+    (
+        "stages.etl.file_size",
+        "The maximum size in bytes of data held in memory before it is flushed to a file.",
+    ),
+    ("peers", "Peer management."),
+    ("peers.refill_slots_interval", "How often to check for free slots for outbound connections."),
+    (
+        "peers.trusted_nodes",
+        "Trusted nodes to connect to or accept connections from, as enode URLs.",
+    ),
+    (
+        "peers.trusted_nodes_only",
+        "Whether to only connect to and accept connections from trusted nodes.",
+    ),
+    (
+        "peers.max_backoff_count",
+        "The maximum number of backoffs before a peer is removed, does not apply to trusted nodes.",
+    ),
+    ("peers.ban_duration", "How long to ban bad peers."),
+    ("peers.connection_info", "Connection limits."),
+    ("peers.connection_info.max_outbound", "The maximum number of outbound connections."),
+    ("peers.connection_info.max_inbound", "The maximum number of inbound connections."),
+    (
+        "peers.connection_info.max_concurrent_outbound_dials",
+        "The maximum number of concurrent outbound dials.",
+    ),
+    ("peers.reputation_weights", "The reputation change of a peer per kind of misbehavior."),
+    ("peers.reputation_weights.bad_message", "The peer sent an invalid message."),
+    ("peers.reputation_weights.bad_block", "The peer sent an invalid block."),
+    ("peers.reputation_weights.bad_transactions", "The peer sent invalid transactions."),
+    (
+        "peers.reputation_weights.already_seen_transactions",
+        "The peer sent transactions it sent before.",
+    ),
+    ("peers.reputation_weights.timeout", "The peer did not respond in time."),
+    ("peers.reputation_weights.bad_protocol", "The peer violated the protocol."),
+    ("peers.reputation_weights.failed_to_connect", "The connection to the peer failed."),
+    ("peers.reputation_weights.dropped", "The connection to the peer was dropped."),
+    ("peers.reputation_weights.bad_announcement", "The peer announced invalid transactions."),
+    (
+        "peers.backoff_durations",
+        "How long to wait before reconnecting to a peer after a failed connection. The duration grows with the number of backoffs.",
+    ),
+    ("peers.backoff_durations.low", "For problems that are likely resolved after a short time."),
+    ("peers.backoff_durations.medium", "For more severe problems that are less likely resolved."),
+    ("peers.backoff_durations.high", "For spammers and bad peers in general."),
+    ("peers.backoff_durations.max", "The maximum total backoff duration."),
+    ("sessions", "Peer sessions."),
+    ("sessions.session_command_buffer", "The size of the command buffer of each session."),
+    ("sessions.session_event_buffer", "The size of the event buffer shared by all sessions."),
+    ("sessions.limits", "Limits on the number of sessions, by default no limits are enforced."),
+    (
+        "sessions.initial_internal_request_timeout",
+        "How long to wait for a response from a peer before the request times out internally.",
+    ),
+    (
+        "sessions.protocol_breach_request_timeout",
+        "How long to wait for a response from a peer before the missing response is considered a protocol violation.",
+    ),
+    (
+        "sessions.pending_session_timeout",
+        "How long a session can be pending before it is considered failed.",
+    ),
+];
+
+/// Documentation and example values of keys that are not set by default, by their dotted path.
+const OPTIONAL_KEYS: &[(&str, &str, &str)] = &[
+    (
+        "stages.etl.dir",
+        "The directory of the temporary files, defaults to `etl-tmp` in the datadir.",
+        "'/path/to/etl-tmp'",
+    ),
+    (
+        "stages.execution.state_cache_size",
+        "The maximum size in megabytes of the accounts, storage slots and bytecodes kept in memory across commits.",
+        "4096",
+    ),
+    (
+        "sessions.proxy",
+        "A SOCKS5 proxy that outbound connections to peers are routed through.",
+        "{ addr = '127.0.0.1:1080' }",
+    ),
+    (
+        "sessions.limits.max_pending_inbound",
+        "The maximum number of pending inbound sessions.",
+        "30",
+    ),
+    (
+        "sessions.limits.max_pending_outbound",
+        "The maximum number of pending outbound sessions.",
+        "100",
+    ),
+    (
+        "sessions.limits.max_established_inbound",
+        "The maximum number of established inbound sessions.",
+        "30",
+    ),
+    (
+        "sessions.limits.max_established_outbound",
+        "The maximum number of established outbound sessions.",
+        "100",
+    ),
+];
+
+/// The optional tables of the configuration file.
+const OPTIONAL_TABLES: &str = "
+# Pruning of historical data, by default nothing is pruned. Each segment is either pruned in
+# `'full'`, up to a `{ distance = N }` of blocks behind the tip, or `{ before = N }` a block number.
+#
+# [prune]
+# # The minimum number of blocks between two prune runs.
+# block_interval = 5
+#
+# [prune.segments]
+# sender_recovery = 'full'
+# transaction_lookup = 'full'
+# receipts = { distance = 10064 }
+# account_history = { distance = 10064 }
+# storage_history = { distance = 10064 }
+# # Keeps the full account and storage history of the given addresses, the history of other
+# # addresses is pruned according to `account_history` and `storage_history`.
+# history_address_allowlist = ['0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48']
+#
+# # Keeps the receipts with logs of the given contracts, from the given block on. Ignored if
+# # `receipts` is set.
+# [prune.segments.receipts_log_filter]
+# '0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48' = { before = 17000000 }
+";
+
+/// Returns the default configuration file, with a description of every setting.
+///
+/// The file deserializes to [`Config::default`].
+pub fn default_config_template() -> String {
+    let config =
+        toml::to_string_pretty(&Config::default()).expect("default config is serializable");
+
+    let mut out = String::from(HEADER);
+    let mut table = String::new();
+    let mut documented = BTreeSet::new();
+    for line in config.lines() {
+        if let Some(header) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+            // the optional keys of the previous table go before the blank line
+            let blank_line = out.ends_with("\n\n");
+            if blank_line {
+                out.pop();
+            }
+            push_optional_keys(&mut out, &table);
+            if blank_line {
+                out.push('\n');
+            }
+            table = header.to_string();
+
+            // document the table and any parent tables that are not written on their own
+            let mut path = String::new();
+            for part in header.split('.') {
+                if !path.is_empty() {
+                    path.push('.');
+                }
+                path.push_str(part);
+                if !documented.insert(path.clone()) {
+                    continue
+                }
+                if let Some(doc) = doc(&path) {
+                    if path != header {
+                        push_comment(&mut out, doc);
+                        out.push('\n');
+                    }
+                }
+            }
+            if let Some(doc) = doc(header) {
+                push_comment(&mut out, doc);
+            }
+        } else if let Some((key, _)) = line.split_once(" = ") {
+            if let Some(doc) = doc(&format!("{table}.{key}")) {
+                push_comment(&mut out, doc);
+            }
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    push_optional_keys(&mut out, &table);
+    out.push_str(OPTIONAL_TABLES);
+
+    out
+}
+
+/// Returns the documentation of the table or key at the given path.
+fn doc(path: &str) -> Option<&'static str> {
+    DOCS.iter().find(|(p, _)| *p == path).map(|(_, doc)| *doc)
+}
+
+/// Writes the optional keys of the table as comments.
+fn push_optional_keys(out: &mut String, table: &str) {
+    for (path, doc, example) in OPTIONAL_KEYS {
+        let Some(key) = path.strip_prefix(table).and_then(|key| key.strip_prefix('.')) else {
+            continue
+        };
+        if key.contains('.') {
+            continue
+        }
+        push_comment(out, doc);
+        out.push_str(&format!("# {key} = {example}\n"));
+    }
+}
+
+/// Writes the text as a comment, wrapped at 100 characters.
+fn push_comment(out: &mut String, text: &str) {
+    let mut line = String::from("#");
+    for word in text.split_whitespace() {
+        if line.len() + 1 + word.len() > 100 {
+            out.push_str(&line);
+            out.push('\n');
+            line = String::from("#");
+        }
+        line.push(' ');
+        line.push_str(word);
+    }
+    out.push_str(&line);
+    out.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Collects the dotted paths of all tables and keys of the value.
+    fn paths(prefix: &str, value: &toml::Value, tables: &mut Vec<String>, keys: &mut Vec<String>) {
+        let toml::Value::Table(table) = value else { return };
+        for (key, value) in table {
+            let path = if prefix.is_empty() { key.clone() } else { format!("{prefix}.{key}") };
+            if value.is_table() {
+                tables.push(path.clone());
+                paths(&path, value, tables, keys);
+            } else {
+                keys.push(path);
+            }
+        }
+    }
+
+    #[test]
+    fn template_is_default_config() {
+        let template = default_config_template();
+        let config: Config = toml::from_str(&template).unwrap();
+        assert_eq!(config, Config::default());
+
+        // the optional settings are valid once uncommented
+        let uncommented = template
+            .lines()
+            .map(|line| match line.strip_prefix("# ") {
+                Some(setting)
+                    if setting.starts_with('[')
+                        || (setting.starts_with(|c: char| c.is_ascii_alphanumeric())
+                            && setting.contains(" = ")) =>
+                {
+                    setting
+                }
+                _ => line,
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert!(toml::from_str::<Config>(&uncommented).is_ok(), "{uncommented}");
+    }
+
+    #[test]
+    fn all_settings_documented() {
+        let default = toml::Value::try_from(Config::default()).unwrap();
+        let (mut tables, mut keys) = (Vec::new(), Vec::new());
+        paths("", &default, &mut tables, &mut keys);
+
+        for table in &tables {
+            assert!(doc(table).is_some(), "table {table} is not documented");
+        }
+        // durations that are written as tables are documented as a whole
+        for key in keys.iter().filter(|key| !key.ends_with(".secs") && !key.ends_with(".nanos")) {
+            assert!(doc(key).is_some(), "key {key} is not documented");
+        }
+
+        // no documentation of removed settings
+        for (path, _) in DOCS {
+            assert!(
+                tables.contains(&path.to_string()) || keys.contains(&path.to_string()),
+                "{path}"
+            );
+        }
+    }
+}
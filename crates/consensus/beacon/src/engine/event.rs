@@ -1,5 +1,5 @@
 use crate::engine::forkchoice::ForkchoiceStatus;
-use reth_primitives::{SealedBlock, SealedHeader, B256};
+use reth_primitives::{BlockNumber, SealedBlock, SealedHeader, B256};
 use reth_rpc_types::engine::ForkchoiceState;
 use std::{sync::Arc, time::Duration};
 
@@ -16,6 +16,13 @@ pub enum BeaconConsensusEngineEvent {
     LiveSyncProgress(ConsensusEngineLiveSyncProgress),
     /// A block was added to the fork chain.
     ForkBlockAdded(Arc<SealedBlock>),
+    /// Canonicalization is halted by a reorg deeper than the configured maximum reorg depth.
+    CanonicalizationHalted {
+        /// The fork block of the reorg.
+        fork_block: BlockNumber,
+        /// The number of canonical blocks the reorg reverts.
+        depth: u64,
+    },
 }
 
 /// Progress of the consensus engine during live sync.
@@ -0,0 +1,30 @@
+use reth_blockchain_tree_api::error::InsertBlockErrorKind;
+use reth_primitives::SealedBlock;
+
+/// Hook that is invoked when the [consensus engine][`crate::engine::BeaconConsensusEngine`]
+/// rejects a block as invalid.
+///
+/// This can be used to collect debugging information about the block, for example by
+/// re-executing it.
+pub trait InvalidBlockHook: Send + Sync {
+    /// Invoked with the invalid block and the error it was rejected with.
+    fn on_invalid_block(&self, block: &SealedBlock, error: &InsertBlockErrorKind);
+}
+
+impl<F> InvalidBlockHook for F
+where
+    F: Fn(&SealedBlock, &InsertBlockErrorKind) + Send + Sync,
+{
+    fn on_invalid_block(&self, block: &SealedBlock, error: &InsertBlockErrorKind) {
+        self(block, error)
+    }
+}
+
+/// An [`InvalidBlockHook`] that does nothing.
+#[derive(Debug, Default, Clone, Copy)]
+#[non_exhaustive]
+pub struct NoopInvalidBlockHook;
+
+impl InvalidBlockHook for NoopInvalidBlockHook {
+    fn on_invalid_block(&self, _block: &SealedBlock, _error: &InsertBlockErrorKind) {}
+}
@@ -55,6 +55,9 @@ pub use error::{
 mod invalid_headers;
 use invalid_headers::InvalidHeaderCache;
 
+mod invalid_block_hook;
+pub use invalid_block_hook::{InvalidBlockHook, NoopInvalidBlockHook};
+
 mod event;
 pub use event::{BeaconConsensusEngineEvent, ConsensusEngineLiveSyncProgress};
 
@@ -199,6 +202,8 @@ where
     /// Tracks the header of invalid payloads that were rejected by the engine because they're
     /// invalid.
     invalid_headers: InvalidHeaderCache,
+    /// Hook that is invoked with every block that is rejected as invalid.
+    invalid_block_hook: Box<dyn InvalidBlockHook>,
     /// After downloading a block corresponding to a recent forkchoice update, the engine will
     /// check whether or not we can connect the block to the current canonical chain. If we can't,
     /// we need to download and execute the missing parents of that block.
@@ -310,6 +315,7 @@ where
             forkchoice_state_tracker: Default::default(),
             payload_builder,
             invalid_headers: InvalidHeaderCache::new(MAX_INVALID_HEADERS),
+            invalid_block_hook: Box::new(NoopInvalidBlockHook),
             blockchain_tree_action: None,
             pending_forkchoice_update: None,
             pipeline_run_threshold,
@@ -331,6 +337,11 @@ where
         Ok((this, handle))
     }
 
+    /// Sets the [`InvalidBlockHook`] that is invoked with every block that is rejected as invalid.
+    pub fn set_invalid_block_hook(&mut self, hook: Box<dyn InvalidBlockHook>) {
+        self.invalid_block_hook = hook;
+    }
+
     /// Returns current [`EngineHookContext`] that's used for polling engine hooks.
     fn current_engine_hook_context(&self) -> RethResult<EngineHookContext> {
         Ok(EngineHookContext {
@@ -1003,6 +1014,15 @@ where
                 self.sync.set_pipeline_sync_target(PipelineTarget::Unwind(*block_number));
                 return Ok(PayloadStatus::from_status(PayloadStatusEnum::Syncing))
             }
+            CanonicalError::ReorgHalted { fork_block, depth } => {
+                // don't sync to the head either, the operator has to resume canonicalization
+                error!(target: "consensus::engine", %error, ?state, "Failed to canonicalize the head hash");
+                self.event_sender.notify(BeaconConsensusEngineEvent::CanonicalizationHalted {
+                    fork_block: *fork_block,
+                    depth: *depth,
+                });
+                return Ok(PayloadStatus::from_status(PayloadStatusEnum::Syncing))
+            }
             _ => {
                 warn!(target: "consensus::engine", %error, ?state, "Failed to canonicalize the head hash");
                 // TODO(mattsse) better error handling before attempting to sync (FCU could be
@@ -1650,6 +1670,7 @@ where
                         // invalid headers cache and `Ok` with [PayloadStatusEnum::Invalid] is
                         // returned.
                         warn!(target: "consensus::engine", invalid_hash=?block.hash(), invalid_number=?block.number, %error, "Invalid block error on new payload");
+                        self.invalid_block_hook.on_invalid_block(&block, &error);
                         let latest_valid_hash = if error.is_block_pre_merge() {
                             // zero hash must be returned if block is pre-merge
                             Some(B256::ZERO)
@@ -1765,6 +1786,7 @@ where
                         if err.kind().is_invalid_block() {
                             let (block, err) = err.split();
                             warn!(target: "consensus::engine", invalid_number=?block.number, invalid_hash=?block.hash(), %err, "Marking block as invalid");
+                            self.invalid_block_hook.on_invalid_block(&block, &err);
 
                             self.invalid_headers.insert(block.header);
                         }
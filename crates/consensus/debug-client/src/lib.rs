@@ -3,6 +3,9 @@
 //! This is a worker that sends FCUs and new payloads by fetching recent blocks from an external
 //! provider like Etherscan or an RPC endpoint. This allows to quickly test the execution client
 //! without running a consensus node.
+//!
+//! [`RpcSyncClient`] additionally allows to download headers and bodies from the RPC endpoint of
+//! another node instead of from peers.
 
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
@@ -14,6 +17,8 @@
 
 mod client;
 mod providers;
+mod sync;
 
 pub use client::{BlockProvider, DebugConsensusClient};
 pub use providers::{EtherscanBlockProvider, RpcBlockProvider};
+pub use sync::RpcSyncClient;
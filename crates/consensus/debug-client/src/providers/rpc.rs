@@ -4,35 +4,91 @@ use alloy_provider::{Provider, ProviderBuilder};
 use futures::StreamExt;
 use reth_node_core::rpc::types::RichBlock;
 use reth_rpc_types::BlockTransactionsKind;
-use tokio::sync::mpsc::Sender;
+use reth_tracing::tracing::warn;
+use std::time::Duration;
+use tokio::{sync::mpsc::Sender, time::interval};
 
-/// Block provider that fetches new blocks from an RPC endpoint using a websocket connection.
+/// Block provider that fetches new blocks from an RPC endpoint.
+///
+/// New blocks are subscribed to over websocket and IPC connections, and polled for over HTTP.
 #[derive(Debug, Clone)]
 pub struct RpcBlockProvider {
-    ws_rpc_url: String,
+    rpc_url: String,
+    interval: Duration,
 }
 
 impl RpcBlockProvider {
-    /// Create a new RPC block provider with the given WS RPC URL.
-    pub const fn new(ws_rpc_url: String) -> Self {
-        Self { ws_rpc_url }
+    /// Create a new RPC block provider with the given RPC URL.
+    pub const fn new(rpc_url: String) -> Self {
+        Self { rpc_url, interval: Duration::from_secs(3) }
+    }
+
+    /// Sets the interval at which the provider polls for new blocks over HTTP.
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Fetches the latest block of the RPC endpoint.
+    pub async fn latest_block(&self) -> eyre::Result<RichBlock> {
+        let provider = ProviderBuilder::new().on_builtin(&self.rpc_url).await?;
+        let block: RichBlock = provider
+            .get_block_by_number(BlockNumberOrTag::Latest, false)
+            .await?
+            .ok_or_else(|| eyre::eyre!("latest block not found"))?
+            .into();
+        Ok(block)
+    }
+
+    /// Returns true if the provider polls for new blocks, because the RPC endpoint doesn't support
+    /// subscriptions.
+    fn is_polling(&self) -> bool {
+        self.rpc_url.starts_with("http://") || self.rpc_url.starts_with("https://")
     }
 }
 
 impl BlockProvider for RpcBlockProvider {
     async fn subscribe_blocks(&self, tx: Sender<RichBlock>) {
-        let ws_provider = ProviderBuilder::new()
-            .on_builtin(&self.ws_rpc_url)
+        let provider = ProviderBuilder::new()
+            .on_builtin(&self.rpc_url)
             .await
-            .expect("failed to create WS provider");
-        let mut stream = ws_provider
+            .expect("failed to create RPC provider");
+
+        if self.is_polling() {
+            let mut last_block_number: Option<u64> = None;
+            let mut interval = interval(self.interval);
+            loop {
+                interval.tick().await;
+                let block = match provider.get_block_by_number(BlockNumberOrTag::Latest, true).await
+                {
+                    Ok(Some(block)) => block,
+                    Ok(None) => continue,
+                    Err(err) => {
+                        warn!(target: "consensus::debug-client", %err, "failed to fetch the latest block");
+                        continue
+                    }
+                };
+                if block.header.number.is_some() && block.header.number == last_block_number {
+                    continue
+                }
+                last_block_number = block.header.number;
+
+                if tx.send(block.into()).await.is_err() {
+                    // channel closed
+                    break
+                }
+            }
+            return
+        }
+
+        let mut stream = provider
             .subscribe_blocks()
             .await
             .expect("failed to subscribe on new blocks")
             .into_stream();
 
         while let Some(block) = stream.next().await {
-            let full_block = ws_provider
+            let full_block = provider
                 .get_block_by_hash(block.header.hash.unwrap(), BlockTransactionsKind::Full)
                 .await
                 .expect("failed to get block")
@@ -45,11 +101,11 @@ impl BlockProvider for RpcBlockProvider {
     }
 
     async fn get_block(&self, block_number: u64) -> eyre::Result<RichBlock> {
-        let ws_provider = ProviderBuilder::new()
-            .on_builtin(&self.ws_rpc_url)
+        let provider = ProviderBuilder::new()
+            .on_builtin(&self.rpc_url)
             .await
-            .expect("failed to create WS provider");
-        let block: RichBlock = ws_provider
+            .expect("failed to create RPC provider");
+        let block: RichBlock = provider
             .get_block_by_number(BlockNumberOrTag::Number(block_number), true)
             .await?
             .ok_or_else(|| eyre::eyre!("block not found by number {}", block_number))?
@@ -0,0 +1,185 @@
+use alloy_eips::BlockNumberOrTag;
+use alloy_provider::{Provider, ProviderBuilder, RootProvider};
+use alloy_transport::BoxTransport;
+use futures::{stream, Future, StreamExt};
+use reth_network_p2p::{
+    bodies::client::{BodiesClient, BodiesFut},
+    download::DownloadClient,
+    error::{PeerRequestResult, RequestError},
+    headers::client::{HeadersClient, HeadersFut, HeadersRequest},
+    priority::Priority,
+};
+use reth_network_peers::PeerId;
+use reth_primitives::{Block, BlockBody, BlockHashOrNumber, Header, HeadersDirection, B256};
+use reth_rpc_types::BlockTransactionsKind;
+use reth_tracing::tracing::{debug, warn};
+use std::pin::Pin;
+use tokio::sync::oneshot;
+
+/// The maximum number of concurrent RPC calls of a single headers or bodies request.
+const MAX_CONCURRENT_CALLS: usize = 16;
+
+/// A client that downloads headers and bodies from the RPC endpoint of another node, instead of
+/// from peers on the network.
+///
+/// This allows the pipeline and the engine to sync from a node that is only reachable over RPC.
+#[derive(Debug, Clone)]
+pub struct RpcSyncClient {
+    provider: RootProvider<BoxTransport>,
+}
+
+impl RpcSyncClient {
+    /// Connects to the RPC endpoint with the given URL. HTTP, WS and IPC endpoints are supported.
+    pub async fn connect(rpc_url: &str) -> eyre::Result<Self> {
+        let provider = ProviderBuilder::new().on_builtin(rpc_url).await?;
+        Ok(Self { provider })
+    }
+
+    /// Fetches the header of the given block, or `None` if the node doesn't have the block.
+    async fn header(&self, block: BlockHashOrNumber) -> Result<Option<Header>, RequestError> {
+        let block = match block {
+            BlockHashOrNumber::Hash(hash) => {
+                self.provider.get_block_by_hash(hash, BlockTransactionsKind::Hashes).await
+            }
+            BlockHashOrNumber::Number(number) => {
+                self.provider.get_block_by_number(BlockNumberOrTag::Number(number), false).await
+            }
+        }
+        .map_err(|err| {
+            debug!(target: "consensus::debug-client", %err, ?block, "Failed to fetch header");
+            RequestError::ConnectionDropped
+        })?;
+
+        block
+            .map(|block| block.header.try_into().map_err(|_| RequestError::BadResponse))
+            .transpose()
+    }
+
+    /// Fetches the requested headers.
+    ///
+    /// Like peers, the response is truncated at the first block the node doesn't have.
+    async fn headers(self, request: HeadersRequest) -> Result<Vec<Header>, RequestError> {
+        let start = match request.start {
+            BlockHashOrNumber::Number(number) => number,
+            BlockHashOrNumber::Hash(hash) => match self.header(hash.into()).await? {
+                Some(header) => header.number,
+                None => return Ok(Vec::new()),
+            },
+        };
+        let numbers: Vec<_> = match request.direction {
+            HeadersDirection::Rising => (start..start.saturating_add(request.limit)).collect(),
+            HeadersDirection::Falling => {
+                (start.saturating_sub(request.limit.saturating_sub(1))..=start).rev().collect()
+            }
+        };
+
+        let mut headers = Vec::with_capacity(numbers.len());
+        let mut responses = stream::iter(numbers)
+            .map(|number| self.header(number.into()))
+            .buffered(MAX_CONCURRENT_CALLS);
+        while let Some(header) = responses.next().await {
+            match header? {
+                Some(header) => headers.push(header),
+                None => break,
+            }
+        }
+        Ok(headers)
+    }
+
+    /// Fetches the body of the block with the given hash.
+    async fn body(&self, hash: B256) -> Result<BlockBody, RequestError> {
+        let block = self
+            .provider
+            .get_block_by_hash(hash, BlockTransactionsKind::Full)
+            .await
+            .map_err(|err| {
+                debug!(target: "consensus::debug-client", %err, %hash, "Failed to fetch body");
+                RequestError::ConnectionDropped
+            })?
+            .ok_or(RequestError::BadResponse)?;
+        let uncles = block.uncles.len();
+        let mut block = Block::try_from(block).map_err(|_| RequestError::BadResponse)?;
+
+        // ommers are not included in the block response, they are fetched one by one
+        for index in 0..uncles {
+            let uncle = self
+                .provider
+                .get_uncle(hash.into(), index as u64)
+                .await
+                .map_err(|_| RequestError::ConnectionDropped)?
+                .ok_or(RequestError::BadResponse)?;
+            block.ommers.push(uncle.header.try_into().map_err(|_| RequestError::BadResponse)?);
+        }
+
+        Ok(BlockBody {
+            transactions: block.body,
+            ommers: block.ommers,
+            withdrawals: block.withdrawals,
+            requests: block.requests,
+        })
+    }
+
+    /// Fetches the bodies of the blocks with the given hashes.
+    async fn bodies(self, hashes: Vec<B256>) -> Result<Vec<BlockBody>, RequestError> {
+        let mut bodies = Vec::with_capacity(hashes.len());
+        let mut responses =
+            stream::iter(hashes).map(|hash| self.body(hash)).buffered(MAX_CONCURRENT_CALLS);
+        while let Some(body) = responses.next().await {
+            bodies.push(body?);
+        }
+        Ok(bodies)
+    }
+}
+
+/// Spawns the request, so that the returned future is `Sync`.
+fn spawn_request<T, F>(
+    request: F,
+) -> Pin<Box<dyn Future<Output = PeerRequestResult<T>> + Send + Sync>>
+where
+    T: Send + 'static,
+    F: Future<Output = Result<T, RequestError>> + Send + 'static,
+{
+    let (tx, rx) = oneshot::channel();
+    tokio::spawn(async move {
+        let _ = tx.send(request.await);
+    });
+    Box::pin(async move {
+        let response = rx.await.map_err(|_| RequestError::ChannelClosed)??;
+        Ok((PeerId::default(), response).into())
+    })
+}
+
+impl HeadersClient for RpcSyncClient {
+    type Output = HeadersFut;
+
+    fn get_headers_with_priority(
+        &self,
+        request: HeadersRequest,
+        _priority: Priority,
+    ) -> Self::Output {
+        spawn_request(self.clone().headers(request))
+    }
+}
+
+impl BodiesClient for RpcSyncClient {
+    type Output = BodiesFut;
+
+    fn get_block_bodies_with_priority(
+        &self,
+        hashes: Vec<B256>,
+        _priority: Priority,
+    ) -> Self::Output {
+        spawn_request(self.clone().bodies(hashes))
+    }
+}
+
+impl DownloadClient for RpcSyncClient {
+    fn report_bad_message(&self, _peer_id: PeerId) {
+        warn!(target: "consensus::debug-client", "Received a bad response from the RPC endpoint");
+    }
+
+    fn num_connected_peers(&self) -> usize {
+        // the RPC endpoint is the only peer
+        1
+    }
+}
@@ -100,6 +100,14 @@ impl ForkCondition {
         }
     }
 
+    /// Returns the block number of the fork condition, if it is block based.
+    pub const fn as_block(&self) -> Option<BlockNumber> {
+        match self {
+            Self::Block(block) => Some(*block),
+            _ => None,
+        }
+    }
+
     /// Returns the timestamp of the fork condition, if it is timestamp based.
     pub const fn as_timestamp(&self) -> Option<u64> {
         match self {
@@ -394,7 +394,7 @@ mod tests {
 
         // check that it deserializes properly
         let genesis: Genesis = serde_json::from_str(hive_london).unwrap();
-        let chainspec = ChainSpec::from(genesis);
+        let chainspec = ChainSpec::try_from(genesis).unwrap();
         let payload_builder_attributes =
             EthPayloadBuilderAttributes::new(chainspec.genesis_hash(), attributes);
 
@@ -0,0 +1,67 @@
+//! Serving of the `BLOCKHASH` opcode for a configurable number of ancestors.
+
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+
+use reth_chainspec::BlockHashHistory;
+use reth_primitives::{revm_primitives::SpecId, B256, U256};
+use reth_revm::{
+    handler::register::HandleRegisterBox,
+    interpreter::{gas, opcode, Host, InstructionResult, Interpreter},
+    Context, Database,
+};
+
+/// Returns a handler register that replaces the `BLOCKHASH` instruction by one returning the
+/// hashes of as many ancestors as the [`BlockHashHistory`] serves at the EVM's spec id.
+///
+/// The hashes are looked up in the database, which reads them from the stored headers, so any
+/// window can be served regardless of the state that is available. The default instruction is
+/// kept if the window is the default one.
+pub fn block_hash_handler_register<EXT, DB: Database>(
+    history: BlockHashHistory,
+) -> HandleRegisterBox<EXT, DB> {
+    Box::new(move |handler| {
+        let window = history.window(SpecId::enabled(handler.cfg.spec_id, SpecId::PRAGUE));
+        if window == BlockHashHistory::DEFAULT_WINDOW {
+            return
+        }
+        handler.instruction_table.insert_boxed(
+            opcode::BLOCKHASH,
+            Box::new(move |interpreter: &mut Interpreter, host: &mut Context<EXT, DB>| {
+                blockhash(interpreter, host, window)
+            }),
+        );
+    })
+}
+
+/// The `BLOCKHASH` instruction, returning the hashes of the last `window` blocks and zero for any
+/// other block.
+fn blockhash<H: Host + ?Sized>(interpreter: &mut Interpreter, host: &mut H, window: u64) {
+    if !interpreter.gas.record_cost(gas::BLOCKHASH) {
+        interpreter.instruction_result = InstructionResult::OutOfGas;
+        return
+    }
+    let number = match interpreter.stack.pop() {
+        Ok(number) => number,
+        Err(result) => {
+            interpreter.instruction_result = result;
+            return
+        }
+    };
+
+    let hash = match host.env().block.number.checked_sub(number) {
+        // the hash of the current block is not known yet
+        Some(diff) if diff != U256::ZERO && diff <= U256::from(window) => {
+            let Some(hash) = host.block_hash(number) else {
+                interpreter.instruction_result = InstructionResult::FatalExternalError;
+                return
+            };
+            hash
+        }
+        _ => B256::ZERO,
+    };
+
+    if let Err(result) = interpreter.stack.push_b256(hash) {
+        interpreter.instruction_result = result;
+    }
+}
@@ -15,7 +15,8 @@ use reth_evm::{
 };
 use reth_execution_types::ExecutionOutcome;
 use reth_primitives::{
-    BlockNumber, BlockWithSenders, Hardfork, Header, Receipt, Request, Withdrawals, U256,
+    BlockNumber, BlockWithSenders, Hardfork, Header, Receipt, Request, TransactionSigned,
+    Withdrawals, U256,
 };
 use reth_prune_types::PruneModes;
 use reth_revm::{
@@ -32,9 +33,23 @@ use revm_primitives::{
     BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, ResultAndState,
 };
 
+#[cfg(feature = "std")]
+use crate::parallel::SpeculativeExecution;
+
 #[cfg(not(feature = "std"))]
 use alloc::{sync::Arc, vec, vec::Vec};
 
+#[cfg(feature = "std")]
+use crate::profile::{ExecutionProfiler, ProfilingInspector};
+#[cfg(feature = "trace")]
+use crate::{
+    slow_block::{SlowBlockDetector, SlowBlockReport},
+    trace::{state_diff, BlockTracer, TraceKind, TransactionTrace},
+};
+#[cfg(feature = "std")]
+use reth_evm::access_list::{BlockAccessListBuilder, BlockAccessListRecorder};
+#[cfg(feature = "trace")]
+use revm_inspectors::tracing::TracingInspector;
 #[cfg(feature = "std")]
 use std::sync::Arc;
 
@@ -43,12 +58,30 @@ use std::sync::Arc;
 pub struct EthExecutorProvider<EvmConfig = EthEvmConfig> {
     chain_spec: Arc<ChainSpec>,
     evm_config: EvmConfig,
+    /// Whether transactions are executed optimistically in parallel.
+    parallel: bool,
+    /// Writes a trace of every executed block, if configured.
+    #[cfg(feature = "trace")]
+    tracer: Option<BlockTracer>,
+    /// Records an opcode and precompile profile of every executed block, if configured.
+    #[cfg(feature = "std")]
+    profiler: Option<ExecutionProfiler>,
+    /// Records the access list of every executed block, if configured.
+    #[cfg(feature = "std")]
+    access_lists: Option<BlockAccessListRecorder>,
+    /// Captures the traces of slowly executed blocks, if configured.
+    #[cfg(feature = "trace")]
+    slow_blocks: Option<SlowBlockDetector>,
 }
 
 impl EthExecutorProvider {
     /// Creates a new default ethereum executor provider.
+    ///
+    /// The EVM follows the execution rules of the chain spec, see
+    /// [`EthEvmConfig::with_chain_rules`].
     pub fn ethereum(chain_spec: Arc<ChainSpec>) -> Self {
-        Self::new(chain_spec, Default::default())
+        let evm_config = EthEvmConfig::default().with_chain_rules(&chain_spec);
+        Self::new(chain_spec, evm_config)
     }
 
     /// Returns a new provider for the mainnet.
@@ -60,7 +93,64 @@ impl EthExecutorProvider {
 impl<EvmConfig> EthExecutorProvider<EvmConfig> {
     /// Creates a new executor provider.
     pub const fn new(chain_spec: Arc<ChainSpec>, evm_config: EvmConfig) -> Self {
-        Self { chain_spec, evm_config }
+        Self {
+            chain_spec,
+            evm_config,
+            parallel: false,
+            #[cfg(feature = "trace")]
+            tracer: None,
+            #[cfg(feature = "std")]
+            profiler: None,
+            #[cfg(feature = "std")]
+            access_lists: None,
+            #[cfg(feature = "trace")]
+            slow_blocks: None,
+        }
+    }
+
+    /// Configures whether the transactions of a block are executed optimistically in parallel.
+    ///
+    /// See [`EthBlockExecutor::with_parallel_execution`].
+    pub const fn with_parallel_execution(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    /// Configures a tracer that writes a trace of every executed block.
+    ///
+    /// See [`EthBlockExecutor::with_tracer`].
+    #[cfg(feature = "trace")]
+    pub fn with_tracer(mut self, tracer: BlockTracer) -> Self {
+        self.tracer = Some(tracer);
+        self
+    }
+
+    /// Configures a profiler that accumulates an opcode and precompile profile of all executed
+    /// blocks.
+    ///
+    /// See [`EthBlockExecutor::with_profiler`].
+    #[cfg(feature = "std")]
+    pub fn with_profiler(mut self, profiler: ExecutionProfiler) -> Self {
+        self.profiler = Some(profiler);
+        self
+    }
+
+    /// Configures a recorder the access lists of all executed blocks are recorded to.
+    ///
+    /// See [`EthBlockExecutor::with_access_list_recorder`].
+    #[cfg(feature = "std")]
+    pub fn with_access_list_recorder(mut self, recorder: BlockAccessListRecorder) -> Self {
+        self.access_lists = Some(recorder);
+        self
+    }
+
+    /// Configures a detector that captures the traces of slowly executed blocks.
+    ///
+    /// See [`EthBlockExecutor::with_slow_block_detector`].
+    #[cfg(feature = "trace")]
+    pub fn with_slow_block_detector(mut self, detector: SlowBlockDetector) -> Self {
+        self.slow_blocks = Some(detector);
+        self
     }
 }
 
@@ -72,11 +162,35 @@ where
     where
         DB: Database<Error = ProviderError>,
     {
-        EthBlockExecutor::new(
+        let executor = EthBlockExecutor::new(
             self.chain_spec.clone(),
             self.evm_config.clone(),
             State::builder().with_database(db).with_bundle_update().without_state_clear().build(),
         )
+        .with_parallel_execution(self.parallel);
+
+        #[cfg(feature = "trace")]
+        let executor = match self.tracer.clone() {
+            Some(tracer) => executor.with_tracer(tracer),
+            None => executor,
+        };
+        #[cfg(feature = "std")]
+        let executor = match self.profiler.clone() {
+            Some(profiler) => executor.with_profiler(profiler),
+            None => executor,
+        };
+        #[cfg(feature = "std")]
+        let executor = match self.access_lists.clone() {
+            Some(recorder) => executor.with_access_list_recorder(recorder),
+            None => executor,
+        };
+        #[cfg(feature = "trace")]
+        let executor = match self.slow_blocks.clone() {
+            Some(detector) => executor.with_slow_block_detector(detector),
+            None => executor,
+        };
+
+        executor
     }
 }
 
@@ -106,6 +220,11 @@ where
             stats: BlockExecutorStats::default(),
         }
     }
+
+    #[cfg(feature = "std")]
+    fn access_list_recorder(&self) -> Option<&BlockAccessListRecorder> {
+        self.access_lists.as_ref()
+    }
 }
 
 /// Helper type for the output of executing a block.
@@ -123,6 +242,20 @@ struct EthEvmExecutor<EvmConfig> {
     chain_spec: Arc<ChainSpec>,
     /// How to create an EVM.
     evm_config: EvmConfig,
+    /// Whether transactions are executed optimistically in parallel.
+    parallel: bool,
+    /// Writes a trace of every executed block, if configured.
+    #[cfg(feature = "trace")]
+    tracer: Option<BlockTracer>,
+    /// Records an opcode and precompile profile of every executed block, if configured.
+    #[cfg(feature = "std")]
+    profiler: Option<ExecutionProfiler>,
+    /// Records the access list of every executed block, if configured.
+    #[cfg(feature = "std")]
+    access_lists: Option<BlockAccessListRecorder>,
+    /// Captures the traces of slowly executed blocks, if configured.
+    #[cfg(feature = "trace")]
+    slow_blocks: Option<SlowBlockDetector>,
 }
 
 impl<EvmConfig> EthEvmExecutor<EvmConfig>
@@ -140,12 +273,27 @@ where
     /// It does __not__ apply post-execution changes that do not require an [EVM](Evm), for that see
     /// [`EthBlockExecutor::post_execution`].
     fn execute_state_transitions<Ext, DB>(
+        &self,
+        block: &BlockWithSenders,
+        evm: Evm<'_, Ext, &mut State<DB>>,
+    ) -> Result<EthExecuteOutput, BlockExecutionError>
+    where
+        DB: Database<Error = ProviderError>,
+    {
+        self.execute_state_transitions_with(block, evm, &mut ())
+    }
+
+    /// Same as [`Self::execute_state_transitions`], but invokes the given [`TransactionHook`]
+    /// around the execution of every transaction.
+    fn execute_state_transitions_with<Ext, DB, H>(
         &self,
         block: &BlockWithSenders,
         mut evm: Evm<'_, Ext, &mut State<DB>>,
+        hook: &mut H,
     ) -> Result<EthExecuteOutput, BlockExecutionError>
     where
         DB: Database<Error = ProviderError>,
+        H: TransactionHook<Ext, DB>,
     {
         // apply pre execution changes
         apply_beacon_root_contract_call(
@@ -163,10 +311,16 @@ where
             block.parent_hash,
         )?;
 
+        // speculatively execute all transactions in parallel against the state at the start of the
+        // block, the results are validated and committed in order below
+        let mut speculative = self.speculate(&mut evm, block);
+        #[cfg(feature = "std")]
+        let mut access_list = self.access_lists.as_ref().map(|_| BlockAccessListBuilder::default());
+
         // execute transactions
         let mut cumulative_gas_used = 0;
         let mut receipts = Vec::with_capacity(block.body.len());
-        for (sender, transaction) in block.transactions_with_sender() {
+        for (tx_index, (sender, transaction)) in block.transactions_with_sender().enumerate() {
             // The sum of the transaction’s gas limit, Tg, and the gas utilized in this block prior,
             // must be no greater than the block’s gasLimit.
             let block_available_gas = block.header.gas_limit - cumulative_gas_used;
@@ -178,16 +332,38 @@ where
                 .into())
             }
 
-            EvmConfig::fill_tx_env(evm.tx_mut(), transaction, *sender);
-
-            // Execute transaction.
-            let ResultAndState { result, state } = evm.transact().map_err(move |err| {
-                // Ensure hash is calculated for error log, if not already done
-                BlockValidationError::EVM {
-                    hash: transaction.recalculate_hash(),
-                    error: err.into(),
+            // Use the speculative result if it is still valid on top of the committed state.
+            let speculative_result = match speculative.as_mut() {
+                Some(speculative) => speculative.take_valid(tx_index, evm.db_mut())?,
+                None => None,
+            };
+
+            let result_and_state = match speculative_result {
+                Some(result_and_state) => result_and_state,
+                None => {
+                    EvmConfig::fill_tx_env(evm.tx_mut(), transaction, *sender);
+                    hook.before_transaction(&mut evm);
+
+                    // Execute transaction.
+                    evm.transact().map_err(move |err| {
+                        // Ensure hash is calculated for error log, if not already done
+                        BlockValidationError::EVM {
+                            hash: transaction.recalculate_hash(),
+                            error: err.into(),
+                        }
+                    })?
                 }
-            })?;
+            };
+            hook.after_transaction(&mut evm, transaction, &result_and_state)?;
+            let ResultAndState { result, state } = result_and_state;
+
+            if let Some(speculative) = speculative.as_mut() {
+                speculative.record_writes(&state);
+            }
+            #[cfg(feature = "std")]
+            if let Some(access_list) = access_list.as_mut() {
+                access_list.add_state(&state);
+            }
             evm.db_mut().commit(state);
 
             // append gas used
@@ -222,8 +398,207 @@ where
             vec![]
         };
 
+        #[cfg(feature = "std")]
+        if let (Some(recorder), Some(access_list)) = (&self.access_lists, access_list) {
+            recorder.record(block.number, block.header.hash_slow(), access_list.build());
+        }
+
         Ok(EthExecuteOutput { receipts, requests, gas_used: cumulative_gas_used })
     }
+
+    /// Speculatively executes the transactions of the block in parallel, if enabled.
+    ///
+    /// Speculative execution is skipped when tracing or profiling, since the traces and profiles
+    /// must be recorded while executing on top of the committed state.
+    #[cfg(feature = "std")]
+    fn speculate<Ext, DB>(
+        &self,
+        evm: &mut Evm<'_, Ext, &mut State<DB>>,
+        block: &BlockWithSenders,
+    ) -> Option<SpeculativeExecution>
+    where
+        DB: Database<Error = ProviderError>,
+    {
+        #[cfg(feature = "trace")]
+        let inspected = self.tracer.is_some() || self.profiler.is_some();
+        #[cfg(not(feature = "trace"))]
+        let inspected = self.profiler.is_some();
+
+        if !self.parallel || inspected || block.body.len() < 2 {
+            return None
+        }
+        SpeculativeExecution::run(&self.evm_config, evm, block)
+    }
+
+    /// Speculative execution requires the `std` feature, so this always returns `None`.
+    #[cfg(not(feature = "std"))]
+    fn speculate<Ext, DB>(
+        &self,
+        _evm: &mut Evm<'_, Ext, &mut State<DB>>,
+        _block: &BlockWithSenders,
+    ) -> Option<SpeculativeExecution>
+    where
+        DB: Database<Error = ProviderError>,
+    {
+        None
+    }
+
+    /// Executes the block like [`Self::execute_state_transitions`] and writes a trace of its
+    /// transactions with the given tracer.
+    #[cfg(feature = "trace")]
+    fn execute_traced<DB>(
+        &self,
+        tracer: &BlockTracer,
+        block: &BlockWithSenders,
+        state: &mut State<DB>,
+        env: EnvWithHandlerCfg,
+    ) -> Result<EthExecuteOutput, BlockExecutionError>
+    where
+        DB: Database<Error = ProviderError>,
+    {
+        let (output, traces) = match tracer.kind() {
+            TraceKind::Call => {
+                let evm = self.evm_config.evm_with_env_and_inspector(
+                    state,
+                    env,
+                    TracingInspector::new(BlockTracer::inspector_config()),
+                );
+                let mut hook = CallTraceHook::default();
+                (self.execute_state_transitions_with(block, evm, &mut hook)?, hook.traces)
+            }
+            TraceKind::StateDiff => {
+                let evm = self.evm_config.evm_with_env(state, env);
+                let mut hook = StateDiffHook::default();
+                (self.execute_state_transitions_with(block, evm, &mut hook)?, hook.traces)
+            }
+        };
+
+        tracer.write(block.number, &traces).map_err(BlockExecutionError::other)?;
+
+        Ok(output)
+    }
+
+    /// Executes the block like [`Self::execute_state_transitions`] and records its opcode and
+    /// precompile profile with the given profiler.
+    #[cfg(feature = "std")]
+    fn execute_profiled<DB>(
+        &self,
+        profiler: &ExecutionProfiler,
+        block: &BlockWithSenders,
+        state: &mut State<DB>,
+        env: EnvWithHandlerCfg,
+    ) -> Result<EthExecuteOutput, BlockExecutionError>
+    where
+        DB: Database<Error = ProviderError>,
+    {
+        let mut inspector = ProfilingInspector::default();
+        let evm = self.evm_config.evm_with_env_and_inspector(state, env, &mut inspector);
+        let output = self.execute_state_transitions(block, evm)?;
+        profiler.record_block(inspector.into_profile());
+        Ok(output)
+    }
+}
+
+/// Stand-in for the speculative results of a block, which are never produced without the `std`
+/// feature.
+#[cfg(not(feature = "std"))]
+#[derive(Debug)]
+enum SpeculativeExecution {}
+
+#[cfg(not(feature = "std"))]
+impl SpeculativeExecution {
+    fn take_valid<DB>(
+        &mut self,
+        _index: usize,
+        _db: &mut DB,
+    ) -> Result<Option<ResultAndState>, ProviderError> {
+        match *self {}
+    }
+
+    fn record_writes(&mut self, _state: &revm_primitives::EvmState) {
+        match *self {}
+    }
+}
+
+/// Hooks into the execution of the transactions of a block.
+trait TransactionHook<Ext, DB: Database> {
+    /// Invoked before a transaction is executed.
+    fn before_transaction(&mut self, _evm: &mut Evm<'_, Ext, &mut State<DB>>) {}
+
+    /// Invoked after a transaction was executed, before its state changes are committed.
+    fn after_transaction(
+        &mut self,
+        evm: &mut Evm<'_, Ext, &mut State<DB>>,
+        transaction: &TransactionSigned,
+        result: &ResultAndState,
+    ) -> Result<(), BlockExecutionError>;
+}
+
+impl<Ext, DB: Database> TransactionHook<Ext, DB> for () {
+    fn after_transaction(
+        &mut self,
+        _evm: &mut Evm<'_, Ext, &mut State<DB>>,
+        _transaction: &TransactionSigned,
+        _result: &ResultAndState,
+    ) -> Result<(), BlockExecutionError> {
+        Ok(())
+    }
+}
+
+/// Records the call trace of every transaction.
+#[cfg(feature = "trace")]
+#[derive(Debug, Default)]
+struct CallTraceHook {
+    traces: Vec<TransactionTrace>,
+}
+
+#[cfg(feature = "trace")]
+impl<DB: Database> TransactionHook<TracingInspector, DB> for CallTraceHook {
+    fn before_transaction(&mut self, evm: &mut Evm<'_, TracingInspector, &mut State<DB>>) {
+        // discard anything recorded outside of transactions, e.g. system calls
+        evm.context.external = TracingInspector::new(BlockTracer::inspector_config());
+    }
+
+    fn after_transaction(
+        &mut self,
+        evm: &mut Evm<'_, TracingInspector, &mut State<DB>>,
+        transaction: &TransactionSigned,
+        result: &ResultAndState,
+    ) -> Result<(), BlockExecutionError> {
+        let inspector = core::mem::replace(
+            &mut evm.context.external,
+            TracingInspector::new(BlockTracer::inspector_config()),
+        );
+        self.traces.push(TransactionTrace {
+            tx_hash: transaction.hash(),
+            result: BlockTracer::call_trace(inspector, result.result.gas_used()),
+        });
+        Ok(())
+    }
+}
+
+/// Records the state changes of every transaction.
+#[cfg(feature = "trace")]
+#[derive(Debug, Default)]
+struct StateDiffHook {
+    traces: Vec<TransactionTrace>,
+}
+
+#[cfg(feature = "trace")]
+impl<Ext, DB> TransactionHook<Ext, DB> for StateDiffHook
+where
+    DB: Database<Error = ProviderError>,
+{
+    fn after_transaction(
+        &mut self,
+        evm: &mut Evm<'_, Ext, &mut State<DB>>,
+        transaction: &TransactionSigned,
+        result: &ResultAndState,
+    ) -> Result<(), BlockExecutionError> {
+        let diff = state_diff(evm.db_mut(), &result.state)?;
+        self.traces.push(TransactionTrace { tx_hash: transaction.hash(), result: diff });
+        Ok(())
+    }
 }
 
 /// A basic Ethereum block executor.
@@ -242,7 +617,78 @@ pub struct EthBlockExecutor<EvmConfig, DB> {
 impl<EvmConfig, DB> EthBlockExecutor<EvmConfig, DB> {
     /// Creates a new Ethereum block executor.
     pub const fn new(chain_spec: Arc<ChainSpec>, evm_config: EvmConfig, state: State<DB>) -> Self {
-        Self { executor: EthEvmExecutor { chain_spec, evm_config }, state }
+        Self {
+            executor: EthEvmExecutor {
+                chain_spec,
+                evm_config,
+                parallel: false,
+                #[cfg(feature = "trace")]
+                tracer: None,
+                #[cfg(feature = "std")]
+                profiler: None,
+                #[cfg(feature = "std")]
+                access_lists: None,
+                #[cfg(feature = "trace")]
+                slow_blocks: None,
+            },
+            state,
+        }
+    }
+
+    /// Configures whether the transactions of a block are executed optimistically in parallel.
+    ///
+    /// If enabled, all transactions of a block are first executed speculatively on multiple
+    /// threads against the state at the start of the block. The results are then committed in
+    /// block order, and a transaction that read state written by an earlier transaction of the same
+    /// block is re-executed on top of the committed state. The outcome is identical to sequential
+    /// execution.
+    ///
+    /// This has no effect without the `std` feature.
+    pub const fn with_parallel_execution(mut self, parallel: bool) -> Self {
+        self.executor.parallel = parallel;
+        self
+    }
+
+    /// Configures a tracer that writes a trace of every executed block.
+    ///
+    /// Optimistic parallel execution is disabled while tracing.
+    #[cfg(feature = "trace")]
+    pub fn with_tracer(mut self, tracer: BlockTracer) -> Self {
+        self.executor.tracer = Some(tracer);
+        self
+    }
+
+    /// Configures a profiler that accumulates the gas charged and time spent per opcode and
+    /// precompile of all executed blocks.
+    ///
+    /// Profiling is skipped while tracing, and optimistic parallel execution is disabled while
+    /// profiling.
+    #[cfg(feature = "std")]
+    pub fn with_profiler(mut self, profiler: ExecutionProfiler) -> Self {
+        self.executor.profiler = Some(profiler);
+        self
+    }
+
+    /// Configures a recorder the access list of every executed block is recorded to.
+    ///
+    /// The access list of a block holds every account and storage slot loaded by its
+    /// transactions, in the order of their addresses and slots.
+    #[cfg(feature = "std")]
+    pub fn with_access_list_recorder(mut self, recorder: BlockAccessListRecorder) -> Self {
+        self.executor.access_lists = Some(recorder);
+        self
+    }
+
+    /// Configures a detector that captures the traces of slowly executed blocks.
+    ///
+    /// A block of a batch that takes longer than the threshold of the detector is re-executed on
+    /// top of its parent state to capture its opcode profile and call traces. The parent state is
+    /// restored from the reverts of the block, so the reverts of all blocks of the batch are kept
+    /// and the changesets that would be pruned are left to the pruner.
+    #[cfg(feature = "trace")]
+    pub fn with_slow_block_detector(mut self, detector: SlowBlockDetector) -> Self {
+        self.executor.slow_blocks = Some(detector);
+        self
     }
 
     #[inline]
@@ -297,10 +743,7 @@ where
 
         // 2. configure the evm and execute
         let env = self.evm_env_for_block(&block.header, total_difficulty);
-        let output = {
-            let evm = self.executor.evm_config.evm_with_env(&mut self.state, env);
-            self.executor.execute_state_transitions(block, evm)
-        }?;
+        let output = self.execute_transactions(block, env)?;
 
         // 3. apply post execution changes
         self.post_execution(block, total_difficulty)?;
@@ -308,6 +751,24 @@ where
         Ok(output)
     }
 
+    /// Executes the transactions of the block with the configured tracer or profiler, if any.
+    fn execute_transactions(
+        &mut self,
+        block: &BlockWithSenders,
+        env: EnvWithHandlerCfg,
+    ) -> Result<EthExecuteOutput, BlockExecutionError> {
+        #[cfg(feature = "trace")]
+        if let Some(tracer) = &self.executor.tracer {
+            return self.executor.execute_traced(tracer, block, &mut self.state, env)
+        }
+        #[cfg(feature = "std")]
+        if let Some(profiler) = &self.executor.profiler {
+            return self.executor.execute_profiled(profiler, block, &mut self.state, env)
+        }
+        let evm = self.executor.evm_config.evm_with_env(&mut self.state, env);
+        self.executor.execute_state_transitions(block, evm)
+    }
+
     /// Apply settings before a new block is executed.
     pub(crate) fn on_new_block(&mut self, header: &Header) {
         // Set state clear flag if the block is after the Spurious Dragon hardfork.
@@ -315,6 +776,45 @@ where
         self.state.set_state_clear_flag(state_clear_flag);
     }
 
+    /// Re-executes the last executed block on top of its parent state and writes its opcode
+    /// profile and call traces with the detector.
+    ///
+    /// The reverts of the block must have been merged into the bundle state. Returns the directory
+    /// the captures were written to, or `None` if the quota of the detector is exhausted.
+    #[cfg(feature = "trace")]
+    fn capture_slow_block(
+        &mut self,
+        detector: &SlowBlockDetector,
+        block: &BlockWithSenders,
+        total_difficulty: U256,
+        report: &SlowBlockReport,
+    ) -> Result<Option<std::path::PathBuf>, BlockExecutionError> {
+        let Some(dir) = detector
+            .create_block_dir(block.number, report.block_hash)
+            .map_err(BlockExecutionError::other)?
+        else {
+            return Ok(None)
+        };
+        report.write(&dir).map_err(BlockExecutionError::other)?;
+
+        let env = self.evm_env_for_block(&block.header, total_difficulty);
+        let state_clear_flag = self.chain_spec().is_spurious_dragon_active_at_block(block.number);
+
+        let profiler = ExecutionProfiler::new();
+        {
+            let mut state = parent_state(&mut self.state, state_clear_flag);
+            self.executor.execute_profiled(&profiler, block, &mut state, env.clone())?;
+        }
+        std::fs::write(dir.join("profile.txt"), profiler.profile().to_string())
+            .map_err(BlockExecutionError::other)?;
+
+        let tracer = BlockTracer::new(&dir, TraceKind::Call);
+        let mut state = parent_state(&mut self.state, state_clear_flag);
+        self.executor.execute_traced(&tracer, block, &mut state, env)?;
+
+        Ok(Some(dir))
+    }
+
     /// Apply post execution state changes that do not require an [EVM](Evm), such as: block
     /// rewards, withdrawals, and irregular DAO hardfork state change
     pub fn post_execution(
@@ -355,6 +855,18 @@ where
     }
 }
 
+/// Returns the state before the last block whose transitions were merged into the bundle state of
+/// the given state, which must have retained the reverts of the block.
+#[cfg(feature = "trace")]
+fn parent_state<DB: Database>(state: &mut State<DB>, state_clear_flag: bool) -> State<&mut DB> {
+    let mut bundle = state.bundle_state.clone();
+    bundle.revert_latest();
+    let mut parent =
+        State::builder().with_database(&mut state.database).with_bundle_prestate(bundle).build();
+    parent.set_state_clear_flag(state_clear_flag);
+    parent
+}
+
 impl<EvmConfig, DB> Executor<DB> for EthBlockExecutor<EvmConfig, DB>
 where
     EvmConfig: ConfigureEvm,
@@ -416,15 +928,70 @@ where
 
     fn execute_and_verify_one(&mut self, input: Self::Input<'_>) -> Result<(), Self::Error> {
         let BlockExecutionInput { block, total_difficulty } = input;
-        let EthExecuteOutput { receipts, requests, gas_used: _ } =
+        #[cfg(feature = "trace")]
+        let started = std::time::Instant::now();
+        let EthExecuteOutput { receipts, requests, gas_used } =
             self.executor.execute_without_verification(block, total_difficulty)?;
+        #[cfg(feature = "trace")]
+        let elapsed = started.elapsed();
+        #[cfg(not(feature = "trace"))]
+        let _ = gas_used;
 
         validate_block_post_execution(block, self.executor.chain_spec(), &receipts, &requests)?;
 
         // prepare the state according to the prune mode
         let retention = self.batch_record.bundle_retention(block.number);
+        // slow blocks are re-executed on top of the state restored from their reverts
+        #[cfg(feature = "trace")]
+        let retention = if self.executor.executor.slow_blocks.is_some() {
+            BundleRetention::Reverts
+        } else {
+            retention
+        };
         self.executor.state.merge_transitions(retention);
 
+        #[cfg(feature = "trace")]
+        if let Some(detector) = self
+            .executor
+            .executor
+            .slow_blocks
+            .as_ref()
+            .filter(|detector| detector.is_slow(elapsed))
+            .cloned()
+        {
+            let report = SlowBlockReport {
+                block_number: block.number,
+                block_hash: block.header.hash_slow(),
+                elapsed_ms: elapsed.as_millis(),
+                gas_used,
+                transactions: block.body.len(),
+            };
+            match self.executor.capture_slow_block(&detector, block, total_difficulty, &report) {
+                Ok(Some(dir)) => tracing::warn!(
+                    target: "evm::slow_block",
+                    number = block.number,
+                    ?elapsed,
+                    gas_used,
+                    dir = %dir.display(),
+                    "Captured traces of slow block"
+                ),
+                Ok(None) => tracing::warn!(
+                    target: "evm::slow_block",
+                    number = block.number,
+                    ?elapsed,
+                    gas_used,
+                    "Slow block, the disk quota for traces is exhausted"
+                ),
+                Err(err) => tracing::warn!(
+                    target: "evm::slow_block",
+                    number = block.number,
+                    ?elapsed,
+                    %err,
+                    "Failed to capture traces of slow block"
+                ),
+            }
+        }
+
         // store receipts in the set
         self.batch_record.save_receipts(receipts)?;
 
@@ -441,6 +1008,11 @@ where
     fn finalize(mut self) -> Self::Output {
         self.stats.log_debug();
 
+        #[cfg(feature = "std")]
+        if let Some(profiler) = &self.executor.executor.profiler {
+            tracing::info!(target: "evm::profile", "\n{}", profiler.profile());
+        }
+
         ExecutionOutcome::new(
             self.executor.state.take_bundle(),
             self.batch_record.take_receipts(),
@@ -469,7 +1041,8 @@ mod tests {
     use reth_chainspec::{ChainSpecBuilder, ForkCondition};
     use reth_primitives::{
         constants::{EMPTY_ROOT_HASH, ETH_TO_WEI},
-        keccak256, public_key_to_address, Account, Block, Transaction, TxKind, TxLegacy, B256,
+        keccak256, public_key_to_address, Account, Address, Block, Transaction, TxKind, TxLegacy,
+        B256,
     };
     use reth_revm::{
         database::StateProviderDatabase, test_utils::StateProviderTest, TransitionState,
@@ -518,7 +1091,7 @@ mod tests {
     }
 
     fn executor_provider(chain_spec: Arc<ChainSpec>) -> EthExecutorProvider<EthEvmConfig> {
-        EthExecutorProvider { chain_spec, evm_config: Default::default() }
+        EthExecutorProvider::new(chain_spec, Default::default())
     }
 
     #[test]
@@ -610,6 +1183,46 @@ mod tests {
         assert_eq!(parent_beacon_block_root_storage, U256::from(0x69));
     }
 
+    #[test]
+    fn scheduled_hardfork_applies_to_running_executor() {
+        let db = create_state_provider_with_beacon_root_contract();
+        let chain_spec = Arc::new(ChainSpecBuilder::mainnet().shanghai_activated().build());
+        let provider = executor_provider(chain_spec.clone());
+
+        let block = BlockWithSenders {
+            block: Block {
+                header: Header { timestamp: 10, number: 1, ..Header::default() },
+                body: vec![],
+                ommers: vec![],
+                withdrawals: None,
+                requests: None,
+            },
+            senders: vec![],
+        };
+
+        // cancun is not active yet, so the block doesn't need a parent beacon block root
+        provider
+            .executor(StateProviderDatabase::new(&db))
+            .execute_without_verification(&block, U256::ZERO)
+            .unwrap();
+
+        // move the cancun activation before the block while the executor is in use
+        let mut updated = (*chain_spec).clone();
+        updated.set_fork_condition(Hardfork::Cancun, ForkCondition::Timestamp(10));
+        let head = reth_primitives::Head { timestamp: 1, ..Default::default() };
+        let updates = chain_spec.hardfork_updates(&updated, &head).unwrap();
+        chain_spec.schedule_hardforks(&updates);
+
+        let err = provider
+            .executor(StateProviderDatabase::new(&db))
+            .execute_without_verification(&block, U256::ZERO)
+            .expect_err("the block is a cancun block after the update");
+        assert_eq!(
+            err.as_validation().unwrap().clone(),
+            BlockValidationError::MissingParentBeaconBlockRoot
+        );
+    }
+
     #[test]
     fn eip_4788_no_code_cancun() {
         // This test ensures that we "silently fail" when cancun is active and there is no code at
@@ -1409,4 +2022,72 @@ mod tests {
             ),
         }
     }
+
+    #[test]
+    fn parallel_execution_matches_sequential() {
+        let chain_spec = Arc::new(ChainSpecBuilder::from(&*MAINNET).shanghai_activated().build());
+
+        let secp = Secp256k1::new();
+        let mut rng = generators::rng();
+        let key_pairs = [Keypair::new(&secp, &mut rng), Keypair::new(&secp, &mut rng)];
+        let [alice, bob] = key_pairs.map(|key_pair| public_key_to_address(key_pair.public_key()));
+        let beneficiary = Address::with_last_byte(0xcb);
+
+        let mut db = StateProviderTest::default();
+        for address in [alice, bob] {
+            db.insert_account(
+                address,
+                Account { nonce: 0, balance: U256::from(ETH_TO_WEI), bytecode_hash: None },
+                None,
+                HashMap::new(),
+            );
+        }
+
+        let mut header = chain_spec.genesis_header();
+        header.number = 1;
+        header.gas_limit = 1_000_000;
+        header.beneficiary = beneficiary;
+
+        // the second transaction of alice depends on her first one, and the transfer from bob to
+        // alice depends on both, while every transaction pays the beneficiary
+        let transfers = [(0, 0, bob), (0, 1, beneficiary), (1, 0, alice)];
+        let body = transfers
+            .into_iter()
+            .map(|(key_pair, nonce, to)| {
+                sign_tx_with_key_pair(
+                    key_pairs[key_pair],
+                    Transaction::Legacy(TxLegacy {
+                        chain_id: Some(chain_spec.chain.id()),
+                        nonce,
+                        gas_price: header.base_fee_per_gas.unwrap() as u128 + 1,
+                        gas_limit: 21_000,
+                        to: TxKind::Call(to),
+                        value: U256::from(1_000),
+                        input: Bytes::default(),
+                    }),
+                )
+            })
+            .collect();
+        let block = Block { header, body, ommers: vec![], withdrawals: None, requests: None }
+            .with_recovered_senders()
+            .unwrap();
+
+        let sequential = executor_provider(chain_spec.clone())
+            .executor(StateProviderDatabase::new(&db))
+            .execute((&block, U256::ZERO).into())
+            .unwrap();
+        let parallel = executor_provider(chain_spec)
+            .with_parallel_execution(true)
+            .executor(StateProviderDatabase::new(&db))
+            .execute((&block, U256::ZERO).into())
+            .unwrap();
+
+        assert_eq!(parallel.receipts, sequential.receipts);
+        assert_eq!(parallel.gas_used, sequential.gas_used);
+        assert_eq!(parallel.state, sequential.state);
+        assert_eq!(
+            parallel.state.account(&beneficiary).unwrap().info.as_ref().unwrap().balance,
+            U256::from(3 * 21_000 + 1_000)
+        );
+    }
 }
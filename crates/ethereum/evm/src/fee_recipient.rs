@@ -0,0 +1,33 @@
+//! Redirection of the priority fees to a recipient other than the block beneficiary.
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, sync::Arc};
+#[cfg(feature = "std")]
+use std::sync::Arc;
+
+use core::mem;
+use reth_primitives::Address;
+use reth_revm::{handler::register::HandleRegisterBox, interpreter::Gas, Context, Database};
+
+/// Returns a handler register that credits the priority fees of every transaction to
+/// `fee_recipient` instead of the block beneficiary.
+///
+/// Only the fee payment is redirected: the `COINBASE` opcode keeps returning the block
+/// beneficiary, and the block rewards follow the
+/// [`BlockRewards`](reth_chainspec::BlockRewards) rules of the chain.
+pub fn fee_recipient_handler_register<EXT, DB: Database>(
+    fee_recipient: Address,
+) -> HandleRegisterBox<EXT, DB> {
+    Box::new(move |handler| {
+        let reward_beneficiary = handler.post_execution.reward_beneficiary.clone();
+        handler.post_execution.reward_beneficiary =
+            Arc::new(move |context: &mut Context<EXT, DB>, gas: &Gas| {
+                // the fees are paid after the transaction has run, so swapping the coinbase for the
+                // payment is not observable by the executed code
+                let beneficiary = mem::replace(&mut context.evm.env.block.coinbase, fee_recipient);
+                let result = reward_beneficiary(context, gas);
+                context.evm.env.block.coinbase = beneficiary;
+                result
+            });
+    })
+}
@@ -12,17 +12,49 @@
 #[cfg(not(feature = "std"))]
 extern crate alloc;
 
-use reth_chainspec::ChainSpec;
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+
+use reth_chainspec::{BlockHashHistory, ChainSpec};
 use reth_evm::{ConfigureEvm, ConfigureEvmEnv};
 use reth_primitives::{
     revm::{config::revm_spec, env::fill_tx_env},
     revm_primitives::{AnalysisKind, CfgEnvWithHandlerCfg, TxEnv},
     Address, Head, Header, TransactionSigned, U256,
 };
-use reth_revm::{Database, EvmBuilder};
+use reth_revm::{
+    handler::register::HandleRegisterBox, inspector_handle_register, Database, EvmBuilder,
+    GetInspector,
+};
 
 pub mod execute;
 
+/// Optimistic parallel transaction execution.
+#[cfg(feature = "std")]
+mod parallel;
+
+/// Tracing of executed blocks.
+#[cfg(feature = "trace")]
+pub mod trace;
+
+/// Profiling of opcode and precompile execution.
+#[cfg(feature = "std")]
+pub mod profile;
+
+/// Capture of the traces of slowly executed blocks.
+#[cfg(feature = "trace")]
+pub mod slow_block;
+
+/// Registration of additional precompiles.
+pub mod precompiles;
+pub use precompiles::CustomPrecompiles;
+
+/// Serving of the `BLOCKHASH` opcode for a configurable number of ancestors.
+pub mod block_hash;
+
+/// Redirection of the priority fees to a recipient other than the block beneficiary.
+pub mod fee_recipient;
+
 /// Ethereum DAO hardfork state change data.
 pub mod dao_fork;
 
@@ -30,9 +62,109 @@ pub mod dao_fork;
 pub mod eip6110;
 
 /// Ethereum-related EVM configuration.
-#[derive(Debug, Clone, Copy, Default)]
+///
+/// Additional precompiles can be installed with [`EthEvmConfig::with_precompiles`], so chains
+/// that need a precompile at a new address don't have to provide their own EVM configuration.
+/// Chains whose `BLOCKHASH` opcode serves more than the last 256 block hashes configure it with
+/// [`EthEvmConfig::with_block_hash_history`], and chains that pay the priority fees to another
+/// recipient than the block beneficiary with [`EthEvmConfig::with_fee_recipient`].
+#[derive(Debug, Clone, Copy)]
 #[non_exhaustive]
-pub struct EthEvmConfig;
+pub struct EthEvmConfig {
+    /// Precompiles installed on top of the hardfork defaults.
+    precompiles: &'static CustomPrecompiles,
+    /// The ancestors whose hashes the `BLOCKHASH` opcode returns.
+    block_hash_history: BlockHashHistory,
+    /// The recipient of the priority fees, if not the block beneficiary.
+    fee_recipient: Option<Address>,
+}
+
+/// The precompiles of a configuration without custom precompiles.
+static NO_PRECOMPILES: CustomPrecompiles = CustomPrecompiles::new();
+
+impl Default for EthEvmConfig {
+    fn default() -> Self {
+        Self {
+            precompiles: &NO_PRECOMPILES,
+            block_hash_history: BlockHashHistory::default(),
+            fee_recipient: None,
+        }
+    }
+}
+
+impl EthEvmConfig {
+    /// Creates a new EVM configuration that installs the given precompiles on top of the default
+    /// precompiles of the active hardfork.
+    ///
+    /// The precompiles are referenced rather than owned, so the configuration stays `Copy`. They
+    /// are usually registered once at startup and leaked, or kept in a `static`.
+    pub fn with_precompiles(precompiles: &'static CustomPrecompiles) -> Self {
+        Self { precompiles, ..Default::default() }
+    }
+
+    /// Sets the ancestors whose hashes the `BLOCKHASH` opcode returns, usually the
+    /// [`ChainSpec::block_hash_history`] of the chain.
+    pub const fn with_block_hash_history(mut self, block_hash_history: BlockHashHistory) -> Self {
+        self.block_hash_history = block_hash_history;
+        self
+    }
+
+    /// Sets the recipient of the priority fees, usually the
+    /// [`BlockRewards::fee_recipient`](reth_chainspec::BlockRewards::fee_recipient) of the chain.
+    ///
+    /// The `COINBASE` opcode still returns the block beneficiary.
+    pub const fn with_fee_recipient(mut self, fee_recipient: Option<Address>) -> Self {
+        self.fee_recipient = fee_recipient;
+        self
+    }
+
+    /// Applies the execution rules of the chain spec: its
+    /// [`ChainSpec::block_hash_history`] and the
+    /// [`BlockRewards::fee_recipient`](reth_chainspec::BlockRewards::fee_recipient).
+    pub const fn with_chain_rules(self, chain_spec: &ChainSpec) -> Self {
+        self.with_block_hash_history(chain_spec.block_hash_history)
+            .with_fee_recipient(chain_spec.block_rewards.fee_recipient)
+    }
+
+    /// Returns the additional precompiles of this configuration.
+    pub const fn precompiles(&self) -> &'static CustomPrecompiles {
+        self.precompiles
+    }
+
+    /// Returns the ancestors whose hashes the `BLOCKHASH` opcode returns.
+    pub const fn block_hash_history(&self) -> &BlockHashHistory {
+        &self.block_hash_history
+    }
+
+    /// Returns the recipient of the priority fees, if not the block beneficiary.
+    pub const fn fee_recipient(&self) -> Option<Address> {
+        self.fee_recipient
+    }
+
+    /// Returns `true` if the EVM's handler is not customised by [`Self::handler_register`].
+    fn is_default_handler(&self) -> bool {
+        self.precompiles.is_empty() &&
+            !self.block_hash_history.is_custom() &&
+            self.fee_recipient.is_none()
+    }
+
+    /// Returns a handler register that installs the custom precompiles and `BLOCKHASH`
+    /// instruction and the fee recipient of this configuration.
+    fn handler_register<EXT, DB: Database>(&self) -> HandleRegisterBox<EXT, DB> {
+        let precompiles =
+            (!self.precompiles.is_empty()).then(|| self.precompiles.handler_register::<EXT, DB>());
+        let block_hashes = self
+            .block_hash_history
+            .is_custom()
+            .then(|| block_hash::block_hash_handler_register::<EXT, DB>(self.block_hash_history));
+        let fees = self.fee_recipient.map(fee_recipient::fee_recipient_handler_register::<EXT, DB>);
+        Box::new(move |handler| {
+            for register in precompiles.iter().chain(&block_hashes).chain(&fees) {
+                register(handler);
+            }
+        })
+    }
+}
 
 impl ConfigureEvmEnv for EthEvmConfig {
     fn fill_tx_env(tx_env: &mut TxEnv, transaction: &TransactionSigned, sender: Address) {
@@ -70,7 +202,35 @@ impl ConfigureEvm for EthEvmConfig {
         &self,
         db: DB,
     ) -> reth_revm::Evm<'a, Self::DefaultExternalContext<'a>, DB> {
-        EvmBuilder::default().with_db(db).build()
+        if self.is_default_handler() {
+            return EvmBuilder::default().with_db(db).build()
+        }
+
+        EvmBuilder::default()
+            .with_db(db)
+            .append_handler_register_box(self.handler_register())
+            .build()
+    }
+
+    fn evm_with_inspector<'a, DB, I>(&self, db: DB, inspector: I) -> reth_revm::Evm<'a, I, DB>
+    where
+        DB: Database + 'a,
+        I: GetInspector<DB>,
+    {
+        if self.is_default_handler() {
+            return EvmBuilder::default()
+                .with_db(db)
+                .with_external_context(inspector)
+                .append_handler_register(inspector_handle_register)
+                .build()
+        }
+
+        EvmBuilder::default()
+            .with_db(db)
+            .with_external_context(inspector)
+            .append_handler_register_box(self.handler_register())
+            .append_handler_register(inspector_handle_register)
+            .build()
     }
 }
 
@@ -98,4 +258,127 @@ mod tests {
 
         assert_eq!(cfg_env.chain_id, chain_spec.chain().id());
     }
+
+    #[test]
+    fn custom_precompiles_by_hardfork() {
+        use reth_primitives::{
+            address,
+            revm_primitives::{Env, PrecompileOutput, PrecompileResult},
+            Bytes,
+        };
+        use reth_revm::{db::EmptyDB, precompile::Precompile};
+
+        fn noop(_data: &Bytes, _gas: u64, _env: &Env) -> PrecompileResult {
+            Ok(PrecompileOutput::new(0, Bytes::new()))
+        }
+
+        let custom = address!("0000000000000000000000000000000000000999");
+        let precompiles =
+            CustomPrecompiles::new().with_precompile(SpecId::CANCUN, custom, Precompile::Env(noop));
+        let config = EthEvmConfig::with_precompiles(Box::leak(Box::new(precompiles)));
+
+        let mut evm = config.evm(EmptyDB::default());
+        evm.modify_spec_id(SpecId::SHANGHAI);
+        let precompiles = (evm.handler.pre_execution.load_precompiles)();
+        assert!(!precompiles.contains(&custom));
+
+        evm.modify_spec_id(SpecId::CANCUN);
+        let precompiles = (evm.handler.pre_execution.load_precompiles)();
+        assert!(precompiles.contains(&custom));
+        // the default precompiles are preserved
+        assert!(precompiles.contains(&address!("0000000000000000000000000000000000000001")));
+    }
+
+    #[test]
+    fn block_hash_beyond_default_window() {
+        use reth_primitives::{
+            bytes,
+            revm_primitives::{AccountInfo, Bytecode, Env, EnvWithHandlerCfg, TransactTo},
+            B256,
+        };
+        use reth_revm::db::{CacheDB, EmptyDB};
+
+        let contract = Address::with_last_byte(0xaa);
+        // stores the hash of block 100 in slot 0: PUSH1 0x64 BLOCKHASH PUSH1 0 SSTORE
+        let code = Bytecode::new_raw(bytes!("606440600055"));
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            contract,
+            AccountInfo { code_hash: code.hash_slow(), code: Some(code), ..Default::default() },
+        );
+        db.block_hashes.insert(U256::from(100), B256::with_last_byte(1));
+
+        let block_hash_at = |config: &EthEvmConfig, spec_id, number: u64| {
+            let mut env = Env::default();
+            env.block.number = U256::from(number);
+            env.tx.transact_to = TransactTo::Call(contract);
+            env.tx.gas_limit = 100_000;
+            let env = EnvWithHandlerCfg::new_with_spec_id(Box::new(env), spec_id);
+            let state = config.evm_with_env(db.clone(), env).transact().unwrap().state;
+            B256::from(state[&contract].storage[&U256::ZERO].present_value)
+        };
+
+        // the last 256 block hashes are served by default
+        let config = EthEvmConfig::default();
+        assert_eq!(block_hash_at(&config, SpecId::CANCUN, 356), B256::with_last_byte(1));
+        assert_eq!(block_hash_at(&config, SpecId::CANCUN, 1100), B256::ZERO);
+
+        let config = EthEvmConfig::default().with_block_hash_history(BlockHashHistory {
+            prague_window: Some(8192),
+            ..Default::default()
+        });
+        assert_eq!(block_hash_at(&config, SpecId::CANCUN, 1100), B256::ZERO);
+        assert_eq!(block_hash_at(&config, SpecId::PRAGUE, 1100), B256::with_last_byte(1));
+        assert_eq!(block_hash_at(&config, SpecId::PRAGUE, 100 + 8193), B256::ZERO);
+        // the hash of the current block is not known
+        assert_eq!(block_hash_at(&config, SpecId::PRAGUE, 100), B256::ZERO);
+    }
+
+    #[test]
+    fn fee_recipient_keeps_coinbase() {
+        use reth_primitives::{
+            bytes,
+            revm_primitives::{AccountInfo, Bytecode, Env, EnvWithHandlerCfg, TransactTo},
+        };
+        use reth_revm::db::{CacheDB, EmptyDB};
+
+        let beneficiary = Address::with_last_byte(0xbb);
+        let fee_recipient = Address::with_last_byte(0xfe);
+        let caller = Address::with_last_byte(0xcc);
+        let contract = Address::with_last_byte(0xaa);
+        // stores the coinbase in slot 0: COINBASE PUSH1 0 SSTORE
+        let code = Bytecode::new_raw(bytes!("41600055"));
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            contract,
+            AccountInfo { code_hash: code.hash_slow(), code: Some(code), ..Default::default() },
+        );
+        db.insert_account_info(
+            caller,
+            AccountInfo { balance: U256::from(10).pow(U256::from(18)), ..Default::default() },
+        );
+
+        let mut env = Env::default();
+        env.block.coinbase = beneficiary;
+        env.block.basefee = U256::from(7);
+        env.tx.caller = caller;
+        env.tx.transact_to = TransactTo::Call(contract);
+        env.tx.gas_limit = 100_000;
+        env.tx.gas_price = U256::from(10);
+        let env = EnvWithHandlerCfg::new_with_spec_id(Box::new(env), SpecId::CANCUN);
+
+        let config = EthEvmConfig::default().with_fee_recipient(Some(fee_recipient));
+        let result = config.evm_with_env(db, env).transact().unwrap();
+        let balance = |address| result.state.get(&address).map_or(U256::ZERO, |a| a.info.balance);
+
+        // the executed code still sees the block beneficiary
+        assert_eq!(
+            result.state[&contract].storage[&U256::ZERO].present_value,
+            U256::from_be_slice(beneficiary.as_slice())
+        );
+        // the priority fee goes to the fee recipient
+        let priority_fee = U256::from(3 * result.result.gas_used());
+        assert_eq!(balance(fee_recipient), priority_fee);
+        assert_eq!(balance(beneficiary), U256::ZERO);
+    }
 }
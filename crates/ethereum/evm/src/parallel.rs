@@ -0,0 +1,486 @@
+//! Optimistic parallel execution of the transactions of a block.
+//!
+//! This follows the approach of Block-STM with a single validation round: all transactions of a
+//! block are first executed speculatively and in parallel against the state at the start of the
+//! block, recording every account and storage slot they read. The results are then committed in
+//! block order. A speculative result is only used if none of the locations it read were written
+//! by a transaction committed before it, otherwise the transaction is re-executed on top of the
+//! committed state. This keeps the outcome identical to sequential execution.
+//!
+//! Every transaction pays its fee to the block beneficiary, which would make all transactions
+//! conflict with each other. The beneficiary balance is therefore tracked as a delta: speculative
+//! results of transactions that do not otherwise observe the beneficiary are rebased on the
+//! committed beneficiary balance.
+//!
+//! The workers run on a thread pool that is shared by all executors. They read the state that
+//! was already loaded by the executor, i.e. everything touched by earlier blocks of the batch and
+//! the pre-execution changes of the block, from a shared snapshot of its cache. Only the remaining
+//! reads are forwarded to the thread that owns the database of the executor.
+
+use reth_evm::{execute::ProviderError, ConfigureEvm};
+use reth_primitives::{Address, BlockWithSenders, B256, U256};
+use reth_revm::{
+    db::CacheState,
+    interpreter::{opcode, CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter},
+    Evm, EvmContext, Inspector, State,
+};
+use revm_primitives::{
+    db::Database, AccountInfo, Bytecode, EnvWithHandlerCfg, EvmState, ResultAndState,
+};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, OnceLock,
+    },
+    thread,
+};
+
+/// Returns the thread pool the workers of all executors run on, created on first use.
+///
+/// The threads inherit the CPU affinity of the thread that first executes a block in parallel.
+fn worker_pool() -> Option<&'static rayon::ThreadPool> {
+    static POOL: OnceLock<Option<rayon::ThreadPool>> = OnceLock::new();
+    POOL.get_or_init(|| {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(thread::available_parallelism().map_or(1, NonZeroUsize::get))
+            .thread_name(|index| format!("reth-parallel-exec-{index}"))
+            .build()
+            .ok()
+    })
+    .as_ref()
+}
+
+/// Speculative results of the transactions of a block, validated as they are committed.
+#[derive(Debug)]
+pub(crate) struct SpeculativeExecution {
+    /// The block beneficiary of the EVM block environment.
+    coinbase: Address,
+    /// Speculative result for each transaction, `None` if the transaction must be re-executed.
+    results: Vec<Option<Speculation>>,
+    /// Locations written by the transactions committed so far.
+    writes: WriteSet,
+}
+
+impl SpeculativeExecution {
+    /// Speculatively executes all transactions of the block in parallel, on top of the current
+    /// state of the given EVM.
+    ///
+    /// Returns `None` if there is no parallelism available.
+    pub(crate) fn run<EvmConfig, Ext, DB>(
+        evm_config: &EvmConfig,
+        evm: &mut Evm<'_, Ext, &mut State<DB>>,
+        block: &BlockWithSenders,
+    ) -> Option<Self>
+    where
+        EvmConfig: ConfigureEvm,
+        DB: Database<Error = ProviderError>,
+    {
+        let pool = worker_pool()?;
+        let workers = pool.current_num_threads().min(block.body.len());
+        if workers < 2 {
+            return None
+        }
+
+        let env = EnvWithHandlerCfg::new(evm.context.evm.env.clone(), *evm.handler_cfg());
+        let coinbase = env.block.coinbase;
+
+        let state = &mut **evm.db_mut();
+        // accounts of a preloaded bundle are only moved into the cache once they are loaded, so
+        // the snapshot would miss them
+        if state.use_preloaded_bundle {
+            return None
+        }
+        let snapshot = StateSnapshot { cache: &state.cache, block_hashes: &state.block_hashes };
+        let database = &mut state.database;
+
+        let next_tx = AtomicUsize::new(0);
+        let (requests_tx, requests_rx) = mpsc::channel();
+        let (results_tx, results_rx) = mpsc::channel();
+
+        pool.in_place_scope(|scope| {
+            for _ in 0..workers {
+                let db = SpeculativeDb::new(&snapshot, requests_tx.clone());
+                let env = env.clone();
+                let results_tx = results_tx.clone();
+                let next_tx = &next_tx;
+                scope.spawn(move |_| {
+                    let _ =
+                        results_tx.send(speculate(evm_config, env, db, block, coinbase, next_tx));
+                });
+            }
+            drop((requests_tx, results_tx));
+
+            // The database is not thread safe, so the reads of the workers that miss the snapshot
+            // are served from here. The loop ends once all workers are done and have dropped their
+            // request senders.
+            for request in requests_rx {
+                request.serve(database);
+            }
+        });
+
+        let mut results: Vec<Option<Speculation>> = Vec::new();
+        results.resize_with(block.body.len(), || None);
+        for (index, speculation) in results_rx.into_iter().flatten() {
+            results[index] = speculation;
+        }
+
+        Some(Self { coinbase, results, writes: WriteSet::default() })
+    }
+
+    /// Returns the speculative result of the transaction at the given index if it is still valid
+    /// on top of the state committed so far.
+    ///
+    /// The beneficiary balance of the returned state is rebased on the committed balance.
+    pub(crate) fn take_valid<DB>(
+        &mut self,
+        index: usize,
+        db: &mut DB,
+    ) -> Result<Option<ResultAndState>, ProviderError>
+    where
+        DB: Database<Error = ProviderError>,
+    {
+        let Some(Speculation { mut result_and_state, reads, coinbase_balance }) =
+            self.results.get_mut(index).and_then(Option::take)
+        else {
+            return Ok(None)
+        };
+
+        if self.writes.conflicts_with(&reads, self.coinbase) {
+            return Ok(None)
+        }
+
+        if let Some(account) = result_and_state.state.get_mut(&self.coinbase) {
+            let fee = account.info.balance.saturating_sub(coinbase_balance);
+            let committed = db.basic(self.coinbase)?.map(|info| info.balance).unwrap_or_default();
+            account.info.balance = committed + fee;
+        }
+
+        Ok(Some(result_and_state))
+    }
+
+    /// Records the locations written by a committed transaction.
+    pub(crate) fn record_writes(&mut self, state: &EvmState) {
+        self.writes.record(state, self.coinbase);
+    }
+}
+
+/// Executes transactions of the block until there are none left, returning the speculative result
+/// for each executed transaction.
+fn speculate<EvmConfig>(
+    evm_config: &EvmConfig,
+    env: EnvWithHandlerCfg,
+    db: SpeculativeDb<'_>,
+    block: &BlockWithSenders,
+    coinbase: Address,
+    next_tx: &AtomicUsize,
+) -> Vec<(usize, Option<Speculation>)>
+where
+    EvmConfig: ConfigureEvm,
+{
+    let mut evm = evm_config.evm_with_env_and_inspector(db, env, CoinbaseInspector::new(coinbase));
+    let mut speculated = Vec::new();
+
+    loop {
+        let index = next_tx.fetch_add(1, Ordering::Relaxed);
+        let (Some(sender), Some(transaction)) = (block.senders.get(index), block.body.get(index))
+        else {
+            break
+        };
+
+        EvmConfig::fill_tx_env(evm.tx_mut(), transaction, *sender);
+        evm.context.external.observed = *sender == coinbase;
+        evm.db_mut().reads = ReadSet::default();
+
+        let result = evm.transact();
+        let reads = std::mem::take(&mut evm.db_mut().reads);
+
+        // Transactions that observe the beneficiary can not be rebased on the committed
+        // beneficiary balance, and failed transactions may be valid on the committed state.
+        let speculation = match result {
+            Ok(result_and_state) if !evm.context.external.observed => Some(Speculation {
+                result_and_state,
+                coinbase_balance: evm.db_mut().cached_balance(coinbase),
+                reads,
+            }),
+            _ => None,
+        };
+        speculated.push((index, speculation));
+    }
+
+    speculated
+}
+
+/// The speculative result of a transaction.
+#[derive(Debug)]
+struct Speculation {
+    /// The result of the transaction and the state it produced.
+    result_and_state: ResultAndState,
+    /// The locations the transaction read.
+    reads: ReadSet,
+    /// The beneficiary balance at the start of the block.
+    coinbase_balance: U256,
+}
+
+/// Accounts and storage slots read by a transaction.
+#[derive(Debug, Default)]
+struct ReadSet {
+    accounts: HashSet<Address>,
+    storage: HashSet<(Address, U256)>,
+}
+
+/// Accounts and storage slots written by committed transactions.
+#[derive(Debug, Default)]
+struct WriteSet {
+    accounts: HashSet<Address>,
+    storage: HashSet<(Address, U256)>,
+    /// Accounts whose storage was cleared, by selfdestruct or (re)creation.
+    wiped: HashSet<Address>,
+}
+
+impl WriteSet {
+    /// Records the writes of the given transaction state.
+    ///
+    /// The beneficiary is skipped, since its balance is tracked as a delta.
+    fn record(&mut self, state: &EvmState, coinbase: Address) {
+        for (address, account) in state {
+            if *address == coinbase || !account.is_touched() {
+                continue
+            }
+
+            self.accounts.insert(*address);
+            if account.is_selfdestructed() || account.is_created() {
+                self.wiped.insert(*address);
+            }
+            self.storage.extend(
+                account
+                    .storage
+                    .iter()
+                    .filter(|(_, slot)| slot.is_changed())
+                    .map(|(key, _)| (*address, *key)),
+            );
+        }
+    }
+
+    /// Returns `true` if any of the reads observed a location that was written.
+    fn conflicts_with(&self, reads: &ReadSet, coinbase: Address) -> bool {
+        reads.accounts.iter().any(|address| *address != coinbase && self.accounts.contains(address)) ||
+            reads.storage.iter().any(|(address, key)| {
+                self.wiped.contains(address) || self.storage.contains(&(*address, *key))
+            })
+    }
+}
+
+/// A read request of a worker, answered by the thread that owns the state.
+enum ReadRequest {
+    Basic(Address, mpsc::Sender<Result<Option<AccountInfo>, ProviderError>>),
+    Code(B256, mpsc::Sender<Result<Bytecode, ProviderError>>),
+    Storage(Address, U256, mpsc::Sender<Result<U256, ProviderError>>),
+    BlockHash(U256, mpsc::Sender<Result<B256, ProviderError>>),
+}
+
+impl ReadRequest {
+    /// Answers the request from the given database.
+    fn serve<DB: Database<Error = ProviderError>>(self, db: &mut DB) {
+        // a closed reply channel means the worker is gone, in which case the reply is not needed
+        match self {
+            Self::Basic(address, reply) => {
+                // empty accounts are loaded into the cache of the state without their code hash,
+                // see `State::load_cache_account`
+                let account = db.basic(address).map(|account| {
+                    account.map(|info| if info.is_empty() { AccountInfo::default() } else { info })
+                });
+                let _ = reply.send(account);
+            }
+            Self::Code(code_hash, reply) => {
+                let _ = reply.send(db.code_by_hash(code_hash));
+            }
+            Self::Storage(address, index, reply) => {
+                let _ = reply.send(db.storage(address, index));
+            }
+            Self::BlockHash(number, reply) => {
+                let _ = reply.send(db.block_hash(number));
+            }
+        }
+    }
+}
+
+/// The state loaded by the executor before the block, shared by all workers.
+struct StateSnapshot<'a> {
+    cache: &'a CacheState,
+    block_hashes: &'a BTreeMap<u64, B256>,
+}
+
+impl StateSnapshot<'_> {
+    /// Returns the value of a storage slot, or `None` if it must be read from the database.
+    ///
+    /// This follows the storage reads of [`State`].
+    fn storage(&self, address: Address, index: U256) -> Option<U256> {
+        let account = self.cache.accounts.get(&address)?;
+        match &account.account {
+            Some(plain) => plain
+                .storage
+                .get(&index)
+                .copied()
+                .or_else(|| account.status.is_storage_known().then_some(U256::ZERO)),
+            None => Some(U256::ZERO),
+        }
+    }
+}
+
+/// The database of a worker.
+///
+/// Reads are answered from the snapshot if possible, and otherwise forwarded to the thread that
+/// owns the database. Either way they are cached, since the state does not change while
+/// transactions are executed speculatively. Account and storage reads are recorded for
+/// validation.
+struct SpeculativeDb<'a> {
+    snapshot: &'a StateSnapshot<'a>,
+    requests: mpsc::Sender<ReadRequest>,
+    accounts: HashMap<Address, Option<AccountInfo>>,
+    contracts: HashMap<B256, Bytecode>,
+    storage: HashMap<(Address, U256), U256>,
+    block_hashes: HashMap<U256, B256>,
+    /// The locations read by the current transaction.
+    reads: ReadSet,
+}
+
+impl<'a> SpeculativeDb<'a> {
+    fn new(snapshot: &'a StateSnapshot<'a>, requests: mpsc::Sender<ReadRequest>) -> Self {
+        Self {
+            snapshot,
+            requests,
+            accounts: HashMap::new(),
+            contracts: HashMap::new(),
+            storage: HashMap::new(),
+            block_hashes: HashMap::new(),
+            reads: ReadSet::default(),
+        }
+    }
+
+    /// Sends a read request and waits for the reply.
+    fn request<T>(
+        &self,
+        request: impl FnOnce(mpsc::Sender<Result<T, ProviderError>>) -> ReadRequest,
+    ) -> Result<T, ProviderError> {
+        let (tx, rx) = mpsc::channel();
+        self.requests.send(request(tx)).expect("requests are served until all workers are done");
+        rx.recv().expect("every request is answered")
+    }
+
+    /// Returns the cached balance of the account at the start of the block.
+    fn cached_balance(&self, address: Address) -> U256 {
+        self.accounts.get(&address).cloned().flatten().map(|info| info.balance).unwrap_or_default()
+    }
+}
+
+impl Database for SpeculativeDb<'_> {
+    type Error = ProviderError;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.reads.accounts.insert(address);
+        if let Some(account) = self.accounts.get(&address) {
+            return Ok(account.clone())
+        }
+        let account = match self.snapshot.cache.accounts.get(&address) {
+            Some(account) => account.account_info(),
+            None => self.request(|reply| ReadRequest::Basic(address, reply))?,
+        };
+        self.accounts.insert(address, account.clone());
+        Ok(account)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        // code is immutable, so there is no need to record the read
+        if let Some(code) = self.contracts.get(&code_hash) {
+            return Ok(code.clone())
+        }
+        let code = match self.snapshot.cache.contracts.get(&code_hash) {
+            Some(code) => code.clone(),
+            None => self.request(|reply| ReadRequest::Code(code_hash, reply))?,
+        };
+        self.contracts.insert(code_hash, code.clone());
+        Ok(code)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.reads.storage.insert((address, index));
+        if let Some(value) = self.storage.get(&(address, index)) {
+            return Ok(*value)
+        }
+        let value = match self.snapshot.storage(address, index) {
+            Some(value) => value,
+            None => self.request(|reply| ReadRequest::Storage(address, index, reply))?,
+        };
+        self.storage.insert((address, index), value);
+        Ok(value)
+    }
+
+    fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
+        // block hashes are not written by transactions
+        if let Some(hash) = self.block_hashes.get(&number) {
+            return Ok(*hash)
+        }
+        let cached =
+            u64::try_from(number).ok().and_then(|number| self.snapshot.block_hashes.get(&number));
+        let hash = match cached {
+            Some(hash) => *hash,
+            None => self.request(|reply| ReadRequest::BlockHash(number, reply))?,
+        };
+        self.block_hashes.insert(number, hash);
+        Ok(hash)
+    }
+}
+
+/// Detects whether a transaction observes the block beneficiary other than by paying its fee.
+#[derive(Debug)]
+struct CoinbaseInspector {
+    coinbase: Address,
+    observed: bool,
+}
+
+impl CoinbaseInspector {
+    const fn new(coinbase: Address) -> Self {
+        Self { coinbase, observed: false }
+    }
+}
+
+impl<DB: Database> Inspector<DB> for CoinbaseInspector {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        match interp.current_opcode() {
+            opcode::COINBASE => self.observed = true,
+            opcode::BALANCE | opcode::EXTCODESIZE | opcode::EXTCODECOPY | opcode::EXTCODEHASH => {
+                if let Ok(word) = interp.stack.peek(0) {
+                    self.observed |= Address::from_word(B256::from(word)) == self.coinbase;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        self.observed |= inputs.target_address == self.coinbase ||
+            inputs.bytecode_address == self.coinbase ||
+            inputs.caller == self.coinbase;
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.observed |= outcome.address == Some(self.coinbase);
+        outcome
+    }
+
+    fn selfdestruct(&mut self, _contract: Address, target: Address, _value: U256) {
+        self.observed |= target == self.coinbase;
+    }
+}
@@ -0,0 +1,92 @@
+//! Registration of additional or overridden precompiles.
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+#[cfg(feature = "std")]
+use std::sync::Arc;
+
+use core::fmt;
+use reth_primitives::{revm_primitives::SpecId, Address};
+use reth_revm::{
+    handler::register::HandleRegisterBox,
+    precompile::{Precompile, PrecompileSpecId},
+    ContextPrecompiles, Database,
+};
+
+/// A set of precompiles that are installed on top of the default precompiles of the active
+/// hardfork.
+///
+/// Each precompile is registered with the hardfork ([`SpecId`]) from which it is active. A
+/// precompile registered at the address of a default precompile replaces it, and if several
+/// registrations for the same address are active, the one registered last wins.
+#[derive(Clone, Default)]
+pub struct CustomPrecompiles {
+    entries: Vec<(SpecId, Address, Precompile)>,
+}
+
+impl CustomPrecompiles {
+    /// Creates an empty set of precompiles.
+    pub const fn new() -> Self {
+        Self { entries: Vec::new() }
+    }
+
+    /// Registers a precompile at the given address, active from the given hardfork onwards.
+    pub fn register(&mut self, activation: SpecId, address: Address, precompile: Precompile) {
+        self.entries.push((activation, address, precompile));
+    }
+
+    /// Registers a precompile at the given address, active from the given hardfork onwards.
+    ///
+    /// See also [`CustomPrecompiles::register`].
+    pub fn with_precompile(
+        mut self,
+        activation: SpecId,
+        address: Address,
+        precompile: Precompile,
+    ) -> Self {
+        self.register(activation, address, precompile);
+        self
+    }
+
+    /// Returns `true` if no precompiles were registered.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the number of registered precompiles.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns the precompiles that are active for the given hardfork, in registration order.
+    pub fn active_at(&self, spec_id: SpecId) -> impl Iterator<Item = (Address, &Precompile)> + '_ {
+        self.entries
+            .iter()
+            .filter(move |(activation, _, _)| SpecId::enabled(spec_id, *activation))
+            .map(|(_, address, precompile)| (*address, precompile))
+    }
+
+    /// Returns a handler register that installs the default precompiles for the EVM's spec id,
+    /// extended by the precompiles active at that spec id.
+    pub fn handler_register<EXT, DB: Database>(&'static self) -> HandleRegisterBox<EXT, DB> {
+        Box::new(move |handler| {
+            let spec_id = handler.cfg.spec_id;
+            handler.pre_execution.load_precompiles = Arc::new(move || {
+                let mut loaded = ContextPrecompiles::new(PrecompileSpecId::from_spec_id(spec_id));
+                loaded.extend(
+                    self.active_at(spec_id)
+                        .map(|(address, precompile)| (address, precompile.clone().into())),
+                );
+                loaded
+            });
+        })
+    }
+}
+
+impl fmt::Debug for CustomPrecompiles {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.entries.iter().map(|(activation, address, _)| (activation, address)))
+            .finish()
+    }
+}
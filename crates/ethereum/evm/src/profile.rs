@@ -0,0 +1,289 @@
+//! Profiling of opcode and precompile execution.
+
+use reth_primitives::Address;
+use reth_revm::{
+    interpreter::{
+        gas::CALL_STIPEND,
+        opcode::{self, OpCode},
+        CallInputs, CallOutcome, CallScheme, CreateInputs, CreateOutcome, Interpreter,
+    },
+    Database, EvmContext, Inspector,
+};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// The number of entries listed per section of the [`ExecutionProfile`] report.
+const REPORT_ENTRIES: usize = 25;
+
+/// Aggregated statistics of an opcode or precompile.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProfileEntry {
+    /// How often the opcode or precompile was executed.
+    pub count: u64,
+    /// The total gas charged.
+    pub gas: u64,
+    /// The total time spent executing.
+    pub duration: Duration,
+}
+
+impl ProfileEntry {
+    fn record(&mut self, gas: u64, duration: Duration) {
+        self.count += 1;
+        self.gas += gas;
+        self.duration += duration;
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.count += other.count;
+        self.gas += other.gas;
+        self.duration += other.duration;
+    }
+}
+
+/// Per-opcode and per-precompile execution statistics, accumulated over a range of blocks.
+///
+/// Call counts and gas are deterministic for a given range of blocks, durations are not.
+///
+/// The gas of an opcode is the gas charged by the instruction itself, gas forwarded to a sub call
+/// or contract creation is not included.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExecutionProfile {
+    /// The number of profiled blocks.
+    pub blocks: u64,
+    /// Statistics per opcode.
+    pub opcodes: BTreeMap<u8, ProfileEntry>,
+    /// Statistics per precompile address.
+    pub precompiles: BTreeMap<Address, ProfileEntry>,
+}
+
+impl ExecutionProfile {
+    /// Adds the statistics of the given profile to this profile.
+    pub fn merge(&mut self, other: &Self) {
+        self.blocks += other.blocks;
+        for (opcode, entry) in &other.opcodes {
+            self.opcodes.entry(*opcode).or_default().merge(entry);
+        }
+        for (address, entry) in &other.precompiles {
+            self.precompiles.entry(*address).or_default().merge(entry);
+        }
+    }
+
+    /// Returns the total gas charged by opcodes and precompiles.
+    pub fn total_gas(&self) -> u64 {
+        self.opcodes.values().chain(self.precompiles.values()).map(|entry| entry.gas).sum()
+    }
+}
+
+impl fmt::Display for ExecutionProfile {
+    /// Renders the opcodes and precompiles with the most gas charged as a table.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn section<K>(
+            f: &mut fmt::Formatter<'_>,
+            title: &str,
+            entries: impl Iterator<Item = (K, ProfileEntry)>,
+            total_gas: u64,
+        ) -> fmt::Result
+        where
+            K: fmt::Display,
+        {
+            let mut entries = entries.collect::<Vec<_>>();
+            entries.sort_by(|(_, a), (_, b)| b.gas.cmp(&a.gas).then(b.duration.cmp(&a.duration)));
+
+            writeln!(
+                f,
+                "{title:<44} {:>14} {:>16} {:>7} {:>12} {:>10}",
+                "count", "gas", "gas %", "time", "ns/exec"
+            )?;
+            for (key, entry) in entries.into_iter().take(REPORT_ENTRIES) {
+                let share = if total_gas == 0 {
+                    0.0
+                } else {
+                    entry.gas as f64 * 100.0 / total_gas as f64
+                };
+                writeln!(
+                    f,
+                    "{:<44} {:>14} {:>16} {:>6.2}% {:>12} {:>10}",
+                    key.to_string(),
+                    entry.count,
+                    entry.gas,
+                    share,
+                    format!("{:.2?}", entry.duration),
+                    entry.duration.as_nanos() / entry.count.max(1) as u128,
+                )?;
+            }
+            Ok(())
+        }
+
+        let total_gas = self.total_gas();
+        writeln!(f, "Execution profile of {} blocks, {total_gas} gas", self.blocks)?;
+        section(
+            f,
+            "opcode",
+            self.opcodes.iter().map(|(opcode, entry)| (OpCode::name_by_op(*opcode), *entry)),
+            total_gas,
+        )?;
+        section(f, "precompile", self.precompiles.iter().map(|(a, e)| (*a, *e)), total_gas)
+    }
+}
+
+/// A shared handle to an [`ExecutionProfile`] that accumulates the profiles of all executed
+/// blocks.
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionProfiler {
+    profile: Arc<Mutex<ExecutionProfile>>,
+}
+
+impl ExecutionProfiler {
+    /// Creates a new profiler with an empty profile.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a snapshot of the profile accumulated so far.
+    pub fn profile(&self) -> ExecutionProfile {
+        self.profile.lock().unwrap_or_else(|err| err.into_inner()).clone()
+    }
+
+    /// Adds the profile of an executed block.
+    pub(crate) fn record_block(&self, mut block: ExecutionProfile) {
+        block.blocks = 1;
+        self.profile.lock().unwrap_or_else(|err| err.into_inner()).merge(&block);
+    }
+}
+
+/// An [`Inspector`] that records the gas charged and time spent per opcode and precompile.
+#[derive(Debug, Default)]
+pub(crate) struct ProfilingInspector {
+    profile: ExecutionProfile,
+    /// The opcode that is currently executed, with the remaining gas and time at its start.
+    step: Option<(u8, u64, Instant)>,
+    /// The call or create opcode whose gas still includes the gas forwarded to the sub call.
+    pending_call: Option<u8>,
+    /// Precompile calls in progress, `None` for calls that don't target a precompile.
+    calls: Vec<Option<(Address, Instant)>>,
+}
+
+impl ProfilingInspector {
+    /// Consumes the inspector and returns the recorded profile.
+    pub(crate) fn into_profile(self) -> ExecutionProfile {
+        self.profile
+    }
+
+    /// Removes the gas forwarded to a sub call or contract creation from the gas of the opcode
+    /// that initiated it.
+    fn forwarded(&mut self, gas: u64) {
+        if let Some(opcode) = self.pending_call.take() {
+            if let Some(entry) = self.profile.opcodes.get_mut(&opcode) {
+                entry.gas = entry.gas.saturating_sub(gas);
+            }
+        }
+    }
+}
+
+impl<DB: Database> Inspector<DB> for ProfilingInspector {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        self.pending_call = None;
+        self.step = Some((interp.current_opcode(), interp.gas().remaining(), Instant::now()));
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        if let Some((opcode, gas_remaining, started_at)) = self.step.take() {
+            let gas = gas_remaining.saturating_sub(interp.gas().remaining());
+            self.profile.opcodes.entry(opcode).or_default().record(gas, started_at.elapsed());
+            if is_call_or_create(opcode) {
+                self.pending_call = Some(opcode);
+            }
+        }
+    }
+
+    fn call(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        // the stipend of value transfers is added to the forwarded gas, but not charged
+        let stipend = if matches!(inputs.scheme, CallScheme::Call | CallScheme::CallCode) &&
+            inputs.transfers_value()
+        {
+            CALL_STIPEND
+        } else {
+            0
+        };
+        self.forwarded(inputs.gas_limit.saturating_sub(stipend));
+
+        let precompile = context
+            .precompiles
+            .contains(&inputs.bytecode_address)
+            .then(|| (inputs.bytecode_address, Instant::now()));
+        self.calls.push(precompile);
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        if let Some(Some((address, started_at))) = self.calls.pop() {
+            self.profile
+                .precompiles
+                .entry(address)
+                .or_default()
+                .record(outcome.gas().spent(), started_at.elapsed());
+        }
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.forwarded(inputs.gas_limit);
+        None
+    }
+}
+
+/// Returns `true` if the opcode forwards gas to a sub call or contract creation.
+const fn is_call_or_create(op: u8) -> bool {
+    matches!(
+        op,
+        opcode::CALL |
+            opcode::CALLCODE |
+            opcode::DELEGATECALL |
+            opcode::STATICCALL |
+            opcode::CREATE |
+            opcode::CREATE2
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_profiles() {
+        let profiler = ExecutionProfiler::new();
+
+        let mut block = ExecutionProfile::default();
+        block.opcodes.entry(0x01).or_default().record(3, Duration::from_nanos(10));
+        let ecrecover = Address::with_last_byte(1);
+        block.precompiles.entry(ecrecover).or_default().record(3000, Duration::ZERO);
+        profiler.record_block(block.clone());
+        profiler.record_block(block);
+
+        let profile = profiler.profile();
+        assert_eq!(profile.blocks, 2);
+        assert_eq!(
+            profile.opcodes[&0x01],
+            ProfileEntry { count: 2, gas: 6, duration: Duration::from_nanos(20) }
+        );
+        assert_eq!(profile.total_gas(), 6006);
+        assert!(profile.to_string().contains("ADD"));
+    }
+}
@@ -0,0 +1,110 @@
+//! Capture of the traces of slowly executed blocks.
+
+use reth_primitives::{BlockNumber, B256};
+use serde::Serialize;
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// Detects blocks whose execution takes longer than a threshold and captures their traces into a
+/// directory, bounded by a disk quota.
+///
+/// A slow block is re-executed on top of its parent state, and a `<number>_<hash>` directory is
+/// created for it that contains:
+///  - `report.json`: the execution time, gas used and number of transactions of the block
+///  - `profile.txt`: the gas charged and time spent per opcode and precompile
+///  - `<number>.json`: the call traces of all transactions, in the format of geth's `callTracer`
+///
+/// Once the directory holds more than the quota, slow blocks are only logged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlowBlockDetector {
+    threshold: Duration,
+    dir: PathBuf,
+    quota: u64,
+}
+
+impl SlowBlockDetector {
+    /// Creates a new detector for blocks taking longer than the threshold, capturing their traces
+    /// into the directory until it holds `quota` bytes.
+    pub fn new(threshold: Duration, dir: impl Into<PathBuf>, quota: u64) -> Self {
+        Self { threshold, dir: dir.into(), quota }
+    }
+
+    /// Returns the execution time above which a block is slow.
+    pub const fn threshold(&self) -> Duration {
+        self.threshold
+    }
+
+    /// Returns `true` if a block executed in the given time is slow.
+    pub fn is_slow(&self, elapsed: Duration) -> bool {
+        elapsed >= self.threshold
+    }
+
+    /// Creates the directory for the captures of the block, returns `None` if the quota is
+    /// exhausted.
+    pub(crate) fn create_block_dir(
+        &self,
+        block_number: BlockNumber,
+        block_hash: B256,
+    ) -> io::Result<Option<PathBuf>> {
+        if reth_fs_util::dir_size(&self.dir).map_err(io::Error::other)? >= self.quota {
+            return Ok(None)
+        }
+        let dir = self.dir.join(format!("{block_number}_{block_hash}"));
+        fs::create_dir_all(&dir)?;
+        Ok(Some(dir))
+    }
+}
+
+/// The report of a slow block.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SlowBlockReport {
+    pub(crate) block_number: BlockNumber,
+    pub(crate) block_hash: B256,
+    /// The execution time in milliseconds.
+    pub(crate) elapsed_ms: u128,
+    pub(crate) gas_used: u64,
+    pub(crate) transactions: usize,
+}
+
+impl SlowBlockReport {
+    /// Writes the report into the directory of the block.
+    pub(crate) fn write(&self, dir: &Path) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(dir.join("report.json"))?);
+        serde_json::to_writer_pretty(&mut writer, self)?;
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_dirs_are_bounded_by_quota() {
+        let dir = std::env::temp_dir().join("reth-slow-block-detector-test");
+        let _ = fs::remove_dir_all(&dir);
+        let detector = SlowBlockDetector::new(Duration::from_secs(1), &dir, 16);
+        assert!(detector.is_slow(Duration::from_secs(2)));
+        assert!(!detector.is_slow(Duration::from_millis(999)));
+
+        let block_dir = detector.create_block_dir(1, B256::ZERO).unwrap().unwrap();
+        let report = SlowBlockReport {
+            block_number: 1,
+            block_hash: B256::ZERO,
+            elapsed_ms: 1500,
+            gas_used: 30_000_000,
+            transactions: 200,
+        };
+        report.write(&block_dir).unwrap();
+
+        // the report exceeds the quota of 16 bytes
+        assert!(detector.create_block_dir(2, B256::ZERO).unwrap().is_none());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,194 @@
+//! Tracing of executed blocks into files.
+
+use alloy_rpc_types_trace::geth::{CallConfig, CallFrame};
+use reth_primitives::{Address, BlockNumber, B256, U256};
+use revm_inspectors::tracing::{TracingInspector, TracingInspectorConfig};
+use revm_primitives::{db::Database, EvmState, KECCAK_EMPTY};
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+};
+
+pub use reth_evm::trace::TraceKind;
+
+/// Writes a trace of every executed block into a directory.
+///
+/// The trace of a block is written to `<dir>/<block number>.json` and contains one entry per
+/// transaction, in block order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockTracer {
+    dir: PathBuf,
+    kind: TraceKind,
+}
+
+impl BlockTracer {
+    /// Creates a new tracer writing traces of the given kind into the given directory.
+    pub fn new(dir: impl Into<PathBuf>, kind: TraceKind) -> Self {
+        Self { dir: dir.into(), kind }
+    }
+
+    /// Returns the kind of trace that is written.
+    pub const fn kind(&self) -> TraceKind {
+        self.kind
+    }
+
+    /// Returns the path of the trace file of the given block.
+    pub fn block_trace_path(&self, block_number: BlockNumber) -> PathBuf {
+        self.dir.join(format!("{block_number}.json"))
+    }
+
+    /// Returns the inspector configuration required for call traces.
+    pub(crate) fn inspector_config() -> TracingInspectorConfig {
+        TracingInspectorConfig::from_geth_call_config(&CallConfig::default())
+    }
+
+    /// Returns the call trace recorded by the given inspector.
+    pub(crate) fn call_trace(inspector: TracingInspector, gas_used: u64) -> TraceResult {
+        TraceResult::Call(
+            inspector.into_geth_builder().geth_call_traces(CallConfig::default(), gas_used),
+        )
+    }
+
+    /// Writes the traces of a block.
+    pub(crate) fn write(
+        &self,
+        block_number: BlockNumber,
+        traces: &[TransactionTrace],
+    ) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let mut writer = BufWriter::new(File::create(self.block_trace_path(block_number))?);
+        serde_json::to_writer(&mut writer, traces)?;
+        writer.flush()
+    }
+}
+
+/// The trace of a single transaction.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TransactionTrace {
+    pub(crate) tx_hash: B256,
+    pub(crate) result: TraceResult,
+}
+
+/// The trace of a transaction.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub(crate) enum TraceResult {
+    Call(CallFrame),
+    StateDiff(BTreeMap<Address, AccountDiff>),
+}
+
+/// A changed value.
+#[derive(Debug, Serialize)]
+pub(crate) struct Delta<T> {
+    from: T,
+    to: T,
+}
+
+impl<T: PartialEq> Delta<T> {
+    fn changed(from: T, to: T) -> Option<Self> {
+        (from != to).then_some(Self { from, to })
+    }
+}
+
+/// The changes of an account.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AccountDiff {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    balance: Option<Delta<U256>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nonce: Option<Delta<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code_hash: Option<Delta<B256>>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    storage: BTreeMap<U256, Delta<U256>>,
+}
+
+impl AccountDiff {
+    fn is_empty(&self) -> bool {
+        self.balance.is_none() &&
+            self.nonce.is_none() &&
+            self.code_hash.is_none() &&
+            self.storage.is_empty()
+    }
+}
+
+/// Returns the state diff of a transaction.
+///
+/// The given database must return the state before the transaction, i.e. the state must not have
+/// been committed yet.
+pub(crate) fn state_diff<DB: Database>(
+    db: &mut DB,
+    state: &EvmState,
+) -> Result<TraceResult, DB::Error> {
+    let mut diff = BTreeMap::new();
+    for (address, account) in state {
+        if !account.is_touched() {
+            continue
+        }
+
+        let before = db.basic(*address)?.unwrap_or_default();
+        let after =
+            if account.is_selfdestructed() { Default::default() } else { account.info.clone() };
+
+        let account_diff = AccountDiff {
+            balance: Delta::changed(before.balance, after.balance),
+            nonce: Delta::changed(before.nonce, after.nonce),
+            code_hash: Delta::changed(
+                if before.code_hash.is_zero() { KECCAK_EMPTY } else { before.code_hash },
+                if after.code_hash.is_zero() { KECCAK_EMPTY } else { after.code_hash },
+            ),
+            storage: account
+                .storage
+                .iter()
+                .filter(|(_, slot)| slot.is_changed())
+                .map(|(key, slot)| {
+                    (*key, Delta { from: slot.original_value(), to: slot.present_value() })
+                })
+                .collect(),
+        };
+        if !account_diff.is_empty() {
+            diff.insert(*address, account_diff);
+        }
+    }
+    Ok(TraceResult::StateDiff(diff))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_block_trace() {
+        let dir = std::env::temp_dir().join("reth-block-tracer-test");
+        let tracer = BlockTracer::new(&dir, TraceKind::StateDiff);
+
+        let traces = vec![TransactionTrace {
+            tx_hash: B256::with_last_byte(1),
+            result: TraceResult::StateDiff(BTreeMap::from([(
+                Address::with_last_byte(2),
+                AccountDiff {
+                    nonce: Delta::changed(0, 1),
+                    storage: BTreeMap::from([(
+                        U256::from(1),
+                        Delta { from: U256::ZERO, to: U256::from(3) },
+                    )]),
+                    ..Default::default()
+                },
+            )])),
+        }];
+        tracer.write(7, &traces).unwrap();
+
+        let written: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(tracer.block_trace_path(7)).unwrap()).unwrap();
+        let diff = &written[0]["result"]["0x0000000000000000000000000000000000000002"];
+        assert_eq!(diff["nonce"], serde_json::json!({ "from": 0, "to": 1 }));
+        assert!(diff.get("balance").is_none());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -4,3 +4,11 @@
 pub use reth_evm_ethereum::execute::EthExecutorProvider;
 #[doc(inline)]
 pub use reth_evm_ethereum::EthEvmConfig;
+#[doc(inline)]
+pub use reth_evm_ethereum::trace::{BlockTracer, TraceKind};
+#[doc(inline)]
+pub use reth_evm_ethereum::profile::{ExecutionProfile, ExecutionProfiler};
+#[doc(inline)]
+pub use reth_evm_ethereum::slow_block::SlowBlockDetector;
+#[doc(inline)]
+pub use reth_evm::access_list::BlockAccessListRecorder;
@@ -7,7 +7,11 @@ use reth_beacon_consensus::EthBeaconConsensus;
 use reth_ethereum_engine_primitives::{
     EthBuiltPayload, EthPayloadAttributes, EthPayloadBuilderAttributes,
 };
-use reth_evm_ethereum::execute::EthExecutorProvider;
+use reth_evm::access_list::BlockAccessListRecorder;
+use reth_evm_ethereum::{
+    execute::EthExecutorProvider, profile::ExecutionProfiler, slow_block::SlowBlockDetector,
+    trace::BlockTracer,
+};
 use reth_network::NetworkHandle;
 use reth_node_builder::{
     components::{
@@ -21,7 +25,8 @@ use reth_payload_builder::{PayloadBuilderHandle, PayloadBuilderService};
 use reth_provider::CanonStateSubscriptions;
 use reth_tracing::tracing::{debug, info};
 use reth_transaction_pool::{
-    blobstore::DiskFileBlobStore, EthTransactionPool, TransactionPool,
+    blobstore::DiskFileBlobStore, CoinbaseTipOrdering, EthPooledTransaction,
+    EthTransactionValidator, TransactionOrdering, TransactionPool,
     TransactionValidationTaskExecutor,
 };
 use std::sync::Arc;
@@ -99,8 +104,27 @@ where
         ctx: &BuilderContext<Node>,
     ) -> eyre::Result<(Self::EVM, Self::Executor)> {
         let chain_spec = ctx.chain_spec();
-        let evm_config = EthEvmConfig::default();
-        let executor = EthExecutorProvider::new(chain_spec, evm_config);
+        let evm_config = EthEvmConfig::default().with_chain_rules(&chain_spec);
+        let mut executor = EthExecutorProvider::new(chain_spec, evm_config)
+            .with_parallel_execution(ctx.config().execution.parallel);
+        if let Some(dir) = &ctx.config().execution.trace_to {
+            let kind = ctx.config().execution.trace_kind;
+            info!(target: "reth::cli", ?dir, %kind, "Writing execution traces");
+            executor = executor.with_tracer(BlockTracer::new(dir, kind));
+        }
+        if ctx.config().execution.profile {
+            executor = executor.with_profiler(ExecutionProfiler::new());
+        }
+        if ctx.config().execution.access_lists {
+            executor = executor.with_access_list_recorder(BlockAccessListRecorder::default());
+        }
+        if let Some(threshold) = ctx.config().execution.slow_block_threshold {
+            executor = executor.with_slow_block_detector(SlowBlockDetector::new(
+                threshold,
+                ctx.config().datadir().slow_blocks(),
+                ctx.config().execution.slow_block_quota_bytes(),
+            ));
+        }
 
         Ok((evm_config, executor))
     }
@@ -110,45 +134,57 @@ where
 ///
 /// This contains various settings that can be configured and take precedence over the node's
 /// config.
-#[derive(Debug, Default, Clone, Copy)]
+///
+/// By default the pool orders transactions by their effective tip, see
+/// [`EthereumPoolBuilder::with_ordering`] to configure a different [`TransactionOrdering`].
+#[derive(Debug, Default, Clone)]
 #[non_exhaustive]
-pub struct EthereumPoolBuilder {
-    // TODO add options for txpool args
+pub struct EthereumPoolBuilder<Ordering = CoinbaseTipOrdering<EthPooledTransaction>> {
+    /// The ordering of the pending transactions, this determines which transactions are included
+    /// in built payloads first.
+    ordering: Ordering,
 }
 
-impl<Node> PoolBuilder<Node> for EthereumPoolBuilder
+impl<Ordering> EthereumPoolBuilder<Ordering> {
+    /// Configures the [`TransactionOrdering`] of the pool.
+    pub fn with_ordering<O>(self, ordering: O) -> EthereumPoolBuilder<O> {
+        EthereumPoolBuilder { ordering }
+    }
+}
+
+impl<Node, Ordering> PoolBuilder<Node> for EthereumPoolBuilder<Ordering>
 where
     Node: FullNodeTypes,
+    Ordering: TransactionOrdering<Transaction = EthPooledTransaction>,
 {
-    type Pool = EthTransactionPool<Node::Provider, DiskFileBlobStore>;
+    type Pool = reth_transaction_pool::Pool<
+        TransactionValidationTaskExecutor<
+            EthTransactionValidator<Node::Provider, EthPooledTransaction>,
+        >,
+        Ordering,
+        DiskFileBlobStore,
+    >;
 
     async fn build_pool(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::Pool> {
         let data_dir = ctx.config().datadir();
         let pool_config = ctx.pool_config();
-        let blob_store = DiskFileBlobStore::open(data_dir.blobstore(), Default::default())?;
-        let validator = TransactionValidationTaskExecutor::eth_builder(ctx.chain_spec())
-            .with_head_timestamp(ctx.head().timestamp)
-            .kzg_settings(ctx.kzg_settings()?)
-            .with_local_transactions_config(pool_config.local_transactions_config.clone())
-            .with_additional_tasks(1)
-            .build_with_tasks(
-                ctx.provider().clone(),
-                ctx.task_executor().clone(),
-                blob_store.clone(),
-            );
+        let blob_store = DiskFileBlobStore::open(data_dir.blobstore(), ctx.blob_store_config())?;
+        let validator = ctx.pool_validator_builder()?.build_with_tasks(
+            ctx.provider().clone(),
+            ctx.task_executor().clone(),
+            blob_store.clone(),
+        );
 
         let transaction_pool =
-            reth_transaction_pool::Pool::eth_pool(validator, blob_store, pool_config);
+            reth_transaction_pool::Pool::new(validator, self.ordering, blob_store, pool_config);
         info!(target: "reth::cli", "Transaction pool initialized");
-        let transactions_path = data_dir.txpool_transactions();
 
         // spawn txpool maintenance task
         {
             let pool = transaction_pool.clone();
             let chain_events = ctx.provider().canonical_state_stream();
             let client = ctx.provider().clone();
-            let transactions_backup_config =
-                reth_transaction_pool::maintain::LocalTransactionBackupConfig::with_local_txs_backup(transactions_path);
+            let transactions_backup_config = ctx.local_transactions_backup_config();
 
             ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
                 "local transactions backup task",
@@ -169,7 +205,7 @@ where
                     pool,
                     chain_events,
                     ctx.task_executor().clone(),
-                    Default::default(),
+                    ctx.pool_maintain_config(),
                 ),
             );
             debug!(target: "reth::cli", "Spawned txpool maintenance task");
@@ -180,6 +216,9 @@ where
 }
 
 /// A basic ethereum payload service.
+///
+/// Payloads are built with the `BLOCKHASH` window of the chain spec, like blocks are executed by
+/// the [`EthereumExecutorBuilder`].
 #[derive(Debug, Default, Clone)]
 #[non_exhaustive]
 pub struct EthereumPayloadBuilder;
@@ -199,7 +238,9 @@ where
         ctx: &BuilderContext<Node>,
         pool: Pool,
     ) -> eyre::Result<PayloadBuilderHandle<Node::Engine>> {
-        let payload_builder = reth_ethereum_payload_builder::EthereumPayloadBuilder::default();
+        let payload_builder = reth_ethereum_payload_builder::EthereumPayloadBuilder::new(
+            EthEvmConfig::default().with_chain_rules(&ctx.chain_spec()),
+        );
         let conf = ctx.payload_builder_config();
 
         let payload_job_config = BasicPayloadJobGeneratorConfig::default()
@@ -0,0 +1,136 @@
+//! Recording of the accounts and storage slots accessed by executed blocks.
+
+use reth_primitives::{AccessList, AccessListItem, Address, BlockNumber, B256};
+use revm_primitives::EvmState;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::{Arc, Mutex},
+};
+
+/// Number of blocks below a persisted block whose recorded access lists are kept.
+///
+/// The access lists of executed blocks that never become canonical are not taken, so they're
+/// discarded once a block this far above them is persisted.
+pub const MAX_PENDING_ACCESS_LISTS_DEPTH: u64 = 256;
+
+/// Builds the access list of a block from the state loaded by its transactions.
+///
+/// Every account and storage slot a transaction loads is part of its state, whether it was
+/// changed or only read.
+#[derive(Debug, Default)]
+pub struct BlockAccessListBuilder {
+    accounts: BTreeMap<Address, BTreeSet<B256>>,
+}
+
+impl BlockAccessListBuilder {
+    /// Adds the accounts and storage slots loaded by a transaction.
+    pub fn add_state(&mut self, state: &EvmState) {
+        for (address, account) in state {
+            self.accounts
+                .entry(*address)
+                .or_default()
+                .extend(account.storage.keys().map(|slot| B256::from(*slot)));
+        }
+    }
+
+    /// Returns the access list, sorted by address and storage slot.
+    pub fn build(self) -> AccessList {
+        AccessList(
+            self.accounts
+                .into_iter()
+                .map(|(address, slots)| AccessListItem {
+                    address,
+                    storage_keys: slots.into_iter().collect(),
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Records the access lists of executed blocks until they're persisted.
+///
+/// This is shared between the executors that record the access lists and the stages that persist
+/// them together with the blocks.
+#[derive(Debug, Clone, Default)]
+pub struct BlockAccessListRecorder {
+    pending: Arc<Mutex<BTreeMap<(BlockNumber, B256), AccessList>>>,
+}
+
+impl BlockAccessListRecorder {
+    /// Records the access list of an executed block.
+    pub fn record(&self, number: BlockNumber, hash: B256, access_list: AccessList) {
+        self.pending
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .insert((number, hash), access_list);
+    }
+
+    /// Takes the access list of a block that is persisted, if it was recorded.
+    ///
+    /// The access lists of blocks more than [`MAX_PENDING_ACCESS_LISTS_DEPTH`] below it are
+    /// discarded.
+    pub fn take(&self, number: BlockNumber, hash: B256) -> Option<AccessList> {
+        let mut pending = self.pending.lock().unwrap_or_else(|err| err.into_inner());
+        let access_list = pending.remove(&(number, hash));
+        let lowest = number.saturating_sub(MAX_PENDING_ACCESS_LISTS_DEPTH);
+        *pending = pending.split_off(&(lowest, B256::ZERO));
+        access_list
+    }
+
+    /// Returns the number of recorded access lists that weren't taken yet.
+    pub fn pending(&self) -> usize {
+        self.pending.lock().unwrap_or_else(|err| err.into_inner()).len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::U256;
+    use revm_primitives::{Account, EvmStorageSlot};
+
+    #[test]
+    fn builds_and_records_access_lists() {
+        let mut builder = BlockAccessListBuilder::default();
+        let account = |slots: &[u64]| {
+            let mut account = Account::default();
+            for slot in slots {
+                account.storage.insert(U256::from(*slot), EvmStorageSlot::default());
+            }
+            account
+        };
+        builder.add_state(&[(Address::with_last_byte(2), account(&[3, 1]))].into_iter().collect());
+        builder.add_state(
+            &[
+                (Address::with_last_byte(2), account(&[2])),
+                (Address::with_last_byte(1), account(&[])),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        let access_list = builder.build();
+        assert_eq!(
+            access_list.0,
+            [
+                AccessListItem { address: Address::with_last_byte(1), storage_keys: vec![] },
+                AccessListItem {
+                    address: Address::with_last_byte(2),
+                    storage_keys: [1u64, 2, 3].map(|slot| B256::from(U256::from(slot))).to_vec(),
+                },
+            ]
+        );
+
+        let recorder = BlockAccessListRecorder::default();
+        recorder.record(1, B256::with_last_byte(1), access_list.clone());
+        recorder.record(10, B256::with_last_byte(10), AccessList::default());
+        recorder.record(10, B256::with_last_byte(11), AccessList::default());
+        assert_eq!(recorder.take(1, B256::with_last_byte(1)), Some(access_list));
+        assert_eq!(recorder.take(1, B256::with_last_byte(1)), None);
+        assert_eq!(recorder.pending(), 2);
+
+        // side chain blocks far below the persisted block are discarded
+        recorder.record(300, B256::with_last_byte(30), AccessList::default());
+        assert!(recorder.take(300, B256::with_last_byte(30)).is_some());
+        assert_eq!(recorder.pending(), 0);
+    }
+}
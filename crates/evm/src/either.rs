@@ -41,6 +41,14 @@ where
             Self::Right(b) => Either::Right(b.batch_executor(db, prune_modes)),
         }
     }
+
+    #[cfg(feature = "std")]
+    fn access_list_recorder(&self) -> Option<&crate::access_list::BlockAccessListRecorder> {
+        match self {
+            Self::Left(a) => a.access_list_recorder(),
+            Self::Right(b) => b.access_list_recorder(),
+        }
+    }
 }
 
 impl<A, B, DB> Executor<DB> for Either<A, B>
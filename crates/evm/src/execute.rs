@@ -9,6 +9,9 @@ use revm_primitives::db::Database;
 #[cfg(not(feature = "std"))]
 use alloc::vec::Vec;
 
+#[cfg(feature = "std")]
+use crate::access_list::BlockAccessListRecorder;
+
 pub use reth_execution_errors::{BlockExecutionError, BlockValidationError};
 pub use reth_storage_errors::provider::ProviderError;
 
@@ -174,6 +177,15 @@ pub trait BlockExecutorProvider: Send + Sync + Clone + Unpin + 'static {
     fn batch_executor<DB>(&self, db: DB, prune_modes: PruneModes) -> Self::BatchExecutor<DB>
     where
         DB: Database<Error = ProviderError>;
+
+    /// Returns the recorder the executors record the access lists of the executed blocks to, if
+    /// configured.
+    ///
+    /// The recorded access lists are persisted together with the blocks.
+    #[cfg(feature = "std")]
+    fn access_list_recorder(&self) -> Option<&BlockAccessListRecorder> {
+        None
+    }
 }
 
 #[cfg(test)]
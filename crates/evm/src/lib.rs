@@ -17,10 +17,14 @@ use reth_primitives::{revm::env::fill_block_env, Address, Header, TransactionSig
 use revm::{inspector_handle_register, Database, Evm, EvmBuilder, GetInspector};
 use revm_primitives::{BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, SpecId, TxEnv};
 
+#[cfg(feature = "std")]
+pub mod access_list;
 pub mod either;
 pub mod execute;
 pub mod noop;
 pub mod provider;
+#[cfg(feature = "std")]
+pub mod trace;
 
 #[cfg(any(test, feature = "test-utils"))]
 /// test helpers for mocking executor
@@ -0,0 +1,47 @@
+//! Kinds of traces written for executed blocks.
+
+use std::{fmt, str::FromStr};
+
+/// What is traced for every executed transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TraceKind {
+    /// Call traces, in the format of geth's `callTracer`.
+    #[default]
+    Call,
+    /// The state changes of the transaction.
+    StateDiff,
+}
+
+impl fmt::Display for TraceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Call => f.write_str("call"),
+            Self::StateDiff => f.write_str("state-diff"),
+        }
+    }
+}
+
+impl FromStr for TraceKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "call" => Ok(Self::Call),
+            "state-diff" | "statediff" => Ok(Self::StateDiff),
+            _ => Err(format!("unknown trace kind {s}, expected one of: call, state-diff")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_trace_kind() {
+        for kind in [TraceKind::Call, TraceKind::StateDiff] {
+            assert_eq!(kind.to_string().parse::<TraceKind>().unwrap(), kind);
+        }
+        assert!("prestate".parse::<TraceKind>().is_err());
+    }
+}
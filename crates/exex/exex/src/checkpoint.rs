@@ -0,0 +1,86 @@
+use reth_primitives::BlockNumber;
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+/// Persists the finished height of each `ExEx`, so it survives node restarts.
+///
+/// Every `ExEx` has its own checkpoint file in the directory, named after its ID. A checkpoint is
+/// updated whenever the `ExEx` emits an [`ExExEvent::FinishedHeight`](crate::ExExEvent) event.
+#[derive(Debug, Clone)]
+pub struct ExExCheckpoints {
+    dir: PathBuf,
+}
+
+impl ExExCheckpoints {
+    /// Creates a new checkpoint store in the given directory.
+    ///
+    /// The directory is created when the first checkpoint is saved.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Returns the directory the checkpoints are stored in.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the last saved finished height of the `ExEx` with the given ID, if any.
+    pub fn get(&self, id: &str) -> io::Result<Option<BlockNumber>> {
+        let contents = match std::fs::read_to_string(self.path(id)) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        contents
+            .trim()
+            .parse()
+            .map(Some)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// Saves the finished height of the `ExEx` with the given ID.
+    ///
+    /// The checkpoint is written to a temporary file first, so a crash while saving doesn't
+    /// corrupt the previous checkpoint.
+    pub fn set(&self, id: &str, height: BlockNumber) -> io::Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        let path = self.path(id);
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, height.to_string())?;
+        std::fs::rename(tmp_path, path)
+    }
+
+    /// Returns the path of the checkpoint file of the `ExEx` with the given ID.
+    ///
+    /// Characters that aren't safe to use in file names are replaced with `_`.
+    fn path(&self, id: &str) -> PathBuf {
+        let name: String = id
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+            .collect();
+        self.dir.join(format!("{name}.checkpoint"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checkpoints_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let checkpoints = ExExCheckpoints::new(dir.path().join("exex"));
+
+        assert_eq!(checkpoints.get("indexer").unwrap(), None);
+
+        checkpoints.set("indexer", 100).unwrap();
+        checkpoints.set("rollup/v1", 50).unwrap();
+        assert_eq!(checkpoints.get("indexer").unwrap(), Some(100));
+        assert_eq!(checkpoints.get("rollup/v1").unwrap(), Some(50));
+
+        checkpoints.set("indexer", 101).unwrap();
+        assert_eq!(checkpoints.get("indexer").unwrap(), Some(101));
+    }
+}
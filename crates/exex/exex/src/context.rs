@@ -1,15 +1,21 @@
 use crate::{ExExEvent, ExExNotification};
 use reth_node_api::FullNodeComponents;
 use reth_node_core::node_config::NodeConfig;
-use reth_primitives::Head;
+use reth_primitives::{BlockNumber, Head};
+use reth_provider::BlockRangeIter;
 use reth_tasks::TaskExecutor;
-use std::fmt::Debug;
+use std::{fmt::Debug, ops::RangeInclusive};
 use tokio::sync::mpsc::{Receiver, UnboundedSender};
 
 /// Captures the context that an `ExEx` has access to.
 pub struct ExExContext<Node: FullNodeComponents> {
     /// The current head of the blockchain at launch.
     pub head: Head,
+    /// The finished height this `ExEx` reported before the node was restarted, if any.
+    ///
+    /// Blocks after the checkpoint and up to [`Self::head`] may not have been processed by the
+    /// `ExEx`, and are not sent as notifications again.
+    pub checkpoint: Option<BlockNumber>,
     /// The config of the node
     pub config: NodeConfig,
     /// The loaded node config
@@ -38,6 +44,7 @@ impl<Node: FullNodeComponents> Debug for ExExContext<Node> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ExExContext")
             .field("head", &self.head)
+            .field("checkpoint", &self.checkpoint)
             .field("config", &self.config)
             .field("reth_config", &self.reth_config)
             .field("events", &self.events)
@@ -68,6 +75,17 @@ impl<Node: FullNodeComponents> ExExContext<Node> {
         self.components.provider()
     }
 
+    /// Returns an iterator over the canonical blocks of `range`, with their senders and receipts.
+    ///
+    /// Useful to backfill the blocks that were committed before the `ExEx` was installed, or
+    /// while it was not running.
+    pub fn block_range_iter(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> BlockRangeIter<'_, Node::Provider> {
+        BlockRangeIter::new(self.provider(), range)
+    }
+
     /// Returns the handle to the network
     pub fn network(&self) -> &reth_network::NetworkHandle {
         self.components.network()
@@ -23,6 +23,12 @@
 //! event. To clarify: if the `ExEx` emits `ExExEvent::FinishedHeight(0)` it will receive
 //! notifications for any `block_number > 0`.
 //!
+//! # Checkpoints
+//!
+//! The last finished height of every `ExEx` is persisted with [`ExExCheckpoints`], and is
+//! restored when the node restarts. It is available in [`ExExContext::checkpoint`], so an `ExEx`
+//! can backfill the blocks between its checkpoint and [`ExExContext::head`] from the provider.
+//!
 //! [`Future`]: std::future::Future
 //! [`ExExContext`]: crate::ExExContext
 //! [`CanonStateNotification`]: reth_provider::CanonStateNotification
@@ -34,6 +40,9 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
+mod checkpoint;
+pub use checkpoint::*;
+
 mod context;
 pub use context::*;
 
@@ -1,8 +1,8 @@
-use crate::{ExExEvent, ExExNotification, FinishedExExHeight};
+use crate::{ExExCheckpoints, ExExEvent, ExExNotification, FinishedExExHeight};
 use metrics::Gauge;
 use reth_metrics::{metrics::Counter, Metrics};
 use reth_primitives::BlockNumber;
-use reth_tracing::tracing::debug;
+use reth_tracing::tracing::{debug, warn};
 use std::{
     collections::VecDeque,
     future::{poll_fn, Future},
@@ -52,6 +52,8 @@ pub struct ExExHandle {
     ///
     /// If this is `None`, the `ExEx` has not emitted a `FinishedHeight` event.
     finished_height: Option<BlockNumber>,
+    /// The store the finished height is persisted to, if any.
+    checkpoints: Option<ExExCheckpoints>,
 }
 
 impl ExExHandle {
@@ -71,12 +73,39 @@ impl ExExHandle {
                 receiver: event_rx,
                 next_notification_id: 0,
                 finished_height: None,
+                checkpoints: None,
             },
             event_tx,
             notification_rx,
         )
     }
 
+    /// Persists the finished height of the `ExEx` to the given store, and resumes from the
+    /// finished height that was last persisted to it.
+    pub fn with_checkpoints(
+        mut self,
+        checkpoints: ExExCheckpoints,
+        checkpoint: Option<BlockNumber>,
+    ) -> Self {
+        self.finished_height = checkpoint;
+        self.checkpoints = Some(checkpoints);
+        self
+    }
+
+    /// Sets the finished height of the `ExEx`, and persists it if it changed.
+    fn set_finished_height(&mut self, height: BlockNumber) {
+        if self.finished_height == Some(height) {
+            return
+        }
+        self.finished_height = Some(height);
+
+        if let Some(checkpoints) = &self.checkpoints {
+            if let Err(err) = checkpoints.set(&self.id, height) {
+                warn!(exex_id = %self.id, %height, %err, "Failed to persist ExEx checkpoint");
+            }
+        }
+    }
+
     /// Reserves a slot in the `PollSender` channel and sends the notification if the slot was
     /// successfully reserved.
     ///
@@ -334,7 +363,7 @@ impl Future for ExExManager {
                 debug!(exex_id = %exex.id, ?event, "Received event from exex");
                 exex.metrics.events_sent_total.increment(1);
                 match event {
-                    ExExEvent::FinishedHeight(height) => exex.finished_height = Some(height),
+                    ExExEvent::FinishedHeight(height) => exex.set_finished_height(height),
                 }
             }
         }
@@ -279,6 +279,7 @@ pub async fn test_exex_context_with_chain_spec(
 
     let ctx = ExExContext {
         head,
+        checkpoint: None,
         config: NodeConfig::test(),
         reth_config: reth_config::Config::default(),
         events: events_tx,
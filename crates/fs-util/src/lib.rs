@@ -12,6 +12,7 @@ use std::{
     io,
     path::{Path, PathBuf},
 };
+use sysinfo::Disks;
 
 /// Result alias for [`FsPathError`].
 pub type Result<T> = std::result::Result<T, FsPathError>;
@@ -241,6 +242,35 @@ pub fn read_dir(path: impl AsRef<Path>) -> Result<ReadDir> {
     fs::read_dir(path).map_err(|err| FsPathError::read_dir(err, path))
 }
 
+/// Returns the total size of the files in the directory, including subdirectories, or zero if
+/// the directory doesn't exist.
+pub fn dir_size(path: impl AsRef<Path>) -> Result<u64> {
+    let path = path.as_ref();
+    if !path.exists() {
+        return Ok(0)
+    }
+
+    let mut size = 0;
+    for entry in read_dir(path)? {
+        let entry = entry.map_err(|err| FsPathError::read_dir(err, path))?;
+        let metadata = entry.metadata().map_err(|err| FsPathError::metadata(err, entry.path()))?;
+        size += if metadata.is_dir() { dir_size(entry.path())? } else { metadata.len() };
+    }
+    Ok(size)
+}
+
+/// Returns the available space of the disk the path is on, or `None` if the path doesn't exist or
+/// its disk can't be found.
+pub fn available_space(path: impl AsRef<Path>) -> Option<u64> {
+    let path = path.as_ref().canonicalize().ok()?;
+    let disks = Disks::new_with_refreshed_list();
+    disks
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| disk.available_space())
+}
+
 /// Wrapper for `std::fs::rename`
 pub fn rename(from: impl AsRef<Path>, to: impl AsRef<Path>) -> Result<()> {
     let from = from.as_ref();
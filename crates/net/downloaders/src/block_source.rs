@@ -0,0 +1,28 @@
+use crate::file_client::{ChunkedFileReader, FileClient, FileClientError};
+use futures::Future;
+
+/// A source of blocks that can feed the import pipeline.
+///
+/// Blocks are yielded in batches of consecutive blocks. Each batch is buffered in a
+/// [`FileClient`], which the header and body downloaders of the pipeline read from, so any
+/// source of blocks, e.g. a file, the RPC of another node or an object storage, can be imported
+/// the same way.
+pub trait BlockSource: Send {
+    /// The error returned when reading the next batch fails.
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Returns the next batch of blocks, or `None` once the source is exhausted.
+    fn next_batch(
+        &mut self,
+    ) -> impl Future<Output = Result<Option<FileClient>, Self::Error>> + Send;
+}
+
+impl BlockSource for ChunkedFileReader {
+    type Error = FileClientError;
+
+    fn next_batch(
+        &mut self,
+    ) -> impl Future<Output = Result<Option<FileClient>, Self::Error>> + Send {
+        self.next_blocks()
+    }
+}
@@ -0,0 +1,184 @@
+use crate::{
+    block_source::BlockSource,
+    file_client::{FileClient, FileClientError},
+};
+use alloy_rlp::Decodable;
+use reth_primitives::{Block, BlockBody, Header};
+use std::{
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+use tracing::debug;
+
+/// Type of the version entry, the first entry of an e2store file.
+pub const VERSION_ENTRY: [u8; 2] = [0x65, 0x32];
+
+/// Type of the entry of a snappy compressed header.
+const COMPRESSED_HEADER_ENTRY: [u8; 2] = [0x03, 0x00];
+
+/// Type of the entry of a snappy compressed body.
+const COMPRESSED_BODY_ENTRY: [u8; 2] = [0x04, 0x00];
+
+/// Length of the header of an e2store entry: the type, the length and two reserved bytes.
+const ENTRY_HEADER_LEN: usize = 8;
+
+/// File extension of era1 archives.
+pub const ERA1_EXTENSION: &str = "era1";
+
+/// Reads the blocks of era1 archives, the e2store archives of the pre-merge chain.
+///
+/// Each archive holds up to 8192 consecutive blocks and is yielded as one batch. Receipts, total
+/// difficulties and the accumulator of the archives are skipped.
+#[derive(Debug)]
+pub struct Era1Reader {
+    /// The archives that are not read yet.
+    files: std::vec::IntoIter<PathBuf>,
+}
+
+impl Era1Reader {
+    /// Opens an era1 archive, or a directory of archives, which are read in file name order.
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let files = if path.is_dir() { era1_files(path)? } else { vec![path.to_path_buf()] };
+        Ok(Self { files: files.into_iter() })
+    }
+}
+
+impl BlockSource for Era1Reader {
+    type Error = FileClientError;
+
+    async fn next_batch(&mut self) -> Result<Option<FileClient>, Self::Error> {
+        for path in self.files.by_ref() {
+            let blocks = read_era1(&std::fs::read(&path)?)
+                .map_err(|err| io::Error::new(err.kind(), format!("{}: {err}", path.display())))?;
+            debug!(target: "downloaders::era1",
+                path = %path.display(),
+                blocks = blocks.len(),
+                "Read era1 archive"
+            );
+            if !blocks.is_empty() {
+                return Ok(Some(FileClient::from_blocks(blocks)))
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Returns the era1 archives of a directory, sorted by file name.
+pub(crate) fn era1_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|extension| extension == ERA1_EXTENSION) {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Decodes the blocks of an era1 archive.
+pub fn read_era1(mut data: &[u8]) -> io::Result<Vec<Block>> {
+    let mut blocks = Vec::new();
+    let mut header = None;
+    while !data.is_empty() {
+        let (entry_type, value) = next_entry(&mut data)?;
+        match entry_type {
+            COMPRESSED_HEADER_ENTRY => {
+                header = Some(decode::<Header>(&decompress(value)?, "header")?);
+            }
+            COMPRESSED_BODY_ENTRY => {
+                let header = header.take().ok_or_else(|| invalid("body without header"))?;
+                let body = decode::<BlockBody>(&decompress(value)?, "body")?;
+                blocks.push(Block {
+                    header,
+                    body: body.transactions,
+                    ommers: body.ommers,
+                    withdrawals: body.withdrawals,
+                    requests: body.requests,
+                });
+            }
+            // the version, receipts, total difficulties, accumulator and block index
+            _ => {}
+        }
+    }
+    Ok(blocks)
+}
+
+/// Reads the next e2store entry, returning its type and value.
+fn next_entry<'a>(data: &mut &'a [u8]) -> io::Result<([u8; 2], &'a [u8])> {
+    if data.len() < ENTRY_HEADER_LEN {
+        return Err(invalid("truncated entry header"))
+    }
+    let entry_type = [data[0], data[1]];
+    let len = u32::from_le_bytes(data[2..6].try_into().expect("4 bytes")) as usize;
+    if data[6..8] != [0, 0] {
+        return Err(invalid("non-zero reserved bytes in entry header"))
+    }
+    let Some(value) = data.get(ENTRY_HEADER_LEN..ENTRY_HEADER_LEN + len) else {
+        return Err(invalid("truncated entry"))
+    };
+    *data = &data[ENTRY_HEADER_LEN + len..];
+    Ok((entry_type, value))
+}
+
+/// Decompresses a value in the snappy framing format.
+fn decompress(value: &[u8]) -> io::Result<Vec<u8>> {
+    let mut decompressed = Vec::new();
+    snap::read::FrameDecoder::new(value).read_to_end(&mut decompressed)?;
+    Ok(decompressed)
+}
+
+fn decode<T: Decodable>(mut rlp: &[u8], name: &str) -> io::Result<T> {
+    T::decode(&mut rlp).map_err(|err| invalid(format!("invalid {name}: {err}")))
+}
+
+fn invalid(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::generate_bodies;
+    use alloy_rlp::Encodable;
+    use std::io::Write;
+
+    fn entry(entry_type: [u8; 2], value: &[u8]) -> Vec<u8> {
+        let mut entry = entry_type.to_vec();
+        entry.extend((value.len() as u32).to_le_bytes());
+        entry.extend([0, 0]);
+        entry.extend(value);
+        entry
+    }
+
+    fn compressed_entry(entry_type: [u8; 2], value: impl Encodable) -> Vec<u8> {
+        let mut encoder = snap::write::FrameEncoder::new(Vec::new());
+        encoder.write_all(&alloy_rlp::encode(value)).unwrap();
+        entry(entry_type, &encoder.into_inner().unwrap())
+    }
+
+    #[test]
+    fn reads_era1_archive() {
+        let (headers, bodies) = generate_bodies(0..=9);
+
+        let mut archive = entry(VERSION_ENTRY, &[]);
+        for header in &headers {
+            let body = &bodies[&header.hash()];
+            archive.extend(compressed_entry(COMPRESSED_HEADER_ENTRY, header.header()));
+            archive.extend(compressed_entry(COMPRESSED_BODY_ENTRY, body));
+            // receipts and total difficulty are skipped
+            archive.extend(entry([0x05, 0x00], &[0xc0]));
+            archive.extend(entry([0x06, 0x00], &[0; 32]));
+        }
+        archive.extend(entry([0x66, 0x32], &[0; 16]));
+
+        let blocks = read_era1(&archive).unwrap();
+        assert_eq!(blocks.len(), headers.len());
+        for (block, header) in blocks.iter().zip(&headers) {
+            assert_eq!(&block.header, header.header());
+            assert_eq!(block.body, bodies[&header.hash()].transactions);
+        }
+
+        assert!(read_era1(&archive[..archive.len() - 1]).is_err());
+    }
+}
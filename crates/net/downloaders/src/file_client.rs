@@ -1,6 +1,8 @@
-use super::file_codec::BlockFileCodec;
+use super::file_codec::{BlockFileCodec, DecodedBlock};
+use alloy_rlp::{Decodable, Encodable};
 use futures::Future;
 use itertools::Either;
+use reth_consensus::ConsensusError;
 use reth_network_p2p::{
     bodies::client::{BodiesClient, BodiesFut},
     download::DownloadClient,
@@ -10,10 +12,16 @@ use reth_network_p2p::{
 };
 use reth_network_peers::PeerId;
 use reth_primitives::{
-    BlockBody, BlockHash, BlockHashOrNumber, BlockNumber, Header, HeadersDirection, SealedHeader,
-    B256,
+    bytes::{Buf, Bytes},
+    keccak256, Block, BlockBody, BlockHash, BlockHashOrNumber, BlockNumber, GotExpected, Header,
+    HeadersDirection, SealedHeader, B256,
+};
+use std::{
+    collections::HashMap,
+    fmt::{self, Write},
+    io,
+    path::Path,
 };
-use std::{collections::HashMap, io, path::Path};
 use thiserror::Error;
 use tokio::{fs::File, io::AsyncReadExt};
 use tokio_stream::StreamExt;
@@ -25,6 +33,12 @@ use tracing::{debug, trace, warn};
 /// Default is 1 GB.
 pub const DEFAULT_BYTE_LEN_CHUNK_CHAIN_FILE: u64 = 1_000_000_000;
 
+/// Number of bytes before the malformed field included in the window of a [`BlockDecodeError`].
+const WINDOW_BYTES_BEFORE: usize = 32;
+
+/// Number of bytes from the malformed field on included in the window of a [`BlockDecodeError`].
+const WINDOW_BYTES_AFTER: usize = 96;
+
 /// Front-end API for fetching chain data from a file.
 ///
 /// Blocks are assumed to be written one after another in a file, as rlp bytes.
@@ -36,16 +50,21 @@ pub const DEFAULT_BYTE_LEN_CHUNK_CHAIN_FILE: u64 = 1_000_000_000;
 /// transactions in memory for use in the bodies stage.
 ///
 /// This reads the entire file into memory, so it is not suitable for large files.
+///
+/// Headers are buffered undecoded, see [`RawHeader`].
 #[derive(Debug)]
 pub struct FileClient {
     /// The buffered headers retrieved when fetching new bodies.
-    headers: HashMap<BlockNumber, Header>,
+    headers: HashMap<BlockNumber, RawHeader>,
 
     /// A mapping between block hash and number.
     hash_to_number: HashMap<BlockHash, BlockNumber>,
 
     /// The buffered bodies retrieved when fetching new headers.
     bodies: HashMap<BlockHash, BlockBody>,
+
+    /// The malformed blocks skipped when decoding in [`DecodeMode::Lenient`].
+    malformed_blocks: Vec<BlockDecodeError>,
 }
 
 /// An error that can occur when constructing and using a [`FileClient`].
@@ -59,6 +78,10 @@ pub enum FileClientError {
     #[error("{0}")]
     Rlp(alloy_rlp::Error, Vec<u8>),
 
+    /// A block of the file is malformed.
+    #[error(transparent)]
+    Malformed(Box<BlockDecodeError>),
+
     /// Custom error message.
     #[error("{0}")]
     Custom(&'static str),
@@ -70,6 +93,103 @@ impl From<&'static str> for FileClientError {
     }
 }
 
+impl From<BlockDecodeError> for FileClientError {
+    fn from(value: BlockDecodeError) -> Self {
+        Self::Malformed(Box::new(value))
+    }
+}
+
+/// How malformed blocks are handled when decoding a file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DecodeMode {
+    /// Malformed blocks are skipped and recorded as warnings, see
+    /// [`FileClient::malformed_blocks`]. Blocks following a malformed block are not buffered,
+    /// since they can only be imported in order.
+    #[default]
+    Lenient,
+    /// Decoding aborts at the first malformed block with a [`FileClientError::Malformed`]. Headers
+    /// are decoded eagerly, so that malformed headers are caught too.
+    Strict,
+}
+
+/// A malformed block of a file, with the context needed to locate it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockDecodeError {
+    /// Byte offset of the block in the file.
+    pub offset: u64,
+    /// Number of the block, if its header could be read.
+    pub number: Option<BlockNumber>,
+    /// Name of the malformed field, e.g. `header` or `transactions`.
+    pub field: &'static str,
+    /// Byte offset of the malformed field in the file.
+    pub field_offset: u64,
+    /// The decoding error.
+    pub error: alloy_rlp::Error,
+    /// Byte offset in the file of the first byte of the window.
+    pub window_offset: u64,
+    /// The bytes around the malformed field.
+    pub window: Vec<u8>,
+}
+
+impl BlockDecodeError {
+    /// Creates the error for the malformed field starting at `field_start` of `block`, the bytes
+    /// of the block starting at byte `offset` of the file.
+    pub(crate) fn new(
+        offset: u64,
+        number: Option<BlockNumber>,
+        field: &'static str,
+        field_start: usize,
+        block: &[u8],
+        error: alloy_rlp::Error,
+    ) -> Self {
+        let window_start = field_start.saturating_sub(WINDOW_BYTES_BEFORE);
+        let window_end = field_start.saturating_add(WINDOW_BYTES_AFTER).min(block.len());
+        Self {
+            offset,
+            number,
+            field,
+            field_offset: offset + field_start as u64,
+            error,
+            window_offset: offset + window_start as u64,
+            window: block[window_start..window_end].to_vec(),
+        }
+    }
+
+    /// Returns a hexdump of the window, 16 bytes per line, each line prefixed with the offset of
+    /// its first byte in the file.
+    pub fn hexdump(&self) -> String {
+        let mut dump = String::new();
+        for (index, line) in self.window.chunks(16).enumerate() {
+            let _ = write!(dump, "{:012x} ", self.window_offset + index as u64 * 16);
+            for byte in line {
+                let _ = write!(dump, " {byte:02x}");
+            }
+            dump.push('\n');
+        }
+        dump
+    }
+}
+
+impl fmt::Display for BlockDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "malformed {} of block ", self.field)?;
+        match self.number {
+            Some(number) => write!(f, "{number}")?,
+            None => write!(f, "<unknown>")?,
+        }
+        write!(
+            f,
+            " at byte offset {} (field at byte offset {}): {}\n{}",
+            self.offset,
+            self.field_offset,
+            self.error,
+            self.hexdump()
+        )
+    }
+}
+
+impl std::error::Error for BlockDecodeError {}
+
 impl FileClient {
     /// Create a new file client from a file path.
     pub async fn new<P: AsRef<Path>>(path: P) -> Result<Self, FileClientError> {
@@ -77,6 +197,32 @@ impl FileClient {
         Self::from_file(file).await
     }
 
+    /// Creates a new file client that buffers the given blocks.
+    ///
+    /// This allows sources other than files to feed the import pipeline, see
+    /// [`BlockSource`](crate::block_source::BlockSource).
+    pub fn from_blocks(blocks: impl IntoIterator<Item = Block>) -> Self {
+        let mut headers = HashMap::new();
+        let mut hash_to_number = HashMap::new();
+        let mut bodies = HashMap::new();
+        for block in blocks {
+            let header = RawHeader::from_header(&block.header);
+            let block_hash = header.hash();
+            hash_to_number.insert(block_hash, block.header.number);
+            bodies.insert(
+                block_hash,
+                BlockBody {
+                    transactions: block.body,
+                    ommers: block.ommers,
+                    withdrawals: block.withdrawals,
+                    requests: block.requests,
+                },
+            );
+            headers.insert(block.header.number, header);
+        }
+        Self { headers, hash_to_number, bodies, malformed_blocks: Vec::new() }
+    }
+
     /// Initialize the [`FileClient`] with a file directly.
     pub(crate) async fn from_file(mut file: File) -> Result<Self, FileClientError> {
         // get file len from metadata before reading
@@ -91,12 +237,12 @@ impl FileClient {
 
     /// Get the tip hash of the chain.
     pub fn tip(&self) -> Option<B256> {
-        self.headers.get(&self.max_block()?).map(|h| h.hash_slow())
+        self.headers.get(&self.max_block()?).map(RawHeader::hash)
     }
 
     /// Get the start hash of the chain.
     pub fn start(&self) -> Option<B256> {
-        self.headers.get(&self.min_block()?).map(|h| h.hash_slow())
+        self.headers.get(&self.min_block()?).map(RawHeader::hash)
     }
 
     /// Returns the highest block number of this client has or `None` if empty
@@ -109,10 +255,10 @@ impl FileClient {
         self.headers.keys().min().copied()
     }
 
-    /// Clones and returns the highest header of this client has or `None` if empty. Seals header
-    /// before returning.
+    /// Decodes and returns the highest header of this client has or `None` if empty or if it can't
+    /// be decoded. Seals header before returning.
     pub fn tip_header(&self) -> Option<SealedHeader> {
-        self.headers.get(&self.max_block()?).map(|h| h.clone().seal_slow())
+        self.headers.get(&self.max_block()?).and_then(|h| h.sealed_header().ok())
     }
 
     /// Returns true if all blocks are canonical (no gaps)
@@ -141,9 +287,12 @@ impl FileClient {
 
     /// Use the provided headers as the file client's block body buffer.
     pub fn with_headers(mut self, headers: HashMap<BlockNumber, Header>) -> Self {
-        self.headers = headers;
+        self.headers = headers
+            .into_iter()
+            .map(|(number, header)| (number, RawHeader::from_header(&header)))
+            .collect();
         for (number, header) in &self.headers {
-            self.hash_to_number.insert(header.hash_slow(), *number);
+            self.hash_to_number.insert(header.hash(), *number);
         }
         self
     }
@@ -158,8 +307,8 @@ impl FileClient {
         self.bodies.len()
     }
 
-    /// Returns an iterator over headers in the client.
-    pub fn headers_iter(&self) -> impl Iterator<Item = &Header> {
+    /// Returns an iterator over the undecoded headers in the client.
+    pub fn headers_iter(&self) -> impl Iterator<Item = &RawHeader> {
         self.headers.values()
     }
 
@@ -172,10 +321,72 @@ impl FileClient {
         bodies.iter_mut().map(|(hash, body)| (numbers[hash], body))
     }
 
+    /// Removes all blocks above the given block number from the client.
+    ///
+    /// Returns the number of removed blocks.
+    pub fn truncate_above(&mut self, max_block: BlockNumber) -> usize {
+        let removed = self.headers.keys().filter(|number| **number > max_block).count();
+        self.headers.retain(|number, _| *number <= max_block);
+        self.hash_to_number.retain(|_, number| *number <= max_block);
+        let numbers = &self.hash_to_number;
+        self.bodies.retain(|hash, _| numbers.contains_key(hash));
+        removed
+    }
+
+    /// Returns the malformed blocks that were skipped when decoding the file, in file order.
+    pub fn malformed_blocks(&self) -> &[BlockDecodeError] {
+        &self.malformed_blocks
+    }
+
     /// Returns the current number of transactions in the client.
     pub fn total_transactions(&self) -> usize {
         self.bodies.iter().fold(0, |acc, (_, body)| acc + body.transactions.len())
     }
+
+    /// Checks the bodies in the client against the transactions root, ommers hash and withdrawals
+    /// root of their headers.
+    ///
+    /// Returns the blocks whose body doesn't match their header, ordered by block number.
+    pub fn validate_bodies(&self) -> Result<Vec<(BlockNumber, ConsensusError)>, FileClientError> {
+        let mut mismatches = Vec::new();
+        for (hash, body) in &self.bodies {
+            let number = self.hash_to_number[hash];
+            let raw = &self.headers[&number];
+            let header =
+                raw.header().map_err(|err| FileClientError::Rlp(err, raw.rlp().to_vec()))?;
+            if let Err(err) = validate_body(&header, body) {
+                mismatches.push((number, err));
+            }
+        }
+        mismatches.sort_unstable_by_key(|(number, _)| *number);
+        Ok(mismatches)
+    }
+}
+
+/// Checks the body against the transactions root, ommers hash and withdrawals root of the header.
+fn validate_body(header: &Header, body: &BlockBody) -> Result<(), ConsensusError> {
+    let ommers_hash = body.calculate_ommers_root();
+    if header.ommers_hash != ommers_hash {
+        return Err(ConsensusError::BodyOmmersHashDiff(
+            GotExpected { got: ommers_hash, expected: header.ommers_hash }.into(),
+        ))
+    }
+
+    let transactions_root = body.calculate_tx_root();
+    if header.transactions_root != transactions_root {
+        return Err(ConsensusError::BodyTransactionRootDiff(
+            GotExpected { got: transactions_root, expected: header.transactions_root }.into(),
+        ))
+    }
+
+    match (body.calculate_withdrawals_root(), header.withdrawals_root) {
+        (Some(got), Some(expected)) if got != expected => {
+            Err(ConsensusError::BodyWithdrawalsRootDiff(GotExpected { got, expected }.into()))
+        }
+        (None, Some(_)) => Err(ConsensusError::BodyWithdrawalsMissing),
+        (Some(_), None) => Err(ConsensusError::WithdrawalsRootMissing),
+        _ => Ok(()),
+    }
 }
 
 impl FromReader for FileClient {
@@ -186,15 +397,34 @@ impl FromReader for FileClient {
         reader: B,
         num_bytes: u64,
     ) -> impl Future<Output = Result<(Self, Vec<u8>), Self::Error>>
+    where
+        B: AsyncReadExt + Unpin,
+    {
+        Self::from_chunk(reader, num_bytes, 0, DecodeMode::default())
+    }
+
+    /// Initialize the [`FileClient`] from bytes that have been read from file, starting at byte
+    /// `offset` of the file.
+    fn from_chunk<B>(
+        reader: B,
+        num_bytes: u64,
+        offset: u64,
+        mode: DecodeMode,
+    ) -> impl Future<Output = Result<(Self, Vec<u8>), Self::Error>>
     where
         B: AsyncReadExt + Unpin,
     {
         let mut headers = HashMap::new();
         let mut hash_to_number = HashMap::new();
         let mut bodies = HashMap::new();
+        let mut malformed_blocks = Vec::new();
 
         // use with_capacity to make sure the internal buffer contains the entire chunk
-        let mut stream = FramedRead::with_capacity(reader, BlockFileCodec, num_bytes as usize);
+        let mut stream = FramedRead::with_capacity(
+            reader,
+            BlockFileCodec::new(mode, offset),
+            num_bytes as usize,
+        );
 
         trace!(target: "downloaders::file",
             target_num_bytes=num_bytes,
@@ -209,8 +439,20 @@ impl FromReader for FileClient {
 
         async move {
             while let Some(block_res) = stream.next().await {
-                let block = match block_res {
-                    Ok(block) => block,
+                let (header, body) = match block_res {
+                    Ok(DecodedBlock::Block(header, body)) => (header, body),
+                    Ok(DecodedBlock::Malformed(malformed)) => {
+                        warn!(target: "downloaders::file",
+                            offset=malformed.offset,
+                            number=?malformed.number,
+                            field=malformed.field,
+                            field_offset=malformed.field_offset,
+                            err=%malformed.error,
+                            "Skipping malformed block"
+                        );
+                        malformed_blocks.push(malformed);
+                        continue
+                    }
                     Err(FileClientError::Rlp(err, bytes)) => {
                         trace!(target: "downloaders::file",
                             %err,
@@ -222,21 +464,17 @@ impl FromReader for FileClient {
                     }
                     Err(err) => return Err(err),
                 };
-                let block_number = block.header.number;
-                let block_hash = block.header.hash_slow();
+                if !malformed_blocks.is_empty() {
+                    // blocks above a malformed block can't be imported
+                    continue
+                }
+                let block_number = header.number();
+                let block_hash = header.hash();
 
                 // add to the internal maps
-                headers.insert(block.header.number, block.header.clone());
-                hash_to_number.insert(block_hash, block.header.number);
-                bodies.insert(
-                    block_hash,
-                    BlockBody {
-                        transactions: block.body,
-                        ommers: block.ommers,
-                        withdrawals: block.withdrawals,
-                        requests: block.requests,
-                    },
-                );
+                headers.insert(block_number, header);
+                hash_to_number.insert(block_hash, block_number);
+                bodies.insert(block_hash, body);
 
                 if log_interval == 0 {
                     trace!(target: "downloaders::file",
@@ -256,7 +494,7 @@ impl FromReader for FileClient {
 
             trace!(target: "downloaders::file", blocks = headers.len(), "Initialized file client");
 
-            Ok((Self { headers, hash_to_number, bodies }, remaining_bytes))
+            Ok((Self { headers, hash_to_number, bodies, malformed_blocks }, remaining_bytes))
         }
     }
 }
@@ -298,8 +536,13 @@ impl HeadersClient for FileClient {
         trace!(target: "downloaders::file", range=?range, "Getting headers with range");
 
         for block_number in range {
-            match self.headers.get(&block_number).cloned() {
-                Some(header) => headers.push(header),
+            // headers are only decoded once requested
+            match self.headers.get(&block_number).map(RawHeader::header) {
+                Some(Ok(header)) => headers.push(header),
+                Some(Err(err)) => {
+                    warn!(number=%block_number, %err, "Could not decode header");
+                    return Box::pin(async move { Err(RequestError::BadResponse) })
+                }
                 None => {
                     warn!(number=%block_number, "Could not find header");
                     return Box::pin(async move { Err(RequestError::BadResponse) })
@@ -347,6 +590,91 @@ impl DownloadClient for FileClient {
     }
 }
 
+/// A block header buffered as its RLP encoding.
+///
+/// When decoded from a file, the encoding is a view into the read buffer, so buffering a header
+/// doesn't allocate. Its number is read from the encoding and its hash is the hash of the encoding,
+/// the header itself is only decoded when it's requested, right before it's written to the
+/// database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawHeader {
+    /// The RLP encoding of the header.
+    rlp: Bytes,
+    /// The block number.
+    number: BlockNumber,
+    /// The block hash.
+    hash: BlockHash,
+}
+
+impl RawHeader {
+    /// Creates a raw header from an encoding whose block number has already been read.
+    pub(crate) fn new(rlp: Bytes, number: BlockNumber) -> Self {
+        let hash = keccak256(&rlp);
+        Self { rlp, number, hash }
+    }
+
+    /// Creates a raw header from its RLP encoding.
+    pub fn decode(rlp: Bytes) -> alloy_rlp::Result<Self> {
+        let number = header_number(&rlp)?;
+        Ok(Self::new(rlp, number))
+    }
+
+    /// Creates a raw header by encoding the given header.
+    pub fn from_header(header: &Header) -> Self {
+        let mut rlp = Vec::with_capacity(header.length());
+        header.encode(&mut rlp);
+        Self::new(rlp.into(), header.number)
+    }
+
+    /// Returns the block number.
+    pub const fn number(&self) -> BlockNumber {
+        self.number
+    }
+
+    /// Returns the block hash.
+    pub const fn hash(&self) -> BlockHash {
+        self.hash
+    }
+
+    /// Returns the RLP encoding of the header.
+    pub const fn rlp(&self) -> &Bytes {
+        &self.rlp
+    }
+
+    /// Decodes the header.
+    pub fn header(&self) -> alloy_rlp::Result<Header> {
+        Header::decode(&mut self.rlp.as_ref())
+    }
+
+    /// Decodes the header and seals it with its hash.
+    pub fn sealed_header(&self) -> alloy_rlp::Result<SealedHeader> {
+        Ok(self.header()?.seal(self.hash))
+    }
+}
+
+/// Reads the block number from the RLP encoding of a header, without decoding the other fields.
+pub(crate) fn header_number(mut rlp: &[u8]) -> alloy_rlp::Result<BlockNumber> {
+    let buf = &mut rlp;
+    if !alloy_rlp::Header::decode(buf)?.list {
+        return Err(alloy_rlp::Error::UnexpectedString)
+    }
+
+    // skip parent hash, ommers hash, beneficiary, state root, transactions root, receipts root,
+    // logs bloom and difficulty
+    for _ in 0..8 {
+        let field = alloy_rlp::Header::decode(buf)?;
+        if field.list {
+            return Err(alloy_rlp::Error::UnexpectedList)
+        }
+        if buf.len() < field.payload_length {
+            return Err(alloy_rlp::Error::InputTooShort)
+        }
+        buf.advance(field.payload_length);
+    }
+
+    BlockNumber::decode(buf)
+}
+
 /// Chunks file into several [`FileClient`]s.
 #[derive(Debug)]
 pub struct ChunkedFileReader {
@@ -358,6 +686,12 @@ pub struct ChunkedFileReader {
     chunk: Vec<u8>,
     /// Max bytes per chunk.
     chunk_byte_len: u64,
+    /// Byte offset in the file of the first byte of the chunk.
+    offset: u64,
+    /// How malformed blocks are handled.
+    decode_mode: DecodeMode,
+    /// The number of malformed blocks skipped by [`ChunkedFileReader::next_blocks`].
+    malformed_blocks: usize,
 }
 
 impl ChunkedFileReader {
@@ -384,7 +718,26 @@ impl ChunkedFileReader {
         let metadata = file.metadata().await?;
         let file_byte_len = metadata.len();
 
-        Ok(Self { file, file_byte_len, chunk: vec![], chunk_byte_len })
+        Ok(Self {
+            file,
+            file_byte_len,
+            chunk: vec![],
+            chunk_byte_len,
+            offset: 0,
+            decode_mode: DecodeMode::default(),
+            malformed_blocks: 0,
+        })
+    }
+
+    /// Returns the number of malformed blocks skipped by [`ChunkedFileReader::next_blocks`].
+    pub const fn malformed_blocks(&self) -> usize {
+        self.malformed_blocks
+    }
+
+    /// Sets how malformed blocks are handled, [`DecodeMode::Lenient`] by default.
+    pub const fn with_decode_mode(mut self, decode_mode: DecodeMode) -> Self {
+        self.decode_mode = decode_mode;
+        self
     }
 
     /// Calculates the number of bytes to read from the chain file. Returns a tuple of the chunk
@@ -441,12 +794,59 @@ impl ChunkedFileReader {
         );
 
         // make new file client from chunk
-        let (file_client, bytes) =
-            T::from_reader(&self.chunk[..], next_chunk_byte_len as u64).await?;
+        let (file_client, bytes) = T::from_chunk(
+            &self.chunk[..],
+            next_chunk_byte_len as u64,
+            self.offset,
+            self.decode_mode,
+        )
+        .await?;
 
         // save left over bytes
+        self.offset += (next_chunk_byte_len - bytes.len()) as u64;
         self.chunk = bytes;
 
+        if self.file_byte_len == 0 && !self.chunk.is_empty() {
+            // the left over bytes are not followed by the rest of the block
+            let msg = format!(
+                "file ends with a partial block of {} bytes at byte offset {}",
+                self.chunk.len(),
+                self.offset
+            );
+            if self.decode_mode == DecodeMode::Strict {
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof, msg).into())
+            }
+            warn!(target: "downloaders::file", %msg, "Skipping partial block");
+            self.chunk.clear();
+        }
+
+        Ok(Some(file_client))
+    }
+
+    /// Reads the next chunk of blocks from file.
+    ///
+    /// Once a chunk contains a malformed block, which is only possible in
+    /// [`DecodeMode::Lenient`], the blocks below it are returned and reading stops, since the
+    /// blocks above it can't be imported. The malformed blocks of the chunk are recorded in
+    /// [`FileClient::malformed_blocks`].
+    pub async fn next_blocks(&mut self) -> Result<Option<FileClient>, FileClientError> {
+        let Some(file_client) = self.next_chunk::<FileClient>().await? else { return Ok(None) };
+
+        if let Some(malformed) = file_client.malformed_blocks().first() {
+            warn!(target: "downloaders::file",
+                offset=malformed.offset,
+                number=?malformed.number,
+                malformed_blocks=file_client.malformed_blocks().len(),
+                "Stopping at malformed block"
+            );
+            self.malformed_blocks += file_client.malformed_blocks().len();
+            self.file_byte_len = 0;
+            self.chunk.clear();
+            if file_client.headers_len() == 0 {
+                return Ok(None)
+            }
+        }
+
         Ok(Some(file_client))
     }
 }
@@ -463,6 +863,24 @@ pub trait FromReader {
     where
         Self: Sized,
         B: AsyncReadExt + Unpin;
+
+    /// Returns a file client for a chunk starting at byte `offset` of the file, handling
+    /// malformed data according to `mode`.
+    ///
+    /// Defaults to [`FromReader::from_reader`], for file clients that don't track the position of
+    /// the decoded data.
+    fn from_chunk<B>(
+        reader: B,
+        num_bytes: u64,
+        _offset: u64,
+        _mode: DecodeMode,
+    ) -> impl Future<Output = Result<(Self, Vec<u8>), Self::Error>>
+    where
+        Self: Sized,
+        B: AsyncReadExt + Unpin,
+    {
+        Self::from_reader(reader, num_bytes)
+    }
 }
 
 #[cfg(test)]
@@ -484,6 +902,7 @@ mod tests {
         bodies::downloader::BodyDownloader,
         headers::downloader::{HeaderDownloader, SyncTarget},
     };
+    use reth_primitives::{proofs, Address, Withdrawal, Withdrawals};
     use reth_provider::test_utils::create_test_provider_factory;
     use std::sync::Arc;
 
@@ -595,6 +1014,40 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn truncate_blocks_above() {
+        let (file, _, _) = generate_bodies_file(0..=19).await;
+        let mut client = FileClient::from_file(file).await.unwrap();
+
+        assert_eq!(client.truncate_above(14), 5);
+        assert_eq!(client.max_block(), Some(14));
+        assert_eq!(client.headers_len(), 15);
+        assert_eq!(client.bodies_len(), 15);
+        assert!(client.has_canonical_blocks());
+    }
+
+    #[tokio::test]
+    async fn validate_bodies_against_headers() {
+        let (file, headers, _) = generate_bodies_file(0..=19).await;
+        let mut client = FileClient::from_file(file).await.unwrap();
+        assert!(client.validate_bodies().unwrap().is_empty());
+
+        // add an uncle to one block and replace the transactions of another
+        for (number, body) in client.bodies_iter_mut() {
+            if number == 3 {
+                body.ommers.push(headers[0].clone().unseal());
+            } else if number == 7 {
+                body.transactions.clear();
+                body.transactions.push(Default::default());
+            }
+        }
+
+        let mismatches = client.validate_bodies().unwrap();
+        assert_eq!(mismatches.len(), 2);
+        assert_matches!(&mismatches[0], (3, ConsensusError::BodyOmmersHashDiff(_)));
+        assert_matches!(&mismatches[1], (7, ConsensusError::BodyTransactionRootDiff(_)));
+    }
+
     #[tokio::test]
     async fn test_chunk_download_headers_from_file() {
         reth_tracing::init_test_tracing();
@@ -640,4 +1093,99 @@ mod tests {
         // the first header is not included in the response
         assert_eq!(headers[1..], downloaded_headers);
     }
+
+    /// Returns the encoding of a block with the given header, whose transactions are encoded as a
+    /// string instead of a list.
+    fn malformed_block(header: &Header) -> Vec<u8> {
+        let mut payload = Vec::new();
+        header.encode(&mut payload);
+        payload.extend([alloy_rlp::EMPTY_STRING_CODE, alloy_rlp::EMPTY_LIST_CODE]);
+
+        let mut block = Vec::new();
+        alloy_rlp::Header { list: true, payload_length: payload.len() }.encode(&mut block);
+        block.extend(payload);
+        block
+    }
+
+    #[tokio::test]
+    async fn decodes_blocks_with_withdrawals() {
+        let (headers, mut bodies) = generate_bodies(0..=0);
+        let mut body = bodies.remove(&headers[0].hash()).unwrap();
+        let withdrawals = Withdrawals::new(vec![Withdrawal {
+            index: 0,
+            validator_index: 1,
+            address: Address::with_last_byte(2),
+            amount: 3,
+        }]);
+        let mut header = headers[0].clone().unseal();
+        header.withdrawals_root = Some(proofs::calculate_withdrawals_root(&withdrawals));
+        body.withdrawals = Some(withdrawals);
+
+        let mut bytes = Vec::new();
+        body.clone().create_block(header).encode(&mut bytes);
+        let (client, _) =
+            FileClient::from_chunk(&bytes[..], bytes.len() as u64, 0, DecodeMode::Strict)
+                .await
+                .unwrap();
+
+        assert_eq!(client.bodies.values().collect::<Vec<_>>(), vec![&body]);
+        assert!(client.validate_bodies().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn decode_modes_handle_malformed_block() {
+        let (mut file, headers, _) = generate_bodies_file(0..=4).await;
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes).await.unwrap();
+
+        // blocks 0..=4, a malformed block 5, and well-formed blocks again
+        let block_offset = bytes.len() as u64;
+        let malformed_header = child_header(headers.last().unwrap());
+        let block = malformed_block(malformed_header.header());
+        let transactions_offset = block_offset + (block.len() - 2) as u64;
+        bytes.extend(block);
+        bytes.extend_from_within(..block_offset as usize);
+        let offset = 1_000;
+
+        let (client, _) =
+            FileClient::from_chunk(&bytes[..], bytes.len() as u64, offset, DecodeMode::Lenient)
+                .await
+                .unwrap();
+        assert_eq!(client.headers_len(), 5);
+        assert_eq!(client.max_block(), Some(4));
+        assert_matches!(client.malformed_blocks(), [malformed] => {
+            assert_eq!(malformed.offset, offset + block_offset);
+            assert_eq!(malformed.number, Some(5));
+            assert_eq!(malformed.field, "transactions");
+            assert_eq!(malformed.field_offset, offset + transactions_offset);
+            assert_eq!(malformed.window_offset, malformed.field_offset - 32);
+            assert_eq!(malformed.window.len(), 34);
+        });
+
+        let err =
+            FileClient::from_chunk(&bytes[..], bytes.len() as u64, offset, DecodeMode::Strict)
+                .await
+                .unwrap_err();
+        assert_matches!(err, FileClientError::Malformed(malformed) => {
+            assert_eq!(malformed.number, Some(5));
+            assert_eq!(malformed.field, "transactions");
+            assert!(malformed.to_string().contains(&format!("{:012x}", malformed.window_offset)));
+        });
+    }
+
+    #[test]
+    fn raw_header_reads_number_and_hash() {
+        let header = Header {
+            number: 1_000_000,
+            difficulty: reth_primitives::U256::from(0x7f),
+            ..Default::default()
+        };
+        let sealed = header.clone().seal_slow();
+
+        let raw = RawHeader::from_header(&header);
+        assert_eq!(raw.number(), header.number);
+        assert_eq!(raw.hash(), sealed.hash());
+        assert_eq!(RawHeader::decode(raw.rlp().clone()).unwrap(), raw);
+        assert_eq!(raw.sealed_header().unwrap(), sealed);
+    }
 }
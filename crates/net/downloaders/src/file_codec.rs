@@ -1,11 +1,9 @@
 //! Codec for reading raw block bodies from a file.
 
-use crate::file_client::FileClientError;
+use crate::file_client::{header_number, BlockDecodeError, DecodeMode, FileClientError, RawHeader};
 use alloy_rlp::{Decodable, Encodable};
-use reth_primitives::{
-    bytes::{Buf, BytesMut},
-    Block,
-};
+use reth_primitives::{bytes::BytesMut, Block, BlockBody, BlockNumber, Header};
+use std::ops::Range;
 use tokio_util::codec::{Decoder, Encoder};
 
 /// Codec for reading raw block bodies from a file.
@@ -21,10 +19,41 @@ use tokio_util::codec::{Decoder, Encoder};
 ///
 /// It's recommended to use [`with_capacity`](tokio_util::codec::FramedRead::with_capacity) to set
 /// the capacity of the framed reader to the size of the file.
-pub(crate) struct BlockFileCodec;
+///
+/// Block headers are not decoded: each is returned as a [`RawHeader`], a view into the read buffer,
+/// and is only decoded once requested by the pipeline. In [`DecodeMode::Strict`], headers are
+/// decoded eagerly so that a malformed header is reported with the rest of the block.
+///
+/// A block that is framed correctly but has a malformed field is returned as
+/// [`DecodedBlock::Malformed`] in [`DecodeMode::Lenient`] and as an error in
+/// [`DecodeMode::Strict`]. A block that can't be framed is an error in both modes, since the start
+/// of the next block can't be found.
+#[derive(Debug, Default)]
+pub(crate) struct BlockFileCodec {
+    /// How malformed blocks are handled.
+    mode: DecodeMode,
+    /// Byte offset in the file of the start of the read buffer.
+    offset: u64,
+}
+
+impl BlockFileCodec {
+    /// Creates a codec for a read buffer starting at byte `offset` of the file.
+    pub(crate) const fn new(mode: DecodeMode, offset: u64) -> Self {
+        Self { mode, offset }
+    }
+}
+
+/// A block read from a file by the [`BlockFileCodec`].
+#[derive(Debug)]
+pub(crate) enum DecodedBlock {
+    /// A well-formed block.
+    Block(RawHeader, BlockBody),
+    /// A block with a malformed field, skipped in [`DecodeMode::Lenient`].
+    Malformed(BlockDecodeError),
+}
 
 impl Decoder for BlockFileCodec {
-    type Item = Block;
+    type Item = DecodedBlock;
     type Error = FileClientError;
 
     fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
@@ -32,15 +61,118 @@ impl Decoder for BlockFileCodec {
             return Ok(None)
         }
 
-        let buf_slice = &mut src.as_ref();
-        let body =
-            Block::decode(buf_slice).map_err(|err| FileClientError::Rlp(err, src.to_vec()))?;
-        src.advance(src.len() - buf_slice.len());
+        let block_len = match block_len(src) {
+            Ok(Some(block_len)) => block_len,
+            // the block continues past the read buffer
+            Ok(None) => {
+                return Err(FileClientError::Rlp(alloy_rlp::Error::InputTooShort, src.to_vec()))
+            }
+            Err(err) => {
+                return Err(BlockDecodeError::new(self.offset, None, "block", 0, src, err).into())
+            }
+        };
 
-        Ok(Some(body))
+        let decoded = decode_block(&src[..block_len], self.mode);
+        let block = src.split_to(block_len).freeze();
+        let offset = self.offset;
+        self.offset += block_len as u64;
+
+        match decoded {
+            Ok((header_range, number, body)) => {
+                // Splitting off the block doesn't copy, the header keeps pointing into the read
+                // buffer.
+                let header = RawHeader::new(block.slice(header_range), number);
+                Ok(Some(DecodedBlock::Block(header, body)))
+            }
+            Err((field, field_start, number, err)) => {
+                let malformed =
+                    BlockDecodeError::new(offset, number, field, field_start, &block, err);
+                match self.mode {
+                    DecodeMode::Strict => Err(malformed.into()),
+                    DecodeMode::Lenient => Ok(Some(DecodedBlock::Malformed(malformed))),
+                }
+            }
+        }
     }
 }
 
+/// A malformed field of a block: its name, its position in the block, the block number if it
+/// could be read, and the decoding error.
+type FieldError = (&'static str, usize, Option<BlockNumber>, alloy_rlp::Error);
+
+/// Returns the length of the block at the start of `buf`, or `None` if `buf` doesn't contain the
+/// entire block.
+fn block_len(buf: &[u8]) -> alloy_rlp::Result<Option<usize>> {
+    let mut rest = buf;
+    let block = match alloy_rlp::Header::decode(&mut rest) {
+        Ok(block) => block,
+        Err(alloy_rlp::Error::InputTooShort) => return Ok(None),
+        Err(err) => return Err(err),
+    };
+    if !block.list {
+        return Err(alloy_rlp::Error::UnexpectedString)
+    }
+    let block_len = buf.len() - rest.len() + block.payload_length;
+    Ok((buf.len() >= block_len).then_some(block_len))
+}
+
+/// Decodes an entire block, returning the range of its header, its number and its body.
+fn decode_block(
+    block: &[u8],
+    mode: DecodeMode,
+) -> Result<(Range<usize>, BlockNumber, BlockBody), FieldError> {
+    let mut rest = block;
+    // the block length is already checked, so the list header is valid
+    alloy_rlp::Header::decode(&mut rest).expect("block is framed");
+    let header_start = block.len() - rest.len();
+
+    let header_error = |err| ("header", header_start, None, err);
+    let header = alloy_rlp::Header::decode(&mut rest).map_err(header_error)?;
+    let header_end = block.len() - rest.len() + header.payload_length;
+    if header_end > block.len() {
+        return Err(header_error(alloy_rlp::Error::UnexpectedLength))
+    }
+    let header_rlp = &block[header_start..header_end];
+
+    let number = header_number(header_rlp).map_err(header_error)?;
+    if mode == DecodeMode::Strict {
+        Header::decode(&mut &header_rlp[..])
+            .map_err(|err| ("header", header_start, Some(number), err))?;
+    }
+
+    let body = decode_body(&block[header_end..])
+        .map_err(|(field, start, err)| (field, header_end + start, Some(number), err))?;
+
+    Ok((header_start..header_end, number, body))
+}
+
+/// Decodes the fields following the header in a block.
+///
+/// On error, returns the name of the malformed field and its position in `buf`.
+fn decode_body(buf: &[u8]) -> Result<BlockBody, (&'static str, usize, alloy_rlp::Error)> {
+    let rest = &mut &buf[..];
+    let transactions = decode_field(buf, rest, "transactions")?;
+    let ommers = decode_field(buf, rest, "ommers")?;
+    let withdrawals =
+        if rest.is_empty() { None } else { Some(decode_field(buf, rest, "withdrawals")?) };
+    let requests = if rest.is_empty() { None } else { Some(decode_field(buf, rest, "requests")?) };
+    if !rest.is_empty() {
+        return Err(("block", buf.len() - rest.len(), alloy_rlp::Error::UnexpectedLength))
+    }
+
+    Ok(BlockBody { transactions, ommers, withdrawals, requests })
+}
+
+/// Decodes the next field of `rest`, the undecoded suffix of `buf`.
+fn decode_field<T: Decodable>(
+    buf: &[u8],
+    rest: &mut &[u8],
+    name: &'static str,
+) -> Result<T, (&'static str, usize, alloy_rlp::Error)> {
+    let start = buf.len() - rest.len();
+    T::decode(rest).map_err(|err| (name, start, err))
+}
+
 impl Encoder<Block> for BlockFileCodec {
     type Error = FileClientError;
 
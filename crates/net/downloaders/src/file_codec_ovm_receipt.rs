@@ -7,7 +7,10 @@ use reth_primitives::{
 };
 use tokio_util::codec::Decoder;
 
-use crate::{file_client::FileClientError, receipt_file_client::ReceiptWithBlockNumber};
+use crate::{
+    file_client::FileClientError,
+    receipt_file_client::{ReceiptL1Fee, ReceiptWithBlockNumber},
+};
 
 /// Codec for reading raw receipts from a file.
 ///
@@ -76,7 +79,16 @@ impl TryFrom<HackReceipt> for ReceiptWithBlockNumber {
     type Error = &'static str;
     fn try_from(exported_receipt: HackReceipt) -> Result<Self, Self::Error> {
         let HackReceipt {
-            tx_type, status, cumulative_gas_used, logs, block_number: number, ..
+            tx_type,
+            status,
+            cumulative_gas_used,
+            logs,
+            block_number: number,
+            l1_gas_price,
+            l1_gas_used,
+            l1_fee,
+            fee_scalar,
+            ..
         } = exported_receipt;
 
         #[allow(clippy::needless_update)]
@@ -88,10 +100,28 @@ impl TryFrom<HackReceipt> for ReceiptWithBlockNumber {
             ..Default::default()
         };
 
-        Ok(Self { receipt, number })
+        let l1_fee = ReceiptL1Fee {
+            l1_gas_price,
+            l1_gas_used,
+            l1_fee,
+            fee_scalar: parse_fee_scalar(&fee_scalar).ok_or("invalid fee scalar")?,
+        };
+
+        Ok(Self { receipt, number, l1_fee: Some(l1_fee) })
     }
 }
 
+/// Parses the decimal fee scalar of a legacy receipt, e.g. `"1.5"`, into millionths.
+fn parse_fee_scalar(scalar: &str) -> Option<u64> {
+    let (int, frac) = scalar.split_once('.').unwrap_or((scalar, ""));
+    if frac.len() > 6 || !frac.bytes().all(|b| b.is_ascii_digit()) {
+        return None
+    }
+    // pad the fraction to six digits, so "5" reads as 500000 millionths
+    let frac = format!("{frac:0<6}").parse::<u64>().ok()?;
+    int.parse::<u64>().ok()?.checked_mul(1_000_000)?.checked_add(frac)
+}
+
 #[cfg(test)]
 pub(super) mod test {
     use reth_primitives::{alloy_primitives::LogData, hex};
@@ -125,6 +155,11 @@ pub(super) mod test {
         }
     }
 
+    /// The L1 fee recorded in all of the test receipts.
+    fn l1_fee() -> ReceiptL1Fee {
+        ReceiptL1Fee { l1_gas_price: 1, l1_gas_used: 4802, l1_fee: 7203, fee_scalar: 1_500_000 }
+    }
+
     pub(crate) fn receipt_block_1() -> ReceiptWithBlockNumber {
         let log_1 = Log {
             address: Address::from(hex!("8ce8c13d816fe6daf12d6fd9e4952e1fc88850af")),
@@ -195,7 +230,7 @@ pub(super) mod test {
         // feature must not be brought into scope
         receipt.logs = vec![log_1, log_2, log_3];
 
-        ReceiptWithBlockNumber { receipt, number: 1 }
+        ReceiptWithBlockNumber { receipt, number: 1, l1_fee: Some(l1_fee()) }
     }
 
     pub(crate) fn receipt_block_2() -> ReceiptWithBlockNumber {
@@ -247,7 +282,7 @@ pub(super) mod test {
         // feature must not be brought into scope
         receipt.logs = vec![log_1, log_2];
 
-        ReceiptWithBlockNumber { receipt, number: 2 }
+        ReceiptWithBlockNumber { receipt, number: 2, l1_fee: Some(l1_fee()) }
     }
 
     pub(crate) fn receipt_block_3() -> ReceiptWithBlockNumber {
@@ -299,7 +334,16 @@ pub(super) mod test {
         // feature must not be brought into scope
         receipt.logs = vec![log_1, log_2];
 
-        ReceiptWithBlockNumber { receipt, number: 3 }
+        ReceiptWithBlockNumber { receipt, number: 3, l1_fee: Some(l1_fee()) }
+    }
+
+    #[test]
+    fn fee_scalar() {
+        assert_eq!(parse_fee_scalar("1.5"), Some(1_500_000));
+        assert_eq!(parse_fee_scalar("1"), Some(1_000_000));
+        assert_eq!(parse_fee_scalar("0.000001"), Some(1));
+        assert_eq!(parse_fee_scalar("0.0000001"), None);
+        assert_eq!(parse_fee_scalar("1.-5"), None);
     }
 
     #[test]
@@ -0,0 +1,133 @@
+//! Codec for reading the transaction metadata of the OP Mainnet legacy chain from a file.
+
+use crate::file_client::FileClientError;
+use alloy_rlp::{Decodable, RlpDecodable, RlpEncodable};
+use reth_primitives::{
+    bytes::{Buf, BytesMut},
+    Bytes, B256,
+};
+use std::path::Path;
+use tokio::fs::File;
+use tokio_stream::StreamExt;
+use tokio_util::codec::{Decoder, FramedRead};
+
+/// The metadata l2geth stored next to a transaction of the legacy chain, which is not part of the
+/// RLP encoding of the transaction.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct TxMeta {
+    /// The L1 timestamp of the transaction.
+    pub timestamp: u64,
+    /// The remaining fields of the metadata, RLP encoded.
+    pub rest: Bytes,
+}
+
+/// An entry of a legacy transaction metadata file, encoded as
+/// `rlp([tx_hash, size, [timestamp, rest]])`.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct TxMetaEntry {
+    /// The hash of the transaction.
+    pub tx_hash: B256,
+    /// The size of the transaction, as reported by the legacy chain.
+    pub size: u64,
+    /// The metadata of the transaction.
+    pub meta: TxMeta,
+}
+
+/// Codec for reading [`TxMetaEntry`]s from a file.
+///
+/// An entry that falls across two read buffers is decoded once the rest of it is read, so the
+/// codec can be used with a [`FramedRead`] of any capacity.
+#[derive(Debug, Default)]
+pub struct TxMetaFileCodec;
+
+impl Decoder for TxMetaFileCodec {
+    type Item = TxMetaEntry;
+    type Error = FileClientError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if src.is_empty() {
+            return Ok(None)
+        }
+
+        let buf_slice = &mut src.as_ref();
+        let entry = match TxMetaEntry::decode(buf_slice) {
+            Ok(entry) => entry,
+            // the rest of the entry is not read yet
+            Err(alloy_rlp::Error::InputTooShort) => return Ok(None),
+            Err(err) => return Err(FileClientError::Rlp(err, src.to_vec())),
+        };
+        src.advance(src.len() - buf_slice.len());
+
+        Ok(Some(entry))
+    }
+}
+
+/// Reads the [`TxMetaEntry`]s of a legacy transaction metadata file one after another.
+#[derive(Debug)]
+pub struct TxMetaFileReader {
+    stream: FramedRead<File, TxMetaFileCodec>,
+}
+
+impl TxMetaFileReader {
+    /// Opens the legacy transaction metadata file at `path`.
+    pub async fn open<P: AsRef<Path>>(path: P) -> Result<Self, FileClientError> {
+        let file = File::open(path).await?;
+        Ok(Self { stream: FramedRead::new(file, TxMetaFileCodec) })
+    }
+
+    /// Returns the next entry of the file, or `None` at the end of the file.
+    ///
+    /// Returns an error if the file ends within an entry.
+    pub async fn next_entry(&mut self) -> Result<Option<TxMetaEntry>, FileClientError> {
+        self.stream.next().await.transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rlp::Encodable;
+    use reth_primitives::bytes::BufMut;
+
+    fn entry(n: u8) -> TxMetaEntry {
+        TxMetaEntry {
+            tx_hash: B256::with_last_byte(n),
+            size: 100 + n as u64,
+            meta: TxMeta {
+                timestamp: 1_636_665_000 + n as u64,
+                rest: Bytes::from(vec![n; n as usize * 20]),
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn decodes_entries_across_reads() {
+        let entries = (0..5).map(entry).collect::<Vec<_>>();
+        let mut file = BytesMut::new();
+        for entry in &entries {
+            entry.encode(&mut file);
+        }
+
+        // entries fall across reads of the small buffer
+        let mut stream = FramedRead::with_capacity(&file[..], TxMetaFileCodec, 16);
+        let mut decoded = Vec::new();
+        while let Some(entry) = stream.next().await {
+            decoded.push(entry.unwrap());
+        }
+        assert_eq!(decoded, entries);
+
+        // a truncated entry at the end of the file is an error
+        let mut stream = FramedRead::new(&file[..file.len() - 1], TxMetaFileCodec);
+        let mut results = Vec::new();
+        while let Some(entry) = stream.next().await {
+            results.push(entry);
+        }
+        assert_eq!(results.len(), entries.len());
+        assert!(results.last().unwrap().is_err());
+
+        // anything but a list is malformed
+        let mut malformed = BytesMut::new();
+        malformed.put_u8(0x80);
+        assert!(TxMetaFileCodec.decode(&mut malformed).is_err());
+    }
+}
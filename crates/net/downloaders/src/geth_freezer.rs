@@ -0,0 +1,281 @@
+use crate::{
+    block_source::BlockSource,
+    file_client::{FileClient, FileClientError},
+};
+use alloy_rlp::Decodable;
+use reth_primitives::{Block, BlockBody, BlockNumber, Header};
+use std::{
+    fs::File,
+    io::{self, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
+use tracing::debug;
+
+/// Default number of blocks read from the freezer per batch.
+pub const DEFAULT_FREEZER_BATCH_SIZE: u64 = 10_000;
+
+/// Length of an entry of a freezer index: the number of the data file and the end offset of the
+/// item in it.
+const INDEX_ENTRY_LEN: u64 = 6;
+
+/// Directories of a geth datadir the freezer of the chain can be in, relative to the given path.
+const FREEZER_DIRS: &[&str] = &[
+    "",
+    "chain",
+    "ancient/chain",
+    "ancient",
+    "chaindata/ancient/chain",
+    "chaindata/ancient",
+    "geth/chaindata/ancient/chain",
+    "geth/chaindata/ancient",
+];
+
+/// Reads the blocks of a geth freezer, the `ancient` database of a geth datadir.
+///
+/// Only the blocks moved to the freezer are read, the most recent blocks of a geth node are in
+/// its key-value store.
+#[derive(Debug)]
+pub struct GethFreezerReader {
+    headers: FreezerTable,
+    bodies: FreezerTable,
+    /// The next block to read.
+    next: BlockNumber,
+    batch_size: u64,
+}
+
+impl GethFreezerReader {
+    /// Opens the freezer at `path`, which is either the freezer itself or a geth datadir.
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let dir = find_freezer(path).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no geth freezer found in {}", path.display()),
+            )
+        })?;
+        let headers = FreezerTable::open(&dir, "headers")?;
+        let bodies = FreezerTable::open(&dir, "bodies")?;
+        let next = headers.tail.max(bodies.tail);
+        debug!(target: "downloaders::freezer",
+            dir = %dir.display(),
+            first_block = next,
+            "Opened geth freezer"
+        );
+        Ok(Self { headers, bodies, next, batch_size: DEFAULT_FREEZER_BATCH_SIZE })
+    }
+
+    /// Sets the number of blocks read per batch.
+    pub fn with_batch_size(mut self, batch_size: u64) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Reads the next `count` blocks.
+    fn read_blocks(&mut self, count: u64) -> io::Result<Vec<Block>> {
+        let headers = self.headers.read_items(self.next, count)?;
+        let bodies = self.bodies.read_items(self.next, count)?;
+        headers
+            .into_iter()
+            .zip(bodies)
+            .map(|(header, body)| {
+                let header = decode::<Header>(&header, "header")?;
+                let body = decode::<BlockBody>(&body, "body")?;
+                Ok(Block {
+                    header,
+                    body: body.transactions,
+                    ommers: body.ommers,
+                    withdrawals: body.withdrawals,
+                    requests: body.requests,
+                })
+            })
+            .collect()
+    }
+}
+
+impl BlockSource for GethFreezerReader {
+    type Error = FileClientError;
+
+    async fn next_batch(&mut self) -> Result<Option<FileClient>, Self::Error> {
+        let end = (self.next + self.batch_size).min(self.headers.items).min(self.bodies.items);
+        if self.next >= end {
+            return Ok(None)
+        }
+
+        let blocks = self.read_blocks(end - self.next)?;
+        self.next = end;
+        Ok(Some(FileClient::from_blocks(blocks)))
+    }
+}
+
+/// Returns the freezer directory of the chain at or below `path`, if any.
+pub(crate) fn find_freezer(path: &Path) -> Option<PathBuf> {
+    FREEZER_DIRS
+        .iter()
+        .map(|dir| path.join(dir))
+        .find(|dir| dir.join("headers.cidx").is_file() || dir.join("headers.ridx").is_file())
+}
+
+/// A table of a geth freezer.
+///
+/// The items of a table are stored one after another in data files, and located through an index
+/// file of [`INDEX_ENTRY_LEN`] byte entries, each holding the data file and the end offset of an
+/// item. An item that doesn't fit into the rest of a data file starts at the beginning of the
+/// next one. The first entry is special: it holds the first data file and the number of items
+/// deleted from the tail of the table.
+#[derive(Debug)]
+struct FreezerTable {
+    dir: PathBuf,
+    name: &'static str,
+    /// Whether the items are snappy compressed.
+    compressed: bool,
+    index: File,
+    /// The number of the first item.
+    tail: u64,
+    /// The number of items, including the deleted ones.
+    items: u64,
+}
+
+impl FreezerTable {
+    fn open(dir: &Path, name: &'static str) -> io::Result<Self> {
+        let compressed = dir.join(format!("{name}.cidx")).is_file();
+        let index_path = dir.join(format!("{name}.{}", if compressed { "cidx" } else { "ridx" }));
+        let mut index = File::open(&index_path)?;
+
+        let entries = index.metadata()?.len() / INDEX_ENTRY_LEN;
+        if entries == 0 {
+            return Err(invalid(format!("empty index {}", index_path.display())))
+        }
+        let (_, tail) = read_entries(&mut index, 0, 1)?[0];
+        let tail = tail as u64;
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            name,
+            compressed,
+            index,
+            tail,
+            items: tail + entries - 1,
+        })
+    }
+
+    /// Reads `count` items, starting at item `start`.
+    fn read_items(&mut self, start: u64, count: u64) -> io::Result<Vec<Vec<u8>>> {
+        let position = start - self.tail;
+        let entries = read_entries(&mut self.index, position, count + 1)?;
+
+        let mut items = Vec::with_capacity(count as usize);
+        let mut data: Option<(u16, File)> = None;
+        for (index, pair) in entries.windows(2).enumerate() {
+            let ((start_file, start_offset), (file_number, end_offset)) = (pair[0], pair[1]);
+            // the offset of the first entry is the number of deleted items
+            let start_offset = if (position == 0 && index == 0) || start_file != file_number {
+                0
+            } else {
+                start_offset
+            };
+
+            if data.as_ref().map(|(number, _)| *number) != Some(file_number) {
+                data = Some((file_number, File::open(self.data_path(file_number))?));
+            }
+            let (_, file) = data.as_mut().expect("opened");
+            let mut item = vec![0; end_offset.saturating_sub(start_offset) as usize];
+            file.seek(SeekFrom::Start(start_offset as u64))?;
+            file.read_exact(&mut item)?;
+
+            if self.compressed {
+                item = snap::raw::Decoder::new()
+                    .decompress_vec(&item)
+                    .map_err(|err| invalid(format!("invalid {} item: {err}", self.name)))?;
+            }
+            items.push(item);
+        }
+        Ok(items)
+    }
+
+    fn data_path(&self, file_number: u16) -> PathBuf {
+        let extension = if self.compressed { "cdat" } else { "rdat" };
+        self.dir.join(format!("{}.{file_number:04}.{extension}", self.name))
+    }
+}
+
+/// Reads `count` entries of an index, starting at entry `start`.
+fn read_entries(index: &mut File, start: u64, count: u64) -> io::Result<Vec<(u16, u32)>> {
+    let mut buf = vec![0; (count * INDEX_ENTRY_LEN) as usize];
+    index.seek(SeekFrom::Start(start * INDEX_ENTRY_LEN))?;
+    index.read_exact(&mut buf)?;
+    Ok(buf
+        .chunks_exact(INDEX_ENTRY_LEN as usize)
+        .map(|entry| {
+            (
+                u16::from_be_bytes([entry[0], entry[1]]),
+                u32::from_be_bytes([entry[2], entry[3], entry[4], entry[5]]),
+            )
+        })
+        .collect())
+}
+
+fn decode<T: Decodable>(mut rlp: &[u8], name: &str) -> io::Result<T> {
+    T::decode(&mut rlp).map_err(|err| invalid(format!("invalid {name}: {err}")))
+}
+
+fn invalid(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::generate_bodies;
+    use alloy_rlp::Encodable;
+
+    /// Writes a compressed table with the given items, starting a new data file every
+    /// `items_per_file` items.
+    fn write_table(dir: &Path, name: &str, items: &[Vec<u8>], items_per_file: usize) {
+        let mut index = vec![0, 0, 0, 0, 0, 0];
+        for (file_number, file_items) in items.chunks(items_per_file).enumerate() {
+            let mut data = Vec::new();
+            for item in file_items {
+                data.extend(snap::raw::Encoder::new().compress_vec(item).unwrap());
+                index.extend((file_number as u16).to_be_bytes());
+                index.extend((data.len() as u32).to_be_bytes());
+            }
+            std::fs::write(dir.join(format!("{name}.{file_number:04}.cdat")), data).unwrap();
+        }
+        std::fs::write(dir.join(format!("{name}.cidx")), index).unwrap();
+    }
+
+    #[tokio::test]
+    async fn reads_freezer_blocks() {
+        let (headers, bodies) = generate_bodies(0..=9);
+        let datadir = tempfile::tempdir().unwrap();
+        let dir = datadir.path().join("geth/chaindata/ancient/chain");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let encode = |value: &dyn Encodable| {
+            let mut buf = Vec::new();
+            value.encode(&mut buf);
+            buf
+        };
+        let header_items = headers.iter().map(|header| encode(header.header())).collect::<Vec<_>>();
+        let body_items =
+            headers.iter().map(|header| encode(&bodies[&header.hash()])).collect::<Vec<_>>();
+        write_table(&dir, "headers", &header_items, 3);
+        write_table(&dir, "bodies", &body_items, 4);
+
+        let mut reader = GethFreezerReader::open(datadir.path()).unwrap().with_batch_size(4);
+        let mut read = Vec::new();
+        while let Some(client) = reader.next_batch().await.unwrap() {
+            assert!(client.headers_len() <= 4);
+            let mut numbers =
+                client.headers_iter().map(|header| header.number()).collect::<Vec<_>>();
+            numbers.sort_unstable();
+            read.extend(numbers);
+        }
+        assert_eq!(read, (0..=9).collect::<Vec<_>>());
+
+        let blocks = GethFreezerReader::open(&dir).unwrap().read_blocks(10).unwrap();
+        for (block, header) in blocks.iter().zip(&headers) {
+            assert_eq!(&block.header, header.header());
+            assert_eq!(block.body, bodies[&header.hash()].transactions);
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use crate::{
+    block_source::BlockSource,
+    era1::{era1_files, Era1Reader, VERSION_ENTRY},
+    file_client::{header_number, ChunkedFileReader, FileClient, FileClientError},
+    geth_freezer::{find_freezer, GethFreezerReader},
+};
+use reth_primitives::BlockNumber;
+use std::{
+    fmt,
+    io::{self, Read},
+    path::Path,
+    str::FromStr,
+};
+
+/// Number of bytes read from the start of a file to detect its format, enough for the first
+/// header up to its block number.
+const SNIFF_LEN: u64 = 1024;
+
+/// A format of block files that can be imported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    /// RLP encoded blocks, one after another, as exported by `geth export`.
+    Rlp,
+    /// RLP encoded OVM blocks of the OP Mainnet chain segment below Bedrock, as exported by
+    /// op-geth.
+    OpLegacy,
+    /// Era1 archives of the pre-merge chain, or a directory of them.
+    Era1,
+    /// The freezer of a geth datadir.
+    GethFreezer,
+}
+
+impl ImportFormat {
+    /// Detects the format of the file or directory at `path`.
+    ///
+    /// Era1 archives start with an e2store version entry, and geth freezers are found by their
+    /// `headers` table. Any other file starting with an RLP list is an RLP block file. OVM block
+    /// files are encoded the same way, so they're told apart by their first block: if it's below
+    /// `legacy_below`, the Bedrock block of the chain, the file is [`ImportFormat::OpLegacy`].
+    pub fn detect(path: &Path, legacy_below: Option<BlockNumber>) -> io::Result<Self> {
+        if path.is_dir() {
+            if find_freezer(path).is_some() {
+                return Ok(Self::GethFreezer)
+            }
+            if !era1_files(path)?.is_empty() {
+                return Ok(Self::Era1)
+            }
+            return Err(unknown(path))
+        }
+
+        let mut buf = Vec::new();
+        std::fs::File::open(path)?.take(SNIFF_LEN).read_to_end(&mut buf)?;
+
+        if buf.starts_with(&VERSION_ENTRY) && buf.get(2..8) == Some(&[0; 6][..]) {
+            return Ok(Self::Era1)
+        }
+
+        let mut rest = &buf[..];
+        match alloy_rlp::Header::decode(&mut rest) {
+            Ok(block) if block.list => {
+                let first_block = header_number(rest).ok();
+                match (first_block, legacy_below) {
+                    (Some(first_block), Some(bedrock)) if first_block < bedrock => {
+                        Ok(Self::OpLegacy)
+                    }
+                    _ => Ok(Self::Rlp),
+                }
+            }
+            _ => Err(unknown(path)),
+        }
+    }
+}
+
+fn unknown(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!(
+            "unknown import format of {}, expected RLP blocks, era1 or a geth freezer",
+            path.display()
+        ),
+    )
+}
+
+impl fmt::Display for ImportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rlp => f.write_str("rlp"),
+            Self::OpLegacy => f.write_str("op-legacy"),
+            Self::Era1 => f.write_str("era1"),
+            Self::GethFreezer => f.write_str("geth-freezer"),
+        }
+    }
+}
+
+impl FromStr for ImportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rlp" => Ok(Self::Rlp),
+            "op-legacy" => Ok(Self::OpLegacy),
+            "era1" => Ok(Self::Era1),
+            "geth-freezer" => Ok(Self::GethFreezer),
+            _ => Err(format!(
+                "unknown import format {s}, expected one of rlp, op-legacy, era1, geth-freezer"
+            )),
+        }
+    }
+}
+
+/// A [`BlockSource`] reading blocks in any of the [`ImportFormat`]s.
+#[derive(Debug)]
+pub enum ImportSource {
+    /// An RLP block file, read in chunks.
+    Rlp(ChunkedFileReader),
+    /// Era1 archives.
+    Era1(Era1Reader),
+    /// A geth freezer.
+    GethFreezer(GethFreezerReader),
+}
+
+impl ImportSource {
+    /// Opens the file or directory at `path` for reading blocks in the given format.
+    ///
+    /// RLP block files are read in chunks of `chunk_byte_len` bytes, see [`ChunkedFileReader`].
+    pub async fn open(
+        format: ImportFormat,
+        path: &Path,
+        chunk_byte_len: Option<u64>,
+    ) -> Result<Self, FileClientError> {
+        Ok(match format {
+            ImportFormat::Rlp | ImportFormat::OpLegacy => {
+                Self::Rlp(ChunkedFileReader::new(path, chunk_byte_len).await?)
+            }
+            ImportFormat::Era1 => Self::Era1(Era1Reader::open(path)?),
+            ImportFormat::GethFreezer => Self::GethFreezer(GethFreezerReader::open(path)?),
+        })
+    }
+}
+
+impl BlockSource for ImportSource {
+    type Error = FileClientError;
+
+    async fn next_batch(&mut self) -> Result<Option<FileClient>, Self::Error> {
+        match self {
+            Self::Rlp(reader) => reader.next_blocks().await,
+            Self::Era1(reader) => reader.next_batch().await,
+            Self::GethFreezer(reader) => reader.next_batch().await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::generate_bodies_file;
+    use tokio::io::AsyncReadExt;
+
+    #[tokio::test]
+    async fn detects_format() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let (mut file, _, _) = generate_bodies_file(5..=9).await;
+        let mut rlp = Vec::new();
+        file.read_to_end(&mut rlp).await.unwrap();
+        let rlp_path = dir.path().join("blocks.rlp");
+        std::fs::write(&rlp_path, rlp).unwrap();
+        assert_eq!(ImportFormat::detect(&rlp_path, None).unwrap(), ImportFormat::Rlp);
+        assert_eq!(ImportFormat::detect(&rlp_path, Some(5)).unwrap(), ImportFormat::Rlp);
+        assert_eq!(ImportFormat::detect(&rlp_path, Some(6)).unwrap(), ImportFormat::OpLegacy);
+
+        let era1_path = dir.path().join("mainnet-00000-5ec1ffb8.era1");
+        std::fs::write(&era1_path, [0x65, 0x32, 0, 0, 0, 0, 0, 0]).unwrap();
+        assert_eq!(ImportFormat::detect(&era1_path, None).unwrap(), ImportFormat::Era1);
+        assert_eq!(ImportFormat::detect(dir.path(), None).unwrap(), ImportFormat::Era1);
+
+        std::fs::create_dir_all(dir.path().join("ancient/chain")).unwrap();
+        std::fs::write(dir.path().join("ancient/chain/headers.cidx"), [0; 6]).unwrap();
+        assert_eq!(ImportFormat::detect(dir.path(), None).unwrap(), ImportFormat::GethFreezer);
+
+        let unknown_path = dir.path().join("blocks.txt");
+        std::fs::write(&unknown_path, "0x1234").unwrap();
+        assert!(ImportFormat::detect(&unknown_path, None).is_err());
+
+        assert_eq!("geth-freezer".parse::<ImportFormat>().unwrap(), ImportFormat::GethFreezer);
+        assert_eq!(ImportFormat::OpLegacy.to_string(), "op-legacy");
+    }
+}
@@ -27,6 +27,20 @@ pub mod metrics;
 /// efficiently buffering headers and bodies for retrieval.
 pub mod file_client;
 
+/// Module with the [`BlockSource`](block_source::BlockSource) trait, the interface of the sources
+/// of blocks that can be imported by the pipeline.
+pub mod block_source;
+
+/// Module with a reader of era1 archives, the e2store archives of the pre-merge chain.
+pub mod era1;
+
+/// Module with a reader of the blocks of a geth freezer, the `ancient` database of a geth datadir.
+pub mod geth_freezer;
+
+/// Module detecting the format of a file to import, see
+/// [`ImportFormat`](import_format::ImportFormat), and reading blocks in any of the formats.
+pub mod import_format;
+
 /// Module managing file-based data retrieval and buffering of receipts.
 ///
 /// Contains [`ReceiptFileClient`](receipt_file_client::ReceiptFileClient) to read receipt data from
@@ -51,5 +65,12 @@ pub mod file_codec;
 /// Enables decoding and encoding `HackReceipt` type. See <https://github.com/testinprod-io/op-geth/pull/1>.
 pub mod file_codec_ovm_receipt;
 
+/// Module with a codec for reading the transaction metadata of the OP Mainnet legacy chain from a
+/// file.
+///
+/// Enables decoding [`TxMetaEntry`](file_codec_ovm_tx_meta::TxMetaEntry) types, the size and
+/// metadata l2geth stored next to each transaction, which are not part of the exported blocks.
+pub mod file_codec_ovm_tx_meta;
+
 #[cfg(any(test, feature = "test-utils"))]
 pub mod test_utils;
@@ -19,6 +19,8 @@ pub struct ReceiptFileClient<D> {
     pub first_block: u64,
     /// Total number of receipts. Count of elements in [`Receipts`] flattened.
     pub total_receipts: usize,
+    /// The L1 fees of the receipts, if the file records them. Nested like the receipts.
+    pub l1_fees: Vec<Vec<Option<ReceiptL1Fee>>>,
     /// marker
     _marker: PhantomData<D>,
 }
@@ -84,6 +86,7 @@ where
         B: AsyncReadExt + Unpin,
     {
         let mut receipts = Receipts::default();
+        let mut l1_fees = vec![];
 
         // use with_capacity to make sure the internal buffer contains the entire chunk
         let mut stream = FramedRead::with_capacity(reader, decoder, num_bytes as usize);
@@ -103,6 +106,7 @@ where
         let mut block_number = 0;
         let mut total_receipts = 0;
         let mut receipts_for_block = vec![];
+        let mut l1_fees_for_block = vec![];
         let mut first_block = None;
 
         async move {
@@ -124,7 +128,7 @@ where
                 };
 
                 match receipt {
-                    Some(ReceiptWithBlockNumber { receipt, number }) => {
+                    Some(ReceiptWithBlockNumber { receipt, number, l1_fee }) => {
                         total_receipts += 1;
 
                         if first_block.is_none() {
@@ -134,12 +138,15 @@ where
 
                         if block_number == number {
                             receipts_for_block.push(Some(receipt));
+                            l1_fees_for_block.push(l1_fee);
                         } else {
                             receipts.push(receipts_for_block);
+                            l1_fees.push(l1_fees_for_block);
 
                             // next block
                             block_number = number;
                             receipts_for_block = vec![Some(receipt)];
+                            l1_fees_for_block = vec![l1_fee];
                         }
                     }
                     None => {
@@ -148,6 +155,7 @@ where
                                 // if there was a block number before this, push receipts for that
                                 // block
                                 receipts.push(receipts_for_block);
+                                l1_fees.push(l1_fees_for_block);
                                 // block with no txns
                                 block_number = num + receipts.len() as u64;
                             }
@@ -160,6 +168,7 @@ where
                         }
 
                         receipts_for_block = vec![];
+                        l1_fees_for_block = vec![];
                     }
                 }
 
@@ -189,6 +198,7 @@ where
 
             // we need to push the last receipts
             receipts.push(receipts_for_block);
+            l1_fees.push(l1_fees_for_block);
 
             trace!(target: "downloaders::file",
                 blocks = receipts.len(),
@@ -201,6 +211,7 @@ where
                     receipts,
                     first_block: first_block.unwrap_or_default(),
                     total_receipts,
+                    l1_fees,
                     _marker: Default::default(),
                 },
                 remaining_bytes,
@@ -216,6 +227,21 @@ pub struct ReceiptWithBlockNumber {
     pub receipt: Receipt,
     /// Block number.
     pub number: u64,
+    /// The L1 fee of the receipt, if the file records it.
+    pub l1_fee: Option<ReceiptL1Fee>,
+}
+
+/// The L1 fee of a receipt of the OP Mainnet legacy chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReceiptL1Fee {
+    /// The L1 gas price the fee was charged at.
+    pub l1_gas_price: u64,
+    /// The L1 gas used by the transaction.
+    pub l1_gas_used: u64,
+    /// The L1 fee paid by the transaction.
+    pub l1_fee: u64,
+    /// The dynamic overhead the fee was scaled by, in millionths.
+    pub fee_scalar: u64,
 }
 
 #[cfg(test)]
@@ -255,12 +281,10 @@ mod test {
         let encoded_byte_len = encoded_receipts.len() as u64;
         let reader = &mut &encoded_receipts[..];
 
-        let (
-            ReceiptFileClient { receipts, first_block, total_receipts, _marker },
-            _remaining_bytes,
-        ) = ReceiptFileClient::<HackReceiptFileCodec>::from_reader(reader, encoded_byte_len)
-            .await
-            .unwrap();
+        let (ReceiptFileClient { receipts, first_block, total_receipts, .. }, _remaining_bytes) =
+            ReceiptFileClient::<HackReceiptFileCodec>::from_reader(reader, encoded_byte_len)
+                .await
+                .unwrap();
 
         // 2 non-empty receipt objects
         assert_eq!(2, total_receipts);
@@ -287,12 +311,10 @@ mod test {
         let encoded_byte_len = encoded_receipts.len() as u64;
         let reader = &mut &encoded_receipts[..];
 
-        let (
-            ReceiptFileClient { receipts, first_block, total_receipts, _marker },
-            _remaining_bytes,
-        ) = ReceiptFileClient::<HackReceiptFileCodec>::from_reader(reader, encoded_byte_len)
-            .await
-            .unwrap();
+        let (ReceiptFileClient { receipts, first_block, total_receipts, .. }, _remaining_bytes) =
+            ReceiptFileClient::<HackReceiptFileCodec>::from_reader(reader, encoded_byte_len)
+                .await
+                .unwrap();
 
         // 2 non-empty receipt objects
         assert_eq!(2, total_receipts);
@@ -321,7 +343,7 @@ mod test {
         let reader = &mut &encoded_receipts[..];
 
         let (
-            ReceiptFileClient { receipts, first_block, total_receipts, _marker },
+            ReceiptFileClient { receipts, first_block, total_receipts, l1_fees, .. },
             _remaining_bytes,
         ) = ReceiptFileClient::<HackReceiptFileCodec>::from_reader(reader, encoded_byte_len)
             .await
@@ -335,5 +357,8 @@ mod test {
         assert_eq!(op_mainnet_receipt_block_2().receipt, receipts[2][0].clone().unwrap());
         assert_eq!(op_mainnet_receipt_block_2().receipt, receipts[2][1].clone().unwrap());
         assert_eq!(op_mainnet_receipt_block_3().receipt, receipts[3][0].clone().unwrap());
+        assert!(l1_fees[0].is_empty());
+        assert_eq!(op_mainnet_receipt_block_2().l1_fee, l1_fees[2][1]);
+        assert_eq!(2, l1_fees[2].len());
     }
 }
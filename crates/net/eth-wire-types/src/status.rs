@@ -1,7 +1,7 @@
 use crate::EthVersion;
 use alloy_genesis::Genesis;
 use alloy_rlp::{RlpDecodable, RlpEncodable};
-use reth_chainspec::{Chain, ChainSpec, NamedChain, MAINNET};
+use reth_chainspec::{Chain, ChainSpec, GenesisConfigError, NamedChain, MAINNET};
 use reth_codecs_derive::derive_arbitrary;
 use reth_primitives::{hex, ForkId, Hardfork, Head, B256, U256};
 #[cfg(feature = "serde")]
@@ -42,20 +42,22 @@ pub struct Status {
     pub forkid: ForkId,
 }
 
-impl From<Genesis> for Status {
-    fn from(genesis: Genesis) -> Self {
+impl TryFrom<Genesis> for Status {
+    type Error = GenesisConfigError;
+
+    fn try_from(genesis: Genesis) -> Result<Self, Self::Error> {
         let chain = genesis.config.chain_id;
         let total_difficulty = genesis.difficulty;
-        let chainspec = ChainSpec::from(genesis);
+        let chainspec = ChainSpec::try_from(genesis)?;
 
-        Self {
+        Ok(Self {
             version: EthVersion::Eth68 as u8,
             chain: Chain::from_id(chain),
             total_difficulty,
             blockhash: chainspec.genesis_hash(),
             genesis: chainspec.genesis_hash(),
             forkid: chainspec.fork_id(&Head::default()),
-        }
+        })
     }
 }
 
@@ -147,7 +147,7 @@ pub use peers::PeersConfig;
 pub use session::{
     ActiveSessionHandle, ActiveSessionMessage, Direction, PeerInfo, PendingSessionEvent,
     PendingSessionHandle, PendingSessionHandshakeError, SessionCommand, SessionEvent, SessionId,
-    SessionLimits, SessionManager, SessionsConfig,
+    SessionLimits, SessionManager, SessionsConfig, Socks5Proxy,
 };
 pub use transactions::{FilterAnnouncement, MessageFilter, ValidateTx68};
 
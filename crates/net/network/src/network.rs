@@ -13,7 +13,7 @@ use reth_network_api::{
 };
 use reth_network_p2p::sync::{NetworkSyncUpdater, SyncState, SyncStateProvider};
 use reth_network_peers::{NodeRecord, PeerId};
-use reth_primitives::{Head, TransactionSigned, B256};
+use reth_primitives::{ForkFilter, Head, TransactionSigned, B256};
 use reth_tokio_util::{EventSender, EventStream};
 use secp256k1::SecretKey;
 use std::{
@@ -114,6 +114,14 @@ impl NetworkHandle {
         self.send_message(NetworkHandleMessage::StatusUpdate { head });
     }
 
+    /// Replaces the fork filter of the node after the hardfork schedule of the chain changed.
+    ///
+    /// The [`ForkId`](reth_primitives::ForkId) of the filter is announced to new peers and over
+    /// discovery.
+    pub fn update_fork_filter(&self, fork_filter: ForkFilter) {
+        self.send_message(NetworkHandleMessage::ForkFilterUpdate { fork_filter });
+    }
+
     /// Announce a block over devp2p
     ///
     /// Caution: in `PoS` this is a noop because new blocks are no longer announced over devp2p.
@@ -457,6 +465,11 @@ pub(crate) enum NetworkHandleMessage {
         /// The head status to apply.
         head: Head,
     },
+    /// Replaces the fork filter after the hardfork schedule changed.
+    ForkFilterUpdate {
+        /// The fork filter of the updated hardfork schedule.
+        fork_filter: ForkFilter,
+    },
     /// Retrieves the current status via a oneshot sender.
     GetStatus(oneshot::Sender<NetworkStatus>),
     /// Gets `PeerInfo` for the specified peer IDs.
@@ -2,7 +2,7 @@
 
 use crate::{
     peers::{DEFAULT_MAX_COUNT_PEERS_INBOUND, DEFAULT_MAX_COUNT_PEERS_OUTBOUND},
-    session::{Direction, ExceedsSessionLimit},
+    session::{Direction, ExceedsSessionLimit, Socks5Proxy},
 };
 use std::time::Duration;
 
@@ -53,6 +53,10 @@ pub struct SessionsConfig {
     pub protocol_breach_request_timeout: Duration,
     /// The timeout after which a pending session attempt is considered failed.
     pub pending_session_timeout: Duration,
+    /// SOCKS5 proxy that outbound `RLPx` connections are routed through, if any.
+    ///
+    /// Inbound connections and discovery are not affected.
+    pub proxy: Option<Socks5Proxy>,
 }
 
 impl Default for SessionsConfig {
@@ -72,6 +76,7 @@ impl Default for SessionsConfig {
             initial_internal_request_timeout: INITIAL_REQUEST_TIMEOUT,
             protocol_breach_request_timeout: PROTOCOL_BREACH_REQUEST_TIMEOUT,
             pending_session_timeout: PENDING_SESSION_TIMEOUT,
+            proxy: None,
         }
     }
 }
@@ -103,6 +108,12 @@ impl SessionsConfig {
         }
         self
     }
+
+    /// Routes all outbound session dials through the given SOCKS5 proxy.
+    pub fn with_proxy(mut self, proxy: Option<Socks5Proxy>) -> Self {
+        self.proxy = proxy;
+        self
+    }
 }
 
 /// Limits for sessions.
@@ -40,6 +40,7 @@ mod active;
 mod config;
 mod conn;
 mod handle;
+mod proxy;
 pub use crate::message::PeerRequestSender;
 use crate::protocol::{IntoRlpxSubProtocol, RlpxSubProtocolHandlers, RlpxSubProtocols};
 pub use config::{SessionLimits, SessionsConfig};
@@ -47,6 +48,7 @@ pub use handle::{
     ActiveSessionHandle, ActiveSessionMessage, PendingSessionEvent, PendingSessionHandle,
     SessionCommand,
 };
+pub use proxy::{ParseProxyError, Socks5Credentials, Socks5Proxy};
 use reth_eth_wire::multiplex::RlpxProtocolMultiplexer;
 pub use reth_network_api::{Direction, PeerInfo};
 
@@ -70,6 +72,8 @@ pub struct SessionManager {
     protocol_breach_request_timeout: Duration,
     /// The timeout after which a pending session attempt is considered failed.
     pending_session_timeout: Duration,
+    /// SOCKS5 proxy used for outbound dials, if configured.
+    proxy: Option<Socks5Proxy>,
     /// The secret key used for authenticating sessions.
     secret_key: SecretKey,
     /// The `Status` message to send to peers.
@@ -133,6 +137,7 @@ impl SessionManager {
             initial_internal_request_timeout: config.initial_internal_request_timeout,
             protocol_breach_request_timeout: config.protocol_breach_request_timeout,
             pending_session_timeout: config.pending_session_timeout,
+            proxy: config.proxy,
             secret_key,
             status,
             hello_message,
@@ -210,6 +215,16 @@ impl SessionManager {
         transition
     }
 
+    /// Invoked when the hardfork schedule of the chain changed.
+    ///
+    /// Replaces the [`ForkFilter`] that the `Status` of new sessions is validated against and
+    /// returns the active [`ForkId`] of the new filter. Established sessions are kept.
+    pub(crate) fn on_fork_filter_update(&mut self, fork_filter: ForkFilter) -> ForkId {
+        self.fork_filter = fork_filter;
+        self.status.forkid = self.fork_filter.current();
+        self.status.forkid
+    }
+
     /// An incoming TCP connection was received. This starts the authentication process to turn this
     /// stream into an active peer session.
     ///
@@ -278,6 +293,7 @@ impl SessionManager {
             let fork_filter = self.fork_filter.clone();
             let status = self.status;
             let extra_handlers = self.extra_protocols.on_outgoing(remote_addr, remote_peer_id);
+            let proxy = self.proxy.clone();
             self.spawn(pending_session_with_timeout(
                 self.pending_session_timeout,
                 session_id,
@@ -295,6 +311,7 @@ impl SessionManager {
                     status,
                     fork_filter,
                     extra_handlers,
+                    proxy,
                 ),
             ));
 
@@ -825,8 +842,13 @@ async fn start_pending_outbound_session(
     status: Status,
     fork_filter: ForkFilter,
     extra_handlers: RlpxSubProtocolHandlers,
+    proxy: Option<Socks5Proxy>,
 ) {
-    let stream = match TcpStream::connect(remote_addr).await {
+    let connect = match &proxy {
+        Some(proxy) => Either::Left(proxy.connect(remote_addr)),
+        None => Either::Right(TcpStream::connect(remote_addr)),
+    };
+    let stream = match connect.await {
         Ok(stream) => {
             if let Err(err) = stream.set_nodelay(true) {
                 tracing::warn!(target: "net::session", "set nodelay failed: {:?}", err);
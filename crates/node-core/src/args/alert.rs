@@ -0,0 +1,104 @@
+//! Health alert arguments
+
+use clap::Args;
+use std::time::Duration;
+use url::Url;
+
+/// Parameters for alerting on node health events
+#[derive(Debug, Clone, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Alerts")]
+pub struct AlertArgs {
+    /// Webhook that alerts are POSTed to as JSON. Can be set multiple times.
+    ///
+    /// Every alert is sent once when its condition starts, with `"status": "triggered"`, and once
+    /// when it ends, with `"status": "resolved"`.
+    #[arg(long = "alert.webhook", value_name = "URL")]
+    pub webhooks: Vec<Url>,
+
+    /// Routing key of a PagerDuty service that alerts are sent to, using the Events API v2.
+    #[arg(long = "alert.pagerduty-key", value_name = "KEY")]
+    pub pagerduty_routing_key: Option<String>,
+
+    /// Alert when no new canonical block was imported and the sync made no progress for this
+    /// many minutes.
+    #[arg(long = "alert.stall-timeout", value_name = "MINUTES", default_value_t = 10)]
+    pub stall_timeout: u64,
+
+    /// Alert when the node had no connected peers for this many minutes.
+    #[arg(long = "alert.no-peers-timeout", value_name = "MINUTES", default_value_t = 5)]
+    pub no_peers_timeout: u64,
+
+    /// Alert when the free disk space of the datadir drops below this many GB. Set to 0 to
+    /// disable.
+    #[arg(long = "alert.min-free-space", value_name = "GB", default_value_t = 50)]
+    pub min_free_space: u64,
+}
+
+impl AlertArgs {
+    /// Returns true if any alert destination is configured.
+    pub fn is_enabled(&self) -> bool {
+        !self.webhooks.is_empty() || self.pagerduty_routing_key.is_some()
+    }
+
+    /// Returns the period without progress after which the node is considered stalled.
+    pub const fn stall_timeout(&self) -> Duration {
+        Duration::from_secs(self.stall_timeout * 60)
+    }
+
+    /// Returns the period without connected peers after which an alert is sent.
+    pub const fn no_peers_timeout(&self) -> Duration {
+        Duration::from_secs(self.no_peers_timeout * 60)
+    }
+
+    /// Returns the free disk space threshold in bytes.
+    pub const fn min_free_space_bytes(&self) -> u64 {
+        self.min_free_space * 1024 * 1024 * 1024
+    }
+}
+
+impl Default for AlertArgs {
+    fn default() -> Self {
+        Self {
+            webhooks: Vec::new(),
+            pagerduty_routing_key: None,
+            stall_timeout: 10,
+            no_peers_timeout: 5,
+            min_free_space: 50,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn alert_args_default_sanity_test() {
+        let default_args = AlertArgs::default();
+        let args = CommandParser::<AlertArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, default_args);
+        assert!(!args.is_enabled());
+
+        let args = CommandParser::<AlertArgs>::parse_from([
+            "reth",
+            "--alert.webhook",
+            "https://example.com/hook",
+            "--alert.webhook",
+            "http://localhost:8080/alerts",
+            "--alert.stall-timeout",
+            "30",
+        ])
+        .args;
+        assert!(args.is_enabled());
+        assert_eq!(args.webhooks.len(), 2);
+        assert_eq!(args.stall_timeout(), Duration::from_secs(30 * 60));
+    }
+}
@@ -19,6 +19,18 @@ pub struct DatabaseArgs {
     /// NFS volume.
     #[arg(long = "db.exclusive")]
     pub exclusive: Option<bool>,
+    /// Record the latencies of reads, seeks and writes per table in the database metrics.
+    ///
+    /// Every database operation is timed, which slows down the node.
+    #[arg(long = "db.latency-metrics")]
+    pub latency_metrics: bool,
+    /// Read ahead when loading database pages, which speeds up linear scans but evicts other
+    /// pages from the page cache.
+    ///
+    /// Disabled by default, unless the database fits into half of the detected memory limit of
+    /// the container.
+    #[arg(long = "db.readahead")]
+    pub readahead: Option<bool>,
 }
 
 impl DatabaseArgs {
@@ -27,6 +39,8 @@ impl DatabaseArgs {
         reth_db::mdbx::DatabaseArguments::new(default_client_version())
             .with_log_level(self.log_level)
             .with_exclusive(self.exclusive)
+            .with_latency_metrics(self.latency_metrics)
+            .with_readahead(self.readahead)
     }
 }
 
@@ -137,4 +151,20 @@ mod tests {
         let cmd = CommandParser::<DatabaseArgs>::try_parse_from(["reth"]).unwrap();
         assert_eq!(cmd.args.log_level, None);
     }
+
+    #[test]
+    fn test_command_parser_with_latency_metrics() {
+        let cmd = CommandParser::<DatabaseArgs>::try_parse_from(["reth", "--db.latency-metrics"])
+            .unwrap();
+        assert!(cmd.args.latency_metrics);
+    }
+
+    #[test]
+    fn test_command_parser_with_readahead() {
+        let cmd = CommandParser::<DatabaseArgs>::try_parse_from(["reth"]).unwrap();
+        assert_eq!(cmd.args.readahead, None);
+        let cmd = CommandParser::<DatabaseArgs>::try_parse_from(["reth", "--db.readahead", "true"])
+            .unwrap();
+        assert_eq!(cmd.args.readahead, Some(true));
+    }
 }
@@ -14,7 +14,7 @@ pub struct DatadirArgs {
     /// Defaults to the OS-specific data directory:
     ///
     /// - Linux: `$XDG_DATA_HOME/reth/` or `$HOME/.local/share/reth/`
-    /// - Windows: `{FOLDERID_RoamingAppData}/reth/`
+    /// - Windows: `{FOLDERID_LocalAppData}/reth/`
     /// - macOS: `$HOME/Library/Application Support/reth/`
     #[arg(long, value_name = "DATA_DIR", verbatim_doc_comment, default_value_t)]
     pub datadir: MaybePlatformPath<DataDirPath>,
@@ -22,6 +22,23 @@ pub struct DatadirArgs {
     /// The absolute path to store static files in.
     #[arg(long = "datadir.static_files", verbatim_doc_comment, value_name = "PATH")]
     pub static_files_path: Option<PathBuf>,
+
+    /// Additional read-only directories of static files, e.g. an archive of old history on a
+    /// network mount shared by several nodes.
+    ///
+    /// They are consulted for the static files below the lowest static file of the node, and a
+    /// static file is read from the first directory that contains it.
+    #[arg(long = "datadir.static-files-read-only", value_name = "PATH", value_delimiter = ',')]
+    pub read_only_static_files: Vec<PathBuf>,
+
+    /// Use a subdirectory of `--datadir` for the chain, e.g. `<DATA_DIR>/mainnet`, so several
+    /// chains can share one data dir.
+    ///
+    /// This is the layout of the default data dir. A custom data dir without a `db` directory is
+    /// also resolved to the subdirectory of the chain if it exists. Without this flag, a database
+    /// in the data dir that belongs to another chain is refused.
+    #[arg(long = "datadir.per-chain")]
+    pub per_chain: bool,
 }
 
 impl DatadirArgs {
@@ -50,4 +67,21 @@ mod tests {
         let args = CommandParser::<DatadirArgs>::parse_from(["reth"]).args;
         assert_eq!(args, default_args);
     }
+
+    #[test]
+    fn test_parse_read_only_static_files() {
+        let args = CommandParser::<DatadirArgs>::parse_from([
+            "reth",
+            "--datadir.static-files-read-only",
+            "/mnt/archive/static_files,/mnt/backup/static_files",
+        ])
+        .args;
+        assert_eq!(
+            args.read_only_static_files,
+            vec![
+                PathBuf::from("/mnt/archive/static_files"),
+                PathBuf::from("/mnt/backup/static_files")
+            ]
+        );
+    }
 }
@@ -14,7 +14,8 @@ pub struct DebugArgs {
 
     /// Set the chain tip manually for testing purposes.
     ///
-    /// NOTE: This is a temporary flag
+    /// The pipeline syncs to the block with the given hash without a consensus client, and the
+    /// node exits once it's reached, as with `--debug.terminate`.
     #[arg(long = "debug.tip", help_heading = "Debug")]
     pub tip: Option<B256>,
 
@@ -42,6 +43,20 @@ pub struct DebugArgs {
     )]
     pub rpc_consensus_ws: Option<String>,
 
+    /// Syncs from the RPC endpoint of another node instead of from peers.
+    ///
+    /// Headers and bodies are downloaded over RPC and a fake consensus client follows the new
+    /// blocks of the endpoint. HTTP, `WebSocket` and IPC endpoints are supported.
+    #[arg(
+        long = "debug.rpc-sync",
+        help_heading = "Debug",
+        conflicts_with = "tip",
+        conflicts_with = "etherscan",
+        conflicts_with = "rpc_consensus_ws",
+        value_name = "URL"
+    )]
+    pub rpc_sync: Option<String>,
+
     /// If provided, the engine will skip `n` consecutive FCUs.
     #[arg(long = "debug.skip-fcu", help_heading = "Debug")]
     pub skip_fcu: Option<usize>,
@@ -51,10 +66,29 @@ pub struct DebugArgs {
     pub skip_new_payload: Option<usize>,
 
     /// The path to store engine API messages at.
-    /// If specified, all of the intercepted engine API messages
-    /// will be written to specified location.
-    #[arg(long = "debug.engine-api-store", help_heading = "Debug", value_name = "PATH")]
+    /// If specified, all of the intercepted engine API messages and the responses of the engine
+    /// will be written to specified location, to be replayed with `reth debug replay-engine`.
+    #[arg(
+        long = "debug.engine-api-store",
+        visible_alias = "debug.engine-api-record",
+        help_heading = "Debug",
+        value_name = "PATH"
+    )]
     pub engine_api_store: Option<PathBuf>,
+
+    /// The directory to dump invalid blocks to.
+    /// If specified, every block that is rejected as invalid is re-executed and the block, the
+    /// state it accessed and the transaction traces are written to the directory.
+    #[arg(long = "debug.invalid-block-hook", help_heading = "Debug", value_name = "PATH")]
+    pub invalid_block_hook: Option<PathBuf>,
+}
+
+impl DebugArgs {
+    /// Returns true if the node exits once the pipeline sync reached its target, because it's
+    /// requested with `--debug.terminate` or the target is the fixed `--debug.tip`.
+    pub const fn terminate_after_sync(&self) -> bool {
+        self.terminate || self.tip.is_some()
+    }
 }
 
 #[cfg(test)]
@@ -75,4 +109,49 @@ mod tests {
         let args = CommandParser::<DebugArgs>::parse_from(["reth"]).args;
         assert_eq!(args, default_args);
     }
+
+    #[test]
+    fn test_parse_rpc_sync() {
+        let args = CommandParser::<DebugArgs>::parse_from([
+            "reth",
+            "--debug.rpc-sync",
+            "http://localhost:8545",
+        ])
+        .args;
+        assert_eq!(args.rpc_sync.as_deref(), Some("http://localhost:8545"));
+
+        assert!(CommandParser::<DebugArgs>::try_parse_from([
+            "reth",
+            "--debug.rpc-sync",
+            "http://localhost:8545",
+            "--debug.rpc-consensus-ws",
+            "ws://localhost:8546",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_debug_tip_terminates() {
+        let args = CommandParser::<DebugArgs>::parse_from(["reth"]).args;
+        assert!(!args.terminate_after_sync());
+
+        let args = CommandParser::<DebugArgs>::parse_from([
+            "reth",
+            "--debug.tip",
+            "0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3",
+        ])
+        .args;
+        assert!(args.terminate_after_sync());
+    }
+
+    #[test]
+    fn test_parse_invalid_block_hook() {
+        let args = CommandParser::<DebugArgs>::parse_from([
+            "reth",
+            "--debug.invalid-block-hook",
+            "/tmp/invalid-blocks",
+        ])
+        .args;
+        assert_eq!(args.invalid_block_hook, Some(PathBuf::from("/tmp/invalid-blocks")));
+    }
 }
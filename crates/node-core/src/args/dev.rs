@@ -1,12 +1,18 @@
 //! clap [Args](clap::Args) for Dev testnet configuration
 
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 
-use clap::Args;
+use clap::{builder::ArgPredicate, Args};
 use humantime::parse_duration;
+use reth_chainspec::ChainSpec;
+use reth_primitives::{Address, GenesisAccount, U256};
+use tracing::info;
+
+/// The default balance of the accounts funded with `--dev.accounts`: 10 000 ETH.
+const DEFAULT_DEV_ACCOUNT_BALANCE: &str = "10000000000000000000000";
 
 /// Parameters for Dev testnet configuration
-#[derive(Debug, Args, PartialEq, Eq, Default, Clone, Copy)]
+#[derive(Debug, Args, PartialEq, Eq, Clone)]
 #[command(next_help_heading = "Dev testnet")]
 pub struct DevArgs {
     /// Start the node in dev mode
@@ -16,7 +22,13 @@ pub struct DevArgs {
     /// Disables network discovery and enables local http server.
     /// Prefunds 20 accounts derived by mnemonic "test test test test test test test test test test
     /// test junk" with 10 000 ETH each.
-    #[arg(long = "dev", alias = "auto-mine", help_heading = "Dev testnet", verbatim_doc_comment)]
+    #[arg(
+        long = "dev",
+        alias = "auto-mine",
+        help_heading = "Dev testnet",
+        default_value_if("fork_url", ArgPredicate::IsPresent, "true"),
+        verbatim_doc_comment
+    )]
     pub dev: bool,
 
     /// How many transactions to mine per block.
@@ -39,6 +51,85 @@ pub struct DevArgs {
         verbatim_doc_comment
     )]
     pub block_time: Option<Duration>,
+
+    /// Additional accounts to fund in the genesis block, comma separated.
+    ///
+    /// This changes the genesis hash, so a datadir initialized without these accounts can't be
+    /// reused.
+    #[arg(
+        long = "dev.accounts",
+        help_heading = "Dev testnet",
+        value_name = "ADDRESS",
+        value_delimiter = ',',
+        requires = "dev"
+    )]
+    pub accounts: Vec<Address>,
+
+    /// The balance in wei of the accounts funded with `--dev.accounts`.
+    #[arg(
+        long = "dev.balance",
+        help_heading = "Dev testnet",
+        value_name = "WEI",
+        default_value = DEFAULT_DEV_ACCOUNT_BALANCE
+    )]
+    pub balance: U256,
+
+    /// Fork the state of the chain served by this RPC node, implies `--dev`.
+    ///
+    /// Accounts and storage slots that aren't available locally are fetched from the RPC node
+    /// when they're first read and cached in the database. Accounts and storage slots of the
+    /// local genesis and the ones changed by local blocks take precedence over the forked state.
+    #[arg(long = "fork-url", help_heading = "Dev testnet", value_name = "URL")]
+    pub fork_url: Option<String>,
+
+    /// The block of the forked chain to read the state at.
+    ///
+    /// Defaults to the block the database was forked at, or the latest block of the RPC node if
+    /// the database wasn't forked yet.
+    #[arg(
+        long = "fork-block-number",
+        help_heading = "Dev testnet",
+        value_name = "BLOCK",
+        requires = "fork_url"
+    )]
+    pub fork_block_number: Option<u64>,
+}
+
+impl Default for DevArgs {
+    fn default() -> Self {
+        Self {
+            dev: false,
+            block_max_transactions: None,
+            block_time: None,
+            accounts: Vec::new(),
+            balance: DEFAULT_DEV_ACCOUNT_BALANCE.parse().expect("valid balance"),
+            fork_url: None,
+            fork_block_number: None,
+        }
+    }
+}
+
+impl DevArgs {
+    /// Funds the configured dev accounts in the genesis of the given chain spec.
+    ///
+    /// Returns the chain spec unchanged if dev mode is disabled or no accounts are configured.
+    pub fn fund_accounts(&self, chain: Arc<ChainSpec>) -> Arc<ChainSpec> {
+        if !self.dev || self.accounts.is_empty() {
+            return chain
+        }
+
+        let mut spec = (*chain).clone();
+        for address in &self.accounts {
+            info!(target: "reth::cli", %address, balance = %self.balance, "Funding dev account");
+            spec.genesis
+                .alloc
+                .insert(*address, GenesisAccount { balance: self.balance, ..Default::default() });
+        }
+        // the genesis state changed, so the genesis hash has to be recomputed
+        spec.genesis_hash = None;
+
+        Arc::new(spec)
+    }
 }
 
 #[cfg(test)]
@@ -56,13 +147,13 @@ mod tests {
     #[test]
     fn test_parse_dev_args() {
         let args = CommandParser::<DevArgs>::parse_from(["reth"]).args;
-        assert_eq!(args, DevArgs { dev: false, block_max_transactions: None, block_time: None });
+        assert_eq!(args, DevArgs::default());
 
         let args = CommandParser::<DevArgs>::parse_from(["reth", "--dev"]).args;
-        assert_eq!(args, DevArgs { dev: true, block_max_transactions: None, block_time: None });
+        assert_eq!(args, DevArgs { dev: true, ..Default::default() });
 
         let args = CommandParser::<DevArgs>::parse_from(["reth", "--auto-mine"]).args;
-        assert_eq!(args, DevArgs { dev: true, block_max_transactions: None, block_time: None });
+        assert_eq!(args, DevArgs { dev: true, ..Default::default() });
 
         let args = CommandParser::<DevArgs>::parse_from([
             "reth",
@@ -71,7 +162,10 @@ mod tests {
             "2",
         ])
         .args;
-        assert_eq!(args, DevArgs { dev: true, block_max_transactions: Some(2), block_time: None });
+        assert_eq!(
+            args,
+            DevArgs { dev: true, block_max_transactions: Some(2), ..Default::default() }
+        );
 
         let args =
             CommandParser::<DevArgs>::parse_from(["reth", "--dev", "--dev.block-time", "1s"]).args;
@@ -80,7 +174,8 @@ mod tests {
             DevArgs {
                 dev: true,
                 block_max_transactions: None,
-                block_time: Some(std::time::Duration::from_secs(1))
+                block_time: Some(std::time::Duration::from_secs(1)),
+                ..Default::default()
             }
         );
     }
@@ -98,6 +193,58 @@ mod tests {
         assert!(args.is_err());
     }
 
+    #[test]
+    fn test_parse_dev_accounts() {
+        let args = CommandParser::<DevArgs>::parse_from([
+            "reth",
+            "--dev",
+            "--dev.accounts",
+            "0x0000000000000000000000000000000000000001,0x0000000000000000000000000000000000000002",
+            "--dev.balance",
+            "1000",
+        ])
+        .args;
+        assert_eq!(args.accounts, vec![Address::with_last_byte(1), Address::with_last_byte(2)]);
+        assert_eq!(args.balance, U256::from(1000));
+
+        // accounts can only be funded in dev mode
+        let args = CommandParser::<DevArgs>::try_parse_from([
+            "reth",
+            "--dev.accounts",
+            "0x0000000000000000000000000000000000000001",
+        ]);
+        assert!(args.is_err());
+    }
+
+    #[test]
+    fn test_parse_fork_args() {
+        let args = CommandParser::<DevArgs>::parse_from([
+            "reth",
+            "--fork-url",
+            "http://localhost:8545",
+            "--fork-block-number",
+            "100",
+        ])
+        .args;
+        assert!(args.dev);
+        assert_eq!(args.fork_url.as_deref(), Some("http://localhost:8545"));
+        assert_eq!(args.fork_block_number, Some(100));
+
+        // the fork block can only be set when forking
+        let args = CommandParser::<DevArgs>::try_parse_from(["reth", "--fork-block-number", "100"]);
+        assert!(args.is_err());
+    }
+
+    #[test]
+    fn fund_dev_accounts() {
+        let address = Address::with_last_byte(1);
+        let args = DevArgs { dev: true, accounts: vec![address], ..Default::default() };
+        let chain = args.fund_accounts(reth_chainspec::DEV.clone());
+
+        assert_eq!(chain.genesis.alloc.get(&address).unwrap().balance, args.balance);
+        assert_ne!(chain.genesis_hash(), reth_chainspec::DEV.genesis_hash());
+    }
+
     #[test]
     fn dev_args_default_sanity_check() {
         let default_args = DevArgs::default();
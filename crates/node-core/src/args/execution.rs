@@ -0,0 +1,207 @@
+//! Block execution arguments
+
+use clap::Args;
+use humantime::parse_duration;
+use reth_evm::trace::TraceKind;
+use reth_tasks::affinity::CpuSet;
+use std::{path::PathBuf, time::Duration};
+
+/// Parameters for block execution
+#[derive(Debug, Clone, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Execution")]
+pub struct ExecutionArgs {
+    /// Execute the transactions of a block optimistically in parallel.
+    ///
+    /// Transactions are executed speculatively on all available cores and committed in block
+    /// order. Transactions that read state written by an earlier transaction of the same block are
+    /// re-executed sequentially, so the result is identical to sequential execution.
+    ///
+    /// Currently only supported for Ethereum chains.
+    #[arg(long = "execution.parallel", default_value_t = false)]
+    pub parallel: bool,
+
+    /// Record the gas charged and time spent per opcode and precompile.
+    ///
+    /// The accumulated profile of all executed blocks is logged whenever a batch of blocks was
+    /// executed. Parallel execution is disabled while profiling.
+    ///
+    /// Currently only supported for Ethereum chains.
+    #[arg(long = "execution.profile", default_value_t = false)]
+    pub profile: bool,
+
+    /// Record the access list of every executed block.
+    ///
+    /// The accounts and storage slots loaded by the transactions of a block are stored together
+    /// with the block, and can be queried with `reth_getBlockAccessList`.
+    ///
+    /// Currently only supported for Ethereum chains.
+    #[arg(long = "execution.access-lists", default_value_t = false)]
+    pub access_lists: bool,
+
+    /// Capture the traces of blocks whose execution takes longer than this, e.g. `2s`.
+    ///
+    /// A slow block is re-executed on top of its parent state, and its opcode and precompile
+    /// profile and the call traces of its transactions are written to the `slow-blocks` directory
+    /// of the datadir. The changesets of all executed blocks are kept for the re-execution and
+    /// left to the pruner.
+    ///
+    /// Currently only supported for Ethereum chains.
+    #[arg(long = "execution.slow-block-threshold", value_name = "DURATION", value_parser = parse_duration)]
+    pub slow_block_threshold: Option<Duration>,
+
+    /// The maximum size in megabytes of the traces of slow blocks.
+    ///
+    /// Once the `slow-blocks` directory holds more, slow blocks are only logged.
+    #[arg(long = "execution.slow-block-quota", value_name = "MB", default_value_t = 1024)]
+    pub slow_block_quota: u64,
+
+    /// Write a trace of every executed block into this directory.
+    ///
+    /// The trace of a block is written to `<DIR>/<BLOCK_NUMBER>.json` and holds one entry per
+    /// transaction. This covers the blocks executed by the pipeline and by the consensus engine,
+    /// and a block that is executed again, e.g. after a reorg, overwrites its trace. Parallel
+    /// execution is disabled while tracing.
+    ///
+    /// Currently only supported for Ethereum chains.
+    #[arg(long = "execution.trace-to", alias = "trace-to", value_name = "DIR")]
+    pub trace_to: Option<PathBuf>,
+
+    /// The kind of trace written with `--execution.trace-to`.
+    ///
+    /// One of: call, state-diff
+    #[arg(
+        long = "execution.trace-kind",
+        alias = "trace-kind",
+        value_name = "KIND",
+        default_value_t = TraceKind::Call,
+        requires = "trace_to"
+    )]
+    pub trace_kind: TraceKind,
+
+    /// The maximum size in megabytes of the state kept in memory by the execution stage.
+    ///
+    /// Accounts, storage slots and bytecodes read or written by a batch of blocks are kept in an
+    /// LRU cache for the following batches, instead of reading them from the database again.
+    /// Overrides `stages.execution.state_cache_size` of the configuration file.
+    #[arg(long = "execution.state-cache-size", value_name = "MB")]
+    pub state_cache_size: Option<u64>,
+
+    /// Pin the threads executing blocks to these CPUs.
+    ///
+    /// Applies to the pipeline and the consensus engine of a running node, and to the workers of
+    /// parallel execution which are spawned from them. Accepts a comma separated list of CPUs and CPU ranges, or the
+    /// CPUs of a NUMA node, e.g. `0-7,16` or `node:1`. Only supported on Linux.
+    #[arg(long = "execution.cpus", value_name = "CPUS")]
+    pub cpus: Option<CpuSet>,
+
+    /// Pin the threads computing state roots to these CPUs.
+    ///
+    /// Applies to the global thread pool of a running node, which computes state roots and hashes
+    /// in parallel. Accepts the same format as `--execution.cpus`. Only supported on Linux.
+    #[arg(long = "execution.trie-cpus", value_name = "CPUS")]
+    pub trie_cpus: Option<CpuSet>,
+}
+
+impl ExecutionArgs {
+    /// Returns the disk quota for the traces of slow blocks in bytes.
+    pub const fn slow_block_quota_bytes(&self) -> u64 {
+        self.slow_block_quota.saturating_mul(1024 * 1024)
+    }
+}
+
+impl Default for ExecutionArgs {
+    fn default() -> Self {
+        Self {
+            parallel: false,
+            profile: false,
+            access_lists: false,
+            slow_block_threshold: None,
+            slow_block_quota: 1024,
+            trace_to: None,
+            trace_kind: TraceKind::Call,
+            state_cache_size: None,
+            cpus: None,
+            trie_cpus: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn execution_args_default_sanity_test() {
+        let default_args = ExecutionArgs::default();
+        let args = CommandParser::<ExecutionArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, default_args);
+
+        let args =
+            CommandParser::<ExecutionArgs>::parse_from(["reth", "--execution.parallel"]).args;
+        assert!(args.parallel);
+        assert!(!args.profile);
+
+        let args =
+            CommandParser::<ExecutionArgs>::parse_from(["reth", "--execution.profile"]).args;
+        assert!(args.profile);
+
+        let args =
+            CommandParser::<ExecutionArgs>::parse_from(["reth", "--execution.access-lists"]).args;
+        assert!(args.access_lists);
+
+        let args = CommandParser::<ExecutionArgs>::parse_from([
+            "reth",
+            "--execution.slow-block-threshold",
+            "1500ms",
+            "--execution.slow-block-quota",
+            "10",
+        ])
+        .args;
+        assert_eq!(args.slow_block_threshold, Some(Duration::from_millis(1500)));
+        assert_eq!(args.slow_block_quota_bytes(), 10 * 1024 * 1024);
+
+        let args = CommandParser::<ExecutionArgs>::parse_from([
+            "reth",
+            "--trace-to",
+            "traces",
+            "--execution.trace-kind",
+            "state-diff",
+        ])
+        .args;
+        assert_eq!(args.trace_to, Some(PathBuf::from("traces")));
+        assert_eq!(args.trace_kind, TraceKind::StateDiff);
+        assert!(CommandParser::<ExecutionArgs>::try_parse_from([
+            "reth",
+            "--execution.trace-kind",
+            "call"
+        ])
+        .is_err());
+
+        let args = CommandParser::<ExecutionArgs>::parse_from([
+            "reth",
+            "--execution.state-cache-size",
+            "4096",
+        ])
+        .args;
+        assert_eq!(args.state_cache_size, Some(4096));
+
+        let args = CommandParser::<ExecutionArgs>::parse_from([
+            "reth",
+            "--execution.cpus",
+            "0-3,8",
+            "--execution.trie-cpus",
+            "4-7",
+        ])
+        .args;
+        assert_eq!(args.cpus, Some("0-3,8".parse().unwrap()));
+        assert_eq!(args.trie_cpus, Some("4-7".parse().unwrap()));
+    }
+}
@@ -0,0 +1,173 @@
+//! clap [Args](clap::Args) for overriding hardfork activations
+
+use clap::Args;
+use reth_chainspec::{ChainSpec, ForkCondition, Hardfork};
+use serde::Deserialize;
+use std::{collections::BTreeMap, path::PathBuf, str::FromStr, sync::Arc};
+use tracing::info;
+
+/// Parameters for overriding hardfork activations of the configured chain.
+///
+/// Overrides are applied to the chain spec at startup. If an override changes the set of hardforks
+/// active at genesis, the genesis hash changes as well and the node refuses to open a datadir that
+/// was initialized with the original genesis.
+#[derive(Debug, Clone, Default, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Hardfork overrides")]
+pub struct HardforkOverrideArgs {
+    /// Overrides the activation timestamp of the Shanghai hardfork.
+    #[arg(long = "override.shanghai", value_name = "TIMESTAMP")]
+    pub shanghai: Option<u64>,
+
+    /// Overrides the activation timestamp of the Cancun hardfork.
+    #[arg(long = "override.cancun", value_name = "TIMESTAMP")]
+    pub cancun: Option<u64>,
+
+    /// Overrides the activation timestamp of the Prague hardfork.
+    #[arg(long = "override.prague", value_name = "TIMESTAMP")]
+    pub prague: Option<u64>,
+
+    /// Path to a JSON file with hardfork activation overrides.
+    ///
+    /// The file maps hardfork names to their new activation condition, e.g.
+    /// {"london": {"block": 0}, "cancun": {"timestamp": 1710338135}, "prague": "never"}
+    ///
+    /// Overrides given as flags take precedence over the file.
+    #[arg(long = "override.file", value_name = "FILE", verbatim_doc_comment)]
+    pub file: Option<PathBuf>,
+}
+
+impl HardforkOverrideArgs {
+    /// Returns `true` if no overrides are configured.
+    pub const fn is_empty(&self) -> bool {
+        self.shanghai.is_none() &&
+            self.cancun.is_none() &&
+            self.prague.is_none() &&
+            self.file.is_none()
+    }
+
+    /// Collects the configured overrides, reading the override file if one is set.
+    pub fn overrides(&self) -> eyre::Result<BTreeMap<Hardfork, ForkCondition>> {
+        let mut overrides = BTreeMap::new();
+
+        if let Some(path) = &self.file {
+            let raw = reth_fs_util::read_to_string(path)?;
+            let file: BTreeMap<String, OverrideCondition> = serde_json::from_str(&raw)
+                .map_err(|err| eyre::eyre!("invalid hardfork override file {path:?}: {err}"))?;
+            for (name, condition) in file {
+                let fork = Hardfork::from_str(&name).map_err(|err| eyre::eyre!(err))?;
+                overrides.insert(fork, condition.into());
+            }
+        }
+
+        for (fork, timestamp) in [
+            (Hardfork::Shanghai, self.shanghai),
+            (Hardfork::Cancun, self.cancun),
+            (Hardfork::Prague, self.prague),
+        ] {
+            if let Some(timestamp) = timestamp {
+                overrides.insert(fork, ForkCondition::Timestamp(timestamp));
+            }
+        }
+
+        Ok(overrides)
+    }
+
+    /// Applies the configured overrides to the given chain spec.
+    ///
+    /// Returns an error if the resulting hardfork schedule activates forks out of order.
+    pub fn apply(&self, chain: Arc<ChainSpec>) -> eyre::Result<Arc<ChainSpec>> {
+        if self.is_empty() {
+            return Ok(chain)
+        }
+
+        let mut spec = (*chain).clone();
+        for (fork, condition) in self.overrides()? {
+            info!(target: "reth::cli", %fork, previous = ?spec.fork(fork), ?condition, "Overriding hardfork activation");
+            spec.set_fork_condition(fork, condition);
+        }
+
+        if let Some((earlier, later)) = spec.misordered_hardforks() {
+            eyre::bail!(
+                "hardfork overrides activate {later} ({:?}) before {earlier} ({:?})",
+                spec.fork(later),
+                spec.fork(earlier)
+            )
+        }
+
+        Ok(Arc::new(spec))
+    }
+}
+
+/// Activation condition of a hardfork in the override file.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum OverrideCondition {
+    Block(u64),
+    Timestamp(u64),
+    Never,
+}
+
+impl From<OverrideCondition> for ForkCondition {
+    fn from(condition: OverrideCondition) -> Self {
+        match condition {
+            OverrideCondition::Block(block) => Self::Block(block),
+            OverrideCondition::Timestamp(timestamp) => Self::Timestamp(timestamp),
+            OverrideCondition::Never => Self::Never,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use reth_chainspec::MAINNET;
+    use std::io::Write;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn parse_override_args() {
+        let args = CommandParser::<HardforkOverrideArgs>::parse_from(["reth"]).args;
+        assert!(args.is_empty());
+
+        let args = CommandParser::<HardforkOverrideArgs>::parse_from([
+            "reth",
+            "--override.cancun",
+            "1800000000",
+        ])
+        .args;
+        assert_eq!(args.cancun, Some(1_800_000_000));
+
+        let spec = args.apply(MAINNET.clone()).unwrap();
+        assert_eq!(spec.fork(Hardfork::Cancun), ForkCondition::Timestamp(1_800_000_000));
+        assert_eq!(spec.genesis_hash(), MAINNET.genesis_hash());
+    }
+
+    #[test]
+    fn override_file() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(file, r#"{{"cancun": {{"timestamp": 1800000000}}, "prague": "never"}}"#).unwrap();
+
+        let args = HardforkOverrideArgs {
+            cancun: Some(1_900_000_000),
+            file: Some(file.path().to_path_buf()),
+            ..Default::default()
+        };
+        let overrides = args.overrides().unwrap();
+        // flags take precedence over the file
+        assert_eq!(overrides[&Hardfork::Cancun], ForkCondition::Timestamp(1_900_000_000));
+        assert_eq!(overrides[&Hardfork::Prague], ForkCondition::Never);
+    }
+
+    #[test]
+    fn reject_misordered_overrides() {
+        let args = HardforkOverrideArgs { cancun: Some(1), ..Default::default() };
+        assert!(args.apply(MAINNET.clone()).is_err());
+    }
+}
@@ -0,0 +1,148 @@
+//! Header sync arguments
+
+use crate::args::DebugArgs;
+use clap::{Args, ValueEnum};
+
+/// Parameters for selecting how the target of the header sync is determined
+#[derive(Debug, Clone, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Header sync")]
+pub struct HeaderSyncArgs {
+    /// How the header sync follows its tip source.
+    #[arg(long = "header-sync-mode", value_name = "MODE", default_value_t = HeaderSyncMode::Continuous)]
+    pub mode: HeaderSyncMode,
+
+    /// Where the target of the header sync comes from.
+    ///
+    /// Without a consensus client, the pipeline can be driven to a fixed block hash set with
+    /// `--debug.tip`, or to the latest block of the RPC endpoint set with `--header-sync.rpc-url`.
+    #[arg(long = "header-sync.tip-source", value_name = "SOURCE", default_value_t = TipSource::Engine)]
+    pub tip_source: TipSource,
+
+    /// The RPC endpoint whose latest block is the target of the header sync.
+    ///
+    /// HTTP endpoints are polled, `WebSocket` and IPC endpoints are subscribed to.
+    #[arg(long = "header-sync.rpc-url", value_name = "URL", required_if_eq("tip_source", "rpc"))]
+    pub rpc_url: Option<String>,
+}
+
+impl HeaderSyncArgs {
+    /// Checks that the tip source can drive the header sync mode.
+    pub fn validate(&self, debug: &DebugArgs) -> eyre::Result<()> {
+        match (self.mode, self.tip_source) {
+            (HeaderSyncMode::Tip, TipSource::Engine) => {
+                eyre::bail!("--header-sync-mode tip requires a tip source other than the engine")
+            }
+            (HeaderSyncMode::Continuous, TipSource::Hash) => {
+                eyre::bail!("a fixed block hash can't be followed, use --header-sync-mode tip")
+            }
+            (_, TipSource::Hash) if debug.tip.is_none() => {
+                eyre::bail!("--header-sync.tip-source hash requires --debug.tip")
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Default for HeaderSyncArgs {
+    fn default() -> Self {
+        Self { mode: HeaderSyncMode::Continuous, tip_source: TipSource::Engine, rpc_url: None }
+    }
+}
+
+/// How the header sync follows its tip source.
+#[derive(Debug, Copy, Clone, ValueEnum, Eq, PartialEq)]
+pub enum HeaderSyncMode {
+    /// Sync once to the target the tip source sets on startup, then stop following it.
+    Tip,
+    /// Sync to every new target of the tip source.
+    Continuous,
+}
+
+impl std::fmt::Display for HeaderSyncMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Tip => write!(f, "tip"),
+            Self::Continuous => write!(f, "continuous"),
+        }
+    }
+}
+
+/// Where the target of the header sync comes from.
+#[derive(Debug, Copy, Clone, ValueEnum, Eq, PartialEq)]
+pub enum TipSource {
+    /// Forkchoice updates of the consensus client over the Engine API.
+    Engine,
+    /// The block hash set with `--debug.tip`.
+    Hash,
+    /// The latest block of the RPC endpoint set with `--header-sync.rpc-url`.
+    Rpc,
+}
+
+impl std::fmt::Display for TipSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Engine => write!(f, "engine"),
+            Self::Hash => write!(f, "hash"),
+            Self::Rpc => write!(f, "rpc"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn header_sync_args_default_sanity_test() {
+        let default_args = HeaderSyncArgs::default();
+        let args = CommandParser::<HeaderSyncArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, default_args);
+        assert!(args.validate(&DebugArgs::default()).is_ok());
+    }
+
+    #[test]
+    fn parse_rpc_tip_source() {
+        let args = CommandParser::<HeaderSyncArgs>::parse_from([
+            "reth",
+            "--header-sync-mode",
+            "tip",
+            "--header-sync.tip-source",
+            "rpc",
+            "--header-sync.rpc-url",
+            "http://localhost:8545",
+        ])
+        .args;
+        assert_eq!(args.mode, HeaderSyncMode::Tip);
+        assert_eq!(args.rpc_url.as_deref(), Some("http://localhost:8545"));
+        assert!(args.validate(&DebugArgs::default()).is_ok());
+
+        assert!(CommandParser::<HeaderSyncArgs>::try_parse_from([
+            "reth",
+            "--header-sync.tip-source",
+            "rpc"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn validate_tip_source_for_mode() {
+        let args = HeaderSyncArgs { mode: HeaderSyncMode::Tip, ..Default::default() };
+        assert!(args.validate(&DebugArgs::default()).is_err());
+
+        let args = HeaderSyncArgs { tip_source: TipSource::Hash, ..args };
+        assert!(args.validate(&DebugArgs::default()).is_err());
+        let debug = DebugArgs { tip: Some(Default::default()), ..Default::default() };
+        assert!(args.validate(&debug).is_ok());
+
+        let args = HeaderSyncArgs { mode: HeaderSyncMode::Continuous, ..args };
+        assert!(args.validate(&debug).is_err());
+    }
+}
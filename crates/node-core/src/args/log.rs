@@ -1,10 +1,13 @@
 //! clap [Args](clap::Args) for logging configuration.
 
-use crate::dirs::{LogsDir, PlatformPath};
+use crate::{
+    crash_report,
+    dirs::{LogsDir, PlatformPath},
+};
 use clap::{ArgAction, Args, ValueEnum};
 use reth_tracing::{
-    tracing_subscriber::filter::Directive, FileInfo, FileWorkerGuard, LayerInfo, LogFormat,
-    RethTracer, Tracer,
+    tracing_subscriber::filter::Directive, FileInfo, FileWorkerGuard, LayerInfo, LogBuffer,
+    LogFormat, LogRotation, RethTracer, Tracer,
 };
 use std::{fmt, fmt::Display};
 use tracing::{level_filters::LevelFilter, Level};
@@ -15,6 +18,23 @@ const MB_TO_BYTES: u64 = 1024 * 1024;
 #[derive(Debug, Args)]
 #[command(next_help_heading = "Logging")]
 pub struct LogArgs {
+    /// The format to use for logs written to stdout and the log file.
+    ///
+    /// Overrides `--log.stdout.format` and `--log.file.format`.
+    #[arg(
+        long = "log.format",
+        value_name = "FORMAT",
+        global = true,
+        conflicts_with_all = ["log_stdout_format", "log_file_format"]
+    )]
+    pub log_format: Option<LogFormat>,
+
+    /// Overrides the log level of a target in all log outputs, e.g. `reth::stages=debug`.
+    ///
+    /// Can be given multiple times or as a comma-separated list.
+    #[arg(long = "log.filter", value_name = "DIRECTIVE", global = true, value_delimiter = ',')]
+    pub log_filter: Vec<Directive>,
+
     /// The format to use for logs written to stdout.
     #[arg(long = "log.stdout.format", value_name = "FORMAT", global = true, default_value_t = LogFormat::Terminal)]
     pub log_stdout_format: LogFormat,
@@ -44,6 +64,11 @@ pub struct LogArgs {
     #[arg(long = "log.file.max-files", value_name = "COUNT", global = true, default_value_t = 5)]
     pub log_file_max_files: usize,
 
+    /// Also rotates the log file at the given interval, not only when it reaches its maximum
+    /// size.
+    #[arg(long = "log.file.rotation", value_name = "INTERVAL", global = true, default_value_t = LogRotation::Never)]
+    pub log_file_rotation: LogRotation,
+
     /// Write logs to journald.
     #[arg(long = "log.journald", global = true)]
     pub journald: bool,
@@ -57,6 +82,15 @@ pub struct LogArgs {
     )]
     pub journald_filter: String,
 
+    /// Export the spans of stage runs, database commits and RPC calls to an OpenTelemetry
+    /// collector at the given OTLP/HTTP endpoint, e.g. `http://localhost:4318/v1/traces`.
+    #[arg(long = "log.otlp", value_name = "URL", global = true)]
+    pub otlp: Option<String>,
+
+    /// The filter to use for spans exported with `--log.otlp`.
+    #[arg(long = "log.otlp.filter", value_name = "FILTER", global = true, default_value = "debug")]
+    pub otlp_filter: String,
+
     /// Sets whether or not the formatter emits ANSI terminal escape codes for colors and other
     /// text formatting.
     #[arg(
@@ -73,15 +107,24 @@ pub struct LogArgs {
 
 impl LogArgs {
     /// Creates a [`LayerInfo`] instance.
-    fn layer(&self, format: LogFormat, filter: String, use_color: bool) -> LayerInfo {
+    fn layer(&self, format: LogFormat, filter: &str, use_color: bool) -> LayerInfo {
         LayerInfo::new(
-            format,
+            self.log_format.unwrap_or(format),
             self.verbosity.directive().to_string(),
-            filter,
+            self.filter(filter),
             if use_color { Some(self.color.to_string()) } else { None },
         )
     }
 
+    /// Returns the filter of a log output, followed by the `--log.filter` overrides.
+    fn filter(&self, filter: &str) -> String {
+        std::iter::once(filter.to_string())
+            .chain(self.log_filter.iter().map(ToString::to_string))
+            .filter(|directive| !directive.is_empty())
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
     /// File info from the current log options.
     fn file_info(&self) -> FileInfo {
         FileInfo::new(
@@ -89,29 +132,49 @@ impl LogArgs {
             self.log_file_max_size * MB_TO_BYTES,
             self.log_file_max_files,
         )
+        .with_rotation(self.log_file_rotation)
     }
 
     /// Initializes tracing with the configured options from cli args.
     ///
     /// Returns the file worker guard, and the file name, if a file worker was configured.
     pub fn init_tracing(&self) -> eyre::Result<Option<FileWorkerGuard>> {
-        let mut tracer = RethTracer::new();
+        let guard = self.tracer().init()?;
+        Ok(guard)
+    }
 
-        let stdout = self.layer(self.log_stdout_format, self.log_stdout_filter.clone(), true);
+    /// Initializes tracing like [`Self::init_tracing`], but writes the logs meant for stdout into
+    /// the given buffer.
+    pub fn init_tracing_with_log_buffer(
+        &self,
+        buffer: LogBuffer,
+    ) -> eyre::Result<Option<FileWorkerGuard>> {
+        let guard = self.tracer().with_stdout_buffer(buffer).init()?;
+        Ok(guard)
+    }
+
+    /// Creates a [`RethTracer`] from the current log options.
+    fn tracer(&self) -> RethTracer {
+        let mut tracer = RethTracer::new().with_recent_logs(crash_report::log_buffer());
+
+        let stdout = self.layer(self.log_stdout_format, &self.log_stdout_filter, true);
         tracer = tracer.with_stdout(stdout);
 
         if self.journald {
-            tracer = tracer.with_journald(self.journald_filter.clone());
+            tracer = tracer.with_journald(self.filter(&self.journald_filter));
+        }
+
+        if let Some(endpoint) = &self.otlp {
+            tracer = tracer.with_otlp(endpoint.clone(), self.filter(&self.otlp_filter));
         }
 
         if self.log_file_max_files > 0 {
             let info = self.file_info();
-            let file = self.layer(self.log_file_format, self.log_file_filter.clone(), false);
+            let file = self.layer(self.log_file_format, &self.log_file_filter, false);
             tracer = tracer.with_file(file, info);
         }
 
-        let guard = tracer.init()?;
-        Ok(guard)
+        tracer
     }
 }
 
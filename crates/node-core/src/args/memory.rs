@@ -0,0 +1,57 @@
+//! Memory budget arguments
+
+use crate::memory::MemoryBudget;
+use clap::Args;
+
+/// Parameters for apportioning a memory budget to the subsystems of the node
+#[derive(Debug, Clone, Copy, Args, PartialEq, Eq, Default)]
+#[command(next_help_heading = "Memory")]
+pub struct MemoryArgs {
+    /// The memory budget of the node in megabytes, e.g. the memory limit of its container.
+    ///
+    /// The budget is apportioned to the buffer of downloaded block bodies, the ETL collectors of
+    /// the pipeline, the state cache of the execution stage and concurrent RPC tracing requests,
+    /// overriding their settings in the configuration file. A quarter of the budget is left to
+    /// the rest of the node.
+    ///
+    /// Defaults to the detected memory limit of the container, see `--resources.no-autodetect`.
+    #[arg(long = "memory.budget", value_name = "MB", value_parser = clap::value_parser!(u64).range(1024..))]
+    pub budget: Option<u64>,
+}
+
+impl MemoryArgs {
+    /// Returns the configured memory budget, if any.
+    pub const fn budget(&self) -> Option<MemoryBudget> {
+        match self.budget {
+            Some(budget) => Some(MemoryBudget::new(budget.saturating_mul(1024 * 1024))),
+            None => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn parse_memory_budget() {
+        let args = CommandParser::<MemoryArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, MemoryArgs::default());
+        assert!(args.budget().is_none());
+
+        let args =
+            CommandParser::<MemoryArgs>::parse_from(["reth", "--memory.budget", "8192"]).args;
+        assert_eq!(args.budget().unwrap().total(), 8 * 1024 * 1024 * 1024);
+
+        assert!(CommandParser::<MemoryArgs>::try_parse_from(["reth", "--memory.budget", "512"])
+            .is_err());
+    }
+}
@@ -51,6 +51,18 @@ pub use dev::DevArgs;
 mod pruning;
 pub use pruning::PruningArgs;
 
+/// ExecutionArgs for configuring block execution
+mod execution;
+pub use execution::ExecutionArgs;
+
+/// MemoryArgs for apportioning a memory budget to the subsystems of the node
+mod memory;
+pub use memory::MemoryArgs;
+
+/// ResourceArgs for adapting the node to the resource limits of its container
+mod resources;
+pub use resources::ResourceArgs;
+
 /// DatadirArgs for configuring data storage paths
 mod datadir_args;
 pub use datadir_args::DatadirArgs;
@@ -59,6 +71,22 @@ pub use datadir_args::DatadirArgs;
 mod benchmark_args;
 pub use benchmark_args::BenchmarkArgs;
 
+/// AlertArgs for configuring health alerts
+mod alert;
+pub use alert::AlertArgs;
+
+/// PublisherArgs for configuring the block publisher
+mod publisher;
+pub use publisher::{PublisherArgs, PublisherFormat};
+
+/// HeaderSyncArgs for selecting how the header sync target is determined
+mod header_sync;
+pub use header_sync::{HeaderSyncArgs, HeaderSyncMode, TipSource};
+
+/// HardforkOverrideArgs struct for overriding hardfork activations
+mod hardfork_override;
+pub use hardfork_override::HardforkOverrideArgs;
+
 pub mod utils;
 
 pub mod types;
@@ -13,25 +13,36 @@ pub struct PruningArgs {
     /// This flag takes priority over pruning configuration in reth.toml.
     #[arg(long, default_value_t = false)]
     pub full: bool,
+
+    /// Only look up transactions by hash in the most recent this many blocks. The lookup entries
+    /// of older blocks are pruned. (0 = disable transaction lookup)
+    ///
+    /// This flag takes priority over pruning configuration in reth.toml.
+    #[arg(long = "txlookup-limit", value_name = "BLOCKS")]
+    pub txlookup_limit: Option<u64>,
 }
 
 impl PruningArgs {
     /// Returns pruning configuration.
     pub fn prune_config(&self, chain_spec: &ChainSpec) -> Option<PruneConfig> {
         if !self.full {
-            return None
+            return self.transaction_lookup_mode().map(|mode| PruneConfig {
+                segments: PruneModes { transaction_lookup: Some(mode), ..PruneModes::none() },
+                ..Default::default()
+            })
         }
         Some(PruneConfig {
             block_interval: 5,
             segments: PruneModes {
                 sender_recovery: Some(PruneMode::Full),
-                transaction_lookup: None,
+                transaction_lookup: self.transaction_lookup_mode(),
                 receipts: chain_spec
                     .deposit_contract
                     .as_ref()
                     .map(|contract| PruneMode::Before(contract.block)),
                 account_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
                 storage_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
+                history_address_allowlist: Default::default(),
                 receipts_log_filter: ReceiptsLogPruneConfig(
                     chain_spec
                         .deposit_contract
@@ -43,12 +54,22 @@ impl PruningArgs {
             },
         })
     }
+
+    /// Returns the prune mode of the transaction lookup, if it's limited with `--txlookup-limit`.
+    pub const fn transaction_lookup_mode(&self) -> Option<PruneMode> {
+        match self.txlookup_limit {
+            Some(0) => Some(PruneMode::Full),
+            Some(blocks) => Some(PruneMode::Distance(blocks)),
+            None => None,
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use clap::Parser;
+    use reth_chainspec::MAINNET;
 
     /// A helper type to parse Args more easily
     #[derive(Parser)]
@@ -63,4 +84,29 @@ mod tests {
         let args = CommandParser::<PruningArgs>::parse_from(["reth"]).args;
         assert_eq!(args, default_args);
     }
+
+    #[test]
+    fn parse_txlookup_limit() {
+        let args =
+            CommandParser::<PruningArgs>::parse_from(["reth", "--txlookup-limit", "100000"]).args;
+        assert_eq!(args.transaction_lookup_mode(), Some(PruneMode::Distance(100_000)));
+        let config = args.prune_config(&MAINNET).unwrap();
+        assert_eq!(
+            config.segments,
+            PruneModes {
+                transaction_lookup: Some(PruneMode::Distance(100_000)),
+                ..PruneModes::none()
+            }
+        );
+
+        let args = CommandParser::<PruningArgs>::parse_from(["reth", "--txlookup-limit", "0"]).args;
+        assert_eq!(args.transaction_lookup_mode(), Some(PruneMode::Full));
+
+        let args =
+            CommandParser::<PruningArgs>::parse_from(["reth", "--full", "--txlookup-limit", "0"])
+                .args;
+        let config = args.prune_config(&MAINNET).unwrap();
+        assert_eq!(config.segments.transaction_lookup, Some(PruneMode::Full));
+        assert_eq!(config.segments.sender_recovery, Some(PruneMode::Full));
+    }
 }
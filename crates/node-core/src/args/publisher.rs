@@ -0,0 +1,81 @@
+//! Block publisher arguments
+
+use clap::{Args, ValueEnum};
+use url::Url;
+
+/// Parameters for publishing canonical blocks to a message broker
+#[derive(Debug, Clone, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Publisher")]
+pub struct PublisherArgs {
+    /// Publish new canonical blocks, their receipts and logs to the message broker at this URL.
+    ///
+    /// Supported schemes are `nats://` and `kafka://`, if reth was built with the `nats` or
+    /// `kafka` feature respectively.
+    #[arg(long = "publisher.url", value_name = "URL")]
+    pub url: Option<Url>,
+
+    /// Prefix of the topics that messages are published to.
+    ///
+    /// Blocks are published to `<TOPIC>.blocks` and logs to `<TOPIC>.logs`.
+    #[arg(long = "publisher.topic", value_name = "TOPIC", default_value = "reth")]
+    pub topic: String,
+
+    /// The encoding of the published messages.
+    #[arg(long = "publisher.format", value_name = "FORMAT", default_value_t = PublisherFormat::Json)]
+    pub format: PublisherFormat,
+}
+
+impl Default for PublisherArgs {
+    fn default() -> Self {
+        Self { url: None, topic: "reth".to_string(), format: PublisherFormat::Json }
+    }
+}
+
+/// The encoding of published messages.
+#[derive(Debug, Copy, Clone, ValueEnum, Eq, PartialEq)]
+pub enum PublisherFormat {
+    /// JSON messages, with hex encoded hashes, addresses and data.
+    Json,
+    /// Protobuf messages.
+    Protobuf,
+}
+
+impl std::fmt::Display for PublisherFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Json => write!(f, "json"),
+            Self::Protobuf => write!(f, "protobuf"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn publisher_args_default_sanity_test() {
+        let default_args = PublisherArgs::default();
+        let args = CommandParser::<PublisherArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, default_args);
+
+        let args = CommandParser::<PublisherArgs>::parse_from([
+            "reth",
+            "--publisher.url",
+            "kafka://localhost:9092",
+            "--publisher.format",
+            "protobuf",
+        ])
+        .args;
+        assert_eq!(args.url.unwrap().scheme(), "kafka");
+        assert_eq!(args.format, PublisherFormat::Protobuf);
+    }
+}
@@ -0,0 +1,51 @@
+//! Resource limit arguments
+
+use crate::resources::ResourceLimits;
+use clap::Args;
+
+/// Parameters for adapting the node to the CPU and memory limits of its container
+#[derive(Debug, Clone, Copy, Args, PartialEq, Eq, Default)]
+#[command(next_help_heading = "Resources")]
+pub struct ResourceArgs {
+    /// Don't adapt the node to the CPU and memory limits of its cgroup, e.g. of its container.
+    ///
+    /// By default the limits are detected on startup. The CPU quota limits the size of the global
+    /// thread pool, the memory limit is the default of `--memory.budget` and decides whether the
+    /// database reads ahead, unless set with `--db.readahead`.
+    #[arg(long = "resources.no-autodetect")]
+    pub no_autodetect: bool,
+}
+
+impl ResourceArgs {
+    /// Returns the detected resource limits, or no limits if autodetection is disabled.
+    pub fn limits(&self) -> ResourceLimits {
+        if self.no_autodetect {
+            ResourceLimits::default()
+        } else {
+            ResourceLimits::detect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn disable_autodetection() {
+        let args = CommandParser::<ResourceArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, ResourceArgs::default());
+
+        let args =
+            CommandParser::<ResourceArgs>::parse_from(["reth", "--resources.no-autodetect"]).args;
+        assert!(!args.limits().is_limited());
+    }
+}
@@ -6,17 +6,20 @@ use crate::args::{
 };
 use alloy_rpc_types_engine::JwtSecret;
 use clap::{
-    builder::{PossibleValue, RangedU64ValueParser, TypedValueParser},
+    builder::{ArgPredicate, PossibleValue, RangedU64ValueParser, TypedValueParser},
     Arg, Args, Command,
 };
+use humantime::parse_duration;
 use rand::Rng;
 use reth_rpc::eth::RPC_DEFAULT_GAS_CAP;
 
 use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
+use reth_tasks::affinity::CpuSet;
 use std::{
     ffi::OsStr,
     net::{IpAddr, Ipv4Addr},
     path::PathBuf,
+    time::Duration,
 };
 
 /// Default max number of subscriptions per connection.
@@ -38,7 +41,11 @@ pub(crate) const RPC_DEFAULT_MAX_CONNECTIONS: u32 = 500;
 #[command(next_help_heading = "RPC")]
 pub struct RpcServerArgs {
     /// Enable the HTTP-RPC server
-    #[arg(long, default_value_if("dev", "true", "true"))]
+    #[arg(
+        long,
+        default_value_if("dev", "true", "true"),
+        default_value_if("fork_url", ArgPredicate::IsPresent, "true")
+    )]
     pub http: bool,
 
     /// Http server address to listen on
@@ -102,6 +109,15 @@ pub struct RpcServerArgs {
     #[arg(long = "authrpc.jwtsecret", value_name = "PATH", global = true, required = false)]
     pub auth_jwtsecret: Option<PathBuf>,
 
+    /// Path to a directory of additionally accepted JWT secrets for the authenticated engine-API
+    /// RPC server, one hex encoded secret per `.hex` file.
+    ///
+    /// The directory is reloaded when its files change, so the secret shared with the consensus
+    /// layer can be rotated without restarting the node: add the new secret to the directory,
+    /// switch the consensus layer to it, then make it the `--authrpc.jwtsecret`.
+    #[arg(long = "authrpc.jwtsecret-dir", value_name = "DIR", global = true, required = false)]
+    pub auth_jwtsecret_dir: Option<PathBuf>,
+
     /// Enable auth engine API over IPC
     #[arg(long)]
     pub auth_ipc: bool,
@@ -146,6 +162,22 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.max-logs-per-response", alias = "rpc-max-logs-per-response", value_name = "COUNT", default_value_t = ZeroAsNoneU64::new(constants::DEFAULT_MAX_LOGS_PER_RESPONSE as u64))]
     pub rpc_max_logs_per_response: ZeroAsNoneU64,
 
+    /// Duration after which an installed filter that was not polled is removed, e.g. `5m`.
+    #[arg(long = "rpc.filter-ttl", value_name = "DURATION", value_parser = parse_duration, default_value = "5m")]
+    pub rpc_filter_ttl: Duration,
+
+    /// Maximum number of installed filters, new filters are rejected once it's reached. (0 = no
+    /// limit)
+    #[arg(long = "rpc.max-filters", value_name = "COUNT", default_value_t = ZeroAsNoneU64::from(0))]
+    pub rpc_max_filters: ZeroAsNoneU64,
+
+    /// Persist the installed log and block filters and their last polled block to the datadir.
+    ///
+    /// Clients polling them with `eth_getFilterChanges` continue where they left off after a
+    /// restart.
+    #[arg(long = "rpc.persist-filters")]
+    pub rpc_persist_filters: bool,
+
     /// Maximum gas limit for `eth_call` and call tracing RPC methods.
     #[arg(
         long = "rpc.gascap",
@@ -156,6 +188,54 @@ pub struct RpcServerArgs {
     )]
     pub rpc_gas_cap: u64,
 
+    /// Maximum duration of the EVM execution of `eth_call`, `eth_estimateGas` and tracing calls,
+    /// e.g. `5s`.
+    ///
+    /// Calls exceeding it fail with a timeout error.
+    #[arg(long = "rpc.evm-timeout", value_name = "DURATION", value_parser = parse_duration)]
+    pub rpc_evm_timeout: Option<Duration>,
+
+    /// Maximum number of blocks that could be traced per `trace_filter` request.
+    #[arg(long = "rpc.max-trace-filter-blocks", value_name = "COUNT", default_value_t = constants::DEFAULT_MAX_TRACE_FILTER_BLOCKS)]
+    pub rpc_max_trace_filter_blocks: u64,
+
+    /// Maximum number of traces that can be returned in a single `trace_filter` response. (0 = no
+    /// limit)
+    #[arg(long = "rpc.max-traces-per-response", value_name = "COUNT", default_value_t = ZeroAsNoneU64::from(0))]
+    pub rpc_max_traces_per_response: ZeroAsNoneU64,
+
+    /// Log RPC calls that take longer than this many milliseconds, together with their sanitized
+    /// parameters. (0 = disabled)
+    #[arg(long = "rpc.slow-query-threshold", value_name = "MILLIS", default_value_t = ZeroAsNoneU64::from(0))]
+    pub rpc_slow_query_threshold: ZeroAsNoneU64,
+
+    /// URL of an archive node that calls are proxied to, if they need history that is pruned
+    /// locally, including the logs of blocks with pruned receipts.
+    ///
+    /// The blocks, transactions, receipts and logs in its responses are validated against the
+    /// local headers. `--rpc.pruned-logs-upstream` is kept as an alias of this flag.
+    #[arg(
+        long = "rpc.historical-upstream",
+        visible_alias = "rpc.pruned-logs-upstream",
+        value_name = "URL"
+    )]
+    pub rpc_historical_upstream: Option<String>,
+
+    /// Read ranges of transactions and receipts from static files through `io_uring`, submitting
+    /// up to this many reads at once.
+    ///
+    /// Requires Linux and a build with the `io-uring` feature.
+    #[arg(long = "rpc.io-uring", value_name = "QUEUE_DEPTH")]
+    pub rpc_io_uring: Option<u32>,
+
+    /// Pin the threads executing CPU heavy RPC calls, like tracing and `eth_getProof`, to these
+    /// CPUs.
+    ///
+    /// Accepts a comma separated list of CPUs and CPU ranges, or the CPUs of a NUMA node, e.g.
+    /// `0-7,16` or `node:1`. Only supported on Linux.
+    #[arg(long = "rpc.cpus", value_name = "CPUS")]
+    pub rpc_cpus: Option<CpuSet>,
+
     /// State cache configuration.
     #[command(flatten)]
     pub rpc_state_cache: RpcStateCacheArgs,
@@ -275,6 +355,7 @@ impl Default for RpcServerArgs {
             auth_addr: Ipv4Addr::LOCALHOST.into(),
             auth_port: constants::DEFAULT_AUTH_PORT,
             auth_jwtsecret: None,
+            auth_jwtsecret_dir: None,
             auth_ipc: false,
             auth_ipc_path: constants::DEFAULT_ENGINE_API_IPC_ENDPOINT.to_string(),
             rpc_jwtsecret: None,
@@ -285,7 +366,17 @@ impl Default for RpcServerArgs {
             rpc_max_tracing_requests: constants::default_max_tracing_requests(),
             rpc_max_blocks_per_filter: constants::DEFAULT_MAX_BLOCKS_PER_FILTER.into(),
             rpc_max_logs_per_response: (constants::DEFAULT_MAX_LOGS_PER_RESPONSE as u64).into(),
+            rpc_filter_ttl: Duration::from_secs(5 * 60),
+            rpc_max_filters: ZeroAsNoneU64::from(0),
+            rpc_persist_filters: false,
             rpc_gas_cap: RPC_DEFAULT_GAS_CAP.into(),
+            rpc_evm_timeout: None,
+            rpc_max_trace_filter_blocks: constants::DEFAULT_MAX_TRACE_FILTER_BLOCKS,
+            rpc_max_traces_per_response: ZeroAsNoneU64::from(0),
+            rpc_slow_query_threshold: ZeroAsNoneU64::from(0),
+            rpc_historical_upstream: None,
+            rpc_io_uring: None,
+            rpc_cpus: None,
             gas_price_oracle: GasPriceOracleArgs::default(),
             rpc_state_cache: RpcStateCacheArgs::default(),
         }
@@ -365,6 +456,17 @@ mod tests {
         assert_eq!(apis, expected);
     }
 
+    #[test]
+    fn test_rpc_server_pruned_logs_upstream_alias() {
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.pruned-logs-upstream",
+            "http://localhost:8545",
+        ])
+        .args;
+        assert_eq!(args.rpc_historical_upstream.as_deref(), Some("http://localhost:8545"));
+    }
+
     #[test]
     fn test_rpc_server_args_parser_none() {
         let args = CommandParser::<RpcServerArgs>::parse_from(["reth", "--http.api", "none"]).args;
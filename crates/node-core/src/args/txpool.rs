@@ -2,14 +2,25 @@
 
 use crate::cli::config::RethTransactionPoolConfig;
 use clap::Args;
+use humantime::parse_duration;
 use reth_primitives::Address;
 use reth_transaction_pool::{
-    blobstore::disk::DEFAULT_MAX_CACHED_BLOBS, validate::DEFAULT_MAX_TX_INPUT_BYTES,
+    blobstore::{disk::DEFAULT_MAX_CACHED_BLOBS, DiskFileBlobStoreConfig},
+    maintain::{
+        LocalTransactionBackupConfig, MaintainPoolConfig, DEFAULT_LOCAL_TRANSACTIONS_LIFETIME,
+        DEFAULT_REJOURNAL_INTERVAL,
+    },
+    validate::{EthTransactionValidatorBuilder, DEFAULT_MAX_TX_INPUT_BYTES},
     LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
     REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
     TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
 };
-/// Parameters for debugging purposes
+use std::{path::PathBuf, time::Duration};
+
+/// The default number of additional tasks that validate incoming transactions.
+pub const DEFAULT_ADDITIONAL_VALIDATION_TASKS: usize = 1;
+
+/// Parameters for the transaction pool
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
 #[command(next_help_heading = "TxPool")]
 pub struct TxPoolArgs {
@@ -34,6 +45,13 @@ pub struct TxPoolArgs {
     #[arg(long = "txpool.queued-max-size", alias = "txpool.queued_max_size", default_value_t = TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT)]
     pub queued_max_size: usize,
 
+    /// Max number of transaction in the blob sub-pool
+    #[arg(long = "blobpool.max-count", default_value_t = TXPOOL_SUBPOOL_MAX_TXS_DEFAULT)]
+    pub blob_max_count: usize,
+    /// Max size of the blob sub-pool in megabytes.
+    #[arg(long = "blobpool.max-size", default_value_t = TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT)]
+    pub blob_max_size: usize,
+
     /// Max number of executable transaction slots guaranteed per account
     #[arg(long = "txpool.max-account-slots", alias = "txpool.max_account_slots", default_value_t = TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER)]
     pub max_account_slots: usize,
@@ -46,6 +64,11 @@ pub struct TxPoolArgs {
     #[arg(long = "blobpool.pricebump", default_value_t = REPLACE_BLOB_PRICE_BUMP)]
     pub blob_transaction_price_bump: u128,
 
+    /// Number of blocks the blobs of mined transactions are kept after their block was finalized,
+    /// to serve them over `eth_getBlobSidecars`.
+    #[arg(long = "blobpool.retention-blocks", default_value_t = 0)]
+    pub blob_retention_blocks: u64,
+
     /// Max size in bytes of a single transaction allowed to enter the pool
     #[arg(long = "txpool.max-tx-input-bytes", alias = "txpool.max_tx_input_bytes", default_value_t = DEFAULT_MAX_TX_INPUT_BYTES)]
     pub max_tx_input_bytes: usize,
@@ -54,6 +77,16 @@ pub struct TxPoolArgs {
     #[arg(long = "txpool.max-cached-entries", alias = "txpool.max_cached_entries", default_value_t = DEFAULT_MAX_CACHED_BLOBS)]
     pub max_cached_entries: u32,
 
+    /// Minimum priority fee (in wei) a transaction must pay to enter the pool.
+    ///
+    /// Local transactions are exempt, unless `--txpool.nolocals` is set.
+    #[arg(long = "txpool.minimum-priority-fee", value_name = "WEI")]
+    pub minimum_priority_fee: Option<u128>,
+
+    /// Number of additional tasks that validate incoming transactions in parallel.
+    #[arg(long = "txpool.additional-validation-tasks", default_value_t = DEFAULT_ADDITIONAL_VALIDATION_TASKS)]
+    pub additional_validation_tasks: usize,
+
     /// Flag to disable local transaction exemptions.
     #[arg(long = "txpool.nolocals")]
     pub no_locals: bool,
@@ -63,6 +96,27 @@ pub struct TxPoolArgs {
     /// Flag to toggle local transaction propagation.
     #[arg(long = "txpool.no-local-transactions-propagation")]
     pub no_local_transactions_propagation: bool,
+
+    /// Disables the journal of local transactions.
+    ///
+    /// By default, local transactions are written to the datadir and reinserted into the pool
+    /// after a restart.
+    #[arg(long = "txpool.nojournal")]
+    pub no_journal: bool,
+    /// Interval at which the journal of local transactions is regenerated, in addition to on
+    /// shutdown.
+    ///
+    /// Parses strings using [`humantime::parse_duration`]
+    /// --txpool.rejournal 30m
+    #[arg(long = "txpool.rejournal", value_parser = parse_duration, default_value = "1h", verbatim_doc_comment)]
+    pub rejournal: Duration,
+    /// Maximum time since a local transaction was submitted, after which it is no longer
+    /// reinserted from the journal.
+    ///
+    /// Parses strings using [`humantime::parse_duration`]
+    /// --txpool.lifetime 3h
+    #[arg(long = "txpool.lifetime", value_parser = parse_duration, default_value = "3h", verbatim_doc_comment)]
+    pub lifetime: Duration,
 }
 
 impl Default for TxPoolArgs {
@@ -74,14 +128,22 @@ impl Default for TxPoolArgs {
             basefee_max_size: TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
             queued_max_count: TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
             queued_max_size: TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
+            blob_max_count: TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
+            blob_max_size: TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
             price_bump: DEFAULT_PRICE_BUMP,
             blob_transaction_price_bump: REPLACE_BLOB_PRICE_BUMP,
+            blob_retention_blocks: 0,
             max_tx_input_bytes: DEFAULT_MAX_TX_INPUT_BYTES,
             max_cached_entries: DEFAULT_MAX_CACHED_BLOBS,
+            minimum_priority_fee: None,
+            additional_validation_tasks: DEFAULT_ADDITIONAL_VALIDATION_TASKS,
             no_locals: false,
             locals: Default::default(),
             no_local_transactions_propagation: false,
+            no_journal: false,
+            rejournal: DEFAULT_REJOURNAL_INTERVAL,
+            lifetime: DEFAULT_LOCAL_TRANSACTIONS_LIFETIME,
         }
     }
 }
@@ -108,8 +170,8 @@ impl RethTransactionPoolConfig for TxPoolArgs {
                 max_size: self.queued_max_size * 1024 * 1024,
             },
             blob_limit: SubPoolLimit {
-                max_txs: self.queued_max_count,
-                max_size: self.queued_max_size * 1024 * 1024,
+                max_txs: self.blob_max_count,
+                max_size: self.blob_max_size * 1024 * 1024,
             },
             max_account_slots: self.max_account_slots,
             price_bumps: PriceBumpConfig {
@@ -118,6 +180,43 @@ impl RethTransactionPoolConfig for TxPoolArgs {
             },
         }
     }
+
+    fn configure_validator(
+        &self,
+        builder: EthTransactionValidatorBuilder,
+    ) -> EthTransactionValidatorBuilder {
+        let mut builder = builder
+            .with_local_transactions_config(self.pool_config().local_transactions_config)
+            .with_max_tx_input_bytes(self.max_tx_input_bytes)
+            .with_additional_tasks(self.additional_validation_tasks);
+        if let Some(minimum_priority_fee) = self.minimum_priority_fee {
+            builder = builder.with_minimum_priority_fee(minimum_priority_fee);
+        }
+        builder
+    }
+
+    fn blob_store_config(&self) -> DiskFileBlobStoreConfig {
+        DiskFileBlobStoreConfig::default().with_max_cached_entries(self.max_cached_entries)
+    }
+
+    fn maintain_config(&self) -> MaintainPoolConfig {
+        MaintainPoolConfig {
+            blob_retention_blocks: self.blob_retention_blocks,
+            ..Default::default()
+        }
+    }
+
+    fn local_transactions_backup_config(
+        &self,
+        transactions_path: PathBuf,
+    ) -> LocalTransactionBackupConfig {
+        if self.no_journal {
+            return LocalTransactionBackupConfig::default()
+        }
+        LocalTransactionBackupConfig::with_local_txs_backup(transactions_path)
+            .with_rejournal_interval(self.rejournal)
+            .with_lifetime(self.lifetime)
+    }
 }
 
 #[cfg(test)]
@@ -138,4 +237,54 @@ mod tests {
         let args = CommandParser::<TxPoolArgs>::parse_from(["reth"]).args;
         assert_eq!(args, default_args);
     }
+
+    #[test]
+    fn txpool_parse_journal_args() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.rejournal",
+            "30m",
+            "--txpool.lifetime",
+            "1h",
+        ])
+        .args;
+        let config = args.local_transactions_backup_config("transactions.rlp".into());
+        assert_eq!(config.rejournal_interval, Some(Duration::from_secs(30 * 60)));
+        assert_eq!(config.lifetime, Some(Duration::from_secs(60 * 60)));
+
+        let args = CommandParser::<TxPoolArgs>::parse_from(["reth", "--txpool.nojournal"]).args;
+        assert!(args
+            .local_transactions_backup_config("transactions.rlp".into())
+            .transactions_path
+            .is_none());
+    }
+
+    #[test]
+    fn txpool_parse_blob_limit() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--blobpool.max-count",
+            "100",
+            "--blobpool.max-size",
+            "50",
+        ])
+        .args;
+        let config = args.pool_config();
+        assert_eq!(config.blob_limit, SubPoolLimit::new(100, 50 * 1024 * 1024));
+        assert_eq!(config.queued_limit, SubPoolLimit::default());
+    }
+
+    #[test]
+    fn txpool_parse_validation_args() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.minimum-priority-fee",
+            "1000000000",
+            "--txpool.additional-validation-tasks",
+            "4",
+        ])
+        .args;
+        assert_eq!(args.minimum_priority_fee, Some(1_000_000_000));
+        assert_eq!(args.additional_validation_tasks, 4);
+    }
 }
@@ -3,9 +3,10 @@
 use alloy_genesis::Genesis;
 use reth_chainspec::ChainSpec;
 use reth_fs_util as fs;
-use reth_primitives::{BlockHashOrNumber, B256};
+use reth_primitives::{BlockHashOrNumber, BlockNumber, B256};
 use std::{
     net::{IpAddr, Ipv4Addr, SocketAddr, ToSocketAddrs},
+    ops::RangeInclusive,
     path::PathBuf,
     str::FromStr,
     sync::Arc,
@@ -38,10 +39,61 @@ pub fn chain_help() -> String {
     format!("The chain this node is running.\nPossible values are either a built-in chain or the path to a chain specification file.\n\nBuilt-in chains:\n    {}", SUPPORTED_CHAINS.join(", "))
 }
 
+/// Hardfork activations of a geth-style genesis config that are supported.
+#[cfg(not(feature = "optimism"))]
+const SUPPORTED_FORK_ACTIVATIONS: &[&str] = &[
+    "homesteadBlock",
+    "daoForkBlock",
+    "eip150Block",
+    "eip155Block",
+    "eip158Block",
+    "byzantiumBlock",
+    "constantinopleBlock",
+    "petersburgBlock",
+    "istanbulBlock",
+    "muirGlacierBlock",
+    "berlinBlock",
+    "londonBlock",
+    "arrowGlacierBlock",
+    "grayGlacierBlock",
+    "mergeNetsplitBlock",
+    "shanghaiTime",
+    "cancunTime",
+    "pragueTime",
+];
+/// Hardfork activations of a geth-style genesis config that are supported.
+#[cfg(feature = "optimism")]
+const SUPPORTED_FORK_ACTIVATIONS: &[&str] = &[
+    "homesteadBlock",
+    "daoForkBlock",
+    "eip150Block",
+    "eip155Block",
+    "eip158Block",
+    "byzantiumBlock",
+    "constantinopleBlock",
+    "petersburgBlock",
+    "istanbulBlock",
+    "muirGlacierBlock",
+    "berlinBlock",
+    "londonBlock",
+    "arrowGlacierBlock",
+    "grayGlacierBlock",
+    "mergeNetsplitBlock",
+    "bedrockBlock",
+    "regolithTime",
+    "shanghaiTime",
+    "canyonTime",
+    "cancunTime",
+    "ecotoneTime",
+    "pragueTime",
+    "fjordTime",
+];
+
 /// Clap value parser for [`ChainSpec`]s.
 ///
 /// The value parser matches either a known chain, the path
-/// to a json file, or a json formatted string in-memory. The json needs to be a Genesis struct.
+/// to a json file, or a json formatted string in-memory. The json needs to be a Genesis struct,
+/// e.g. a geth genesis file. See [`genesis_value_parser`].
 pub fn chain_value_parser(s: &str) -> eyre::Result<Arc<ChainSpec>, eyre::Error> {
     Ok(match s {
         #[cfg(not(feature = "optimism"))]
@@ -54,11 +106,11 @@ pub fn chain_value_parser(s: &str) -> eyre::Result<Arc<ChainSpec>, eyre::Error>
         "holesky" => HOLESKY.clone(),
         "dev" => DEV.clone(),
         #[cfg(feature = "optimism")]
-        "optimism" => OP_MAINNET.clone(),
+        "optimism" | "optimism_mainnet" | "optimism-mainnet" => OP_MAINNET.clone(),
         #[cfg(feature = "optimism")]
         "optimism_sepolia" | "optimism-sepolia" => OP_SEPOLIA.clone(),
         #[cfg(feature = "optimism")]
-        "base" => BASE_MAINNET.clone(),
+        "base" | "base_mainnet" | "base-mainnet" => BASE_MAINNET.clone(),
         #[cfg(feature = "optimism")]
         "base_sepolia" | "base-sepolia" => BASE_SEPOLIA.clone(),
         _ => {
@@ -75,14 +127,96 @@ pub fn chain_value_parser(s: &str) -> eyre::Result<Arc<ChainSpec>, eyre::Error>
                 }
             };
 
-            // both serialized Genesis and ChainSpec structs supported
-            let genesis: Genesis = serde_json::from_str(&raw)?;
-
-            Arc::new(genesis.into())
+            genesis_value_parser(&raw)?
         }
     })
 }
 
+/// Parses a geth-style genesis json into a [`ChainSpec`].
+///
+/// The hardfork schedule, base fee parameters and the optimism specific fields of the `config`
+/// section are taken into account. Options of the `config` section that would silently change the
+/// rules of the chain if they were ignored are rejected.
+pub fn genesis_value_parser(raw: &str) -> eyre::Result<Arc<ChainSpec>, eyre::Error> {
+    let value: serde_json::Value =
+        serde_json::from_str(raw).map_err(|err| eyre::eyre!("invalid genesis json: {err}"))?;
+    if let Some(config) = value.get("config").and_then(|config| config.as_object()) {
+        check_genesis_config(config)?;
+    }
+
+    let genesis: Genesis = serde_json::from_value(value)
+        .map_err(|err| eyre::eyre!("invalid genesis json: {err}"))?;
+    let spec = ChainSpec::try_from(genesis)?;
+
+    if let Some((earlier, later)) = spec.misordered_hardforks() {
+        eyre::bail!(
+            "genesis config activates {later} ({:?}) before {earlier} ({:?})",
+            spec.fork(later),
+            spec.fork(earlier)
+        )
+    }
+
+    Ok(Arc::new(spec))
+}
+
+/// Rejects options of a genesis `config` section that are not supported.
+fn check_genesis_config(config: &serde_json::Map<String, serde_json::Value>) -> eyre::Result<()> {
+    for key in config.keys() {
+        if (key.ends_with("Block") || key.ends_with("Time")) &&
+            !SUPPORTED_FORK_ACTIVATIONS.contains(&key.as_str())
+        {
+            #[cfg(not(feature = "optimism"))]
+            if matches!(
+                key.as_str(),
+                "bedrockBlock" | "regolithTime" | "canyonTime" | "ecotoneTime" | "fjordTime"
+            ) {
+                eyre::bail!("genesis config field {key} is only supported by op-reth")
+            }
+            eyre::bail!(
+                "unsupported hardfork activation {key} in genesis config, supported are: {}",
+                SUPPORTED_FORK_ACTIVATIONS.join(", ")
+            )
+        }
+    }
+
+    if config.get("daoForkSupport").and_then(|support| support.as_bool()) == Some(false) &&
+        config.get("daoForkBlock").is_some_and(|block| !block.is_null())
+    {
+        eyre::bail!("genesis config sets daoForkBlock with daoForkSupport false, which is unsupported")
+    }
+
+    if let Some(optimism) = config.get("optimism") {
+        #[cfg(not(feature = "optimism"))]
+        {
+            let _ = optimism;
+            eyre::bail!("genesis config field optimism is only supported by op-reth")
+        }
+        #[cfg(feature = "optimism")]
+        {
+            let param = |name: &str| optimism.get(name).and_then(|value| value.as_u64());
+            let (elasticity, denominator) =
+                (param("eip1559Elasticity"), param("eip1559Denominator"));
+            if elasticity.is_some() != denominator.is_some() {
+                eyre::bail!(
+                    "genesis config field optimism must set both eip1559Elasticity and eip1559Denominator"
+                )
+            }
+            if param("eip1559DenominatorCanyon").is_some() && denominator.is_none() {
+                eyre::bail!(
+                    "genesis config field optimism sets eip1559DenominatorCanyon without eip1559Denominator"
+                )
+            }
+            for name in ["eip1559Elasticity", "eip1559Denominator", "eip1559DenominatorCanyon"] {
+                if param(name) == Some(0) {
+                    eyre::bail!("genesis config field optimism.{name} must not be zero")
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
 /// Parse [`BlockHashOrNumber`]
 pub fn hash_or_num_value_parser(value: &str) -> eyre::Result<BlockHashOrNumber, eyre::Error> {
     match B256::from_str(value) {
@@ -91,6 +225,16 @@ pub fn hash_or_num_value_parser(value: &str) -> eyre::Result<BlockHashOrNumber,
     }
 }
 
+/// Parse an inclusive block range, either `<FROM>-<TO>` or a single block number.
+pub fn block_range_value_parser(value: &str) -> eyre::Result<RangeInclusive<BlockNumber>> {
+    let (from, to) = value.split_once('-').unwrap_or((value, value));
+    let (from, to): (BlockNumber, BlockNumber) = (from.trim().parse()?, to.trim().parse()?);
+    if from > to {
+        eyre::bail!("block range {value} starts above its end")
+    }
+    Ok(from..=to)
+}
+
 /// Error thrown while parsing a socket address.
 #[derive(thiserror::Error, Debug)]
 pub enum SocketAddressParsingError {
@@ -142,6 +286,14 @@ mod tests {
     use proptest::prelude::Rng;
     use secp256k1::rand::thread_rng;
 
+    #[test]
+    fn parse_block_range() {
+        assert_eq!(block_range_value_parser("100-200").unwrap(), 100..=200);
+        assert_eq!(block_range_value_parser("985").unwrap(), 985..=985);
+        assert!(block_range_value_parser("200-100").is_err());
+        assert!(block_range_value_parser("100-").is_err());
+    }
+
     #[test]
     fn parse_known_chain_spec() {
         for chain in SUPPORTED_CHAINS {
@@ -149,6 +301,108 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(feature = "optimism")]
+    fn parse_op_chain_aliases() {
+        assert_eq!(chain_value_parser("optimism-mainnet").unwrap(), OP_MAINNET.clone());
+        assert_eq!(chain_value_parser("optimism_mainnet").unwrap(), OP_MAINNET.clone());
+        assert_eq!(chain_value_parser("base-mainnet").unwrap(), BASE_MAINNET.clone());
+        assert_eq!(chain_value_parser("base_mainnet").unwrap(), BASE_MAINNET.clone());
+    }
+
+    #[test]
+    fn parse_geth_genesis() {
+        let spec = genesis_value_parser(
+            r#"{
+                "config": {
+                    "chainId": 1337,
+                    "homesteadBlock": 0,
+                    "eip150Block": 0,
+                    "eip155Block": 0,
+                    "eip158Block": 0,
+                    "byzantiumBlock": 0,
+                    "constantinopleBlock": 0,
+                    "petersburgBlock": 0,
+                    "istanbulBlock": 0,
+                    "berlinBlock": 0,
+                    "londonBlock": 0,
+                    "terminalTotalDifficulty": 0,
+                    "terminalTotalDifficultyPassed": true,
+                    "shanghaiTime": 0,
+                    "cancunTime": 10
+                },
+                "difficulty": "0x0",
+                "gasLimit": "0x1c9c380",
+                "alloc": {}
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(spec.chain.id(), 1337);
+        assert!(spec.is_cancun_active_at_timestamp(10));
+    }
+
+    #[test]
+    fn reject_unsupported_genesis_config() {
+        let genesis = |config: &str| format!(r#"{{"config": {config}, "alloc": {{}}}}"#);
+
+        let err = genesis_value_parser(&genesis(r#"{"chainId": 1, "osakaTime": 0}"#)).unwrap_err();
+        assert!(err.to_string().contains("osakaTime"));
+
+        let err = genesis_value_parser(&genesis(
+            r#"{"chainId": 1, "daoForkBlock": 10, "daoForkSupport": false}"#,
+        ))
+        .unwrap_err();
+        assert!(err.to_string().contains("daoForkSupport"));
+
+        let err = genesis_value_parser(&genesis(
+            r#"{"chainId": 1, "shanghaiTime": 10, "cancunTime": 5}"#,
+        ))
+        .unwrap_err();
+        assert!(err.to_string().contains("Cancun"));
+
+        // amounts of wei above 2^64 must be strings
+        let err = genesis_value_parser(&genesis(
+            r#"{"chainId": 1, "blockRewards": {"blockReward": 100000000000000000000}}"#,
+        ))
+        .unwrap_err();
+        assert!(err.to_string().contains("blockRewards"));
+        let spec = genesis_value_parser(&genesis(
+            r#"{"chainId": 1, "blockRewards": {"blockReward": "100000000000000000000"}}"#,
+        ))
+        .unwrap();
+        assert_eq!(spec.block_rewards.block_reward, Some(100_000_000_000_000_000_000));
+    }
+
+    #[test]
+    #[cfg(feature = "optimism")]
+    fn parse_op_genesis() {
+        let spec = genesis_value_parser(
+            r#"{
+                "config": {
+                    "chainId": 901,
+                    "bedrockBlock": 0,
+                    "regolithTime": 0,
+                    "canyonTime": 10,
+                    "optimism": {
+                        "eip1559Elasticity": 6,
+                        "eip1559Denominator": 50,
+                        "eip1559DenominatorCanyon": 250
+                    }
+                },
+                "alloc": {}
+            }"#,
+        )
+        .unwrap();
+        assert!(spec.is_optimism());
+        assert_eq!(spec.base_fee_params_at_timestamp(10).max_change_denominator, 250);
+
+        let err = genesis_value_parser(
+            r#"{"config": {"chainId": 901, "optimism": {"eip1559Denominator": 50}}, "alloc": {}}"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("eip1559Elasticity"));
+    }
+
     #[test]
     fn parse_socket_addresses() {
         for value in ["localhost:9000", ":9000", "9000"] {
@@ -2,8 +2,13 @@
 
 use reth_network::protocol::IntoRlpxSubProtocol;
 use reth_primitives::Bytes;
-use reth_transaction_pool::PoolConfig;
-use std::{borrow::Cow, time::Duration};
+use reth_transaction_pool::{
+    blobstore::DiskFileBlobStoreConfig,
+    maintain::{LocalTransactionBackupConfig, MaintainPoolConfig},
+    validate::EthTransactionValidatorBuilder,
+    PoolConfig,
+};
+use std::{borrow::Cow, path::PathBuf, time::Duration};
 
 /// A trait that provides payload builder settings.
 ///
@@ -64,4 +69,23 @@ impl<C> RethNetworkConfig for reth_network::NetworkManager<C> {
 pub trait RethTransactionPoolConfig {
     /// Returns transaction pool configuration.
     fn pool_config(&self) -> PoolConfig;
+
+    /// Applies the configured validation rules to the given transaction validator builder.
+    fn configure_validator(
+        &self,
+        builder: EthTransactionValidatorBuilder,
+    ) -> EthTransactionValidatorBuilder;
+
+    /// Returns the configuration of the on-disk blob store.
+    fn blob_store_config(&self) -> DiskFileBlobStoreConfig;
+
+    /// Returns the configuration of the task that maintains the transaction pool.
+    fn maintain_config(&self) -> MaintainPoolConfig;
+
+    /// Returns the configuration of the journal of local transactions, which is written to the
+    /// given path.
+    fn local_transactions_backup_config(
+        &self,
+        transactions_path: PathBuf,
+    ) -> LocalTransactionBackupConfig;
 }
@@ -0,0 +1,276 @@
+//! Diagnostic reports written when a command panics or fails with a fatal error.
+//!
+//! A report contains the build info, the chain, the recent log lines and, once a database was
+//! opened, the stage checkpoints and table statistics. Reports are written to the
+//! [`CRASH_REPORTS_DIR`] directory of the datadir, so they can be attached to bug reports.
+
+use crate::{dirs::ChainPath, version};
+use reth_chainspec::ChainSpec;
+use reth_db::tables;
+use reth_db_api::{database::Database, database_metrics::DatabaseMetrics, transaction::DbTx};
+use reth_primitives::keccak256;
+use reth_stages_types::StageId;
+use reth_tracing::LogBuffer;
+use std::{
+    any::Any,
+    backtrace::Backtrace,
+    fmt::Write as _,
+    io,
+    panic::{self, Location},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc, Mutex, OnceLock, PoisonError,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// The name of the directory in the datadir that crash reports are written to.
+pub const CRASH_REPORTS_DIR: &str = "crash-reports";
+
+/// The number of recent log lines included in a crash report.
+pub const CRASH_REPORT_LOG_LINES: usize = 500;
+
+/// The time given to read the database section of a report, which may hang if the database is in
+/// a broken state.
+const DATABASE_SECTION_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Recent log lines, see [`log_buffer`].
+static LOGS: OnceLock<LogBuffer> = OnceLock::new();
+
+/// The context of the running command, set once it resolved its datadir.
+static CONTEXT: Mutex<Option<CrashReportContext>> = Mutex::new(None);
+
+/// Set while a report is written, so a panic while writing it doesn't write another one.
+static WRITING: AtomicBool = AtomicBool::new(false);
+
+/// Writes the database section of a report.
+type DatabaseSection = Arc<dyn Fn() -> String + Send + Sync>;
+
+struct CrashReportContext {
+    /// The directory the reports are written to.
+    report_dir: PathBuf,
+    /// The chain the command runs on.
+    chain_spec: Arc<ChainSpec>,
+    /// The database of the command, if it was opened.
+    database: Option<DatabaseSection>,
+}
+
+/// Returns the buffer of recent log lines that is included in crash reports.
+///
+/// The buffer is written to by the logging layer installed with
+/// [`LogArgs`](crate::args::LogArgs).
+pub fn log_buffer() -> LogBuffer {
+    LOGS.get_or_init(|| LogBuffer::new(CRASH_REPORT_LOG_LINES)).clone()
+}
+
+/// Writes crash reports to the given datadir, and includes the chain in them.
+///
+/// Crash reports are only written once the datadir of the command is known.
+pub fn set_datadir<D>(datadir: &ChainPath<D>, chain_spec: Arc<ChainSpec>) {
+    let mut context = CONTEXT.lock().unwrap_or_else(PoisonError::into_inner);
+    *context = Some(CrashReportContext {
+        report_dir: datadir.data_dir().join(CRASH_REPORTS_DIR),
+        chain_spec,
+        database: None,
+    });
+}
+
+/// Includes the stage checkpoints and table statistics of the database in crash reports.
+///
+/// Has no effect until [`set_datadir`] was called.
+pub fn set_database<DB>(db: DB)
+where
+    DB: Database + DatabaseMetrics + 'static,
+{
+    let mut context = CONTEXT.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(context) = context.as_mut() {
+        context.database = Some(Arc::new(move || database_section(&db)));
+    }
+}
+
+/// Installs a panic hook that writes a crash report after the previously installed hook ran.
+pub fn install_panic_hook() {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        previous(info);
+
+        let message = panic_message(info.payload(), info.location());
+        let details = format!("{message}\n\n{}", Backtrace::force_capture());
+        report_written(write_report("panic", &details));
+    }));
+}
+
+/// Writes a crash report about a fatal error of a command.
+///
+/// Errors are only reported once the command opened its database, so invalid arguments or
+/// configurations don't leave a report behind.
+pub fn write_error_report(err: &eyre::Report) {
+    let has_database = CONTEXT
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .as_ref()
+        .is_some_and(|context| context.database.is_some());
+    if has_database {
+        report_written(write_report("error", &format!("{err:?}")));
+    }
+}
+
+/// Prints the path of a written report, or why it couldn't be written.
+fn report_written(result: io::Result<Option<PathBuf>>) {
+    match result {
+        Ok(Some(path)) => eprintln!(
+            "A crash report was written to {}, please attach it when reporting the issue at \
+             https://github.com/paradigmxyz/reth/issues/new",
+            path.display()
+        ),
+        Ok(None) => {}
+        Err(err) => eprintln!("Failed to write the crash report: {err}"),
+    }
+}
+
+/// Writes a report of the given kind, returning its path.
+///
+/// Returns `None` if no datadir is set, or another report is currently written.
+fn write_report(kind: &str, details: &str) -> io::Result<Option<PathBuf>> {
+    if WRITING.swap(true, Ordering::SeqCst) {
+        return Ok(None)
+    }
+    let result = (|| {
+        // the lock isn't held while the database is read, so a hanging read can't block it
+        let context =
+            CONTEXT.lock().unwrap_or_else(PoisonError::into_inner).as_ref().map(|context| {
+                (context.report_dir.clone(), context.chain_spec.clone(), context.database.clone())
+            });
+        let Some((report_dir, chain_spec, database)) = context else { return Ok(None) };
+
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let contents = render_report(kind, details, timestamp, &chain_spec, database);
+
+        std::fs::create_dir_all(&report_dir)?;
+        let path = report_dir.join(format!("{kind}-{timestamp}.txt"));
+        std::fs::write(&path, contents)?;
+        Ok(Some(path))
+    })();
+    WRITING.store(false, Ordering::SeqCst);
+    result
+}
+
+/// Renders the contents of a report.
+fn render_report(
+    kind: &str,
+    details: &str,
+    timestamp: u64,
+    chain_spec: &ChainSpec,
+    database: Option<DatabaseSection>,
+) -> String {
+    let hardforks = chain_spec.display_hardforks().to_string();
+    let chain_spec_hash = keccak256(
+        format!("{}\n{}\n{hardforks}", chain_spec.chain, chain_spec.genesis_hash()).as_bytes(),
+    );
+
+    let mut report = String::new();
+    let _ = writeln!(report, "# reth crash report ({kind})\n");
+    let _ = writeln!(report, "timestamp: {timestamp}");
+    let _ = writeln!(report, "{}\n", version::LONG_VERSION);
+
+    let _ =
+        writeln!(report, "## {}\n\n{details}\n", if kind == "panic" { "Panic" } else { "Error" });
+
+    let _ = writeln!(report, "## Chain\n");
+    let _ = writeln!(report, "chain: {}", chain_spec.chain);
+    let _ = writeln!(report, "genesis: {}", chain_spec.genesis_hash());
+    let _ = writeln!(report, "chain spec hash: {chain_spec_hash}");
+    let _ = writeln!(report, "{hardforks}");
+
+    if let Some(database) = database {
+        let _ = writeln!(report, "## Database\n");
+        let (tx, rx) = mpsc::channel();
+        let _ = std::thread::Builder::new()
+            .name("crash-report".to_string())
+            .spawn(move || tx.send(database()));
+        match rx.recv_timeout(DATABASE_SECTION_TIMEOUT) {
+            Ok(section) => report.push_str(&section),
+            Err(_) => report.push_str("failed to read the database in time\n"),
+        }
+        report.push('\n');
+    }
+
+    let lines = log_buffer().last_lines(CRASH_REPORT_LOG_LINES);
+    let _ = writeln!(report, "## Last {} log lines\n", lines.len());
+    for line in lines {
+        let _ = writeln!(report, "{line}");
+    }
+    report
+}
+
+/// Returns the stage checkpoints and table statistics of the database.
+fn database_section<DB: Database + DatabaseMetrics>(db: &DB) -> String {
+    let mut section = String::new();
+    match db.tx() {
+        Ok(tx) => {
+            for stage_id in StageId::ALL {
+                match tx.get::<tables::StageCheckpoints>(stage_id) {
+                    Ok(Some(checkpoint)) => {
+                        let _ = writeln!(section, "{stage_id}: {}", checkpoint.block_number);
+                    }
+                    Ok(None) => {
+                        let _ = writeln!(section, "{stage_id}: none");
+                    }
+                    Err(err) => {
+                        let _ = writeln!(section, "{stage_id}: {err}");
+                    }
+                }
+            }
+        }
+        Err(err) => {
+            let _ = writeln!(section, "failed to read the stage checkpoints: {err}");
+        }
+    }
+
+    section.push('\n');
+    for (name, value, labels) in db.gauge_metrics() {
+        let labels = labels
+            .iter()
+            .map(|label| format!("{}={}", label.key(), label.value()))
+            .collect::<Vec<_>>()
+            .join(",");
+        let _ = writeln!(section, "{name}{{{labels}}} {value}");
+    }
+    section
+}
+
+/// Returns the message and location of a panic.
+fn panic_message(payload: &(dyn Any + Send), location: Option<&Location<'_>>) -> String {
+    let payload = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("Box<dyn Any>");
+    let thread = std::thread::current();
+    let location = location.map(ToString::to_string).unwrap_or_default();
+    format!("thread '{}' panicked at {location}:\n{payload}", thread.name().unwrap_or("<unnamed>"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chainspec::MAINNET;
+
+    #[test]
+    fn render_report_sections() {
+        let report = render_report(
+            "error",
+            "stage Execution failed",
+            1_700_000_000,
+            &MAINNET,
+            Some(Arc::new(|| "Headers: 100\n".to_string())),
+        );
+
+        assert!(report.starts_with("# reth crash report (error)\n"));
+        assert!(report.contains("## Error\n\nstage Execution failed\n"));
+        assert!(report.contains(&format!("genesis: {}\n", MAINNET.genesis_hash())));
+        assert!(report.contains("## Database\n\nHeaders: 100\n"));
+        assert!(report.contains("log lines"));
+    }
+}
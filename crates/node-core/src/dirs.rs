@@ -2,6 +2,7 @@
 
 use crate::{args::DatadirArgs, utils::parse_path};
 use reth_chainspec::Chain;
+use reth_primitives::B256;
 use std::{
     env::VarError,
     fmt::{Debug, Display, Formatter},
@@ -16,8 +17,21 @@ pub fn config_path_prefix(chain: Chain) -> String {
 
 /// Returns the path to the reth data directory.
 ///
-/// Refer to [`dirs_next::data_dir`] for cross-platform behavior.
+/// - Linux: `$XDG_DATA_HOME/reth/` or `$HOME/.local/share/reth/`
+/// - Windows: `{FOLDERID_LocalAppData}/reth/`
+/// - macOS: `$HOME/Library/Application Support/reth/`
+///
+/// On Windows, a data directory created in `{FOLDERID_RoamingAppData}/reth/` by earlier versions
+/// is still used if it exists.
 pub fn data_dir() -> Option<PathBuf> {
+    if cfg!(windows) {
+        // the chain data is too large for a roaming profile, which is synced between machines
+        let roaming = dirs_next::data_dir().map(|root| root.join("reth"));
+        if roaming.as_ref().is_some_and(|dir| dir.exists()) {
+            return roaming
+        }
+        return dirs_next::data_local_dir().map(|root| root.join("reth"))
+    }
     dirs_next::data_dir().map(|root| root.join("reth"))
 }
 
@@ -44,8 +58,13 @@ pub fn cache_dir() -> Option<PathBuf> {
 
 /// Returns the path to the reth logs directory.
 ///
-/// Refer to [`dirs_next::cache_dir`] for cross-platform behavior.
+/// - Linux: `$XDG_CACHE_HOME/reth/logs/` or `$HOME/.cache/reth/logs/`
+/// - Windows: `{FOLDERID_LocalAppData}/reth/logs/`
+/// - macOS: `$HOME/Library/Logs/reth/`, where the logs are found by the Console app
 pub fn logs_dir() -> Option<PathBuf> {
+    if cfg!(target_os = "macos") {
+        return dirs_next::home_dir().map(|home| home.join("Library").join("Logs").join("reth"))
+    }
     cache_dir().map(|root| root.join("logs"))
 }
 
@@ -65,7 +84,7 @@ impl XdgPath for DataDirPath {
 
 /// Returns the path to the reth logs directory.
 ///
-/// Refer to [`dirs_next::cache_dir`] for cross-platform behavior.
+/// Refer to [`logs_dir`] for cross-platform behavior.
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
 #[non_exhaustive]
 pub struct LogsDir;
@@ -168,6 +187,11 @@ impl<D> PlatformPath<D> {
         Self(path, std::marker::PhantomData)
     }
 
+    /// Returns `true` if the path has no database, but its subdirectory for the chain has one.
+    fn has_chain_db(&self, chain: Chain) -> bool {
+        !self.0.join("db").exists() && self.platform_path_from_chain(chain).0.join("db").exists()
+    }
+
     /// Map the inner path to a new type `T`.
     pub fn map_to<T>(&self) -> PlatformPath<T> {
         PlatformPath(self.0.clone(), std::marker::PhantomData)
@@ -184,14 +208,19 @@ pub struct MaybePlatformPath<D>(Option<PlatformPath<D>>);
 
 impl<D: XdgPath> MaybePlatformPath<D> {
     /// Returns the path if it is set, otherwise returns the default path for the given chain.
+    ///
+    /// A set path is resolved to the subdirectory of the chain if
+    /// [`DatadirArgs::per_chain`] is enabled, or if the path has no database but the subdirectory
+    /// of the chain has one.
     pub fn unwrap_or_chain_default(&self, chain: Chain, datadir_args: DatadirArgs) -> ChainPath<D> {
-        ChainPath(
-            self.0
-                .clone()
-                .unwrap_or_else(|| PlatformPath::default().platform_path_from_chain(chain)),
-            chain,
-            datadir_args,
-        )
+        let path = match &self.0 {
+            Some(path) if datadir_args.per_chain || path.has_chain_db(chain) => {
+                path.platform_path_from_chain(chain)
+            }
+            Some(path) => path.clone(),
+            None => PlatformPath::default().platform_path_from_chain(chain),
+        };
+        ChainPath(path, chain, datadir_args)
     }
 
     /// Returns the default platform path for the specified [Chain].
@@ -282,6 +311,35 @@ impl<D> ChainPath<D> {
         self.0.as_ref()
     }
 
+    /// Checks that the storage of this data dir belongs to the chain with the given genesis hash,
+    /// given the genesis hash that is stored in it, if any.
+    ///
+    /// A shared `--datadir` root without [`DatadirArgs::per_chain`] resolves to the same database
+    /// for every chain, so a second chain would otherwise open the database of the first one.
+    pub fn ensure_genesis(
+        &self,
+        genesis_hash: B256,
+        stored_genesis_hash: Option<B256>,
+    ) -> eyre::Result<()> {
+        let Some(stored) = stored_genesis_hash.filter(|stored| *stored != genesis_hash) else {
+            return Ok(())
+        };
+        let chain_dir = config_path_prefix(self.1);
+        let hint = if self.data_dir().ends_with(&chain_dir) {
+            String::new()
+        } else {
+            format!(
+                ", use a separate --datadir or --datadir.per-chain to store it in {}",
+                self.data_dir().join(&chain_dir).display()
+            )
+        };
+        eyre::bail!(
+            "the database in {} belongs to the chain with genesis {stored}, not to {} with genesis {genesis_hash}{hint}",
+            self.data_dir().display(),
+            self.1
+        )
+    }
+
     /// Returns the path to the db directory for this chain.
     ///
     /// `<DIR>/<CHAIN_ID>/db`
@@ -289,6 +347,13 @@ impl<D> ChainPath<D> {
         self.data_dir().join("db")
     }
 
+    /// Returns the path to the directory of the execution extension checkpoints for this chain.
+    ///
+    /// `<DIR>/<CHAIN_ID>/exex`
+    pub fn exex(&self) -> PathBuf {
+        self.data_dir().join("exex")
+    }
+
     /// Returns the path to the static files directory for this chain.
     ///
     /// `<DIR>/<CHAIN_ID>/static_files`
@@ -301,6 +366,11 @@ impl<D> ChainPath<D> {
         }
     }
 
+    /// Returns the additional read-only directories of static files.
+    pub fn read_only_static_files(&self) -> &[PathBuf] {
+        &self.2.read_only_static_files
+    }
+
     /// Returns the path to the reth p2p secret key for this chain.
     ///
     /// `<DIR>/<CHAIN_ID>/discovery-secret`
@@ -330,6 +400,20 @@ impl<D> ChainPath<D> {
         self.data_dir().join("txpool-transactions-backup.rlp")
     }
 
+    /// Returns the path to the file the installed RPC filters are persisted to
+    ///
+    /// `<DIR>/<CHAIN_ID>/rpc-filters.json`
+    pub fn rpc_filters(&self) -> PathBuf {
+        self.data_dir().join("rpc-filters.json")
+    }
+
+    /// Returns the path to the directory the traces of slowly executed blocks are captured into.
+    ///
+    /// `<DIR>/<CHAIN_ID>/slow-blocks`
+    pub fn slow_blocks(&self) -> PathBuf {
+        self.data_dir().join("slow-blocks")
+    }
+
     /// Returns the path to the config file for this chain.
     ///
     /// `<DIR>/<CHAIN_ID>/reth.toml`
@@ -395,4 +479,34 @@ mod tests {
         let path = path.unwrap_or_chain_default(Chain::sepolia(), DatadirArgs::default());
         assert!(path.as_ref().ends_with("reth/sepolia"), "{path:?}");
     }
+
+    #[test]
+    fn test_per_chain_datadir_path() {
+        let root = tempfile::tempdir().unwrap();
+        let datadir = MaybePlatformPath::<DataDirPath>::from(root.path().to_path_buf());
+
+        let args = DatadirArgs { per_chain: true, ..Default::default() };
+        let path = datadir.unwrap_or_chain_default(Chain::mainnet(), args);
+        assert_eq!(path.data_dir(), root.path().join("mainnet"));
+        assert_eq!(path.db(), root.path().join("mainnet/db"));
+
+        // resolved to the chain subdirectory without the flag once it has a database
+        std::fs::create_dir_all(root.path().join("sepolia/db")).unwrap();
+        let path = datadir.unwrap_or_chain_default(Chain::sepolia(), DatadirArgs::default());
+        assert_eq!(path.data_dir(), root.path().join("sepolia"));
+        let path = datadir.unwrap_or_chain_default(Chain::mainnet(), DatadirArgs::default());
+        assert_eq!(path.data_dir(), root.path());
+
+        // a database in the root takes precedence
+        std::fs::create_dir_all(root.path().join("db")).unwrap();
+        let path = datadir.unwrap_or_chain_default(Chain::sepolia(), DatadirArgs::default());
+        assert_eq!(path.data_dir(), root.path());
+
+        // but only if it belongs to the chain
+        let genesis = B256::with_last_byte(1);
+        assert!(path.ensure_genesis(genesis, None).is_ok());
+        assert!(path.ensure_genesis(genesis, Some(genesis)).is_ok());
+        let err = path.ensure_genesis(genesis, Some(B256::with_last_byte(2))).unwrap_err();
+        assert!(err.to_string().contains("--datadir.per-chain"), "{err}");
+    }
 }
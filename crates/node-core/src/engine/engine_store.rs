@@ -1,23 +1,28 @@
 //! Stores engine API messages to disk for later inspection and replay.
 
 use futures::{Stream, StreamExt};
-use reth_beacon_consensus::BeaconEngineMessage;
+use reth_beacon_consensus::{BeaconEngineMessage, ForkchoiceStatus};
 use reth_engine_primitives::EngineTypes;
 use reth_fs_util as fs;
 use reth_rpc_types::{
-    engine::{CancunPayloadFields, ForkchoiceState},
+    engine::{CancunPayloadFields, ForkchoiceState, PayloadStatus},
     ExecutionPayload,
 };
 use serde::{Deserialize, Serialize};
 use std::{
     collections::BTreeMap,
-    path::PathBuf,
+    path::{Path, PathBuf},
     pin::Pin,
     task::{ready, Context, Poll},
     time::SystemTime,
 };
+use tokio::sync::oneshot;
 use tracing::*;
 
+/// The subdirectory of the store that the responses to the messages are stored in, under the
+/// filenames of the messages.
+const RESPONSES_DIR: &str = "responses";
+
 /// A message from the engine API that has been stored to disk.
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -38,8 +43,51 @@ pub enum StoredEngineApiMessage<Attributes> {
     },
 }
 
+/// The response of the engine to an engine API message that has been stored to disk.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StoredEngineApiResponse {
+    /// The on-disk representation of the response to an `engine_forkchoiceUpdated` method call.
+    ForkchoiceUpdated {
+        /// The status of the forkchoice state.
+        status: StoredForkchoiceStatus,
+    },
+    /// The on-disk representation of the response to an `engine_newPayload` method call.
+    NewPayload {
+        /// The status of the payload.
+        status: PayloadStatus,
+    },
+    /// The error the method call failed with.
+    Error {
+        /// The error message.
+        message: String,
+    },
+}
+
+/// The on-disk representation of a [`ForkchoiceStatus`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StoredForkchoiceStatus {
+    /// The forkchoice state is valid.
+    Valid,
+    /// The forkchoice state is invalid.
+    Invalid,
+    /// The forkchoice state is unknown.
+    Syncing,
+}
+
+impl From<ForkchoiceStatus> for StoredForkchoiceStatus {
+    fn from(status: ForkchoiceStatus) -> Self {
+        match status {
+            ForkchoiceStatus::Valid => Self::Valid,
+            ForkchoiceStatus::Invalid => Self::Invalid,
+            ForkchoiceStatus::Syncing => Self::Syncing,
+        }
+    }
+}
+
 /// This can read and write engine API messages in a specific directory.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct EngineMessageStore {
     /// The path to the directory that stores the engine API messages.
     path: PathBuf,
@@ -55,31 +103,34 @@ impl EngineMessageStore {
 
     /// Stores the received [`BeaconEngineMessage`] to disk, appending the `received_at` time to the
     /// path.
+    ///
+    /// Returns the filename the message was stored under, if it was stored.
     pub fn on_message<Engine>(
         &self,
         msg: &BeaconEngineMessage<Engine>,
         received_at: SystemTime,
-    ) -> eyre::Result<()>
+    ) -> eyre::Result<Option<String>>
     where
         Engine: EngineTypes,
     {
         fs::create_dir_all(&self.path)?; // ensure that store path had been created
         let timestamp = received_at.duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis();
-        match msg {
+        let filename = match msg {
             BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs, tx: _tx } => {
                 let filename = format!("{}-fcu-{}.json", timestamp, state.head_block_hash);
                 fs::write(
-                    self.path.join(filename),
+                    self.path.join(&filename),
                     serde_json::to_vec(&StoredEngineApiMessage::ForkchoiceUpdated {
                         state: *state,
                         payload_attrs: payload_attrs.clone(),
                     })?,
                 )?;
+                filename
             }
             BeaconEngineMessage::NewPayload { payload, cancun_fields, tx: _tx } => {
                 let filename = format!("{}-new_payload-{}.json", timestamp, payload.block_hash());
                 fs::write(
-                    self.path.join(filename),
+                    self.path.join(&filename),
                     serde_json::to_vec(
                         &StoredEngineApiMessage::<Engine::PayloadAttributes>::NewPayload {
                             payload: payload.clone(),
@@ -87,19 +138,98 @@ impl EngineMessageStore {
                         },
                     )?,
                 )?;
+                filename
             }
             // noop
-            BeaconEngineMessage::TransitionConfigurationExchanged => (),
+            BeaconEngineMessage::TransitionConfigurationExchanged => return Ok(None),
         };
+        Ok(Some(filename))
+    }
+
+    /// Stores the response of the engine to the message stored under the given filename.
+    pub fn on_response(
+        &self,
+        filename: &str,
+        response: &StoredEngineApiResponse,
+    ) -> eyre::Result<()> {
+        let responses = self.path.join(RESPONSES_DIR);
+        fs::create_dir_all(&responses)?;
+        fs::write(responses.join(filename), serde_json::to_vec(response)?)?;
         Ok(())
     }
 
+    /// Returns the stored response to the message at the given path, if the response was
+    /// recorded.
+    pub fn response(&self, message_path: &Path) -> eyre::Result<Option<StoredEngineApiResponse>> {
+        let Some(filename) = message_path.file_name() else { return Ok(None) };
+        let path = self.path.join(RESPONSES_DIR).join(filename);
+        if !path.exists() {
+            return Ok(None)
+        }
+        Ok(Some(serde_json::from_slice(&fs::read(path)?)?))
+    }
+
+    /// Replaces the response sender of the message with one that stores the response under the
+    /// given filename, before forwarding it to the original sender.
+    fn record_response<Engine>(
+        &self,
+        msg: BeaconEngineMessage<Engine>,
+        filename: String,
+    ) -> BeaconEngineMessage<Engine>
+    where
+        Engine: EngineTypes,
+    {
+        let store = self.clone();
+        match msg {
+            BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs, tx } => {
+                let (response_tx, response_rx) = oneshot::channel();
+                tokio::spawn(async move {
+                    let Ok(res) = response_rx.await else { return };
+                    let response = match &res {
+                        Ok(on_updated) => StoredEngineApiResponse::ForkchoiceUpdated {
+                            status: on_updated.forkchoice_status().into(),
+                        },
+                        Err(err) => StoredEngineApiResponse::Error { message: err.to_string() },
+                    };
+                    store.store_response(&filename, &response);
+                    let _ = tx.send(res);
+                });
+                BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs, tx: response_tx }
+            }
+            BeaconEngineMessage::NewPayload { payload, cancun_fields, tx } => {
+                let (response_tx, response_rx) = oneshot::channel();
+                tokio::spawn(async move {
+                    let Ok(res) = response_rx.await else { return };
+                    let response = match &res {
+                        Ok(status) => {
+                            StoredEngineApiResponse::NewPayload { status: status.clone() }
+                        }
+                        Err(err) => StoredEngineApiResponse::Error { message: err.to_string() },
+                    };
+                    store.store_response(&filename, &response);
+                    let _ = tx.send(res);
+                });
+                BeaconEngineMessage::NewPayload { payload, cancun_fields, tx: response_tx }
+            }
+            msg => msg,
+        }
+    }
+
+    fn store_response(&self, filename: &str, response: &StoredEngineApiResponse) {
+        if let Err(error) = self.on_response(filename, response) {
+            error!(target: "engine::intercept", ?response, %error, "Error storing Engine API response");
+        }
+    }
+
     /// Finds and iterates through any stored engine API message files, ordered by timestamp.
     pub fn engine_messages_iter(&self) -> eyre::Result<impl Iterator<Item = PathBuf>> {
         let mut filenames_by_ts = BTreeMap::<u64, Vec<PathBuf>>::default();
         for entry in fs::read_dir(&self.path)? {
             let entry = entry?;
             let filename = entry.file_name();
+            if entry.file_type()?.is_dir() {
+                continue
+            }
             if let Some(filename) = filename.to_str().filter(|n| n.ends_with(".json")) {
                 if let Some(Ok(timestamp)) = filename.split('-').next().map(|n| n.parse::<u64>()) {
                     filenames_by_ts.entry(timestamp).or_default().push(entry.path());
@@ -144,11 +274,14 @@ where
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let mut this = self.project();
         let next = ready!(this.stream.poll_next_unpin(cx));
-        if let Some(msg) = &next {
-            if let Err(error) = this.store.on_message(msg, SystemTime::now()) {
+        let next = next.map(|msg| match this.store.on_message(&msg, SystemTime::now()) {
+            Ok(Some(filename)) => this.store.record_response(msg, filename),
+            Ok(None) => msg,
+            Err(error) => {
                 error!(target: "engine::intercept", ?msg, %error, "Error handling Engine API message");
+                msg
             }
-        }
+        });
         Poll::Ready(next)
     }
 }
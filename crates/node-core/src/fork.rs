@@ -0,0 +1,110 @@
+//! Fetching of the state of a forked chain from its RPC node.
+
+use crate::proxy::{build_http_client, ProxiedHttpClient, Socks5Proxy};
+use reth_primitives::{
+    keccak256, Account, Address, BlockId, BlockNumber, Bytecode, StorageKey, StorageValue,
+};
+use reth_provider::providers::ForkStateSource;
+use reth_rpc_api::EthApiClient;
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use std::{fmt, future::Future, sync::mpsc};
+use tokio::runtime::Handle;
+
+/// A [`ForkStateSource`] that fetches the state of the forked chain from its RPC node, at a fixed
+/// block.
+///
+/// The state is read from blocking contexts, so every request is spawned on the runtime and
+/// awaited synchronously.
+#[derive(Clone)]
+pub struct RpcForkState {
+    url: String,
+    client: ProxiedHttpClient,
+    block: BlockNumber,
+    handle: Handle,
+}
+
+impl RpcForkState {
+    /// Connects to the RPC node at the given URL and forks it at the given block, or at its latest
+    /// block if none is given. Connections are routed through the given proxy, if any.
+    ///
+    /// Must be called from within a tokio runtime.
+    pub async fn connect(
+        url: &str,
+        block: Option<BlockNumber>,
+        proxy: Option<&Socks5Proxy>,
+    ) -> eyre::Result<Self> {
+        let client = build_http_client(url, proxy)?;
+        let block = match block {
+            Some(block) => block,
+            None => EthApiClient::block_number(&client).await?.to(),
+        };
+        Ok(Self { url: url.to_string(), client, block, handle: Handle::current() })
+    }
+
+    /// Returns the URL of the RPC node.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn block_id(&self) -> Option<BlockId> {
+        Some(self.block.into())
+    }
+
+    /// Runs the request on the runtime and waits for its result.
+    fn request<T, F>(&self, request: F) -> ProviderResult<T>
+    where
+        T: Send + 'static,
+        F: Future<Output = Result<T, jsonrpsee::core::client::Error>> + Send + 'static,
+    {
+        let (tx, rx) = mpsc::sync_channel(1);
+        self.handle.spawn(async move {
+            let _ = tx.send(request.await);
+        });
+        rx.recv()
+            .map_err(|_| ProviderError::ForkState("request was cancelled".to_string()))?
+            .map_err(|err| ProviderError::ForkState(err.to_string()))
+    }
+}
+
+impl fmt::Debug for RpcForkState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RpcForkState").field("url", &self.url).field("block", &self.block).finish()
+    }
+}
+
+impl ForkStateSource for RpcForkState {
+    fn block(&self) -> BlockNumber {
+        self.block
+    }
+
+    fn account(&self, address: Address) -> ProviderResult<Option<(Account, Option<Bytecode>)>> {
+        let client = self.client.clone();
+        let block = self.block_id();
+        let (balance, nonce, code) = self.request(async move {
+            let balance = EthApiClient::balance(&client, address, block).await?;
+            let nonce = EthApiClient::transaction_count(&client, address, block).await?;
+            let code = EthApiClient::get_code(&client, address, block).await?;
+            Ok((balance, nonce, code))
+        })?;
+
+        let bytecode = (!code.is_empty()).then(|| Bytecode::new_raw(code));
+        let account = Account {
+            nonce: nonce.to(),
+            balance,
+            bytecode_hash: bytecode.as_ref().map(|code| keccak256(code.original_bytes())),
+        };
+        if account.is_empty() {
+            return Ok(None)
+        }
+        Ok(Some((account, bytecode)))
+    }
+
+    fn storage(&self, address: Address, key: StorageKey) -> ProviderResult<StorageValue> {
+        let client = self.client.clone();
+        let block = self.block_id();
+        let value = self.request(async move {
+            EthApiClient::storage_at(&client, address, key.into(), block).await
+        })?;
+        Ok(StorageValue::from_be_bytes(value.0))
+    }
+}
@@ -0,0 +1,303 @@
+//! Datadir layout versioning.
+//!
+//! The layout of the files and directories in a datadir is versioned, so that a datadir with an
+//! outdated layout is refused with a clear upgrade path instead of failing on missing files. The
+//! version is stored in the [`LAYOUT_VERSION_FILE_NAME`] file in the datadir of a chain, and
+//! outdated datadirs are upgraded with [`upgrade_layout`].
+
+use crate::dirs::ChainPath;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// The name of the file that contains the layout version of the datadir.
+pub const LAYOUT_VERSION_FILE_NAME: &str = "layout.version";
+
+/// The layout version of the datadir stored in the [`LAYOUT_VERSION_FILE_NAME`] file.
+///
+/// Versions:
+/// - `1`: static files are stored in `<DIR>/<CHAIN_ID>/snapshots`.
+/// - `2`: static files are stored in `<DIR>/<CHAIN_ID>/static_files`, or the path set with
+///   `--datadir.static_files`.
+pub const LAYOUT_VERSION: u64 = 2;
+
+/// The directory of the static files in datadirs with layout version `1`.
+const LEGACY_STATIC_FILES_DIR: &str = "snapshots";
+
+/// Error when checking or upgrading the layout version of a datadir.
+#[derive(thiserror::Error, Debug)]
+pub enum LayoutVersionError {
+    /// Unable to determine the layout version of the datadir; the file is malformed.
+    #[error("unable to determine the layout version of the datadir, {path} is malformed")]
+    MalformedFile {
+        /// The path to the layout version file.
+        path: PathBuf,
+    },
+    /// The layout of the datadir is outdated and needs to be upgraded.
+    #[error(
+        "the datadir layout (v{version}) is outdated, the latest layout is v{LAYOUT_VERSION}: \
+         upgrade it with `reth datadir upgrade`, using the same --datadir and --chain arguments"
+    )]
+    Outdated {
+        /// The detected layout version of the datadir.
+        version: u64,
+    },
+    /// The layout of the datadir is newer than the latest known layout.
+    #[error(
+        "the datadir layout (v{version}) was created by a newer version of reth, the latest \
+         supported layout is v{LAYOUT_VERSION}"
+    )]
+    Unsupported {
+        /// The detected layout version of the datadir.
+        version: u64,
+    },
+    /// A migration can't move a directory, because its destination already contains files.
+    #[error("unable to move {from} to {to}, the destination is not empty")]
+    DestinationNotEmpty {
+        /// The directory that is moved.
+        from: PathBuf,
+        /// The destination of the directory.
+        to: PathBuf,
+    },
+    /// IO error occurred while accessing the datadir.
+    #[error("IO error occurred while accessing {path}: {err}")]
+    Io {
+        /// The encountered IO error.
+        err: io::Error,
+        /// The path that was accessed.
+        path: PathBuf,
+    },
+}
+
+/// A change of the datadir layout, from [`LayoutMigration::version`] to the next version.
+#[derive(Debug, Clone, Copy)]
+pub struct LayoutMigration {
+    /// The layout version the migration upgrades from.
+    pub version: u64,
+    /// A description of the change.
+    pub description: &'static str,
+    /// Applies the migration, given the datadir of the chain and the static files directory.
+    apply: fn(&Path, &Path) -> Result<(), LayoutVersionError>,
+}
+
+/// All layout migrations, ordered by version.
+const MIGRATIONS: [LayoutMigration; 1] = [LayoutMigration {
+    version: 1,
+    description: "move the static files from the `snapshots` directory to the static files path",
+    apply: move_legacy_static_files,
+}];
+
+/// Returns the layout version of the datadir.
+///
+/// If the datadir has no [`LAYOUT_VERSION_FILE_NAME`] file, the version is detected from its
+/// contents. Datadirs that don't exist yet have the latest version.
+pub fn get_layout_version<D>(datadir: &ChainPath<D>) -> Result<u64, LayoutVersionError> {
+    let path = layout_version_file_path(datadir.data_dir());
+    match fs::read_to_string(&path) {
+        Ok(raw_version) => {
+            raw_version.trim().parse().map_err(|_| LayoutVersionError::MalformedFile { path })
+        }
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            if datadir.data_dir().join(LEGACY_STATIC_FILES_DIR).exists() {
+                Ok(1)
+            } else {
+                Ok(LAYOUT_VERSION)
+            }
+        }
+        Err(err) => Err(LayoutVersionError::Io { err, path }),
+    }
+}
+
+/// Checks that the datadir has the latest layout version.
+///
+/// Returns [`LayoutVersionError::Outdated`] if the datadir needs to be upgraded with
+/// [`upgrade_layout`].
+pub fn check_layout_version<D>(datadir: &ChainPath<D>) -> Result<(), LayoutVersionError> {
+    match get_layout_version(datadir)? {
+        LAYOUT_VERSION => Ok(()),
+        version if version < LAYOUT_VERSION => Err(LayoutVersionError::Outdated { version }),
+        version => Err(LayoutVersionError::Unsupported { version }),
+    }
+}
+
+/// Checks that the datadir has the latest layout version, and records the version in the
+/// [`LAYOUT_VERSION_FILE_NAME`] file if it's missing.
+///
+/// Creates the datadir if it doesn't exist.
+pub fn init_layout_version<D>(datadir: &ChainPath<D>) -> Result<(), LayoutVersionError> {
+    check_layout_version(datadir)?;
+
+    let path = layout_version_file_path(datadir.data_dir());
+    if !path.exists() {
+        create_layout_version_file(datadir.data_dir(), LAYOUT_VERSION)?;
+    }
+    Ok(())
+}
+
+/// Returns the migrations that [`upgrade_layout`] applies to the datadir, in order.
+pub fn pending_migrations<D>(
+    datadir: &ChainPath<D>,
+) -> Result<Vec<LayoutMigration>, LayoutVersionError> {
+    let version = get_layout_version(datadir)?;
+    if version > LAYOUT_VERSION {
+        return Err(LayoutVersionError::Unsupported { version })
+    }
+    Ok(MIGRATIONS.into_iter().filter(|migration| migration.version >= version).collect())
+}
+
+/// Upgrades the datadir to the latest layout version.
+///
+/// The version file is updated after each migration, so an interrupted upgrade continues with the
+/// remaining migrations when it's run again. Returns the applied migrations.
+pub fn upgrade_layout<D>(
+    datadir: &ChainPath<D>,
+) -> Result<Vec<LayoutMigration>, LayoutVersionError> {
+    let migrations = pending_migrations(datadir)?;
+    for migration in &migrations {
+        (migration.apply)(datadir.data_dir(), &datadir.static_files())?;
+        create_layout_version_file(datadir.data_dir(), migration.version + 1)?;
+    }
+
+    if migrations.is_empty() && datadir.data_dir().exists() {
+        init_layout_version(datadir)?;
+    }
+    Ok(migrations)
+}
+
+/// Creates a layout version file with [`LAYOUT_VERSION_FILE_NAME`] name containing the given
+/// version.
+///
+/// This function will create the file and the datadir if they do not exist, and will entirely
+/// replace its contents if it does.
+pub fn create_layout_version_file(data_dir: &Path, version: u64) -> Result<(), LayoutVersionError> {
+    fs::create_dir_all(data_dir)
+        .map_err(|err| LayoutVersionError::Io { err, path: data_dir.to_path_buf() })?;
+
+    let path = layout_version_file_path(data_dir);
+    fs::write(&path, version.to_string()).map_err(|err| LayoutVersionError::Io { err, path })
+}
+
+/// Returns the layout version file path.
+pub fn layout_version_file_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(LAYOUT_VERSION_FILE_NAME)
+}
+
+/// Moves the static files from `<DIR>/<CHAIN_ID>/snapshots` to the static files directory.
+fn move_legacy_static_files(
+    data_dir: &Path,
+    static_files: &Path,
+) -> Result<(), LayoutVersionError> {
+    let from = data_dir.join(LEGACY_STATIC_FILES_DIR);
+    if !from.exists() {
+        return Ok(())
+    }
+
+    // an empty static files directory may have been created by a newer reth version
+    match fs::read_dir(static_files) {
+        Ok(mut entries) => {
+            if entries.next().is_some() {
+                return Err(LayoutVersionError::DestinationNotEmpty {
+                    from,
+                    to: static_files.to_path_buf(),
+                });
+            }
+            fs::remove_dir(static_files)
+                .map_err(|err| LayoutVersionError::Io { err, path: static_files.to_path_buf() })?;
+        }
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(LayoutVersionError::Io { err, path: static_files.to_path_buf() }),
+    }
+
+    if let Some(parent) = static_files.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|err| LayoutVersionError::Io { err, path: parent.to_path_buf() })?;
+    }
+    fs::rename(&from, static_files).map_err(|err| LayoutVersionError::Io { err, path: from })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        args::DatadirArgs,
+        dirs::{DataDirPath, MaybePlatformPath},
+    };
+    use assert_matches::assert_matches;
+    use reth_chainspec::Chain;
+    use tempfile::tempdir;
+
+    fn datadir(path: &Path) -> ChainPath<DataDirPath> {
+        MaybePlatformPath::<DataDirPath>::from(path.to_path_buf())
+            .unwrap_or_chain_default(Chain::mainnet(), DatadirArgs::default())
+    }
+
+    #[test]
+    fn new_datadir_has_latest_layout() {
+        let dir = tempdir().unwrap();
+        let datadir = datadir(dir.path());
+
+        assert_eq!(get_layout_version(&datadir).unwrap(), LAYOUT_VERSION);
+        init_layout_version(&datadir).unwrap();
+        assert_eq!(
+            fs::read_to_string(layout_version_file_path(datadir.data_dir())).unwrap(),
+            LAYOUT_VERSION.to_string()
+        );
+    }
+
+    #[test]
+    fn refuses_mismatched_layout() {
+        let dir = tempdir().unwrap();
+        let datadir = datadir(dir.path());
+
+        create_layout_version_file(datadir.data_dir(), LAYOUT_VERSION + 1).unwrap();
+        assert_matches!(
+            check_layout_version(&datadir),
+            Err(LayoutVersionError::Unsupported { version }) if version == LAYOUT_VERSION + 1
+        );
+
+        fs::write(layout_version_file_path(datadir.data_dir()), "invalid-version").unwrap();
+        assert_matches!(
+            check_layout_version(&datadir),
+            Err(LayoutVersionError::MalformedFile { .. })
+        );
+    }
+
+    #[test]
+    fn upgrade_legacy_static_files() {
+        let dir = tempdir().unwrap();
+        let datadir = datadir(dir.path());
+        let legacy = datadir.data_dir().join(LEGACY_STATIC_FILES_DIR);
+        fs::create_dir_all(&legacy).unwrap();
+        fs::write(legacy.join("static_file_headers_0_499999"), []).unwrap();
+
+        assert_matches!(
+            init_layout_version(&datadir),
+            Err(LayoutVersionError::Outdated { version: 1 })
+        );
+
+        let migrations = upgrade_layout(&datadir).unwrap();
+        assert_eq!(migrations.len(), 1);
+        assert!(!legacy.exists());
+        assert!(datadir.static_files().join("static_file_headers_0_499999").exists());
+        check_layout_version(&datadir).unwrap();
+
+        // upgrading again is a no-op
+        assert!(upgrade_layout(&datadir).unwrap().is_empty());
+    }
+
+    #[test]
+    fn upgrade_refuses_non_empty_destination() {
+        let dir = tempdir().unwrap();
+        let datadir = datadir(dir.path());
+        fs::create_dir_all(datadir.data_dir().join(LEGACY_STATIC_FILES_DIR)).unwrap();
+        fs::create_dir_all(datadir.static_files()).unwrap();
+        fs::write(datadir.static_files().join("static_file_headers_0_499999"), []).unwrap();
+
+        assert_matches!(
+            upgrade_layout(&datadir),
+            Err(LayoutVersionError::DestinationNotEmpty { .. })
+        );
+        assert_eq!(get_layout_version(&datadir).unwrap(), 1);
+    }
+}
@@ -10,11 +10,17 @@
 
 pub mod args;
 pub mod cli;
+pub mod crash_report;
 pub mod dirs;
 pub mod engine;
 pub mod exit;
+pub mod fork;
+pub mod layout;
+pub mod memory;
 pub mod metrics;
 pub mod node_config;
+pub mod proxy;
+pub mod resources;
 pub mod utils;
 pub mod version;
 
@@ -0,0 +1,158 @@
+//! Apportioning of a memory budget to the subsystems of the node.
+
+use crate::args::{ExecutionArgs, RpcServerArgs};
+use reth_config::Config;
+use reth_metrics::{metrics::Gauge, Metrics};
+
+/// One megabyte in bytes.
+const MB: u64 = 1024 * 1024;
+
+/// The share of the budget in percent for the buffer of downloaded block bodies.
+const DOWNLOADER_BUFFERS_SHARE: u64 = 15;
+/// The share of the budget in percent for the data ETL collectors hold in memory.
+const ETL_SHARE: u64 = 15;
+/// The share of the budget in percent for the state cache of the execution stage.
+const EXECUTION_CACHE_SHARE: u64 = 30;
+/// The share of the budget in percent for concurrent RPC tracing requests.
+const RPC_TRACING_SHARE: u64 = 15;
+
+/// The memory a single tracing request is expected to use, which determines how many tracing
+/// requests are admitted concurrently.
+pub const TRACING_REQUEST_MEMORY: u64 = 256 * MB;
+
+/// A memory budget apportioned to the subsystems of the node with configurable memory usage.
+///
+/// The subsystems get a fixed share of the budget each, the remaining quarter is left to the
+/// rest of the node, e.g. the transaction pool, the network and the state root computation. The
+/// pages of the database are held by the page cache of the OS and are not part of the budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryBudget {
+    total: u64,
+}
+
+impl MemoryBudget {
+    /// Creates a new budget of the given number of bytes.
+    pub const fn new(total: u64) -> Self {
+        Self { total }
+    }
+
+    /// Returns the total budget in bytes.
+    pub const fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Returns the size in bytes of the buffer of downloaded block bodies.
+    pub const fn downloader_buffers(&self) -> u64 {
+        self.share(DOWNLOADER_BUFFERS_SHARE)
+    }
+
+    /// Returns the size in bytes of the data ETL collectors hold in memory before flushing it to
+    /// disk.
+    pub const fn etl(&self) -> u64 {
+        self.share(ETL_SHARE)
+    }
+
+    /// Returns the size in bytes of the state cache of the execution stage.
+    pub const fn execution_cache(&self) -> u64 {
+        self.share(EXECUTION_CACHE_SHARE)
+    }
+
+    /// Returns the memory in bytes of concurrent RPC tracing requests.
+    pub const fn rpc_tracing(&self) -> u64 {
+        self.share(RPC_TRACING_SHARE)
+    }
+
+    /// Returns the number of tracing requests admitted concurrently, at least one.
+    pub fn max_tracing_requests(&self) -> usize {
+        (self.rpc_tracing() / TRACING_REQUEST_MEMORY).max(1) as usize
+    }
+
+    /// Applies the budget to the configuration of the subsystems.
+    ///
+    /// The state cache size set with `--execution.state-cache-size` takes precedence, and the
+    /// configured number of concurrent tracing requests is only lowered.
+    pub fn apply(&self, config: &mut Config, execution: &ExecutionArgs, rpc: &mut RpcServerArgs) {
+        config.stages.bodies.downloader_max_buffered_blocks_size_bytes =
+            self.downloader_buffers() as usize;
+        config.stages.etl.file_size = self.etl() as usize;
+        if execution.state_cache_size.is_none() {
+            config.stages.execution.state_cache_size = Some(self.execution_cache() / MB);
+        }
+        rpc.rpc_max_tracing_requests =
+            rpc.rpc_max_tracing_requests.min(self.max_tracing_requests());
+
+        let metrics = MemoryBudgetMetrics::default();
+        metrics.budget_bytes.set(self.total as f64);
+        metrics.downloader_buffers_bytes.set(self.downloader_buffers() as f64);
+        metrics.etl_bytes.set(self.etl() as f64);
+        metrics
+            .execution_cache_bytes
+            .set(config.stages.execution.state_cache_size.unwrap_or_default() as f64 * MB as f64);
+        metrics.rpc_tracing_bytes.set(self.rpc_tracing() as f64);
+        metrics.rpc_max_tracing_requests.set(rpc.rpc_max_tracing_requests as f64);
+    }
+
+    const fn share(&self, percent: u64) -> u64 {
+        self.total / 100 * percent
+    }
+}
+
+/// Metrics of the apportioned memory budget.
+#[derive(Metrics)]
+#[metrics(scope = "memory_budget")]
+struct MemoryBudgetMetrics {
+    /// The total memory budget
+    budget_bytes: Gauge,
+    /// The size of the buffer of downloaded block bodies
+    downloader_buffers_bytes: Gauge,
+    /// The size of the data ETL collectors hold in memory
+    etl_bytes: Gauge,
+    /// The size of the state cache of the execution stage
+    execution_cache_bytes: Gauge,
+    /// The memory of concurrent RPC tracing requests
+    rpc_tracing_bytes: Gauge,
+    /// The number of tracing requests admitted concurrently
+    rpc_max_tracing_requests: Gauge,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apportions_budget() {
+        let budget = MemoryBudget::new(8 * 1024 * MB);
+        let mut config = Config::default();
+        let mut rpc = RpcServerArgs { rpc_max_tracing_requests: 16, ..Default::default() };
+        budget.apply(&mut config, &ExecutionArgs::default(), &mut rpc);
+
+        assert_eq!(
+            config.stages.bodies.downloader_max_buffered_blocks_size_bytes as u64,
+            budget.downloader_buffers()
+        );
+        assert_eq!(config.stages.etl.file_size as u64, budget.etl());
+        assert_eq!(config.stages.execution.state_cache_size, Some(budget.execution_cache() / MB));
+        // 15% of 8GB admits 4 requests of 256MB
+        assert_eq!(rpc.rpc_max_tracing_requests, 4);
+
+        let total = budget.downloader_buffers() +
+            budget.etl() +
+            budget.execution_cache() +
+            budget.rpc_tracing();
+        assert!(total <= budget.total() / 4 * 3);
+    }
+
+    #[test]
+    fn explicit_settings_take_precedence() {
+        let budget = MemoryBudget::new(1024 * MB);
+        let mut config = Config::default();
+        let execution = ExecutionArgs { state_cache_size: Some(4096), ..Default::default() };
+        config.stages.execution.state_cache_size = execution.state_cache_size;
+        let mut rpc = RpcServerArgs { rpc_max_tracing_requests: 2, ..Default::default() };
+        budget.apply(&mut config, &execution, &mut rpc);
+
+        assert_eq!(config.stages.execution.state_cache_size, Some(4096));
+        // at least one tracing request is admitted
+        assert_eq!(rpc.rpc_max_tracing_requests, 1);
+    }
+}
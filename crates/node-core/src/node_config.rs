@@ -2,8 +2,9 @@
 
 use crate::{
     args::{
-        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, NetworkArgs, PayloadBuilderArgs,
-        PruningArgs, RpcServerArgs, TxPoolArgs,
+        AlertArgs, DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, ExecutionArgs, HeaderSyncArgs,
+        MemoryArgs, NetworkArgs, PayloadBuilderArgs, PruningArgs, PublisherArgs, ResourceArgs,
+        RpcServerArgs, TxPoolArgs,
     },
     dirs::{ChainPath, DataDirPath},
     metrics::prometheus_exporter,
@@ -101,6 +102,18 @@ pub struct NodeConfig {
     /// Possible values are either a built-in chain or the path to a chain specification file.
     pub chain: Arc<ChainSpec>,
 
+    /// The chain specification file that is reloaded when the node receives `SIGHUP` or an
+    /// `admin_reloadChainSpec` request.
+    ///
+    /// Changed hardfork activations in the future of the head are applied to the running node.
+    pub chain_reload: Option<PathBuf>,
+
+    /// Halt canonicalization on a reorg of more than this many canonical blocks.
+    ///
+    /// The halt is persisted until the operator resumes canonicalization with
+    /// `reth unwind --force`.
+    pub max_reorg_depth: Option<u64>,
+
     /// Enable Prometheus metrics.
     ///
     /// The metrics will be served at the given interface and port.
@@ -137,6 +150,9 @@ pub struct NodeConfig {
     /// All debug related arguments with --debug prefix
     pub debug: DebugArgs,
 
+    /// All header sync related arguments with --header-sync prefix
+    pub header_sync: HeaderSyncArgs,
+
     /// All database related arguments
     pub db: DatabaseArgs,
 
@@ -145,6 +161,21 @@ pub struct NodeConfig {
 
     /// All pruning related arguments
     pub pruning: PruningArgs,
+
+    /// All block execution related arguments with --execution prefix
+    pub execution: ExecutionArgs,
+
+    /// All memory budget related arguments with --memory prefix
+    pub memory: MemoryArgs,
+
+    /// All resource limit related arguments with --resources prefix
+    pub resources: ResourceArgs,
+
+    /// All health alert related arguments with --alert prefix
+    pub alerts: AlertArgs,
+
+    /// All block publisher related arguments with --publisher prefix
+    pub publisher: PublisherArgs,
 }
 
 impl NodeConfig {
@@ -179,6 +210,18 @@ impl NodeConfig {
         self
     }
 
+    /// Set the chain specification file that is reloaded on `SIGHUP` or `admin_reloadChainSpec`.
+    pub fn with_chain_reload(mut self, path: impl Into<PathBuf>) -> Self {
+        self.chain_reload = Some(path.into());
+        self
+    }
+
+    /// Set the depth of reorgs beyond which canonicalization is halted
+    pub const fn with_max_reorg_depth(mut self, max_reorg_depth: u64) -> Self {
+        self.max_reorg_depth = Some(max_reorg_depth);
+        self
+    }
+
     /// Set the metrics address for the node
     pub const fn with_metrics(mut self, metrics: SocketAddr) -> Self {
         self.metrics = Some(metrics);
@@ -221,6 +264,12 @@ impl NodeConfig {
         self
     }
 
+    /// Set the header sync args for the node
+    pub fn with_header_sync(mut self, header_sync: HeaderSyncArgs) -> Self {
+        self.header_sync = header_sync;
+        self
+    }
+
     /// Set the database args for the node
     pub const fn with_db(mut self, db: DatabaseArgs) -> Self {
         self.db = db;
@@ -239,6 +288,36 @@ impl NodeConfig {
         self
     }
 
+    /// Set the execution args for the node
+    pub fn with_execution(mut self, execution: ExecutionArgs) -> Self {
+        self.execution = execution;
+        self
+    }
+
+    /// Set the memory budget args for the node
+    pub const fn with_memory(mut self, memory: MemoryArgs) -> Self {
+        self.memory = memory;
+        self
+    }
+
+    /// Set the resource limit args for the node
+    pub const fn with_resources(mut self, resources: ResourceArgs) -> Self {
+        self.resources = resources;
+        self
+    }
+
+    /// Set the alert args for the node
+    pub fn with_alerts(mut self, alerts: AlertArgs) -> Self {
+        self.alerts = alerts;
+        self
+    }
+
+    /// Set the publisher args for the node
+    pub fn with_publisher(mut self, publisher: PublisherArgs) -> Self {
+        self.publisher = publisher;
+        self
+    }
+
     /// Returns pruning configuration.
     pub fn prune_config(&self) -> Option<PruneConfig> {
         self.pruning.prune_config(&self.chain)
@@ -411,6 +490,8 @@ impl Default for NodeConfig {
         Self {
             config: None,
             chain: MAINNET.clone(),
+            chain_reload: None,
+            max_reorg_depth: None,
             metrics: None,
             instance: 1,
             network: NetworkArgs::default(),
@@ -418,9 +499,15 @@ impl Default for NodeConfig {
             txpool: TxPoolArgs::default(),
             builder: PayloadBuilderArgs::default(),
             debug: DebugArgs::default(),
+            header_sync: HeaderSyncArgs::default(),
             db: DatabaseArgs::default(),
             dev: DevArgs::default(),
             pruning: PruningArgs::default(),
+            execution: ExecutionArgs::default(),
+            memory: MemoryArgs::default(),
+            resources: ResourceArgs::default(),
+            alerts: AlertArgs::default(),
+            publisher: PublisherArgs::default(),
             datadir: DatadirArgs::default(),
         }
     }
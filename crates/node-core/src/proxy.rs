@@ -0,0 +1,131 @@
+//! HTTP clients for RPC endpoints whose connections are routed through the SOCKS5 proxy of
+//! `--network.proxy`.
+
+use futures::future::BoxFuture;
+use http::Uri;
+use hyper::body::Incoming;
+use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
+use hyper_util::{
+    client::legacy::{self, Client},
+    rt::{TokioExecutor, TokioIo},
+};
+use jsonrpsee::{
+    core::{client::Error as ClientError, http_helpers::HttpError},
+    http_client::{
+        transport::{Error as TransportError, HttpBackend},
+        HttpBody, HttpClient, HttpClientBuilder, HttpRequest, HttpResponse,
+    },
+};
+use std::{
+    io,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tokio::net::TcpStream;
+use tower::{Layer, Service, ServiceBuilder};
+
+pub use reth_network::Socks5Proxy;
+
+/// A JSON-RPC HTTP client that connects through a SOCKS5 proxy, if one is configured.
+pub type ProxiedHttpClient = HttpClient<ProxiedHttpBackend>;
+
+/// Builds a JSON-RPC HTTP client for the given URL, which connects through the given proxy if
+/// any.
+pub fn build_http_client(
+    url: &str,
+    proxy: Option<&Socks5Proxy>,
+) -> Result<ProxiedHttpClient, ClientError> {
+    let layer = ProxyLayer { proxy: proxy.cloned().map(Arc::new) };
+    HttpClientBuilder::default().set_http_middleware(ServiceBuilder::new().layer(layer)).build(url)
+}
+
+/// Replaces the transport of a jsonrpsee client with one that connects through the proxy.
+#[derive(Debug, Clone)]
+struct ProxyLayer {
+    proxy: Option<Arc<Socks5Proxy>>,
+}
+
+impl Layer<HttpBackend> for ProxyLayer {
+    type Service = ProxiedHttpBackend;
+
+    fn layer(&self, backend: HttpBackend) -> Self::Service {
+        let Some(proxy) = self.proxy.clone() else { return ProxiedHttpBackend::Direct(backend) };
+
+        // same TLS setup as the transport of jsonrpsee, on top of the tunneled connection
+        let connector = HttpsConnectorBuilder::new()
+            .with_tls_config(rustls_platform_verifier::tls_config())
+            .https_or_http()
+            .enable_all_versions()
+            .wrap_connector(Socks5Connector { proxy });
+        ProxiedHttpBackend::Socks5(Client::builder(TokioExecutor::new()).build(connector))
+    }
+}
+
+/// The transport of a [`ProxiedHttpClient`].
+#[derive(Debug, Clone)]
+pub enum ProxiedHttpBackend {
+    /// Connects to the endpoint directly.
+    Direct(HttpBackend),
+    /// Connects to the endpoint through a SOCKS5 proxy.
+    Socks5(Client<HttpsConnector<Socks5Connector>, HttpBody>),
+}
+
+impl Service<HttpRequest> for ProxiedHttpBackend {
+    type Response = HttpResponse<Incoming>;
+    type Error = TransportError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        match self {
+            Self::Direct(backend) => backend.poll_ready(cx),
+            Self::Socks5(_) => Poll::Ready(Ok(())),
+        }
+    }
+
+    fn call(&mut self, req: HttpRequest) -> Self::Future {
+        match self {
+            Self::Direct(backend) => backend.call(req),
+            Self::Socks5(client) => {
+                let response = client.request(req);
+                Box::pin(async move {
+                    response.await.map_err(|err: legacy::Error| {
+                        TransportError::Http(HttpError::Stream(err.into()))
+                    })
+                })
+            }
+        }
+    }
+}
+
+/// A connector for [`hyper`] clients that tunnels every connection through a SOCKS5 proxy.
+#[derive(Debug, Clone)]
+pub struct Socks5Connector {
+    proxy: Arc<Socks5Proxy>,
+}
+
+impl Service<Uri> for Socks5Connector {
+    type Response = TokioIo<TcpStream>;
+    type Error = io::Error;
+    type Future = BoxFuture<'static, io::Result<Self::Response>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        let proxy = self.proxy.clone();
+        Box::pin(async move {
+            let host = uri
+                .host()
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "URL has no host"))?;
+            // IPv6 hosts are bracketed in URLs
+            let host = host.trim_start_matches('[').trim_end_matches(']');
+            let port =
+                uri.port_u16().unwrap_or(if uri.scheme_str() == Some("https") { 443 } else { 80 });
+
+            let stream = proxy.connect_host(host, port).await?;
+            stream.set_nodelay(true)?;
+            Ok(TokioIo::new(stream))
+        })
+    }
+}
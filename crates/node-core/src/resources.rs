@@ -0,0 +1,194 @@
+//! Detection of the CPU and memory limits of the cgroup the node runs in.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    thread,
+};
+
+/// The root of the cgroup filesystem.
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+/// Memory limits of cgroup v1 at or above this are the kernel's representation of no limit.
+const CGROUP_V1_UNLIMITED_MEMORY: u64 = 1 << 62;
+
+/// The CPU and memory limits of the cgroup the node runs in, e.g. the limits of its container.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ResourceLimits {
+    /// The CPU quota in number of CPUs, `None` if unlimited.
+    pub cpus: Option<f64>,
+    /// The memory limit in bytes, `None` if unlimited.
+    pub memory: Option<u64>,
+}
+
+impl ResourceLimits {
+    /// Detects the limits of the cgroup of the current process.
+    ///
+    /// Supports cgroup v1 and v2 on Linux, the limits are unlimited on other platforms or if they
+    /// can't be read.
+    pub fn detect() -> Self {
+        if !cfg!(target_os = "linux") {
+            return Self::default()
+        }
+        fs::read_to_string("/proc/self/cgroup")
+            .map(|cgroups| Self::from_cgroups(Path::new(CGROUP_ROOT), &cgroups))
+            .unwrap_or_default()
+    }
+
+    /// Reads the limits of the cgroups listed in the format of `/proc/self/cgroup` from the cgroup
+    /// filesystem mounted at `root`.
+    fn from_cgroups(root: &Path, cgroups: &str) -> Self {
+        let mut limits = Self::default();
+        for line in cgroups.lines() {
+            let mut parts = line.splitn(3, ':');
+            let (Some(_), Some(controllers), Some(path)) =
+                (parts.next(), parts.next(), parts.next())
+            else {
+                continue
+            };
+            let path = path.trim_start_matches('/');
+
+            if controllers.is_empty() {
+                // cgroup v2, all controllers are in the unified hierarchy
+                let dir = cgroup_dir(root, path, "cpu.max");
+                limits.cpus = limits
+                    .cpus
+                    .or_else(|| read(&dir, "cpu.max").and_then(|cpu_max| parse_cpu_max(&cpu_max)));
+                let dir = cgroup_dir(root, path, "memory.max");
+                limits.memory = limits.memory.or_else(|| {
+                    read(&dir, "memory.max").and_then(|memory_max| memory_max.parse().ok())
+                });
+            } else {
+                // cgroup v1, the controllers are mounted as a hierarchy each
+                let hierarchy = root.join(controllers);
+                for controller in controllers.split(',') {
+                    match controller {
+                        "cpu" => {
+                            let dir = cgroup_dir(&hierarchy, path, "cpu.cfs_quota_us");
+                            limits.cpus = limits.cpus.or_else(|| {
+                                let quota = read(&dir, "cpu.cfs_quota_us")?.parse::<i64>().ok()?;
+                                let period =
+                                    read(&dir, "cpu.cfs_period_us")?.parse::<i64>().ok()?;
+                                (quota > 0 && period > 0).then(|| quota as f64 / period as f64)
+                            });
+                        }
+                        "memory" => {
+                            let dir = cgroup_dir(&hierarchy, path, "memory.limit_in_bytes");
+                            limits.memory = limits.memory.or_else(|| {
+                                read(&dir, "memory.limit_in_bytes")?
+                                    .parse::<u64>()
+                                    .ok()
+                                    .filter(|limit| *limit < CGROUP_V1_UNLIMITED_MEMORY)
+                            });
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        limits
+    }
+
+    /// Returns `true` if the CPUs or the memory are limited.
+    pub const fn is_limited(&self) -> bool {
+        self.cpus.is_some() || self.memory.is_some()
+    }
+
+    /// Returns the number of threads that can run in parallel, the CPU quota rounded up if it is
+    /// lower than the number of available CPUs.
+    pub fn available_parallelism(&self) -> Option<usize> {
+        let available = thread::available_parallelism().ok()?.get();
+        Some(self.cpus.map_or(available, |cpus| available.min(cpus.ceil().max(1.) as usize)))
+    }
+
+    /// Returns whether MDBX should read ahead, if a memory limit is set.
+    ///
+    /// Reading ahead speeds up linear scans but evicts other pages from the page cache, so it's
+    /// only enabled if the database fits into half of the memory limit. MDBX can't decide this on
+    /// its own, because it compares the size of the database with the memory of the host.
+    pub fn db_readahead(&self, db_path: &Path) -> Option<bool> {
+        let memory = self.memory?;
+        let db_size = fs::metadata(db_path.join("mdbx.dat")).map_or(0, |metadata| metadata.len());
+        Some(db_size.saturating_mul(2) <= memory)
+    }
+}
+
+/// Returns the directory of the cgroup at `path` within the hierarchy mounted at `root`, or
+/// `root` itself if the cgroup isn't visible, e.g. in a container with its own cgroup namespace.
+fn cgroup_dir(root: &Path, path: &str, file: &str) -> PathBuf {
+    let dir = root.join(path);
+    if dir.join(file).exists() {
+        dir
+    } else {
+        root.to_path_buf()
+    }
+}
+
+fn read(dir: &Path, file: &str) -> Option<String> {
+    fs::read_to_string(dir.join(file)).ok().map(|content| content.trim().to_string())
+}
+
+/// Parses the `<quota> <period>` content of `cpu.max` of cgroup v2 into a number of CPUs.
+fn parse_cpu_max(cpu_max: &str) -> Option<f64> {
+    let (quota, period) = cpu_max.split_once(' ')?;
+    let (quota, period) = (quota.parse::<f64>().ok()?, period.parse::<f64>().ok()?);
+    (quota > 0. && period > 0.).then(|| quota / period)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &Path, file: &str, content: &str) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(dir.join(file), content).unwrap();
+    }
+
+    #[test]
+    fn detects_cgroup_v2_limits() {
+        let root = tempfile::tempdir().unwrap();
+        write(root.path(), "cpu.max", "150000 100000\n");
+        write(root.path(), "memory.max", "8589934592\n");
+
+        // the cgroup of the process isn't visible in a cgroup namespace
+        let limits = ResourceLimits::from_cgroups(root.path(), "0::/kubepods/pod1\n");
+        assert_eq!(limits, ResourceLimits { cpus: Some(1.5), memory: Some(8589934592) });
+        assert_eq!(limits.available_parallelism().map(|num| num <= 2), Some(true));
+
+        let cgroup = root.path().join("system.slice/reth.service");
+        write(&cgroup, "cpu.max", "max 100000\n");
+        write(&cgroup, "memory.max", "max\n");
+        let limits = ResourceLimits::from_cgroups(root.path(), "0::/system.slice/reth.service\n");
+        assert!(!limits.is_limited());
+    }
+
+    #[test]
+    fn detects_cgroup_v1_limits() {
+        let root = tempfile::tempdir().unwrap();
+        let cpu = root.path().join("cpu,cpuacct");
+        write(&cpu, "cpu.cfs_quota_us", "400000\n");
+        write(&cpu, "cpu.cfs_period_us", "100000\n");
+        let memory = root.path().join("memory");
+        write(&memory, "memory.limit_in_bytes", "9223372036854771712\n");
+
+        let cgroups = "12:memory:/docker/abc\n4:cpu,cpuacct:/docker/abc\n1:name=systemd:/\n";
+        let limits = ResourceLimits::from_cgroups(root.path(), cgroups);
+        assert_eq!(limits, ResourceLimits { cpus: Some(4.), memory: None });
+
+        write(&memory, "memory.limit_in_bytes", "17179869184\n");
+        let limits = ResourceLimits::from_cgroups(root.path(), cgroups);
+        assert_eq!(limits.memory, Some(17179869184));
+    }
+
+    #[test]
+    fn db_readahead_within_memory_limit() {
+        let db = tempfile::tempdir().unwrap();
+        write(db.path(), "mdbx.dat", &"0".repeat(1024));
+
+        assert_eq!(ResourceLimits::default().db_readahead(db.path()), None);
+        let limits = ResourceLimits { cpus: None, memory: Some(2048) };
+        assert_eq!(limits.db_readahead(db.path()), Some(true));
+        let limits = ResourceLimits { cpus: None, memory: Some(2047) };
+        assert_eq!(limits.db_readahead(db.path()), Some(false));
+    }
+}
@@ -36,7 +36,12 @@ use reth_node_core::{
 use reth_primitives::revm_primitives::EnvKzgSettings;
 use reth_provider::{providers::BlockchainProvider, ChainSpecProvider};
 use reth_tasks::TaskExecutor;
-use reth_transaction_pool::{PoolConfig, TransactionPool};
+use reth_transaction_pool::{
+    blobstore::DiskFileBlobStoreConfig,
+    maintain::{LocalTransactionBackupConfig, MaintainPoolConfig},
+    validate::EthTransactionValidatorBuilder,
+    PoolConfig, TransactionPool,
+};
 use secp256k1::SecretKey;
 pub use states::*;
 use std::{
@@ -469,6 +474,32 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         self.config().txpool.pool_config()
     }
 
+    /// Returns the configuration of the on-disk blob store of the transaction pool.
+    pub fn blob_store_config(&self) -> DiskFileBlobStoreConfig {
+        self.config().txpool.blob_store_config()
+    }
+
+    /// Returns the configuration of the task that maintains the transaction pool.
+    pub fn pool_maintain_config(&self) -> MaintainPoolConfig {
+        self.config().txpool.maintain_config()
+    }
+
+    /// Returns the configuration of the journal of local transactions of the transaction pool.
+    pub fn local_transactions_backup_config(&self) -> LocalTransactionBackupConfig {
+        self.config()
+            .txpool
+            .local_transactions_backup_config(self.config().datadir().txpool_transactions())
+    }
+
+    /// Returns a transaction validator builder for the head of the node, with the configured
+    /// validation rules of the transaction pool applied.
+    pub fn pool_validator_builder(&self) -> eyre::Result<EthTransactionValidatorBuilder> {
+        let builder = EthTransactionValidatorBuilder::new(self.chain_spec())
+            .with_head_timestamp(self.head().timestamp)
+            .kzg_settings(self.kzg_settings()?);
+        Ok(self.config().txpool.configure_validator(builder))
+    }
+
     /// Loads `EnvKzgSettings::Default`.
     pub const fn kzg_settings(&self) -> eyre::Result<EnvKzgSettings> {
         Ok(EnvKzgSettings::Default)
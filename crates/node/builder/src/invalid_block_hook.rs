@@ -0,0 +1,291 @@
+//! An [`InvalidBlockHook`] that dumps invalid blocks for offline debugging.
+
+use eyre::OptionExt;
+use reth_beacon_consensus::InvalidBlockHook;
+use reth_blockchain_tree::error::InsertBlockErrorKind;
+use reth_chainspec::ChainSpec;
+use reth_evm::{
+    execute::{BlockExecutorProvider, Executor},
+    ConfigureEvm,
+};
+use reth_primitives::{Address, Bytes, SealedBlock, SealedBlockWithSenders, B256, U256};
+use reth_provider::{HeaderProvider, ProviderError, StateProviderFactory, StateRootProvider};
+use reth_revm::{
+    database::StateProviderDatabase,
+    db::State,
+    primitives::{BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, ResultAndState, TxEnv},
+    state_change::apply_beacon_root_contract_call,
+    witness::{AccessedState, RecordingDatabase},
+    DatabaseCommit,
+};
+use reth_rpc_types::trace::geth::{GethDefaultTracingOptions, GethTrace, TraceResult};
+use reth_tracing::tracing::{info, warn};
+use revm_inspectors::tracing::{TracingInspector, TracingInspectorConfig};
+use serde::Serialize;
+use std::{collections::BTreeMap, path::PathBuf, sync::Arc};
+
+/// An [`InvalidBlockHook`] that re-executes invalid blocks on top of their parent state and writes
+/// the block, the state it accessed (its witness), the transaction traces and the re-execution
+/// result into a directory.
+///
+/// For every invalid block a `<number>_<hash>` directory is created that contains:
+///  - `block.rlp`: the RLP encoded block
+///  - `block.json`: the block
+///  - `witness.json`: all accounts, storage slots, bytecodes and block hashes that were read
+///    while executing the block, as of the parent state
+///  - `trace.json`: the struct log traces of all transactions, in the format of
+///    `debug_traceBlock`
+///  - `report.json`: the error the block was rejected with and the re-execution result
+///
+/// Invalid blocks are rare, so the block is re-executed on the caller's thread.
+#[derive(Debug)]
+pub struct InvalidBlockWitnessHook<Provider, EvmConfig, E> {
+    /// The provider for the parent state.
+    provider: Provider,
+    /// The EVM configuration to trace the transactions with.
+    evm_config: EvmConfig,
+    /// The executor to re-execute the block with.
+    executor: E,
+    /// The chain spec.
+    chain_spec: Arc<ChainSpec>,
+    /// The directory to write invalid blocks to.
+    output_directory: PathBuf,
+}
+
+impl<Provider, EvmConfig, E> InvalidBlockWitnessHook<Provider, EvmConfig, E> {
+    /// Creates a new hook that writes invalid blocks to the given directory.
+    pub const fn new(
+        provider: Provider,
+        evm_config: EvmConfig,
+        executor: E,
+        chain_spec: Arc<ChainSpec>,
+        output_directory: PathBuf,
+    ) -> Self {
+        Self { provider, evm_config, executor, chain_spec, output_directory }
+    }
+}
+
+impl<Provider, EvmConfig, E> InvalidBlockWitnessHook<Provider, EvmConfig, E>
+where
+    Provider: StateProviderFactory + HeaderProvider,
+    EvmConfig: ConfigureEvm,
+    E: BlockExecutorProvider,
+{
+    /// Re-executes the block and writes all debugging information, returns the directory it was
+    /// written to.
+    fn dump(&self, block: &SealedBlock, error: &InsertBlockErrorKind) -> eyre::Result<PathBuf> {
+        let dir = self.output_directory.join(format!("{}_{}", block.number, block.hash()));
+        reth_fs_util::create_dir_all(&dir)?;
+
+        reth_fs_util::write(dir.join("block.rlp"), alloy_rlp::encode(block.clone().unseal()))?;
+        write_json(dir.join("block.json"), block)?;
+
+        let block = block.clone().seal_with_senders().ok_or_eyre("failed to recover senders")?;
+        let total_difficulty = self
+            .provider
+            .header_td(&block.parent_hash)?
+            .ok_or(ProviderError::TotalDifficultyNotFound(block.number.saturating_sub(1)))? +
+            block.difficulty;
+
+        // re-execute the block and record all state that is read from the parent state
+        let mut db = RecordingDatabase::new(StateProviderDatabase::new(
+            self.provider.state_by_block_hash(block.parent_hash)?,
+        ));
+        let output = self
+            .executor
+            .executor(&mut db)
+            .execute((&block.clone().unseal(), total_difficulty).into());
+        write_json(dir.join("witness.json"), &ExecutionWitness::from(db.into_accessed()))?;
+
+        let traces = self.trace_transactions(&block, total_difficulty)?;
+        write_json(dir.join("trace.json"), &traces)?;
+
+        let mut report = InvalidBlockReport {
+            block_hash: block.hash(),
+            block_number: block.number,
+            error: error.to_string(),
+            execution_error: None,
+            expected_gas_used: block.gas_used,
+            gas_used: None,
+            expected_state_root: block.state_root,
+            state_root: None,
+        };
+        match output {
+            Ok(output) => {
+                let state = self.provider.state_by_block_hash(block.parent_hash)?;
+                report.gas_used = Some(output.gas_used);
+                report.state_root = Some(state.state_root(&output.state)?);
+            }
+            Err(err) => report.execution_error = Some(err.to_string()),
+        }
+        write_json(dir.join("report.json"), &report)?;
+
+        Ok(dir)
+    }
+
+    /// Replays the transactions of the block with the struct logger.
+    fn trace_transactions(
+        &self,
+        block: &SealedBlockWithSenders,
+        total_difficulty: U256,
+    ) -> eyre::Result<Vec<TraceResult>> {
+        let state = self.provider.state_by_block_hash(block.parent_hash)?;
+        let mut db = State::builder()
+            .with_database(StateProviderDatabase::new(state))
+            .with_bundle_update()
+            .build();
+
+        let mut cfg = CfgEnvWithHandlerCfg::new(Default::default(), Default::default());
+        let mut block_env = BlockEnv::default();
+        EvmConfig::fill_cfg_and_block_env(
+            &mut cfg,
+            &mut block_env,
+            &self.chain_spec,
+            &block.header,
+            total_difficulty,
+        );
+
+        // the transactions must observe the state after the pre-block system call
+        {
+            let env = EnvWithHandlerCfg::new_with_cfg_env(
+                cfg.clone(),
+                block_env.clone(),
+                Default::default(),
+            );
+            let mut evm = self.evm_config.evm_with_env(&mut db, env);
+            apply_beacon_root_contract_call(
+                &self.chain_spec,
+                block.timestamp,
+                block.number,
+                block.parent_beacon_block_root,
+                &mut evm,
+            )?;
+        }
+
+        let opts = GethDefaultTracingOptions::default();
+        let mut traces = Vec::with_capacity(block.body.len());
+        for (sender, tx) in block.transactions_with_sender() {
+            let tx_hash = Some(tx.hash());
+            let mut tx_env = TxEnv::default();
+            EvmConfig::fill_tx_env(&mut tx_env, tx, *sender);
+            let env = EnvWithHandlerCfg::new_with_cfg_env(cfg.clone(), block_env.clone(), tx_env);
+
+            let mut inspector =
+                TracingInspector::new(TracingInspectorConfig::from_geth_config(&opts));
+            let res =
+                self.evm_config.evm_with_env_and_inspector(&mut db, env, &mut inspector).transact();
+            let ResultAndState { result, state } = match res {
+                Ok(res) => res,
+                Err(err) => {
+                    // the following transactions can't be replayed on the correct state
+                    traces.push(TraceResult::Error { error: err.to_string(), tx_hash });
+                    break
+                }
+            };
+            db.commit(state);
+
+            let gas_used = result.gas_used();
+            let return_value = result.into_output().unwrap_or_default();
+            let frame =
+                inspector.into_geth_builder().geth_traces(gas_used, return_value, opts.clone());
+            traces.push(TraceResult::Success { result: GethTrace::Default(frame), tx_hash });
+        }
+
+        Ok(traces)
+    }
+}
+
+impl<Provider, EvmConfig, E> InvalidBlockHook for InvalidBlockWitnessHook<Provider, EvmConfig, E>
+where
+    Provider: StateProviderFactory + HeaderProvider,
+    EvmConfig: ConfigureEvm,
+    E: BlockExecutorProvider,
+{
+    fn on_invalid_block(&self, block: &SealedBlock, error: &InsertBlockErrorKind) {
+        match self.dump(block, error) {
+            Ok(dir) => {
+                info!(target: "reth::cli", block_hash = %block.hash(), ?dir, "Dumped invalid block")
+            }
+            Err(err) => {
+                warn!(target: "reth::cli", block_hash = %block.hash(), %err, "Failed to dump invalid block")
+            }
+        }
+    }
+}
+
+/// Writes the value as pretty printed JSON to the given path.
+fn write_json(path: PathBuf, value: &impl Serialize) -> eyre::Result<()> {
+    reth_fs_util::write(path, serde_json::to_vec_pretty(value)?)?;
+    Ok(())
+}
+
+/// The result of re-executing an invalid block.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct InvalidBlockReport {
+    /// The hash of the invalid block.
+    block_hash: B256,
+    /// The number of the invalid block.
+    block_number: u64,
+    /// The error the block was rejected with.
+    error: String,
+    /// The error the re-execution failed with, if any.
+    execution_error: Option<String>,
+    /// The gas used according to the header.
+    expected_gas_used: u64,
+    /// The gas used by the re-execution.
+    gas_used: Option<u64>,
+    /// The state root according to the header.
+    expected_state_root: B256,
+    /// The state root after the re-execution.
+    state_root: Option<B256>,
+}
+
+/// All state that was read from the parent state while executing a block.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExecutionWitness {
+    /// The accessed accounts, `None` if the account doesn't exist.
+    accounts: BTreeMap<Address, Option<WitnessAccount>>,
+    /// The accessed storage slots.
+    storage: BTreeMap<Address, BTreeMap<U256, U256>>,
+    /// The bytecode of the accessed contracts, by code hash.
+    codes: BTreeMap<B256, Bytes>,
+    /// The accessed block hashes, by block number.
+    block_hashes: BTreeMap<u64, B256>,
+}
+
+impl From<AccessedState> for ExecutionWitness {
+    fn from(accessed: AccessedState) -> Self {
+        Self {
+            accounts: accessed
+                .accounts
+                .into_iter()
+                .map(|(address, info)| {
+                    let account = info.map(|info| WitnessAccount {
+                        balance: info.balance,
+                        nonce: info.nonce,
+                        code_hash: info.code_hash,
+                    });
+                    (address, account)
+                })
+                .collect(),
+            storage: accessed.storage,
+            codes: accessed
+                .codes
+                .into_iter()
+                .map(|(code_hash, code)| (code_hash, code.original_bytes()))
+                .collect(),
+            block_hashes: accessed.block_hashes,
+        }
+    }
+}
+
+/// An account of the [`ExecutionWitness`].
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WitnessAccount {
+    balance: U256,
+    nonce: u64,
+    code_hash: B256,
+}
@@ -25,21 +25,25 @@ use reth_network_p2p::headers::client::HeadersClient;
 use reth_node_api::FullNodeTypes;
 use reth_node_core::{
     dirs::{ChainPath, DataDirPath},
+    fork::RpcForkState,
+    memory::MemoryBudget,
     node_config::NodeConfig,
+    resources::ResourceLimits,
 };
 use reth_primitives::{BlockNumber, Head, B256};
 use reth_provider::{
     providers::{BlockchainProvider, StaticFileProvider},
-    CanonStateNotificationSender, ProviderFactory, StaticFileProviderFactory,
+    BlockHashReader, CanonStateNotificationSender, ForkBlockReader, ForkBlockWriter,
+    PendingUnwindReader, ProviderFactory, StaticFileProviderFactory,
 };
 use reth_prune::{PruneModes, PrunerBuilder};
 use reth_rpc_builder::config::RethRpcServerConfig;
 use reth_rpc_layer::JwtSecret;
 use reth_stages::{sets::DefaultStages, MetricEvent, Pipeline, PipelineTarget};
 use reth_static_file::StaticFileProducer;
-use reth_tasks::TaskExecutor;
+use reth_tasks::{affinity::CpuSet, TaskExecutor};
 use reth_tracing::tracing::{debug, error, info, warn};
-use std::{marker::PhantomData, sync::Arc, thread::available_parallelism};
+use std::{marker::PhantomData, sync::Arc};
 use tokio::sync::{
     mpsc::{unbounded_channel, Receiver, UnboundedSender},
     oneshot, watch,
@@ -95,6 +99,9 @@ impl LaunchContext {
 
         // Update the config with the command line arguments
         toml_config.peers.trusted_nodes_only = config.network.trusted_only;
+        if let Some(size) = config.execution.state_cache_size {
+            toml_config.stages.execution.state_cache_size = Some(size);
+        }
 
         Ok(toml_config)
     }
@@ -123,11 +130,28 @@ impl LaunchContext {
         self
     }
 
+    /// Convenience function to [`Self::configure_globals_with`]
+    pub fn with_configured_globals_and_trie_cpus(
+        self,
+        trie_cpus: Option<CpuSet>,
+        limits: &ResourceLimits,
+    ) -> Self {
+        self.configure_globals_with(trie_cpus, limits);
+        self
+    }
+
     /// Configure global settings this includes:
     ///
     /// - Raising the file descriptor limit
     /// - Configuring the global rayon thread pool
     pub fn configure_globals(&self) {
+        self.configure_globals_with(None, &ResourceLimits::default())
+    }
+
+    /// Configure global settings like [`Self::configure_globals`], pinning the threads of the
+    /// global rayon thread pool, which computes state roots, to `trie_cpus` if set, and sizing it
+    /// within the CPU quota of the resource limits.
+    pub fn configure_globals_with(&self, trie_cpus: Option<CpuSet>, limits: &ResourceLimits) {
         // Raise the fd limit of the process.
         // Does not do anything on windows.
         match fdlimit::raise_fd_limit() {
@@ -140,12 +164,18 @@ impl LaunchContext {
 
         // Limit the global rayon thread pool, reserving 2 cores for the rest of the system
         let num_threads =
-            available_parallelism().map_or(0, |num| num.get().saturating_sub(2).max(2));
-        if let Err(err) = ThreadPoolBuilder::new()
+            limits.available_parallelism().map_or(0, |num| num.saturating_sub(2).max(2));
+        let mut builder = ThreadPoolBuilder::new()
             .num_threads(num_threads)
-            .thread_name(|i| format!("reth-rayon-{i}"))
-            .build_global()
-        {
+            .thread_name(|i| format!("reth-rayon-{i}"));
+        if let Some(cpus) = trie_cpus {
+            builder = builder.start_handler(move |_| {
+                if let Err(err) = cpus.pin_current_thread() {
+                    warn!(%err, %cpus, "Failed to pin global thread pool thread");
+                }
+            });
+        }
+        if let Err(err) = builder.build_global() {
             error!(%err, "Failed to build global thread pool")
         }
     }
@@ -250,8 +280,9 @@ impl<R> LaunchContextWith<Attached<WithConfigs, R>> {
     /// This includes:
     /// - Making sure the ETL dir is set to the datadir
     /// - RPC settings are adjusted to the correct port
+    /// - The memory budget is apportioned to the subsystems
     pub fn with_adjusted_configs(self) -> Self {
-        self.ensure_etl_datadir().with_adjusted_rpc_instance_ports()
+        self.ensure_etl_datadir().with_adjusted_rpc_instance_ports().with_memory_budget()
     }
 
     /// Make sure ETL doesn't default to /tmp/, but to whatever datadir is set to
@@ -270,6 +301,29 @@ impl<R> LaunchContextWith<Attached<WithConfigs, R>> {
         self
     }
 
+    /// Apportions the memory budget set with `--memory.budget`, or the detected memory limit, to
+    /// the subsystems.
+    pub fn with_memory_budget(mut self) -> Self {
+        let config = self.node_config();
+        let budget = config
+            .memory
+            .budget()
+            .or_else(|| config.resources.limits().memory.map(MemoryBudget::new));
+        if let Some(budget) = budget {
+            let WithConfigs { config, toml_config } = self.left_mut();
+            budget.apply(toml_config, &config.execution, &mut config.rpc);
+            info!(target: "reth::cli",
+                budget_mb = budget.total() >> 20,
+                downloader_buffers_mb = budget.downloader_buffers() >> 20,
+                etl_mb = budget.etl() >> 20,
+                state_cache_mb = ?toml_config.stages.execution.state_cache_size,
+                max_tracing_requests = config.rpc.rpc_max_tracing_requests,
+                "Apportioned memory budget"
+            );
+        }
+        self
+    }
+
     /// Returns the container for all config types
     pub const fn configs(&self) -> &WithConfigs {
         self.attachment.left()
@@ -317,7 +371,14 @@ impl<R> LaunchContextWith<Attached<WithConfigs, R>> {
 
     /// Returns the configured [`PruneConfig`]
     pub fn prune_config(&self) -> Option<PruneConfig> {
-        self.toml_config().prune.clone().or_else(|| self.node_config().prune_config())
+        let Some(mut config) = self.toml_config().prune.clone() else {
+            return self.node_config().prune_config()
+        };
+        // the transaction lookup limit takes priority over the pruning configuration in reth.toml
+        if let Some(mode) = self.node_config().pruning.transaction_lookup_mode() {
+            config.segments.transaction_lookup = Some(mode);
+        }
+        Some(config)
     }
 
     /// Returns the configured [`PruneModes`]
@@ -359,12 +420,34 @@ where
     /// between the database and static files. **It may execute a pipeline unwind if it fails this
     /// check.**
     pub async fn create_provider_factory(&self) -> eyre::Result<ProviderFactory<DB>> {
-        let factory = ProviderFactory::new(
-            self.right().clone(),
-            self.chain_spec(),
-            StaticFileProvider::read_write(self.data_dir().static_files())?,
-        )
-        .with_static_files_metrics();
+        let mut static_file_provider =
+            StaticFileProvider::read_write(self.data_dir().static_files())?;
+        let read_only_static_files = self.data_dir().read_only_static_files();
+        if !read_only_static_files.is_empty() {
+            info!(target: "reth::cli", dirs = ?read_only_static_files, "Reading old static files from read-only directories");
+            static_file_provider =
+                static_file_provider.with_read_only_dirs(read_only_static_files.to_vec())?;
+        }
+        if let Some(queue_depth) = self.node_config().rpc.rpc_io_uring {
+            info!(target: "reth::cli", queue_depth, "Reading static files through io_uring");
+            static_file_provider = static_file_provider.with_io_uring(queue_depth)?;
+        }
+        // the genesis header is the first header of the static files
+        self.data_dir().ensure_genesis(
+            self.chain_spec().genesis_hash(),
+            static_file_provider.block_hash(0).ok().flatten(),
+        )?;
+
+        let factory =
+            ProviderFactory::new(self.right().clone(), self.chain_spec(), static_file_provider)
+                .with_static_files_metrics();
+
+        if let Some(target) = factory.provider()?.pending_unwind()? {
+            eyre::bail!(
+                "An unwind to block {target} was interrupted, run `reth unwind to-block {target}` \
+                 to complete it before starting the node"
+            )
+        }
 
         let has_receipt_pruning =
             self.toml_config().prune.as_ref().map_or(false, |a| a.has_receipts_pruning());
@@ -485,6 +568,39 @@ where
         init_genesis(self.provider_factory().clone())
     }
 
+    /// Connects to the RPC node of the forked chain if the node is started with `--fork-url`, and
+    /// serves the state that isn't available locally from it.
+    ///
+    /// The block the chain is forked at is stored in the database, so a restarted node keeps
+    /// reading the state of the same block.
+    pub async fn with_fork_state(mut self) -> eyre::Result<Self> {
+        let Some(url) = self.node_config().dev.fork_url.clone() else { return Ok(self) };
+
+        let stored = self.provider_factory().provider()?.fork_block()?;
+        let configured = self.node_config().dev.fork_block_number;
+        if let (Some(stored), Some(configured)) = (stored, configured) {
+            eyre::ensure!(
+                stored == configured,
+                "the database was forked at block {stored}, it can't be forked at block {configured}"
+            );
+        }
+
+        let proxy = self.node_config().network.proxy.as_ref();
+        let source = RpcForkState::connect(&url, configured.or(stored), proxy)
+            .await
+            .wrap_err_with(|| format!("failed to connect to the forked chain at {url}"))?;
+        if stored.is_none() {
+            let provider = self.provider_factory().provider_rw()?;
+            provider.save_fork_block(source.block())?;
+            provider.commit()?;
+        }
+
+        info!(target: "reth::cli", url = source.url(), block = source.block(), "Forking chain");
+        let factory = self.provider_factory().clone().with_fork_state(Arc::new(source));
+        *self.right_mut() = factory;
+        Ok(self)
+    }
+
     /// Creates a new `WithMeteredProvider` container and attaches it to the
     /// launch context.
     pub fn with_metrics(self) -> LaunchContextWith<Attached<WithConfigs, WithMeteredProvider<DB>>> {
@@ -525,7 +641,10 @@ where
     where
         T: FullNodeTypes<Provider = BlockchainProvider<<T as FullNodeTypes>::DB>>,
     {
-        let tree_config = BlockchainTreeConfig::default();
+        let mut tree_config = BlockchainTreeConfig::default();
+        if let Some(max_depth) = self.node_config().max_reorg_depth {
+            tree_config = tree_config.with_reorg_halt_depth(max_depth);
+        }
 
         // NOTE: This is a temporary workaround to provide the canon state notification sender to the components builder because there's a cyclic dependency between the blockchain provider and the tree component. This will be removed once the Blockchain provider no longer depends on an instance of the tree: <https://github.com/paradigmxyz/reth/issues/7154>
         let (canon_state_notification_sender, _receiver) =
@@ -2,11 +2,11 @@
 
 use crate::{common::WithConfigs, exex::BoxedLaunchExEx};
 use futures::future;
-use reth_exex::{ExExContext, ExExHandle, ExExManager, ExExManagerHandle};
+use reth_exex::{ExExCheckpoints, ExExContext, ExExHandle, ExExManager, ExExManagerHandle};
 use reth_node_api::FullNodeComponents;
 use reth_primitives::Head;
 use reth_provider::CanonStateSubscriptions;
-use reth_tracing::tracing::{debug, info};
+use reth_tracing::tracing::{debug, info, warn};
 use std::{fmt, fmt::Debug};
 
 /// Can launch execution extensions.
@@ -37,20 +37,28 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
 
         if extensions.is_empty() {
             // nothing to launch
-            return None
+            return None;
         }
 
         let mut exex_handles = Vec::with_capacity(extensions.len());
         let mut exexs = Vec::with_capacity(extensions.len());
+        let checkpoints = ExExCheckpoints::new(config_container.config.datadir().exex());
 
         for (id, exex) in extensions {
+            // resume from the last persisted finished height of the exex
+            let checkpoint = checkpoints.get(&id).unwrap_or_else(|err| {
+                warn!(target: "reth::cli", id, %err, "Failed to read exex checkpoint");
+                None
+            });
+
             // create a new exex handle
             let (handle, events, notifications) = ExExHandle::new(id.clone());
-            exex_handles.push(handle);
+            exex_handles.push(handle.with_checkpoints(checkpoints.clone(), checkpoint));
 
             // create the launch context for the exex
             let context = ExExContext {
                 head,
+                checkpoint,
                 config: config_container.config.clone(),
                 reth_config: config_container.toml_config.clone(),
                 components: components.clone(),
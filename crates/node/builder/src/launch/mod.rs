@@ -4,6 +4,7 @@ use crate::{
     builder::{NodeAdapter, NodeAddOns, NodeTypesAdapter},
     components::{NodeComponents, NodeComponentsBuilder},
     hooks::NodeHooks,
+    invalid_block_hook::InvalidBlockWitnessHook,
     node::FullNode,
     NodeBuilderWithComponents, NodeHandle,
 };
@@ -12,23 +13,33 @@ use reth_beacon_consensus::{
     hooks::{EngineHooks, PruneHook, StaticFileHook},
     BeaconConsensusEngine,
 };
-use reth_consensus_debug_client::{DebugConsensusClient, EtherscanBlockProvider, RpcBlockProvider};
+use reth_consensus_debug_client::{
+    DebugConsensusClient, EtherscanBlockProvider, RpcBlockProvider, RpcSyncClient,
+};
 use reth_exex::ExExManagerHandle;
 use reth_network::NetworkEvents;
 use reth_node_api::FullNodeTypes;
 use reth_node_core::{
+    args::{HeaderSyncMode, TipSource},
     dirs::{ChainPath, DataDirPath},
     engine::EngineMessageStreamExt,
     exit::NodeExitFuture,
     version::{CARGO_PKG_VERSION, CLIENT_CODE, NAME_CLIENT, VERGEN_GIT_SHA},
 };
-use reth_node_events::{cl::ConsensusLayerHealthEvents, node};
+use reth_node_events::{
+    alert::{self, AlertConfig},
+    cl::ConsensusLayerHealthEvents,
+    node, sync_history,
+};
 
 use reth_primitives::format_ether;
 use reth_provider::providers::BlockchainProvider;
 use reth_rpc_engine_api::EngineApi;
 use reth_rpc_types::engine::ClientVersionV1;
-use reth_tasks::TaskExecutor;
+use reth_tasks::{
+    affinity::{run_pinned, PinnedTaskSpawner},
+    TaskExecutor, TaskSpawner,
+};
 use reth_tracing::tracing::{debug, info};
 use reth_transaction_pool::TransactionPool;
 use std::{future::Future, sync::Arc};
@@ -39,6 +50,7 @@ pub mod common;
 pub use common::LaunchContext;
 mod exex;
 pub use exex::ExExLauncher;
+pub(crate) mod reload;
 
 /// A general purpose trait that launches a new node of any kind.
 ///
@@ -101,9 +113,34 @@ where
         } = target;
         let NodeHooks { on_component_initialized, on_node_started, .. } = hooks;
 
+        // only a tip given with --debug.tip exits the node, not one resolved by the header sync
+        let terminate = config.debug.terminate_after_sync();
+
+        // drive the header sync from the configured tip source, the engine is driven by the
+        // consensus client by default
+        let mut config = config;
+        config.header_sync.validate(&config.debug)?;
+        if let (TipSource::Rpc, Some(rpc_url)) =
+            (config.header_sync.tip_source, config.header_sync.rpc_url.clone())
+        {
+            match config.header_sync.mode {
+                HeaderSyncMode::Tip => {
+                    let tip = RpcBlockProvider::new(rpc_url).latest_block().await?;
+                    info!(target: "reth::cli",
+                        number = ?tip.header.number,
+                        hash = ?tip.header.hash,
+                        "Syncing to the latest block of the RPC endpoint"
+                    );
+                    config.debug.tip = tip.header.hash;
+                }
+                HeaderSyncMode::Continuous => config.debug.rpc_consensus_ws = Some(rpc_url),
+            }
+        }
+
         // setup the launch context
+        let limits = config.resources.limits();
         let ctx = ctx
-            .with_configured_globals()
+            .with_configured_globals_and_trie_cpus(config.execution.trie_cpus, &limits)
             // load the toml config
             .with_loaded_toml_config(config).await?
             // add resolved peers
@@ -122,6 +159,7 @@ where
                 debug!(target: "reth::cli", chain=%this.chain_id(), genesis=?this.genesis_hash(), "Initializing genesis");
             })
             .with_genesis()?
+            .with_fork_state().await?
             .inspect(|this| {
                 info!(target: "reth::cli", "\n{}", this.chain_spec().display_hardforks());
             })
@@ -211,6 +249,26 @@ where
             ctx.task_executor().spawn(Box::pin(task));
 
             (pipeline, Either::Left(client))
+        } else if let Some(rpc_url) = ctx.node_config().debug.rpc_sync.clone() {
+            info!(target: "reth::cli", %rpc_url, "Syncing from RPC endpoint");
+
+            let client = RpcSyncClient::connect(&rpc_url).await?;
+            let pipeline = crate::setup::build_networked_pipeline(
+                &ctx.toml_config().stages,
+                client.clone(),
+                ctx.consensus(),
+                ctx.provider_factory().clone(),
+                ctx.task_executor(),
+                ctx.sync_metrics_tx(),
+                ctx.prune_config(),
+                max_block,
+                static_file_producer,
+                ctx.components().block_executor().clone(),
+                pipeline_exex_handle,
+            )
+            .await?;
+
+            (pipeline, Either::Right(Either::Right(client)))
         } else {
             let pipeline = crate::setup::build_networked_pipeline(
                 &ctx.toml_config().stages,
@@ -227,10 +285,13 @@ where
             )
             .await?;
 
-            (pipeline, Either::Right(network_client.clone()))
+            (pipeline, Either::Right(Either::Left(network_client.clone())))
         };
 
         let pipeline_events = pipeline.events();
+        let alert_pipeline_events =
+            ctx.node_config().alerts.is_enabled().then(|| pipeline.events());
+        let sync_history_events = pipeline.events();
 
         let initial_target = ctx.node_config().debug.tip;
 
@@ -247,21 +308,38 @@ where
         info!(target: "reth::cli", prune_config=?ctx.prune_config().unwrap_or_default(), "Pruner initialized");
         hooks.add(PruneHook::new(pruner, Box::new(ctx.task_executor().clone())));
 
+        // Pipeline runs are spawned as blocking tasks, pin them to the execution CPUs if set
+        let pipeline_task_spawner: Box<dyn TaskSpawner> = match ctx.node_config().execution.cpus {
+            Some(cpus) => Box::new(PinnedTaskSpawner::new(ctx.task_executor().clone(), cpus)),
+            None => Box::new(ctx.task_executor().clone()),
+        };
+
         // Configure the consensus engine
-        let (beacon_consensus_engine, beacon_engine_handle) = BeaconConsensusEngine::with_channel(
-            client,
-            pipeline,
-            ctx.blockchain_db().clone(),
-            Box::new(ctx.task_executor().clone()),
-            Box::new(ctx.components().network().clone()),
-            max_block,
-            ctx.components().payload_builder().clone(),
-            initial_target,
-            reth_beacon_consensus::MIN_BLOCKS_FOR_PIPELINE_RUN,
-            consensus_engine_tx,
-            Box::pin(consensus_engine_stream),
-            hooks,
-        )?;
+        let (mut beacon_consensus_engine, beacon_engine_handle) =
+            BeaconConsensusEngine::with_channel(
+                client,
+                pipeline,
+                ctx.blockchain_db().clone(),
+                pipeline_task_spawner,
+                Box::new(ctx.components().network().clone()),
+                max_block,
+                ctx.components().payload_builder().clone(),
+                initial_target,
+                reth_beacon_consensus::MIN_BLOCKS_FOR_PIPELINE_RUN,
+                consensus_engine_tx,
+                Box::pin(consensus_engine_stream),
+                hooks,
+            )?;
+        if let Some(dir) = &ctx.node_config().debug.invalid_block_hook {
+            info!(target: "reth::cli", ?dir, "Dumping invalid blocks");
+            beacon_consensus_engine.set_invalid_block_hook(Box::new(InvalidBlockWitnessHook::new(
+                ctx.blockchain_db().clone(),
+                ctx.components().evm_config().clone(),
+                ctx.components().block_executor().clone(),
+                ctx.chain_spec(),
+                dir.clone(),
+            )));
+        }
         info!(target: "reth::cli", "Consensus engine initialized");
 
         let events = stream_select!(
@@ -289,6 +367,62 @@ where
             ),
         );
 
+        if let Some(pipeline_events) = alert_pipeline_events {
+            let alerts = &ctx.node_config().alerts;
+            let config = AlertConfig {
+                webhooks: alerts.webhooks.clone(),
+                pagerduty_routing_key: alerts.pagerduty_routing_key.clone(),
+                stall_timeout: alerts.stall_timeout(),
+                no_peers_timeout: alerts.no_peers_timeout(),
+                min_free_space: alerts.min_free_space_bytes(),
+                datadir: ctx.data_dir().data_dir().to_path_buf(),
+            };
+            let events = stream_select!(
+                beacon_engine_handle.event_listener().map(Into::into),
+                pipeline_events.map(Into::into),
+            );
+            ctx.task_executor().spawn(Box::pin(alert::monitor_alerts(
+                config,
+                Some(ctx.components().network().clone()),
+                events,
+            )));
+            info!(target: "reth::cli", "Alerts enabled");
+        }
+
+        #[cfg(any(feature = "nats", feature = "kafka"))]
+        if let Some(url) = ctx.node_config().publisher.url.clone() {
+            use reth_node_core::args::PublisherFormat;
+            use reth_node_events::publisher::{self, PublishFormat, PublisherConfig};
+            use reth_provider::CanonStateSubscriptions;
+
+            let args = &ctx.node_config().publisher;
+            let config = PublisherConfig {
+                url,
+                topic: args.topic.clone(),
+                format: match args.format {
+                    PublisherFormat::Json => PublishFormat::Json,
+                    PublisherFormat::Protobuf => PublishFormat::Protobuf,
+                },
+            };
+            config.validate().map_err(|err| eyre::eyre!("invalid --publisher.url: {err}"))?;
+            ctx.task_executor().spawn(Box::pin(publisher::publish_canonical_state(
+                config,
+                ctx.blockchain_db().subscribe_to_canonical_state(),
+            )));
+        }
+        #[cfg(not(any(feature = "nats", feature = "kafka")))]
+        if ctx.node_config().publisher.url.is_some() {
+            eyre::bail!(
+                "--publisher.url requires reth to be built with the `nats` or `kafka` feature"
+            )
+        }
+
+        ctx.task_executor().spawn(Box::pin(sync_history::record_sync_history(
+            ctx.provider_factory().clone(),
+            vec![ctx.data_dir().db(), ctx.data_dir().static_files()],
+            sync_history_events.map(Into::into),
+        )));
+
         let client = ClientVersionV1 {
             code: CLIENT_CODE,
             name: NAME_CLIENT.to_string(),
@@ -302,12 +436,35 @@ where
             ctx.components().payload_builder().clone().into(),
             Box::new(ctx.task_executor().clone()),
             client,
+            ctx.components().pool().clone(),
         );
         info!(target: "reth::cli", "Engine API handler initialized");
 
         // extract the jwt secret from the args if possible
         let jwt_secret = ctx.auth_jwt_secret()?;
 
+        // reload the hardfork schedule on SIGHUP and admin_reloadChainSpec
+        let chain_spec_reloader = ctx
+            .node_config()
+            .chain_reload
+            .clone()
+            .map(|path| {
+                reload::ChainSpecReloader::new(
+                    path,
+                    ctx.chain_spec(),
+                    ctx.blockchain_db().clone(),
+                    ctx.components().network().clone(),
+                )
+            })
+            .transpose()?;
+        if chain_spec_reloader.is_some() {
+            info!(target: "reth::cli", path = ?ctx.node_config().chain_reload, "Chain spec reloads enabled");
+        }
+        #[cfg(unix)]
+        if let Some(reloader) = chain_spec_reloader.clone() {
+            ctx.task_executor().spawn(Box::pin(reloader.reload_on_sighup()));
+        }
+
         // Start RPC servers
         let (rpc_server_handles, mut rpc_registry) = crate::rpc::launch_rpc_servers(
             ctx.node_adapter().clone(),
@@ -315,6 +472,7 @@ where
             ctx.node_config(),
             jwt_secret,
             rpc,
+            chain_spec_reloader,
         )
         .await?;
 
@@ -326,10 +484,14 @@ where
         // Run consensus engine to completion
         let (tx, rx) = oneshot::channel();
         info!(target: "reth::cli", "Starting consensus engine");
-        ctx.task_executor().spawn_critical_blocking("consensus engine", async move {
-            let res = beacon_consensus_engine.await;
-            let _ = tx.send(res);
-        });
+        let execution_cpus = ctx.node_config().execution.cpus;
+        ctx.task_executor().spawn_critical_blocking(
+            "consensus engine",
+            run_pinned(execution_cpus, async move {
+                let res = beacon_consensus_engine.await;
+                let _ = tx.send(res);
+            }),
+        );
 
         if let Some(maybe_custom_etherscan_url) = ctx.node_config().debug.etherscan.clone() {
             info!(target: "reth::cli", "Using etherscan as consensus client");
@@ -360,10 +522,13 @@ where
             });
         }
 
-        if let Some(rpc_ws_url) = ctx.node_config().debug.rpc_consensus_ws.clone() {
+        let debug_args = &ctx.node_config().debug;
+        if let Some(rpc_url) =
+            debug_args.rpc_consensus_ws.clone().or_else(|| debug_args.rpc_sync.clone())
+        {
             info!(target: "reth::cli", "Using rpc provider as consensus client");
 
-            let block_provider = RpcBlockProvider::new(rpc_ws_url);
+            let block_provider = RpcBlockProvider::new(rpc_url);
             let rpc_consensus_client = DebugConsensusClient::new(
                 rpc_server_handles.auth.clone(),
                 Arc::new(block_provider),
@@ -389,10 +554,8 @@ where
         // Notify on node started
         on_node_started.on_event(full_node.clone())?;
 
-        let handle = NodeHandle {
-            node_exit_future: NodeExitFuture::new(rx, full_node.config.debug.terminate),
-            node: full_node,
-        };
+        let handle =
+            NodeHandle { node_exit_future: NodeExitFuture::new(rx, terminate), node: full_node };
 
         Ok(handle)
     }
@@ -0,0 +1,161 @@
+//! Reloading the hardfork schedule of a running node from its chain spec file.
+
+use eyre::Context;
+use jsonrpsee::{core::RpcResult, types::ErrorObject};
+use reth_chainspec::{ChainSpec, HardforkUpdate};
+use reth_network::NetworkHandle;
+use reth_node_core::{args::utils::genesis_value_parser, rpc::api::AdminChainSpecApiServer};
+use reth_primitives::Head;
+use reth_provider::{BlockReaderIdExt, HeaderProvider};
+use reth_tracing::tracing::{info, warn};
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, PoisonError},
+};
+
+/// Re-reads the chain spec file of the node and applies changed hardfork activations.
+///
+/// Only activations in the future of the head of the chain can change. They are
+/// [scheduled](ChainSpec::schedule_hardforks) on the chain spec of the node, which is shared by
+/// consensus, the block executor, the payload builder, the transaction pool and the Engine API,
+/// and the fork ID of the network is updated.
+#[derive(Debug, Clone)]
+pub(crate) struct ChainSpecReloader<Provider> {
+    path: PathBuf,
+    /// The chain spec of the node.
+    chain_spec: Arc<ChainSpec>,
+    /// The chain spec as last read from the file.
+    ///
+    /// Changes are computed against the file rather than the chain spec of the node, so hardfork
+    /// overrides of the node are not reported as changes. The lock also serializes reloads.
+    loaded: Arc<Mutex<Arc<ChainSpec>>>,
+    provider: Provider,
+    network: NetworkHandle,
+}
+
+impl<Provider> ChainSpecReloader<Provider>
+where
+    Provider: BlockReaderIdExt + HeaderProvider,
+{
+    /// Creates a new reloader for the given chain spec file.
+    ///
+    /// Returns an error if the file can't be read or describes a different chain than the one
+    /// the node runs.
+    pub(crate) fn new(
+        path: PathBuf,
+        chain_spec: Arc<ChainSpec>,
+        provider: Provider,
+        network: NetworkHandle,
+    ) -> eyre::Result<Self> {
+        let loaded = load(&path)?;
+        if loaded.chain != chain_spec.chain || loaded.genesis_hash() != chain_spec.genesis_hash() {
+            eyre::bail!(
+                "chain spec file {} describes a different chain than the one the node runs",
+                path.display()
+            )
+        }
+
+        Ok(Self { path, chain_spec, loaded: Arc::new(Mutex::new(loaded)), provider, network })
+    }
+
+    /// Re-reads the chain spec file and applies the hardfork activations that changed since it
+    /// was last read.
+    ///
+    /// Returns the applied changes, or an error if the file changed anything but the activations
+    /// of hardforks in the future of the head. Nothing is applied on error.
+    pub(crate) fn reload(&self) -> eyre::Result<Vec<HardforkUpdate>> {
+        let mut loaded = self.loaded.lock().unwrap_or_else(PoisonError::into_inner);
+        let file = load(&self.path)?;
+        let head = self.head()?;
+
+        let updates = loaded.hardfork_updates(&file, &head)?;
+        // the node may override the activation of a fork that the file schedules
+        if let Some(update) =
+            updates.iter().find(|update| self.chain_spec.fork(update.fork).active_at_head(&head))
+        {
+            eyre::bail!(
+                "{} can't be changed to {:?}, it is active at block {} on this node",
+                update.fork,
+                update.to,
+                head.number
+            )
+        }
+
+        if !updates.is_empty() {
+            self.chain_spec.schedule_hardforks(&updates);
+            self.network.update_fork_filter(self.chain_spec.fork_filter(head));
+        }
+        for update in &updates {
+            info!(target: "reth::cli", fork = %update.fork, from = ?update.from, to = ?update.to, "Applied hardfork activation");
+        }
+        *loaded = file;
+
+        Ok(updates)
+    }
+
+    /// Returns the current head of the chain.
+    fn head(&self) -> eyre::Result<Head> {
+        let header = self
+            .provider
+            .latest_header()?
+            .ok_or_else(|| eyre::eyre!("the head block is missing"))?;
+        let total_difficulty =
+            self.provider.header_td_by_number(header.number)?.unwrap_or_default();
+        Ok(Head {
+            number: header.number,
+            hash: header.hash(),
+            difficulty: header.difficulty,
+            total_difficulty,
+            timestamp: header.timestamp,
+        })
+    }
+
+    /// Reloads the chain spec file whenever the node receives `SIGHUP`.
+    #[cfg(unix)]
+    pub(crate) async fn reload_on_sighup(self) {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let mut hangup = match signal(SignalKind::hangup()) {
+            Ok(hangup) => hangup,
+            Err(err) => {
+                warn!(target: "reth::cli", %err, "Failed to listen for SIGHUP, chain spec reloads are only available over RPC");
+                return;
+            }
+        };
+
+        while hangup.recv().await.is_some() {
+            info!(target: "reth::cli", path = %self.path.display(), "Received SIGHUP, reloading chain spec");
+            match self.reload() {
+                Ok(updates) if updates.is_empty() => {
+                    info!(target: "reth::cli", "No hardfork activations changed")
+                }
+                Ok(_) => {}
+                Err(err) => warn!(target: "reth::cli", %err, "Rejected chain spec reload"),
+            }
+        }
+    }
+}
+
+impl<Provider> AdminChainSpecApiServer for ChainSpecReloader<Provider>
+where
+    Provider: BlockReaderIdExt + HeaderProvider + Clone + 'static,
+{
+    /// Handler for `admin_reloadChainSpec`
+    fn reload_chain_spec(&self) -> RpcResult<Vec<HardforkUpdate>> {
+        self.reload().map_err(|err| {
+            ErrorObject::owned(
+                jsonrpsee::types::error::INTERNAL_ERROR_CODE,
+                format!("{err:#}"),
+                None::<()>,
+            )
+        })
+    }
+}
+
+/// Reads the chain spec file at the given path.
+fn load(path: &Path) -> eyre::Result<Arc<ChainSpec>> {
+    let raw = std::fs::read_to_string(path)
+        .wrap_err_with(|| format!("failed to read chain spec file {}", path.display()))?;
+    genesis_value_parser(&raw)
+        .wrap_err_with(|| format!("invalid chain spec file {}", path.display()))
+}
@@ -1,14 +1,20 @@
 //! Builder support for rpc components.
 
+use crate::launch::reload::ChainSpecReloader;
 use futures::TryFutureExt;
+use jsonrpsee::Methods;
 use reth_network::NetworkHandle;
 use reth_node_api::FullNodeComponents;
-use reth_node_core::{node_config::NodeConfig, rpc::api::EngineApiServer};
+use reth_node_core::{
+    node_config::NodeConfig,
+    rpc::api::{AdminChainSpecApiServer, EngineApiServer},
+};
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_rpc_builder::{
     auth::{AuthRpcModule, AuthServerHandle},
     config::RethRpcServerConfig,
-    RethModuleRegistry, RpcModuleBuilder, RpcServerHandle, TransportRpcModules,
+    HistoricalProxyLayer, RethModuleRegistry, RethRpcModule, RpcModuleBuilder, RpcModuleSelection,
+    RpcServerHandle, TransportRpcModules,
 };
 use reth_rpc_layer::JwtSecret;
 use reth_tasks::TaskExecutor;
@@ -247,12 +253,16 @@ impl<'a, Node: FullNodeComponents> RpcContext<'a, Node> {
 }
 
 /// Launch the rpc servers.
+///
+/// The `admin_reloadChainSpec` method of the given reloader is added to every transport that
+/// serves the `admin` namespace.
 pub(crate) async fn launch_rpc_servers<Node, Engine>(
     node: Node,
     engine_api: Engine,
     config: &NodeConfig,
     jwt_secret: JwtSecret,
     hooks: RpcHooks<Node>,
+    chain_spec_reloader: Option<ChainSpecReloader<Node::Provider>>,
 ) -> eyre::Result<(RethRpcServerHandles, RpcRegistry<Node>)>
 where
     Node: FullNodeComponents + Clone,
@@ -261,7 +271,12 @@ where
     let RpcHooks { on_rpc_started, extend_rpc_modules } = hooks;
 
     let auth_config = config.rpc.auth_server_config(jwt_secret)?;
-    let module_config = config.rpc.transport_rpc_module_config();
+    let mut module_config = config.rpc.transport_rpc_module_config();
+    if config.rpc.rpc_persist_filters {
+        if let Some(rpc_config) = module_config.config_mut() {
+            rpc_config.eth_mut().filters_path = Some(config.datadir().rpc_filters());
+        }
+    }
     debug!(target: "reth::cli", http=?module_config.http(), ws=?module_config.ws(), "Using RPC module config");
 
     let (mut modules, mut auth_module, registry) = RpcModuleBuilder::default()
@@ -273,6 +288,25 @@ where
         .with_evm_config(node.evm_config().clone())
         .build_with_auth_server(module_config, engine_api);
 
+    if let Some(reloader) = chain_spec_reloader {
+        let methods: Methods = reloader.into_rpc().into();
+        let serves_admin = |selection: Option<&RpcModuleSelection>| {
+            selection.is_some_and(|selection| {
+                selection.iter_selection().any(|module| module == RethRpcModule::Admin)
+            })
+        };
+        let module_config = modules.module_config().clone();
+        if serves_admin(module_config.http()) {
+            modules.merge_http(methods.clone())?;
+        }
+        if serves_admin(module_config.ws()) {
+            modules.merge_ws(methods.clone())?;
+        }
+        if serves_admin(module_config.ipc()) {
+            modules.merge_ipc(methods)?;
+        }
+    }
+
     let mut registry = RpcRegistry { registry };
     let ctx = RpcContext {
         node: node.clone(),
@@ -284,7 +318,13 @@ where
 
     extend_rpc_modules.extend_rpc_modules(ctx)?;
 
-    let server_config = config.rpc.rpc_server_config();
+    let mut server_config = config.rpc.rpc_server_config();
+    if let Some(url) = &config.rpc.rpc_historical_upstream {
+        let proxy =
+            HistoricalProxyLayer::new(url, node.provider().clone(), config.network.proxy.as_ref())?;
+        server_config = server_config.with_historical_proxy(proxy);
+        info!(target: "reth::cli", %url, "Proxying calls that need pruned history");
+    }
     let launch_rpc = modules.clone().start_server(server_config).map_ok(|handle| {
         if let Some(path) = handle.ipc_endpoint() {
             info!(target: "reth::cli", %path, "RPC IPC server started");
@@ -123,6 +123,7 @@ where
                     prune_modes,
                     exex_manager_handle,
                 )
+                .with_state_cache_size(stage_config.execution.state_cache_size)
                 .with_metrics_tx(metrics_tx),
             ),
         )
@@ -0,0 +1,413 @@
+//! Alerts about the health of the node, sent to webhooks and PagerDuty.
+
+use crate::node::NodeEvent;
+use futures::{Stream, StreamExt};
+use reqwest::{Client, Url};
+use reth_beacon_consensus::BeaconConsensusEngineEvent;
+use reth_network::NetworkHandle;
+use reth_network_api::PeersInfo;
+use reth_primitives::BlockNumber;
+use reth_stages::{PipelineEvent, StageId};
+use serde_json::json;
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::time::Instant;
+use tracing::{debug, warn};
+
+/// Interval of checking the health of the node.
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+/// Timeout of a single alert request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// The endpoint of the PagerDuty Events API v2.
+const PAGERDUTY_EVENTS_URL: &str = "https://events.pagerduty.com/v2/enqueue";
+
+/// Configuration of the alerts.
+#[derive(Debug, Clone)]
+pub struct AlertConfig {
+    /// Webhooks the alerts are POSTed to as JSON.
+    pub webhooks: Vec<Url>,
+    /// The routing key of the PagerDuty service the alerts are sent to.
+    pub pagerduty_routing_key: Option<String>,
+    /// Period without new canonical blocks or sync progress, after which the node is stalled.
+    pub stall_timeout: Duration,
+    /// Period without connected peers, after which an alert is sent.
+    pub no_peers_timeout: Duration,
+    /// Free disk space in bytes below which an alert is sent, disabled if zero.
+    pub min_free_space: u64,
+    /// The directory whose disk is checked for free space.
+    pub datadir: PathBuf,
+}
+
+/// An alert about the health of the node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Alert {
+    /// No new canonical block was imported and the pipeline made no progress for a while.
+    Stalled {
+        /// The latest canonical block, if known.
+        latest_block: Option<BlockNumber>,
+        /// The period since the last progress.
+        elapsed: Duration,
+    },
+    /// The node had no connected peers for a while.
+    NoPeers {
+        /// The period since the last connected peer.
+        elapsed: Duration,
+    },
+    /// The free disk space of the datadir is below the threshold.
+    LowDiskSpace {
+        /// The free disk space in bytes.
+        available: u64,
+        /// The configured threshold in bytes.
+        threshold: u64,
+    },
+    /// A pipeline stage failed.
+    StageError {
+        /// The stage that failed.
+        stage_id: StageId,
+    },
+    /// Canonicalization is halted by a reorg deeper than the maximum reorg depth.
+    ReorgHalted {
+        /// The fork block of the reorg.
+        fork_block: BlockNumber,
+        /// The number of canonical blocks the reorg reverts.
+        depth: u64,
+    },
+}
+
+impl Alert {
+    /// Returns the kind of the alert, as sent in the payload.
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::Stalled { .. } => "stalled",
+            Self::NoPeers { .. } => "no_peers",
+            Self::LowDiskSpace { .. } => "low_disk_space",
+            Self::StageError { .. } => "stage_error",
+            Self::ReorgHalted { .. } => "reorg_halted",
+        }
+    }
+
+    /// Returns the key that identifies the alert until it's resolved.
+    fn key(&self) -> String {
+        match self {
+            Self::StageError { stage_id } => format!("reth-{}-{stage_id}", self.kind()),
+            _ => format!("reth-{}", self.kind()),
+        }
+    }
+
+    /// Returns the severity of the alert, using the PagerDuty severities.
+    const fn severity(&self) -> &'static str {
+        match self {
+            Self::Stalled { .. } | Self::StageError { .. } | Self::ReorgHalted { .. } => "critical",
+            Self::NoPeers { .. } | Self::LowDiskSpace { .. } => "warning",
+        }
+    }
+
+    /// Returns a human readable summary of the alert.
+    pub fn summary(&self) -> String {
+        match self {
+            Self::Stalled { latest_block: Some(block), elapsed } => format!(
+                "No new canonical block for {}, latest block is {block}",
+                humantime::format_duration(*elapsed)
+            ),
+            Self::Stalled { latest_block: None, elapsed } => {
+                format!("No new canonical block for {}", humantime::format_duration(*elapsed))
+            }
+            Self::NoPeers { elapsed } => {
+                format!("No connected peers for {}", humantime::format_duration(*elapsed))
+            }
+            Self::LowDiskSpace { available, threshold } => format!(
+                "Free disk space is {} GB, below the threshold of {} GB",
+                available / GB,
+                threshold / GB
+            ),
+            Self::StageError { stage_id } => format!("Stage {stage_id} failed"),
+            Self::ReorgHalted { fork_block, depth } => format!(
+                "Canonicalization is halted by a reorg of {depth} blocks to block {fork_block}, \
+                 resume it with `reth unwind --force`"
+            ),
+        }
+    }
+}
+
+/// Bytes in a GB.
+const GB: u64 = 1024 * 1024 * 1024;
+
+/// The status of an alert notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AlertStatus {
+    /// The condition of the alert started.
+    Triggered,
+    /// The condition of the alert ended.
+    Resolved,
+}
+
+impl AlertStatus {
+    const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Triggered => "triggered",
+            Self::Resolved => "resolved",
+        }
+    }
+
+    /// Returns the PagerDuty event action of the status.
+    const fn pagerduty_action(&self) -> &'static str {
+        match self {
+            Self::Triggered => "trigger",
+            Self::Resolved => "resolve",
+        }
+    }
+}
+
+/// Monitors the health of the node and sends alerts while it's unhealthy.
+///
+/// Every alert is sent once when its condition starts, and once more when it ends.
+pub async fn monitor_alerts<E>(config: AlertConfig, network: Option<NetworkHandle>, mut events: E)
+where
+    E: Stream<Item = NodeEvent> + Unpin,
+{
+    let client = match Client::builder().timeout(REQUEST_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(err) => {
+            warn!(target: "reth::cli", %err, "Failed to create the alert client, alerts are disabled");
+            return
+        }
+    };
+    let mut monitor = AlertMonitor::new(config, client, network);
+
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => monitor.check(),
+            event = events.next() => match event {
+                Some(event) => monitor.handle_event(event),
+                None => return,
+            },
+        }
+    }
+}
+
+/// The state of the alert monitor.
+#[derive(Debug)]
+struct AlertMonitor {
+    config: AlertConfig,
+    client: Client,
+    network: Option<NetworkHandle>,
+    /// The alerts whose condition didn't end yet, by key.
+    active: HashMap<String, Alert>,
+    /// The time of the latest canonical block or sync progress.
+    last_progress: Instant,
+    /// The latest canonical block.
+    latest_block: Option<BlockNumber>,
+    /// The time since which the node has no connected peers.
+    no_peers_since: Option<Instant>,
+    /// The latest checkpoint of each stage.
+    checkpoints: HashMap<StageId, BlockNumber>,
+}
+
+impl AlertMonitor {
+    fn new(config: AlertConfig, client: Client, network: Option<NetworkHandle>) -> Self {
+        Self {
+            config,
+            client,
+            network,
+            active: HashMap::new(),
+            last_progress: Instant::now(),
+            latest_block: None,
+            no_peers_since: None,
+            checkpoints: HashMap::new(),
+        }
+    }
+
+    /// Records the progress of the node from its events.
+    fn handle_event(&mut self, event: NodeEvent) {
+        match event {
+            NodeEvent::Pipeline(PipelineEvent::Ran { stage_id, result, .. }) => {
+                let block_number = result.checkpoint.block_number;
+                if self.checkpoints.insert(stage_id, block_number) != Some(block_number) {
+                    self.record_progress();
+                }
+                self.resolve(&Alert::StageError { stage_id }.key());
+            }
+            NodeEvent::Pipeline(PipelineEvent::Error { stage_id }) => {
+                self.trigger(Alert::StageError { stage_id });
+            }
+            NodeEvent::ConsensusEngine(BeaconConsensusEngineEvent::CanonicalChainCommitted(
+                head,
+                _,
+            )) => {
+                self.latest_block = Some(head.number);
+                self.record_progress();
+            }
+            // the halt only ends when the node is restarted after the operator cleared it
+            NodeEvent::ConsensusEngine(BeaconConsensusEngineEvent::CanonicalizationHalted {
+                fork_block,
+                depth,
+            }) => {
+                self.trigger(Alert::ReorgHalted { fork_block, depth });
+            }
+            _ => {}
+        }
+    }
+
+    fn record_progress(&mut self) {
+        self.last_progress = Instant::now();
+        self.resolve(&Alert::Stalled { latest_block: None, elapsed: Duration::ZERO }.key());
+    }
+
+    /// Checks the health conditions that are not signaled by events.
+    fn check(&mut self) {
+        let elapsed = self.last_progress.elapsed();
+        if elapsed >= self.config.stall_timeout {
+            self.trigger(Alert::Stalled { latest_block: self.latest_block, elapsed });
+        }
+
+        if let Some(network) = &self.network {
+            if network.num_connected_peers() == 0 {
+                let elapsed = self.no_peers_since.get_or_insert_with(Instant::now).elapsed();
+                if elapsed >= self.config.no_peers_timeout {
+                    self.trigger(Alert::NoPeers { elapsed });
+                }
+            } else {
+                self.no_peers_since = None;
+                self.resolve(&Alert::NoPeers { elapsed: Duration::ZERO }.key());
+            }
+        }
+
+        let threshold = self.config.min_free_space;
+        if threshold > 0 {
+            match reth_fs_util::available_space(&self.config.datadir) {
+                Some(available) if available < threshold => {
+                    self.trigger(Alert::LowDiskSpace { available, threshold });
+                }
+                Some(_) => {
+                    self.resolve(&Alert::LowDiskSpace { available: 0, threshold }.key());
+                }
+                None => {
+                    debug!(target: "reth::cli", datadir = %self.config.datadir.display(), "Could not determine the free disk space");
+                }
+            }
+        }
+    }
+
+    /// Sends the alert, unless it's already active.
+    fn trigger(&mut self, alert: Alert) {
+        let key = alert.key();
+        if self.active.contains_key(&key) {
+            return
+        }
+        warn!(target: "reth::cli", kind = alert.kind(), "{}", alert.summary());
+        self.send(&alert, AlertStatus::Triggered);
+        self.active.insert(key, alert);
+    }
+
+    /// Sends the resolution of the alert with the given key, if it's active.
+    fn resolve(&mut self, key: &str) {
+        if let Some(alert) = self.active.remove(key) {
+            self.send(&alert, AlertStatus::Resolved);
+        }
+    }
+
+    /// Sends the alert to all webhooks and PagerDuty in the background.
+    fn send(&self, alert: &Alert, status: AlertStatus) {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let summary = alert.summary();
+
+        let payload = json!({
+            "kind": alert.kind(),
+            "status": status.as_str(),
+            "severity": alert.severity(),
+            "summary": summary,
+            "timestamp": timestamp,
+        });
+        let mut requests = self
+            .config
+            .webhooks
+            .iter()
+            .map(|url| self.client.post(url.clone()).json(&payload))
+            .collect::<Vec<_>>();
+
+        if let Some(routing_key) = &self.config.pagerduty_routing_key {
+            let event = json!({
+                "routing_key": routing_key,
+                "event_action": status.pagerduty_action(),
+                "dedup_key": alert.key(),
+                "payload": {
+                    "summary": summary,
+                    "source": "reth",
+                    "severity": alert.severity(),
+                },
+            });
+            requests.push(self.client.post(PAGERDUTY_EVENTS_URL).json(&event));
+        }
+
+        for request in requests {
+            tokio::spawn(async move {
+                if let Err(err) = request.send().await.and_then(|res| res.error_for_status()) {
+                    warn!(target: "reth::cli", %err, "Failed to send alert");
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(stall_timeout: Duration) -> AlertMonitor {
+        let config = AlertConfig {
+            webhooks: Vec::new(),
+            pagerduty_routing_key: None,
+            stall_timeout,
+            no_peers_timeout: Duration::from_secs(300),
+            min_free_space: 0,
+            datadir: PathBuf::new(),
+        };
+        AlertMonitor::new(config, Client::new(), None)
+    }
+
+    #[test]
+    fn stage_error_is_sent_once_until_resolved() {
+        let mut monitor = monitor(Duration::from_secs(600));
+        let stage_id = StageId::Execution;
+
+        monitor.handle_event(NodeEvent::Pipeline(PipelineEvent::Error { stage_id }));
+        monitor.handle_event(NodeEvent::Pipeline(PipelineEvent::Error { stage_id }));
+        assert_eq!(monitor.active.len(), 1);
+
+        monitor.resolve(&Alert::StageError { stage_id }.key());
+        assert!(monitor.active.is_empty());
+    }
+
+    #[test]
+    fn reorg_halt_is_critical() {
+        let mut monitor = monitor(Duration::from_secs(600));
+        for _ in 0..2 {
+            monitor.handle_event(NodeEvent::ConsensusEngine(
+                BeaconConsensusEngineEvent::CanonicalizationHalted { fork_block: 100, depth: 70 },
+            ));
+        }
+        let alerts = monitor.active.values().collect::<Vec<_>>();
+        assert_eq!(alerts, [&Alert::ReorgHalted { fork_block: 100, depth: 70 }]);
+        assert_eq!(alerts[0].severity(), "critical");
+    }
+
+    #[test]
+    fn stall_is_resolved_by_progress() {
+        let mut healthy = monitor(Duration::from_secs(600));
+        healthy.check();
+        assert!(healthy.active.is_empty());
+
+        let mut stalled = monitor(Duration::ZERO);
+        stalled.check();
+        assert_eq!(stalled.active.values().map(Alert::kind).collect::<Vec<_>>(), ["stalled"]);
+
+        stalled.record_progress();
+        assert!(stalled.active.is_empty());
+    }
+}
@@ -8,5 +8,9 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+pub mod alert;
 pub mod cl;
 pub mod node;
+#[cfg(any(feature = "nats", feature = "kafka"))]
+pub mod publisher;
+pub mod sync_history;
@@ -7,6 +7,7 @@ use reth_beacon_consensus::{
     BeaconConsensusEngineEvent, ConsensusEngineLiveSyncProgress, ForkchoiceStatus,
 };
 use reth_db_api::{database::Database, database_metrics::DatabaseMetadata};
+use reth_metrics::{metrics::Gauge, Metrics};
 use reth_network::{NetworkEvent, NetworkHandle};
 use reth_network_api::PeersInfo;
 use reth_primitives::{constants, BlockNumber, B256};
@@ -14,6 +15,7 @@ use reth_prune::PrunerEvent;
 use reth_stages::{EntitiesCheckpoint, ExecOutput, PipelineEvent, StageCheckpoint, StageId};
 use reth_static_file::StaticFileProducerEvent;
 use std::{
+    collections::{HashMap, VecDeque},
     fmt::{Display, Formatter},
     future::Future,
     pin::Pin,
@@ -21,10 +23,12 @@ use std::{
     time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 use tokio::time::Interval;
-use tracing::{debug, info, warn};
+use tracing::{debug, error, info, warn};
 
 /// Interval of reporting node state.
 const INFO_MESSAGE_INTERVAL: Duration = Duration::from_secs(25);
+/// The period of stage checkpoints the throughput of a stage is calculated over for its ETA.
+const ETA_WINDOW: Duration = Duration::from_secs(10 * 60);
 
 /// The current high-level state of the node, including the node's database environment, network
 /// connections, current processing stage, and the latest block information. It provides
@@ -49,14 +53,12 @@ struct NodeState<DB> {
     safe_block_hash: Option<B256>,
     /// Hash of finalized block last set by fork choice update
     finalized_block_hash: Option<B256>,
+    /// The ETA metrics of the stages, by stage.
+    eta_metrics: HashMap<StageId, StageEtaMetrics>,
 }
 
 impl<DB> NodeState<DB> {
-    const fn new(
-        db: DB,
-        network: Option<NetworkHandle>,
-        latest_block: Option<BlockNumber>,
-    ) -> Self {
+    fn new(db: DB, network: Option<NetworkHandle>, latest_block: Option<BlockNumber>) -> Self {
         Self {
             db,
             network,
@@ -66,6 +68,7 @@ impl<DB> NodeState<DB> {
             head_block_hash: None,
             safe_block_hash: None,
             finalized_block_hash: None,
+            eta_metrics: HashMap::new(),
         }
     }
 
@@ -82,7 +85,7 @@ impl<DB> NodeState<DB> {
                     stage_id,
                     eta: match &self.current_stage {
                         Some(current_stage) if current_stage.stage_id == stage_id => {
-                            current_stage.eta
+                            current_stage.eta.clone()
                         }
                         _ => Eta::default(),
                     },
@@ -112,7 +115,7 @@ impl<DB> NodeState<DB> {
                     stage_id,
                     eta: match &self.current_stage {
                         Some(current_stage) if current_stage.stage_id == stage_id => {
-                            current_stage.eta
+                            current_stage.eta.clone()
                         }
                         _ => Eta::default(),
                     },
@@ -160,6 +163,7 @@ impl<DB> NodeState<DB> {
                     current_stage.checkpoint = checkpoint;
                     current_stage.entities_checkpoint = checkpoint.entities();
                     current_stage.eta.update(stage_id, checkpoint);
+                    record_eta_metrics(&mut self.eta_metrics, stage_id, &current_stage.eta);
 
                     let target = OptionalField(current_stage.target);
                     let stage_progress = current_stage
@@ -214,6 +218,7 @@ impl<DB> NodeState<DB> {
                 }
 
                 if done {
+                    record_eta_metrics(&mut self.eta_metrics, stage_id, &Eta::default());
                     self.current_stage = None;
                 }
             }
@@ -298,6 +303,13 @@ impl<DB> NodeState<DB> {
             BeaconConsensusEngineEvent::ForkBlockAdded(block) => {
                 info!(number=block.number, hash=?block.hash(), "Block added to fork chain");
             }
+            BeaconConsensusEngineEvent::CanonicalizationHalted { fork_block, depth } => {
+                error!(
+                    fork_block,
+                    depth,
+                    "Canonicalization is halted by a deep reorg, resume it with `reth unwind --force`"
+                );
+            }
         }
     }
 
@@ -592,11 +604,16 @@ where
 /// A container calculating the estimated time that a stage will complete in, based on stage
 /// checkpoints reported by the pipeline.
 ///
+/// The throughput of the stage is calculated over the checkpoints of the last [`ETA_WINDOW`], so
+/// the ETA follows changes of the throughput during long running stages.
+///
 /// One `Eta` is only valid for a single stage.
-#[derive(Default, Copy, Clone)]
+#[derive(Default, Clone)]
 struct Eta {
-    /// The last stage checkpoint
-    last_checkpoint: EntitiesCheckpoint,
+    /// The stage checkpoints within the window, with the time they were reported, oldest first.
+    ///
+    /// The newest checkpoint older than the window is kept, so that the window is fully covered.
+    checkpoints: VecDeque<(Instant, EntitiesCheckpoint)>,
     /// The last time the stage reported its checkpoint
     last_checkpoint_time: Option<Instant>,
     /// The current ETA
@@ -607,29 +624,50 @@ impl Eta {
     /// Update the ETA given the checkpoint, if possible.
     fn update(&mut self, stage: StageId, checkpoint: StageCheckpoint) {
         let Some(current) = checkpoint.entities() else { return };
+        let now = Instant::now();
 
-        if let Some(last_checkpoint_time) = &self.last_checkpoint_time {
-            let Some(processed_since_last) =
-                current.processed.checked_sub(self.last_checkpoint.processed)
-            else {
-                self.eta = None;
-                debug!(target: "reth::cli", %stage, ?current, ?self.last_checkpoint, "Failed to calculate the ETA: processed entities is less than the last checkpoint");
-                return
-            };
-            let elapsed = last_checkpoint_time.elapsed();
-            let per_second = processed_since_last as f64 / elapsed.as_secs_f64();
-
-            let Some(remaining) = current.total.checked_sub(current.processed) else {
-                self.eta = None;
-                debug!(target: "reth::cli", %stage, ?current, "Failed to calculate the ETA: total entities is less than processed entities");
-                return
-            };
-
-            self.eta = Duration::try_from_secs_f64(remaining as f64 / per_second).ok();
+        if self.checkpoints.back().is_some_and(|(_, last)| current.processed < last.processed) {
+            debug!(target: "reth::cli", %stage, ?current, last_checkpoint = ?self.checkpoints.back(), "Resetting the ETA: processed entities is less than the last checkpoint");
+            self.checkpoints.clear();
         }
+        while self
+            .checkpoints
+            .get(1)
+            .is_some_and(|(time, _)| now.duration_since(*time) >= ETA_WINDOW)
+        {
+            self.checkpoints.pop_front();
+        }
+        self.checkpoints.push_back((now, current));
+        self.last_checkpoint_time = Some(now);
+
+        let Some(per_second) = self.throughput() else {
+            self.eta = None;
+            return
+        };
+        let Some(remaining) = current.total.checked_sub(current.processed) else {
+            self.eta = None;
+            debug!(target: "reth::cli", %stage, ?current, "Failed to calculate the ETA: total entities is less than processed entities");
+            return
+        };
+
+        self.eta = Duration::try_from_secs_f64(remaining as f64 / per_second).ok();
+    }
 
-        self.last_checkpoint = current;
-        self.last_checkpoint_time = Some(Instant::now());
+    /// Returns the entities processed per second over the window, if at least two checkpoints
+    /// were reported.
+    ///
+    /// The entities of [`StageId::Execution`] are gas, so its throughput is gas per second.
+    fn throughput(&self) -> Option<f64> {
+        let ((first_time, first), (last_time, last)) =
+            self.checkpoints.front().zip(self.checkpoints.back())?;
+        let elapsed = last_time.duration_since(*first_time).as_secs_f64();
+        (elapsed > 0.0).then(|| (last.processed - first.processed) as f64 / elapsed)
+    }
+
+    /// Returns the time until the stage completes, as of now.
+    fn remaining(&self) -> Option<Duration> {
+        let (eta, last_checkpoint_time) = self.eta.zip(self.last_checkpoint_time)?;
+        eta.checked_sub(last_checkpoint_time.elapsed())
     }
 
     /// Returns `true` if the ETA is available, i.e. at least one checkpoint has been reported.
@@ -640,12 +678,11 @@ impl Eta {
     /// Format ETA for a given stage.
     ///
     /// NOTE: Currently ETA is enabled only for the stages that have predictable progress.
-    /// It's not the case for network-dependent ([`StageId::Headers`] and [`StageId::Bodies`]) and
-    /// [`StageId::Execution`] stages.
+    /// It's not the case for network-dependent ([`StageId::Headers`] and [`StageId::Bodies`])
+    /// stages. The progress of [`StageId::Execution`] is counted in gas, so its ETA accounts for
+    /// the blocks getting heavier.
     fn fmt_for_stage(&self, stage: StageId) -> Option<String> {
-        if !self.is_available() ||
-            matches!(stage, StageId::Headers | StageId::Bodies | StageId::Execution)
-        {
+        if !self.is_available() || matches!(stage, StageId::Headers | StageId::Bodies) {
             None
         } else {
             Some(self.to_string())
@@ -655,22 +692,43 @@ impl Eta {
 
 impl Display for Eta {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        if let Some((eta, last_checkpoint_time)) = self.eta.zip(self.last_checkpoint_time) {
-            let remaining = eta.checked_sub(last_checkpoint_time.elapsed());
-
-            if let Some(remaining) = remaining {
-                return write!(
-                    f,
-                    "{}",
-                    humantime::format_duration(Duration::from_secs(remaining.as_secs()))
-                )
-            }
+        if let Some(remaining) = self.remaining() {
+            return write!(
+                f,
+                "{}",
+                humantime::format_duration(Duration::from_secs(remaining.as_secs()))
+            )
         }
 
         write!(f, "unknown")
     }
 }
 
+/// Sets the ETA metrics of the stage, or resets them if its ETA isn't available.
+fn record_eta_metrics(
+    metrics: &mut HashMap<StageId, StageEtaMetrics>,
+    stage_id: StageId,
+    eta: &Eta,
+) {
+    let metrics = metrics
+        .entry(stage_id)
+        .or_insert_with(|| StageEtaMetrics::new_with_labels(&[("stage", stage_id.to_string())]));
+    let remaining = eta.fmt_for_stage(stage_id).and(eta.remaining()).unwrap_or_default();
+    metrics.eta_seconds.set(remaining.as_secs_f64());
+    metrics.entities_per_second.set(eta.throughput().unwrap_or_default());
+}
+
+/// Metrics for the ETA of a stage.
+#[derive(Metrics)]
+#[metrics(scope = "sync")]
+struct StageEtaMetrics {
+    /// The estimated time in seconds until the stage completes, zero if unknown.
+    eta_seconds: Gauge,
+    /// The entities processed per second over the ETA window, gas per second for the execution
+    /// stage.
+    entities_per_second: Gauge,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -690,4 +748,30 @@ mod tests {
 
         assert_eq!(eta, "13m 37s");
     }
+
+    #[test]
+    fn eta_throughput_over_window() {
+        let now = Instant::now();
+        let checkpoint = |processed| EntitiesCheckpoint { processed, total: 1_000 };
+        let mut eta = Eta::default();
+        assert_eq!(eta.throughput(), None);
+
+        eta.checkpoints.push_back((now, checkpoint(100)));
+        assert_eq!(eta.throughput(), None);
+
+        eta.checkpoints.push_back((now + Duration::from_secs(100), checkpoint(200)));
+        eta.checkpoints.push_back((now + Duration::from_secs(200), checkpoint(500)));
+        assert_eq!(eta.throughput(), Some(2.0));
+    }
+
+    #[test]
+    fn eta_for_execution() {
+        let eta = Eta {
+            last_checkpoint_time: Some(Instant::now()),
+            eta: Some(Duration::from_secs(60 * 60)),
+            ..Default::default()
+        };
+        assert!(eta.fmt_for_stage(StageId::Execution).is_some());
+        assert!(eta.fmt_for_stage(StageId::Bodies).is_none());
+    }
 }
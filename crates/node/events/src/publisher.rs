@@ -0,0 +1,602 @@
+//! Publishes new canonical blocks, their receipts and logs to a message broker.
+//!
+//! Supported brokers are NATS (`nats://host:port`) with the `nats` feature and Kafka
+//! (`kafka://host:port`) with the `kafka` feature. Blocks are published to the `<topic>.blocks`
+//! topic and logs to the `<topic>.logs` topic, encoded as JSON or protobuf, see [`proto`] for the
+//! protobuf schema.
+//!
+//! Delivery is at most once: messages that can't be published are dropped.
+
+use reth_metrics::{metrics::Counter, Metrics};
+use reth_primitives::{Address, BlockNumber, Bytes, Receipt, SealedBlockWithSenders, B256};
+use reth_provider::{CanonStateNotification, CanonStateNotifications, Chain};
+#[cfg(feature = "kafka")]
+use rskafka::{
+    client::{
+        partition::{Compression, PartitionClient, UnknownTopicHandling},
+        ClientBuilder,
+    },
+    record::Record,
+};
+use serde::Serialize;
+#[cfg(feature = "kafka")]
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{debug, info, warn};
+use url::Url;
+
+/// Delay between attempts to connect to the message broker.
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+/// The port of a Kafka broker if the URL doesn't specify one.
+#[cfg(feature = "kafka")]
+const DEFAULT_KAFKA_PORT: u16 = 9092;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Configuration of the publisher.
+#[derive(Debug, Clone)]
+pub struct PublisherConfig {
+    /// The URL of the message broker, with a `nats` or `kafka` scheme.
+    pub url: Url,
+    /// The prefix of the topics that messages are published to.
+    pub topic: String,
+    /// The encoding of the messages.
+    pub format: PublishFormat,
+}
+
+/// The encoding of published messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PublishFormat {
+    /// JSON, with hex encoded hashes, addresses and data.
+    Json,
+    /// Protobuf, with the messages in [`proto`].
+    Protobuf,
+}
+
+/// Publishes every canonical state notification to the message broker, until the notification
+/// channel is closed.
+///
+/// Blocks of reverted chains are published with `removed` set, before the blocks of the new chain.
+pub async fn publish_canonical_state(
+    config: PublisherConfig,
+    mut notifications: CanonStateNotifications,
+) {
+    let metrics = PublisherMetrics::default();
+    let mut broker = loop {
+        match Broker::connect(&config.url).await {
+            Ok(broker) => break broker,
+            Err(err) => {
+                warn!(target: "reth::cli", url = %config.url, %err, "Failed to connect to the message broker, retrying");
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        }
+    };
+    info!(target: "reth::cli", url = %config.url, topic = %config.topic, "Publishing canonical blocks");
+
+    loop {
+        let notification = match notifications.recv().await {
+            Ok(notification) => notification,
+            Err(RecvError::Lagged(skipped)) => {
+                warn!(target: "reth::cli", skipped, "Publisher fell behind, notifications were skipped");
+                metrics.notifications_skipped_total.increment(skipped);
+                continue
+            }
+            Err(RecvError::Closed) => return,
+        };
+
+        let messages = notification_messages(&notification, &config.topic, config.format);
+        let count = messages.len() as u64;
+        match broker.publish(messages).await {
+            Ok(()) => {
+                debug!(target: "reth::cli", tip = notification.tip().number, count, "Published canonical blocks");
+                metrics.messages_published_total.increment(count);
+            }
+            Err(err) => {
+                warn!(target: "reth::cli", tip = notification.tip().number, %err, "Failed to publish canonical blocks");
+                metrics.messages_failed_total.increment(count);
+            }
+        }
+    }
+}
+
+/// Metrics for the publisher.
+#[derive(Metrics)]
+#[metrics(scope = "publisher")]
+struct PublisherMetrics {
+    /// The total number of messages published to the message broker.
+    messages_published_total: Counter,
+    /// The total number of messages that failed to be published.
+    messages_failed_total: Counter,
+    /// The total number of canonical state notifications skipped because the publisher fell
+    /// behind.
+    notifications_skipped_total: Counter,
+}
+
+/// A connection to a message broker.
+enum Broker {
+    #[cfg(feature = "nats")]
+    Nats(async_nats::Client),
+    #[cfg(feature = "kafka")]
+    Kafka {
+        client: rskafka::client::Client,
+        /// Clients of partition 0 of every topic, by topic.
+        partitions: HashMap<String, PartitionClient>,
+    },
+}
+
+impl Broker {
+    /// Connects to the message broker at the given URL.
+    async fn connect(url: &Url) -> Result<Self, BoxError> {
+        match url.scheme() {
+            #[cfg(feature = "nats")]
+            "nats" => Ok(Self::Nats(async_nats::connect(url.as_str()).await?)),
+            #[cfg(feature = "kafka")]
+            "kafka" => {
+                let host = url.host_str().ok_or("missing Kafka broker host")?;
+                let port = url.port().unwrap_or(DEFAULT_KAFKA_PORT);
+                let client = ClientBuilder::new(vec![format!("{host}:{port}")]).build().await?;
+                Ok(Self::Kafka { client, partitions: HashMap::new() })
+            }
+            scheme => Err(unsupported_scheme(scheme).into()),
+        }
+    }
+
+    /// Publishes the messages in order.
+    async fn publish(&mut self, messages: Vec<Message>) -> Result<(), BoxError> {
+        match self {
+            #[cfg(feature = "nats")]
+            Self::Nats(client) => {
+                for message in messages {
+                    client.publish(message.topic, message.payload.into()).await?;
+                }
+                client.flush().await?;
+            }
+            #[cfg(feature = "kafka")]
+            Self::Kafka { client, partitions } => {
+                let mut records = HashMap::<String, Vec<Record>>::new();
+                for message in messages {
+                    records.entry(message.topic).or_default().push(Record {
+                        key: Some(message.key),
+                        value: Some(message.payload),
+                        headers: Default::default(),
+                        timestamp: chrono::Utc::now(),
+                    });
+                }
+                for (topic, records) in records {
+                    if !partitions.contains_key(&topic) {
+                        let partition = client
+                            .partition_client(topic.clone(), 0, UnknownTopicHandling::Retry)
+                            .await?;
+                        partitions.insert(topic.clone(), partition);
+                    }
+                    partitions[&topic].produce(records, Compression::NoCompression).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns an error message if reth can't publish to brokers with the given URL scheme.
+fn unsupported_scheme(scheme: &str) -> String {
+    match scheme {
+        "nats" | "kafka" => {
+            format!("reth was built without {scheme} support, enable the `{scheme}` feature")
+        }
+        scheme => format!("unsupported message broker scheme: {scheme}"),
+    }
+}
+
+impl PublisherConfig {
+    /// Checks that reth was built with support for the message broker of the URL.
+    pub fn validate(&self) -> Result<(), String> {
+        match self.url.scheme() {
+            #[cfg(feature = "nats")]
+            "nats" => Ok(()),
+            #[cfg(feature = "kafka")]
+            "kafka" => Ok(()),
+            scheme => Err(unsupported_scheme(scheme)),
+        }
+    }
+}
+
+/// An encoded message for a topic.
+#[derive(Debug)]
+struct Message {
+    topic: String,
+    /// The key of the message, the hash of its block.
+    #[cfg_attr(not(feature = "kafka"), allow(dead_code))]
+    key: Vec<u8>,
+    payload: Vec<u8>,
+}
+
+/// Returns the messages of the blocks of a notification.
+fn notification_messages(
+    notification: &CanonStateNotification,
+    topic: &str,
+    format: PublishFormat,
+) -> Vec<Message> {
+    let mut blocks = Vec::new();
+    if let Some(reverted) = notification.reverted() {
+        let mut reverted = chain_blocks(&reverted, true);
+        reverted.reverse();
+        blocks.extend(reverted);
+    }
+    blocks.extend(chain_blocks(&notification.committed(), false));
+
+    let mut messages = Vec::new();
+    for (block, logs) in blocks {
+        let key = block.hash.to_vec();
+        messages.push(Message {
+            topic: format!("{topic}.blocks"),
+            key: key.clone(),
+            payload: format.encode(&block),
+        });
+        for log in logs {
+            messages.push(Message {
+                topic: format!("{topic}.logs"),
+                key: key.clone(),
+                payload: format.encode(&log),
+            });
+        }
+    }
+    messages
+}
+
+impl PublishFormat {
+    /// Encodes a message in this format.
+    fn encode<T>(self, message: &T) -> Vec<u8>
+    where
+        T: Serialize + ToProto,
+    {
+        match self {
+            Self::Json => serde_json::to_vec(message).expect("messages serialize to JSON"),
+            Self::Protobuf => prost::Message::encode_to_vec(&message.to_proto()),
+        }
+    }
+}
+
+/// Returns the block and log messages of the blocks of a chain, in increasing block order.
+fn chain_blocks(chain: &Chain, removed: bool) -> Vec<(BlockMessage, Vec<LogMessage>)> {
+    chain
+        .blocks_and_receipts()
+        .map(|(block, receipts)| block_messages(block, receipts, removed))
+        .collect()
+}
+
+/// Returns the messages of a block and its logs.
+///
+/// Receipts that were pruned are left out.
+fn block_messages(
+    block: &SealedBlockWithSenders,
+    receipts: &[Option<Receipt>],
+    removed: bool,
+) -> (BlockMessage, Vec<LogMessage>) {
+    let mut block_message = BlockMessage {
+        number: block.number,
+        hash: block.hash(),
+        parent_hash: block.parent_hash,
+        timestamp: block.timestamp,
+        miner: block.beneficiary,
+        gas_used: block.gas_used,
+        gas_limit: block.gas_limit,
+        base_fee_per_gas: block.base_fee_per_gas,
+        removed,
+        receipts: Vec::new(),
+    };
+    let mut logs = Vec::new();
+
+    let mut cumulative_gas_used = 0;
+    let mut log_index = 0;
+    for (index, ((from, transaction), receipt)) in
+        block.transactions_with_sender().zip(receipts).enumerate()
+    {
+        let Some(receipt) = receipt else { continue };
+        let gas_used = receipt.cumulative_gas_used - cumulative_gas_used;
+        cumulative_gas_used = receipt.cumulative_gas_used;
+
+        block_message.receipts.push(ReceiptMessage {
+            transaction_hash: transaction.hash(),
+            transaction_index: index as u64,
+            from: *from,
+            to: transaction.to(),
+            success: receipt.success,
+            gas_used,
+            cumulative_gas_used: receipt.cumulative_gas_used,
+            log_count: receipt.logs.len() as u64,
+        });
+
+        for log in &receipt.logs {
+            logs.push(LogMessage {
+                block_number: block.number,
+                block_hash: block_message.hash,
+                transaction_hash: transaction.hash(),
+                transaction_index: index as u64,
+                log_index,
+                address: log.address,
+                topics: log.topics().to_vec(),
+                data: log.data.data.clone(),
+                removed,
+            });
+            log_index += 1;
+        }
+    }
+
+    (block_message, logs)
+}
+
+/// A canonical block, published to the `<topic>.blocks` topic.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockMessage {
+    /// The number of the block.
+    pub number: BlockNumber,
+    /// The hash of the block.
+    pub hash: B256,
+    /// The hash of the parent block.
+    pub parent_hash: B256,
+    /// The timestamp of the block.
+    pub timestamp: u64,
+    /// The beneficiary of the block.
+    pub miner: Address,
+    /// The gas used by the block.
+    pub gas_used: u64,
+    /// The gas limit of the block.
+    pub gas_limit: u64,
+    /// The base fee of the block, if any.
+    pub base_fee_per_gas: Option<u64>,
+    /// Whether the block was removed from the canonical chain by a reorg.
+    pub removed: bool,
+    /// The receipts of the transactions of the block.
+    pub receipts: Vec<ReceiptMessage>,
+}
+
+/// The receipt of a transaction, part of a [`BlockMessage`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReceiptMessage {
+    /// The hash of the transaction.
+    pub transaction_hash: B256,
+    /// The index of the transaction in the block.
+    pub transaction_index: u64,
+    /// The sender of the transaction.
+    pub from: Address,
+    /// The recipient of the transaction, `None` for contract creations.
+    pub to: Option<Address>,
+    /// Whether the transaction was executed successfully.
+    pub success: bool,
+    /// The gas used by the transaction.
+    pub gas_used: u64,
+    /// The gas used by the block up to and including the transaction.
+    pub cumulative_gas_used: u64,
+    /// The number of logs emitted by the transaction.
+    pub log_count: u64,
+}
+
+/// A log emitted by a transaction, published to the `<topic>.logs` topic.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogMessage {
+    /// The number of the block.
+    pub block_number: BlockNumber,
+    /// The hash of the block.
+    pub block_hash: B256,
+    /// The hash of the transaction.
+    pub transaction_hash: B256,
+    /// The index of the transaction in the block.
+    pub transaction_index: u64,
+    /// The index of the log in the block.
+    pub log_index: u64,
+    /// The address of the contract that emitted the log.
+    pub address: Address,
+    /// The topics of the log.
+    pub topics: Vec<B256>,
+    /// The data of the log.
+    pub data: Bytes,
+    /// Whether the block of the log was removed from the canonical chain by a reorg.
+    pub removed: bool,
+}
+
+/// Converts a message into its protobuf representation.
+trait ToProto {
+    type Proto: prost::Message;
+
+    fn to_proto(&self) -> Self::Proto;
+}
+
+impl ToProto for BlockMessage {
+    type Proto = proto::Block;
+
+    fn to_proto(&self) -> Self::Proto {
+        proto::Block {
+            number: self.number,
+            hash: self.hash.to_vec(),
+            parent_hash: self.parent_hash.to_vec(),
+            timestamp: self.timestamp,
+            miner: self.miner.to_vec(),
+            gas_used: self.gas_used,
+            gas_limit: self.gas_limit,
+            base_fee_per_gas: self.base_fee_per_gas,
+            removed: self.removed,
+            receipts: self.receipts.iter().map(ToProto::to_proto).collect(),
+        }
+    }
+}
+
+impl ToProto for ReceiptMessage {
+    type Proto = proto::Receipt;
+
+    fn to_proto(&self) -> Self::Proto {
+        proto::Receipt {
+            transaction_hash: self.transaction_hash.to_vec(),
+            transaction_index: self.transaction_index,
+            from: self.from.to_vec(),
+            to: self.to.map(|to| to.to_vec()),
+            success: self.success,
+            gas_used: self.gas_used,
+            cumulative_gas_used: self.cumulative_gas_used,
+            log_count: self.log_count,
+        }
+    }
+}
+
+impl ToProto for LogMessage {
+    type Proto = proto::Log;
+
+    fn to_proto(&self) -> Self::Proto {
+        proto::Log {
+            block_number: self.block_number,
+            block_hash: self.block_hash.to_vec(),
+            transaction_hash: self.transaction_hash.to_vec(),
+            transaction_index: self.transaction_index,
+            log_index: self.log_index,
+            address: self.address.to_vec(),
+            topics: self.topics.iter().map(|topic| topic.to_vec()).collect(),
+            data: self.data.to_vec(),
+            removed: self.removed,
+        }
+    }
+}
+
+/// The protobuf messages of the publisher, in the `reth.publisher.v1` package.
+///
+/// Hashes and addresses are encoded as raw big endian bytes.
+pub mod proto {
+    /// A canonical block.
+    #[derive(Clone, PartialEq, Eq, prost::Message)]
+    pub struct Block {
+        /// The number of the block.
+        #[prost(uint64, tag = "1")]
+        pub number: u64,
+        /// The hash of the block.
+        #[prost(bytes = "vec", tag = "2")]
+        pub hash: Vec<u8>,
+        /// The hash of the parent block.
+        #[prost(bytes = "vec", tag = "3")]
+        pub parent_hash: Vec<u8>,
+        /// The timestamp of the block.
+        #[prost(uint64, tag = "4")]
+        pub timestamp: u64,
+        /// The beneficiary of the block.
+        #[prost(bytes = "vec", tag = "5")]
+        pub miner: Vec<u8>,
+        /// The gas used by the block.
+        #[prost(uint64, tag = "6")]
+        pub gas_used: u64,
+        /// The gas limit of the block.
+        #[prost(uint64, tag = "7")]
+        pub gas_limit: u64,
+        /// The base fee of the block, if any.
+        #[prost(uint64, optional, tag = "8")]
+        pub base_fee_per_gas: Option<u64>,
+        /// Whether the block was removed from the canonical chain by a reorg.
+        #[prost(bool, tag = "9")]
+        pub removed: bool,
+        /// The receipts of the transactions of the block.
+        #[prost(message, repeated, tag = "10")]
+        pub receipts: Vec<Receipt>,
+    }
+
+    /// The receipt of a transaction.
+    #[derive(Clone, PartialEq, Eq, prost::Message)]
+    pub struct Receipt {
+        /// The hash of the transaction.
+        #[prost(bytes = "vec", tag = "1")]
+        pub transaction_hash: Vec<u8>,
+        /// The index of the transaction in the block.
+        #[prost(uint64, tag = "2")]
+        pub transaction_index: u64,
+        /// The sender of the transaction.
+        #[prost(bytes = "vec", tag = "3")]
+        pub from: Vec<u8>,
+        /// The recipient of the transaction, unset for contract creations.
+        #[prost(bytes = "vec", optional, tag = "4")]
+        pub to: Option<Vec<u8>>,
+        /// Whether the transaction was executed successfully.
+        #[prost(bool, tag = "5")]
+        pub success: bool,
+        /// The gas used by the transaction.
+        #[prost(uint64, tag = "6")]
+        pub gas_used: u64,
+        /// The gas used by the block up to and including the transaction.
+        #[prost(uint64, tag = "7")]
+        pub cumulative_gas_used: u64,
+        /// The number of logs emitted by the transaction.
+        #[prost(uint64, tag = "8")]
+        pub log_count: u64,
+    }
+
+    /// A log emitted by a transaction.
+    #[derive(Clone, PartialEq, Eq, prost::Message)]
+    pub struct Log {
+        /// The number of the block.
+        #[prost(uint64, tag = "1")]
+        pub block_number: u64,
+        /// The hash of the block.
+        #[prost(bytes = "vec", tag = "2")]
+        pub block_hash: Vec<u8>,
+        /// The hash of the transaction.
+        #[prost(bytes = "vec", tag = "3")]
+        pub transaction_hash: Vec<u8>,
+        /// The index of the transaction in the block.
+        #[prost(uint64, tag = "4")]
+        pub transaction_index: u64,
+        /// The index of the log in the block.
+        #[prost(uint64, tag = "5")]
+        pub log_index: u64,
+        /// The address of the contract that emitted the log.
+        #[prost(bytes = "vec", tag = "6")]
+        pub address: Vec<u8>,
+        /// The topics of the log.
+        #[prost(bytes = "vec", repeated, tag = "7")]
+        pub topics: Vec<Vec<u8>>,
+        /// The data of the log.
+        #[prost(bytes = "vec", tag = "8")]
+        pub data: Vec<u8>,
+        /// Whether the block of the log was removed from the canonical chain by a reorg.
+        #[prost(bool, tag = "9")]
+        pub removed: bool,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Log, TransactionSigned, TxType};
+
+    #[test]
+    fn block_messages_split_logs() {
+        let mut block = SealedBlockWithSenders::default();
+        block.block.body.push(TransactionSigned::default());
+        block.senders.push(Address::with_last_byte(1));
+        let receipts = vec![Some(Receipt {
+            tx_type: TxType::Legacy,
+            success: true,
+            cumulative_gas_used: 21_000,
+            logs: vec![
+                Log::new_unchecked(Address::with_last_byte(2), vec![B256::ZERO], Bytes::new()),
+                Log::new_unchecked(Address::with_last_byte(3), vec![], Bytes::new()),
+            ],
+            ..Default::default()
+        })];
+
+        let (block_message, logs) = block_messages(&block, &receipts, true);
+        assert_eq!(block_message.receipts.len(), 1);
+        assert_eq!(block_message.receipts[0].gas_used, 21_000);
+        assert_eq!(block_message.receipts[0].log_count, 2);
+        assert_eq!(logs.len(), 2);
+        assert_eq!(logs[1].log_index, 1);
+        assert_eq!(logs[1].address, Address::with_last_byte(3));
+        assert!(logs.iter().all(|log| log.removed));
+
+        let json: serde_json::Value =
+            serde_json::from_slice(&PublishFormat::Json.encode(&block_message)).unwrap();
+        assert_eq!(json["removed"], true);
+        assert_eq!(json["receipts"][0]["from"], Address::with_last_byte(1).to_string());
+
+        let encoded = PublishFormat::Protobuf.encode(&block_message);
+        let decoded: proto::Block = prost::Message::decode(encoded.as_slice()).unwrap();
+        assert_eq!(decoded, block_message.to_proto());
+    }
+}
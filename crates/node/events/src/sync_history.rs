@@ -0,0 +1,150 @@
+//! Periodic snapshots of the sync progress, recorded in the database.
+
+use crate::node::NodeEvent;
+use futures::{Stream, StreamExt};
+use reth_db_api::{database::Database, models::SyncSnapshot};
+use reth_primitives::BlockNumber;
+use reth_provider::{ProviderFactory, ProviderResult, StageCheckpointReader, SyncHistoryWriter};
+use reth_stages::{ExecOutput, PipelineEvent, StageId};
+use std::{
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::time::MissedTickBehavior;
+use tracing::{debug, warn};
+
+/// Interval of recording a snapshot of the sync progress.
+pub const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(5 * 60);
+/// The maximum number of snapshots kept in the sync history, 30 days at [`SNAPSHOT_INTERVAL`].
+pub const MAX_SNAPSHOTS: usize = 30 * 24 * 12;
+
+/// Records a snapshot of the sync progress in the sync history every [`SNAPSHOT_INTERVAL`], until
+/// the event stream ends.
+///
+/// The running pipeline stage is tracked with the pipeline events of the stream, the disk usage
+/// is the total size of the given directories.
+pub async fn record_sync_history<DB, E>(
+    provider_factory: ProviderFactory<DB>,
+    dirs: Vec<PathBuf>,
+    mut events: E,
+) where
+    DB: Database + 'static,
+    E: Stream<Item = NodeEvent> + Unpin,
+{
+    let mut running = None;
+    let mut interval = tokio::time::interval(SNAPSHOT_INTERVAL);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                let (provider_factory, dirs) = (provider_factory.clone(), dirs.clone());
+                // the write waits for the write transactions of the pipeline
+                let recorded = tokio::task::spawn_blocking(move || {
+                    record_snapshot(&provider_factory, &dirs, running)
+                })
+                .await;
+                match recorded {
+                    Ok(Ok(snapshot)) => {
+                        debug!(target: "reth::cli", ?snapshot, "Recorded sync progress snapshot");
+                    }
+                    Ok(Err(err)) => {
+                        warn!(target: "reth::cli", %err, "Failed to record sync progress snapshot");
+                    }
+                    Err(err) => {
+                        warn!(target: "reth::cli", %err, "Failed to record sync progress snapshot");
+                    }
+                }
+            }
+            event = events.next() => match event {
+                Some(NodeEvent::Pipeline(event)) => update_running_stage(&mut running, event),
+                Some(_) => {}
+                None => return,
+            },
+        }
+    }
+}
+
+/// Records a snapshot of the current sync progress, with the running stage and its checkpoint.
+fn record_snapshot<DB: Database>(
+    provider_factory: &ProviderFactory<DB>,
+    dirs: &[PathBuf],
+    running: Option<(StageId, BlockNumber)>,
+) -> ProviderResult<SyncSnapshot> {
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    let disk_usage = dirs.iter().map(|dir| reth_fs_util::dir_size(dir).unwrap_or_default()).sum();
+
+    let provider = provider_factory.provider_rw()?;
+    let block_number =
+        provider.get_stage_checkpoint(StageId::Finish)?.unwrap_or_default().block_number;
+    let snapshot = SyncSnapshot {
+        block_number,
+        stage: running.map(|(stage_id, _)| stage_id.to_string()).unwrap_or_default(),
+        stage_checkpoint: running.map_or(block_number, |(_, checkpoint)| checkpoint),
+        disk_usage,
+    };
+    provider.record_sync_snapshot(timestamp, snapshot.clone(), MAX_SNAPSHOTS)?;
+    provider.commit()?;
+
+    Ok(snapshot)
+}
+
+/// Updates the running stage and its checkpoint with the pipeline event.
+fn update_running_stage(running: &mut Option<(StageId, BlockNumber)>, event: PipelineEvent) {
+    match event {
+        PipelineEvent::Run { stage_id, checkpoint, .. } => {
+            *running = Some((stage_id, checkpoint.unwrap_or_default().block_number));
+        }
+        PipelineEvent::Ran { stage_id, result: ExecOutput { checkpoint, done }, .. } => {
+            *running = (!done).then_some((stage_id, checkpoint.block_number));
+        }
+        PipelineEvent::Unwind { stage_id, input } => {
+            *running = Some((stage_id, input.checkpoint.block_number));
+        }
+        PipelineEvent::Unwound { .. } | PipelineEvent::Error { .. } => *running = None,
+        PipelineEvent::Prepare { .. } | PipelineEvent::Skipped { .. } => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_stages::{PipelineStagesProgress, StageCheckpoint};
+
+    #[test]
+    fn tracks_running_stage() {
+        let progress = PipelineStagesProgress { current: 4, total: 12 };
+        let mut running = None;
+
+        update_running_stage(
+            &mut running,
+            PipelineEvent::Run {
+                pipeline_stages_progress: progress.clone(),
+                stage_id: StageId::Execution,
+                checkpoint: Some(StageCheckpoint::new(100)),
+                target: Some(1000),
+            },
+        );
+        assert_eq!(running, Some((StageId::Execution, 100)));
+
+        update_running_stage(
+            &mut running,
+            PipelineEvent::Ran {
+                pipeline_stages_progress: progress.clone(),
+                stage_id: StageId::Execution,
+                result: ExecOutput { checkpoint: StageCheckpoint::new(500), done: false },
+            },
+        );
+        assert_eq!(running, Some((StageId::Execution, 500)));
+
+        update_running_stage(
+            &mut running,
+            PipelineEvent::Ran {
+                pipeline_stages_progress: progress,
+                stage_id: StageId::Execution,
+                result: ExecOutput { checkpoint: StageCheckpoint::new(1000), done: true },
+            },
+        );
+        assert_eq!(running, None);
+    }
+}
@@ -0,0 +1,183 @@
+//! Consensus rules for the legacy OVM chain segment below Bedrock.
+
+use crate::OptimismBeaconConsensus;
+use reth_chainspec::ChainSpec;
+use reth_consensus::{Consensus, ConsensusError, PostExecutionInput};
+use reth_consensus_common::validation::{
+    validate_against_parent_hash_number, validate_block_pre_execution, validate_header_gas,
+};
+use reth_primitives::{
+    gas_spent_by_transactions, BlockWithSenders, GotExpected, Header, SealedBlock, SealedHeader,
+    U256,
+};
+use std::sync::Arc;
+
+/// Consensus for OP chains that include the legacy (pre-Bedrock) OVM history.
+///
+/// Blocks below Bedrock were produced by a single sequencer running l2geth. They carry neither a
+/// valid proof-of-work seal nor clique signatures, can share their parent's timestamp and do not
+/// follow the EIP-1559 base fee rules. For those blocks only the structural checks that hold for
+/// OVM headers are applied:
+///
+/// - gas used does not exceed the gas limit
+/// - parent hash and number link up
+/// - timestamps are non-decreasing
+/// - ommers hash and transactions root match the block body
+///
+/// From the Bedrock activation block onwards, validation is delegated to
+/// [`OptimismBeaconConsensus`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpLegacyConsensus {
+    /// Consensus used for blocks at and above Bedrock.
+    inner: OptimismBeaconConsensus,
+    /// Configuration
+    chain_spec: Arc<ChainSpec>,
+}
+
+impl OpLegacyConsensus {
+    /// Create a new instance of [`OpLegacyConsensus`]
+    ///
+    /// # Panics
+    ///
+    /// If given chain spec is not optimism [`ChainSpec::is_optimism`]
+    pub fn new(chain_spec: Arc<ChainSpec>) -> Self {
+        Self { inner: OptimismBeaconConsensus::new(chain_spec.clone()), chain_spec }
+    }
+
+    /// Returns `true` if the block is part of the legacy OVM chain segment.
+    #[inline]
+    pub fn is_legacy_block(&self, block_number: u64) -> bool {
+        !self.chain_spec.is_bedrock_active_at_block(block_number)
+    }
+}
+
+impl Consensus for OpLegacyConsensus {
+    fn validate_header(&self, header: &SealedHeader) -> Result<(), ConsensusError> {
+        if !self.is_legacy_block(header.number) {
+            return self.inner.validate_header(header)
+        }
+
+        validate_header_gas(header)
+    }
+
+    fn validate_header_against_parent(
+        &self,
+        header: &SealedHeader,
+        parent: &SealedHeader,
+    ) -> Result<(), ConsensusError> {
+        if !self.is_legacy_block(header.number) {
+            return self.inner.validate_header_against_parent(header, parent)
+        }
+
+        validate_against_parent_hash_number(header, parent)?;
+
+        // l2geth derived block timestamps from L1, so consecutive blocks may share a timestamp
+        if header.timestamp < parent.timestamp {
+            return Err(ConsensusError::TimestampIsInPast {
+                parent_timestamp: parent.timestamp,
+                timestamp: header.timestamp,
+            })
+        }
+
+        Ok(())
+    }
+
+    fn validate_header_with_total_difficulty(
+        &self,
+        header: &Header,
+        total_difficulty: U256,
+    ) -> Result<(), ConsensusError> {
+        if !self.is_legacy_block(header.number) {
+            return self.inner.validate_header_with_total_difficulty(header, total_difficulty)
+        }
+
+        // legacy blocks have no seal to verify, and are historical so there is no point in
+        // checking the timestamp against the system clock
+        Ok(())
+    }
+
+    fn validate_block_pre_execution(&self, block: &SealedBlock) -> Result<(), ConsensusError> {
+        if !self.is_legacy_block(block.number) {
+            return self.inner.validate_block_pre_execution(block)
+        }
+
+        validate_block_pre_execution(block, &self.chain_spec)
+    }
+
+    fn validate_block_post_execution(
+        &self,
+        block: &BlockWithSenders,
+        input: PostExecutionInput<'_>,
+    ) -> Result<(), ConsensusError> {
+        if !self.is_legacy_block(block.number) {
+            return self.inner.validate_block_post_execution(block, input)
+        }
+
+        // OVM receipts roots are not reproducible with the post-Bedrock receipt encoding, so only
+        // the gas used is checked
+        let cumulative_gas_used =
+            input.receipts.last().map(|receipt| receipt.cumulative_gas_used).unwrap_or(0);
+        if block.gas_used != cumulative_gas_used {
+            return Err(ConsensusError::BlockGasUsed {
+                gas: GotExpected { got: cumulative_gas_used, expected: block.gas_used },
+                gas_spent_by_tx: gas_spent_by_transactions(input.receipts),
+            })
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chainspec::OP_MAINNET;
+
+    fn sealed(header: Header) -> SealedHeader {
+        header.seal_slow()
+    }
+
+    #[test]
+    fn legacy_headers_may_share_timestamp() {
+        let consensus = OpLegacyConsensus::new(OP_MAINNET.clone());
+
+        let parent = sealed(Header { number: 1, timestamp: 100, ..Default::default() });
+        let child = sealed(Header {
+            number: 2,
+            timestamp: 100,
+            parent_hash: parent.hash(),
+            difficulty: U256::from(2),
+            ..Default::default()
+        });
+
+        assert!(consensus.is_legacy_block(child.number));
+        assert_eq!(consensus.validate_header(&child), Ok(()));
+        assert_eq!(consensus.validate_header_against_parent(&child, &parent), Ok(()));
+        assert_eq!(
+            consensus.validate_header_with_total_difficulty(&child, U256::from(4)),
+            Ok(())
+        );
+
+        let stale = sealed(Header {
+            number: 2,
+            timestamp: 99,
+            parent_hash: parent.hash(),
+            ..Default::default()
+        });
+        assert_eq!(
+            consensus.validate_header_against_parent(&stale, &parent),
+            Err(ConsensusError::TimestampIsInPast { parent_timestamp: 100, timestamp: 99 })
+        );
+    }
+
+    #[test]
+    fn legacy_header_gas_limit() {
+        let consensus = OpLegacyConsensus::new(OP_MAINNET.clone());
+        let header =
+            sealed(Header { number: 1, gas_limit: 10, gas_used: 11, ..Default::default() });
+        assert_eq!(
+            consensus.validate_header(&header),
+            Err(ConsensusError::HeaderGasUsedExceedsGasLimit { gas_used: 11, gas_limit: 10 })
+        );
+    }
+}
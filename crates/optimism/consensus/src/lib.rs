@@ -22,6 +22,9 @@ use reth_primitives::{
 };
 use std::{sync::Arc, time::SystemTime};
 
+mod legacy;
+pub use legacy::OpLegacyConsensus;
+
 mod validation;
 pub use validation::validate_block_post_execution;
 
@@ -3,10 +3,13 @@
 use crate::OptimismBlockExecutionError;
 use reth_chainspec::{ChainSpec, Hardfork};
 use reth_execution_errors::BlockExecutionError;
-use reth_primitives::{address, b256, hex, Address, Block, Bytes, B256, U256};
+use reth_primitives::{
+    address, b256, hex, AccessList, AccessListItem, Address, Block, Bytes, Signature, Transaction,
+    TransactionSigned, TxEip1559, B256, U256,
+};
 use revm::{
-    primitives::{Bytecode, HashMap, SpecId},
-    DatabaseCommit, L1BlockInfo,
+    primitives::{Bytecode, HashMap, SpecId, TxEnv},
+    Database, DatabaseCommit, L1BlockInfo,
 };
 use std::sync::Arc;
 use tracing::trace;
@@ -29,6 +32,21 @@ const L1_BLOCK_ECOTONE_SELECTOR: [u8; 4] = hex!("440a5e20");
 ///
 /// Returns an error if the L1 info transaction is not found, if the block is empty.
 pub fn extract_l1_info(block: &Block) -> Result<L1BlockInfo, OptimismBlockExecutionError> {
+    let l1_info_tx_data = l1_info_tx_data(block)?;
+
+    // If the first 4 bytes of the calldata are the L1BlockInfoEcotone selector, then we parse the
+    // calldata as an Ecotone hardfork L1BlockInfo transaction. Otherwise, we parse it as a
+    // Bedrock hardfork L1BlockInfo transaction.
+    if l1_info_tx_data[0..4] == L1_BLOCK_ECOTONE_SELECTOR {
+        parse_l1_info_tx_ecotone(l1_info_tx_data[4..].as_ref())
+    } else {
+        parse_l1_info_tx_bedrock(l1_info_tx_data[4..].as_ref())
+    }
+}
+
+/// Returns the calldata of the L1 info transaction of the L2 block, which is at least 4 bytes
+/// long.
+fn l1_info_tx_data(block: &Block) -> Result<&Bytes, OptimismBlockExecutionError> {
     let l1_info_tx_data = block
         .body
         .first()
@@ -43,16 +61,77 @@ pub fn extract_l1_info(block: &Block) -> Result<L1BlockInfo, OptimismBlockExecut
         })
     }
 
-    // If the first 4 bytes of the calldata are the L1BlockInfoEcotone selector, then we parse the
-    // calldata as an Ecotone hardfork L1BlockInfo transaction. Otherwise, we parse it as a
-    // Bedrock hardfork L1BlockInfo transaction.
+    Ok(l1_info_tx_data)
+}
+
+/// The L1 block an L2 block is derived from, as set by the L1 info transaction of the L2 block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct L1Origin {
+    /// The number of the L1 block.
+    pub number: u64,
+    /// The timestamp of the L1 block.
+    pub timestamp: u64,
+    /// The hash of the L1 block.
+    pub hash: B256,
+    /// The position of the L2 block in the sequencing epoch of the L1 block, starting at 0.
+    pub sequence_number: u64,
+    /// The versioned hash of the batcher address the batches are posted by.
+    pub batcher_hash: B256,
+}
+
+impl L1Origin {
+    /// Returns the batcher address the batches are posted by.
+    pub fn batcher(&self) -> Address {
+        Address::from_word(self.batcher_hash)
+    }
+}
+
+/// Extracts the [`L1Origin`] from the L2 block. The L1 info transaction is always the first
+/// transaction in the L2 block.
+///
+/// Returns an error if the L1 info transaction is not found, if the block is empty.
+pub fn extract_l1_origin(block: &Block) -> Result<L1Origin, OptimismBlockExecutionError> {
+    let l1_info_tx_data = l1_info_tx_data(block)?;
+    let data = &l1_info_tx_data[4..];
+    let invalid_length = || OptimismBlockExecutionError::L1BlockInfoError {
+        message: "unexpected l1 block info tx calldata length found".to_string(),
+    };
+
+    // See `parse_l1_info_tx_bedrock` and `parse_l1_info_tx_ecotone` for the layouts
     if l1_info_tx_data[0..4] == L1_BLOCK_ECOTONE_SELECTOR {
-        parse_l1_info_tx_ecotone(l1_info_tx_data[4..].as_ref())
+        if data.len() != 160 {
+            return Err(invalid_length())
+        }
+        Ok(L1Origin {
+            number: be_u64(&data[16..24])?,
+            timestamp: be_u64(&data[24..32])?,
+            hash: B256::from_slice(&data[96..128]),
+            sequence_number: be_u64(&data[0..8])?,
+            batcher_hash: B256::from_slice(&data[128..160]),
+        })
     } else {
-        parse_l1_info_tx_bedrock(l1_info_tx_data[4..].as_ref())
+        if data.len() != 256 {
+            return Err(invalid_length())
+        }
+        Ok(L1Origin {
+            number: be_u64(&data[0..32])?,
+            timestamp: be_u64(&data[32..64])?,
+            hash: B256::from_slice(&data[96..128]),
+            sequence_number: be_u64(&data[128..160])?,
+            batcher_hash: B256::from_slice(&data[160..192]),
+        })
     }
 }
 
+/// Converts the big-endian integer to a `u64`.
+fn be_u64(data: &[u8]) -> Result<u64, OptimismBlockExecutionError> {
+    U256::from_be_slice(data).try_into().map_err(|_| {
+        OptimismBlockExecutionError::L1BlockInfoError {
+            message: "l1 block info value does not fit into u64".to_string(),
+        }
+    })
+}
+
 /// Parses the calldata of the [`L1BlockInfo`] transaction pre-Ecotone hardfork.
 pub fn parse_l1_info_tx_bedrock(data: &[u8]) -> Result<L1BlockInfo, OptimismBlockExecutionError> {
     // The setL1BlockValues tx calldata must be exactly 260 bytes long, considering that
@@ -230,6 +309,47 @@ impl RethL1BlockInfo for L1BlockInfo {
     }
 }
 
+/// Returns the L1 data fee the block executor charges for the transaction of the given
+/// [`TxEnv`], reading the L1 fee parameters from the state of the `L1Block` contract.
+///
+/// The transaction is not signed, so it's encoded as an EIP-1559 transaction with a signature of
+/// non-zero bytes, which makes the fee an upper bound of the fee of the signed transaction. Deposit
+/// transactions are not charged an L1 data fee.
+pub fn estimate_tx_l1_fee<DB: Database>(
+    db: &mut DB,
+    spec_id: SpecId,
+    tx: &TxEnv,
+) -> Result<U256, DB::Error> {
+    if tx.optimism.source_hash.is_some() {
+        return Ok(U256::ZERO)
+    }
+
+    let transaction = Transaction::Eip1559(TxEip1559 {
+        chain_id: tx.chain_id.unwrap_or_default(),
+        nonce: tx.nonce.unwrap_or_default(),
+        gas_limit: tx.gas_limit,
+        max_fee_per_gas: tx.gas_price.saturating_to(),
+        max_priority_fee_per_gas: tx.gas_priority_fee.unwrap_or_default().saturating_to(),
+        to: tx.transact_to,
+        value: tx.value,
+        access_list: AccessList(
+            tx.access_list
+                .iter()
+                .map(|(address, keys)| AccessListItem {
+                    address: *address,
+                    storage_keys: keys.iter().map(|key| B256::from(*key)).collect(),
+                })
+                .collect(),
+        ),
+        input: tx.data.clone(),
+    });
+    let signature = Signature { r: U256::MAX, s: U256::MAX, odd_y_parity: true };
+    let envelope = TransactionSigned::from_transaction_and_signature(transaction, signature)
+        .envelope_encoded();
+
+    Ok(L1BlockInfo::try_fetch(db, spec_id)?.calculate_tx_l1_cost(&envelope, spec_id))
+}
+
 /// The Canyon hardfork issues an irregular state transition that force-deploys the create2
 /// deployer contract. This is done by directly setting the code of the create2 deployer account
 /// prior to executing any transactions on the timestamp activation of the fork.
@@ -294,6 +414,16 @@ mod tests {
         assert_eq!(l1_info.l1_base_fee_scalar, U256::from(1_000_000));
         assert_eq!(l1_info.l1_blob_base_fee, None);
         assert_eq!(l1_info.l1_blob_base_fee_scalar, None);
+
+        let l1_origin = extract_l1_origin(&mock_block).unwrap();
+        assert_eq!(l1_origin.number, 8_410_981);
+        assert_eq!(l1_origin.timestamp, 1_675_193_616);
+        assert_eq!(
+            l1_origin.hash,
+            b256!("73d89754a1e0387b89520d989d3be9c37c1f32495a88faf1ea05c61121ab0d19")
+        );
+        assert_eq!(l1_origin.sequence_number, 1);
+        assert_eq!(l1_origin.batcher(), address!("2d679b567db6187c0c8323fa982cfb88b74dbcc7"));
     }
 
     #[test]
@@ -316,5 +446,80 @@ mod tests {
         assert_eq!(l1_info.l1_blob_base_fee, Some(U256::from(22_380_075_395u64)));
         assert_eq!(l1_info.l1_blob_base_fee_scalar, Some(U256::from(0)));
         assert_eq!(l1_info.l1_fee_overhead, None);
+
+        let l1_origin = extract_l1_origin(&mock_block).unwrap();
+        assert_eq!(l1_origin.number, 10_501_995);
+        assert_eq!(l1_origin.timestamp, 1_707_351_912);
+        assert_eq!(
+            l1_origin.hash,
+            b256!("dea59eac60478a64ecfdcde8571e611404295350de7ed4ccb404296c1a84ab7a")
+        );
+        assert_eq!(l1_origin.batcher(), address!("73b4168cc87f35cc239200a20eb841cded23493b"));
+    }
+
+    #[test]
+    fn l1_fee_from_l1_block_state() {
+        use revm::{
+            db::{CacheDB, EmptyDB},
+            primitives::{AccountInfo, TxKind},
+            L1_BLOCK_CONTRACT,
+        };
+
+        let tx = TxEnv {
+            chain_id: Some(10),
+            gas_limit: 21_000,
+            gas_price: U256::from(1_000_000_000),
+            transact_to: TxKind::Call(address!("4200000000000000000000000000000000000006")),
+            // deposit()
+            data: Bytes::from_static(&hex!("d0e30db0")),
+            ..Default::default()
+        };
+        let l1_block = |slots: &[(u64, U256)]| {
+            let mut db = CacheDB::new(EmptyDB::default());
+            db.insert_account_info(L1_BLOCK_CONTRACT, AccountInfo::default());
+            for (slot, value) in slots {
+                db.insert_account_storage(L1_BLOCK_CONTRACT, U256::from(*slot), *value).unwrap();
+            }
+            db
+        };
+
+        // The expected fees follow the L1 cost functions of op-geth. The 109 byte envelope has 19
+        // zero bytes, so its calldata gas is 19 * 4 + 90 * 16 = 1516.
+        //
+        // Regolith: (1516 + overhead) * l1BaseFee * scalar / 1e6
+        let mut db = l1_block(&[
+            (1, U256::from(652_114)),
+            (5, U256::from(2100)),
+            (6, U256::from(1_000_000)),
+        ]);
+        assert_eq!(
+            estimate_tx_l1_fee(&mut db, SpecId::REGOLITH, &tx).unwrap(),
+            U256::from(2_358_044_224u64)
+        );
+
+        // Ecotone: 1516 * (l1BaseFee * 16 * baseFeeScalar + blobBaseFee * blobBaseFeeScalar) /
+        // 16e6, with the scalars packed into one slot
+        let mut scalars = [0u8; 32];
+        scalars[16..20].copy_from_slice(&1368u32.to_be_bytes());
+        scalars[20..24].copy_from_slice(&810_949u32.to_be_bytes());
+        let mut db = l1_block(&[
+            (1, U256::from(10_000_000_000u64)),
+            (3, U256::from_be_bytes(scalars)),
+            (7, U256::from(1)),
+        ]);
+        assert_eq!(
+            estimate_tx_l1_fee(&mut db, SpecId::ECOTONE, &tx).unwrap(),
+            U256::from(20_738_880_076u64)
+        );
+
+        // deposits don't pay an L1 fee
+        let deposit = TxEnv {
+            optimism: revm::primitives::OptimismFields {
+                source_hash: Some(B256::ZERO),
+                ..Default::default()
+            },
+            ..tx
+        };
+        assert_eq!(estimate_tx_l1_fee(&mut db, SpecId::ECOTONE, &deposit).unwrap(), U256::ZERO);
     }
 }
@@ -1,7 +1,8 @@
 //! Helpers for optimism specific RPC implementations.
 
 use jsonrpsee::types::ErrorObject;
-use reqwest::Client;
+use reqwest::{Client, Proxy, Url};
+use reth_network::Socks5Proxy;
 use reth_rpc::eth::{
     error::{EthApiError, EthResult},
     traits::RawTransactionForwarder,
@@ -18,6 +19,9 @@ pub enum SequencerRpcError {
     /// Thrown when serializing transaction to forward to sequencer
     #[error("invalid sequencer transaction")]
     InvalidSequencerTransaction,
+    /// Thrown when the proxy to connect to the sequencer through is not a valid URL
+    #[error("invalid sequencer proxy {0}")]
+    InvalidProxy(String),
 }
 
 impl ToRpcError for SequencerRpcError {
@@ -49,6 +53,25 @@ impl SequencerClient {
         Self::with_client(sequencer_endpoint, client)
     }
 
+    /// Creates a new [`SequencerClient`] that connects to the sequencer through the given SOCKS5
+    /// proxy.
+    pub fn with_proxy(
+        sequencer_endpoint: impl Into<String>,
+        proxy: &Socks5Proxy,
+    ) -> Result<Self, SequencerRpcError> {
+        let scheme = if proxy.remote_dns { "socks5h" } else { "socks5" };
+        let mut url = Url::parse(&format!("{scheme}://{}", proxy.addr))
+            .map_err(|_| SequencerRpcError::InvalidProxy(proxy.to_string()))?;
+        if let Some(credentials) = &proxy.credentials {
+            url.set_username(&credentials.username)
+                .and_then(|_| url.set_password(Some(&credentials.password)))
+                .map_err(|_| SequencerRpcError::InvalidProxy(proxy.to_string()))?;
+        }
+
+        let client = Client::builder().use_rustls_tls().proxy(Proxy::all(url)?).build()?;
+        Ok(Self::with_client(sequencer_endpoint, client))
+    }
+
     /// Creates a new [`SequencerClient`].
     pub fn with_client(sequencer_endpoint: impl Into<String>, http_client: Client) -> Self {
         let inner = SequencerClientInner {
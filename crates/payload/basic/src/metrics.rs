@@ -1,6 +1,10 @@
 //! Metrics for the payload builder impl
 
-use reth_metrics::{metrics::Counter, Metrics};
+use reth_metrics::{
+    metrics::{Counter, Gauge, Histogram},
+    Metrics,
+};
+use reth_primitives::U256;
 
 /// Transaction pool metrics
 #[derive(Metrics)]
@@ -12,6 +16,12 @@ pub(crate) struct PayloadBuilderMetrics {
     pub(crate) initiated_payload_builds: Counter,
     /// Total number of failed payload build attempts
     pub(crate) failed_payload_builds: Counter,
+    /// Total number of payload build attempts that yielded a better payload
+    pub(crate) better_payload_builds: Counter,
+    /// The value of built payloads that were better than the previous best payload, in wei
+    pub(crate) built_payload_value: Histogram,
+    /// The value of the latest best payload, in wei
+    pub(crate) best_payload_value: Gauge,
 }
 
 impl PayloadBuilderMetrics {
@@ -26,4 +36,11 @@ impl PayloadBuilderMetrics {
     pub(crate) fn inc_failed_payload_builds(&self) {
         self.failed_payload_builds.increment(1);
     }
+
+    pub(crate) fn record_better_payload(&self, value: U256) {
+        let value = u128::try_from(value).unwrap_or(u128::MAX) as f64;
+        self.better_payload_builds.increment(1);
+        self.built_payload_value.record(value);
+        self.best_payload_value.set(value);
+    }
 }
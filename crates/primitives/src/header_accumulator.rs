@@ -0,0 +1,121 @@
+//! The historical header accumulator of the pre-merge chain.
+//!
+//! Pre-merge headers are grouped into epochs of [`EPOCH_SIZE`] blocks. The accumulator of an epoch
+//! is the SSZ `List[HeaderRecord, EPOCH_SIZE]` of the hash and total difficulty of each header,
+//! and the historical accumulator is the SSZ `List[Bytes32, MAX_HISTORICAL_EPOCHS]` of the
+//! `hash_tree_root`s of the epoch accumulators. This is the format used by the Portal network, so
+//! a header can be verified against the root of the historical accumulator with an SSZ proof.
+
+use crate::{B256, U256};
+use sha2::{Digest, Sha256};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Number of headers in an epoch of the accumulator.
+pub const EPOCH_SIZE: u64 = 8192;
+
+/// Maximum number of epochs of the historical accumulator.
+pub const MAX_HISTORICAL_EPOCHS: u64 = 131072;
+
+/// Hash and total difficulty of a header, the leaves of an epoch accumulator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeaderRecord {
+    /// Hash of the header.
+    pub block_hash: B256,
+    /// Total difficulty of the chain up to and including the header.
+    pub total_difficulty: U256,
+}
+
+impl HeaderRecord {
+    /// Returns the SSZ `hash_tree_root` of the record.
+    pub fn tree_hash_root(&self) -> B256 {
+        hash_pair(self.block_hash, B256::from(self.total_difficulty.to_le_bytes::<32>()))
+    }
+}
+
+/// Returns the epoch of the accumulator the block is part of.
+pub const fn epoch_of(block: u64) -> u64 {
+    block / EPOCH_SIZE
+}
+
+/// Returns the SSZ `hash_tree_root` of an epoch accumulator with the given records.
+///
+/// The records of the last pre-merge epoch may be fewer than [`EPOCH_SIZE`].
+pub fn epoch_accumulator_root(records: &[HeaderRecord]) -> B256 {
+    debug_assert!(records.len() as u64 <= EPOCH_SIZE);
+    let leaves = records.iter().map(HeaderRecord::tree_hash_root).collect();
+    mix_in_length(merkleize(leaves, EPOCH_SIZE.trailing_zeros()), records.len())
+}
+
+/// Returns the SSZ `hash_tree_root` of the historical accumulator with the given epoch roots.
+pub fn historical_accumulator_root(epoch_roots: &[B256]) -> B256 {
+    debug_assert!(epoch_roots.len() as u64 <= MAX_HISTORICAL_EPOCHS);
+    // the root of a container with a single field is the root of the field
+    mix_in_length(
+        merkleize(epoch_roots.to_vec(), MAX_HISTORICAL_EPOCHS.trailing_zeros()),
+        epoch_roots.len(),
+    )
+}
+
+/// Merkleizes the leaves into a tree of the given depth, padding it with zero leaves.
+fn merkleize(mut layer: Vec<B256>, depth: u32) -> B256 {
+    let mut zero = B256::ZERO;
+    for _ in 0..depth {
+        if layer.len() % 2 == 1 {
+            layer.push(zero);
+        }
+        layer = layer.chunks_exact(2).map(|pair| hash_pair(pair[0], pair[1])).collect();
+        zero = hash_pair(zero, zero);
+    }
+    layer.first().copied().unwrap_or(zero)
+}
+
+fn mix_in_length(root: B256, len: usize) -> B256 {
+    hash_pair(root, B256::from(U256::from(len).to_le_bytes::<32>()))
+}
+
+fn hash_pair(left: B256, right: B256) -> B256 {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    B256::from_slice(&hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Merkleizes the full tree, without skipping the zero subtrees.
+    fn naive_root(leaves: &[B256], limit: u64) -> B256 {
+        let mut layer = leaves.to_vec();
+        layer.resize(limit as usize, B256::ZERO);
+        while layer.len() > 1 {
+            layer = layer.chunks_exact(2).map(|pair| hash_pair(pair[0], pair[1])).collect();
+        }
+        mix_in_length(layer[0], leaves.len())
+    }
+
+    #[test]
+    fn epoch_accumulator_matches_full_tree() {
+        let records = (0..3u64)
+            .map(|number| HeaderRecord {
+                block_hash: B256::with_last_byte(number as u8 + 1),
+                total_difficulty: U256::from(number * 17_179_869_184),
+            })
+            .collect::<Vec<_>>();
+        let leaves = records.iter().map(HeaderRecord::tree_hash_root).collect::<Vec<_>>();
+
+        assert_eq!(epoch_accumulator_root(&records), naive_root(&leaves, EPOCH_SIZE));
+        assert_eq!(epoch_accumulator_root(&[]), naive_root(&[], EPOCH_SIZE));
+    }
+
+    #[test]
+    fn historical_accumulator_matches_full_tree() {
+        let epoch_roots = [B256::with_last_byte(1), B256::with_last_byte(2)];
+        assert_eq!(
+            historical_accumulator_root(&epoch_roots),
+            naive_root(&epoch_roots, MAX_HISTORICAL_EPOCHS)
+        );
+    }
+}
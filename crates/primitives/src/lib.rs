@@ -30,10 +30,13 @@ pub mod constants;
 pub mod eip4844;
 pub mod genesis;
 pub mod header;
+pub mod header_accumulator;
 pub mod proofs;
 mod receipt;
+pub mod receipt_compaction;
 /// Helpers for working with revm
 pub mod revm;
+pub mod storage_layout;
 pub use reth_static_file_types as static_file;
 pub mod transaction;
 #[cfg(any(test, feature = "arbitrary"))]
@@ -0,0 +1,305 @@
+//! Space efficient encoding of [`Receipt`]s for finalized static file segments.
+//!
+//! Receipts of a segment are encoded row by row so they can still be accessed randomly by
+//! transaction number:
+//!
+//! * the transaction type and status are packed into a single byte,
+//! * the cumulative gas used and every length are written as LEB128 varints,
+//! * log topics are replaced with their index in a [`TopicDictionary`] built from the most frequent
+//!   topics of the segment (e.g. the `Transfer` event signature), falling back to the inlined 32
+//!   bytes for topics that are not part of the dictionary.
+
+use crate::{Address, Bytes, Log, LogData, Receipt, TxType, B256};
+use alloy_rlp::Error;
+use bytes::Buf;
+
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+/// Default maximum number of topics kept in a [`TopicDictionary`].
+pub const DEFAULT_TOPIC_DICTIONARY_SIZE: usize = 1 << 16;
+
+/// Flag set on the first byte of an encoded receipt if the transaction was successful.
+const SUCCESS_FLAG: u8 = 0x80;
+
+/// Dictionary of log topics shared by all receipts of a static file segment.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TopicDictionary {
+    /// Topics ordered by their index.
+    topics: Vec<B256>,
+    /// Reverse lookup of a topic index.
+    indices: BTreeMap<B256, u32>,
+}
+
+impl TopicDictionary {
+    /// Creates a dictionary out of the given topics. The position of a topic is its index.
+    pub fn new(topics: Vec<B256>) -> Self {
+        let indices =
+            topics.iter().enumerate().map(|(index, topic)| (*topic, index as u32)).collect();
+        Self { topics, indices }
+    }
+
+    /// Builds a dictionary holding the `max_len` most frequent topics of `receipts`.
+    ///
+    /// Topics that only appear once are not worth an entry and are left out.
+    pub fn from_receipts<'a>(
+        receipts: impl IntoIterator<Item = &'a Receipt>,
+        max_len: usize,
+    ) -> Self {
+        let mut frequencies = BTreeMap::<B256, u64>::new();
+        for receipt in receipts {
+            for topic in receipt.logs.iter().flat_map(|log| log.topics()) {
+                *frequencies.entry(*topic).or_default() += 1;
+            }
+        }
+
+        let mut topics =
+            frequencies.into_iter().filter(|(_, count)| *count > 1).collect::<Vec<_>>();
+        // Most frequent topics get the lowest indices, which have the shortest varint encoding.
+        topics.sort_unstable_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        topics.truncate(max_len);
+
+        Self::new(topics.into_iter().map(|(topic, _)| topic).collect())
+    }
+
+    /// Returns the number of topics in the dictionary.
+    pub fn len(&self) -> usize {
+        self.topics.len()
+    }
+
+    /// Returns `true` if the dictionary has no topics.
+    pub fn is_empty(&self) -> bool {
+        self.topics.is_empty()
+    }
+
+    /// Serializes the dictionary as a big-endian `u32` topic count followed by the topics.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(4 + self.topics.len() * 32);
+        out.extend_from_slice(&(self.topics.len() as u32).to_be_bytes());
+        for topic in &self.topics {
+            out.extend_from_slice(topic.as_slice());
+        }
+        out
+    }
+
+    /// Deserializes a dictionary written by [`Self::to_bytes`].
+    pub fn from_bytes(mut buf: &[u8]) -> alloy_rlp::Result<Self> {
+        if buf.len() < 4 {
+            return Err(Error::InputTooShort)
+        }
+        let len = buf.get_u32() as usize;
+        if buf.len() != len * 32 {
+            return Err(Error::Custom("invalid topic dictionary length"))
+        }
+        Ok(Self::new(buf.chunks_exact(32).map(B256::from_slice).collect()))
+    }
+
+    /// Encodes `receipt` into `out`.
+    pub fn encode_receipt(&self, receipt: &Receipt, out: &mut Vec<u8>) {
+        let mut flags = u8::from(receipt.tx_type);
+        if receipt.success {
+            flags |= SUCCESS_FLAG;
+        }
+        out.push(flags);
+        write_varint(receipt.cumulative_gas_used, out);
+
+        write_varint(receipt.logs.len() as u64, out);
+        for log in &receipt.logs {
+            out.extend_from_slice(log.address.as_slice());
+
+            write_varint(log.topics().len() as u64, out);
+            for topic in log.topics() {
+                match self.indices.get(topic) {
+                    // `0` is reserved for inlined topics.
+                    Some(index) => write_varint(*index as u64 + 1, out),
+                    None => {
+                        out.push(0);
+                        out.extend_from_slice(topic.as_slice());
+                    }
+                }
+            }
+
+            write_varint(log.data.data.len() as u64, out);
+            out.extend_from_slice(&log.data.data);
+        }
+
+        #[cfg(feature = "optimism")]
+        {
+            let presence = receipt.deposit_nonce.is_some() as u8 |
+                (receipt.deposit_receipt_version.is_some() as u8) << 1;
+            out.push(presence);
+            if let Some(nonce) = receipt.deposit_nonce {
+                write_varint(nonce, out);
+            }
+            if let Some(version) = receipt.deposit_receipt_version {
+                write_varint(version, out);
+            }
+        }
+    }
+
+    /// Decodes a receipt encoded by [`Self::encode_receipt`].
+    pub fn decode_receipt(&self, mut buf: &[u8]) -> alloy_rlp::Result<Receipt> {
+        let buf = &mut buf;
+
+        let flags = read_u8(buf)?;
+        let tx_type = TxType::try_from(flags & !SUCCESS_FLAG).map_err(Error::Custom)?;
+        let success = flags & SUCCESS_FLAG != 0;
+        let cumulative_gas_used = read_varint(buf)?;
+
+        let logs_len = read_varint(buf)? as usize;
+        let mut logs = Vec::with_capacity(logs_len.min(buf.len()));
+        for _ in 0..logs_len {
+            let address = Address::from_slice(read_slice(buf, 20)?);
+
+            let topics_len = read_varint(buf)? as usize;
+            let mut topics = Vec::with_capacity(topics_len.min(4));
+            for _ in 0..topics_len {
+                let topic = match read_varint(buf)? {
+                    0 => B256::from_slice(read_slice(buf, 32)?),
+                    index => *self
+                        .topics
+                        .get(index as usize - 1)
+                        .ok_or(Error::Custom("topic missing from dictionary"))?,
+                };
+                topics.push(topic);
+            }
+
+            let data_len = read_varint(buf)? as usize;
+            let data = Bytes::copy_from_slice(read_slice(buf, data_len)?);
+
+            logs.push(Log { address, data: LogData::new_unchecked(topics, data) });
+        }
+
+        #[cfg(feature = "optimism")]
+        let (deposit_nonce, deposit_receipt_version) = {
+            let presence = read_u8(buf)?;
+            let nonce = if presence & 1 != 0 { Some(read_varint(buf)?) } else { None };
+            let version = if presence & 2 != 0 { Some(read_varint(buf)?) } else { None };
+            (nonce, version)
+        };
+
+        if !buf.is_empty() {
+            return Err(Error::Custom("trailing bytes after compacted receipt"))
+        }
+
+        Ok(Receipt {
+            tx_type,
+            success,
+            cumulative_gas_used,
+            logs,
+            #[cfg(feature = "optimism")]
+            deposit_nonce,
+            #[cfg(feature = "optimism")]
+            deposit_receipt_version,
+        })
+    }
+}
+
+/// Writes `value` as an unsigned LEB128 varint.
+fn write_varint(mut value: u64, out: &mut Vec<u8>) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// Reads an unsigned LEB128 varint.
+fn read_varint(buf: &mut &[u8]) -> alloy_rlp::Result<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = read_u8(buf)?;
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value)
+        }
+    }
+    Err(Error::Overflow)
+}
+
+fn read_u8(buf: &mut &[u8]) -> alloy_rlp::Result<u8> {
+    if buf.is_empty() {
+        return Err(Error::InputTooShort)
+    }
+    Ok(buf.get_u8())
+}
+
+fn read_slice<'a>(buf: &mut &'a [u8], len: usize) -> alloy_rlp::Result<&'a [u8]> {
+    if buf.len() < len {
+        return Err(Error::InputTooShort)
+    }
+    let (slice, rest) = buf.split_at(len);
+    *buf = rest;
+    Ok(slice)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{address, b256, bytes};
+
+    fn receipts() -> Vec<Receipt> {
+        let transfer = b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+        let log = |data| Log {
+            address: address!("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"),
+            data: LogData::new_unchecked(vec![transfer, B256::with_last_byte(1)], data),
+        };
+
+        vec![
+            Receipt {
+                tx_type: TxType::Legacy,
+                success: false,
+                cumulative_gas_used: 21_000,
+                ..Default::default()
+            },
+            Receipt {
+                tx_type: TxType::Eip1559,
+                success: true,
+                cumulative_gas_used: 29_999_999,
+                logs: vec![log(bytes!("01")), log(Bytes::new())],
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn dictionary_keeps_repeated_topics() {
+        let receipts = receipts();
+        let dictionary = TopicDictionary::from_receipts(&receipts, DEFAULT_TOPIC_DICTIONARY_SIZE);
+        assert_eq!(dictionary.len(), 2);
+
+        let truncated = TopicDictionary::from_receipts(&receipts, 1);
+        assert_eq!(truncated.len(), 1);
+
+        let decoded = TopicDictionary::from_bytes(&dictionary.to_bytes()).unwrap();
+        assert_eq!(decoded, dictionary);
+    }
+
+    #[test]
+    fn receipt_roundtrip() {
+        let receipts = receipts();
+        for dictionary in [
+            TopicDictionary::default(),
+            TopicDictionary::from_receipts(&receipts, DEFAULT_TOPIC_DICTIONARY_SIZE),
+        ] {
+            for receipt in &receipts {
+                let mut buf = Vec::new();
+                dictionary.encode_receipt(receipt, &mut buf);
+                assert_eq!(&dictionary.decode_receipt(&buf).unwrap(), receipt);
+            }
+        }
+    }
+
+    #[test]
+    fn dictionary_shrinks_encoding() {
+        let receipts = receipts();
+        let dictionary = TopicDictionary::from_receipts(&receipts, DEFAULT_TOPIC_DICTIONARY_SIZE);
+
+        let (mut inlined, mut compacted) = (Vec::new(), Vec::new());
+        TopicDictionary::default().encode_receipt(&receipts[1], &mut inlined);
+        dictionary.encode_receipt(&receipts[1], &mut compacted);
+        assert!(compacted.len() + 4 * 31 <= inlined.len());
+    }
+}
@@ -0,0 +1,703 @@
+//! Decoding of contract storage with the storage layout emitted by the Solidity compiler.
+//!
+//! The layout is the `storageLayout` output of solc: the state variables of a contract with their
+//! slot, offset and type, and the types they refer to. Variables are addressed by paths starting
+//! at a state variable and following struct members, array indices and mapping keys, e.g. `owner`,
+//! `config.fee`, `holders[3]` or `allowances[0x..][0x..]`.
+
+use crate::{keccak256, Address, Bytes, B256, U256};
+use alloy_primitives::I256;
+use core::{fmt, str::FromStr};
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+/// Maximum number of storage slots read to decode a single variable.
+///
+/// Large arrays and byte strings exceed it, their elements can be read by index instead.
+pub const MAX_STORAGE_LAYOUT_READS: usize = 16_384;
+
+/// The storage layout of a contract, as emitted by solc with `--storage-layout`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageLayout {
+    /// The state variables of the contract.
+    pub storage: Vec<StorageLayoutEntry>,
+    /// The types of the state variables and of their members and elements, by type id.
+    #[serde(default)]
+    pub types: BTreeMap<String, StorageLayoutType>,
+}
+
+/// A state variable, or a member of a struct.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageLayoutEntry {
+    /// The name of the variable.
+    pub label: String,
+    /// The first slot of the variable, relative to the struct for members.
+    #[serde(with = "decimal")]
+    pub slot: U256,
+    /// The offset in bytes of the variable in its slot.
+    pub offset: u8,
+    /// The id of the type of the variable.
+    #[serde(rename = "type")]
+    pub ty: String,
+}
+
+/// A type of the storage layout.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageLayoutType {
+    /// How values of the type are stored.
+    pub encoding: StorageEncoding,
+    /// The name of the type in Solidity, e.g. `uint256` or `mapping(address => uint256)`.
+    pub label: String,
+    /// The number of bytes the type occupies in place.
+    #[serde(with = "decimal")]
+    pub number_of_bytes: u64,
+    /// The type id of the keys of a mapping.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    /// The type id of the values of a mapping.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// The type id of the elements of an array.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    /// The members of a struct.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub members: Option<Vec<StorageLayoutEntry>>,
+}
+
+/// How values of a type are stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageEncoding {
+    /// Stored in place, packed with other values in the same slot if they fit.
+    Inplace,
+    /// A mapping, whose values are stored at the hash of their key and the slot of the mapping.
+    Mapping,
+    /// A dynamic array, whose length is stored in place and elements at the hash of the slot.
+    DynamicArray,
+    /// A byte string, stored in place if shorter than 32 bytes, at the hash of the slot otherwise.
+    Bytes,
+}
+
+/// A variable decoded from storage.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageVariable {
+    /// The path of the variable.
+    pub path: String,
+    /// The name of the type of the variable in Solidity.
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// The first slot of the variable.
+    pub slot: B256,
+    /// The offset in bytes of the variable in its slot.
+    pub offset: u8,
+    /// The value of the variable, `None` for mappings, whose values can only be read by key.
+    pub value: Option<StorageLayoutValue>,
+}
+
+/// A decoded storage value.
+///
+/// Integers are decimal strings, addresses are checksummed, fixed bytes and byte strings are hex
+/// encoded and strings are decoded as UTF-8. Members of structs that are mappings are omitted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum StorageLayoutValue {
+    /// A boolean.
+    Bool(bool),
+    /// An integer, address, fixed bytes, byte string or string.
+    Text(String),
+    /// The elements of an array.
+    Array(Vec<StorageLayoutValue>),
+    /// The members of a struct.
+    Struct(BTreeMap<String, StorageLayoutValue>),
+}
+
+/// Errors of decoding storage with a storage layout.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror_no_std::Error)]
+pub enum StorageLayoutError {
+    /// The path is malformed.
+    #[error("invalid path {0}")]
+    InvalidPath(String),
+    /// The layout has no state variable with the name.
+    #[error("unknown variable {0}")]
+    UnknownVariable(String),
+    /// The layout has no type with the id.
+    #[error("unknown type {0}")]
+    UnknownType(String),
+    /// The struct has no member with the name.
+    #[error("type {ty} has no member {member}")]
+    UnknownMember {
+        /// The name of the struct type.
+        ty: String,
+        /// The name of the member.
+        member: String,
+    },
+    /// The type is neither an array nor a mapping.
+    #[error("type {0} can't be indexed")]
+    NotIndexable(String),
+    /// The key or index can't be converted to the key type.
+    #[error("invalid key {key} for type {ty}")]
+    InvalidKey {
+        /// The key.
+        key: String,
+        /// The name of the key type.
+        ty: String,
+    },
+    /// The index is not less than the length of the array.
+    #[error("index {index} is out of bounds of an array of length {length}")]
+    IndexOutOfBounds {
+        /// The index.
+        index: U256,
+        /// The length of the array.
+        length: U256,
+    },
+    /// Decoding the variable needs more than [`MAX_STORAGE_LAYOUT_READS`] slots.
+    #[error(
+        "decoding needs more than {} storage reads, read the elements by index instead",
+        MAX_STORAGE_LAYOUT_READS
+    )]
+    TooManyReads,
+}
+
+/// A part of a path after the state variable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PathSegment<'a> {
+    /// A member of a struct, `.member`.
+    Member(&'a str),
+    /// An index of an array or a key of a mapping, `[key]`.
+    Index(&'a str),
+}
+
+/// Splits a path into the name of the state variable and the segments following it.
+fn parse_path(path: &str) -> Result<(&str, Vec<PathSegment<'_>>), StorageLayoutError> {
+    let invalid = || StorageLayoutError::InvalidPath(path.to_string());
+    let is_ident = |name: &str| {
+        !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+    };
+
+    let end = path.find(['.', '[']).unwrap_or(path.len());
+    let (variable, mut rest) = path.split_at(end);
+    if !is_ident(variable) {
+        return Err(invalid())
+    }
+
+    let mut segments = Vec::new();
+    while !rest.is_empty() {
+        if let Some(member) = rest.strip_prefix('.') {
+            let end = member.find(['.', '[']).unwrap_or(member.len());
+            let (member, tail) = member.split_at(end);
+            if !is_ident(member) {
+                return Err(invalid())
+            }
+            segments.push(PathSegment::Member(member));
+            rest = tail;
+        } else if let Some(index) = rest.strip_prefix('[') {
+            let end = index.find(']').ok_or_else(invalid)?;
+            let key = index[..end].trim();
+            if key.is_empty() {
+                return Err(invalid())
+            }
+            segments.push(PathSegment::Index(key));
+            rest = &index[end + 1..];
+        } else {
+            return Err(invalid())
+        }
+    }
+    Ok((variable, segments))
+}
+
+/// The position of a value in storage.
+#[derive(Debug, Clone, Copy)]
+struct Location<'a> {
+    slot: U256,
+    offset: u8,
+    ty: &'a str,
+}
+
+impl StorageLayout {
+    /// Returns the names of the state variables.
+    pub fn variables(&self) -> impl Iterator<Item = &str> {
+        self.storage.iter().map(|entry| entry.label.as_str())
+    }
+
+    /// Reads and decodes the variable at the given path, reading storage slots with `read`.
+    pub fn read_variable<E>(
+        &self,
+        path: &str,
+        read: impl FnMut(B256) -> Result<U256, E>,
+    ) -> Result<StorageVariable, E>
+    where
+        E: From<StorageLayoutError>,
+    {
+        let mut decoder = Decoder { layout: self, read, reads: 0 };
+        let location = decoder.resolve(path)?;
+        let ty = self.ty(location.ty)?;
+        let value = decoder.decode(location)?;
+        Ok(StorageVariable {
+            path: path.to_string(),
+            ty: ty.label.clone(),
+            slot: location.slot.into(),
+            offset: location.offset,
+            value,
+        })
+    }
+
+    fn ty(&self, id: &str) -> Result<&StorageLayoutType, StorageLayoutError> {
+        self.types.get(id).ok_or_else(|| StorageLayoutError::UnknownType(id.to_string()))
+    }
+}
+
+/// Resolves paths and decodes values, counting the storage reads.
+struct Decoder<'a, F> {
+    layout: &'a StorageLayout,
+    read: F,
+    reads: usize,
+}
+
+impl<'a, F, E> Decoder<'a, F>
+where
+    F: FnMut(B256) -> Result<U256, E>,
+    E: From<StorageLayoutError>,
+{
+    fn read(&mut self, slot: U256) -> Result<U256, E> {
+        self.reads += 1;
+        if self.reads > MAX_STORAGE_LAYOUT_READS {
+            return Err(StorageLayoutError::TooManyReads.into())
+        }
+        (self.read)(slot.into())
+    }
+
+    /// Returns the location of the value at the path.
+    fn resolve(&mut self, path: &str) -> Result<Location<'a>, E> {
+        let (variable, segments) = parse_path(path)?;
+        let entry = self
+            .layout
+            .storage
+            .iter()
+            .find(|entry| entry.label == variable)
+            .ok_or_else(|| StorageLayoutError::UnknownVariable(variable.to_string()))?;
+
+        let mut location = Location { slot: entry.slot, offset: entry.offset, ty: &entry.ty };
+        for segment in segments {
+            let ty = self.layout.ty(location.ty)?;
+            location = match segment {
+                PathSegment::Member(name) => {
+                    let member = ty
+                        .members
+                        .iter()
+                        .flatten()
+                        .find(|member| member.label == name)
+                        .ok_or_else(|| StorageLayoutError::UnknownMember {
+                            ty: ty.label.clone(),
+                            member: name.to_string(),
+                        })?;
+                    Location {
+                        slot: location.slot.wrapping_add(member.slot),
+                        offset: member.offset,
+                        ty: &member.ty,
+                    }
+                }
+                PathSegment::Index(key) => match (ty.encoding, &ty.key, &ty.value, &ty.base) {
+                    (StorageEncoding::Mapping, Some(key_ty), Some(value_ty), _) => {
+                        let mut preimage = encode_key(key, key_ty, self.layout)?;
+                        preimage.extend_from_slice(&location.slot.to_be_bytes::<32>());
+                        Location { slot: keccak256(preimage).into(), offset: 0, ty: value_ty }
+                    }
+                    (StorageEncoding::DynamicArray, _, _, Some(base)) => {
+                        let length = self.read(location.slot)?;
+                        let data = keccak256(location.slot.to_be_bytes::<32>()).into();
+                        self.element(data, length, key, base)?
+                    }
+                    (StorageEncoding::Inplace, _, _, Some(base)) => {
+                        let length = static_array_length(&ty.label)?;
+                        self.element(location.slot, length, key, base)?
+                    }
+                    _ => return Err(StorageLayoutError::NotIndexable(ty.label.clone()).into()),
+                },
+            };
+        }
+        Ok(location)
+    }
+
+    /// Returns the location of the element of an array whose elements start at `data`.
+    fn element(
+        &self,
+        data: U256,
+        length: U256,
+        index: &str,
+        base: &'a str,
+    ) -> Result<Location<'a>, E> {
+        let index = U256::from_str(index).map_err(|_| StorageLayoutError::InvalidKey {
+            key: index.to_string(),
+            ty: "uint256".to_string(),
+        })?;
+        if index >= length {
+            return Err(StorageLayoutError::IndexOutOfBounds { index, length }.into())
+        }
+        let size = self.layout.ty(base)?.number_of_bytes;
+        Ok(element_location(data, index, size, base))
+    }
+
+    /// Decodes the value at the location, `None` for mappings.
+    fn decode(&mut self, location: Location<'a>) -> Result<Option<StorageLayoutValue>, E> {
+        let ty = self.layout.ty(location.ty)?;
+        let value = match ty.encoding {
+            StorageEncoding::Mapping => return Ok(None),
+            StorageEncoding::Bytes => {
+                let bytes = self.decode_bytes(location.slot)?;
+                if ty.label == "string" {
+                    StorageLayoutValue::Text(String::from_utf8_lossy(&bytes).into_owned())
+                } else {
+                    StorageLayoutValue::Text(Bytes::from(bytes).to_string())
+                }
+            }
+            StorageEncoding::DynamicArray => {
+                let base = ty.base.as_deref().ok_or_else(|| missing_field(location.ty))?;
+                let length = self.read(location.slot)?;
+                let data = keccak256(location.slot.to_be_bytes::<32>()).into();
+                self.decode_array(data, length, base)?
+            }
+            StorageEncoding::Inplace => {
+                if let Some(members) = &ty.members {
+                    let mut values = BTreeMap::new();
+                    for member in members {
+                        let member_location = Location {
+                            slot: location.slot.wrapping_add(member.slot),
+                            offset: member.offset,
+                            ty: &member.ty,
+                        };
+                        if let Some(value) = self.decode(member_location)? {
+                            values.insert(member.label.clone(), value);
+                        }
+                    }
+                    StorageLayoutValue::Struct(values)
+                } else if let Some(base) = &ty.base {
+                    let length = static_array_length(&ty.label)?;
+                    self.decode_array(location.slot, length, base)?
+                } else {
+                    let word = self.read(location.slot)?;
+                    decode_value(word, location.offset, ty.number_of_bytes, location.ty)
+                }
+            }
+        };
+        Ok(Some(value))
+    }
+
+    fn decode_array(
+        &mut self,
+        data: U256,
+        length: U256,
+        base: &'a str,
+    ) -> Result<StorageLayoutValue, E> {
+        if length > U256::from(MAX_STORAGE_LAYOUT_READS) {
+            return Err(StorageLayoutError::TooManyReads.into())
+        }
+        let size = self.layout.ty(base)?.number_of_bytes;
+        let mut elements = Vec::new();
+        for index in 0..length.to::<u64>() {
+            let location = element_location(data, U256::from(index), size, base);
+            elements.extend(self.decode(location)?);
+        }
+        Ok(StorageLayoutValue::Array(elements))
+    }
+
+    /// Decodes a byte string, stored in place with twice its length in the lowest byte if it's
+    /// shorter than 32 bytes, or as twice its length plus one and at the hash of the slot
+    /// otherwise.
+    fn decode_bytes(&mut self, slot: U256) -> Result<Vec<u8>, E> {
+        let word = self.read(slot)?;
+        if !word.bit(0) {
+            let bytes = word.to_be_bytes::<32>();
+            let length = (bytes[31] / 2).min(31) as usize;
+            return Ok(bytes[..length].to_vec())
+        }
+
+        let length = word >> 1;
+        if length > U256::from(MAX_STORAGE_LAYOUT_READS * 32) {
+            return Err(StorageLayoutError::TooManyReads.into())
+        }
+        let length = length.to::<usize>();
+        let data: U256 = keccak256(slot.to_be_bytes::<32>()).into();
+        let mut bytes = Vec::with_capacity(length.next_multiple_of(32));
+        for index in 0..length.div_ceil(32) {
+            let word = self.read(data.wrapping_add(U256::from(index)))?;
+            bytes.extend_from_slice(&word.to_be_bytes::<32>());
+        }
+        bytes.truncate(length);
+        Ok(bytes)
+    }
+}
+
+/// Returns the location of the element at the index of an array whose elements start at `data`.
+///
+/// Elements shorter than 32 bytes are packed, as many as fit into a slot.
+fn element_location(data: U256, index: U256, size: u64, base: &str) -> Location<'_> {
+    if size < 32 {
+        let per_slot = U256::from(32 / size.max(1));
+        let offset = (index % per_slot).to::<u64>() * size;
+        Location { slot: data.wrapping_add(index / per_slot), offset: offset as u8, ty: base }
+    } else {
+        let slots = U256::from(size.div_ceil(32));
+        Location { slot: data.wrapping_add(index.wrapping_mul(slots)), offset: 0, ty: base }
+    }
+}
+
+/// Returns the length of a static array from its type name, e.g. `3` for `uint256[3]`.
+fn static_array_length(label: &str) -> Result<U256, StorageLayoutError> {
+    label
+        .rsplit_once('[')
+        .and_then(|(_, length)| length.strip_suffix(']'))
+        .and_then(|length| U256::from_str(length).ok())
+        .ok_or_else(|| StorageLayoutError::UnknownType(label.to_string()))
+}
+
+fn missing_field(ty: &str) -> StorageLayoutError {
+    StorageLayoutError::UnknownType(ty.to_string())
+}
+
+/// Returns the size of a fixed bytes type id, e.g. `32` for `t_bytes32`.
+fn fixed_bytes_size(ty: &str) -> Option<usize> {
+    ty.strip_prefix("t_bytes").and_then(|size| size.parse().ok())
+}
+
+/// Decodes the value of an elementary type that occupies `size` bytes at `offset` of the word.
+fn decode_value(word: U256, offset: u8, size: u64, ty: &str) -> StorageLayoutValue {
+    let bytes = word.to_be_bytes::<32>();
+    let size = (size as usize).min(32);
+    let end = 32usize.saturating_sub(offset as usize).max(size);
+    let raw = &bytes[end - size..end];
+    let value = U256::from_be_slice(raw);
+    if ty == "t_bool" {
+        return StorageLayoutValue::Bool(!value.is_zero())
+    }
+
+    let text = if ty.starts_with("t_address") || ty.starts_with("t_contract(") {
+        Address::from_word(value.into()).to_checksum(None)
+    } else if ty.starts_with("t_uint") || ty.starts_with("t_enum(") {
+        value.to_string()
+    } else if ty.starts_with("t_int") {
+        // sign extend the value to 256 bits
+        let bits = size * 8;
+        let raw =
+            if bits < 256 && value.bit(bits - 1) { value | (U256::MAX << bits) } else { value };
+        I256::from_raw(raw).to_string()
+    } else {
+        // fixed bytes, user defined value types and function pointers
+        Bytes::copy_from_slice(raw).to_string()
+    };
+    StorageLayoutValue::Text(text)
+}
+
+/// Encodes a mapping key as it's hashed with the slot of the mapping: value types are padded to
+/// 32 bytes, strings and byte strings are hashed unpadded.
+fn encode_key(key: &str, ty: &str, layout: &StorageLayout) -> Result<Vec<u8>, StorageLayoutError> {
+    let label = layout.types.get(ty).map_or(ty, |ty| ty.label.as_str());
+    let invalid = || StorageLayoutError::InvalidKey { key: key.to_string(), ty: label.to_string() };
+
+    if ty.starts_with("t_string") {
+        return Ok(key.as_bytes().to_vec())
+    }
+    if ty.starts_with("t_bytes") && fixed_bytes_size(ty).is_none() {
+        return crate::hex::decode(key).map_err(|_| invalid())
+    }
+
+    let word = if let Some(size) = fixed_bytes_size(ty) {
+        let bytes = crate::hex::decode(key).map_err(|_| invalid())?;
+        if bytes.len() != size {
+            return Err(invalid())
+        }
+        let mut word = B256::ZERO;
+        word[..size].copy_from_slice(&bytes);
+        word
+    } else if ty.starts_with("t_address") || ty.starts_with("t_contract(") {
+        Address::from_str(key).map_err(|_| invalid())?.into_word()
+    } else if ty == "t_bool" {
+        match key {
+            "true" => B256::with_last_byte(1),
+            "false" => B256::ZERO,
+            _ => return Err(invalid()),
+        }
+    } else if ty.starts_with("t_uint") || ty.starts_with("t_enum(") {
+        U256::from_str(key).map_err(|_| invalid())?.into()
+    } else if ty.starts_with("t_int") {
+        I256::from_dec_str(key).map_err(|_| invalid())?.into_raw().into()
+    } else {
+        return Err(invalid())
+    };
+    Ok(word.to_vec())
+}
+
+/// (De)serializes integers as decimal strings, like solc does for slots and sizes.
+mod decimal {
+    use super::*;
+    use serde::{de::Error, Deserializer, Serializer};
+
+    pub(super) fn serialize<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: fmt::Display,
+    {
+        serializer.collect_str(value)
+    }
+
+    pub(super) fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: FromStr + From<u64>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Decimal {
+            Number(u64),
+            String(String),
+        }
+
+        match Decimal::deserialize(deserializer)? {
+            Decimal::Number(number) => Ok(number.into()),
+            Decimal::String(string) => string
+                .parse()
+                .map_err(|_| D::Error::custom(format!("invalid decimal number {string}"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::address;
+    use std::collections::HashMap;
+
+    /// The layout of
+    ///
+    /// ```solidity
+    /// contract Token {
+    ///     struct Config { uint64 fee; bool paused; address admin; }
+    ///     address owner;
+    ///     int16 delta;
+    ///     bool active;
+    ///     Config config;
+    ///     mapping(address => uint256) balances;
+    ///     uint128[] amounts;
+    ///     string name;
+    /// }
+    /// ```
+    const LAYOUT: &str = r#"{
+        "storage": [
+            {"astId": 1, "contract": "Token", "label": "owner", "offset": 0, "slot": "0", "type": "t_address"},
+            {"astId": 2, "contract": "Token", "label": "delta", "offset": 20, "slot": "0", "type": "t_int16"},
+            {"astId": 3, "contract": "Token", "label": "active", "offset": 22, "slot": "0", "type": "t_bool"},
+            {"astId": 4, "contract": "Token", "label": "config", "offset": 0, "slot": "1", "type": "t_struct(Config)10_storage"},
+            {"astId": 5, "contract": "Token", "label": "balances", "offset": 0, "slot": "3", "type": "t_mapping(t_address,t_uint256)"},
+            {"astId": 6, "contract": "Token", "label": "amounts", "offset": 0, "slot": "4", "type": "t_array(t_uint128)dyn_storage"},
+            {"astId": 7, "contract": "Token", "label": "name", "offset": 0, "slot": "5", "type": "t_string_storage"}
+        ],
+        "types": {
+            "t_address": {"encoding": "inplace", "label": "address", "numberOfBytes": "20"},
+            "t_bool": {"encoding": "inplace", "label": "bool", "numberOfBytes": "1"},
+            "t_int16": {"encoding": "inplace", "label": "int16", "numberOfBytes": "2"},
+            "t_uint64": {"encoding": "inplace", "label": "uint64", "numberOfBytes": "8"},
+            "t_uint128": {"encoding": "inplace", "label": "uint128", "numberOfBytes": "16"},
+            "t_uint256": {"encoding": "inplace", "label": "uint256", "numberOfBytes": "32"},
+            "t_string_storage": {"encoding": "bytes", "label": "string", "numberOfBytes": "32"},
+            "t_array(t_uint128)dyn_storage": {"encoding": "dynamic_array", "label": "uint128[]", "numberOfBytes": "32", "base": "t_uint128"},
+            "t_mapping(t_address,t_uint256)": {"encoding": "mapping", "label": "mapping(address => uint256)", "numberOfBytes": "32", "key": "t_address", "value": "t_uint256"},
+            "t_struct(Config)10_storage": {"encoding": "inplace", "label": "struct Token.Config", "numberOfBytes": "64", "members": [
+                {"astId": 8, "contract": "Token", "label": "fee", "offset": 0, "slot": "0", "type": "t_uint64"},
+                {"astId": 9, "contract": "Token", "label": "paused", "offset": 8, "slot": "0", "type": "t_bool"},
+                {"astId": 10, "contract": "Token", "label": "admin", "offset": 0, "slot": "1", "type": "t_address"}
+            ]}
+        }
+    }"#;
+
+    fn text(value: impl ToString) -> Option<StorageLayoutValue> {
+        Some(StorageLayoutValue::Text(value.to_string()))
+    }
+
+    #[test]
+    fn decodes_storage_with_layout() {
+        let layout: StorageLayout = serde_json::from_str(LAYOUT).unwrap();
+        let owner = address!("00000000000000000000000000000000000000aa");
+        let holder = address!("00000000000000000000000000000000000000bb");
+
+        let mut storage = HashMap::<U256, U256>::new();
+        // owner, delta = -2 and active packed into slot 0
+        let mut slot0 = owner.into_word().0;
+        slot0[10..12].copy_from_slice(&(-2i16).to_be_bytes());
+        slot0[9] = 1;
+        storage.insert(U256::ZERO, U256::from_be_bytes(slot0));
+        // config.fee = 7, config.paused = true, config.admin = holder
+        storage.insert(U256::from(1), U256::from(7) | (U256::from(1) << 64));
+        storage.insert(U256::from(2), holder.into_word().into());
+        // balances[holder] = 100
+        let mut preimage = holder.into_word().to_vec();
+        preimage.extend_from_slice(&U256::from(3).to_be_bytes::<32>());
+        storage.insert(keccak256(preimage).into(), U256::from(100));
+        // amounts = [1, 2, 3], two per slot
+        let data: U256 = keccak256(U256::from(4).to_be_bytes::<32>()).into();
+        storage.insert(U256::from(4), U256::from(3));
+        storage.insert(data, U256::from(1) | (U256::from(2) << 128));
+        storage.insert(data + U256::from(1), U256::from(3));
+        // name = "reth", stored in place
+        let mut name = [0u8; 32];
+        name[..4].copy_from_slice(b"reth");
+        name[31] = 8;
+        storage.insert(U256::from(5), U256::from_be_bytes(name));
+
+        let read = |path: &str| {
+            layout.read_variable(path, |slot| {
+                Ok::<_, StorageLayoutError>(storage.get(&slot.into()).copied().unwrap_or_default())
+            })
+        };
+
+        assert_eq!(read("owner").unwrap().value, text(owner.to_checksum(None)));
+        assert_eq!(read("delta").unwrap().value, text(-2));
+        assert_eq!(read("active").unwrap().value, Some(StorageLayoutValue::Bool(true)));
+        assert_eq!(read("config.fee").unwrap().value, text(7));
+        assert_eq!(
+            read("config").unwrap().value,
+            Some(StorageLayoutValue::Struct(BTreeMap::from([
+                ("fee".to_string(), StorageLayoutValue::Text("7".to_string())),
+                ("paused".to_string(), StorageLayoutValue::Bool(true)),
+                ("admin".to_string(), StorageLayoutValue::Text(holder.to_checksum(None))),
+            ])))
+        );
+        assert_eq!(read("balances").unwrap().value, None);
+        assert_eq!(read(&format!("balances[{holder}]")).unwrap().value, text(100));
+        assert_eq!(read(&format!("balances[{owner}]")).unwrap().value, text(0));
+        assert_eq!(
+            read("amounts").unwrap().value,
+            Some(StorageLayoutValue::Array(vec![
+                StorageLayoutValue::Text("1".to_string()),
+                StorageLayoutValue::Text("2".to_string()),
+                StorageLayoutValue::Text("3".to_string()),
+            ]))
+        );
+        let element = read("amounts[1]").unwrap();
+        assert_eq!((element.slot, element.offset, element.value), (data.into(), 16, text(2)));
+        assert_eq!(read("name").unwrap().value, text("reth"));
+
+        assert_eq!(
+            read("amounts[3]"),
+            Err(StorageLayoutError::IndexOutOfBounds {
+                index: U256::from(3),
+                length: U256::from(3)
+            })
+        );
+        assert_eq!(read("supply"), Err(StorageLayoutError::UnknownVariable("supply".to_string())));
+        assert_eq!(read("owner[1]"), Err(StorageLayoutError::NotIndexable("address".to_string())));
+        assert_eq!(
+            read("balances[1"),
+            Err(StorageLayoutError::InvalidPath("balances[1".to_string()))
+        );
+    }
+}
@@ -4,10 +4,12 @@ use crate::{
     },
     PrunerError,
 };
+use alloy_primitives::Address;
 use reth_db::tables;
 use reth_db_api::{database::Database, models::ShardedKey};
-use reth_provider::DatabaseProviderRW;
+use reth_provider::{bundle_state::BytecodeReferenceChanges, DatabaseProviderRW};
 use reth_prune_types::{PruneInterruptReason, PruneMode, PruneProgress, PruneSegment};
+use std::collections::BTreeSet;
 use tracing::{instrument, trace};
 
 /// Number of account history tables to prune in one step.
@@ -19,11 +21,13 @@ const ACCOUNT_HISTORY_TABLES_TO_PRUNE: usize = 2;
 #[derive(Debug)]
 pub struct AccountHistory {
     mode: PruneMode,
+    /// Addresses whose account history is never pruned.
+    allowlist: BTreeSet<Address>,
 }
 
 impl AccountHistory {
-    pub const fn new(mode: PruneMode) -> Self {
-        Self { mode }
+    pub const fn new(mode: PruneMode, allowlist: BTreeSet<Address>) -> Self {
+        Self { mode, allowlist }
     }
 }
 
@@ -64,15 +68,23 @@ impl<DB: Database> Segment<DB> for AccountHistory {
         }
 
         let mut last_changeset_pruned_block = None;
+        let mut bytecode_references = BytecodeReferenceChanges::default();
         let (pruned_changesets, done) = provider
             .prune_table_with_range::<tables::AccountChangeSets>(
                 range,
                 &mut limiter,
-                |_| false,
-                |row| last_changeset_pruned_block = Some(row.0),
+                |row| self.allowlist.contains(&row.1.address),
+                |row| {
+                    last_changeset_pruned_block = Some(row.0);
+                    bytecode_references.remove(row.1.info.as_ref());
+                },
             )?;
         trace!(target: "pruner", pruned = %pruned_changesets, %done, "Pruned account history (changesets)");
 
+        // Pruned changesets no longer reference the bytecodes of their accounts.
+        let pruned_bytecodes = bytecode_references.write_to_db(provider.tx_ref())?;
+        trace!(target: "pruner", pruned = %pruned_bytecodes, "Pruned account history (bytecodes)");
+
         let last_changeset_pruned_block = last_changeset_pruned_block
             // If there's more account account changesets to prune, set the checkpoint block number
             // to previous, so we could finish pruning its account changesets on the next run.
@@ -84,6 +96,7 @@ impl<DB: Database> Segment<DB> for AccountHistory {
             last_changeset_pruned_block,
             |a, b| a.key == b.key,
             |key| ShardedKey::last(key.key),
+            |key| self.allowlist.contains(&key.key),
         )?;
         trace!(target: "pruner", %processed, pruned = %pruned_indices, %done, "Pruned account history (history)");
 
@@ -91,7 +104,7 @@ impl<DB: Database> Segment<DB> for AccountHistory {
 
         Ok(PruneOutput {
             progress,
-            pruned: pruned_changesets + pruned_indices,
+            pruned: pruned_changesets + pruned_indices + pruned_bytecodes,
             checkpoint: Some(PruneOutputCheckpoint {
                 block_number: Some(last_changeset_pruned_block),
                 tx_number: None,
@@ -106,9 +119,10 @@ mod tests {
         account_history::ACCOUNT_HISTORY_TABLES_TO_PRUNE, AccountHistory, PruneInput, PruneOutput,
         Segment,
     };
-    use alloy_primitives::{BlockNumber, B256};
+    use alloy_primitives::{Address, BlockNumber, B256};
     use assert_matches::assert_matches;
     use reth_db::{tables, BlockNumberList};
+    use reth_db_api::models::ShardedKey;
     use reth_provider::PruneCheckpointReader;
     use reth_prune_types::{
         PruneCheckpoint, PruneInterruptReason, PruneLimiter, PruneMode, PruneProgress, PruneSegment,
@@ -118,7 +132,10 @@ mod tests {
         generators,
         generators::{random_block_range, random_changeset_range, random_eoa_accounts},
     };
-    use std::{collections::BTreeMap, ops::AddAssign};
+    use std::{
+        collections::{BTreeMap, BTreeSet},
+        ops::AddAssign,
+    };
 
     #[test]
     fn prune() {
@@ -172,7 +189,7 @@ mod tests {
                     to_block,
                     limiter: limiter.clone(),
                 };
-                let segment = AccountHistory::new(prune_mode);
+                let segment = AccountHistory::new(prune_mode, BTreeSet::new());
 
                 let provider = db.factory.provider_rw().unwrap();
                 let result = segment.prune(&provider, input).unwrap();
@@ -278,4 +295,55 @@ mod tests {
         test_prune(998, 2, (PruneProgress::Finished, 998));
         test_prune(1400, 3, (PruneProgress::Finished, 804));
     }
+
+    #[test]
+    fn prune_retains_allowlisted_history() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 1..=2000, B256::ZERO, 0..1);
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+
+        let accounts = random_eoa_accounts(&mut rng, 4).into_iter().collect::<BTreeMap<_, _>>();
+        let retained = *accounts.keys().next().unwrap();
+
+        let (changesets, _) = random_changeset_range(
+            &mut rng,
+            blocks.iter(),
+            accounts.into_iter().map(|(addr, acc)| (addr, (acc, Vec::new()))),
+            0..0,
+            0..0,
+        );
+        db.insert_changesets(changesets.clone(), None).expect("insert changesets");
+        db.insert_history(changesets, None).expect("insert history");
+
+        let original_changesets = db.table::<tables::AccountChangeSets>().unwrap();
+        let original_shards = db.table::<tables::AccountsHistory>().unwrap();
+
+        let to_block = 1000;
+        let input =
+            PruneInput { previous_checkpoint: None, to_block, limiter: PruneLimiter::default() };
+        let segment = AccountHistory::new(PruneMode::Before(to_block), BTreeSet::from([retained]));
+
+        let provider = db.factory.provider_rw().unwrap();
+        let result = segment.prune(&provider, input).unwrap();
+        assert!(result.progress.is_finished());
+        provider.commit().expect("commit");
+
+        let expected_changesets = original_changesets
+            .into_iter()
+            .filter(|(block_number, change)| change.address == retained || *block_number > to_block)
+            .collect::<Vec<_>>();
+        assert_eq!(db.table::<tables::AccountChangeSets>().unwrap(), expected_changesets);
+
+        let retained_shards = |shards: Vec<(ShardedKey<Address>, BlockNumberList)>| {
+            shards.into_iter().filter(|(key, _)| key.key == retained).collect::<Vec<_>>()
+        };
+        let actual_shards = db.table::<tables::AccountsHistory>().unwrap();
+        assert_eq!(retained_shards(actual_shards.clone()), retained_shards(original_shards));
+        assert!(actual_shards
+            .iter()
+            .filter(|(key, _)| key.key != retained)
+            .all(|(_, blocks)| blocks.iter().all(|block| block > to_block)));
+    }
 }
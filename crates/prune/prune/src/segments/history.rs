@@ -10,7 +10,8 @@ use reth_db_api::{
 };
 use reth_provider::DatabaseProviderRW;
 
-/// Prune history indices up to the provided block, inclusive.
+/// Prune history indices up to the provided block, inclusive. The shards of keys matching
+/// `retain_key` are kept.
 ///
 /// Returns total number of processed (walked) and deleted entities.
 pub(crate) fn prune_history_indices<DB, T, SK>(
@@ -18,6 +19,7 @@ pub(crate) fn prune_history_indices<DB, T, SK>(
     to_block: BlockNumber,
     key_matches: impl Fn(&T::Key, &T::Key) -> bool,
     last_key: impl Fn(&T::Key) -> T::Key,
+    retain_key: impl Fn(&T::Key) -> bool,
 ) -> Result<(usize, usize), DatabaseError>
 where
     DB: Database,
@@ -37,6 +39,15 @@ where
     while let Some(result) = cursor.next()? {
         let (key, blocks): (T::Key, BlockNumberList) = result;
 
+        // Keep all shards of a retained key, jumping to its last shard.
+        if retain_key(&key) {
+            if key.as_ref().highest_block_number != u64::MAX {
+                cursor.seek_exact(last_key(&key))?;
+            }
+            processed += 1;
+            continue
+        }
+
         // If shard consists only of block numbers less than the target one, delete shard
         // completely.
         if key.as_ref().highest_block_number <= to_block {
@@ -44,14 +44,20 @@ impl<DB: Database> SegmentSet<DB> {
             receipts,
             account_history,
             storage_history,
+            history_address_allowlist,
             receipts_log_filter,
         } = prune_modes;
 
         Self::default()
             // Account history
-            .segment_opt(account_history.map(AccountHistory::new))
+            .segment_opt(
+                account_history
+                    .map(|mode| AccountHistory::new(mode, history_address_allowlist.clone())),
+            )
             // Storage history
-            .segment_opt(storage_history.map(StorageHistory::new))
+            .segment_opt(
+                storage_history.map(|mode| StorageHistory::new(mode, history_address_allowlist)),
+            )
             // Receipts
             .segment_opt(receipts.map(Receipts::new))
             // Receipts by logs
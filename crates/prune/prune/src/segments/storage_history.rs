@@ -4,6 +4,7 @@ use crate::{
     },
     PrunerError,
 };
+use alloy_primitives::Address;
 use reth_db::tables;
 use reth_db_api::{
     database::Database,
@@ -11,6 +12,7 @@ use reth_db_api::{
 };
 use reth_provider::DatabaseProviderRW;
 use reth_prune_types::{PruneInterruptReason, PruneMode, PruneProgress, PruneSegment};
+use std::collections::BTreeSet;
 use tracing::{instrument, trace};
 
 /// Number of storage history tables to prune in one step
@@ -22,11 +24,13 @@ const STORAGE_HISTORY_TABLES_TO_PRUNE: usize = 2;
 #[derive(Debug)]
 pub struct StorageHistory {
     mode: PruneMode,
+    /// Addresses whose storage history is never pruned.
+    allowlist: BTreeSet<Address>,
 }
 
 impl StorageHistory {
-    pub const fn new(mode: PruneMode) -> Self {
-        Self { mode }
+    pub const fn new(mode: PruneMode, allowlist: BTreeSet<Address>) -> Self {
+        Self { mode, allowlist }
     }
 }
 
@@ -71,7 +75,7 @@ impl<DB: Database> Segment<DB> for StorageHistory {
             .prune_table_with_range::<tables::StorageChangeSets>(
                 BlockNumberAddress::range(range),
                 &mut limiter,
-                |_| false,
+                |row| self.allowlist.contains(&row.0.address()),
                 |row| last_changeset_pruned_block = Some(row.0.block_number()),
             )?;
         trace!(target: "pruner", deleted = %pruned_changesets, %done, "Pruned storage history (changesets)");
@@ -87,6 +91,7 @@ impl<DB: Database> Segment<DB> for StorageHistory {
             last_changeset_pruned_block,
             |a, b| a.address == b.address && a.sharded_key.key == b.sharded_key.key,
             |key| StorageShardedKey::last(key.address, key.sharded_key.key),
+            |key| self.allowlist.contains(&key.address),
         )?;
         trace!(target: "pruner", %processed, deleted = %pruned_indices, %done, "Pruned storage history (history)");
 
@@ -119,7 +124,10 @@ mod tests {
         generators,
         generators::{random_block_range, random_changeset_range, random_eoa_accounts},
     };
-    use std::{collections::BTreeMap, ops::AddAssign};
+    use std::{
+        collections::{BTreeMap, BTreeSet},
+        ops::AddAssign,
+    };
 
     #[test]
     fn prune() {
@@ -174,7 +182,7 @@ mod tests {
                 to_block,
                 limiter: limiter.clone(),
             };
-            let segment = StorageHistory::new(prune_mode);
+            let segment = StorageHistory::new(prune_mode, BTreeSet::new());
 
             let provider = db.factory.provider_rw().unwrap();
             let result = segment.prune(&provider, input).unwrap();
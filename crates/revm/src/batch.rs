@@ -98,6 +98,12 @@ impl BlockBatchRecord {
 
     /// Returns the [`BundleRetention`] for the given block based on the configured prune modes.
     pub fn bundle_retention(&self, block_number: BlockNumber) -> BundleRetention {
+        // The history of allowlisted addresses is retained, so the reverts are always kept and
+        // the changesets of other addresses are left to the pruner.
+        if !self.prune_modes.history_address_allowlist.is_empty() {
+            return BundleRetention::Reverts
+        }
+
         if self.tip.map_or(true, |tip| {
             !self
                 .prune_modes
@@ -34,8 +34,11 @@ use std::collections::HashMap;
 
 /// Collect all balance changes at the end of the block.
 ///
-/// Balance changes might include the block reward, uncle rewards, withdrawals, or irregular
-/// state changes (DAO fork).
+/// Balance changes might include the block reward, uncle rewards, treasury payouts, withdrawals,
+/// or irregular state changes (DAO fork).
+///
+/// Block, uncle and treasury rewards follow the [`BlockRewards`](reth_chainspec::BlockRewards)
+/// rules of the chain spec.
 #[allow(clippy::too_many_arguments)]
 #[inline]
 pub fn post_block_balance_increments(
@@ -49,20 +52,36 @@ pub fn post_block_balance_increments(
     withdrawals: Option<&[Withdrawal]>,
 ) -> HashMap<Address, u128> {
     let mut balance_increments = HashMap::new();
+    let rules = &chain_spec.block_rewards;
 
     // Add block rewards if they are enabled.
-    if let Some(base_block_reward) =
-        calc::base_block_reward(chain_spec, block_number, block_difficulty, total_difficulty)
-    {
-        // Ommer rewards
-        for ommer in ommers {
-            *balance_increments.entry(ommer.beneficiary).or_default() +=
-                calc::ommer_reward(base_block_reward, block_number, ommer.number);
+    let base_block_reward = match rules.block_reward {
+        Some(block_reward) => (block_reward > 0).then_some(block_reward),
+        None => {
+            calc::base_block_reward(chain_spec, block_number, block_difficulty, total_difficulty)
+        }
+    };
+    if let Some(base_block_reward) = base_block_reward {
+        let recipient = rules.reward_recipient.unwrap_or(beneficiary);
+        if rules.ommer_rewards {
+            // Ommer rewards
+            for ommer in ommers {
+                *balance_increments.entry(ommer.beneficiary).or_default() +=
+                    calc::ommer_reward(base_block_reward, block_number, ommer.number);
+            }
+
+            // Full block reward
+            *balance_increments.entry(recipient).or_default() +=
+                calc::block_reward(base_block_reward, ommers.len());
+        } else {
+            *balance_increments.entry(recipient).or_default() += base_block_reward;
         }
+    }
 
-        // Full block reward
-        *balance_increments.entry(beneficiary).or_default() +=
-            calc::block_reward(base_block_reward, ommers.len());
+    // Treasury payouts
+    for payout in rules.treasury_payouts_at(block_number) {
+        let balance = balance_increments.entry(payout.address).or_default();
+        *balance = balance.saturating_add(payout.amount);
     }
 
     // process withdrawals
@@ -92,4 +92,8 @@ impl StateProvider for StateProviderTest {
     fn proof(&self, _address: Address, _keys: &[B256]) -> ProviderResult<AccountProof> {
         unimplemented!("proof generation is not supported")
     }
+
+    fn witness(&self, _targets: &HashMap<Address, Vec<B256>>) -> ProviderResult<Vec<Bytes>> {
+        unimplemented!("proof generation is not supported")
+    }
 }
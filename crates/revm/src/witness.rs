@@ -0,0 +1,103 @@
+//! Recording of the state that is accessed during execution.
+
+use reth_primitives::{Address, B256, U256};
+use revm::{
+    primitives::{AccountInfo, Bytecode},
+    Database,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+/// All state that was read from a database, e.g. while executing a block.
+///
+/// Only the first read of a value is recorded, so this reflects the state of the database before
+/// any changes were committed on top of it.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct AccessedState {
+    /// The accessed accounts, `None` if the account doesn't exist.
+    pub accounts: BTreeMap<Address, Option<AccountInfo>>,
+    /// The accessed storage slots.
+    pub storage: BTreeMap<Address, BTreeMap<U256, U256>>,
+    /// The bytecode of the accessed contracts, by code hash.
+    pub codes: BTreeMap<B256, Bytecode>,
+    /// The accessed block hashes, by block number.
+    pub block_hashes: BTreeMap<u64, B256>,
+}
+
+impl AccessedState {
+    /// Returns all accessed accounts and their accessed storage slots, e.g. to generate the trie
+    /// nodes that prove the accessed state.
+    pub fn proof_targets(&self) -> impl Iterator<Item = (Address, Vec<B256>)> + '_ {
+        let storage_only =
+            self.storage.keys().filter(|address| !self.accounts.contains_key(address));
+        self.accounts.keys().chain(storage_only).map(|address| {
+            let slots = self
+                .storage
+                .get(address)
+                .map(|storage| storage.keys().map(|slot| B256::from(*slot)).collect())
+                .unwrap_or_default();
+            (*address, slots)
+        })
+    }
+}
+
+/// A [`Database`] that records all state that is read from the inner database in an
+/// [`AccessedState`].
+#[derive(Debug)]
+pub struct RecordingDatabase<DB> {
+    /// The inner database.
+    db: DB,
+    /// The state that was read so far.
+    accessed: AccessedState,
+}
+
+impl<DB> RecordingDatabase<DB> {
+    /// Creates a new recording database on top of the given database.
+    pub fn new(db: DB) -> Self {
+        Self { db, accessed: AccessedState::default() }
+    }
+
+    /// Returns the state that was read so far.
+    pub const fn accessed(&self) -> &AccessedState {
+        &self.accessed
+    }
+
+    /// Consumes the database and returns the state that was read.
+    pub fn into_accessed(self) -> AccessedState {
+        self.accessed
+    }
+}
+
+impl<DB: Database> Database for RecordingDatabase<DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let info = self.db.basic(address)?;
+        if let Some(code) = info.as_ref().and_then(|info| info.code.as_ref()) {
+            self.accessed.codes.entry(code.hash_slow()).or_insert_with(|| code.clone());
+        }
+        self.accessed.accounts.entry(address).or_insert_with(|| info.clone());
+        Ok(info)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        let code = self.db.code_by_hash(code_hash)?;
+        self.accessed.codes.entry(code_hash).or_insert_with(|| code.clone());
+        Ok(code)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        let value = self.db.storage(address, index)?;
+        self.accessed.storage.entry(address).or_default().entry(index).or_insert(value);
+        Ok(value)
+    }
+
+    fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
+        let hash = self.db.block_hash(number)?;
+        self.accessed.block_hashes.insert(number.saturating_to(), hash);
+        Ok(hash)
+    }
+}
@@ -1,4 +1,5 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_chainspec::HardforkUpdate;
 use reth_network_peers::{AnyNode, NodeRecord};
 use reth_rpc_types::{admin::NodeInfo, PeerInfo};
 
@@ -46,3 +47,16 @@ pub trait AdminApi {
     #[method(name = "nodeInfo")]
     async fn node_info(&self) -> RpcResult<NodeInfo>;
 }
+
+/// Admin namespace rpc interface for updating the hardfork schedule of a running node.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "admin"))]
+pub trait AdminChainSpecApi {
+    /// Re-reads the chain specification file of the node and applies the changed hardfork
+    /// activations.
+    ///
+    /// Returns the applied changes, an empty list if nothing changed. Only activations in the
+    /// future of the head of the chain can change, any other change is rejected.
+    #[method(name = "reloadChainSpec")]
+    fn reload_chain_spec(&self) -> RpcResult<Vec<HardforkUpdate>>;
+}
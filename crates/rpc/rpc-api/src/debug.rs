@@ -5,7 +5,7 @@ use reth_rpc_types::{
         BlockTraceResult, GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace,
         TraceResult,
     },
-    Bundle, RichBlock, StateContext, TransactionRequest,
+    Bundle, ExecutionWitness, RichBlock, StateContext, TransactionRequest,
 };
 
 /// Debug rpc interface.
@@ -192,6 +192,15 @@ pub trait DebugApi {
     #[method(name = "dumpBlock")]
     async fn debug_dump_block(&self, number: BlockId) -> RpcResult<()>;
 
+    /// Re-executes a block on top of the state of its parent and returns the witness of the
+    /// execution: the trie nodes of all accessed accounts and storage slots, the accessed
+    /// bytecodes and the ancestor headers whose hashes were accessed.
+    ///
+    /// The witness is sufficient to re-execute the block statelessly.
+    #[method(name = "executionWitness")]
+    async fn debug_execution_witness(&self, block: BlockNumberOrTag)
+        -> RpcResult<ExecutionWitness>;
+
     /// Forces garbage collection.
     #[method(name = "freeOSMemory")]
     async fn debug_free_os_memory(&self) -> RpcResult<()>;
@@ -3,8 +3,8 @@ use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_primitives::{Address, BlockId, BlockNumberOrTag, Bytes, B256, B64, U256, U64};
 use reth_rpc_types::{
     serde_helpers::JsonStorageKey, state::StateOverride, AccessListWithGasUsed,
-    AnyTransactionReceipt, BlockOverrides, Bundle, EIP1186AccountProofResponse, EthCallResponse,
-    FeeHistory, Header, Index, RichBlock, StateContext, SyncStatus, Transaction,
+    AnyTransactionReceipt, BlockBlobSidecar, BlockOverrides, Bundle, EIP1186AccountProofResponse,
+    EthCallResponse, FeeHistory, Header, Index, RichBlock, StateContext, SyncStatus, Transaction,
     TransactionRequest, Work,
 };
 
@@ -77,6 +77,11 @@ pub trait EthApi {
         block_id: BlockId,
     ) -> RpcResult<Option<Vec<AnyTransactionReceipt>>>;
 
+    /// Returns the blob sidecars of all blob transactions in the given block, as far as they are
+    /// still retained by the node.
+    #[method(name = "getBlobSidecars")]
+    async fn blob_sidecars(&self, block_id: BlockId) -> RpcResult<Option<Vec<BlockBlobSidecar>>>;
+
     /// Returns an uncle block of the given block and index.
     #[method(name = "getUncleByBlockHashAndIndex")]
     async fn uncle_by_block_hash_and_index(
@@ -216,12 +221,15 @@ pub trait EthApi {
 
     /// Generates and returns an estimate of how much gas is necessary to allow the transaction to
     /// complete.
+    ///
+    /// The estimate is computed on top of the state and block overrides, like `eth_call`.
     #[method(name = "estimateGas")]
     async fn estimate_gas(
         &self,
         request: TransactionRequest,
         block_number: Option<BlockId>,
         state_override: Option<StateOverride>,
+        block_overrides: Option<Box<BlockOverrides>>,
     ) -> RpcResult<U256>;
 
     /// Returns the current price per gas in wei.
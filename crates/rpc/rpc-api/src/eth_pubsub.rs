@@ -1,10 +1,13 @@
 use jsonrpsee::proc_macros::rpc;
-use reth_rpc_types::pubsub::{Params, SubscriptionKind};
+use reth_rpc_types::{pubsub::SubscriptionKind, EthSubscriptionParams};
 
 /// Ethereum pub-sub rpc interface.
 #[rpc(server, namespace = "eth")]
 pub trait EthPubSubApi {
     /// Create an ethereum subscription for the given params
+    ///
+    /// Besides the standard params, `newPendingTransactions` subscriptions accept an object with
+    /// the `fullTransactions` flag and filters on the recipient and the tip of the transactions.
     #[subscription(
         name = "subscribe" => "subscription",
         unsubscribe = "unsubscribe",
@@ -13,6 +16,6 @@ pub trait EthPubSubApi {
     async fn subscribe(
         &self,
         kind: SubscriptionKind,
-        params: Option<Params>,
+        params: Option<EthSubscriptionParams>,
     ) -> jsonrpsee::core::SubscriptionResult;
 }
@@ -1,5 +1,12 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use reth_primitives::{Address, BlockId, U256};
+use reth_primitives::{
+    storage_layout::{StorageLayout, StorageVariable},
+    AccessList, Address, BlockId, BlockNumberOrTag, B256, U256,
+};
+use reth_rpc_types::{
+    AccountSummary, BlockStateDiff, EthCallResponse, HeaderAccumulator, HistoricalCall,
+    LegacyTxMeta, Reorg, RethSubscriptionKind,
+};
 use std::collections::HashMap;
 
 /// Reth API namespace for reth-specific methods
@@ -12,4 +19,105 @@ pub trait RethApi {
         &self,
         block_id: BlockId,
     ) -> RpcResult<HashMap<Address, U256>>;
+
+    /// Returns the balances, nonces and code hashes of the accounts at the given block, or the
+    /// latest block if not set, in the order of the addresses.
+    ///
+    /// The accounts are read from a single snapshot of the state. At most 10000 accounts can be
+    /// requested at once.
+    #[method(name = "getAccounts")]
+    async fn reth_get_accounts(
+        &self,
+        block_id: Option<BlockId>,
+        addresses: Vec<Address>,
+    ) -> RpcResult<Vec<AccountSummary>>;
+
+    /// Returns the reorgs of the canonical chain recorded in the reorg journal, in the order they
+    /// happened.
+    ///
+    /// Starts at the reorg with the sequence number `from`, or the first one if not set, and
+    /// returns at most `limit` reorgs, or 100 if not set.
+    #[method(name = "getReorgs")]
+    async fn reth_get_reorgs(
+        &self,
+        from: Option<u64>,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<Reorg>>;
+
+    /// Returns the pre-merge header accumulator, with the roots of the epochs that are complete.
+    ///
+    /// Pre-merge headers can be verified against the root of the accumulator with an SSZ proof of
+    /// their epoch.
+    #[method(name = "getHeaderAccumulator")]
+    async fn reth_get_header_accumulator(&self) -> RpcResult<HeaderAccumulator>;
+
+    /// Returns the metadata of a transaction of the OP Mainnet legacy chain, as exported by
+    /// l2geth.
+    ///
+    /// Returns `None` if the transaction is unknown, or its metadata was not imported.
+    #[method(name = "getLegacyTxMeta")]
+    async fn reth_get_legacy_tx_meta(&self, hash: B256) -> RpcResult<Option<LegacyTxMeta>>;
+
+    /// Returns the accounts and storage slots loaded by the transactions of a canonical block, as
+    /// recorded during its execution.
+    ///
+    /// Returns `None` if the block is unknown, or the node did not record the access list of the
+    /// block, see `--execution.access-lists`.
+    #[method(name = "getBlockAccessList")]
+    async fn reth_get_block_access_list(&self, block_id: BlockId) -> RpcResult<Option<AccessList>>;
+
+    /// Returns the account, storage and code changes of the blocks in the given range, in
+    /// increasing block order.
+    ///
+    /// At most 100 blocks can be requested at once.
+    #[method(name = "getStateDiffs")]
+    async fn reth_get_state_diffs(
+        &self,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+    ) -> RpcResult<Vec<BlockStateDiff>>;
+
+    /// Returns the values of the storage variables of a contract at the given block, or the latest
+    /// block if not set, decoded with the storage layout emitted by the Solidity compiler.
+    ///
+    /// Variables are addressed by paths like `balances[0x..]` or `config.owners[2]`. If no paths
+    /// are given, all top-level variables of the layout are returned. At most 100 paths can be
+    /// requested at once.
+    #[method(name = "getStorageSlots")]
+    async fn reth_get_storage_slots(
+        &self,
+        address: Address,
+        layout: StorageLayout,
+        paths: Option<Vec<String>>,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<Vec<StorageVariable>>;
+
+    /// Creates a subscription for reth-specific events.
+    ///
+    /// `stateDiffs` emits the account, storage and code changes of every new canonical block. On
+    /// reorgs, the changes of the new canonical blocks are emitted.
+    #[subscription(
+        name = "subscribe" => "subscription",
+        unsubscribe = "unsubscribe",
+        item = BlockStateDiff
+    )]
+    async fn reth_subscribe(
+        &self,
+        kind: RethSubscriptionKind,
+    ) -> jsonrpsee::core::SubscriptionResult;
+}
+
+/// The `reth_callMany` method of the reth namespace, which executes calls on top of historical
+/// blocks.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait RethCallApi {
+    /// Executes each call on top of the state of its block, like `eth_call`, and returns the
+    /// results in the order of the calls.
+    ///
+    /// Calls are independent of each other, the state changes of a call are not visible to the
+    /// following calls. Calls on the same block share the state of the block, so it is only set up
+    /// once. A failed call is reported in its result instead of failing the whole request.
+    #[method(name = "callMany")]
+    async fn reth_call_many(&self, calls: Vec<HistoricalCall>) -> RpcResult<Vec<EthCallResponse>>;
 }
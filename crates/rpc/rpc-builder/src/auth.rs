@@ -11,10 +11,13 @@ use reth_rpc::EthSubscriptionIdProvider;
 use reth_rpc_api::servers::*;
 use reth_rpc_layer::{
     secret_to_bearer_header, AuthClientLayer, AuthClientService, AuthLayer, JwtAuthValidator,
-    JwtSecret,
+    JwtSecret, JwtSecretDir,
 };
 use reth_rpc_server_types::constants;
-use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    path::PathBuf,
+};
 use tower::layer::util::Identity;
 
 pub use jsonrpsee::server::ServerBuilder;
@@ -27,6 +30,8 @@ pub struct AuthServerConfig {
     pub(crate) socket_addr: SocketAddr,
     /// The secret for the auth layer of the server.
     pub(crate) secret: JwtSecret,
+    /// The directory of additionally accepted secrets, if any.
+    pub(crate) secret_dir: Option<PathBuf>,
     /// Configs for JSON-RPC Http.
     pub(crate) server_config: ServerBuilder<Identity, Identity>,
     /// Configs for IPC server
@@ -50,11 +55,21 @@ impl AuthServerConfig {
 
     /// Convenience function to start a server in one step.
     pub async fn start(self, module: AuthRpcModule) -> Result<AuthServerHandle, RpcError> {
-        let Self { socket_addr, secret, server_config, ipc_server_config, ipc_endpoint } = self;
+        let Self {
+            socket_addr,
+            secret,
+            secret_dir,
+            server_config,
+            ipc_server_config,
+            ipc_endpoint,
+        } = self;
 
         // Create auth middleware.
-        let middleware =
-            tower::ServiceBuilder::new().layer(AuthLayer::new(JwtAuthValidator::new(secret)));
+        let mut validator = JwtAuthValidator::new(secret);
+        if let Some(secret_dir) = secret_dir {
+            validator = validator.with_secret_dir(JwtSecretDir::new(secret_dir));
+        }
+        let middleware = tower::ServiceBuilder::new().layer(AuthLayer::new(validator));
 
         // By default, both http and ws are enabled.
         let server = server_config
@@ -91,6 +106,7 @@ impl AuthServerConfig {
 pub struct AuthServerConfigBuilder {
     socket_addr: Option<SocketAddr>,
     secret: JwtSecret,
+    secret_dir: Option<PathBuf>,
     server_config: Option<ServerBuilder<Identity, Identity>>,
     ipc_server_config: Option<IpcServerBuilder<Identity, Identity>>,
     ipc_endpoint: Option<String>,
@@ -104,6 +120,7 @@ impl AuthServerConfigBuilder {
         Self {
             socket_addr: None,
             secret,
+            secret_dir: None,
             server_config: None,
             ipc_server_config: None,
             ipc_endpoint: None,
@@ -128,6 +145,13 @@ impl AuthServerConfigBuilder {
         self
     }
 
+    /// Set the directory of additionally accepted secrets, which is reloaded when its files
+    /// change.
+    pub fn secret_dir(mut self, secret_dir: PathBuf) -> Self {
+        self.secret_dir = Some(secret_dir);
+        self
+    }
+
     /// Configures the JSON-RPC server
     ///
     /// Note: this always configures an [`EthSubscriptionIdProvider`]
@@ -158,6 +182,7 @@ impl AuthServerConfigBuilder {
                 SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), constants::DEFAULT_AUTH_PORT)
             }),
             secret: self.secret,
+            secret_dir: self.secret_dir,
             server_config: self.server_config.unwrap_or_else(|| {
                 ServerBuilder::new()
                     // This needs to large enough to handle large eth_getLogs responses and maximum
@@ -7,7 +7,7 @@ use reth_node_core::{args::RpcServerArgs, utils::get_or_create_jwt_secret_from_p
 use reth_rpc::eth::{cache::EthStateCacheConfig, gas_oracle::GasPriceOracleConfig};
 use reth_rpc_layer::{JwtError, JwtSecret};
 use reth_rpc_server_types::RpcModuleSelection;
-use std::{net::SocketAddr, path::PathBuf};
+use std::{net::SocketAddr, path::PathBuf, time::Duration};
 use tower::layer::util::Identity;
 use tracing::debug;
 
@@ -91,7 +91,14 @@ impl RethRpcServerConfig for RpcServerArgs {
             .max_tracing_requests(self.rpc_max_tracing_requests)
             .max_blocks_per_filter(self.rpc_max_blocks_per_filter.unwrap_or_max())
             .max_logs_per_response(self.rpc_max_logs_per_response.unwrap_or_max() as usize)
+            .stale_filter_ttl(self.rpc_filter_ttl)
+            .max_filters(self.rpc_max_filters.unwrap_or_max() as usize)
             .rpc_gas_cap(self.rpc_gas_cap)
+            .evm_timeout(self.rpc_evm_timeout)
+            .max_trace_filter_blocks(self.rpc_max_trace_filter_blocks)
+            .max_traces_per_response(self.rpc_max_traces_per_response.unwrap_or_max() as usize)
+            .pruned_logs_upstream(self.rpc_historical_upstream.clone())
+            .blocking_pool_cpus(self.rpc_cpus)
             .state_cache(self.state_cache_config())
             .gpo_config(self.gas_price_oracle_config())
     }
@@ -161,7 +168,9 @@ impl RethRpcServerConfig for RpcServerArgs {
     }
 
     fn rpc_server_config(&self) -> RpcServerConfig {
-        let mut config = RpcServerConfig::default().with_jwt_secret(self.rpc_secret_key());
+        let mut config = RpcServerConfig::default()
+            .with_jwt_secret(self.rpc_secret_key())
+            .with_slow_query_threshold(self.rpc_slow_query_threshold.0.map(Duration::from_millis));
 
         if self.http {
             let socket_address = SocketAddr::new(self.http_addr, self.http_port);
@@ -189,6 +198,9 @@ impl RethRpcServerConfig for RpcServerArgs {
         let address = SocketAddr::new(self.auth_addr, self.auth_port);
 
         let mut builder = AuthServerConfig::builder(jwt_secret).socket_addr(address);
+        if let Some(secret_dir) = &self.auth_jwtsecret_dir {
+            builder = builder.secret_dir(secret_dir.clone());
+        }
         if self.auth_ipc {
             builder = builder
                 .ipc_endpoint(self.auth_ipc_path.clone())
@@ -218,7 +230,10 @@ mod tests {
     use reth_node_core::args::RpcServerArgs;
     use reth_rpc::eth::RPC_DEFAULT_GAS_CAP;
     use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
-    use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+    use std::{
+        net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+        time::Duration,
+    };
 
     use crate::config::RethRpcServerConfig;
 
@@ -244,6 +259,32 @@ mod tests {
         assert!(args.is_err());
     }
 
+    #[test]
+    fn test_rpc_evm_timeout_and_trace_limits() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        let config = args.eth_config();
+        assert_eq!(config.evm_timeout, None);
+        assert_eq!(
+            config.trace_config().max_blocks_per_filter,
+            constants::DEFAULT_MAX_TRACE_FILTER_BLOCKS
+        );
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.evm-timeout",
+            "5s",
+            "--rpc.max-trace-filter-blocks",
+            "10",
+            "--rpc.max-traces-per-response",
+            "1000",
+        ])
+        .args;
+        let config = args.eth_config();
+        assert_eq!(config.evm_timeout, Some(Duration::from_secs(5)));
+        assert_eq!(config.trace_config().max_blocks_per_filter, 10);
+        assert_eq!(config.trace_config().max_traces_per_response, Some(1000));
+    }
+
     #[test]
     fn test_transport_rpc_module_config() {
         let args = CommandParser::<RpcServerArgs>::parse_from([
@@ -361,4 +402,24 @@ mod tests {
         assert_eq!(config.max_blocks_per_filter, Some(100));
         assert_eq!(config.max_logs_per_response, Some(200));
     }
+
+    #[test]
+    fn test_filter_ttl_and_max_filters() {
+        let config = RpcServerArgs::default().eth_config().filter_config();
+        assert_eq!(config.stale_filter_ttl, Duration::from_secs(5 * 60));
+        assert_eq!(config.max_filters, Some(usize::MAX));
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.filter-ttl",
+            "1h",
+            "--rpc.max-filters",
+            "1000",
+        ])
+        .args;
+
+        let config = args.eth_config().filter_config();
+        assert_eq!(config.stale_filter_ttl, Duration::from_secs(60 * 60));
+        assert_eq!(config.max_filters, Some(1000));
+    }
 }
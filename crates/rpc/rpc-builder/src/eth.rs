@@ -13,15 +13,17 @@ use reth_rpc::{
         traits::RawTransactionForwarder,
         EthFilterConfig, FeeHistoryCache, FeeHistoryCacheConfig, RPC_DEFAULT_GAS_CAP,
     },
-    EthApi, EthFilter, EthPubSub,
+    EthApi, EthFilter, EthPubSub, TraceApiConfig,
 };
 use reth_rpc_server_types::constants::{
     default_max_tracing_requests, DEFAULT_MAX_BLOCKS_PER_FILTER, DEFAULT_MAX_LOGS_PER_RESPONSE,
+    DEFAULT_MAX_TRACE_FILTER_BLOCKS,
 };
-use reth_tasks::{pool::BlockingTaskPool, TaskSpawner};
+use reth_tasks::{affinity::CpuSet, pool::BlockingTaskPool, TaskSpawner};
 use reth_transaction_pool::TransactionPool;
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc, time::Duration};
+use tracing::warn;
 
 /// All handlers for the `eth` namespace
 #[derive(Debug, Clone)]
@@ -182,7 +184,19 @@ where
 
     /// Initializes the `BlockingTaskPool`.
     fn init_blocking_task_pool(&self) -> BlockingTaskPool {
-        BlockingTaskPool::build().expect("failed to build tracing pool")
+        let Some(cpus) = self.rpc_config.eth.blocking_pool_cpus else {
+            return BlockingTaskPool::build().expect("failed to build tracing pool")
+        };
+
+        BlockingTaskPool::builder()
+            .start_handler(move |_| {
+                if let Err(err) = cpus.pin_current_thread() {
+                    warn!(target: "rpc", %err, %cpus, "Failed to pin blocking task pool thread");
+                }
+            })
+            .build()
+            .map(BlockingTaskPool::new)
+            .expect("failed to build tracing pool")
     }
 
     /// Initializes the `EthApi`.
@@ -200,6 +214,7 @@ where
             cache.clone(),
             gas_oracle,
             self.rpc_config.eth.rpc_gas_cap,
+            self.rpc_config.eth.evm_timeout,
             Box::new(self.eth_handlers_config.executor.clone()),
             blocking_task_pool.clone(),
             fee_history_cache.clone(),
@@ -248,20 +263,55 @@ pub struct EthConfig {
     ///
     /// Defaults to [`RPC_DEFAULT_GAS_CAP`]
     pub rpc_gas_cap: u64,
+    /// Maximum duration of the EVM execution of `eth_call`, gas estimation and tracing calls.
+    ///
+    /// If `None` then no timeout is enforced.
+    pub evm_timeout: Option<Duration>,
+    /// Maximum number of blocks that can be traced by a single `trace_filter` request.
+    pub max_trace_filter_blocks: u64,
+    /// Maximum number of traces that can be returned in a single response in `trace_filter`
+    /// calls.
+    pub max_traces_per_response: usize,
     ///
     /// Sets TTL for stale filters
     pub stale_filter_ttl: std::time::Duration,
+    /// Maximum number of installed filters.
+    pub max_filters: usize,
+    /// The file the log and block filters are persisted to, if any.
+    pub filters_path: Option<PathBuf>,
     /// Settings for the fee history cache
     pub fee_history_cache: FeeHistoryCacheConfig,
+    /// URL of an archive node that `eth_getLogs` queries for blocks with pruned receipts are
+    /// proxied to, its logs are validated against the local headers.
+    pub pruned_logs_upstream: Option<String>,
+    /// CPUs the threads of the blocking task pool are pinned to.
+    #[serde(skip)]
+    pub blocking_pool_cpus: Option<CpuSet>,
 }
 
 impl EthConfig {
+    /// Returns the config for the `trace` handler.
+    pub fn trace_config(&self) -> TraceApiConfig {
+        TraceApiConfig::default()
+            .max_blocks_per_filter(self.max_trace_filter_blocks)
+            .max_traces_per_response(self.max_traces_per_response)
+    }
+
     /// Returns the filter config for the `eth_filter` handler.
     pub fn filter_config(&self) -> EthFilterConfig {
-        EthFilterConfig::default()
+        let config = EthFilterConfig::default()
             .max_blocks_per_filter(self.max_blocks_per_filter)
             .max_logs_per_response(self.max_logs_per_response)
             .stale_filter_ttl(self.stale_filter_ttl)
+            .max_filters(self.max_filters);
+        let config = match &self.filters_path {
+            Some(path) => config.persistence_path(path.clone()),
+            None => config,
+        };
+        match &self.pruned_logs_upstream {
+            Some(url) => config.pruned_logs_upstream(url.clone()),
+            None => config,
+        }
     }
 }
 
@@ -277,8 +327,15 @@ impl Default for EthConfig {
             max_blocks_per_filter: DEFAULT_MAX_BLOCKS_PER_FILTER,
             max_logs_per_response: DEFAULT_MAX_LOGS_PER_RESPONSE,
             rpc_gas_cap: RPC_DEFAULT_GAS_CAP.into(),
+            evm_timeout: None,
+            max_trace_filter_blocks: DEFAULT_MAX_TRACE_FILTER_BLOCKS,
+            max_traces_per_response: usize::MAX,
             stale_filter_ttl: DEFAULT_STALE_FILTER_TTL,
+            max_filters: usize::MAX,
+            filters_path: None,
             fee_history_cache: FeeHistoryCacheConfig::default(),
+            pruned_logs_upstream: None,
+            blocking_pool_cpus: None,
         }
     }
 }
@@ -319,4 +376,54 @@ impl EthConfig {
         self.rpc_gas_cap = rpc_gas_cap;
         self
     }
+
+    /// Configures the maximum duration of the EVM execution of `eth_call`, gas estimation and
+    /// tracing calls
+    pub const fn evm_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.evm_timeout = timeout;
+        self
+    }
+
+    /// Configures the maximum block range to trace per `trace_filter` request
+    pub const fn max_trace_filter_blocks(mut self, max_blocks: u64) -> Self {
+        self.max_trace_filter_blocks = max_blocks;
+        self
+    }
+
+    /// Configures the maximum number of traces per `trace_filter` response
+    pub const fn max_traces_per_response(mut self, max_traces: usize) -> Self {
+        self.max_traces_per_response = max_traces;
+        self
+    }
+
+    /// Configures how long a filter remains valid after the last poll
+    pub const fn stale_filter_ttl(mut self, ttl: Duration) -> Self {
+        self.stale_filter_ttl = ttl;
+        self
+    }
+
+    /// Configures the maximum number of installed filters
+    pub const fn max_filters(mut self, max_filters: usize) -> Self {
+        self.max_filters = max_filters;
+        self
+    }
+
+    /// Configures the file the log and block filters are persisted to
+    pub fn filters_path(mut self, path: Option<PathBuf>) -> Self {
+        self.filters_path = path;
+        self
+    }
+
+    /// Configures the archive node that `eth_getLogs` queries for blocks with pruned receipts are
+    /// proxied to
+    pub fn pruned_logs_upstream(mut self, url: Option<String>) -> Self {
+        self.pruned_logs_upstream = url;
+        self
+    }
+
+    /// Configures the CPUs the threads of the blocking task pool are pinned to
+    pub const fn blocking_pool_cpus(mut self, cpus: Option<CpuSet>) -> Self {
+        self.blocking_pool_cpus = cpus;
+        self
+    }
 }
@@ -0,0 +1,229 @@
+//! Proxying of calls that need pruned history to an upstream node.
+
+use futures::future::{BoxFuture, Either};
+use jsonrpsee::{
+    core::{
+        client::{ClientT, Error as ClientError},
+        params::ToRpcParams,
+    },
+    server::middleware::rpc::RpcServiceT,
+    types::{error::INTERNAL_ERROR_CODE, ErrorObject, Id, Request, ResponsePayload},
+    MethodResponse,
+};
+use reth_node_core::proxy::{build_http_client, ProxiedHttpClient, Socks5Proxy};
+use reth_primitives::{BlockNumber, B256};
+use reth_provider::BlockHashReader;
+use reth_rpc_server_types::constants::PRUNED_HISTORY_UNAVAILABLE_CODE;
+use serde_json::{value::RawValue, Value};
+use std::{fmt, str::FromStr, sync::Arc};
+use tower::Layer;
+use tracing::{trace, warn};
+
+/// Pairs of the block number and block hash fields of the RPC types that belong to a block, e.g.
+/// blocks, transactions, receipts and logs.
+const BLOCK_FIELDS: [(&str, &str); 2] = [("number", "hash"), ("blockNumber", "blockHash")];
+
+/// A [`RpcServiceT`] middleware layer that proxies calls to an upstream node, if they fail because
+/// the history they need has been pruned locally.
+///
+/// Calls are proxied if they fail with [`PRUNED_HISTORY_UNAVAILABLE_CODE`]. Headers are never
+/// pruned, so the responses of the upstream node are validated against the local chain: all
+/// blocks, transactions, receipts and logs in the response must belong to canonical blocks.
+#[derive(Clone, Default)]
+pub struct HistoricalProxyLayer {
+    inner: Option<Arc<HistoricalProxy>>,
+}
+
+impl HistoricalProxyLayer {
+    /// Creates a new layer that proxies calls to the node at the given HTTP URL, and validates
+    /// its responses against the given chain.
+    ///
+    /// Connections to the upstream node are routed through the given SOCKS5 proxy, if any.
+    pub fn new<Chain>(
+        url: &str,
+        chain: Chain,
+        socks_proxy: Option<&Socks5Proxy>,
+    ) -> Result<Self, ClientError>
+    where
+        Chain: BlockHashReader + 'static,
+    {
+        let client = build_http_client(url, socks_proxy)?;
+        Ok(Self { inner: Some(Arc::new(HistoricalProxy { client, chain: Box::new(chain) })) })
+    }
+
+    /// Creates a layer that doesn't proxy any calls.
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+}
+
+impl fmt::Debug for HistoricalProxyLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HistoricalProxyLayer").field("enabled", &self.inner.is_some()).finish()
+    }
+}
+
+impl<S> Layer<S> for HistoricalProxyLayer {
+    type Service = HistoricalProxyService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        HistoricalProxyService { inner, proxy: self.inner.clone() }
+    }
+}
+
+/// The upstream node and the local chain its responses are validated against.
+struct HistoricalProxy {
+    client: ProxiedHttpClient,
+    chain: Box<dyn BlockHashReader>,
+}
+
+impl HistoricalProxy {
+    /// Proxies the call to the upstream node.
+    ///
+    /// Returns the local response if the upstream node can't be reached.
+    async fn call(
+        &self,
+        id: Id<'static>,
+        method: String,
+        params: Option<Box<RawValue>>,
+        local: MethodResponse,
+    ) -> MethodResponse {
+        trace!(target: "rpc::historical", %method, "proxying call that needs pruned history");
+        let value: Value = match self.client.request(&method, RawParams(params)).await {
+            Ok(value) => value,
+            Err(ClientError::Call(err)) => return MethodResponse::error(id, err),
+            Err(err) => {
+                warn!(target: "rpc::historical", %method, %err, "Failed to proxy call to the upstream node");
+                return local
+            }
+        };
+
+        if let Err(err) = self.validate(&value) {
+            warn!(target: "rpc::historical", %method, %err, "Upstream response doesn't match the local chain");
+            return MethodResponse::error(
+                id,
+                ErrorObject::owned(
+                    INTERNAL_ERROR_CODE,
+                    format!("upstream response doesn't match the local chain: {err}"),
+                    None::<()>,
+                ),
+            )
+        }
+
+        // the size of the response is already limited by the client
+        MethodResponse::response(id, ResponsePayload::success(value), usize::MAX)
+    }
+
+    /// Checks that all blocks the response refers to are canonical blocks of the local chain.
+    fn validate(&self, value: &Value) -> Result<(), String> {
+        match value {
+            Value::Array(values) => values.iter().try_for_each(|value| self.validate(value)),
+            Value::Object(object) => {
+                for (number_field, hash_field) in BLOCK_FIELDS {
+                    let (Some(number), Some(hash)) = (
+                        object.get(number_field).and_then(Value::as_str),
+                        object.get(hash_field).and_then(Value::as_str),
+                    ) else {
+                        continue
+                    };
+                    let number = parse_quantity(number)
+                        .ok_or_else(|| format!("invalid block number {number}"))?;
+                    let hash =
+                        B256::from_str(hash).map_err(|_| format!("invalid block hash {hash}"))?;
+                    let local = self.chain.block_hash(number).map_err(|err| err.to_string())?;
+                    if local != Some(hash) {
+                        return Err(format!("block #{number} {hash} is not canonical"))
+                    }
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A [`RpcServiceT`] middleware that proxies calls that need pruned history to an upstream node.
+#[derive(Clone)]
+pub struct HistoricalProxyService<S> {
+    inner: S,
+    proxy: Option<Arc<HistoricalProxy>>,
+}
+
+impl<S: fmt::Debug> fmt::Debug for HistoricalProxyService<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HistoricalProxyService")
+            .field("inner", &self.inner)
+            .field("enabled", &self.proxy.is_some())
+            .finish()
+    }
+}
+
+impl<'a, S> RpcServiceT<'a> for HistoricalProxyService<S>
+where
+    S: RpcServiceT<'a> + Send + Sync + Clone + 'static,
+{
+    type Future = Either<S::Future, BoxFuture<'a, MethodResponse>>;
+
+    fn call(&self, req: Request<'a>) -> Self::Future {
+        let Some(proxy) = self.proxy.clone() else { return Either::Left(self.inner.call(req)) };
+
+        let id = req.id.clone().into_owned();
+        let method = req.method.to_string();
+        let params = req.params.as_ref().map(|params| params.get().to_owned());
+        let fut = self.inner.call(req);
+        Either::Right(Box::pin(async move {
+            let local = fut.await;
+            if local.as_error_code() != Some(PRUNED_HISTORY_UNAVAILABLE_CODE) {
+                return local
+            }
+            let params = params.and_then(|params| RawValue::from_string(params).ok());
+            proxy.call(id, method, params, local).await
+        }))
+    }
+}
+
+/// The raw parameters of a call that are forwarded as they are.
+struct RawParams(Option<Box<RawValue>>);
+
+impl ToRpcParams for RawParams {
+    fn to_rpc_params(self) -> Result<Option<Box<RawValue>>, serde_json::Error> {
+        Ok(self.0)
+    }
+}
+
+/// Parses a hex encoded quantity, e.g. a block number.
+fn parse_quantity(quantity: &str) -> Option<BlockNumber> {
+    BlockNumber::from_str_radix(quantity.strip_prefix("0x")?, 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_provider::test_utils::NoopProvider;
+    use serde_json::json;
+
+    #[test]
+    fn parse_block_number_quantity() {
+        assert_eq!(parse_quantity("0x0"), Some(0));
+        assert_eq!(parse_quantity("0x1b4"), Some(436));
+        assert_eq!(parse_quantity("1b4"), None);
+        assert_eq!(parse_quantity("0xzz"), None);
+    }
+
+    #[tokio::test]
+    async fn validate_upstream_response() {
+        let proxy = HistoricalProxy {
+            client: build_http_client("http://localhost:8545", None).unwrap(),
+            chain: Box::new(NoopProvider::default()),
+        };
+
+        // responses without blocks are accepted as they are
+        assert!(proxy.validate(&json!("0x1")).is_ok());
+        assert!(proxy.validate(&json!([{ "address": "0x01" }])).is_ok());
+
+        // the noop provider has no canonical blocks
+        let log = json!({ "blockNumber": "0x1", "blockHash": B256::with_last_byte(1) });
+        assert!(proxy.validate(&json!([log])).is_err());
+        assert!(proxy.validate(&json!({ "blockNumber": "1", "blockHash": B256::ZERO })).is_err());
+    }
+}
@@ -20,8 +20,9 @@
 //! use reth_evm::ConfigureEvm;
 //! use reth_network_api::{NetworkInfo, Peers};
 //! use reth_provider::{
-//!     AccountReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider,
-//!     ChangeSetReader, EvmEnvProvider, StateProviderFactory,
+//!     AccountReader, BlockAccessListReader, BlockReaderIdExt, CanonStateSubscriptions,
+//!     ChainSpecProvider, ChangeSetReader, EvmEnvProvider, HeaderAccumulatorReader,
+//!     LegacyTxMetaReader, PruneCheckpointReader, ReorgJournalReader, StateProviderFactory,
 //! };
 //! use reth_rpc_builder::{
 //!     RethRpcModule, RpcModuleBuilder, RpcServerConfig, ServerBuilder, TransportRpcModuleConfig,
@@ -39,6 +40,11 @@
 //!         + BlockReaderIdExt
 //!         + ChainSpecProvider
 //!         + ChangeSetReader
+//!         + ReorgJournalReader
+//!         + HeaderAccumulatorReader
+//!         + LegacyTxMetaReader
+//!         + BlockAccessListReader
+//!         + PruneCheckpointReader
 //!         + StateProviderFactory
 //!         + EvmEnvProvider
 //!         + Clone
@@ -81,8 +87,9 @@
 //! use reth_evm::ConfigureEvm;
 //! use reth_network_api::{NetworkInfo, Peers};
 //! use reth_provider::{
-//!     AccountReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider,
-//!     ChangeSetReader, EvmEnvProvider, StateProviderFactory,
+//!     AccountReader, BlockAccessListReader, BlockReaderIdExt, CanonStateSubscriptions,
+//!     ChainSpecProvider, ChangeSetReader, EvmEnvProvider, HeaderAccumulatorReader,
+//!     LegacyTxMetaReader, PruneCheckpointReader, ReorgJournalReader, StateProviderFactory,
 //! };
 //! use reth_rpc_api::EngineApiServer;
 //! use reth_rpc_builder::{
@@ -105,6 +112,11 @@
 //!         + BlockReaderIdExt
 //!         + ChainSpecProvider
 //!         + ChangeSetReader
+//!         + ReorgJournalReader
+//!         + HeaderAccumulatorReader
+//!         + LegacyTxMetaReader
+//!         + BlockAccessListReader
+//!         + PruneCheckpointReader
 //!         + StateProviderFactory
 //!         + EvmEnvProvider
 //!         + Clone
@@ -174,13 +186,14 @@ use reth_evm::ConfigureEvm;
 use reth_ipc::server::IpcServer;
 use reth_network_api::{noop::NoopNetwork, NetworkInfo, Peers};
 use reth_provider::{
-    AccountReader, BlockReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider,
-    ChangeSetReader, EvmEnvProvider, StateProviderFactory,
+    AccountReader, BlockAccessListReader, BlockReader, BlockReaderIdExt, CanonStateSubscriptions,
+    ChainSpecProvider, ChangeSetReader, EvmEnvProvider, HeaderAccumulatorReader,
+    LegacyTxMetaReader, PruneCheckpointReader, ReorgJournalReader, StateProviderFactory,
 };
 use reth_rpc::{
     eth::{cache::EthStateCache, traits::RawTransactionForwarder, EthBundle},
     AdminApi, DebugApi, EngineEthApi, EthApi, EthSubscriptionIdProvider, NetApi, OtterscanApi,
-    RPCApi, RethApi, TraceApi, TxPoolApi, Web3Api,
+    RPCApi, RethApi, RethCall, TraceApi, TxPoolApi, Web3Api,
 };
 use reth_rpc_api::servers::*;
 use reth_rpc_layer::{AuthLayer, Claims, JwtAuthValidator, JwtSecret};
@@ -221,6 +234,10 @@ pub mod error;
 mod eth;
 pub use eth::{EthConfig, EthHandlers};
 
+/// Proxying of calls that need pruned history
+mod historical;
+pub use historical::{HistoricalProxyLayer, HistoricalProxyService};
+
 // Rpc server metrics
 mod metrics;
 
@@ -243,6 +260,11 @@ where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + ReorgJournalReader
+        + HeaderAccumulatorReader
+        + LegacyTxMetaReader
+        + BlockAccessListReader
+        + PruneCheckpointReader
         + Clone
         + Unpin
         + 'static,
@@ -434,6 +456,11 @@ where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + ReorgJournalReader
+        + HeaderAccumulatorReader
+        + LegacyTxMetaReader
+        + BlockAccessListReader
+        + PruneCheckpointReader
         + Clone
         + Unpin
         + 'static,
@@ -759,6 +786,11 @@ where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + ReorgJournalReader
+        + HeaderAccumulatorReader
+        + LegacyTxMetaReader
+        + BlockAccessListReader
+        + PruneCheckpointReader
         + Clone
         + Unpin
         + 'static,
@@ -855,8 +887,9 @@ where
     ///
     /// If called outside of the tokio runtime.
     pub fn register_reth(&mut self) -> &mut Self {
-        let rethapi = self.reth_api();
-        self.modules.insert(RethRpcModule::Reth, rethapi.into_rpc().into());
+        let mut module = self.reth_api().into_rpc();
+        module.merge(self.reth_call_api().into_rpc()).expect("No conflicts");
+        self.modules.insert(RethRpcModule::Reth, module.into());
         self
     }
 
@@ -947,10 +980,11 @@ where
                         RethRpcModule::Net => {
                             NetApi::new(self.network.clone(), eth_api.clone()).into_rpc().into()
                         }
-                        RethRpcModule::Trace => TraceApi::new(
+                        RethRpcModule::Trace => TraceApi::with_config(
                             self.provider.clone(),
                             eth_api.clone(),
                             self.blocking_pool_guard.clone(),
+                            self.config.eth.trace_config(),
                         )
                         .into_rpc()
                         .into(),
@@ -968,9 +1002,23 @@ where
                         .into(),
                         RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
                         RethRpcModule::Reth => {
-                            RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
-                                .into_rpc()
-                                .into()
+                            let mut module = RethApi::new(
+                                self.provider.clone(),
+                                self.events.clone(),
+                                Box::new(self.executor.clone()),
+                            )
+                            .into_rpc();
+                            module
+                                .merge(
+                                    RethCall::new(
+                                        eth_api.clone(),
+                                        self.blocking_pool_guard.clone(),
+                                    )
+                                    .into_rpc(),
+                                )
+                                .expect("No conflicts");
+
+                            module.into()
                         }
                         RethRpcModule::EthCallBundle => {
                             EthBundle::new(eth_api.clone(), self.blocking_pool_guard.clone())
@@ -1049,7 +1097,12 @@ where
     /// If called outside of the tokio runtime. See also [`Self::eth_api`]
     pub fn trace_api(&mut self) -> TraceApi<Provider, EthApi<Provider, Pool, Network, EvmConfig>> {
         let eth = self.eth_handlers();
-        TraceApi::new(self.provider.clone(), eth.api, self.blocking_pool_guard.clone())
+        TraceApi::with_config(
+            self.provider.clone(),
+            eth.api,
+            self.blocking_pool_guard.clone(),
+            self.config.eth.trace_config(),
+        )
     }
 
     /// Instantiates [`EthBundle`] Api
@@ -1062,6 +1115,16 @@ where
         EthBundle::new(eth_api, self.blocking_pool_guard.clone())
     }
 
+    /// Instantiates [`RethCall`] Api
+    ///
+    /// # Panics
+    ///
+    /// If called outside of the tokio runtime. See also [`Self::eth_api`]
+    pub fn reth_call_api(&mut self) -> RethCall<EthApi<Provider, Pool, Network, EvmConfig>> {
+        let eth_api = self.eth_api();
+        RethCall::new(eth_api, self.blocking_pool_guard.clone())
+    }
+
     /// Instantiates `OtterscanApi`
     ///
     /// # Panics
@@ -1093,8 +1156,8 @@ where
     }
 
     /// Instantiates `RethApi`
-    pub fn reth_api(&self) -> RethApi<Provider> {
-        RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
+    pub fn reth_api(&self) -> RethApi<Provider, Events> {
+        RethApi::new(self.provider.clone(), self.events.clone(), Box::new(self.executor.clone()))
     }
 }
 
@@ -1129,6 +1192,10 @@ pub struct RpcServerConfig {
     ipc_endpoint: Option<String>,
     /// JWT secret for authentication
     jwt_secret: Option<JwtSecret>,
+    /// Duration above which calls are logged as slow queries
+    slow_query_threshold: Option<Duration>,
+    /// Proxies calls that need pruned history to an upstream node
+    historical_proxy: HistoricalProxyLayer,
 }
 
 // === impl RpcServerConfig ===
@@ -1246,6 +1313,18 @@ impl RpcServerConfig {
         self
     }
 
+    /// Configures the duration above which calls are logged as slow queries.
+    pub const fn with_slow_query_threshold(mut self, threshold: Option<Duration>) -> Self {
+        self.slow_query_threshold = threshold;
+        self
+    }
+
+    /// Configures the upstream node that calls which need pruned history are proxied to.
+    pub fn with_historical_proxy(mut self, proxy: HistoricalProxyLayer) -> Self {
+        self.historical_proxy = proxy;
+        self
+    }
+
     /// Returns true if any server is configured.
     ///
     /// If no server is configured, no server will be launched on [`RpcServerConfig::start`].
@@ -1335,14 +1414,17 @@ impl RpcServerConfig {
                         .option_layer(self.maybe_jwt_layer()),
                 )
                 .set_rpc_middleware(
-                    RpcServiceBuilder::new().layer(
-                        modules
-                            .http
-                            .as_ref()
-                            .or(modules.ws.as_ref())
-                            .map(RpcRequestMetrics::same_port)
-                            .unwrap_or_default(),
-                    ),
+                    RpcServiceBuilder::new()
+                        .layer(
+                            modules
+                                .http
+                                .as_ref()
+                                .or(modules.ws.as_ref())
+                                .map(RpcRequestMetrics::same_port)
+                                .unwrap_or_default()
+                                .with_slow_query_threshold(self.slow_query_threshold),
+                        )
+                        .layer(self.historical_proxy.clone()),
                 )
                 .build(http_socket_addr)
                 .await
@@ -1373,7 +1455,15 @@ impl RpcServerConfig {
                 )
                 .set_rpc_middleware(
                     RpcServiceBuilder::new()
-                        .layer(modules.ws.as_ref().map(RpcRequestMetrics::ws).unwrap_or_default()),
+                        .layer(
+                            modules
+                                .ws
+                                .as_ref()
+                                .map(RpcRequestMetrics::ws)
+                                .unwrap_or_default()
+                                .with_slow_query_threshold(self.slow_query_threshold),
+                        )
+                        .layer(self.historical_proxy.clone()),
                 )
                 .build(ws_socket_addr)
                 .await
@@ -1395,9 +1485,16 @@ impl RpcServerConfig {
                         .option_layer(self.maybe_jwt_layer()),
                 )
                 .set_rpc_middleware(
-                    RpcServiceBuilder::new().layer(
-                        modules.http.as_ref().map(RpcRequestMetrics::http).unwrap_or_default(),
-                    ),
+                    RpcServiceBuilder::new()
+                        .layer(
+                            modules
+                                .http
+                                .as_ref()
+                                .map(RpcRequestMetrics::http)
+                                .unwrap_or_default()
+                                .with_slow_query_threshold(self.slow_query_threshold),
+                        )
+                        .layer(self.historical_proxy.clone()),
                 )
                 .build(http_socket_addr)
                 .await
@@ -1428,11 +1525,18 @@ impl RpcServerConfig {
         server.ws_http = self.build_ws_http(modules).await?;
 
         if let Some(builder) = self.ipc_server_config {
-            let metrics = modules.ipc.as_ref().map(RpcRequestMetrics::ipc).unwrap_or_default();
+            let metrics = modules
+                .ipc
+                .as_ref()
+                .map(RpcRequestMetrics::ipc)
+                .unwrap_or_default()
+                .with_slow_query_threshold(self.slow_query_threshold);
             let ipc_path =
                 self.ipc_endpoint.unwrap_or_else(|| constants::DEFAULT_IPC_ENDPOINT.into());
             let ipc = builder
-                .set_rpc_middleware(IpcRpcServiceBuilder::new().layer(metrics))
+                .set_rpc_middleware(
+                    IpcRpcServiceBuilder::new().layer(metrics).layer(self.historical_proxy),
+                )
                 .build(ipc_path);
             server.ipc = Some(ipc);
         }
@@ -1671,7 +1775,7 @@ type WsHttpServerKind = Server<
         tower::util::Either<AuthLayer<JwtAuthValidator>, Identity>,
         Stack<tower::util::Either<CorsLayer, Identity>, Identity>,
     >,
-    Stack<RpcRequestMetrics, Identity>,
+    Stack<HistoricalProxyLayer, Stack<RpcRequestMetrics, Identity>>,
 >;
 
 /// Enum for holding the http and ws servers in all possible combinations.
@@ -1735,7 +1839,9 @@ pub struct RpcServer {
     /// Configured ws,http servers
     ws_http: WsHttpServer,
     /// ipc server
-    ipc: Option<IpcServer<Identity, Stack<RpcRequestMetrics, Identity>>>,
+    ipc: Option<
+        IpcServer<Identity, Stack<HistoricalProxyLayer, Stack<RpcRequestMetrics, Identity>>>,
+    >,
 }
 
 // === impl RpcServer ===
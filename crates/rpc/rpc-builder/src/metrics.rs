@@ -9,34 +9,55 @@ use std::{
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
-    time::Instant,
+    time::{Duration, Instant},
 };
 use tower::Layer;
+use tracing::{debug_span, instrument::Instrumented, warn, Instrument};
+
+/// Maximum number of characters of the parameters logged for a slow query.
+const MAX_SLOW_QUERY_PARAMS_LEN: usize = 256;
+
+/// Prefixes of the methods whose parameters can contain secrets, e.g. account passwords, and are
+/// never logged.
+const REDACTED_PARAMS_METHOD_PREFIXES: [&str; 2] = ["personal_", "admin_"];
 
 /// Metrics for the RPC server.
 ///
 /// Metrics are divided into two categories:
 /// - Connection metrics: metrics for the connection (e.g. number of connections opened, relevant
 ///   for WS and IPC)
-/// - Request metrics: metrics for each RPC method (e.g. number of calls started, time taken to
-///   process a call)
+/// - Request metrics: metrics for each RPC method and transport (e.g. number of calls started,
+///   time taken to process a call)
+///
+/// Calls that take longer than the slow query threshold are logged.
 #[derive(Default, Debug, Clone)]
 pub(crate) struct RpcRequestMetrics {
     inner: Arc<RpcServerMetricsInner>,
+    /// Duration above which calls are logged as slow queries
+    slow_query_threshold: Option<Duration>,
 }
 
 impl RpcRequestMetrics {
     pub(crate) fn new(module: &RpcModule<()>, transport: RpcTransport) -> Self {
         Self {
             inner: Arc::new(RpcServerMetricsInner {
+                transport: Some(transport),
                 connection_metrics: transport.connection_metrics(),
                 call_metrics: HashMap::from_iter(module.method_names().map(|method| {
-                    (method, RpcServerCallMetrics::new_with_labels(&[("method", method)]))
+                    let labels = [("method", method), ("transport", transport.as_str())];
+                    (method, RpcServerCallMetrics::new_with_labels(&labels))
                 })),
             }),
+            slow_query_threshold: None,
         }
     }
 
+    /// Logs calls that take longer than the given threshold as slow queries.
+    pub(crate) const fn with_slow_query_threshold(mut self, threshold: Option<Duration>) -> Self {
+        self.slow_query_threshold = threshold;
+        self
+    }
+
     /// Creates a new instance of the metrics layer for HTTP.
     pub(crate) fn http(module: &RpcModule<()>) -> Self {
         Self::new(module, RpcTransport::Http)
@@ -72,6 +93,8 @@ impl<S> Layer<S> for RpcRequestMetrics {
 /// Metrics for the RPC server
 #[derive(Default, Clone, Debug)]
 struct RpcServerMetricsInner {
+    /// The transport the server is reached over
+    transport: Option<RpcTransport>,
     /// Connection metrics per transport type
     connection_metrics: RpcServerConnectionMetrics,
     /// Call metrics per RPC method
@@ -99,7 +122,7 @@ impl<'a, S> RpcServiceT<'a> for RpcRequestMetricsService<S>
 where
     S: RpcServiceT<'a> + Send + Sync + Clone + 'static,
 {
-    type Future = MeteredRequestFuture<S::Future>;
+    type Future = MeteredRequestFuture<Instrumented<S::Future>>;
 
     fn call(&self, req: Request<'a>) -> Self::Future {
         self.metrics.inner.connection_metrics.requests_started_total.increment(1);
@@ -107,11 +130,22 @@ where
         if let Some((_, call_metrics)) = &call_metrics {
             call_metrics.started_total.increment(1);
         }
+        let slow_query = self.metrics.slow_query_threshold.map(|_| SlowQuery {
+            method: req.method.to_string(),
+            params: sanitize_params(&req.method, req.params.as_ref().map(|params| params.get())),
+        });
+        let span = debug_span!(
+            target: "rpc::server",
+            "rpc_call",
+            method = %req.method,
+            transport = self.metrics.inner.transport.map(|transport| transport.as_str()),
+        );
         MeteredRequestFuture {
-            fut: self.inner.call(req),
+            fut: self.inner.call(req).instrument(span),
             started_at: Instant::now(),
             metrics: self.metrics.clone(),
             method: call_metrics.map(|(method, _)| *method),
+            slow_query,
         }
     }
 }
@@ -134,6 +168,16 @@ pub(crate) struct MeteredRequestFuture<F> {
     metrics: RpcRequestMetrics,
     /// the method name if known
     method: Option<&'static str>,
+    /// the call details logged if the call is slow, if slow queries are logged
+    slow_query: Option<SlowQuery>,
+}
+
+/// The details of a call that are logged if it's slow.
+#[derive(Debug)]
+struct SlowQuery {
+    method: String,
+    /// the sanitized parameters of the call
+    params: String,
 }
 
 impl<F> std::fmt::Debug for MeteredRequestFuture<F> {
@@ -167,11 +211,48 @@ impl<F: Future<Output = MethodResponse>> Future for MeteredRequestFuture<F> {
                     call_metrics.failed_total.increment(1);
                 }
             }
+
+            if let (Some(threshold), Some(query)) =
+                (this.metrics.slow_query_threshold, this.slow_query.as_ref())
+            {
+                let elapsed = this.started_at.elapsed();
+                if elapsed >= threshold {
+                    warn!(
+                        target: "rpc::server",
+                        method = %query.method,
+                        transport = this.metrics.inner.transport.map(|transport| transport.as_str()),
+                        ?elapsed,
+                        success = resp.is_success(),
+                        params = %query.params,
+                        "Slow RPC call"
+                    );
+                }
+            }
         }
         res
     }
 }
 
+/// Returns the parameters of a call in a form that can be logged.
+///
+/// The parameters of methods that can contain secrets are redacted, and long parameters are
+/// truncated.
+fn sanitize_params(method: &str, params: Option<&str>) -> String {
+    let Some(params) = params else { return String::new() };
+    if REDACTED_PARAMS_METHOD_PREFIXES.iter().any(|prefix| method.starts_with(prefix)) {
+        return "<redacted>".to_string()
+    }
+    if params.len() <= MAX_SLOW_QUERY_PARAMS_LEN {
+        return params.to_string()
+    }
+
+    let mut end = MAX_SLOW_QUERY_PARAMS_LEN;
+    while !params.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}... ({} bytes)", &params[..end], params.len())
+}
+
 /// The transport protocol used for the RPC connection.
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub(crate) enum RpcTransport {
@@ -226,3 +307,26 @@ struct RpcServerCallMetrics {
     /// Response for a single call
     time_seconds: Histogram,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_slow_query_params() {
+        assert_eq!(sanitize_params("eth_blockNumber", None), "");
+        assert_eq!(
+            sanitize_params("eth_getBalance", Some(r#"["0x00","latest"]"#)),
+            r#"["0x00","latest"]"#
+        );
+        assert_eq!(
+            sanitize_params("personal_unlockAccount", Some(r#"["0x00", "password"]"#)),
+            "<redacted>"
+        );
+
+        let long = format!("[\"{}\"]", "a".repeat(1000));
+        let sanitized = sanitize_params("eth_call", Some(&long));
+        assert!(sanitized.starts_with(&long[..MAX_SLOW_QUERY_PARAMS_LEN]));
+        assert!(sanitized.ends_with("... (1004 bytes)"));
+    }
+}
@@ -12,18 +12,25 @@ use reth_payload_primitives::{
 };
 use reth_primitives::{BlockHash, BlockHashOrNumber, BlockNumber, Hardfork, B256, U64};
 use reth_rpc_api::EngineApiServer;
-use reth_rpc_types::engine::{
-    CancunPayloadFields, ClientVersionV1, ExecutionPayload, ExecutionPayloadBodiesV1,
-    ExecutionPayloadInputV2, ExecutionPayloadV1, ExecutionPayloadV3, ExecutionPayloadV4,
-    ForkchoiceState, ForkchoiceUpdated, PayloadId, PayloadStatus, TransitionConfiguration,
-    CAPABILITIES,
+use reth_rpc_types::{
+    engine::{
+        CancunPayloadFields, ClientVersionV1, ExecutionPayload, ExecutionPayloadBodiesV1,
+        ExecutionPayloadInputV2, ExecutionPayloadV1, ExecutionPayloadV3, ExecutionPayloadV4,
+        ForkchoiceState, ForkchoiceUpdated, PayloadId, PayloadStatus, TransitionConfiguration,
+        CAPABILITIES,
+    },
+    BlobAndProofV1,
 };
 use reth_rpc_types_compat::engine::payload::{
     convert_payload_input_v2_to_payload, convert_to_payload_body_v1,
 };
 use reth_storage_api::{BlockReader, HeaderProvider, StateProviderFactory};
 use reth_tasks::TaskSpawner;
-use std::{sync::Arc, time::Instant};
+use reth_transaction_pool::{BlobAndProof, TransactionPool};
+use std::{
+    sync::{Arc, Mutex},
+    time::Instant,
+};
 use tokio::sync::oneshot;
 use tracing::{trace, warn};
 
@@ -33,13 +40,19 @@ pub type EngineApiSender<Ok> = oneshot::Sender<EngineApiResult<Ok>>;
 /// The upper limit for payload bodies request.
 const MAX_PAYLOAD_BODIES_LIMIT: u64 = 1024;
 
+/// The upper limit for blobs request.
+const MAX_BLOB_LIMIT: u64 = 128;
+
+/// The supported methods that are not part of [`CAPABILITIES`] yet.
+const ADDITIONAL_CAPABILITIES: &[&str] = &["engine_getBlobsV1"];
+
 /// The Engine API implementation that grants the Consensus layer access to data and
 /// functions in the Execution layer that are crucial for the consensus process.
-pub struct EngineApi<Provider, EngineT: EngineTypes> {
-    inner: Arc<EngineApiInner<Provider, EngineT>>,
+pub struct EngineApi<Provider, EngineT: EngineTypes, Pool> {
+    inner: Arc<EngineApiInner<Provider, EngineT, Pool>>,
 }
 
-struct EngineApiInner<Provider, EngineT: EngineTypes> {
+struct EngineApiInner<Provider, EngineT: EngineTypes, Pool> {
     /// The provider to interact with the chain.
     provider: Provider,
     /// Consensus configuration
@@ -54,12 +67,17 @@ struct EngineApiInner<Provider, EngineT: EngineTypes> {
     metrics: EngineApiMetrics,
     /// Identification of the execution client used by the consensus client
     client: ClientVersionV1,
+    /// The transaction pool, to serve blobs from.
+    tx_pool: Pool,
+    /// The methods last reported by the consensus client that this node doesn't support.
+    unsupported_capabilities: Mutex<Vec<String>>,
 }
 
-impl<Provider, EngineT> EngineApi<Provider, EngineT>
+impl<Provider, EngineT, Pool> EngineApi<Provider, EngineT, Pool>
 where
     Provider: HeaderProvider + BlockReader + StateProviderFactory + EvmEnvProvider + 'static,
     EngineT: EngineTypes + 'static,
+    Pool: TransactionPool + 'static,
 {
     /// Create new instance of [`EngineApi`].
     pub fn new(
@@ -69,6 +87,7 @@ where
         payload_store: PayloadStore<EngineT>,
         task_spawner: Box<dyn TaskSpawner>,
         client: ClientVersionV1,
+        tx_pool: Pool,
     ) -> Self {
         let inner = Arc::new(EngineApiInner {
             provider,
@@ -78,6 +97,8 @@ where
             task_spawner,
             metrics: EngineApiMetrics::default(),
             client,
+            tx_pool,
+            unsupported_capabilities: Default::default(),
         });
         Self { inner }
     }
@@ -442,6 +463,64 @@ where
         Ok(result)
     }
 
+    /// Called to exchange the supported methods with the consensus client.
+    ///
+    /// Returns the methods supported by this node. The methods the consensus client supports but
+    /// this node doesn't are logged whenever they change, since the consensus client falls back to
+    /// older versions of them.
+    pub fn exchange_capabilities(&self, capabilities: Vec<String>) -> Vec<String> {
+        let supported: Vec<String> = CAPABILITIES
+            .iter()
+            .chain(ADDITIONAL_CAPABILITIES)
+            .copied()
+            .map(str::to_owned)
+            .collect();
+
+        let unsupported: Vec<String> =
+            capabilities.into_iter().filter(|method| !supported.contains(method)).collect();
+        let mut last_unsupported =
+            self.inner.unsupported_capabilities.lock().unwrap_or_else(|err| err.into_inner());
+        if *last_unsupported != unsupported {
+            if !unsupported.is_empty() {
+                warn!(target: "rpc::engine",
+                    ?unsupported,
+                    "Consensus client supports engine API methods this node doesn't"
+                );
+            }
+            *last_unsupported = unsupported;
+        }
+
+        supported
+    }
+
+    /// Called to retrieve the blobs and their proofs for the given versioned hashes from the
+    /// transaction pool.
+    pub fn get_blobs_v1(
+        &self,
+        versioned_hashes: Vec<B256>,
+    ) -> EngineApiResult<Vec<Option<BlobAndProofV1>>> {
+        let len = versioned_hashes.len() as u64;
+        if len > MAX_BLOB_LIMIT {
+            return Err(EngineApiError::PayloadRequestTooLarge { len })
+        }
+
+        let blobs = self
+            .inner
+            .tx_pool
+            .get_blobs_for_versioned_hashes(&versioned_hashes)
+            .map_err(|err| EngineApiError::Internal(Box::new(err)))?;
+
+        Ok(blobs
+            .into_iter()
+            .map(|blob| {
+                blob.map(|BlobAndProof { blob, proof }| BlobAndProofV1 {
+                    blob: Box::new(blob),
+                    proof,
+                })
+            })
+            .collect())
+    }
+
     /// Called to verify network configuration parameters and ensure that Consensus and Execution
     /// layers are using the latest configuration.
     pub async fn exchange_transition_configuration(
@@ -552,10 +631,11 @@ where
 }
 
 #[async_trait]
-impl<Provider, EngineT> EngineApiServer<EngineT> for EngineApi<Provider, EngineT>
+impl<Provider, EngineT, Pool> EngineApiServer<EngineT> for EngineApi<Provider, EngineT, Pool>
 where
     Provider: HeaderProvider + BlockReader + StateProviderFactory + EvmEnvProvider + 'static,
     EngineT: EngineTypes + 'static,
+    Pool: TransactionPool + 'static,
 {
     /// Handler for `engine_newPayloadV1`
     /// See also <https://github.com/ethereum/execution-apis/blob/3d627c95a4d3510a8187dd02e0250ecb4331d27e/src/engine/paris.md#engine_newpayloadv1>
@@ -819,14 +899,31 @@ where
         Ok(res?)
     }
 
+    /// Handler for `engine_getBlobsV1`
+    /// See also <https://github.com/ethereum/execution-apis/blob/main/src/engine/cancun.md#engine_getblobsv1>
+    async fn get_blobs_v1(
+        &self,
+        versioned_hashes: Vec<B256>,
+    ) -> RpcResult<Vec<Option<BlobAndProofV1>>> {
+        trace!(target: "rpc::engine", "Serving engine_getBlobsV1");
+        let start = Instant::now();
+        let res = Self::get_blobs_v1(self, versioned_hashes);
+        self.inner.metrics.latency.get_blobs_v1.record(start.elapsed());
+        Ok(res?)
+    }
+
     /// Handler for `engine_exchangeCapabilitiesV1`
     /// See also <https://github.com/ethereum/execution-apis/blob/6452a6b194d7db269bf1dbd087a267251d3cc7f8/src/engine/common.md#capabilities>
-    async fn exchange_capabilities(&self, _capabilities: Vec<String>) -> RpcResult<Vec<String>> {
-        Ok(CAPABILITIES.iter().cloned().map(str::to_owned).collect())
+    async fn exchange_capabilities(&self, capabilities: Vec<String>) -> RpcResult<Vec<String>> {
+        trace!(target: "rpc::engine", "Serving engine_exchangeCapabilities");
+        let start = Instant::now();
+        let res = Self::exchange_capabilities(self, capabilities);
+        self.inner.metrics.latency.exchange_capabilities.record(start.elapsed());
+        Ok(res)
     }
 }
 
-impl<Provider, EngineT> std::fmt::Debug for EngineApi<Provider, EngineT>
+impl<Provider, EngineT, Pool> std::fmt::Debug for EngineApi<Provider, EngineT, Pool>
 where
     EngineT: EngineTypes,
 {
@@ -851,9 +948,11 @@ mod tests {
     use reth_rpc_types_compat::engine::payload::execution_payload_from_sealed_block;
     use reth_tasks::TokioTaskExecutor;
     use reth_tokio_util::EventSender;
+    use reth_transaction_pool::noop::NoopTransactionPool;
     use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
 
-    fn setup_engine_api() -> (EngineApiTestHandle, EngineApi<Arc<MockEthProvider>, EthEngineTypes>)
+    fn setup_engine_api(
+    ) -> (EngineApiTestHandle, EngineApi<Arc<MockEthProvider>, EthEngineTypes, NoopTransactionPool>)
     {
         let client = ClientVersionV1 {
             code: ClientCode::RH,
@@ -875,6 +974,7 @@ mod tests {
             payload_store.into(),
             task_executor,
             client,
+            NoopTransactionPool::default(),
         );
         let handle = EngineApiTestHandle { chain_spec, provider, from_api: engine_rx };
         (handle, api)
@@ -893,6 +993,33 @@ mod tests {
         assert_eq!(res.unwrap(), vec![client]);
     }
 
+    #[tokio::test]
+    async fn exchange_capabilities() {
+        let (_, api) = setup_engine_api();
+        let capabilities = vec![
+            "engine_newPayloadV4".to_string(),
+            "engine_getPayloadV4".to_string(),
+            "engine_unknownMethodV1".to_string(),
+        ];
+
+        let supported = api.exchange_capabilities(capabilities);
+        for method in [
+            "engine_newPayloadV3",
+            "engine_newPayloadV4",
+            "engine_getPayloadV3",
+            "engine_getPayloadV4",
+            "engine_getPayloadBodiesByHashV1",
+            "engine_getPayloadBodiesByRangeV1",
+            "engine_getBlobsV1",
+        ] {
+            assert!(supported.contains(&method.to_string()), "{method} not supported");
+        }
+        assert_eq!(
+            *api.inner.unsupported_capabilities.lock().unwrap(),
+            vec!["engine_unknownMethodV1".to_string()]
+        );
+    }
+
     struct EngineApiTestHandle {
         chain_spec: Arc<ChainSpec>,
         provider: Arc<MockEthProvider>,
@@ -1025,6 +1152,27 @@ mod tests {
         }
     }
 
+    mod get_blobs {
+        use super::*;
+
+        #[tokio::test]
+        async fn request_too_large() {
+            let (_, api) = setup_engine_api();
+
+            let hashes = vec![B256::ZERO; MAX_BLOB_LIMIT as usize + 1];
+            let res = api.get_blobs_v1(hashes);
+            assert_matches!(res, Err(EngineApiError::PayloadRequestTooLarge { .. }));
+        }
+
+        #[tokio::test]
+        async fn returns_none_for_unknown_blobs() {
+            let (_, api) = setup_engine_api();
+
+            let res = api.get_blobs_v1(vec![B256::ZERO, B256::random()]).unwrap();
+            assert_eq!(res, vec![None, None]);
+        }
+    }
+
     // https://github.com/ethereum/execution-apis/blob/main/src/engine/paris.md#specification-3
     mod exchange_transition_configuration {
         use super::*;
@@ -48,6 +48,10 @@ pub(crate) struct EngineApiLatencyMetrics {
     pub(crate) get_payload_bodies_by_hash_v1: Histogram,
     /// Latency for `engine_exchangeTransitionConfigurationV1`
     pub(crate) exchange_transition_configuration: Histogram,
+    /// Latency for `engine_getBlobsV1`
+    pub(crate) get_blobs_v1: Histogram,
+    /// Latency for `engine_exchangeCapabilities`
+    pub(crate) exchange_capabilities: Histogram,
 }
 
 /// Metrics for engine API forkchoiceUpdated responses.
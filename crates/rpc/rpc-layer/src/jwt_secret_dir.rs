@@ -0,0 +1,141 @@
+use crate::JwtSecret;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime},
+};
+use tracing::{info, warn};
+
+/// The minimum time between two checks of the directory for changes.
+const RELOAD_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A directory of additionally accepted JWT secrets, one hex encoded secret per `.hex` file.
+///
+/// The directory is checked for added, removed or modified files at most once every
+/// [`RELOAD_INTERVAL`], when the secrets are requested, so that the secret shared with the
+/// consensus client can be rotated without restarting the node.
+#[derive(Clone, Debug)]
+pub struct JwtSecretDir {
+    path: PathBuf,
+    state: Arc<Mutex<SecretDirState>>,
+}
+
+#[derive(Debug, Default)]
+struct SecretDirState {
+    /// When the directory was last checked for changes.
+    checked_at: Option<Instant>,
+    /// The files the secrets were loaded from, with their modification times.
+    files: Vec<(PathBuf, SystemTime)>,
+    /// The loaded secrets.
+    secrets: Vec<JwtSecret>,
+}
+
+impl JwtSecretDir {
+    /// Creates a new [`JwtSecretDir`] and loads the secrets of the directory.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let dir = Self { path: path.into(), state: Default::default() };
+        dir.secrets();
+        dir
+    }
+
+    /// Returns the path of the directory.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the secrets of the directory, reloading them if the files changed.
+    pub fn secrets(&self) -> Vec<JwtSecret> {
+        let mut state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+        if state.checked_at.map_or(true, |checked_at| checked_at.elapsed() >= RELOAD_INTERVAL) {
+            state.checked_at = Some(Instant::now());
+            match secret_files(&self.path) {
+                Ok(files) if files != state.files => {
+                    state.secrets = load_secrets(&files);
+                    state.files = files;
+                    info!(target: "engine::jwt-validator",
+                        dir = %self.path.display(),
+                        secrets = state.secrets.len(),
+                        "Loaded JWT secrets"
+                    );
+                }
+                Ok(_) => {}
+                Err(err) => {
+                    warn!(target: "engine::jwt-validator",
+                        %err,
+                        dir = %self.path.display(),
+                        "Failed to read JWT secret directory"
+                    );
+                }
+            }
+        }
+        state.secrets.clone()
+    }
+}
+
+/// Returns the `.hex` files of the directory with their modification times, sorted by path.
+fn secret_files(dir: &Path) -> std::io::Result<Vec<(PathBuf, SystemTime)>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.extension().map_or(true, |ext| ext != "hex") {
+            continue
+        }
+        let metadata = entry.metadata()?;
+        if metadata.is_file() {
+            files.push((path, metadata.modified()?));
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Loads the secrets of the files, skipping the files that don't contain a valid secret.
+fn load_secrets(files: &[(PathBuf, SystemTime)]) -> Vec<JwtSecret> {
+    files
+        .iter()
+        .filter_map(|(path, _)| match JwtSecret::from_file(path) {
+            Ok(secret) => Some(secret),
+            Err(err) => {
+                warn!(target: "engine::jwt-validator",
+                    %err,
+                    path = %path.display(),
+                    "Skipping invalid JWT secret"
+                );
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "f79ae8046bc11c9927afe911db7143c51a806c4a537cc08e0d37140b0192f430";
+    const ROTATED: &str = "0x7365637265747365637265747365637265747365637265747365637265747365";
+
+    #[test]
+    fn reloads_changed_secrets() {
+        let dir = tempfile::tempdir().unwrap();
+        let (secret, rotated) =
+            (JwtSecret::from_hex(SECRET).unwrap(), JwtSecret::from_hex(ROTATED).unwrap());
+        fs::write(dir.path().join("a.hex"), SECRET).unwrap();
+        fs::write(dir.path().join("ignored.txt"), ROTATED).unwrap();
+
+        let secret_dir = JwtSecretDir::new(dir.path());
+        assert_eq!(secret_dir.secrets(), vec![secret]);
+
+        fs::write(dir.path().join("b.hex"), ROTATED).unwrap();
+        // the directory isn't checked again within the reload interval
+        assert_eq!(secret_dir.secrets(), vec![secret]);
+
+        secret_dir.state.lock().unwrap().checked_at = None;
+        assert_eq!(secret_dir.secrets(), vec![secret, rotated]);
+
+        fs::remove_file(dir.path().join("a.hex")).unwrap();
+        secret_dir.state.lock().unwrap().checked_at = None;
+        assert_eq!(secret_dir.secrets(), vec![rotated]);
+    }
+}
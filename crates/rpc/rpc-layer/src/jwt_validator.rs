@@ -1,4 +1,4 @@
-use crate::{AuthValidator, JwtError, JwtSecret};
+use crate::{AuthValidator, JwtError, JwtSecret, JwtSecretDir};
 use http::{header, HeaderMap, Response, StatusCode};
 use jsonrpsee_http_client::{HttpBody, HttpResponse};
 use tracing::error;
@@ -10,6 +10,8 @@ use tracing::error;
 #[allow(missing_debug_implementations)]
 pub struct JwtAuthValidator {
     secret: JwtSecret,
+    /// Additionally accepted secrets, to rotate the secret without downtime.
+    secret_dir: Option<JwtSecretDir>,
 }
 
 impl JwtAuthValidator {
@@ -17,14 +19,35 @@ impl JwtAuthValidator {
     /// Validation logics are implemented by the `secret`
     /// argument (see [`JwtSecret`]).
     pub const fn new(secret: JwtSecret) -> Self {
-        Self { secret }
+        Self { secret, secret_dir: None }
+    }
+
+    /// Additionally accepts the tokens signed with any of the secrets of the directory.
+    pub fn with_secret_dir(mut self, secret_dir: JwtSecretDir) -> Self {
+        self.secret_dir = Some(secret_dir);
+        self
+    }
+
+    /// Validates the token against the secret, falling back to the secrets of the directory.
+    ///
+    /// Returns the error of the primary secret if no secret accepts the token.
+    fn validate_token(&self, jwt: &str) -> Result<(), JwtError> {
+        let err = match self.secret.validate(jwt) {
+            Ok(()) => return Ok(()),
+            Err(err) => err,
+        };
+        let Some(secret_dir) = &self.secret_dir else { return Err(err) };
+        if secret_dir.secrets().iter().any(|secret| secret.validate(jwt).is_ok()) {
+            return Ok(())
+        }
+        Err(err)
     }
 }
 
 impl AuthValidator for JwtAuthValidator {
     fn validate(&self, headers: &HeaderMap) -> Result<(), HttpResponse> {
         match get_bearer(headers) {
-            Some(jwt) => match self.secret.validate(&jwt) {
+            Some(jwt) => match self.validate_token(&jwt) {
                 Ok(_) => Ok(()),
                 Err(e) => {
                     error!(target: "engine::jwt-validator", "Invalid JWT: {e}");
@@ -65,8 +88,29 @@ fn err_response(err: JwtError) -> HttpResponse {
 
 #[cfg(test)]
 mod tests {
-    use crate::jwt_validator::get_bearer;
+    use crate::{jwt_validator::get_bearer, Claims, JwtAuthValidator, JwtSecret, JwtSecretDir};
     use http::{header, HeaderMap};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn accepts_secrets_of_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let rotated_hex = "7365637265747365637265747365637265747365637265747365637265747365";
+        std::fs::write(dir.path().join("rotated.hex"), rotated_hex).unwrap();
+        let secret = JwtSecret::random();
+        let rotated = JwtSecret::from_hex(rotated_hex).unwrap();
+
+        let iat = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        let claims = Claims { iat, exp: None };
+        let token = rotated.encode(&claims).unwrap();
+
+        let validator = JwtAuthValidator::new(secret);
+        assert!(validator.validate_token(&token).is_err());
+        let validator = validator.with_secret_dir(JwtSecretDir::new(dir.path()));
+        assert!(validator.validate_token(&token).is_ok());
+        assert!(validator.validate_token(&secret.encode(&claims).unwrap()).is_ok());
+        assert!(validator.validate_token(&JwtSecret::random().encode(&claims).unwrap()).is_err());
+    }
 
     #[test]
     fn auth_header_available() {
@@ -13,6 +13,7 @@ use jsonrpsee_http_client::HttpResponse;
 
 mod auth_client_layer;
 mod auth_layer;
+mod jwt_secret_dir;
 mod jwt_validator;
 
 // Export alloy JWT types
@@ -20,6 +21,7 @@ pub use alloy_rpc_types_engine::{Claims, JwtError, JwtSecret};
 
 pub use auth_client_layer::{secret_to_bearer_header, AuthClientLayer, AuthClientService};
 pub use auth_layer::AuthLayer;
+pub use jwt_secret_dir::JwtSecretDir;
 pub use jwt_validator::JwtAuthValidator;
 
 /// General purpose trait to validate Http Authorization headers. It's supposed to be integrated as
@@ -64,8 +64,8 @@ fn fill(
             (Some(gas_price), Some(signed_tx.max_fee_per_gas()))
         }
         _ => {
-            // OP-deposit
-            (None, None)
+            // OP-deposit, the gas is bought on L1
+            (Some(0), None)
         }
     };
 
@@ -105,7 +105,8 @@ fn fill(
         other: reth_rpc_types::optimism::OptimismTransactionFields {
             source_hash: signed_tx.source_hash(),
             mint: signed_tx.mint().map(reth_primitives::U128::from),
-            is_system_tx: signed_tx.is_deposit().then_some(signed_tx.is_system_transaction()),
+            // only set for system transactions, like in op-geth
+            is_system_tx: signed_tx.is_system_transaction().then_some(true),
         }
         .into(),
         #[cfg(not(feature = "optimism"))]
@@ -153,3 +154,44 @@ pub fn transaction_to_call_request(tx: TransactionSignedEcRecovered) -> Transact
         sidecar: None,
     }
 }
+
+#[cfg(all(test, feature = "optimism"))]
+mod tests {
+    use super::*;
+    use reth_primitives::{Signature, TransactionSigned, TxDeposit};
+
+    fn deposit(is_system_transaction: bool) -> serde_json::Value {
+        let from = Address::with_last_byte(1);
+        let tx = TxDeposit {
+            source_hash: B256::with_last_byte(3),
+            from,
+            to: TxKind::Call(Address::with_last_byte(2)),
+            mint: Some(100),
+            gas_limit: 21_000,
+            is_system_transaction,
+            ..Default::default()
+        };
+        let signed = TransactionSigned::from_transaction_and_signature(
+            reth_primitives::Transaction::Deposit(tx),
+            Signature::optimism_deposit_tx_signature(),
+        );
+        let tx = TransactionSignedEcRecovered::from_signed_transaction(signed, from);
+        serde_json::to_value(from_recovered_with_block_context(tx, B256::ZERO, 1, Some(7), 0))
+            .unwrap()
+    }
+
+    #[test]
+    fn serialize_deposit_like_op_geth() {
+        let tx = deposit(false);
+        assert_eq!(tx["v"], "0x0");
+        assert_eq!(tx["r"], "0x0");
+        assert_eq!(tx["s"], "0x0");
+        assert!(tx.get("yParity").is_none());
+        assert_eq!(tx["gasPrice"], "0x0");
+        assert!(tx.get("maxFeePerGas").is_none());
+        assert!(tx.get("isSystemTx").is_none());
+
+        let tx = deposit(true);
+        assert_eq!(tx["isSystemTx"], true);
+    }
+}
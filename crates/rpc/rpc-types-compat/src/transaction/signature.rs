@@ -38,7 +38,9 @@ pub(crate) fn from_typed_primitive_signature(signature: PrimitiveSignature) -> S
 ///
 /// If the transaction is a legacy transaction, it will use the `chain_id` to compute the
 /// signature's recovery id. If the transaction is a typed transaction, it will set the `v`
-/// value to `0` or `1` depending on the signature's `odd_y_parity`.
+/// value to `0` or `1` depending on the signature's `odd_y_parity`. Optimism deposit
+/// transactions are not signed, so their `v`, `r` and `s` values are zero and they have no
+/// `yParity`, like in op-geth.
 pub(crate) fn from_primitive_signature(
     signature: PrimitiveSignature,
     tx_type: TxType,
@@ -46,6 +48,32 @@ pub(crate) fn from_primitive_signature(
 ) -> Signature {
     match tx_type {
         TxType::Legacy => from_legacy_primitive_signature(signature, chain_id),
+        #[cfg(feature = "optimism")]
+        TxType::Deposit => {
+            Signature { r: U256::ZERO, s: U256::ZERO, v: U256::ZERO, y_parity: None }
+        }
         _ => from_typed_primitive_signature(signature),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typed_signature_has_y_parity() {
+        let signature =
+            PrimitiveSignature { r: U256::from(1), s: U256::from(2), odd_y_parity: true };
+        let rpc = from_primitive_signature(signature, TxType::Eip1559, Some(1));
+        assert_eq!(rpc.v, U256::from(1));
+        assert_eq!(rpc.y_parity, Some(Parity(true)));
+    }
+
+    #[cfg(feature = "optimism")]
+    #[test]
+    fn deposit_signature_is_zero() {
+        let signature = PrimitiveSignature::optimism_deposit_tx_signature();
+        let rpc = from_primitive_signature(signature, TxType::Deposit, Some(10));
+        assert_eq!(rpc, Signature { r: U256::ZERO, s: U256::ZERO, v: U256::ZERO, y_parity: None });
+    }
+}
@@ -0,0 +1,41 @@
+use alloy_primitives::{Address, B256, U256};
+use serde::{Deserialize, Serialize};
+
+/// The balance, nonce and code hash of an account at a block, as returned by `reth_getAccounts`.
+///
+/// An account that doesn't exist has a zero balance and nonce, and the hash of empty code.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountSummary {
+    /// The address of the account.
+    pub address: Address,
+    /// The balance of the account.
+    pub balance: U256,
+    /// The nonce of the account.
+    #[serde(with = "alloy_rpc_types::serde_helpers::quantity")]
+    pub nonce: u64,
+    /// The hash of the code of the account.
+    pub code_hash: B256,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256};
+
+    #[test]
+    fn serde_account_summary() {
+        let account = AccountSummary {
+            address: address!("d8da6bf26964af9d7eed9e03e53415d37aa96045"),
+            balance: U256::from(1_000_000_000u64),
+            nonce: 16,
+            code_hash: b256!("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"),
+        };
+        let json = serde_json::to_string(&account).unwrap();
+        assert_eq!(
+            json,
+            r#"{"address":"0xd8da6bf26964af9d7eed9e03e53415d37aa96045","balance":"0x3b9aca00","nonce":"0x10","codeHash":"0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"}"#
+        );
+        assert_eq!(serde_json::from_str::<AccountSummary>(&json).unwrap(), account);
+    }
+}
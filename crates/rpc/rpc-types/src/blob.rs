@@ -0,0 +1,30 @@
+use alloy_primitives::{FixedBytes, B256};
+use alloy_rpc_types::BlobTransactionSidecar;
+use serde::{Deserialize, Serialize};
+
+/// The size of a blob in bytes.
+const BYTES_PER_BLOB: usize = 131_072;
+
+/// The sidecar of a blob transaction included in a block, as returned by `eth_getBlobSidecars`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockBlobSidecar {
+    /// The hash of the blob transaction.
+    pub transaction_hash: B256,
+    /// The index of the blob transaction in the block.
+    #[serde(with = "alloy_rpc_types::serde_helpers::quantity")]
+    pub transaction_index: u64,
+    /// The blobs of the transaction, with their commitments and proofs.
+    #[serde(flatten)]
+    pub sidecar: BlobTransactionSidecar,
+}
+
+/// A blob and its KZG proof, as returned by `engine_getBlobsV1`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobAndProofV1 {
+    /// The blob.
+    pub blob: Box<FixedBytes<BYTES_PER_BLOB>>,
+    /// The KZG proof of the blob.
+    pub proof: FixedBytes<48>,
+}
@@ -0,0 +1,12 @@
+use alloy_primitives::B256;
+use serde::{Deserialize, Serialize};
+
+/// The pre-merge header accumulator, as returned by `reth_getHeaderAccumulator`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HeaderAccumulator {
+    /// The roots of the epoch accumulators of the complete epochs, in epoch order.
+    pub historical_epochs: Vec<B256>,
+    /// The root of the historical accumulator over the epochs.
+    pub root: B256,
+}
@@ -0,0 +1,12 @@
+use crate::{BlockId, TransactionRequest};
+use serde::{Deserialize, Serialize};
+
+/// A call executed on top of the state of a block, as accepted by `reth_callMany`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoricalCall {
+    /// The block whose state the call is executed on.
+    pub block: BlockId,
+    /// The call to execute.
+    pub call: TransactionRequest,
+}
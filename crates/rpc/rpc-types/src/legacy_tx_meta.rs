@@ -0,0 +1,17 @@
+use alloy_primitives::{Bytes, B256};
+use serde::{Deserialize, Serialize};
+
+/// The metadata of a transaction of the OP Mainnet legacy chain, as returned by
+/// `reth_getLegacyTxMeta`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LegacyTxMeta {
+    /// The hash of the transaction.
+    pub hash: B256,
+    /// The size of the transaction, as reported by the legacy chain.
+    pub size: u64,
+    /// The L1 timestamp of the transaction.
+    pub timestamp: u64,
+    /// The remaining fields of the metadata, RLP encoded, as exported from the legacy chain.
+    pub rest: Bytes,
+}
@@ -10,10 +10,19 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #[allow(hidden_glob_reexports)]
+mod account_summary;
+mod blob;
 mod eth;
+mod header_accumulator;
+mod historical_call;
+mod legacy_tx_meta;
 mod mev;
 mod peer;
+mod reorg;
 mod rpc;
+mod state_diff;
+mod subscription;
+mod witness;
 
 // re-export for convenience
 pub use alloy_rpc_types::serde_helpers;
@@ -51,6 +60,15 @@ pub use eth::{
     transaction::{self, TransactionRequest, TypedTransactionRequest},
 };
 
+pub use account_summary::*;
+pub use blob::*;
+pub use header_accumulator::*;
+pub use historical_call::*;
+pub use legacy_tx_meta::*;
 pub use mev::*;
 pub use peer::*;
+pub use reorg::*;
 pub use rpc::*;
+pub use state_diff::*;
+pub use subscription::*;
+pub use witness::*;
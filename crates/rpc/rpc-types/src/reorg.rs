@@ -0,0 +1,22 @@
+use alloy_primitives::{BlockNumber, B256};
+use serde::{Deserialize, Serialize};
+
+/// A reorg of the canonical chain, as returned by `reth_getReorgs`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Reorg {
+    /// The sequence number of the reorg in the journal.
+    pub id: u64,
+    /// The unix timestamp in seconds at which the reorg was committed.
+    pub timestamp: u64,
+    /// The number of blocks removed from the canonical chain.
+    pub depth: u64,
+    /// The number of the last block the old and the new chain have in common.
+    pub fork_block_number: BlockNumber,
+    /// The hash of the last block the old and the new chain have in common.
+    pub fork_block_hash: B256,
+    /// The hashes of the blocks removed from the canonical chain, in increasing block order.
+    pub old_blocks: Vec<B256>,
+    /// The hashes of the blocks added to the canonical chain, in increasing block order.
+    pub new_blocks: Vec<B256>,
+}
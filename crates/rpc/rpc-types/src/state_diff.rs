@@ -0,0 +1,62 @@
+use alloy_primitives::{Address, BlockNumber, Bytes, B256, U256};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The state changes of a block, as returned by `reth_getStateDiffs` and the `stateDiffs`
+/// subscription.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockStateDiff {
+    /// The number of the block.
+    pub block_number: BlockNumber,
+    /// The hash of the block.
+    pub block_hash: B256,
+    /// The hash of the parent of the block.
+    pub parent_hash: B256,
+    /// The changed accounts.
+    pub accounts: BTreeMap<Address, AccountStateDiff>,
+}
+
+/// The changes of an account in a block.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountStateDiff {
+    /// The balance of the account before and after the block, if it changed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<StateDelta<U256>>,
+    /// The nonce of the account before and after the block, if it changed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<StateDelta<u64>>,
+    /// The code of the account before and after the block, if it changed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<StateDelta<Bytes>>,
+    /// The changed storage slots of the account.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub storage: BTreeMap<B256, StateDelta<U256>>,
+}
+
+impl AccountStateDiff {
+    /// Returns true if nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.balance.is_none()
+            && self.nonce.is_none()
+            && self.code.is_none()
+            && self.storage.is_empty()
+    }
+}
+
+/// A changed value.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateDelta<T> {
+    /// The value before the change.
+    pub from: T,
+    /// The value after the change.
+    pub to: T,
+}
+
+impl<T: PartialEq> StateDelta<T> {
+    /// Returns the delta between the given values, if they differ.
+    pub fn changed(from: T, to: T) -> Option<Self> {
+        (from != to).then_some(Self { from, to })
+    }
+}
@@ -0,0 +1,128 @@
+use alloy_primitives::Address;
+use alloy_rpc_types::pubsub::Params;
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+/// The kind of a `reth_subscribe` subscription.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RethSubscriptionKind {
+    /// The state changes of every new canonical block, see
+    /// [`BlockStateDiff`](crate::BlockStateDiff).
+    StateDiffs,
+}
+
+/// Params of an `eth_subscribe` call.
+///
+/// Extends the standard [`Params`] with [`PendingTransactionsParams`] for the
+/// `newPendingTransactions` subscription. An object is parsed as [`PendingTransactionsParams`] if
+/// it has any of their fields, and as a logs filter otherwise.
+#[derive(Clone, Debug, PartialEq)]
+pub enum EthSubscriptionParams {
+    /// Standard params: a logs filter or the full transactions flag.
+    Standard(Params),
+    /// Filters of a `newPendingTransactions` subscription.
+    PendingTransactions(PendingTransactionsParams),
+}
+
+impl From<Params> for EthSubscriptionParams {
+    fn from(params: Params) -> Self {
+        Self::Standard(params)
+    }
+}
+
+impl From<PendingTransactionsParams> for EthSubscriptionParams {
+    fn from(params: PendingTransactionsParams) -> Self {
+        Self::PendingTransactions(params)
+    }
+}
+
+impl Serialize for EthSubscriptionParams {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Standard(params) => params.serialize(serializer),
+            Self::PendingTransactions(params) => params.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for EthSubscriptionParams {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let is_pending_transactions = value.as_object().map_or(false, |object| {
+            PendingTransactionsParams::FIELDS.iter().any(|field| object.contains_key(*field))
+        });
+
+        if is_pending_transactions {
+            serde_json::from_value(value).map(Self::PendingTransactions).map_err(D::Error::custom)
+        } else {
+            serde_json::from_value(value).map(Self::Standard).map_err(D::Error::custom)
+        }
+    }
+}
+
+/// Filters of a `newPendingTransactions` subscription.
+///
+/// Transactions that don't match all of the set filters are not sent to the subscriber.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct PendingTransactionsParams {
+    /// Whether full transaction objects are sent instead of transaction hashes.
+    #[serde(default)]
+    pub full_transactions: bool,
+    /// Only send transactions calling one of these addresses.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_addresses: Option<Vec<Address>>,
+    /// Only send transactions paying at least this tip per gas at the base fee of the pending
+    /// block.
+    #[serde(
+        default,
+        with = "alloy_rpc_types::serde_helpers::quantity::opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub min_tip: Option<u128>,
+}
+
+impl PendingTransactionsParams {
+    /// The JSON names of the fields.
+    const FIELDS: [&'static str; 3] = ["fullTransactions", "toAddresses", "minTip"];
+
+    /// Returns `true` if no filter is set.
+    pub const fn is_unfiltered(&self) -> bool {
+        self.to_addresses.is_none() && self.min_tip.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    #[test]
+    fn parse_subscription_params() {
+        let params: EthSubscriptionParams = serde_json::from_str("true").unwrap();
+        assert_eq!(params, EthSubscriptionParams::Standard(Params::Bool(true)));
+
+        let params: EthSubscriptionParams =
+            serde_json::from_str(r#"{"address":"0xd8da6bf26964af9d7eed9e03e53415d37aa96045"}"#)
+                .unwrap();
+        assert!(matches!(params, EthSubscriptionParams::Standard(Params::Logs(_))));
+
+        let params: EthSubscriptionParams = serde_json::from_str(
+            r#"{"fullTransactions":true,"toAddresses":["0xd8da6bf26964af9d7eed9e03e53415d37aa96045"],"minTip":"0x3b9aca00"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            params,
+            EthSubscriptionParams::PendingTransactions(PendingTransactionsParams {
+                full_transactions: true,
+                to_addresses: Some(vec![address!("d8da6bf26964af9d7eed9e03e53415d37aa96045")]),
+                min_tip: Some(1_000_000_000),
+            })
+        );
+
+        assert!(serde_json::from_str::<EthSubscriptionParams>(
+            r#"{"fullTransactions":true,"address":"0xd8da6bf26964af9d7eed9e03e53415d37aa96045"}"#
+        )
+        .is_err());
+    }
+}
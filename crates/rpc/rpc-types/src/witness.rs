@@ -0,0 +1,20 @@
+use alloy_primitives::Bytes;
+use serde::{Deserialize, Serialize};
+
+/// The witness of the execution of a block, as returned by `debug_executionWitness`.
+///
+/// Contains the parts of the parent state and chain that the block accessed, which is everything
+/// that is needed to re-execute the block without access to the full state, e.g. by a stateless
+/// client or a prover.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionWitness {
+    /// The RLP encoded trie nodes of the parent state that prove all accounts and storage slots
+    /// the block accessed, deduplicated and sorted.
+    pub state: Vec<Bytes>,
+    /// The bytecodes of all contracts the block accessed, sorted by code hash.
+    pub codes: Vec<Bytes>,
+    /// The RLP encoded headers of the parent block and of all ancestors whose hashes the block
+    /// accessed, in increasing block order.
+    pub headers: Vec<Bytes>,
+}
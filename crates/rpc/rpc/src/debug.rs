@@ -1,6 +1,7 @@
 use crate::{
     eth::{
         error::{EthApiError, EthResult},
+        pre_block_beacon_root_contract_call, pre_block_blockhashes_update,
         revm_utils::prepare_call_env,
         EthTransactions,
     },
@@ -17,7 +18,10 @@ use reth_primitives::{
 use reth_provider::{
     BlockReaderIdExt, ChainSpecProvider, HeaderProvider, StateProviderBox, TransactionVariant,
 };
-use reth_revm::database::StateProviderDatabase;
+use reth_revm::{
+    database::StateProviderDatabase, state_change::post_block_balance_increments,
+    witness::RecordingDatabase,
+};
 use reth_rpc_api::DebugApiServer;
 use reth_rpc_types::{
     state::EvmOverrides,
@@ -25,12 +29,15 @@ use reth_rpc_types::{
         BlockTraceResult, FourByteFrame, GethDebugBuiltInTracerType, GethDebugTracerType,
         GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, NoopFrame, TraceResult,
     },
-    BlockError, Bundle, RichBlock, StateContext, TransactionRequest,
+    BlockError, Bundle, ExecutionWitness, RichBlock, StateContext, TransactionRequest,
 };
 use reth_tasks::pool::BlockingTaskGuard;
 use revm::{
     db::CacheDB,
-    primitives::{db::DatabaseCommit, BlockEnv, CfgEnvWithHandlerCfg, Env, EnvWithHandlerCfg},
+    primitives::{
+        db::{Database, DatabaseCommit},
+        BlockEnv, CfgEnvWithHandlerCfg, Env, EnvWithHandlerCfg,
+    },
 };
 use revm_inspectors::tracing::{
     js::{JsInspector, TransactionContext},
@@ -507,6 +514,105 @@ where
             .await
     }
 
+    /// Re-executes the block on top of the state of its parent and returns the witness of the
+    /// execution.
+    ///
+    /// The block is replayed like [`Self::debug_trace_block`], including the pre-block system
+    /// calls and the post-block balance increments, while recording all state that is read from
+    /// the parent state.
+    pub async fn debug_execution_witness(
+        &self,
+        block_id: BlockNumberOrTag,
+    ) -> EthResult<ExecutionWitness> {
+        let block = self
+            .inner
+            .eth_api
+            .block_by_id_with_senders(block_id.into())
+            .await?
+            .ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+        let (cfg, block_env, _) = self.inner.eth_api.evm_env_at(block.hash().into()).await?;
+        let total_difficulty = self
+            .inner
+            .provider
+            .header_td(&block.hash())?
+            .ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+        let chain_spec = self.inner.provider.chain_spec();
+
+        let this = self.clone();
+        let (state, codes, block_hashes) = self
+            .eth_api()
+            .spawn_with_state_at_block(block.parent_hash.into(), move |state| {
+                let mut db =
+                    CacheDB::new(RecordingDatabase::new(StateProviderDatabase::new(&state)));
+
+                pre_block_beacon_root_contract_call(
+                    &mut db,
+                    &chain_spec,
+                    block.number,
+                    &cfg,
+                    &block_env,
+                    block.parent_beacon_block_root,
+                )?;
+                pre_block_blockhashes_update(
+                    &mut db,
+                    &chain_spec,
+                    &block_env,
+                    block.number,
+                    block.parent_hash,
+                )?;
+
+                for tx in block.clone().into_transactions_ecrecovered() {
+                    let env = EnvWithHandlerCfg {
+                        env: Env::boxed(
+                            cfg.cfg_env.clone(),
+                            block_env.clone(),
+                            tx_env_with_recovered(&tx),
+                        ),
+                        handler_cfg: cfg.handler_cfg,
+                    };
+                    let (res, _) = this.eth_api().transact(&mut db, env)?;
+                    db.commit(res.state);
+                }
+
+                // the accounts of the block rewards and withdrawals are read to increment their
+                // balances
+                let balance_increments = post_block_balance_increments(
+                    &chain_spec,
+                    block.number,
+                    block.difficulty,
+                    block.beneficiary,
+                    block.timestamp,
+                    total_difficulty,
+                    &block.ommers,
+                    block.withdrawals.as_ref().map(Withdrawals::as_ref),
+                );
+                for address in balance_increments.into_keys() {
+                    db.basic(address)?;
+                }
+
+                let accessed = db.db.into_accessed();
+                let state = state.witness(&accessed.proof_targets().collect())?;
+                let codes =
+                    accessed.codes.into_values().map(|code| code.original_bytes()).collect();
+                Ok((state, codes, accessed.block_hashes))
+            })
+            .await?;
+
+        // the parent header is needed for the pre-state root, older headers for the accessed
+        // block hashes
+        let parent_number = block.number.saturating_sub(1);
+        let first = block_hashes.keys().next().copied().unwrap_or(parent_number).min(parent_number);
+        let headers = self
+            .inner
+            .provider
+            .headers_range(first..=parent_number)?
+            .into_iter()
+            .map(|header| alloy_rlp::encode(header).into())
+            .collect();
+
+        Ok(ExecutionWitness { state, codes, headers })
+    }
+
     /// Executes the configured transaction with the environment on the given database.
     ///
     /// Returns the trace frame and the state that got updated after executing the transaction.
@@ -813,6 +919,15 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_executionWitness`
+    async fn debug_execution_witness(
+        &self,
+        block: BlockNumberOrTag,
+    ) -> RpcResult<ExecutionWitness> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(Self::debug_execution_witness(self, block).await?)
+    }
+
     async fn debug_free_os_memory(&self) -> RpcResult<()> {
         Ok(())
     }
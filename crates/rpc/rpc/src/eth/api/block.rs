@@ -2,19 +2,20 @@
 
 use crate::{
     eth::{
-        api::transactions::build_transaction_receipt_with_block_receipts,
+        api::transactions::build_transaction_receipt_with_sender,
         error::{EthApiError, EthResult},
     },
     EthApi,
 };
+use reth_errors::RethError;
 use reth_evm::ConfigureEvm;
 use reth_network_api::NetworkInfo;
-use reth_primitives::{BlockId, TransactionMeta};
+use reth_primitives::{BlockId, SealedBlockWithSenders, TransactionMeta};
 use reth_provider::{BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProviderFactory};
-use reth_rpc_types::{AnyTransactionReceipt, Header, Index, RichBlock};
+use reth_rpc_types::{AnyTransactionReceipt, BlockBlobSidecar, Header, Index, RichBlock};
 use reth_rpc_types_compat::block::{from_block, uncle_block_from_header};
 use reth_transaction_pool::TransactionPool;
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 impl<Provider, Pool, Network, EvmConfig> EthApi<Provider, Pool, Network, EvmConfig>
 where
@@ -65,17 +66,29 @@ where
     ) -> EthResult<Option<Vec<AnyTransactionReceipt>>> {
         // Fetch block and receipts based on block_id
         let block_and_receipts = if block_id.is_pending() {
-            self.provider()
-                .pending_block_and_receipts()?
-                .map(|(sb, receipts)| (sb, Arc::new(receipts)))
+            match self.provider().pending_block_and_receipts()? {
+                Some((block, receipts)) => {
+                    let block = block
+                        .seal_with_senders()
+                        .ok_or(EthApiError::InvalidTransactionSignature)?;
+                    Some((block, Arc::new(receipts)))
+                }
+                None => None,
+            }
         } else if let Some(block_hash) = self.provider().block_hash_for_id(block_id)? {
-            self.cache().get_block_and_receipts(block_hash).await?
+            // The receipts of the block are read with a single range scan, and the stored senders
+            // are used instead of recovering the signer of every transaction.
+            let (block, receipts) = futures::try_join!(
+                self.cache().get_sealed_block_with_senders(block_hash),
+                self.cache().get_receipts(block_hash),
+            )?;
+            block.zip(receipts)
         } else {
             None
         };
 
         // If no block and receipts found, return None
-        let Some((block, receipts)) = block_and_receipts else {
+        let Some((SealedBlockWithSenders { block, senders }, receipts)) = block_and_receipts else {
             return Ok(None);
         };
 
@@ -88,43 +101,47 @@ where
         let block = block.unseal();
 
         #[cfg(feature = "optimism")]
-        let (block_timestamp, l1_block_info) = {
-            let body = reth_evm_optimism::extract_l1_info(&block);
-            (block.timestamp, body.ok())
-        };
+        let (block_timestamp, l1_block_info) = (block.timestamp, self.op_l1_block_info(&block));
 
-        // Build transaction receipts
-        block
-            .body
-            .into_iter()
-            .zip(receipts.iter())
-            .enumerate()
-            .map(|(idx, (tx, receipt))| {
-                let meta = TransactionMeta {
-                    tx_hash: tx.hash,
-                    index: idx as u64,
-                    block_hash,
-                    block_number,
-                    base_fee,
-                    excess_blob_gas,
-                    timestamp,
-                };
+        // Build transaction receipts in a single pass, keeping track of the gas used and the logs
+        // emitted by the previous transactions
+        let mut tx_receipts = Vec::with_capacity(receipts.len());
+        let mut prev_cumulative_gas_used = 0;
+        let mut num_logs = 0;
+        for (idx, ((tx, from), receipt)) in
+            block.body.into_iter().zip(senders).zip(receipts.iter()).enumerate()
+        {
+            let meta = TransactionMeta {
+                tx_hash: tx.hash,
+                index: idx as u64,
+                block_hash,
+                block_number,
+                base_fee,
+                excess_blob_gas,
+                timestamp,
+            };
+
+            #[cfg(feature = "optimism")]
+            let op_tx_meta = self.build_op_tx_meta(&tx, l1_block_info.clone(), block_timestamp)?;
+
+            let gas_used = receipt.cumulative_gas_used - prev_cumulative_gas_used;
+            prev_cumulative_gas_used = receipt.cumulative_gas_used;
+            let tx_num_logs = receipt.logs.len();
 
+            tx_receipts.push(build_transaction_receipt_with_sender(
+                tx,
+                from,
+                meta,
+                receipt.clone(),
+                gas_used,
+                num_logs,
                 #[cfg(feature = "optimism")]
-                let op_tx_meta =
-                    self.build_op_tx_meta(&tx, l1_block_info.clone(), block_timestamp)?;
-
-                build_transaction_receipt_with_block_receipts(
-                    tx,
-                    meta,
-                    receipt.clone(),
-                    &receipts,
-                    #[cfg(feature = "optimism")]
-                    op_tx_meta,
-                )
-            })
-            .collect::<EthResult<Vec<_>>>()
-            .map(Some)
+                op_tx_meta,
+            ));
+            num_logs += tx_num_logs;
+        }
+
+        Ok(Some(tx_receipts))
     }
 
     /// Returns the number transactions in the given block.
@@ -149,6 +166,45 @@ where
         Ok(self.cache().get_block_transactions(block_hash).await?.map(|txs| txs.len()))
     }
 
+    /// Returns the blob sidecars of all blob transactions in the given block.
+    ///
+    /// Sidecars are served from the blob store of the transaction pool, so only sidecars that are
+    /// still retained are returned.
+    ///
+    /// Returns `None` if the block does not exist
+    pub(crate) async fn blob_sidecars(
+        &self,
+        block_id: impl Into<BlockId>,
+    ) -> EthResult<Option<Vec<BlockBlobSidecar>>> {
+        let Some(block) = self.block(block_id).await? else { return Ok(None) };
+
+        let blob_txs = block
+            .body
+            .iter()
+            .enumerate()
+            .filter(|(_, tx)| tx.is_eip4844())
+            .map(|(index, tx)| (tx.hash(), index as u64))
+            .collect::<HashMap<_, _>>();
+        if blob_txs.is_empty() {
+            return Ok(Some(Vec::new()))
+        }
+
+        let mut sidecars = self
+            .pool()
+            .get_all_blobs(blob_txs.keys().copied().collect())
+            .map_err(|err| EthApiError::Internal(RethError::other(err)))?
+            .into_iter()
+            .map(|(transaction_hash, sidecar)| BlockBlobSidecar {
+                transaction_hash,
+                transaction_index: blob_txs[&transaction_hash],
+                sidecar,
+            })
+            .collect::<Vec<_>>();
+        sidecars.sort_unstable_by_key(|sidecar| sidecar.transaction_index);
+
+        Ok(Some(sidecars))
+    }
+
     /// Returns the block object for the given block id.
     pub(crate) async fn block(
         &self,
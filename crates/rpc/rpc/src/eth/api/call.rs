@@ -4,7 +4,7 @@ use crate::{
     eth::{
         error::{ensure_success, EthApiError, EthResult, RevertError, RpcInvalidTransactionError},
         revm_utils::{
-            apply_state_overrides, build_call_evm_env, caller_gas_allowance,
+            apply_block_overrides, apply_state_overrides, build_call_evm_env, caller_gas_allowance,
             cap_tx_gas_limit_with_caller_allowance, get_precompiles, prepare_call_env,
         },
         EthTransactions,
@@ -51,13 +51,13 @@ where
         &self,
         request: TransactionRequest,
         at: BlockId,
-        state_override: Option<StateOverride>,
+        overrides: EvmOverrides,
     ) -> EthResult<U256> {
         let (cfg, block_env, at) = self.evm_env_at(at).await?;
 
         self.on_blocking_task(|this| async move {
             let state = this.state_at(at)?;
-            this.estimate_gas_with(cfg, block_env, request, state, state_override)
+            this.estimate_gas_with(cfg, block_env, request, state, overrides)
         })
         .await
     }
@@ -178,10 +178,10 @@ where
     pub fn estimate_gas_with<S>(
         &self,
         mut cfg: CfgEnvWithHandlerCfg,
-        block: BlockEnv,
+        mut block: BlockEnv,
         request: TransactionRequest,
         state: S,
-        state_override: Option<StateOverride>,
+        overrides: EvmOverrides,
     ) -> EthResult<U256>
     where
         S: StateProvider,
@@ -195,6 +195,13 @@ where
         // <https://github.com/ethereum/go-ethereum/blob/ee8e83fa5f6cb261dad2ed0a7bbcde4930c41e6c/internal/ethapi/api.go#L985>
         cfg.disable_base_fee = true;
 
+        let mut db = CacheDB::new(StateProviderDatabase::new(state));
+
+        // Apply any block overrides first, the block gas limit caps the estimate
+        if let Some(block_overrides) = overrides.block {
+            apply_block_overrides(*block_overrides, &mut db, &mut block);
+        }
+
         // Keep a copy of gas related request values
         let tx_request_gas_limit = request.gas;
         let tx_request_gas_price = request.gas_price;
@@ -208,18 +215,18 @@ where
 
         // Configure the evm env
         let mut env = build_call_evm_env(cfg, block, request)?;
-        let mut db = CacheDB::new(StateProviderDatabase::new(state));
 
         // Apply any state overrides if specified.
-        if let Some(state_override) = state_override {
+        if let Some(state_override) = overrides.state {
             apply_state_overrides(state_override, &mut db)?;
         }
 
         // Optimize for simple transfer transactions, potentially reducing the gas estimate.
         if env.tx.data.is_empty() {
             if let TxKind::Call(to) = env.tx.transact_to {
-                if let Ok(code) = db.db.account_code(to) {
-                    let no_code_callee = code.map(|code| code.is_empty()).unwrap_or(true);
+                // the code of the callee may be overridden, so it is looked up in the cache
+                if let Ok(callee) = db.basic_ref(to) {
+                    let no_code_callee = callee.map_or(true, |callee| callee.is_empty_code_hash());
                     if no_code_callee {
                         // If the tx is a simple transfer (call to an account with no code) we can
                         // shortcircuit. But simply returning
@@ -242,8 +249,24 @@ where
         //
         // The caller allowance is check by doing `(account.balance - tx.value) / tx.gas_price`
         if env.tx.gas_price > U256::ZERO {
+            let allowance = caller_gas_allowance(&mut db, &env.tx)?;
+            // on OP chains the caller pays the L1 data fee of the transaction as well, which the
+            // call itself is not charged
+            #[cfg(feature = "optimism")]
+            let allowance = if env.handler_cfg.is_optimism {
+                let l1_fee = reth_evm_optimism::estimate_tx_l1_fee(
+                    &mut db,
+                    env.handler_cfg.spec_id,
+                    &env.tx,
+                )?;
+                allowance
+                    .checked_sub(l1_fee.div_ceil(env.tx.gas_price))
+                    .ok_or(RpcInvalidTransactionError::InsufficientFunds)?
+            } else {
+                allowance
+            };
             // cap the highest gas limit by max gas caller can afford with given gas price
-            highest_gas_limit = highest_gas_limit.min(caller_gas_allowance(&mut db, &env.tx)?);
+            highest_gas_limit = highest_gas_limit.min(allowance);
         }
 
         // We can now normalize the highest gas limit to a u64
@@ -443,8 +466,13 @@ where
 
         // calculate the gas used using the access list
         request.access_list = Some(access_list.clone());
-        let gas_used =
-            self.estimate_gas_with(cfg_with_spec_id, env.block.clone(), request, &*db.db, None)?;
+        let gas_used = self.estimate_gas_with(
+            cfg_with_spec_id,
+            env.block.clone(),
+            request,
+            &*db.db,
+            EvmOverrides::default(),
+        )?;
 
         Ok(AccessListWithGasUsed { access_list, gas_used })
     }
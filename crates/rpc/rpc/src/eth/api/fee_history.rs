@@ -56,19 +56,31 @@ impl FeeHistoryCache {
         self.config().resolution
     }
 
-    /// Returns all blocks that are missing in the cache in the [`lower_bound`, `upper_bound`]
-    /// range.
+    /// Returns all blocks below the `upper_bound` that are missing in the cache, newest first.
     ///
-    /// This function is used to populate the cache with missing blocks, which can happen if the
-    /// node switched to stage sync node.
+    /// The cache keeps the reward percentiles of the `max_blocks` most recent blocks, so large
+    /// `eth_feeHistory` requests don't need to scan the transactions of every block. This function
+    /// is used to populate the cache with missing blocks, which can happen if the node started
+    /// recently or switched to stage sync.
     async fn missing_consecutive_blocks(&self) -> VecDeque<u64> {
+        let upper_bound = self.upper_bound();
+        let lower_bound =
+            upper_bound.saturating_sub(self.inner.config.max_blocks.saturating_sub(1));
         let entries = self.inner.entries.read().await;
-        (self.lower_bound()..self.upper_bound())
+        (lower_bound..upper_bound)
             .rev()
             .filter(|&block_number| !entries.contains_key(&block_number))
             .collect()
     }
 
+    /// Removes the entries of the given blocks, e.g. because they were reorged out.
+    async fn remove_blocks(&self, block_numbers: impl IntoIterator<Item = u64>) {
+        let mut entries = self.inner.entries.write().await;
+        for block_number in block_numbers {
+            entries.remove(&block_number);
+        }
+    }
+
     /// Insert block data into the cache.
     async fn insert_blocks<I>(&self, blocks: I)
     where
@@ -128,8 +140,8 @@ impl FeeHistoryCache {
     /// Collect fee history for given range.
     ///
     /// This function retrieves fee history entries from the cache for the specified range.
-    /// If the requested range (`start_block` to `end_block`) is within the cache bounds,
-    /// it returns the corresponding entries.
+    /// If all blocks of the requested range (`start_block` to `end_block`) are cached, it returns
+    /// the corresponding entries.
     /// Otherwise it returns None.
     pub async fn get_history(
         &self,
@@ -145,7 +157,8 @@ impl FeeHistoryCache {
                 .map(|(_, fee_entry)| fee_entry.clone())
                 .collect::<Vec<_>>();
 
-            if result.is_empty() {
+            // there may be gaps while missing blocks are being fetched
+            if result.len() as u64 != end_block.saturating_sub(start_block) + 1 {
                 return None
             }
 
@@ -215,6 +228,14 @@ pub async fn fee_history_cache_new_blocks_task<St, Provider>(
     let mut missing_blocks = VecDeque::new();
     let mut fetch_missing_block = Fuse::terminated();
 
+    // Populate the cache with the most recent blocks right away, so requests don't have to wait
+    // for `max_blocks` new blocks to be served from the cache
+    if let Ok(best_block) = provider.best_block_number() {
+        let max_blocks = fee_history_cache.config().max_blocks;
+        missing_blocks =
+            (best_block.saturating_sub(max_blocks.saturating_sub(1))..=best_block).rev().collect();
+    }
+
     loop {
         if fetch_missing_block.is_terminated() {
             if let Some(block_number) = missing_blocks.pop_front() {
@@ -242,6 +263,10 @@ pub async fn fee_history_cache_new_blocks_task<St, Provider>(
                      // the stream ended, we are done
                     break;
                 };
+                if let Some(reverted) = event.reverted() {
+                    // the entries of reorged out blocks above the new tip would be kept otherwise
+                    fee_history_cache.remove_blocks(reverted.blocks().keys().copied()).await;
+                }
                 let (blocks, receipts): (Vec<_>, Vec<_>) = event
                     .committed()
                     .blocks_and_receipts()
@@ -397,3 +422,38 @@ impl FeeHistoryEntry {
         Some(calculate_excess_blob_gas(self.excess_blob_gas?, self.blob_gas_used?))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives::Header;
+    use reth_provider::test_utils::NoopProvider;
+
+    fn block(number: u64) -> (SealedBlock, Arc<Vec<Receipt>>) {
+        let header = Header { number, gas_limit: 1, ..Default::default() }.seal_slow();
+        (SealedBlock { header, ..Default::default() }, Arc::new(Vec::new()))
+    }
+
+    #[tokio::test]
+    async fn history_requires_all_blocks() {
+        let eth_cache = EthStateCache::spawn(
+            NoopProvider::default(),
+            Default::default(),
+            EthEvmConfig::default(),
+        );
+        let cache =
+            FeeHistoryCache::new(eth_cache, FeeHistoryCacheConfig { max_blocks: 5, resolution: 1 });
+
+        cache.insert_blocks([block(5), block(7)]).await;
+        assert!(cache.get_history(5, 7).await.is_none());
+        assert_eq!(cache.get_history(7, 7).await.unwrap().len(), 1);
+        assert_eq!(cache.missing_consecutive_blocks().await, VecDeque::from([6, 4, 3]));
+
+        cache.insert_blocks([block(6)]).await;
+        assert_eq!(cache.get_history(5, 7).await.unwrap().len(), 3);
+
+        cache.remove_blocks([6, 7]).await;
+        assert!(cache.get_history(5, 7).await.is_none());
+    }
+}
@@ -43,7 +43,7 @@ pub(crate) mod fee_history;
 mod fees;
 #[cfg(feature = "optimism")]
 mod optimism;
-mod pending_block;
+pub(crate) mod pending_block;
 mod server;
 mod sign;
 mod state;
@@ -95,6 +95,22 @@ impl<Provider, Pool, Network, EvmConfig> EthApi<Provider, Pool, Network, EvmConf
     pub fn set_eth_raw_transaction_forwarder(&self, forwarder: Arc<dyn RawTransactionForwarder>) {
         self.inner.raw_transaction_forwarder.write().replace(forwarder);
     }
+
+    /// Awaits the result of an EVM execution, failing with [`EthApiError::ExecutionTimedOut`] if it
+    /// takes longer than the configured EVM timeout.
+    ///
+    /// Note: the execution itself is not interrupted, it's only bounded by the gas cap.
+    pub(crate) async fn with_evm_timeout<F, R>(&self, fut: F) -> EthResult<R>
+    where
+        F: Future<Output = EthResult<R>>,
+    {
+        match self.inner.evm_timeout {
+            Some(timeout) => tokio::time::timeout(timeout, fut)
+                .await
+                .map_err(|_| EthApiError::ExecutionTimedOut(timeout))?,
+            None => fut.await,
+        }
+    }
 }
 
 impl<Provider, Pool, Network, EvmConfig> EthApi<Provider, Pool, Network, EvmConfig>
@@ -122,6 +138,7 @@ where
             eth_cache,
             gas_oracle,
             gas_cap.into().into(),
+            None,
             Box::<TokioTaskExecutor>::default(),
             blocking_task_pool,
             fee_history_cache,
@@ -139,6 +156,7 @@ where
         eth_cache: EthStateCache,
         gas_oracle: GasPriceOracle<Provider>,
         gas_cap: u64,
+        evm_timeout: Option<Duration>,
         task_spawner: Box<dyn TaskSpawner>,
         blocking_task_pool: BlockingTaskPool,
         fee_history_cache: FeeHistoryCache,
@@ -161,6 +179,7 @@ where
             eth_cache,
             gas_oracle,
             gas_cap,
+            evm_timeout,
             starting_block: U256::from(latest_block),
             task_spawner,
             pending_block: Default::default(),
@@ -486,6 +505,8 @@ struct EthApiInner<Provider, Pool, Network, EvmConfig> {
     gas_oracle: GasPriceOracle<Provider>,
     /// Maximum gas limit for `eth_call` and call tracing RPC methods.
     gas_cap: u64,
+    /// Maximum duration of the EVM execution of `eth_call`, gas estimation and tracing calls.
+    evm_timeout: Option<Duration>,
     /// The block number at which the node started
     starting_block: U256,
     /// The type that can spawn tasks which would otherwise block.
@@ -17,6 +17,8 @@ pub(crate) struct OptimismTxMeta {
     pub(crate) l1_fee: Option<u128>,
     /// The L1 data gas for the block.
     pub(crate) l1_data_gas: Option<u128>,
+    /// The L1 fee of a transaction of the legacy chain, as recorded in its receipt.
+    pub(crate) legacy: Option<LegacyL1Fee>,
 }
 
 impl OptimismTxMeta {
@@ -26,6 +28,25 @@ impl OptimismTxMeta {
         l1_fee: Option<u128>,
         l1_data_gas: Option<u128>,
     ) -> Self {
-        Self { l1_block_info, l1_fee, l1_data_gas }
+        Self { l1_block_info, l1_fee, l1_data_gas, legacy: None }
     }
+
+    /// Creates a new [`OptimismTxMeta`] for a transaction of the legacy chain.
+    pub(crate) const fn legacy(legacy: LegacyL1Fee) -> Self {
+        Self { l1_block_info: None, l1_fee: None, l1_data_gas: None, legacy: Some(legacy) }
+    }
+}
+
+/// The L1 fee of a transaction of the OP Mainnet legacy chain, which can't be derived from the L1
+/// block info of its block.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct LegacyL1Fee {
+    /// The L1 fee paid by the transaction.
+    pub(crate) l1_fee: u128,
+    /// The L1 gas used by the transaction.
+    pub(crate) l1_gas_used: u128,
+    /// The L1 gas price the fee was charged at.
+    pub(crate) l1_gas_price: u128,
+    /// The dynamic overhead the fee was scaled by.
+    pub(crate) l1_fee_scalar: f64,
 }
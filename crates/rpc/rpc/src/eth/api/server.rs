@@ -22,7 +22,7 @@ use reth_rpc_api::EthApiServer;
 use reth_rpc_types::{
     serde_helpers::JsonStorageKey,
     state::{EvmOverrides, StateOverride},
-    AccessListWithGasUsed, AnyTransactionReceipt, BlockOverrides, Bundle,
+    AccessListWithGasUsed, AnyTransactionReceipt, BlockBlobSidecar, BlockOverrides, Bundle,
     EIP1186AccountProofResponse, EthCallResponse, FeeHistory, Header, Index, RichBlock,
     StateContext, SyncStatus, TransactionRequest, Work,
 };
@@ -134,6 +134,12 @@ where
         Ok(Self::block_receipts(self, block_id).await?)
     }
 
+    /// Handler for: `eth_getBlobSidecars`
+    async fn blob_sidecars(&self, block_id: BlockId) -> Result<Option<Vec<BlockBlobSidecar>>> {
+        trace!(target: "rpc::eth", ?block_id, "Serving eth_getBlobSidecars");
+        Ok(Self::blob_sidecars(self, block_id).await?)
+    }
+
     /// Handler for: `eth_getUncleByBlockHashAndIndex`
     async fn uncle_by_block_hash_and_index(
         &self,
@@ -308,9 +314,16 @@ where
         request: TransactionRequest,
         block_number: Option<BlockId>,
         state_override: Option<StateOverride>,
+        block_overrides: Option<Box<BlockOverrides>>,
     ) -> Result<U256> {
-        trace!(target: "rpc::eth", ?request, ?block_number, "Serving eth_estimateGas");
-        Ok(self.estimate_gas_at(request, block_number.unwrap_or_default(), state_override).await?)
+        trace!(target: "rpc::eth", ?request, ?block_number, ?state_override, ?block_overrides, "Serving eth_estimateGas");
+        Ok(self
+            .estimate_gas_at(
+                request,
+                block_number.unwrap_or_default(),
+                EvmOverrides::new(state_override, block_overrides),
+            )
+            .await?)
     }
 
     /// Handler for: `eth_gasPrice`
@@ -880,7 +880,9 @@ where
 
         let chain_id = self.chain_id();
 
-        let estimated_gas = self.estimate_gas_at(request.clone(), BlockId::pending(), None).await?;
+        let estimated_gas = self
+            .estimate_gas_at(request.clone(), BlockId::pending(), EvmOverrides::default())
+            .await?;
         let gas_limit = estimated_gas;
 
         let TransactionRequest {
@@ -1073,24 +1075,24 @@ where
     {
         let (cfg, block_env, at) = self.evm_env_at(at).await?;
         let this = self.clone();
-        self.inner
-            .blocking_task_pool
-            .spawn(move || {
-                let state = this.state_at(at)?;
-                let mut db = CacheDB::new(StateProviderDatabase::new(state));
-
-                let env = prepare_call_env(
-                    cfg,
-                    block_env,
-                    request,
-                    this.call_gas_limit(),
-                    &mut db,
-                    overrides,
-                )?;
-                f(&mut db, env)
-            })
-            .await
-            .map_err(|_| EthApiError::InternalBlockingTaskError)?
+        let task = self.inner.blocking_task_pool.spawn(move || {
+            let state = this.state_at(at)?;
+            let mut db = CacheDB::new(StateProviderDatabase::new(state));
+
+            let env = prepare_call_env(
+                cfg,
+                block_env,
+                request,
+                this.call_gas_limit(),
+                &mut db,
+                overrides,
+            )?;
+            f(&mut db, env)
+        });
+        self.with_evm_timeout(async {
+            task.await.map_err(|_| EthApiError::InternalBlockingTaskError)?
+        })
+        .await
     }
 
     async fn transact_call_at(
@@ -1373,11 +1375,11 @@ where
         T: Send + 'static,
     {
         let this = self.clone();
-        self.inner
-            .blocking_task_pool
-            .spawn(move || f(this))
-            .await
-            .map_err(|_| EthApiError::InternalBlockingTaskError)?
+        let task = self.inner.blocking_task_pool.spawn(move || f(this));
+        self.with_evm_timeout(async {
+            task.await.map_err(|_| EthApiError::InternalBlockingTaskError)?
+        })
+        .await
     }
 }
 
@@ -1539,7 +1541,7 @@ where
             .ok_or(EthApiError::UnknownBlockNumber)?;
 
         let block = block.unseal();
-        let l1_block_info = reth_evm_optimism::extract_l1_info(&block).ok();
+        let l1_block_info = self.op_l1_block_info(&block);
         let optimism_tx_meta = self.build_op_tx_meta(&tx, l1_block_info, block.timestamp)?;
 
         build_transaction_receipt_with_block_receipts(
@@ -1551,9 +1553,22 @@ where
         )
     }
 
+    /// Returns the L1 block info of the block, or `None` if the block precedes Bedrock.
+    #[cfg(feature = "optimism")]
+    pub(crate) fn op_l1_block_info(
+        &self,
+        block: &reth_primitives::Block,
+    ) -> Option<revm::L1BlockInfo> {
+        if !self.inner.provider.chain_spec().is_bedrock_active_at_block(block.number) {
+            return None
+        }
+        reth_evm_optimism::extract_l1_info(block).ok()
+    }
+
     /// Builds op metadata object using the provided [`TransactionSigned`], L1 block info and
     /// `block_timestamp`. The `L1BlockInfo` is used to calculate the l1 fee and l1 data gas for the
-    /// transaction. If the `L1BlockInfo` is not provided, the meta info will be empty.
+    /// transaction. If the `L1BlockInfo` is not provided, the meta info is read from the L1 fees
+    /// imported with the receipts of the legacy chain, and is empty if there are none.
     #[cfg(feature = "optimism")]
     pub(crate) fn build_op_tx_meta(
         &self,
@@ -1561,10 +1576,30 @@ where
         l1_block_info: Option<revm::L1BlockInfo>,
         block_timestamp: u64,
     ) -> EthResult<crate::eth::api::optimism::OptimismTxMeta> {
-        use crate::eth::{api::optimism::OptimismTxMeta, optimism::OptimismEthApiError};
+        use crate::eth::{
+            api::optimism::{LegacyL1Fee, OptimismTxMeta},
+            optimism::OptimismEthApiError,
+        };
         use reth_evm_optimism::RethL1BlockInfo;
+        use reth_provider::{ReceiptProvider, TransactionsProvider};
 
-        let Some(l1_block_info) = l1_block_info else { return Ok(OptimismTxMeta::default()) };
+        let Some(l1_block_info) = l1_block_info else {
+            if tx.is_deposit() {
+                return Ok(OptimismTxMeta::default())
+            }
+            let provider = &self.inner.provider;
+            let fee = match provider.transaction_id(tx.hash)? {
+                Some(id) => provider.legacy_l1_fee(id)?,
+                None => None,
+            };
+            let Some(fee) = fee else { return Ok(OptimismTxMeta::default()) };
+            return Ok(OptimismTxMeta::legacy(LegacyL1Fee {
+                l1_fee: fee.l1_fee.into(),
+                l1_gas_used: fee.l1_gas_used.into(),
+                l1_gas_price: fee.l1_gas_price.into(),
+                l1_fee_scalar: fee.fee_scalar as f64 / 1_000_000.0,
+            }))
+        };
 
         let (l1_fee, l1_data_gas) = if !tx.is_deposit() {
             let envelope_buf = tx.envelope_encoded();
@@ -1708,17 +1743,41 @@ pub(crate) fn build_transaction_receipt_with_block_receipts(
             .unwrap_or_default()
     };
 
-    let blob_gas_used = transaction.transaction.blob_gas_used();
-    // Blob gas price should only be present if the transaction is a blob transaction
-    let blob_gas_price = blob_gas_used.and_then(|_| meta.excess_blob_gas.map(calc_blob_gasprice));
-    let logs_bloom = receipt.bloom_slow();
-
     // get number of logs in the block
     let mut num_logs = 0;
     for prev_receipt in all_receipts.iter().take(meta.index as usize) {
         num_logs += prev_receipt.logs.len();
     }
 
+    Ok(build_transaction_receipt_with_sender(
+        transaction,
+        from,
+        meta,
+        receipt,
+        gas_used,
+        num_logs,
+        #[cfg(feature = "optimism")]
+        optimism_tx_meta,
+    ))
+}
+
+/// Helper function to construct a transaction receipt from its already recovered sender, the gas
+/// used by the transaction and the number of logs emitted by the previous transactions of the
+/// block.
+pub(crate) fn build_transaction_receipt_with_sender(
+    transaction: TransactionSigned,
+    from: Address,
+    meta: TransactionMeta,
+    receipt: Receipt,
+    gas_used: u64,
+    num_logs: usize,
+    #[cfg(feature = "optimism")] optimism_tx_meta: crate::eth::api::optimism::OptimismTxMeta,
+) -> AnyTransactionReceipt {
+    let blob_gas_used = transaction.transaction.blob_gas_used();
+    // Blob gas price should only be present if the transaction is a blob transaction
+    let blob_gas_price = blob_gas_used.and_then(|_| meta.excess_blob_gas.map(calc_blob_gasprice));
+    let logs_bloom = receipt.bloom_slow();
+
     let mut logs = Vec::with_capacity(receipt.logs.len());
     for (tx_log_idx, log) in receipt.logs.into_iter().enumerate() {
         let rpclog = Log {
@@ -1779,6 +1838,11 @@ pub(crate) fn build_transaction_receipt_with_block_receipts(
             op_fields.l1_fee_scalar =
                 Some(f64::from(l1_block_info.l1_base_fee_scalar) / 1_000_000.0);
             op_fields.l1_gas_price = Some(l1_block_info.l1_base_fee.saturating_to());
+        } else if let Some(legacy) = optimism_tx_meta.legacy {
+            op_fields.l1_fee = Some(legacy.l1_fee);
+            op_fields.l1_gas_used = Some(legacy.l1_gas_used);
+            op_fields.l1_fee_scalar = Some(legacy.l1_fee_scalar);
+            op_fields.l1_gas_price = Some(legacy.l1_gas_price);
         }
 
         res_receipt.other = op_fields.into();
@@ -1793,7 +1857,7 @@ pub(crate) fn build_transaction_receipt_with_block_receipts(
         }
     }
 
-    Ok(res_receipt)
+    res_receipt
 }
 
 #[cfg(test)]
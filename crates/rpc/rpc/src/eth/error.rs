@@ -4,7 +4,8 @@ use crate::result::{internal_rpc_err, invalid_params_rpc_err, rpc_err, rpc_error
 use alloy_sol_types::decode_revert_reason;
 use jsonrpsee::types::{error::CALL_EXECUTION_FAILED_CODE, ErrorObject};
 use reth_errors::RethError;
-use reth_primitives::{revm_primitives::InvalidHeader, Address, Bytes};
+use reth_primitives::{revm_primitives::InvalidHeader, Address, BlockNumber, Bytes};
+use reth_rpc_server_types::constants::PRUNED_HISTORY_UNAVAILABLE_CODE;
 use reth_rpc_types::{
     error::EthRpcErrorCode, request::TransactionInputError, BlockError, ToRpcError,
 };
@@ -49,6 +50,9 @@ pub enum EthApiError {
     /// Thrown when an unknown block or transaction index is encountered
     #[error("unknown block or tx index")]
     UnknownBlockOrTxIndex,
+    /// Thrown when the state of a block is requested but its history is pruned
+    #[error("pruned history unavailable, state at block #{0} is pruned")]
+    StateAtBlockPruned(BlockNumber),
     /// When an invalid block range is provided
     #[error("invalid block range")]
     InvalidBlockRange,
@@ -162,6 +166,9 @@ impl From<EthApiError> for ErrorObject<'static> {
             EthApiError::UnknownSafeOrFinalizedBlock => {
                 rpc_error_with_code(EthRpcErrorCode::UnknownBlock.code(), error.to_string())
             }
+            EthApiError::StateAtBlockPruned(_) => {
+                rpc_error_with_code(PRUNED_HISTORY_UNAVAILABLE_CODE, error.to_string())
+            }
             EthApiError::Unsupported(msg) => internal_rpc_err(msg),
             EthApiError::InternalJsTracerError(msg) => internal_rpc_err(msg),
             EthApiError::InvalidParams(msg) => invalid_params_rpc_err(msg),
@@ -209,6 +216,8 @@ impl From<reth_errors::ProviderError> for EthApiError {
             ProviderError::FinalizedBlockNotFound | ProviderError::SafeBlockNotFound => {
                 Self::UnknownSafeOrFinalizedBlock
             }
+            ProviderError::StateAtBlockPruned(number) => Self::StateAtBlockPruned(number),
+            ProviderError::StorageLayout(err) => Self::InvalidParams(err.to_string()),
             err => Self::Internal(err.into()),
         }
     }
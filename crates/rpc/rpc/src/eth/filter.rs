@@ -2,7 +2,7 @@ use super::cache::EthStateCache;
 use crate::{
     eth::{
         error::EthApiError,
-        logs_utils::{self, append_matching_block_logs},
+        logs_utils::{self, append_matching_block_logs, DeliveredLogs},
     },
     result::{rpc_error_with_code, ToRpcResult},
     EthSubscriptionIdProvider,
@@ -10,11 +10,21 @@ use crate::{
 use core::fmt;
 
 use async_trait::async_trait;
-use jsonrpsee::{core::RpcResult, server::IdProvider};
+use jsonrpsee::{
+    core::{client::ClientT, RpcResult},
+    http_client::{HttpClient, HttpClientBuilder},
+    rpc_params,
+    server::IdProvider,
+};
 use reth_chainspec::ChainInfo;
-use reth_primitives::{IntoRecoveredTransaction, TxHash};
-use reth_provider::{BlockIdReader, BlockReader, EvmEnvProvider, ProviderError};
+use reth_primitives::{BlockNumber, Bloom, IntoRecoveredTransaction, TxHash, B256, U64};
+use reth_provider::{
+    BlockIdReader, BlockReader, EvmEnvProvider, ProviderError, ProviderResult,
+    PruneCheckpointReader, ReorgJournalReader,
+};
+use reth_prune_types::PruneSegment;
 use reth_rpc_api::EthFilterApiServer;
+use reth_rpc_server_types::constants::PRUNED_HISTORY_UNAVAILABLE_CODE;
 use reth_rpc_types::{
     BlockNumHash, Filter, FilterBlockOption, FilterChanges, FilterId, FilteredParams, Log,
     PendingTransactionFilterKind,
@@ -22,18 +32,21 @@ use reth_rpc_types::{
 
 use reth_tasks::TaskSpawner;
 use reth_transaction_pool::{NewSubpoolTransactionStream, PoolTransaction, TransactionPool};
+use serde::{Deserialize, Serialize};
 use std::{
     collections::HashMap,
+    io,
     iter::StepBy,
     ops::RangeInclusive,
+    path::{Path, PathBuf},
     sync::Arc,
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 use tokio::{
-    sync::{mpsc::Receiver, Mutex},
+    sync::{mpsc::Receiver, Mutex, Notify},
     time::MissedTickBehavior,
 };
-use tracing::trace;
+use tracing::{trace, warn};
 
 /// The maximum number of headers we read at once when handling a range filter.
 const MAX_HEADERS_RANGE: u64 = 1_000; // with ~530bytes per header this is ~500kb
@@ -56,7 +69,9 @@ where
     ///
     /// See also [`EthFilterConfig`].
     ///
-    /// This also spawns a task that periodically clears stale filters.
+    /// This also spawns a task that periodically clears stale filters, and if a
+    /// [`EthFilterConfig::persistence_path`] is set, restores the filters persisted to it and
+    /// spawns a task that keeps it up to date.
     pub fn new(
         provider: Provider,
         pool: Pool,
@@ -64,11 +79,29 @@ where
         config: EthFilterConfig,
         task_spawner: Box<dyn TaskSpawner>,
     ) -> Self {
-        let EthFilterConfig { max_blocks_per_filter, max_logs_per_response, stale_filter_ttl } =
-            config;
+        let EthFilterConfig {
+            max_blocks_per_filter,
+            max_logs_per_response,
+            stale_filter_ttl,
+            pruned_logs_upstream,
+            max_filters,
+            persistence_path,
+        } = config;
+        let pruned_logs_upstream =
+            pruned_logs_upstream.and_then(|url| match HttpClientBuilder::default().build(&url) {
+                Ok(client) => Some(client),
+                Err(err) => {
+                    warn!(target: "rpc::eth::filter", %url, %err, "Invalid upstream RPC for pruned logs, logs of pruned blocks won't be proxied");
+                    None
+                }
+            });
+        let active_filters = match &persistence_path {
+            Some(path) => ActiveFilters::restore(path, stale_filter_ttl),
+            None => ActiveFilters::default(),
+        };
         let inner = EthFilterInner {
             provider,
-            active_filters: Default::default(),
+            active_filters,
             pool,
             id_provider: Arc::new(EthSubscriptionIdProvider::default()),
             eth_cache,
@@ -78,6 +111,9 @@ where
             // if not set, use the max value, which is effectively no limit
             max_blocks_per_filter: max_blocks_per_filter.unwrap_or(u64::MAX),
             max_logs_per_response: max_logs_per_response.unwrap_or(usize::MAX),
+            max_filters: max_filters.unwrap_or(usize::MAX),
+            pruned_logs_upstream,
+            filters_changed: persistence_path.is_some().then(Notify::new),
         };
 
         let eth_filter = Self { inner: Arc::new(inner) };
@@ -90,6 +126,16 @@ where
             }),
         );
 
+        if let Some(path) = persistence_path {
+            let this = eth_filter.clone();
+            eth_filter.inner.task_spawner.spawn_critical(
+                "eth-filters_persist-filters",
+                Box::pin(async move {
+                    this.persist_filters(path).await;
+                }),
+            );
+        }
+
         eth_filter
     }
 
@@ -113,7 +159,9 @@ where
     /// `stale_filter_ttl` at the given instant.
     pub async fn clear_stale_filters(&self, now: Instant) {
         trace!(target: "rpc::eth", "clear stale filters");
-        self.active_filters().inner.lock().await.retain(|id, filter| {
+        let mut filters = self.active_filters().inner.lock().await;
+        let len = filters.len();
+        filters.retain(|id, filter| {
             let is_valid = (now - filter.last_poll_timestamp) < self.inner.stale_filter_ttl;
 
             if !is_valid {
@@ -121,13 +169,38 @@ where
             }
 
             is_valid
-        })
+        });
+        if filters.len() != len {
+            self.inner.filters_changed();
+        }
+    }
+
+    /// Endless future that writes the log and block filters to the file at `path` whenever they
+    /// change.
+    async fn persist_filters(&self, path: PathBuf) {
+        let Some(filters_changed) = &self.inner.filters_changed else { return };
+        loop {
+            filters_changed.notified().await;
+            let filters = self.active_filters().to_persisted().await;
+            if let Err(err) = write_persisted_filters(&path, &filters).await {
+                warn!(target: "rpc::eth::filter",
+                    %err,
+                    path = %path.display(),
+                    "Failed to persist filters"
+                );
+            }
+        }
     }
 }
 
 impl<Provider, Pool> EthFilter<Provider, Pool>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReader
+        + BlockIdReader
+        + EvmEnvProvider
+        + PruneCheckpointReader
+        + ReorgJournalReader
+        + 'static,
     Pool: TransactionPool + 'static,
     <Pool as TransactionPool>::Transaction: 'static,
 {
@@ -138,13 +211,29 @@ where
 
         // start_block is the block from which we should start fetching changes, the next block from
         // the last time changes were polled, in other words the best block at last poll + 1
-        let (start_block, kind) = {
+        let (start_block, kind, mut removed_logs, rewound) = {
             let mut filters = self.inner.active_filters.inner.lock().await;
-            let filter = filters.get_mut(&id).ok_or(FilterError::FilterNotFound(id))?;
+            let filter =
+                filters.get_mut(&id).ok_or_else(|| FilterError::FilterNotFound(id.clone()))?;
+
+            // if blocks the filter returned the changes of were reorged out since the last poll,
+            // the filter is rewound to return the changes of the new blocks that replaced them
+            let last_block = filter.block;
+            let removed_logs = match filter.kind {
+                FilterKind::Log(_) | FilterKind::Block => self.inner.rewind_reorged(filter)?,
+                FilterKind::PendingTransaction(_) => Vec::new(),
+            };
+            let rewound = filter.block < last_block;
+            if rewound {
+                self.inner.filters_changed();
+            }
 
             if filter.block > best_number {
                 // no new blocks since the last poll
-                return Ok(FilterChanges::Empty)
+                if removed_logs.is_empty() {
+                    return Ok(FilterChanges::Empty)
+                }
+                return Ok(FilterChanges::Logs(removed_logs))
             }
 
             // update filter
@@ -154,8 +243,9 @@ where
             std::mem::swap(&mut filter.block, &mut block);
             filter.last_poll_timestamp = Instant::now();
 
-            (block, filter.kind.clone())
+            (block, filter.kind.clone(), removed_logs, rewound)
         };
+        self.inner.filters_changed();
 
         match kind {
             FilterKind::PendingTransaction(filter) => Ok(filter.drain().await),
@@ -190,11 +280,26 @@ where
                         (start_block, best_number)
                     }
                 };
-                let logs = self
+                let mut logs = self
                     .inner
                     .get_logs_in_block_range(&filter, from_block_number, to_block_number, info)
                     .await?;
-                Ok(FilterChanges::Logs(logs))
+
+                let mut filters = self.inner.active_filters.inner.lock().await;
+                if let Some(filter) = filters.get_mut(&id) {
+                    if rewound {
+                        // a poll between the commit of the reorg and its record in the journal
+                        // may have returned the logs of new blocks already
+                        logs.retain(|log| {
+                            log.block_hash.map_or(true, |hash| !filter.delivered.contains(&hash))
+                        });
+                    }
+                    filter.delivered.record_logs(&logs);
+                }
+
+                // removed logs come first, followed by the logs of the new blocks
+                removed_logs.extend(logs);
+                Ok(FilterChanges::Logs(removed_logs))
             }
         }
     }
@@ -224,7 +329,12 @@ where
 #[async_trait]
 impl<Provider, Pool> EthFilterApiServer for EthFilter<Provider, Pool>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReader
+        + BlockIdReader
+        + EvmEnvProvider
+        + PruneCheckpointReader
+        + ReorgJournalReader
+        + 'static,
     Pool: TransactionPool + 'static,
 {
     /// Handler for `eth_newFilter`
@@ -289,6 +399,7 @@ where
         let mut filters = self.inner.active_filters.inner.lock().await;
         if filters.remove(&id).is_some() {
             trace!(target: "rpc::eth::filter", ?id, "uninstalled filter");
+            self.inner.filters_changed();
             Ok(true)
         } else {
             Ok(false)
@@ -339,11 +450,31 @@ struct EthFilterInner<Provider, Pool> {
     task_spawner: Box<dyn TaskSpawner>,
     /// Duration since the last filter poll, after which the filter is considered stale
     stale_filter_ttl: Duration,
+    /// Maximum number of installed filters
+    max_filters: usize,
+    /// The archive node that log queries for blocks with pruned receipts are proxied to.
+    pruned_logs_upstream: Option<HttpClient>,
+    /// Notified when the filters change, if they are persisted.
+    filters_changed: Option<Notify>,
+}
+
+impl<Provider, Pool> EthFilterInner<Provider, Pool> {
+    /// Signals the task persisting the filters that they changed.
+    fn filters_changed(&self) {
+        if let Some(filters_changed) = &self.filters_changed {
+            filters_changed.notify_one();
+        }
+    }
 }
 
 impl<Provider, Pool> EthFilterInner<Provider, Pool>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReader
+        + BlockIdReader
+        + EvmEnvProvider
+        + PruneCheckpointReader
+        + ReorgJournalReader
+        + 'static,
     Pool: TransactionPool + 'static,
 {
     /// Returns logs matching given filter object.
@@ -357,6 +488,16 @@ where
                     .header_by_hash_or_number(block_hash.into())?
                     .ok_or(ProviderError::HeaderNotFound(block_hash.into()))?;
 
+                // the receipts of the block are pruned, but its bloom still tells whether it can
+                // contain matching logs
+                let earliest_receipts_block = self.earliest_receipts_block()?;
+                if block.number < earliest_receipts_block {
+                    if !filter_matches_bloom(&filter, block.logs_bloom) {
+                        return Ok(Vec::new())
+                    }
+                    return self.pruned_logs(filter, earliest_receipts_block).await
+                }
+
                 // we also need to ensure that the receipts are available and return an error if
                 // not, in case the block hash been reorged
                 let receipts = self
@@ -401,19 +542,118 @@ where
         }
     }
 
+    /// Returns the first block whose receipts are available, the receipts of all blocks before it
+    /// are pruned.
+    ///
+    /// Only full receipts pruning is considered, receipts that are retained for the contracts of
+    /// the receipts log filter are served as usual.
+    fn earliest_receipts_block(&self) -> Result<BlockNumber, FilterError> {
+        Ok(self
+            .provider
+            .get_prune_checkpoint(PruneSegment::Receipts)?
+            .and_then(|checkpoint| checkpoint.block_number)
+            .map_or(0, |number| number + 1))
+    }
+
+    /// Returns the logs of a filter that matches blocks with pruned receipts.
+    ///
+    /// The query is proxied to the upstream RPC if one is configured, otherwise this returns
+    /// [`FilterError::ReceiptsPruned`]. Headers are never pruned, so the logs of the upstream RPC
+    /// are validated against the local chain.
+    async fn pruned_logs(
+        &self,
+        filter: Filter,
+        earliest_receipts_block: BlockNumber,
+    ) -> Result<Vec<Log>, FilterError> {
+        let Some(upstream) = &self.pruned_logs_upstream else {
+            return Err(FilterError::ReceiptsPruned(earliest_receipts_block))
+        };
+        trace!(target: "rpc::eth::filter", ?filter, "proxying logs of pruned blocks");
+        let logs: Vec<Log> = upstream
+            .request("eth_getLogs", rpc_params![filter])
+            .await
+            .map_err(FilterError::Upstream)?;
+        self.validate_upstream_logs(&logs)?;
+        Ok(logs)
+    }
+
+    /// Checks that all logs of the upstream RPC belong to canonical blocks of the local chain.
+    fn validate_upstream_logs(&self, logs: &[Log]) -> Result<(), FilterError> {
+        for log in logs {
+            let (Some(number), Some(hash)) = (log.block_number, log.block_hash) else {
+                return Err(FilterError::UpstreamMismatch("log without block".to_string()))
+            };
+            if self.provider.block_hash(number)? != Some(hash) {
+                warn!(target: "rpc::eth::filter", number, %hash, "Upstream logs don't match the local chain");
+                return Err(FilterError::UpstreamMismatch(format!(
+                    "block #{number} {hash} is not canonical"
+                )))
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the first block in the given _inclusive_ range whose bloom matches the filter.
+    fn first_bloom_match(
+        &self,
+        filter: &Filter,
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<Option<BlockNumber>, FilterError> {
+        for (from, to) in
+            BlockRangeInclusiveIter::new(from_block..=to_block, self.max_headers_range)
+        {
+            let headers = self.provider.headers_range(from..=to)?;
+            if let Some(header) =
+                headers.iter().find(|header| filter_matches_bloom(filter, header.logs_bloom))
+            {
+                return Ok(Some(header.number))
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the sequence number the next reorg recorded in the journal gets.
+    fn next_reorg_id(&self) -> ProviderResult<u64> {
+        Ok(self.provider.last_reorg_id()?.map_or(0, |id| id + 1))
+    }
+
+    /// Rewinds the filter past the reorgs recorded in the journal since the last poll, see
+    /// [`ActiveFilter::rewind`], and returns the logs of the reorged out blocks.
+    fn rewind_reorged(&self, filter: &mut ActiveFilter) -> ProviderResult<Vec<Log>> {
+        let reorgs_from = match filter.reorgs_from {
+            Some(reorgs_from) => reorgs_from,
+            None => self.next_reorg_id()?,
+        };
+        let mut removed_logs = Vec::new();
+        filter.reorgs_from = Some(reorgs_from);
+        for (id, reorg) in self.provider.reorgs(reorgs_from, usize::MAX)? {
+            removed_logs.extend(filter.rewind(reorg.fork_block, &reorg.old_blocks));
+            filter.reorgs_from = Some(id + 1);
+        }
+        Ok(removed_logs)
+    }
+
     /// Installs a new filter and returns the new identifier.
     async fn install_filter(&self, kind: FilterKind) -> RpcResult<FilterId> {
         let last_poll_block_number = self.provider.best_block_number().to_rpc_result()?;
+        let reorgs_from = self.next_reorg_id().to_rpc_result()?;
         let id = FilterId::from(self.id_provider.next_id());
         let mut filters = self.active_filters.inner.lock().await;
+        if filters.len() >= self.max_filters {
+            return Err(FilterError::TooManyFilters(self.max_filters).into())
+        }
         filters.insert(
             id.clone(),
             ActiveFilter {
                 block: last_poll_block_number,
                 last_poll_timestamp: Instant::now(),
+                reorgs_from: Some(reorgs_from),
+                delivered: DeliveredLogs::default(),
                 kind,
             },
         );
+        self.filters_changed();
         Ok(id)
     }
 
@@ -422,6 +662,7 @@ where
     /// Returns an error if:
     ///  - underlying database error
     ///  - amount of matches exceeds configured limit
+    ///  - the receipts of a matching block are pruned and no upstream RPC is configured
     async fn get_logs_in_block_range(
         &self,
         filter: &Filter,
@@ -464,6 +705,22 @@ where
             return Ok(all_logs)
         }
 
+        // the headers of blocks with pruned receipts are still available, so their blooms tell
+        // whether the pruned part of the range can contain matching logs at all
+        let earliest_receipts_block = self.earliest_receipts_block()?;
+        let mut receipts_from_block = from_block;
+        if from_block < earliest_receipts_block {
+            let pruned_to = to_block.min(earliest_receipts_block - 1);
+            if let Some(first_match) = self.first_bloom_match(filter, from_block, pruned_to)? {
+                let pruned_filter = filter.clone().from_block(first_match).to_block(pruned_to);
+                all_logs.extend(self.pruned_logs(pruned_filter, earliest_receipts_block).await?);
+                if from_block != to_block && all_logs.len() > self.max_logs_per_response {
+                    return Err(FilterError::QueryExceedsMaxResults(self.max_logs_per_response))
+                }
+            }
+            receipts_from_block = pruned_to + 1;
+        }
+
         // derive bloom filters from filter input, so we can check headers for matching logs
         let address_filter = FilteredParams::address_filter(&filter.address);
         let topics_filter = FilteredParams::topics_filter(&filter.topics);
@@ -471,7 +728,7 @@ where
         // loop over the range of new blocks and check logs if the filter matches the log's bloom
         // filter
         for (from, to) in
-            BlockRangeInclusiveIter::new(from_block..=to_block, self.max_headers_range)
+            BlockRangeInclusiveIter::new(receipts_from_block..=to_block, self.max_headers_range)
         {
             let headers = self.provider.headers_range(from..=to)?;
 
@@ -534,6 +791,20 @@ pub struct EthFilterConfig {
     /// A filter is considered stale if it has not been polled for longer than this duration and
     /// will be removed.
     pub stale_filter_ttl: Duration,
+    /// The URL of an archive node that `eth_getLogs` queries are proxied to, if they match blocks
+    /// whose receipts are pruned. The logs it returns must belong to canonical blocks of the local
+    /// chain.
+    ///
+    /// If `None`, such queries fail with an error that contains the earliest block with receipts.
+    pub pruned_logs_upstream: Option<String>,
+    /// Maximum number of installed filters, new filters are rejected once it's reached.
+    ///
+    /// If `None` then no limit is enforced.
+    pub max_filters: Option<usize>,
+    /// The file the log and block filters are persisted to, so they survive restarts.
+    ///
+    /// If `None`, the filters are lost on shutdown.
+    pub persistence_path: Option<PathBuf>,
 }
 
 impl EthFilterConfig {
@@ -555,6 +826,24 @@ impl EthFilterConfig {
         self.stale_filter_ttl = duration;
         self
     }
+
+    /// Sets the archive node that queries for logs of blocks with pruned receipts are proxied to.
+    pub fn pruned_logs_upstream(mut self, url: impl Into<String>) -> Self {
+        self.pruned_logs_upstream = Some(url.into());
+        self
+    }
+
+    /// Sets the maximum number of installed filters.
+    pub const fn max_filters(mut self, num: usize) -> Self {
+        self.max_filters = Some(num);
+        self
+    }
+
+    /// Sets the file the log and block filters are persisted to.
+    pub fn persistence_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.persistence_path = Some(path.into());
+        self
+    }
 }
 
 impl Default for EthFilterConfig {
@@ -564,6 +853,9 @@ impl Default for EthFilterConfig {
             max_logs_per_response: None,
             // 5min
             stale_filter_ttl: Duration::from_secs(5 * 60),
+            pruned_logs_upstream: None,
+            max_filters: None,
+            persistence_path: None,
         }
     }
 }
@@ -574,6 +866,117 @@ pub struct ActiveFilters {
     inner: Arc<Mutex<HashMap<FilterId, ActiveFilter>>>,
 }
 
+impl ActiveFilters {
+    /// Restores the filters persisted to the file at `path`, skipping the ones that were not
+    /// polled within the `ttl`.
+    fn restore(path: &Path, ttl: Duration) -> Self {
+        let persisted = match read_persisted_filters(path) {
+            Ok(persisted) => persisted,
+            Err(err) => {
+                warn!(target: "rpc::eth::filter",
+                    %err,
+                    path = %path.display(),
+                    "Failed to restore persisted filters"
+                );
+                Vec::new()
+            }
+        };
+
+        let now = Instant::now();
+        let unix_now = unix_timestamp();
+        let filters = persisted
+            .into_iter()
+            .filter_map(|filter| {
+                let since_last_poll =
+                    Duration::from_secs(unix_now.saturating_sub(filter.last_poll_timestamp));
+                if since_last_poll >= ttl {
+                    return None
+                }
+                let kind = match filter.filter {
+                    Some(filter) => FilterKind::Log(Box::new(filter)),
+                    None => FilterKind::Block,
+                };
+                let active = ActiveFilter {
+                    block: filter.block,
+                    last_poll_timestamp: now.checked_sub(since_last_poll).unwrap_or(now),
+                    reorgs_from: filter.reorgs_from,
+                    delivered: DeliveredLogs::default(),
+                    kind,
+                };
+                Some((filter.id, active))
+            })
+            .collect::<HashMap<_, _>>();
+        trace!(target: "rpc::eth::filter", filters = filters.len(), "restored persisted filters");
+
+        Self { inner: Arc::new(Mutex::new(filters)) }
+    }
+
+    /// Returns the log and block filters in their persisted form, pending transaction filters
+    /// can't be persisted.
+    async fn to_persisted(&self) -> Vec<PersistedFilter> {
+        let now = Instant::now();
+        let unix_now = unix_timestamp();
+        self.inner
+            .lock()
+            .await
+            .iter()
+            .filter_map(|(id, active)| {
+                let filter = match &active.kind {
+                    FilterKind::Log(filter) => Some(filter.as_ref().clone()),
+                    FilterKind::Block => None,
+                    FilterKind::PendingTransaction(_) => return None,
+                };
+                Some(PersistedFilter {
+                    id: id.clone(),
+                    block: active.block,
+                    last_poll_timestamp: unix_now
+                        .saturating_sub((now - active.last_poll_timestamp).as_secs()),
+                    reorgs_from: active.reorgs_from,
+                    filter,
+                })
+            })
+            .collect()
+    }
+}
+
+/// A log or block filter, as persisted to the datadir.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PersistedFilter {
+    id: FilterId,
+    /// The block the next poll returns the changes from.
+    block: u64,
+    /// Unix timestamp of the last poll, in seconds.
+    last_poll_timestamp: u64,
+    /// The first sequence number of the reorg journal the next poll consults.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    reorgs_from: Option<u64>,
+    /// The filter of a log filter, `None` for a block filter.
+    filter: Option<Filter>,
+}
+
+/// Reads the persisted filters from the file at `path`, there are none if it doesn't exist.
+fn read_persisted_filters(path: &Path) -> io::Result<Vec<PersistedFilter>> {
+    match std::fs::read(path) {
+        Ok(contents) => Ok(serde_json::from_slice(&contents)?),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(err) => Err(err),
+    }
+}
+
+/// Writes the persisted filters to the file at `path`, replacing it at once so a crash doesn't
+/// leave a partially written file behind.
+async fn write_persisted_filters(path: &Path, filters: &[PersistedFilter]) -> io::Result<()> {
+    let contents = serde_json::to_vec(filters)?;
+    let tmp_path = path.with_extension("tmp");
+    tokio::fs::write(&tmp_path, contents).await?;
+    tokio::fs::rename(tmp_path, path).await
+}
+
+fn unix_timestamp() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
 /// An installed filter
 #[derive(Debug)]
 struct ActiveFilter {
@@ -581,10 +984,30 @@ struct ActiveFilter {
     block: u64,
     /// Last time this filter was polled.
     last_poll_timestamp: Instant,
+    /// The first sequence number of the reorg journal the next poll consults, `None` if the
+    /// filter is restored from a file that doesn't have it, in which case the next poll starts at
+    /// the end of the journal.
+    reorgs_from: Option<u64>,
+    /// The logs a log filter returned for the most recent blocks.
+    delivered: DeliveredLogs,
     /// What kind of filter it is.
     kind: FilterKind,
 }
 
+impl ActiveFilter {
+    /// Rewinds the filter to the first block above the fork block of a reorg, if it already
+    /// returned the changes of that block, and returns the logs it returned for the reorged out
+    /// blocks, marked as removed.
+    fn rewind(&mut self, fork_block: BlockNumber, old_blocks: &[B256]) -> Vec<Log> {
+        let first_replaced = fork_block + 1;
+        if first_replaced >= self.block {
+            return Vec::new()
+        }
+        self.block = first_replaced;
+        self.delivered.remove_blocks(old_blocks)
+    }
+}
+
 /// A receiver for pending transactions that returns all new transactions since the last poll.
 #[derive(Debug, Clone)]
 struct PendingTransactionsReceiver {
@@ -693,6 +1116,19 @@ pub enum FilterError {
     QueryExceedsMaxBlocks(u64),
     #[error("query exceeds max results {0}")]
     QueryExceedsMaxResults(usize),
+    /// Error thrown when the receipts of a matching block are pruned, contains the earliest block
+    /// with receipts.
+    #[error("pruned history unavailable, earliest available block is {0}")]
+    ReceiptsPruned(BlockNumber),
+    /// Error thrown when the maximum number of installed filters is reached.
+    #[error("too many installed filters, max {0}")]
+    TooManyFilters(usize),
+    /// Error thrown when the upstream RPC failed to serve logs of pruned blocks.
+    #[error("upstream RPC error: {0}")]
+    Upstream(jsonrpsee::core::client::Error),
+    /// Error thrown when the logs of the upstream RPC don't belong to the local chain.
+    #[error("upstream response doesn't match the local chain: {0}")]
+    UpstreamMismatch(String),
     #[error(transparent)]
     EthAPIError(#[from] EthApiError),
     /// Error thrown when a spawned task failed to deliver a response.
@@ -712,6 +1148,21 @@ impl From<FilterError> for jsonrpsee::types::error::ErrorObject<'static> {
                 rpc_error_with_code(jsonrpsee::types::error::INTERNAL_ERROR_CODE, err.to_string())
             }
             FilterError::EthAPIError(err) => err.into(),
+            err @ FilterError::ReceiptsPruned(earliest) => {
+                jsonrpsee::types::error::ErrorObject::owned(
+                    PRUNED_HISTORY_UNAVAILABLE_CODE,
+                    err.to_string(),
+                    Some(U64::from(earliest)),
+                )
+            }
+            err @ FilterError::TooManyFilters(_) => rpc_error_with_code(
+                jsonrpsee::types::error::TOO_MANY_SUBSCRIPTIONS_CODE,
+                err.to_string(),
+            ),
+            FilterError::Upstream(jsonrpsee::core::client::Error::Call(err)) => err,
+            err @ FilterError::Upstream(_) | err @ FilterError::UpstreamMismatch(_) => {
+                rpc_error_with_code(jsonrpsee::types::error::INTERNAL_ERROR_CODE, err.to_string())
+            }
             err @ FilterError::InvalidBlockRangeParams |
             err @ FilterError::QueryExceedsMaxBlocks(_) |
             err @ FilterError::QueryExceedsMaxResults(_) => {
@@ -727,6 +1178,12 @@ impl From<ProviderError> for FilterError {
     }
 }
 
+/// Returns whether the bloom can contain logs that match the filter.
+fn filter_matches_bloom(filter: &Filter, bloom: Bloom) -> bool {
+    FilteredParams::matches_address(bloom, &FilteredParams::address_filter(&filter.address)) &&
+        FilteredParams::matches_topics(bloom, &FilteredParams::topics_filter(&filter.topics))
+}
+
 /// An iterator that yields _inclusive_ block ranges of a given step size
 #[derive(Debug)]
 struct BlockRangeInclusiveIter {
@@ -758,6 +1215,7 @@ impl Iterator for BlockRangeInclusiveIter {
 mod tests {
     use super::*;
     use rand::{thread_rng, Rng};
+    use reth_primitives::{Address, BloomInput, B256};
 
     #[test]
     fn test_block_range_iter() {
@@ -781,4 +1239,118 @@ mod tests {
             assert_eq!(end, *range.end());
         }
     }
+
+    #[test]
+    fn test_filter_matches_bloom() {
+        let address = Address::with_last_byte(1);
+        let mut bloom = Bloom::default();
+        bloom.accrue(BloomInput::Raw(address.as_slice()));
+
+        assert!(filter_matches_bloom(&Filter::new(), bloom));
+        assert!(filter_matches_bloom(&Filter::new().address(address), bloom));
+        assert!(!filter_matches_bloom(&Filter::new().address(Address::with_last_byte(2)), bloom));
+        assert!(!filter_matches_bloom(&Filter::new().event_signature(B256::ZERO), bloom));
+    }
+
+    #[tokio::test]
+    async fn test_persisted_filters() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rpc-filters.json");
+        let ttl = Duration::from_secs(2);
+        let log_filter = Filter::new().address(Address::with_last_byte(1));
+
+        let filters = ActiveFilters::default();
+        {
+            let now = Instant::now();
+            let mut inner = filters.inner.lock().await;
+            for (id, block, last_poll_timestamp, kind) in [
+                ("0x1", 10, now, FilterKind::Log(Box::new(log_filter.clone()))),
+                ("0x2", 20, now, FilterKind::Block),
+                ("0x3", 30, now - Duration::from_secs(10), FilterKind::Block),
+            ] {
+                inner.insert(
+                    FilterId::Str(id.to_string()),
+                    ActiveFilter {
+                        block,
+                        last_poll_timestamp,
+                        reorgs_from: Some(block / 10),
+                        delivered: DeliveredLogs::default(),
+                        kind,
+                    },
+                );
+            }
+        }
+        write_persisted_filters(&path, &filters.to_persisted().await).await.unwrap();
+
+        // the stale filter is not restored
+        let mut restored = ActiveFilters::restore(&path, ttl).to_persisted().await;
+        restored.sort_by_key(|filter| filter.block);
+        let restored = restored
+            .into_iter()
+            .map(|filter| (filter.id, filter.block, filter.reorgs_from, filter.filter))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            restored,
+            [
+                (FilterId::Str("0x1".to_string()), 10, Some(1), Some(log_filter)),
+                (FilterId::Str("0x2".to_string()), 20, Some(2), None),
+            ]
+        );
+
+        let missing = ActiveFilters::restore(&dir.path().join("missing.json"), ttl);
+        assert!(missing.inner.lock().await.is_empty());
+    }
+
+    #[test]
+    fn test_rewind_deep_reorg() {
+        let block_hash = |number: u64, fork: u8| {
+            let mut hash = B256::with_last_byte(fork);
+            hash[..8].copy_from_slice(&number.to_be_bytes());
+            hash
+        };
+        let log = |number: u64, fork: u8| Log {
+            block_hash: Some(block_hash(number, fork)),
+            block_number: Some(number),
+            ..Default::default()
+        };
+
+        let mut filter = ActiveFilter {
+            block: 31,
+            last_poll_timestamp: Instant::now(),
+            reorgs_from: Some(0),
+            delivered: DeliveredLogs::default(),
+            kind: FilterKind::Log(Box::new(Filter::new())),
+        };
+        // the filter returned a log of each block up to block 30
+        filter.delivered.record_logs(&(1..=30).map(|number| log(number, 0)).collect::<Vec<_>>());
+
+        // a reorg of blocks the filter didn't return the changes of yet doesn't rewind it
+        assert!(filter.rewind(30, &[block_hash(31, 0)]).is_empty());
+        assert_eq!(filter.block, 31);
+
+        // a reorg 20 blocks deep
+        let old_blocks = (11..=30).map(|number| block_hash(number, 0)).collect::<Vec<_>>();
+        let removed = filter.rewind(10, &old_blocks);
+        assert_eq!(filter.block, 11);
+        assert_eq!(
+            removed,
+            (11..=30).map(|number| Log { removed: true, ..log(number, 0) }).collect::<Vec<_>>()
+        );
+
+        // a deeper reorg replacing blocks of both chains
+        filter.delivered.record_logs(&[log(11, 1)]);
+        filter.block = 12;
+        let old_blocks = (6..=10)
+            .map(|number| block_hash(number, 0))
+            .chain([block_hash(11, 1)])
+            .collect::<Vec<_>>();
+        let removed = filter.rewind(5, &old_blocks);
+        assert_eq!(filter.block, 6);
+        assert_eq!(
+            removed.iter().map(|log| (log.block_number, log.removed)).collect::<Vec<_>>(),
+            [6, 7, 8, 9, 10, 11].map(|number| (Some(number), true))
+        );
+        assert!(filter.delivered.contains(&block_hash(5, 0)));
+        assert!(!filter.delivered.contains(&block_hash(6, 0)));
+    }
 }
@@ -1,10 +1,62 @@
 use super::filter::FilterError;
 use alloy_primitives::TxHash;
 use reth_chainspec::ChainInfo;
-use reth_primitives::{BlockNumHash, Receipt};
+use reth_primitives::{BlockNumHash, Receipt, B256};
 use reth_provider::{BlockReader, ProviderError};
 use reth_rpc_types::{FilteredParams, Log};
 
+/// Number of blocks below the tip whose delivered logs are kept, so they can be delivered again as
+/// removed if the blocks are reorged out.
+pub(crate) const DELIVERED_LOGS_DEPTH: u64 = 64;
+
+/// The logs recently delivered to a log filter or subscription, by block.
+///
+/// If blocks are reorged out, their logs are delivered again with `removed: true`, so consumers can
+/// roll back what they indexed. Only the logs of the last [`DELIVERED_LOGS_DEPTH`] blocks are kept.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DeliveredLogs {
+    /// The delivered blocks and their logs, in the order they were delivered.
+    blocks: Vec<(BlockNumHash, Vec<Log>)>,
+}
+
+impl DeliveredLogs {
+    /// Records the logs delivered for a block, which may be none.
+    ///
+    /// Blocks that fell more than [`DELIVERED_LOGS_DEPTH`] blocks below it are forgotten.
+    pub(crate) fn record_block(&mut self, block: BlockNumHash, logs: Vec<Log>) {
+        self.blocks.retain(|(recorded, _)| recorded.number + DELIVERED_LOGS_DEPTH >= block.number);
+        self.blocks.push((block, logs));
+    }
+
+    /// Records delivered logs, which are grouped by their block.
+    pub(crate) fn record_logs(&mut self, logs: &[Log]) {
+        for block_logs in logs.chunk_by(|a, b| a.block_hash == b.block_hash) {
+            let log = &block_logs[0];
+            let (Some(hash), Some(number)) = (log.block_hash, log.block_number) else { continue };
+            self.record_block(BlockNumHash::new(number, hash), block_logs.to_vec());
+        }
+    }
+
+    /// Returns `true` if the logs of the block were delivered.
+    pub(crate) fn contains(&self, hash: &B256) -> bool {
+        self.blocks.iter().any(|(block, _)| block.hash == *hash)
+    }
+
+    /// Forgets the given blocks, returning the logs delivered for them marked as removed, in the
+    /// order they were delivered.
+    pub(crate) fn remove_blocks(&mut self, hashes: &[B256]) -> Vec<Log> {
+        let mut removed = Vec::new();
+        self.blocks.retain_mut(|(block, logs)| {
+            if !hashes.contains(&block.hash) {
+                return true
+            }
+            removed.extend(logs.drain(..).map(|log| Log { removed: true, ..log }));
+            false
+        });
+        removed
+    }
+}
+
 /// Returns all matching of a block's receipts when the transaction hashes are known.
 pub(crate) fn matching_block_logs_with_tx_hashes<'a, I>(
     filter: &FilteredParams,
@@ -201,6 +253,56 @@ mod tests {
         assert_eq!(range, (info.best_number, info.best_number));
     }
 
+    /// Returns the block `number` of the chain `fork`.
+    fn block(number: u64, fork: u8) -> BlockNumHash {
+        let mut hash = B256::with_last_byte(fork);
+        hash[..8].copy_from_slice(&number.to_be_bytes());
+        BlockNumHash::new(number, hash)
+    }
+
+    fn block_logs(block: BlockNumHash, count: u64) -> Vec<Log> {
+        (0..count)
+            .map(|index| Log {
+                block_hash: Some(block.hash),
+                block_number: Some(block.number),
+                log_index: Some(index),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn removes_logs_of_deep_reorg() {
+        let mut delivered = DeliveredLogs::default();
+        let logs = (1..=20).flat_map(|number| block_logs(block(number, 0), 2)).collect::<Vec<_>>();
+        delivered.record_logs(&logs);
+        // a block without matching logs
+        delivered.record_block(block(21, 0), Vec::new());
+
+        // the chain is reorged to a fork of block 9, 12 blocks deep
+        let old_blocks = (10..=21).map(|number| block(number, 0).hash).collect::<Vec<_>>();
+        let removed = delivered.remove_blocks(&old_blocks);
+        assert_eq!(removed.len(), 22);
+        assert!(removed.iter().all(|log| log.removed));
+        assert_eq!(
+            removed,
+            logs[18..].iter().map(|log| Log { removed: true, ..log.clone() }).collect::<Vec<_>>()
+        );
+        assert!(delivered.contains(&block(9, 0).hash));
+        assert!(!delivered.contains(&block(10, 0).hash));
+
+        // reorging out the same blocks again removes nothing
+        assert!(delivered.remove_blocks(&old_blocks).is_empty());
+
+        delivered.record_logs(&block_logs(block(10, 1), 1));
+        assert!(delivered.contains(&block(10, 1).hash));
+
+        // blocks too far below the tip are forgotten
+        delivered.record_block(block(10 + DELIVERED_LOGS_DEPTH, 1), Vec::new());
+        assert!(!delivered.contains(&block(9, 0).hash));
+        assert!(delivered.contains(&block(10, 1).hash));
+    }
+
     #[test]
     fn parse_log_from_only() {
         let s = r#"{"fromBlock":"0xf47a42","address":["0x7de93682b9b5d80d45cd371f7a14f74d49b0914c","0x0f00392fcb466c0e4e4310d81b941e07b4d5a079","0xebf67ab8cff336d3f609127e8bbf8bd6dd93cd81"],"topics":["0x0559884fd3a460db3073b7fc896cc77986f16e378210ded43186175bf646fc5f"]}"#;
@@ -21,6 +21,9 @@ pub use api::{
     fee_history::{fee_history_cache_new_blocks_task, FeeHistoryCache, FeeHistoryCacheConfig},
     EthApi, EthApiSpec, EthTransactions, TransactionSource, RPC_DEFAULT_GAS_CAP,
 };
+pub(crate) use api::pending_block::{
+    pre_block_beacon_root_contract_call, pre_block_blockhashes_update,
+};
 
 pub use bundle::EthBundle;
 pub use filter::{EthFilter, EthFilterConfig};
@@ -1,7 +1,7 @@
 //! `eth_` `PubSub` RPC handler implementation
 
 use crate::{
-    eth::logs_utils,
+    eth::logs_utils::{self, DeliveredLogs},
     result::{internal_rpc_err, invalid_params_rpc_err},
 };
 use futures::StreamExt;
@@ -9,24 +9,28 @@ use jsonrpsee::{
     server::SubscriptionMessage, types::ErrorObject, PendingSubscriptionSink, SubscriptionSink,
 };
 use reth_network_api::NetworkInfo;
-use reth_primitives::{IntoRecoveredTransaction, TxHash};
-use reth_provider::{BlockReader, CanonStateSubscriptions, EvmEnvProvider};
+use reth_primitives::{BlockNumHash, BlockNumber, IntoRecoveredTransaction, TxHash};
+use reth_provider::{
+    BlockReader, CanonStateNotification, CanonStateSubscriptions, EvmEnvProvider, ProviderResult,
+    ReorgJournalReader,
+};
 use reth_rpc_api::EthPubSubApiServer;
 use reth_rpc_types::{
     pubsub::{
         Params, PubSubSyncStatus, SubscriptionKind, SubscriptionResult as EthSubscriptionResult,
         SyncStatusMetadata,
     },
-    FilteredParams, Header, Log,
+    EthSubscriptionParams, FilteredParams, Header, Log, PendingTransactionsParams,
 };
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
-use reth_transaction_pool::{NewTransactionEvent, TransactionPool};
+use reth_transaction_pool::{NewTransactionEvent, PoolTransaction, TransactionPool};
 use serde::Serialize;
 use std::sync::Arc;
 use tokio_stream::{
-    wrappers::{BroadcastStream, ReceiverStream},
+    wrappers::{errors::BroadcastStreamRecvError, BroadcastStream, ReceiverStream},
     Stream,
 };
+use tracing::warn;
 
 /// `Eth` pubsub RPC implementation.
 ///
@@ -72,7 +76,7 @@ impl<Provider, Pool, Events, Network> EthPubSub<Provider, Pool, Events, Network>
 impl<Provider, Pool, Events, Network> EthPubSubApiServer
     for EthPubSub<Provider, Pool, Events, Network>
 where
-    Provider: BlockReader + EvmEnvProvider + Clone + 'static,
+    Provider: BlockReader + EvmEnvProvider + ReorgJournalReader + Clone + 'static,
     Pool: TransactionPool + 'static,
     Events: CanonStateSubscriptions + Clone + 'static,
     Network: NetworkInfo + Clone + 'static,
@@ -82,7 +86,7 @@ where
         &self,
         pending: PendingSubscriptionSink,
         kind: SubscriptionKind,
-        params: Option<Params>,
+        params: Option<EthSubscriptionParams>,
     ) -> jsonrpsee::core::SubscriptionResult {
         let sink = pending.accept().await?;
         let pubsub = self.inner.clone();
@@ -99,10 +103,10 @@ async fn handle_accepted<Provider, Pool, Events, Network>(
     pubsub: Arc<EthPubSubInner<Provider, Pool, Events, Network>>,
     accepted_sink: SubscriptionSink,
     kind: SubscriptionKind,
-    params: Option<Params>,
+    params: Option<EthSubscriptionParams>,
 ) -> Result<(), ErrorObject<'static>>
 where
-    Provider: BlockReader + EvmEnvProvider + Clone + 'static,
+    Provider: BlockReader + EvmEnvProvider + ReorgJournalReader + Clone + 'static,
     Pool: TransactionPool + 'static,
     Events: CanonStateSubscriptions + Clone + 'static,
     Network: NetworkInfo + Clone + 'static,
@@ -117,45 +121,60 @@ where
         SubscriptionKind::Logs => {
             // if no params are provided, used default filter params
             let filter = match params {
-                Some(Params::Logs(filter)) => FilteredParams::new(Some(*filter)),
-                Some(Params::Bool(_)) => {
-                    return Err(invalid_params_rpc_err("Invalid params for logs"))
+                Some(EthSubscriptionParams::Standard(Params::Logs(filter))) => {
+                    FilteredParams::new(Some(*filter))
                 }
+                Some(
+                    EthSubscriptionParams::Standard(Params::Bool(_)) |
+                    EthSubscriptionParams::PendingTransactions(_),
+                ) => return Err(invalid_params_rpc_err("Invalid params for logs")),
                 _ => FilteredParams::default(),
             };
             let stream =
                 pubsub.log_stream(filter).map(|log| EthSubscriptionResult::Log(Box::new(log)));
-            pipe_from_stream(accepted_sink, stream).await
+            pipe_from_stream(accepted_sink, Box::pin(stream)).await
         }
         SubscriptionKind::NewPendingTransactions => {
-            if let Some(params) = params {
-                match params {
-                    Params::Bool(true) => {
-                        // full transaction objects requested
-                        let stream = pubsub.full_pending_transaction_stream().map(|tx| {
-                            EthSubscriptionResult::FullTransaction(Box::new(
-                                reth_rpc_types_compat::transaction::from_recovered(
-                                    tx.transaction.to_recovered_transaction(),
-                                ),
-                            ))
-                        });
-                        return pipe_from_stream(accepted_sink, stream).await
-                    }
-                    Params::Bool(false) | Params::None => {
-                        // only hashes requested
-                    }
-                    Params::Logs(_) => {
-                        return Err(invalid_params_rpc_err(
-                            "Invalid params for newPendingTransactions",
-                        ))
-                    }
+            let params = match params {
+                Some(EthSubscriptionParams::PendingTransactions(params)) => params,
+                Some(EthSubscriptionParams::Standard(Params::Bool(full_transactions))) => {
+                    PendingTransactionsParams { full_transactions, ..Default::default() }
                 }
+                Some(EthSubscriptionParams::Standard(Params::None)) | None => Default::default(),
+                Some(EthSubscriptionParams::Standard(Params::Logs(_))) => {
+                    return Err(invalid_params_rpc_err("Invalid params for newPendingTransactions"))
+                }
+            };
+
+            if !params.full_transactions && params.is_unfiltered() {
+                // only hashes requested
+                let stream = pubsub
+                    .pending_transaction_hashes_stream()
+                    .map(EthSubscriptionResult::TransactionHash);
+                return pipe_from_stream(accepted_sink, stream).await
             }
 
+            let full_transactions = params.full_transactions;
             let stream = pubsub
-                .pending_transaction_hashes_stream()
-                .map(EthSubscriptionResult::TransactionHash);
-            pipe_from_stream(accepted_sink, stream).await
+                .full_pending_transaction_stream()
+                .filter(move |tx| {
+                    futures::future::ready(
+                        pubsub.matches_pending_transaction(&params, &tx.transaction.transaction),
+                    )
+                })
+                .map(move |tx| {
+                    if full_transactions {
+                        // full transaction objects requested
+                        EthSubscriptionResult::FullTransaction(Box::new(
+                            reth_rpc_types_compat::transaction::from_recovered(
+                                tx.transaction.to_recovered_transaction(),
+                            ),
+                        ))
+                    } else {
+                        EthSubscriptionResult::TransactionHash(*tx.transaction.hash())
+                    }
+                });
+            pipe_from_stream(accepted_sink, Box::pin(stream)).await
         }
         SubscriptionKind::Syncing => {
             // get new block subscription
@@ -302,11 +321,34 @@ where
     ) -> impl Stream<Item = NewTransactionEvent<<Pool as TransactionPool>::Transaction>> {
         self.pool.new_pending_pool_transactions_listener()
     }
+
+    /// Returns `true` if the pending transaction matches the filters of a `newPendingTransactions`
+    /// subscription.
+    fn matches_pending_transaction(
+        &self,
+        params: &PendingTransactionsParams,
+        tx: &<Pool as TransactionPool>::Transaction,
+    ) -> bool {
+        if let Some(to_addresses) = &params.to_addresses {
+            if !tx.to().map_or(false, |to| to_addresses.contains(&to)) {
+                return false
+            }
+        }
+
+        if let Some(min_tip) = params.min_tip {
+            let base_fee = self.pool.block_info().pending_basefee;
+            if tx.effective_tip_per_gas(base_fee).map_or(true, |tip| tip < min_tip) {
+                return false
+            }
+        }
+
+        true
+    }
 }
 
 impl<Provider, Pool, Events, Network> EthPubSubInner<Provider, Pool, Events, Network>
 where
-    Provider: BlockReader + EvmEnvProvider + 'static,
+    Provider: BlockReader + EvmEnvProvider + ReorgJournalReader + Clone + 'static,
     Events: CanonStateSubscriptions + 'static,
     Network: NetworkInfo + 'static,
     Pool: 'static,
@@ -322,20 +364,141 @@ where
     }
 
     /// Returns a stream that yields all logs that match the given filter.
+    ///
+    /// The logs delivered for blocks that are reorged out are yielded again with `removed: true`.
+    /// If the stream falls behind the canonical state notifications, it catches up through the
+    /// reorg journal and the database, see [`LogStream::catch_up`].
     fn log_stream(&self, filter: FilteredParams) -> impl Stream<Item = Log> {
-        BroadcastStream::new(self.chain_events.subscribe_to_canonical_state())
-            .map(move |canon_state| {
-                canon_state.expect("new block subscription never ends").block_receipts()
-            })
-            .flat_map(futures::stream::iter)
-            .flat_map(move |(block_receipts, removed)| {
-                let all_logs = logs_utils::matching_block_logs_with_tx_hashes(
-                    &filter,
-                    block_receipts.block,
-                    block_receipts.tx_receipts.iter().map(|(tx, receipt)| (*tx, receipt)),
-                    removed,
+        let reorgs_from = match self.provider.last_reorg_id() {
+            Ok(id) => id.map_or(0, |id| id + 1),
+            Err(err) => {
+                warn!(target: "rpc::eth::pubsub", %err, "Failed to read the reorg journal");
+                0
+            }
+        };
+        let state = LogStream {
+            provider: self.provider.clone(),
+            filter,
+            notifications: BroadcastStream::new(self.chain_events.subscribe_to_canonical_state()),
+            delivered: DeliveredLogs::default(),
+            reorgs_from,
+            next_block: self.provider.best_block_number().ok().map(|number| number + 1),
+        };
+        futures::stream::unfold(state, |mut state| async move {
+            let logs = state.next_logs().await?;
+            Some((futures::stream::iter(logs), state))
+        })
+        .flatten()
+    }
+}
+
+/// The state of a `logs` subscription.
+struct LogStream<Provider> {
+    provider: Provider,
+    filter: FilteredParams,
+    notifications: BroadcastStream<CanonStateNotification>,
+    /// The logs delivered for the most recent blocks.
+    delivered: DeliveredLogs,
+    /// The first sequence number of the reorg journal that wasn't consulted yet.
+    reorgs_from: u64,
+    /// The block after the last delivered block, `None` if the best block was unknown when the
+    /// subscription started and no block was delivered yet.
+    next_block: Option<BlockNumber>,
+}
+
+impl<Provider> LogStream<Provider>
+where
+    Provider: BlockReader + ReorgJournalReader,
+{
+    /// Waits for the next canonical state notification and returns the logs to deliver for it,
+    /// `None` if there are no more notifications.
+    async fn next_logs(&mut self) -> Option<Vec<Log>> {
+        match self.notifications.next().await? {
+            Ok(notification) => Some(self.on_notification(&notification)),
+            Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                warn!(target: "rpc::eth::pubsub",
+                    skipped,
+                    "Logs subscription lagged behind the canonical state, catching up"
                 );
-                futures::stream::iter(all_logs)
-            })
+                Some(self.catch_up().unwrap_or_else(|err| {
+                    warn!(target: "rpc::eth::pubsub", %err, "Failed to catch up on missed logs");
+                    Vec::new()
+                }))
+            }
+        }
+    }
+
+    /// Returns the logs of the new blocks of the notification, preceded by the logs delivered for
+    /// the blocks it reverts, marked as removed.
+    ///
+    /// Blocks that were delivered while catching up are skipped.
+    fn on_notification(&mut self, notification: &CanonStateNotification) -> Vec<Log> {
+        let mut logs = Vec::new();
+        for (block_receipts, removed) in notification.block_receipts() {
+            let block = block_receipts.block;
+            if removed {
+                logs.extend(self.delivered.remove_blocks(&[block.hash]));
+                continue
+            }
+            if self.delivered.contains(&block.hash) {
+                continue
+            }
+            let block_logs = logs_utils::matching_block_logs_with_tx_hashes(
+                &self.filter,
+                block,
+                block_receipts.tx_receipts.iter().map(|(tx, receipt)| (*tx, receipt)),
+                false,
+            );
+            self.deliver(block, block_logs, &mut logs);
+        }
+        logs
+    }
+
+    /// Catches up on missed notifications.
+    ///
+    /// The logs delivered for blocks that were reorged out since, according to the reorg journal,
+    /// are returned as removed, followed by the logs of the canonical blocks that weren't
+    /// delivered, read from the database.
+    fn catch_up(&mut self) -> ProviderResult<Vec<Log>> {
+        let mut logs = Vec::new();
+        for (id, reorg) in self.provider.reorgs(self.reorgs_from, usize::MAX)? {
+            self.reorgs_from = id + 1;
+            if reorg.old_blocks.iter().any(|hash| self.delivered.contains(hash)) {
+                logs.extend(self.delivered.remove_blocks(&reorg.old_blocks));
+                self.next_block = self.next_block.map(|next| next.min(reorg.fork_block + 1));
+            }
+        }
+
+        let Some(next_block) = self.next_block else { return Ok(logs) };
+        let best_block = self.provider.best_block_number()?;
+        for number in next_block..=best_block {
+            let Some(hash) = self.provider.block_hash(number)? else { break };
+            if self.delivered.contains(&hash) {
+                continue
+            }
+            // the receipts of the block may be pruned
+            let (Some(transactions), Some(receipts)) = (
+                self.provider.transactions_by_block(number.into())?,
+                self.provider.receipts_by_block(number.into())?,
+            ) else {
+                continue
+            };
+            let block = BlockNumHash::new(number, hash);
+            let block_logs = logs_utils::matching_block_logs_with_tx_hashes(
+                &self.filter,
+                block,
+                transactions.iter().map(|tx| tx.hash()).zip(&receipts),
+                false,
+            );
+            self.deliver(block, block_logs, &mut logs);
+        }
+        Ok(logs)
+    }
+
+    /// Appends the logs of the block to the logs to deliver and records them as delivered.
+    fn deliver(&mut self, block: BlockNumHash, block_logs: Vec<Log>, logs: &mut Vec<Log>) {
+        logs.extend(block_logs.iter().cloned());
+        self.delivered.record_block(block, block_logs);
+        self.next_block = Some(block.number + 1);
     }
 }
@@ -119,13 +119,8 @@ where
 
     // apply block overrides, we need to apply them first so that they take effect when we we create
     // the evm env via `build_call_evm_env`, e.g. basefee
-    if let Some(mut block_overrides) = overrides.block {
-        if let Some(block_hashes) = block_overrides.block_hash.take() {
-            // override block hashes
-            db.block_hashes
-                .extend(block_hashes.into_iter().map(|(num, hash)| (U256::from(num), hash)))
-        }
-        apply_block_overrides(*block_overrides, &mut block);
+    if let Some(block_overrides) = overrides.block {
+        apply_block_overrides(*block_overrides, db, &mut block);
     }
 
     let request_gas = request.gas;
@@ -401,8 +396,13 @@ impl CallFees {
     }
 }
 
-/// Applies the given block overrides to the env
-fn apply_block_overrides(overrides: BlockOverrides, env: &mut BlockEnv) {
+/// Applies the given block overrides to the env, and the overridden block hashes to the
+/// [`CacheDB`], so they're returned by the `BLOCKHASH` opcode.
+pub fn apply_block_overrides<DB>(
+    overrides: BlockOverrides,
+    db: &mut CacheDB<DB>,
+    env: &mut BlockEnv,
+) {
     let BlockOverrides {
         number,
         difficulty,
@@ -411,9 +411,13 @@ fn apply_block_overrides(overrides: BlockOverrides, env: &mut BlockEnv) {
         coinbase,
         random,
         base_fee,
-        block_hash: _,
+        block_hash,
     } = overrides;
 
+    if let Some(block_hashes) = block_hash {
+        db.block_hashes.extend(block_hashes.into_iter().map(|(num, hash)| (U256::from(num), hash)))
+    }
+
     if let Some(number) = number {
         env.number = number;
     }
@@ -467,7 +471,11 @@ where
         account_info.nonce = nonce.to();
     }
     if let Some(code) = account_override.code {
-        account_info.code = Some(Bytecode::new_raw(code));
+        // the code hash must match the new code, otherwise `EXTCODEHASH` and the code lookup by
+        // hash still return the original code
+        let code = Bytecode::new_raw(code);
+        account_info.code_hash = code.hash_slow();
+        account_info.code = Some(code);
     }
     if let Some(balance) = account_override.balance {
         account_info.balance = balance;
@@ -614,4 +622,46 @@ mod tests {
         );
         assert!(call_fees.is_err());
     }
+
+    #[test]
+    fn test_state_and_block_overrides() {
+        use reth_primitives::{keccak256, Bytes};
+        use reth_provider::ProviderError;
+        use revm::{db::EmptyDBTyped, primitives::AccountInfo};
+
+        let mut db = CacheDB::<EmptyDBTyped<ProviderError>>::default();
+        let address = Address::with_last_byte(1);
+        let original = Bytecode::new_raw(Bytes::from_static(&[0x00]));
+        db.insert_account_info(
+            address,
+            AccountInfo {
+                code_hash: original.hash_slow(),
+                code: Some(original),
+                ..Default::default()
+            },
+        );
+
+        // overriding the code of a contract replaces its code hash
+        let code = Bytes::from_static(&[0x60, 0x00, 0x00]);
+        let overrides =
+            [(address, AccountOverride { code: Some(code.clone()), ..Default::default() })]
+                .into_iter()
+                .collect();
+        apply_state_overrides(overrides, &mut db).unwrap();
+        let account = db.basic_ref(address).unwrap().unwrap();
+        assert_eq!(account.code_hash, keccak256(&code));
+        assert_eq!(db.code_by_hash_ref(account.code_hash).unwrap().original_bytes(), code);
+
+        let mut block = BlockEnv::default();
+        let overrides = BlockOverrides {
+            number: Some(U256::from(10)),
+            time: Some(1_700_000_000),
+            block_hash: Some([(5, B256::with_last_byte(5))].into_iter().collect()),
+            ..Default::default()
+        };
+        apply_block_overrides(overrides, &mut db, &mut block);
+        assert_eq!(block.number, U256::from(10));
+        assert_eq!(block.timestamp, U256::from(1_700_000_000));
+        assert_eq!(db.block_hash_ref(U256::from(5)).unwrap(), B256::with_last_byte(5));
+    }
 }
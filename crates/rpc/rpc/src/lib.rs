@@ -49,9 +49,9 @@ pub use engine::{EngineApi, EngineEthApi};
 pub use eth::{EthApi, EthApiSpec, EthFilter, EthPubSub, EthSubscriptionIdProvider};
 pub use net::NetApi;
 pub use otterscan::OtterscanApi;
-pub use reth::RethApi;
+pub use reth::{RethApi, RethCall};
 pub use rpc::RPCApi;
-pub use trace::TraceApi;
+pub use trace::{TraceApi, TraceApiConfig};
 pub use txpool::TxPoolApi;
 pub use web3::Web3Api;
 pub mod result;
@@ -1,39 +1,94 @@
-use crate::eth::error::{EthApiError, EthResult};
+use crate::{
+    eth::{
+        error::{ensure_success, EthApiError, EthResult},
+        revm_utils::prepare_call_env,
+        EthTransactions,
+    },
+    result::internal_rpc_err,
+};
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
+use futures::StreamExt;
+use jsonrpsee::{
+    core::RpcResult, server::SubscriptionMessage, types::ErrorObject, PendingSubscriptionSink,
+    SubscriptionSink,
+};
 use reth_errors::RethResult;
-use reth_primitives::{Address, BlockId, U256};
-use reth_provider::{BlockReaderIdExt, ChangeSetReader, StateProviderFactory};
-use reth_rpc_api::RethApiServer;
-use reth_tasks::TaskSpawner;
-use std::{collections::HashMap, future::Future, sync::Arc};
+use reth_primitives::{
+    header_accumulator::historical_accumulator_root,
+    storage_layout::{StorageLayout, StorageVariable},
+    AccessList, Address, BlockId, BlockNumber, BlockNumberOrTag, Bytes, B256, U256,
+};
+use reth_provider::{
+    BlockAccessListReader, BlockReaderIdExt, CanonStateSubscriptions, ChangeSetReader,
+    HeaderAccumulatorReader, LegacyTxMetaReader, ReorgJournalReader, StateProvider,
+    StateProviderFactory,
+};
+use reth_revm::database::StateProviderDatabase;
+use reth_rpc_api::{RethApiServer, RethCallApiServer};
+use reth_rpc_types::{
+    state::EvmOverrides, AccountStateDiff, AccountSummary, BlockStateDiff, EthCallResponse,
+    HeaderAccumulator, HistoricalCall, LegacyTxMeta, Reorg, RethSubscriptionKind, StateDelta,
+    TransactionRequest,
+};
+use reth_tasks::{pool::BlockingTaskGuard, TaskSpawner};
+use revm::db::CacheDB;
+use std::{
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    sync::Arc,
+};
 use tokio::sync::oneshot;
 
+/// The number of reorgs returned by `reth_getReorgs` if no limit is given.
+const DEFAULT_REORGS_LIMIT: usize = 100;
+/// The maximum number of reorgs returned by `reth_getReorgs`.
+const MAX_REORGS_LIMIT: usize = 1000;
+/// The maximum number of blocks whose state diffs can be requested with `reth_getStateDiffs`.
+const MAX_STATE_DIFFS_BLOCKS: u64 = 100;
+/// The maximum number of calls that can be executed with a single `reth_callMany` request.
+const MAX_HISTORICAL_CALLS: usize = 10_000;
+/// The maximum number of storage variables that can be requested with `reth_getStorageSlots`.
+const MAX_STORAGE_VARIABLES: usize = 100;
+/// The maximum number of accounts that can be requested with `reth_getAccounts`.
+const MAX_ACCOUNTS: usize = 10_000;
+
 /// `reth` API implementation.
 ///
 /// This type provides the functionality for handling `reth` prototype RPC requests.
-pub struct RethApi<Provider> {
-    inner: Arc<RethApiInner<Provider>>,
+pub struct RethApi<Provider, Events> {
+    inner: Arc<RethApiInner<Provider, Events>>,
 }
 
 // === impl RethApi ===
 
-impl<Provider> RethApi<Provider> {
+impl<Provider, Events> RethApi<Provider, Events> {
     /// The provider that can interact with the chain.
     pub fn provider(&self) -> &Provider {
         &self.inner.provider
     }
 
     /// Create a new instance of the [`RethApi`]
-    pub fn new(provider: Provider, task_spawner: Box<dyn TaskSpawner>) -> Self {
-        let inner = Arc::new(RethApiInner { provider, task_spawner });
+    pub fn new(
+        provider: Provider,
+        chain_events: Events,
+        task_spawner: Box<dyn TaskSpawner>,
+    ) -> Self {
+        let inner = Arc::new(RethApiInner { provider, chain_events, task_spawner });
         Self { inner }
     }
 }
 
-impl<Provider> RethApi<Provider>
+impl<Provider, Events> RethApi<Provider, Events>
 where
-    Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Provider: BlockReaderIdExt
+        + ChangeSetReader
+        + ReorgJournalReader
+        + HeaderAccumulatorReader
+        + LegacyTxMetaReader
+        + BlockAccessListReader
+        + StateProviderFactory
+        + 'static,
+    Events: CanonStateSubscriptions + 'static,
 {
     /// Executes the future on a new blocking task.
     async fn on_blocking_task<C, F, R>(&self, c: C) -> EthResult<R>
@@ -81,12 +136,236 @@ where
         )?;
         Ok(hash_map)
     }
+
+    /// Returns the balances, nonces and code hashes of the accounts at the given block, in the
+    /// order of the addresses.
+    ///
+    /// All accounts are read within one read transaction of the state, each account once and in
+    /// the order of the addresses, so consecutive reads hit the database pages loaded before.
+    pub async fn accounts(
+        &self,
+        block_id: BlockId,
+        addresses: Vec<Address>,
+    ) -> EthResult<Vec<AccountSummary>> {
+        self.on_blocking_task(|this| async move {
+            let state = this.provider().state_by_block_id(block_id)?;
+
+            let mut sorted = addresses.clone();
+            sorted.sort_unstable();
+            sorted.dedup();
+            let accounts = sorted
+                .into_iter()
+                .map(|address| Ok((address, state.basic_account(address)?.unwrap_or_default())))
+                .collect::<EthResult<Vec<_>>>()?;
+
+            Ok(addresses
+                .into_iter()
+                .map(|address| {
+                    let account = accounts
+                        .binary_search_by_key(&address, |(address, _)| *address)
+                        .map(|index| accounts[index].1)
+                        .unwrap_or_default();
+                    AccountSummary {
+                        address,
+                        balance: account.balance,
+                        nonce: account.nonce,
+                        code_hash: account.get_bytecode_hash(),
+                    }
+                })
+                .collect())
+        })
+        .await
+    }
+
+    /// Returns the roots of the complete epochs of the header accumulator, and the root of the
+    /// historical accumulator over them.
+    pub async fn header_accumulator(&self) -> EthResult<HeaderAccumulator> {
+        self.on_blocking_task(|this| async move {
+            let historical_epochs = this.provider().header_accumulator_epochs()?;
+            let root = historical_accumulator_root(&historical_epochs);
+            Ok(HeaderAccumulator { historical_epochs, root })
+        })
+        .await
+    }
+
+    /// Returns the legacy metadata of the transaction with the given hash, if it was imported.
+    pub async fn legacy_tx_meta(&self, hash: B256) -> EthResult<Option<LegacyTxMeta>> {
+        self.on_blocking_task(|this| async move {
+            let Some(id) = this.provider().transaction_id(hash)? else { return Ok(None) };
+            let meta = this.provider().legacy_tx_meta(id)?.map(|meta| LegacyTxMeta {
+                hash,
+                size: meta.size,
+                timestamp: meta.timestamp,
+                rest: meta.rest,
+            });
+            Ok(meta)
+        })
+        .await
+    }
+
+    /// Returns the recorded access list of the canonical block with the given id.
+    pub async fn block_access_list(&self, block_id: BlockId) -> EthResult<Option<AccessList>> {
+        self.on_blocking_task(|this| async move {
+            let Some(number) = this.provider().block_number_for_id(block_id)? else {
+                return Ok(None)
+            };
+            // the access lists are stored by number, a block that is not canonical has none
+            if let BlockId::Hash(hash) = block_id {
+                if this.provider().block_hash(number)? != Some(hash.block_hash) {
+                    return Ok(None)
+                }
+            }
+            Ok(this.provider().block_access_list(number)?)
+        })
+        .await
+    }
+
+    /// Returns up to `limit` reorgs from the reorg journal, starting at the reorg with the
+    /// sequence number `from`.
+    pub async fn reorgs(&self, from: u64, limit: usize) -> EthResult<Vec<Reorg>> {
+        self.on_blocking_task(|this| async move {
+            let reorgs = this.provider().reorgs(from, limit)?;
+            Ok(reorgs
+                .into_iter()
+                .map(|(id, reorg)| Reorg {
+                    id,
+                    timestamp: reorg.timestamp,
+                    depth: reorg.depth(),
+                    fork_block_number: reorg.fork_block,
+                    fork_block_hash: reorg.fork_hash,
+                    old_blocks: reorg.old_blocks,
+                    new_blocks: reorg.new_blocks,
+                })
+                .collect())
+        })
+        .await
+    }
+
+    /// Returns the state diffs of the blocks in the given range.
+    pub async fn state_diffs(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> EthResult<Vec<BlockStateDiff>> {
+        self.on_blocking_task(|this| async move {
+            (from..=to).map(|block_number| this.try_state_diff(block_number)).collect()
+        })
+        .await
+    }
+
+    fn try_state_diff(&self, block_number: BlockNumber) -> EthResult<BlockStateDiff> {
+        let header =
+            self.provider().sealed_header(block_number)?.ok_or(EthApiError::UnknownBlockNumber)?;
+
+        // the state after the block, the state before it is in the changesets
+        let state = self.provider().history_by_block_number(block_number)?;
+
+        let mut accounts = BTreeMap::<Address, AccountStateDiff>::new();
+        for account_before in self.provider().account_block_changeset(block_number)? {
+            let address = account_before.address;
+            let before = account_before.info.unwrap_or_default();
+            let after = state.basic_account(address)?.unwrap_or_default();
+            let code = if before.get_bytecode_hash() == after.get_bytecode_hash() {
+                None
+            } else {
+                Some(StateDelta {
+                    from: code_by_hash(&state, before.bytecode_hash)?,
+                    to: code_by_hash(&state, after.bytecode_hash)?,
+                })
+            };
+            accounts.insert(
+                address,
+                AccountStateDiff {
+                    balance: StateDelta::changed(before.balance, after.balance),
+                    nonce: StateDelta::changed(before.nonce, after.nonce),
+                    code,
+                    storage: BTreeMap::new(),
+                },
+            );
+        }
+        for (address, entry) in self.provider().storage_block_changeset(block_number)? {
+            let value = state.storage(address, entry.key)?.unwrap_or_default();
+            if let Some(delta) = StateDelta::changed(entry.value, value) {
+                accounts.entry(address).or_default().storage.insert(entry.key, delta);
+            }
+        }
+        accounts.retain(|_, diff| !diff.is_empty());
+
+        Ok(BlockStateDiff {
+            block_number,
+            block_hash: header.hash(),
+            parent_hash: header.parent_hash,
+            accounts,
+        })
+    }
+
+    /// Returns the storage variables of the contract at the given paths, decoded with its storage
+    /// layout at the given block.
+    pub async fn storage_variables(
+        &self,
+        address: Address,
+        layout: StorageLayout,
+        paths: Vec<String>,
+        block_id: BlockId,
+    ) -> EthResult<Vec<StorageVariable>> {
+        self.on_blocking_task(|this| async move {
+            let state = this.provider().state_by_block_id(block_id)?;
+            let variables = paths
+                .iter()
+                .map(|path| state.storage_variable(address, &layout, path))
+                .collect::<Result<_, _>>()?;
+            Ok(variables)
+        })
+        .await
+    }
+
+    /// Sends the state diffs of all new canonical blocks to the subscription sink.
+    async fn pipe_state_diffs(&self, sink: SubscriptionSink) -> Result<(), ErrorObject<'static>> {
+        let mut notifications = self.inner.chain_events.canonical_state_stream();
+        loop {
+            tokio::select! {
+                _ = sink.closed() => {
+                    // connection dropped
+                    break Ok(())
+                }
+                notification = notifications.next() => {
+                    let Some(notification) = notification else {
+                        // stream ended
+                        break Ok(())
+                    };
+                    let range = notification.committed().range();
+                    let diffs = self.state_diffs(*range.start(), *range.end()).await?;
+                    for diff in diffs {
+                        let msg = SubscriptionMessage::from_json(&diff)
+                            .map_err(|err| internal_rpc_err(err.to_string()))?;
+                        if sink.send(msg).await.is_err() {
+                            return Ok(())
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns the code with the given hash, or empty code if there is none.
+fn code_by_hash(state: &dyn StateProvider, hash: Option<B256>) -> EthResult<Bytes> {
+    let Some(hash) = hash else { return Ok(Bytes::new()) };
+    Ok(state.bytecode_by_hash(hash)?.map(|code| code.original_bytes()).unwrap_or_default())
 }
 
 #[async_trait]
-impl<Provider> RethApiServer for RethApi<Provider>
+impl<Provider, Events> RethApiServer for RethApi<Provider, Events>
 where
-    Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Provider: BlockReaderIdExt
+        + ChangeSetReader
+        + ReorgJournalReader
+        + HeaderAccumulatorReader
+        + LegacyTxMetaReader
+        + BlockAccessListReader
+        + StateProviderFactory
+        + 'static,
+    Events: CanonStateSubscriptions + 'static,
 {
     /// Handler for `reth_getBalanceChangesInBlock`
     async fn reth_get_balance_changes_in_block(
@@ -95,23 +374,246 @@ where
     ) -> RpcResult<HashMap<Address, U256>> {
         Ok(Self::balance_changes_in_block(self, block_id).await?)
     }
+
+    /// Handler for `reth_getAccounts`
+    async fn reth_get_accounts(
+        &self,
+        block_id: Option<BlockId>,
+        addresses: Vec<Address>,
+    ) -> RpcResult<Vec<AccountSummary>> {
+        if addresses.len() > MAX_ACCOUNTS {
+            return Err(EthApiError::InvalidParams(format!(
+                "at most {MAX_ACCOUNTS} accounts can be requested"
+            ))
+            .into())
+        }
+        Ok(Self::accounts(self, block_id.unwrap_or_default(), addresses).await?)
+    }
+
+    /// Handler for `reth_getReorgs`
+    async fn reth_get_reorgs(
+        &self,
+        from: Option<u64>,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<Reorg>> {
+        let limit = limit.unwrap_or(DEFAULT_REORGS_LIMIT).min(MAX_REORGS_LIMIT);
+        Ok(Self::reorgs(self, from.unwrap_or_default(), limit).await?)
+    }
+
+    /// Handler for `reth_getHeaderAccumulator`
+    async fn reth_get_header_accumulator(&self) -> RpcResult<HeaderAccumulator> {
+        Ok(Self::header_accumulator(self).await?)
+    }
+
+    /// Handler for `reth_getLegacyTxMeta`
+    async fn reth_get_legacy_tx_meta(&self, hash: B256) -> RpcResult<Option<LegacyTxMeta>> {
+        Ok(Self::legacy_tx_meta(self, hash).await?)
+    }
+
+    /// Handler for `reth_getBlockAccessList`
+    async fn reth_get_block_access_list(&self, block_id: BlockId) -> RpcResult<Option<AccessList>> {
+        Ok(Self::block_access_list(self, block_id).await?)
+    }
+
+    /// Handler for `reth_getStateDiffs`
+    async fn reth_get_state_diffs(
+        &self,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+    ) -> RpcResult<Vec<BlockStateDiff>> {
+        let (Some(from), Some(to)) = (
+            self.provider().convert_block_number(from_block).map_err(EthApiError::from)?,
+            self.provider().convert_block_number(to_block).map_err(EthApiError::from)?,
+        ) else {
+            return Err(EthApiError::UnknownBlockNumber.into())
+        };
+        if from > to {
+            return Err(EthApiError::InvalidBlockRange.into())
+        }
+        if to - from >= MAX_STATE_DIFFS_BLOCKS {
+            return Err(EthApiError::InvalidParams(format!(
+                "at most {MAX_STATE_DIFFS_BLOCKS} blocks can be requested"
+            ))
+            .into())
+        }
+        Ok(Self::state_diffs(self, from, to).await?)
+    }
+
+    /// Handler for `reth_getStorageSlots`
+    async fn reth_get_storage_slots(
+        &self,
+        address: Address,
+        layout: StorageLayout,
+        paths: Option<Vec<String>>,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<Vec<StorageVariable>> {
+        let paths = paths.unwrap_or_else(|| layout.variables().map(ToString::to_string).collect());
+        if paths.len() > MAX_STORAGE_VARIABLES {
+            return Err(EthApiError::InvalidParams(format!(
+                "at most {MAX_STORAGE_VARIABLES} variables can be requested"
+            ))
+            .into())
+        }
+        Ok(Self::storage_variables(self, address, layout, paths, block_id.unwrap_or_default())
+            .await?)
+    }
+
+    /// Handler for `reth_subscribe`
+    async fn reth_subscribe(
+        &self,
+        pending: PendingSubscriptionSink,
+        kind: RethSubscriptionKind,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        let sink = pending.accept().await?;
+        let this = self.clone();
+        self.inner.task_spawner.spawn(Box::pin(async move {
+            let _ = match kind {
+                RethSubscriptionKind::StateDiffs => this.pipe_state_diffs(sink).await,
+            };
+        }));
+
+        Ok(())
+    }
 }
 
-impl<Provider> std::fmt::Debug for RethApi<Provider> {
+impl<Provider, Events> std::fmt::Debug for RethApi<Provider, Events> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("RethApi").finish_non_exhaustive()
     }
 }
 
-impl<Provider> Clone for RethApi<Provider> {
+impl<Provider, Events> Clone for RethApi<Provider, Events> {
     fn clone(&self) -> Self {
         Self { inner: Arc::clone(&self.inner) }
     }
 }
 
-struct RethApiInner<Provider> {
+struct RethApiInner<Provider, Events> {
     /// The provider that can interact with the chain.
     provider: Provider,
+    /// A type that allows to create new event subscriptions.
+    chain_events: Events,
     /// The type that can spawn tasks which would otherwise block.
     task_spawner: Box<dyn TaskSpawner>,
 }
+
+/// `reth_callMany` implementation.
+///
+/// This is separate from [`RethApi`] because it needs access to the EVM setup of the `eth`
+/// namespace.
+pub struct RethCall<Eth> {
+    inner: Arc<RethCallInner<Eth>>,
+}
+
+impl<Eth> RethCall<Eth> {
+    /// Create a new `RethCall` instance.
+    pub fn new(eth_api: Eth, blocking_task_guard: BlockingTaskGuard) -> Self {
+        Self { inner: Arc::new(RethCallInner { eth_api, blocking_task_guard }) }
+    }
+}
+
+impl<Eth> RethCall<Eth>
+where
+    Eth: EthTransactions + 'static,
+{
+    /// Executes each call on top of the state of its block, without committing its state
+    /// changes.
+    ///
+    /// The calls are grouped by block, so the state of a block is only set up once and the
+    /// accounts and storage read by a call are cached for the following calls on the same block.
+    pub async fn call_many(&self, calls: Vec<HistoricalCall>) -> EthResult<Vec<EthCallResponse>> {
+        let num_calls = calls.len();
+
+        // group the calls by block, keeping track of their position in the request
+        let mut blocks = Vec::<(BlockId, Vec<(usize, TransactionRequest)>)>::new();
+        for (index, HistoricalCall { block, call }) in calls.into_iter().enumerate() {
+            match blocks.iter_mut().rev().find(|(id, _)| *id == block) {
+                Some((_, calls)) => calls.push((index, call)),
+                None => blocks.push((block, vec![(index, call)])),
+            }
+        }
+
+        let _permit = self.inner.blocking_task_guard.clone().acquire_owned().await;
+
+        let mut results = (0..num_calls).map(|_| None).collect::<Vec<_>>();
+        for (block, calls) in blocks {
+            let (cfg, block_env, at) = self.inner.eth_api.evm_env_at(block).await?;
+            let gas_limit = self.inner.eth_api.call_gas_limit();
+
+            let this = self.clone();
+            let responses = self
+                .inner
+                .eth_api
+                .spawn_with_state_at_block(at, move |state| {
+                    let mut db = CacheDB::new(StateProviderDatabase::new(state));
+                    let responses = calls
+                        .into_iter()
+                        .map(|(index, call)| {
+                            let output = prepare_call_env(
+                                cfg.clone(),
+                                block_env.clone(),
+                                call,
+                                gas_limit,
+                                &mut db,
+                                EvmOverrides::default(),
+                            )
+                            .and_then(|env| this.inner.eth_api.transact(&mut db, env))
+                            .and_then(|(res, _)| ensure_success(res.result));
+
+                            let response = match output {
+                                Ok(output) => EthCallResponse { value: Some(output), error: None },
+                                Err(err) => {
+                                    EthCallResponse { value: None, error: Some(err.to_string()) }
+                                }
+                            };
+                            (index, response)
+                        })
+                        .collect::<Vec<_>>();
+                    Ok(responses)
+                })
+                .await?;
+
+            for (index, response) in responses {
+                results[index] = Some(response);
+            }
+        }
+
+        Ok(results.into_iter().flatten().collect())
+    }
+}
+
+#[async_trait]
+impl<Eth> RethCallApiServer for RethCall<Eth>
+where
+    Eth: EthTransactions + 'static,
+{
+    /// Handler for `reth_callMany`
+    async fn reth_call_many(&self, calls: Vec<HistoricalCall>) -> RpcResult<Vec<EthCallResponse>> {
+        if calls.len() > MAX_HISTORICAL_CALLS {
+            return Err(EthApiError::InvalidParams(format!(
+                "at most {MAX_HISTORICAL_CALLS} calls can be executed at once"
+            ))
+            .into())
+        }
+        Ok(Self::call_many(self, calls).await?)
+    }
+}
+
+impl<Eth> std::fmt::Debug for RethCall<Eth> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RethCall").finish_non_exhaustive()
+    }
+}
+
+impl<Eth> Clone for RethCall<Eth> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+struct RethCallInner<Eth> {
+    /// Access to commonly used code of the `eth` namespace
+    eth_api: Eth,
+    /// Restricts the number of concurrent `reth_callMany` requests.
+    blocking_task_guard: BlockingTaskGuard,
+}
@@ -13,6 +13,7 @@ use reth_primitives::{revm::env::tx_env_with_recovered, BlockId, Bytes, Header,
 use reth_provider::{BlockReader, ChainSpecProvider, EvmEnvProvider, StateProviderFactory};
 use reth_revm::database::StateProviderDatabase;
 use reth_rpc_api::TraceApiServer;
+use reth_rpc_server_types::constants::DEFAULT_MAX_TRACE_FILTER_BLOCKS;
 use reth_rpc_types::{
     state::{EvmOverrides, StateOverride},
     trace::{
@@ -52,7 +53,17 @@ impl<Provider, Eth> TraceApi<Provider, Eth> {
 
     /// Create a new instance of the [`TraceApi`]
     pub fn new(provider: Provider, eth_api: Eth, blocking_task_guard: BlockingTaskGuard) -> Self {
-        let inner = Arc::new(TraceApiInner { provider, eth_api, blocking_task_guard });
+        Self::with_config(provider, eth_api, blocking_task_guard, TraceApiConfig::default())
+    }
+
+    /// Create a new instance of the [`TraceApi`] with the given limits
+    pub fn with_config(
+        provider: Provider,
+        eth_api: Eth,
+        blocking_task_guard: BlockingTaskGuard,
+        config: TraceApiConfig,
+    ) -> Self {
+        let inner = Arc::new(TraceApiInner { provider, eth_api, blocking_task_guard, config });
         Self { inner }
     }
 
@@ -257,10 +268,11 @@ where
 
         // ensure that the range is not too large, since we need to fetch all blocks in the range
         let distance = end.saturating_sub(start);
-        if distance > 100 {
-            return Err(EthApiError::InvalidParams(
-                "Block range too large; currently limited to 100 blocks".to_string(),
-            ))
+        let max_blocks = self.inner.config.max_blocks_per_filter;
+        if distance > max_blocks {
+            return Err(EthApiError::InvalidParams(format!(
+                "Block range too large; currently limited to {max_blocks} blocks"
+            )))
         }
 
         // fetch all blocks in that range
@@ -331,6 +343,14 @@ where
             }
         }
 
+        if let Some(max_traces) = self.inner.config.max_traces_per_response {
+            if all_traces.len() > max_traces {
+                return Err(EthApiError::InvalidParams(format!(
+                    "query exceeds max results {max_traces}, retry with a smaller block range"
+                )))
+            }
+        }
+
         Ok(all_traces)
     }
 
@@ -484,8 +504,14 @@ where
     /// - if Paris hardfork is activated, no block rewards are given
     /// - if Paris hardfork is not activated, calculate block rewards with block number only
     /// - if Paris hardfork is unknown, calculate block rewards with block number and ttd
+    ///
+    /// A fixed block reward configured in the chain spec takes precedence.
     fn calculate_base_block_reward(&self, header: &Header) -> EthResult<Option<u128>> {
         let chain_spec = self.provider().chain_spec();
+        if let Some(block_reward) = chain_spec.block_rewards.block_reward {
+            return Ok((block_reward > 0).then_some(block_reward))
+        }
+
         let is_paris_activated = chain_spec.is_paris_active_at_block(header.number);
 
         Ok(match is_paris_activated {
@@ -518,17 +544,27 @@ where
         base_block_reward: u128,
     ) -> Vec<LocalizedTransactionTrace> {
         let mut traces = Vec::with_capacity(ommers.len() + 1);
+        let chain_spec = self.provider().chain_spec();
+        let rules = &chain_spec.block_rewards;
 
-        let block_reward = block_reward(base_block_reward, ommers.len());
+        let block_reward = if rules.ommer_rewards {
+            block_reward(base_block_reward, ommers.len())
+        } else {
+            base_block_reward
+        };
         traces.push(reward_trace(
             header,
             RewardAction {
-                author: header.beneficiary,
+                author: rules.reward_recipient.unwrap_or(header.beneficiary),
                 reward_type: RewardType::Block,
                 value: U256::from(block_reward),
             },
         ));
 
+        if !rules.ommer_rewards {
+            return traces
+        }
+
         for uncle in ommers {
             let uncle_reward = ommer_reward(base_block_reward, header.number, uncle.number);
             traces.push(reward_trace(
@@ -681,6 +717,44 @@ struct TraceApiInner<Provider, Eth> {
     eth_api: Eth,
     // restrict the number of concurrent calls to `trace_*`
     blocking_task_guard: BlockingTaskGuard,
+    /// Limits of the `trace_filter` requests
+    config: TraceApiConfig,
+}
+
+/// Config for the `trace` namespace
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceApiConfig {
+    /// Maximum number of blocks that can be traced by a single `trace_filter` request.
+    pub max_blocks_per_filter: u64,
+    /// Maximum number of traces that can be returned in a single response in `trace_filter`
+    /// calls.
+    ///
+    /// If `None` then no limit is enforced.
+    pub max_traces_per_response: Option<usize>,
+}
+
+impl TraceApiConfig {
+    /// Sets the maximum number of blocks that can be traced by a single `trace_filter` request.
+    pub const fn max_blocks_per_filter(mut self, num: u64) -> Self {
+        self.max_blocks_per_filter = num;
+        self
+    }
+
+    /// Sets the maximum number of traces that can be returned in a single response in
+    /// `trace_filter` calls.
+    pub const fn max_traces_per_response(mut self, num: usize) -> Self {
+        self.max_traces_per_response = Some(num);
+        self
+    }
+}
+
+impl Default for TraceApiConfig {
+    fn default() -> Self {
+        Self {
+            max_blocks_per_filter: DEFAULT_MAX_TRACE_FILTER_BLOCKS,
+            max_traces_per_response: None,
+        }
+    }
 }
 
 /// Helper to construct a [`LocalizedTransactionTrace`] that describes a reward to the block
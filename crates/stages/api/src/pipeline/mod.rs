@@ -364,8 +364,13 @@ where
                         // this function is interrupted before the static files commit, we can just
                         // truncate the static files according to the
                         // checkpoints on the next start-up.
-                        provider_rw.commit()?;
-                        self.provider_factory.static_file_provider().commit()?;
+                        let commit_span =
+                            debug_span!(target: "sync::pipeline", "commit", stage = %stage_id);
+                        commit_span.in_scope(|| -> Result<(), PipelineError> {
+                            provider_rw.commit()?;
+                            self.provider_factory.static_file_provider().commit()?;
+                            Ok(())
+                        })?;
 
                         stage.post_unwind_commit()?;
 
@@ -429,7 +434,9 @@ where
                 target,
             });
 
-            if let Err(err) = stage.execute_ready(exec_input).await {
+            let ready = stage.execute_ready(exec_input);
+            let span = debug_span!(target: "sync::pipeline", "execute_ready", stage = %stage_id);
+            if let Err(err) = ready.instrument(span).await {
                 self.event_sender.notify(PipelineEvent::Error { stage_id });
 
                 match on_stage_error(&self.provider_factory, stage_id, prev_checkpoint, err)? {
@@ -449,7 +456,14 @@ where
             });
 
             let provider_rw = self.provider_factory.provider_rw()?;
-            match stage.execute(&provider_rw, exec_input) {
+            let span = debug_span!(
+                target: "sync::pipeline",
+                "execute",
+                stage = %stage_id,
+                from = prev_checkpoint.unwrap_or_default().block_number,
+                to = exec_input.target(),
+            );
+            match span.in_scope(|| stage.execute(&provider_rw, exec_input)) {
                 Ok(out @ ExecOutput { checkpoint, done }) => {
                     made_progress |=
                         checkpoint.block_number != prev_checkpoint.unwrap_or_default().block_number;
@@ -476,8 +490,12 @@ where
                     // this function is interrupted before the database commit, we can just truncate
                     // the static files according to the checkpoints on the next
                     // start-up.
-                    self.provider_factory.static_file_provider().commit()?;
-                    provider_rw.commit()?;
+                    let span = debug_span!(target: "sync::pipeline", "commit", stage = %stage_id);
+                    span.in_scope(|| -> Result<(), PipelineError> {
+                        self.provider_factory.static_file_provider().commit()?;
+                        provider_rw.commit()?;
+                        Ok(())
+                    })?;
 
                     stage.post_execute_commit()?;
 
@@ -489,6 +507,11 @@ where
                             ControlFlow::NoProgress { block_number: Some(block_number) }
                         })
                     }
+
+                    // All progress of this batch is committed, so this is a safe point to stop
+                    // the pipeline. Yield to give the caller a chance to drop it, e.g. on
+                    // shutdown, since the next batch may be ready without ever yielding.
+                    tokio::task::yield_now().await;
                 }
                 Err(err) => {
                     drop(provider_rw);
@@ -48,7 +48,7 @@ use reth_evm::execute::BlockExecutorProvider;
 use reth_network_p2p::{bodies::downloader::BodyDownloader, headers::downloader::HeaderDownloader};
 use reth_primitives::B256;
 use reth_provider::HeaderSyncGapProvider;
-use reth_prune_types::PruneModes;
+use reth_prune_types::{PruneMode, PruneModes};
 use std::sync::Arc;
 use tokio::sync::watch;
 
@@ -367,6 +367,11 @@ pub struct HistoryIndexingStages {
 
 impl<DB: Database> StageSet<DB> for HistoryIndexingStages {
     fn builder(self) -> StageSetBuilder<DB> {
+        // The history of allowlisted addresses is retained, so it has to be indexed from genesis.
+        let history_prune_mode = |mode: Option<PruneMode>| {
+            mode.filter(|_| self.prune_modes.history_address_allowlist.is_empty())
+        };
+
         StageSetBuilder::default()
             .add_stage(TransactionLookupStage::new(
                 self.stages_config.transaction_lookup,
@@ -376,12 +381,12 @@ impl<DB: Database> StageSet<DB> for HistoryIndexingStages {
             .add_stage(IndexStorageHistoryStage::new(
                 self.stages_config.index_storage_history,
                 self.stages_config.etl.clone(),
-                self.prune_modes.account_history,
+                history_prune_mode(self.prune_modes.account_history),
             ))
             .add_stage(IndexAccountHistoryStage::new(
                 self.stages_config.index_account_history,
                 self.stages_config.etl.clone(),
-                self.prune_modes.storage_history,
+                history_prune_mode(self.prune_modes.storage_history),
             ))
     }
 }
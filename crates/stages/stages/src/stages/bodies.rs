@@ -320,6 +320,11 @@ impl<DB: Database, D: BodyDownloader> Stage<DB> for BodyStage<D> {
         // on static file
         let db_tx_num =
             body_cursor.last()?.map(|(_, block_meta)| block_meta.last_tx_num()).unwrap_or_default();
+
+        // Delete the legacy metadata and L1 fees of the unwound transactions
+        provider.unwind_table_by_num::<tables::LegacyTxMeta>(db_tx_num)?;
+        provider.unwind_table_by_num::<tables::LegacyL1Fees>(db_tx_num)?;
+
         let static_file_tx_num: u64 = static_file_provider
             .get_highest_static_file_tx(StaticFileSegment::Transactions)
             .unwrap_or_default();
@@ -1,4 +1,6 @@
-use crate::stages::MERKLE_STAGE_DEFAULT_CLEAN_THRESHOLD;
+use crate::stages::{
+    CachedStateDatabase, ExecutionStateCache, MERKLE_STAGE_DEFAULT_CLEAN_THRESHOLD,
+};
 use num_traits::Zero;
 use reth_config::config::ExecutionConfig;
 use reth_db::{static_file::HeaderMask, tables};
@@ -8,12 +10,13 @@ use reth_execution_types::{Chain, ExecutionOutcome};
 use reth_exex::{ExExManagerHandle, ExExNotification};
 use reth_primitives::{
     constants::gas_units::{GIGAGAS, KILOGAS, MEGAGAS},
-    BlockNumber, Header, StaticFileSegment,
+    BlockNumHash, BlockNumber, Header, StaticFileSegment,
 };
 use reth_provider::{
     providers::{StaticFileProvider, StaticFileProviderRWRefMut, StaticFileWriter},
-    BlockReader, DatabaseProviderRW, HeaderProvider, LatestStateProviderRef, OriginalValuesKnown,
-    ProviderError, StateWriter, StatsReader, TransactionVariant,
+    BlockAccessListWriter, BlockHashReader, BlockReader, DatabaseProviderRW, HeaderProvider,
+    LatestStateProviderRef, OriginalValuesKnown, ProviderError, StateWriter, StatsReader,
+    TransactionVariant,
 };
 use reth_prune_types::PruneModes;
 use reth_revm::database::StateProviderDatabase;
@@ -53,6 +56,7 @@ use tracing::*;
 /// - [`tables::Bytecodes`]
 /// - [`tables::AccountChangeSets`]
 /// - [`tables::StorageChangeSets`]
+/// - [`tables::BlockAccessLists`], if the executor records access lists
 ///
 /// For unwinds we are accessing:
 /// - [`tables::BlockBodyIndices`] get tx index to know what needs to be unwinded
@@ -84,6 +88,8 @@ pub struct ExecutionStage<E> {
     post_unwind_commit_input: Option<Chain>,
     /// Handle to communicate with `ExEx` manager.
     exex_manager_handle: ExExManagerHandle,
+    /// State read and written by previous batches, if enabled.
+    state_cache: Option<ExecutionStateCache>,
 }
 
 impl<E> ExecutionStage<E> {
@@ -104,6 +110,7 @@ impl<E> ExecutionStage<E> {
             post_execute_commit_input: None,
             post_unwind_commit_input: None,
             exex_manager_handle,
+            state_cache: None,
         }
     }
 
@@ -134,6 +141,7 @@ impl<E> ExecutionStage<E> {
             prune_modes,
             ExExManagerHandle::empty(),
         )
+        .with_state_cache_size(config.state_cache_size)
     }
 
     /// Set the metric events sender.
@@ -142,6 +150,13 @@ impl<E> ExecutionStage<E> {
         self
     }
 
+    /// Keeps up to `size` megabytes of state in memory across batches, see
+    /// [`ExecutionStateCache`]. The cache is disabled if `None`.
+    pub fn with_state_cache_size(mut self, size: Option<u64>) -> Self {
+        self.state_cache = size.map(|size| ExecutionStateCache::new(size as usize * 1024 * 1024));
+        self
+    }
+
     /// Adjusts the prune modes related to changesets.
     ///
     /// This function verifies whether the [`super::MerkleStage`] or Hashing stages will run from
@@ -220,10 +235,20 @@ where
             None
         };
 
-        let db = StateProviderDatabase(LatestStateProviderRef::new(
-            provider.tx_ref(),
-            provider.static_file_provider().clone(),
-        ));
+        if let Some(cache) = &mut self.state_cache {
+            let checkpoint = input.checkpoint().block_number;
+            let hash = provider
+                .block_hash(checkpoint)?
+                .ok_or_else(|| ProviderError::HeaderNotFound(checkpoint.into()))?;
+            cache.ensure_tip(BlockNumHash::new(checkpoint, hash));
+        }
+        let db = CachedStateDatabase::new(
+            StateProviderDatabase(LatestStateProviderRef::new(
+                provider.tx_ref(),
+                provider.static_file_provider().clone(),
+            )),
+            self.state_cache.as_mut(),
+        );
         let mut executor = self.executor_provider.batch_executor(db, prune_modes);
         executor.set_tip(max_block);
 
@@ -272,6 +297,15 @@ where
             })?;
             execution_duration += execute_start.elapsed();
 
+            if let Some(recorder) = self.executor_provider.access_list_recorder() {
+                let hash = provider
+                    .block_hash(block_number)?
+                    .ok_or_else(|| ProviderError::HeaderNotFound(block_number.into()))?;
+                if let Some(access_list) = recorder.take(block_number, hash) {
+                    provider.insert_block_access_list(block_number, access_list)?;
+                }
+            }
+
             // Gas metrics
             if let Some(metrics_tx) = &mut self.metrics_tx {
                 let _ =
@@ -334,6 +368,15 @@ where
             }
         }
 
+        // The cache is cleared on the next run if writing fails, since the checkpoint won't match.
+        if let Some(cache) = &mut self.state_cache {
+            let hash = provider
+                .block_hash(stage_progress)?
+                .ok_or_else(|| ProviderError::HeaderNotFound(stage_progress.into()))?;
+            cache.commit(&state.bundle, BlockNumHash::new(stage_progress, hash));
+            debug!(target: "sync::stages::execution", size = cache.size(), "Updated state cache");
+        }
+
         let time = Instant::now();
         // write output
         state.write_to_storage(
@@ -389,6 +432,9 @@ where
         //
         // This also updates `PlainStorageState` and `PlainAccountState`.
         let bundle_state_with_receipts = provider.unwind_or_peek_state::<true>(range.clone())?;
+        if let Some(cache) = &mut self.state_cache {
+            cache.clear();
+        }
 
         // Prepare the input for post unwind commit hook, where an `ExExNotification` will be sent.
         if self.exex_manager_handle.has_exexs() {
@@ -2,15 +2,19 @@ use futures_util::StreamExt;
 use reth_codecs::Compact;
 use reth_config::config::EtlConfig;
 use reth_consensus::Consensus;
-use reth_db::{tables, RawKey, RawTable, RawValue};
+use reth_db::{static_file::HeaderMask, tables, RawKey, RawTable, RawValue};
 use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW},
     database::Database,
+    models::CompactU256,
     transaction::DbTxMut,
 };
 use reth_etl::Collector;
 use reth_network_p2p::headers::{downloader::HeaderDownloader, error::HeadersDownloaderError};
-use reth_primitives::{BlockHash, BlockNumber, SealedHeader, StaticFileSegment, B256};
+use reth_primitives::{
+    header_accumulator::{epoch_accumulator_root, epoch_of, HeaderRecord, EPOCH_SIZE},
+    BlockHash, BlockNumber, SealedHeader, StaticFileSegment, B256,
+};
 use reth_provider::{
     providers::{StaticFileProvider, StaticFileWriter},
     BlockHashReader, DatabaseProviderRW, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider,
@@ -33,7 +37,9 @@ use tracing::*;
 /// the perceived highest block on the network.
 ///
 /// The headers are processed and data is inserted into static files, as well as into the
-/// [`HeaderNumbers`][reth_db::tables::HeaderNumbers] table.
+/// [`HeaderNumbers`][reth_db::tables::HeaderNumbers] table. The roots of the epochs of the
+/// pre-merge header accumulator are written to the
+/// [`HeaderAccumulators`][reth_db::tables::HeaderAccumulators] table as the epochs are completed.
 ///
 /// NOTE: This stage downloads headers in reverse and pushes them to the ETL [`Collector`]. It then
 /// proceeds to push them sequentially to static files. The stage checkpoint is not updated until
@@ -108,6 +114,10 @@ where
             .header_td_by_number(last_header_number)?
             .ok_or(ProviderError::TotalDifficultyNotFound(last_header_number))?;
 
+        // Records of the current epoch of the header accumulator, `None` after the merge
+        let mut epoch_records =
+            Self::accumulator_epoch_records(&static_file_provider, last_header_number)?;
+
         // Although headers were downloaded in reverse order, the collector iterates it in ascending
         // order
         let mut writer = static_file_provider.latest_writer(StaticFileSegment::Headers)?;
@@ -137,6 +147,24 @@ where
                 }
             })?;
 
+            if let Some(records) = &mut epoch_records {
+                if header.difficulty.is_zero() {
+                    // The merge is reached, the last pre-merge epoch is complete
+                    if !records.is_empty() {
+                        let root = epoch_accumulator_root(records);
+                        tx.put::<tables::HeaderAccumulators>(epoch_of(header.number - 1), root)?;
+                    }
+                    epoch_records = None;
+                } else {
+                    records.push(HeaderRecord { block_hash: header_hash, total_difficulty: td });
+                    if records.len() as u64 == EPOCH_SIZE {
+                        let root = epoch_accumulator_root(records);
+                        tx.put::<tables::HeaderAccumulators>(epoch_of(header.number), root)?;
+                        records.clear();
+                    }
+                }
+            }
+
             // Append to Headers segment
             writer.append_header(header, td, header_hash)?;
         }
@@ -180,6 +208,33 @@ where
 
         Ok(last_header_number)
     }
+
+    /// Returns the records of the headers up to `last_header_number` in the current epoch of the
+    /// header accumulator, or `None` if `last_header_number` is a post-merge block.
+    fn accumulator_epoch_records(
+        static_file_provider: &StaticFileProvider,
+        last_header_number: BlockNumber,
+    ) -> Result<Option<Vec<HeaderRecord>>, StageError> {
+        let last_header = static_file_provider
+            .header_by_number(last_header_number)?
+            .ok_or(ProviderError::HeaderNotFound(last_header_number.into()))?;
+        if last_header.difficulty.is_zero() {
+            return Ok(None)
+        }
+
+        let epoch_start = epoch_of(last_header_number + 1) * EPOCH_SIZE;
+        let records = static_file_provider.fetch_range_with_predicate(
+            StaticFileSegment::Headers,
+            epoch_start..last_header_number + 1,
+            |cursor, number| {
+                Ok(cursor
+                    .get_two::<HeaderMask<CompactU256, BlockHash>>(number.into())?
+                    .map(|(td, block_hash)| HeaderRecord { block_hash, total_difficulty: td.0 }))
+            },
+            |_| true,
+        )?;
+        Ok(Some(records))
+    }
 }
 
 impl<DB, Provider, D> Stage<DB> for HeaderStage<Provider, D>
@@ -321,6 +376,20 @@ where
         )?;
         provider.unwind_table_by_num::<tables::CanonicalHeaders>(input.unwind_to)?;
         provider.unwind_table_by_num::<tables::HeaderTerminalDifficulties>(input.unwind_to)?;
+
+        // Remove the accumulator epochs that end after the unwind target. If the block after the
+        // target is post-merge, all epochs end before it.
+        let is_pre_merge = provider
+            .header_by_number(input.unwind_to + 1)?
+            .map_or(false, |header| !header.difficulty.is_zero());
+        if is_pre_merge {
+            let mut cursor = provider.tx_ref().cursor_write::<tables::HeaderAccumulators>()?;
+            let mut walker = cursor.walk(Some(epoch_of(input.unwind_to + 1)))?;
+            while walker.next().transpose()?.is_some() {
+                walker.delete_current()?;
+            }
+        }
+
         let unfinalized_headers_unwound =
             provider.unwind_table_by_num::<tables::Headers>(input.unwind_to)?;
 
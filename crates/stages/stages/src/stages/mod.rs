@@ -18,6 +18,8 @@ mod index_storage_history;
 mod merkle;
 /// The sender recovery stage.
 mod sender_recovery;
+/// State cache of the execution stage.
+mod state_cache;
 /// The transaction lookup stage
 mod tx_lookup;
 
@@ -32,6 +34,7 @@ pub use index_storage_history::*;
 pub use merkle::*;
 
 pub use sender_recovery::*;
+pub use state_cache::*;
 pub use tx_lookup::*;
 
 mod utils;
@@ -0,0 +1,350 @@
+use reth_metrics::{
+    metrics::{Counter, Gauge},
+    Metrics,
+};
+use reth_primitives::{Address, BlockNumHash, B256, U256};
+use reth_provider::ProviderError;
+use reth_revm::{
+    db::BundleState,
+    primitives::{AccountInfo, Bytecode},
+    Database,
+};
+use schnellru::{LruMap, Unlimited};
+use std::{collections::HashSet, hash::Hash, mem::size_of};
+use tracing::debug;
+
+/// Bounded LRU of the accounts, storage slots and bytecodes read by the
+/// [`ExecutionStage`](super::ExecutionStage), kept across its batches.
+///
+/// The cache holds the database state as of [`Self::tip`]. After every batch the state changes of
+/// the batch are written to the cache, so the next batch starts with a warm cache instead of
+/// reading everything from the database again. If the database state changed behind the back of the
+/// stage, e.g. because blocks were inserted through the blockchain tree, the cache is cleared. Both
+/// the number and the hash of the tip are compared, since a reorg through the tree can replace the
+/// canonical block at the same height.
+#[derive(Debug)]
+pub struct ExecutionStateCache {
+    /// The block the cached state belongs to.
+    tip: Option<BlockNumHash>,
+    accounts: BoundedLru<Address, Option<AccountInfo>>,
+    storage: BoundedLru<(Address, U256), U256>,
+    bytecodes: BoundedLru<B256, Bytecode>,
+    metrics: ExecutionStateCacheMetrics,
+}
+
+impl ExecutionStateCache {
+    /// Creates an empty cache that holds at most `max_size` bytes of state.
+    ///
+    /// Half of the budget is used for storage slots, the rest is split between accounts and
+    /// bytecodes.
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            tip: None,
+            accounts: BoundedLru::new(max_size / 4),
+            storage: BoundedLru::new(max_size / 2),
+            bytecodes: BoundedLru::new(max_size / 4),
+            metrics: ExecutionStateCacheMetrics::default(),
+        }
+    }
+
+    /// Returns the block the cached state belongs to, `None` if the cache is empty.
+    pub const fn tip(&self) -> Option<BlockNumHash> {
+        self.tip
+    }
+
+    /// Returns the estimated size of the cached state in bytes.
+    pub const fn size(&self) -> usize {
+        self.accounts.size + self.storage.size + self.bytecodes.size
+    }
+
+    /// Clears the cache if it doesn't hold the state as of the canonical `block`.
+    pub(crate) fn ensure_tip(&mut self, block: BlockNumHash) {
+        if self.tip.is_some_and(|tip| tip != block) {
+            debug!(target: "sync::stages::execution", tip = ?self.tip, ?block, "State cache is stale, clearing");
+            self.metrics.invalidations.increment(1);
+            self.clear();
+        }
+    }
+
+    /// Removes all cached state.
+    pub(crate) fn clear(&mut self) {
+        self.tip = None;
+        self.accounts.clear();
+        self.storage.clear();
+        self.bytecodes.clear();
+        self.metrics.size.set(0.0);
+    }
+
+    /// Applies the state changes of a batch that was written to the database, making `tip` the
+    /// block the cached state belongs to.
+    pub(crate) fn commit(&mut self, bundle: &BundleState, tip: BlockNumHash) {
+        // Storage of destroyed accounts is wiped, drop all cached slots of them.
+        let destroyed = bundle
+            .state
+            .iter()
+            .filter(|(_, account)| account.status.was_destroyed())
+            .map(|(address, _)| *address)
+            .collect::<HashSet<_>>();
+        if !destroyed.is_empty() {
+            self.storage.retain(|(address, _)| !destroyed.contains(address));
+        }
+
+        for (address, account) in &bundle.state {
+            let info = account.info.clone().map(|info| AccountInfo { code: None, ..info });
+            self.accounts.insert(*address, info, ACCOUNT_ENTRY_SIZE);
+            for (slot, value) in &account.storage {
+                self.storage.insert((*address, *slot), value.present_value, STORAGE_ENTRY_SIZE);
+            }
+        }
+        for (hash, bytecode) in &bundle.contracts {
+            self.bytecodes.insert(*hash, bytecode.clone(), BYTECODE_ENTRY_SIZE + bytecode.len());
+        }
+
+        self.tip = Some(tip);
+        self.flush_metrics();
+    }
+
+    /// Records the hits and misses of the last batch and the current size.
+    fn flush_metrics(&mut self) {
+        let metrics = &self.metrics;
+        for (lru, hits, misses) in [
+            (&mut self.accounts.stats, &metrics.account_hits, &metrics.account_misses),
+            (&mut self.storage.stats, &metrics.storage_hits, &metrics.storage_misses),
+            (&mut self.bytecodes.stats, &metrics.bytecode_hits, &metrics.bytecode_misses),
+        ] {
+            hits.increment(lru.hits);
+            misses.increment(lru.misses);
+            *lru = LruStats::default();
+        }
+        metrics.size.set(self.size() as f64);
+    }
+}
+
+/// A [Database] that looks up state in an [`ExecutionStateCache`] before reading it from the
+/// underlying database, and caches what it reads.
+#[derive(Debug)]
+pub struct CachedStateDatabase<'a, DB> {
+    db: DB,
+    cache: Option<&'a mut ExecutionStateCache>,
+}
+
+impl<'a, DB> CachedStateDatabase<'a, DB> {
+    /// Creates a new database reading through the `cache`, if any.
+    pub fn new(db: DB, cache: Option<&'a mut ExecutionStateCache>) -> Self {
+        Self { db, cache }
+    }
+}
+
+impl<DB: Database<Error = ProviderError>> Database for CachedStateDatabase<'_, DB> {
+    type Error = ProviderError;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let Some(cache) = self.cache.as_deref_mut() else { return self.db.basic(address) };
+        if let Some(info) = cache.accounts.get(&address) {
+            return Ok(info.clone())
+        }
+        let info = self.db.basic(address)?;
+        cache.accounts.insert(address, info.clone(), ACCOUNT_ENTRY_SIZE);
+        Ok(info)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        let Some(cache) = self.cache.as_deref_mut() else { return self.db.code_by_hash(code_hash) };
+        if let Some(bytecode) = cache.bytecodes.get(&code_hash) {
+            return Ok(bytecode.clone())
+        }
+        let bytecode = self.db.code_by_hash(code_hash)?;
+        cache.bytecodes.insert(code_hash, bytecode.clone(), BYTECODE_ENTRY_SIZE + bytecode.len());
+        Ok(bytecode)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        let Some(cache) = self.cache.as_deref_mut() else { return self.db.storage(address, index) };
+        if let Some(value) = cache.storage.get(&(address, index)) {
+            return Ok(*value)
+        }
+        let value = self.db.storage(address, index)?;
+        cache.storage.insert((address, index), value, STORAGE_ENTRY_SIZE);
+        Ok(value)
+    }
+
+    fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
+        self.db.block_hash(number)
+    }
+}
+
+/// Estimated memory used by a cached account.
+const ACCOUNT_ENTRY_SIZE: usize = entry_size::<Address, Option<AccountInfo>>();
+/// Estimated memory used by a cached storage slot.
+const STORAGE_ENTRY_SIZE: usize = entry_size::<(Address, U256), U256>();
+/// Estimated memory used by a cached bytecode, without the code itself.
+const BYTECODE_ENTRY_SIZE: usize = entry_size::<B256, Bytecode>();
+
+const fn entry_size<K, V>() -> usize {
+    // the map stores the size next to the value, and links each entry to its neighbours
+    size_of::<K>() + size_of::<(V, usize)>() + 2 * size_of::<u32>()
+}
+
+/// Metrics of the [`ExecutionStateCache`].
+#[derive(Metrics)]
+#[metrics(scope = "sync.execution.state_cache")]
+struct ExecutionStateCacheMetrics {
+    /// The number of account lookups served from the cache
+    account_hits: Counter,
+    /// The number of account lookups that had to read the database
+    account_misses: Counter,
+    /// The number of storage lookups served from the cache
+    storage_hits: Counter,
+    /// The number of storage lookups that had to read the database
+    storage_misses: Counter,
+    /// The number of bytecode lookups served from the cache
+    bytecode_hits: Counter,
+    /// The number of bytecode lookups that had to read the database
+    bytecode_misses: Counter,
+    /// The number of times the cache was cleared because the database state changed
+    invalidations: Counter,
+    /// The estimated size of the cached state in bytes
+    size: Gauge,
+}
+
+/// Hits and misses of a [`BoundedLru`] since the metrics were last recorded.
+#[derive(Debug, Default)]
+struct LruStats {
+    hits: u64,
+    misses: u64,
+}
+
+/// LRU map that evicts the least recently used entries once their total size exceeds a limit.
+struct BoundedLru<K, V> {
+    map: LruMap<K, (V, usize), Unlimited>,
+    size: usize,
+    max_size: usize,
+    stats: LruStats,
+}
+
+impl<K: Hash + PartialEq, V> BoundedLru<K, V> {
+    fn new(max_size: usize) -> Self {
+        Self { map: LruMap::new(Unlimited), size: 0, max_size, stats: LruStats::default() }
+    }
+
+    fn get(&mut self, key: &K) -> Option<&V> {
+        match self.map.get(key) {
+            Some((value, _)) => {
+                self.stats.hits += 1;
+                Some(value)
+            }
+            None => {
+                self.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    fn insert(&mut self, key: K, value: V, size: usize) {
+        if let Some((_, old_size)) = self.map.remove(&key) {
+            self.size -= old_size;
+        }
+        if size > self.max_size {
+            return
+        }
+
+        self.map.insert(key, (value, size));
+        self.size += size;
+        while self.size > self.max_size {
+            let Some((_, (_, evicted))) = self.map.pop_oldest() else { break };
+            self.size -= evicted;
+        }
+    }
+
+    fn retain(&mut self, mut keep: impl FnMut(&K) -> bool)
+    where
+        K: Clone,
+    {
+        let removed = self
+            .map
+            .iter()
+            .filter(|(key, _)| !keep(key))
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+        for key in removed {
+            if let Some((_, size)) = self.map.remove(&key) {
+                self.size -= size;
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.map.clear();
+        self.size = 0;
+    }
+}
+
+impl<K, V> std::fmt::Debug for BoundedLru<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BoundedLru")
+            .field("len", &self.map.len())
+            .field("size", &self.size)
+            .field("max_size", &self.max_size)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_revm::db::{states::StorageSlot, AccountStatus, BundleAccount};
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut lru = BoundedLru::<u64, u64>::new(30);
+        lru.insert(1, 1, 10);
+        lru.insert(2, 2, 10);
+        lru.insert(3, 3, 10);
+        assert_eq!(lru.get(&1), Some(&1));
+
+        lru.insert(4, 4, 10);
+        assert_eq!(lru.size, 30);
+        assert_eq!(lru.get(&2), None);
+        assert_eq!(lru.get(&1), Some(&1));
+
+        // entries larger than the limit are not cached
+        lru.insert(5, 5, 31);
+        assert_eq!(lru.get(&5), None);
+        assert_eq!(lru.size, 30);
+    }
+
+    #[test]
+    fn commit_applies_batch() {
+        let address = Address::with_last_byte(1);
+        let mut cache = ExecutionStateCache::new(1 << 20);
+        cache.storage.insert((address, U256::from(1)), U256::from(1), STORAGE_ENTRY_SIZE);
+        cache.storage.insert((address, U256::from(2)), U256::from(2), STORAGE_ENTRY_SIZE);
+
+        let mut bundle = BundleState::default();
+        bundle.state.insert(
+            address,
+            BundleAccount {
+                info: Some(AccountInfo::default()),
+                original_info: None,
+                storage: [(U256::from(1), StorageSlot::new_changed(U256::ZERO, U256::from(3)))]
+                    .into_iter()
+                    .collect(),
+                status: AccountStatus::DestroyedChanged,
+            },
+        );
+        let tip = BlockNumHash::new(10, B256::with_last_byte(10));
+        cache.commit(&bundle, tip);
+        assert_eq!(cache.tip(), Some(tip));
+        assert_eq!(cache.accounts.get(&address), Some(&Some(AccountInfo::default())));
+        assert_eq!(cache.storage.get(&(address, U256::from(1))), Some(&U256::from(3)));
+        assert_eq!(cache.storage.get(&(address, U256::from(2))), None);
+
+        cache.ensure_tip(tip);
+        assert_eq!(cache.tip(), Some(tip));
+
+        // the tip was replaced by a reorg at the same height
+        cache.ensure_tip(BlockNumHash::new(10, B256::with_last_byte(11)));
+        assert_eq!(cache.tip(), None);
+        assert_eq!(cache.size(), 0);
+    }
+}
@@ -0,0 +1,65 @@
+//! Legacy transaction metadata models.
+
+use reth_codecs::{main_codec, Compact};
+use reth_primitives::Bytes;
+
+/// Metadata of a transaction of the OP Mainnet legacy chain, as exported by l2geth.
+///
+/// The legacy chain stored metadata next to each transaction that isn't part of its RLP encoding,
+/// so it's kept verbatim in an auxiliary table.
+#[main_codec]
+#[derive(Debug, Default, Eq, PartialEq, Clone)]
+pub struct StoredLegacyTxMeta {
+    /// The size of the transaction, as reported by the legacy chain.
+    pub size: u64,
+    /// The L1 timestamp of the transaction.
+    pub timestamp: u64,
+    /// The remaining fields of the metadata, RLP encoded.
+    pub rest: Bytes,
+}
+
+/// The L1 fee of a transaction of the OP Mainnet legacy chain, as recorded in its receipt.
+///
+/// The fee of the legacy chain can't be derived from the L1 block info of its blocks, so it's
+/// kept next to the receipts in an auxiliary table.
+#[main_codec]
+#[derive(Debug, Default, Eq, PartialEq, Clone, Copy)]
+pub struct StoredLegacyL1Fee {
+    /// The L1 gas price the fee was charged at.
+    pub l1_gas_price: u64,
+    /// The L1 gas used by the transaction, including the fixed overhead.
+    pub l1_gas_used: u64,
+    /// The L1 fee paid by the transaction.
+    pub l1_fee: u64,
+    /// The dynamic overhead the fee was scaled by, in millionths.
+    pub fee_scalar: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::{Compress, Decompress};
+
+    #[test]
+    fn test_stored_legacy_tx_meta() {
+        let meta = StoredLegacyTxMeta {
+            size: 110,
+            timestamp: 1_636_665_399,
+            rest: Bytes::from_static(&[0xc3, 0x01, 0x02, 0x03]),
+        };
+        assert_eq!(
+            meta.clone(),
+            StoredLegacyTxMeta::decompress::<Vec<_>>(meta.compress()).unwrap()
+        );
+    }
+    #[test]
+    fn test_stored_legacy_l1_fee() {
+        let fee = StoredLegacyL1Fee {
+            l1_gas_price: 1,
+            l1_gas_used: 4802,
+            l1_fee: 7203,
+            fee_scalar: 1_500_000,
+        };
+        assert_eq!(fee, StoredLegacyL1Fee::decompress::<Vec<_>>(fee.compress()).unwrap());
+    }
+}
@@ -14,13 +14,19 @@ pub mod accounts;
 pub mod blocks;
 pub mod client_version;
 pub mod integer_list;
+pub mod legacy_tx_meta;
+pub mod reorg;
 pub mod sharded_key;
 pub mod storage_sharded_key;
+pub mod sync_history;
 
 pub use accounts::*;
 pub use blocks::*;
 pub use client_version::ClientVersion;
+pub use legacy_tx_meta::{StoredLegacyL1Fee, StoredLegacyTxMeta};
+pub use reorg::StoredReorg;
 pub use sharded_key::ShardedKey;
+pub use sync_history::SyncSnapshot;
 
 /// Macro that implements [`Encode`] and [`Decode`] for uint types.
 macro_rules! impl_uints {
@@ -221,7 +227,12 @@ impl_compression_for_compact!(
     StageCheckpoint,
     PruneCheckpoint,
     ClientVersion,
+    StoredReorg,
+    SyncSnapshot,
+    StoredLegacyTxMeta,
+    StoredLegacyL1Fee,
     Requests,
+    AccessList,
     // Non-DB
     GenesisAccount
 );
@@ -340,6 +351,9 @@ mod tests {
             assert_eq!(StoredBlockBodyIndices::bitflag_encoded_bytes(), 1);
             assert_eq!(StoredBlockOmmers::bitflag_encoded_bytes(), 0);
             assert_eq!(StoredBlockWithdrawals::bitflag_encoded_bytes(), 0);
+            assert_eq!(StoredLegacyL1Fee::bitflag_encoded_bytes(), 2);
+            assert_eq!(StoredLegacyTxMeta::bitflag_encoded_bytes(), 1);
+            assert_eq!(StoredReorg::bitflag_encoded_bytes(), 1);
             assert_eq!(StorageHashingCheckpoint::bitflag_encoded_bytes(), 1);
             assert_eq!(TxEip1559::bitflag_encoded_bytes(), 4);
             assert_eq!(TxEip2930::bitflag_encoded_bytes(), 3);
@@ -372,6 +386,9 @@ mod tests {
             assert_eq!(StoredBlockBodyIndices::bitflag_encoded_bytes(), 1);
             assert_eq!(StoredBlockOmmers::bitflag_encoded_bytes(), 0);
             assert_eq!(StoredBlockWithdrawals::bitflag_encoded_bytes(), 0);
+            assert_eq!(StoredLegacyL1Fee::bitflag_encoded_bytes(), 2);
+            assert_eq!(StoredLegacyTxMeta::bitflag_encoded_bytes(), 1);
+            assert_eq!(StoredReorg::bitflag_encoded_bytes(), 1);
             assert_eq!(StorageHashingCheckpoint::bitflag_encoded_bytes(), 1);
             assert_eq!(TxEip1559::bitflag_encoded_bytes(), 4);
             assert_eq!(TxEip2930::bitflag_encoded_bytes(), 3);
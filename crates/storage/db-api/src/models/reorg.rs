@@ -0,0 +1,46 @@
+//! Reorg journal model.
+
+use reth_codecs::{main_codec, Compact};
+use reth_primitives::{BlockNumber, B256};
+
+/// A reorg of the canonical chain, as recorded in the reorg journal.
+#[main_codec]
+#[derive(Debug, Default, Eq, PartialEq, Clone)]
+pub struct StoredReorg {
+    /// The unix timestamp in seconds at which the reorg was committed.
+    pub timestamp: u64,
+    /// The number of the last block the old and the new chain have in common.
+    pub fork_block: BlockNumber,
+    /// The hash of the last block the old and the new chain have in common.
+    pub fork_hash: B256,
+    /// The hashes of the blocks removed from the canonical chain, in increasing block order.
+    pub old_blocks: Vec<B256>,
+    /// The hashes of the blocks added to the canonical chain, in increasing block order.
+    pub new_blocks: Vec<B256>,
+}
+
+impl StoredReorg {
+    /// Returns the number of blocks removed from the canonical chain.
+    pub fn depth(&self) -> u64 {
+        self.old_blocks.len() as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::{Compress, Decompress};
+
+    #[test]
+    fn test_stored_reorg() {
+        let reorg = StoredReorg {
+            timestamp: 1_700_000_000,
+            fork_block: 100,
+            fork_hash: B256::with_last_byte(1),
+            old_blocks: vec![B256::with_last_byte(2), B256::with_last_byte(3)],
+            new_blocks: vec![B256::with_last_byte(4)],
+        };
+        assert_eq!(reorg.depth(), 2);
+        assert_eq!(reorg.clone(), StoredReorg::decompress::<Vec<_>>(reorg.compress()).unwrap());
+    }
+}
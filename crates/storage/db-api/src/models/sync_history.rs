@@ -0,0 +1,70 @@
+//! Sync history model.
+
+use reth_codecs::Compact;
+use reth_primitives::BlockNumber;
+use serde::{Deserialize, Serialize};
+
+/// A snapshot of the sync progress, as recorded periodically in the sync history.
+#[derive(Debug, Default, Eq, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SyncSnapshot {
+    /// The highest block that went through all stages of the pipeline.
+    pub block_number: BlockNumber,
+    /// The pipeline stage that was running, empty if the pipeline was idle.
+    pub stage: String,
+    /// The checkpoint of the running stage.
+    pub stage_checkpoint: BlockNumber,
+    /// The size in bytes of the database and the static files.
+    pub disk_usage: u64,
+}
+
+impl Compact for SyncSnapshot {
+    fn to_compact<B>(self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        let Self { block_number, stage, stage_checkpoint, disk_usage } = self;
+        buf.put_u64(block_number);
+        buf.put_u64(stage_checkpoint);
+        buf.put_u64(disk_usage);
+        24 + stage.into_bytes().to_compact(buf)
+    }
+
+    fn from_compact(mut buf: &[u8], len: usize) -> (Self, &[u8]) {
+        use bytes::Buf;
+
+        let block_number = buf.get_u64();
+        let stage_checkpoint = buf.get_u64();
+        let disk_usage = buf.get_u64();
+        let (stage, buf) = Vec::<u8>::from_compact(buf, len);
+        let snapshot = Self {
+            block_number,
+            stage: String::from_utf8_lossy(&stage).into_owned(),
+            stage_checkpoint,
+            disk_usage,
+        };
+        (snapshot, buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::{Compress, Decompress};
+
+    #[test]
+    fn test_sync_snapshot() {
+        let snapshot = SyncSnapshot {
+            block_number: 19_000_000,
+            stage: "Execution".to_string(),
+            stage_checkpoint: 19_500_000,
+            disk_usage: 1 << 40,
+        };
+        assert_eq!(
+            snapshot.clone(),
+            SyncSnapshot::decompress::<Vec<_>>(snapshot.compress()).unwrap()
+        );
+
+        let idle = SyncSnapshot { block_number: 1, ..Default::default() };
+        assert_eq!(idle.clone(), SyncSnapshot::decompress::<Vec<_>>(idle.compress()).unwrap());
+    }
+}
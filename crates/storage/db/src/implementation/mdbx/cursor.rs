@@ -1,7 +1,7 @@
 //! Cursor wrapper for libmdbx-sys.
 
 use crate::{
-    metrics::{DatabaseEnvMetrics, Operation},
+    metrics::{DatabaseEnvMetrics, Operation, OperationKind},
     tables::utils::*,
     DatabaseError,
 };
@@ -59,6 +59,22 @@ impl<K: TransactionKind, T: Table> Cursor<K, T> {
             f(self)
         }
     }
+
+    /// If `self.metrics` is `Some(...)` and records the latencies of operations, record the
+    /// latency of the closure as an operation of the provided kind.
+    ///
+    /// Otherwise, just execute the closure.
+    fn execute_with_latency_metric<R>(
+        &mut self,
+        kind: OperationKind,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        if let Some(metrics) = self.metrics.as_ref().filter(|m| m.records_latencies()).cloned() {
+            metrics.record_latency(T::NAME, kind, || f(self))
+        } else {
+            f(self)
+        }
+    }
 }
 
 /// Decodes a `(key, value)` pair from the database.
@@ -90,36 +106,44 @@ macro_rules! compress_to_buf_or_ref {
 
 impl<K: TransactionKind, T: Table> DbCursorRO<T> for Cursor<K, T> {
     fn first(&mut self) -> PairResult<T> {
-        decode::<T>(self.inner.first())
+        self.execute_with_latency_metric(OperationKind::Read, |this| {
+            decode::<T>(this.inner.first())
+        })
     }
 
     fn seek_exact(&mut self, key: <T as Table>::Key) -> PairResult<T> {
-        decode::<T>(self.inner.set_key(key.encode().as_ref()))
+        self.execute_with_latency_metric(OperationKind::Seek, |this| {
+            decode::<T>(this.inner.set_key(key.encode().as_ref()))
+        })
     }
 
     fn seek(&mut self, key: <T as Table>::Key) -> PairResult<T> {
-        decode::<T>(self.inner.set_range(key.encode().as_ref()))
+        self.execute_with_latency_metric(OperationKind::Seek, |this| {
+            decode::<T>(this.inner.set_range(key.encode().as_ref()))
+        })
     }
 
     fn next(&mut self) -> PairResult<T> {
-        decode::<T>(self.inner.next())
+        self.execute_with_latency_metric(OperationKind::Read, |this| decode::<T>(this.inner.next()))
     }
 
     fn prev(&mut self) -> PairResult<T> {
-        decode::<T>(self.inner.prev())
+        self.execute_with_latency_metric(OperationKind::Read, |this| decode::<T>(this.inner.prev()))
     }
 
     fn last(&mut self) -> PairResult<T> {
-        decode::<T>(self.inner.last())
+        self.execute_with_latency_metric(OperationKind::Read, |this| decode::<T>(this.inner.last()))
     }
 
     fn current(&mut self) -> PairResult<T> {
-        decode::<T>(self.inner.get_current())
+        self.execute_with_latency_metric(OperationKind::Read, |this| {
+            decode::<T>(this.inner.get_current())
+        })
     }
 
     fn walk(&mut self, start_key: Option<T::Key>) -> Result<Walker<'_, T, Self>, DatabaseError> {
         let start = if let Some(start_key) = start_key {
-            decode::<T>(self.inner.set_range(start_key.encode().as_ref())).transpose()
+            self.seek(start_key).transpose()
         } else {
             self.first().transpose()
         };
@@ -132,13 +156,13 @@ impl<K: TransactionKind, T: Table> DbCursorRO<T> for Cursor<K, T> {
         range: impl RangeBounds<T::Key>,
     ) -> Result<RangeWalker<'_, T, Self>, DatabaseError> {
         let start = match range.start_bound().cloned() {
-            Bound::Included(key) => self.inner.set_range(key.encode().as_ref()),
+            Bound::Included(key) => self.seek(key),
             Bound::Excluded(_key) => {
                 unreachable!("Rust doesn't allow for Bound::Excluded in starting bounds");
             }
-            Bound::Unbounded => self.inner.first(),
+            Bound::Unbounded => self.first(),
         };
-        let start = decode::<T>(start).transpose();
+        let start = start.transpose();
         Ok(RangeWalker::new(self, start, range.end_bound().cloned()))
     }
 
@@ -146,12 +170,9 @@ impl<K: TransactionKind, T: Table> DbCursorRO<T> for Cursor<K, T> {
         &mut self,
         start_key: Option<T::Key>,
     ) -> Result<ReverseWalker<'_, T, Self>, DatabaseError> {
-        let start = if let Some(start_key) = start_key {
-            decode::<T>(self.inner.set_range(start_key.encode().as_ref()))
-        } else {
-            self.last()
-        }
-        .transpose();
+        let start =
+            if let Some(start_key) = start_key { self.seek(start_key) } else { self.last() }
+                .transpose();
 
         Ok(ReverseWalker::new(self, start))
     }
@@ -160,21 +181,27 @@ impl<K: TransactionKind, T: Table> DbCursorRO<T> for Cursor<K, T> {
 impl<K: TransactionKind, T: DupSort> DbDupCursorRO<T> for Cursor<K, T> {
     /// Returns the next `(key, value)` pair of a DUPSORT table.
     fn next_dup(&mut self) -> PairResult<T> {
-        decode::<T>(self.inner.next_dup())
+        self.execute_with_latency_metric(OperationKind::Read, |this| {
+            decode::<T>(this.inner.next_dup())
+        })
     }
 
     /// Returns the next `(key, value)` pair skipping the duplicates.
     fn next_no_dup(&mut self) -> PairResult<T> {
-        decode::<T>(self.inner.next_nodup())
+        self.execute_with_latency_metric(OperationKind::Read, |this| {
+            decode::<T>(this.inner.next_nodup())
+        })
     }
 
     /// Returns the next `value` of a duplicate `key`.
     fn next_dup_val(&mut self) -> ValueOnlyResult<T> {
-        self.inner
-            .next_dup()
-            .map_err(|e| DatabaseError::Read(e.into()))?
-            .map(decode_value::<T>)
-            .transpose()
+        self.execute_with_latency_metric(OperationKind::Read, |this| {
+            this.inner
+                .next_dup()
+                .map_err(|e| DatabaseError::Read(e.into()))?
+                .map(decode_value::<T>)
+                .transpose()
+        })
     }
 
     fn seek_by_key_subkey(
@@ -182,11 +209,13 @@ impl<K: TransactionKind, T: DupSort> DbDupCursorRO<T> for Cursor<K, T> {
         key: <T as Table>::Key,
         subkey: <T as DupSort>::SubKey,
     ) -> ValueOnlyResult<T> {
-        self.inner
-            .get_both_range(key.encode().as_ref(), subkey.encode().as_ref())
-            .map_err(|e| DatabaseError::Read(e.into()))?
-            .map(decode_one::<T>)
-            .transpose()
+        self.execute_with_latency_metric(OperationKind::Seek, |this| {
+            this.inner
+                .get_both_range(key.encode().as_ref(), subkey.encode().as_ref())
+                .map_err(|e| DatabaseError::Read(e.into()))?
+                .map(decode_one::<T>)
+                .transpose()
+        })
     }
 
     /// Depending on its arguments, returns an iterator starting at:
@@ -201,20 +230,26 @@ impl<K: TransactionKind, T: DupSort> DbDupCursorRO<T> for Cursor<K, T> {
     ) -> Result<DupWalker<'_, T, Self>, DatabaseError> {
         let start = match (key, subkey) {
             (Some(key), Some(subkey)) => {
-                // encode key and decode it after.
-                let key: Vec<u8> = key.encode().into();
-                self.inner
-                    .get_both_range(key.as_ref(), subkey.encode().as_ref())
-                    .map_err(|e| DatabaseError::Read(e.into()))?
-                    .map(|val| decoder::<T>((Cow::Owned(key), val)))
+                self.execute_with_latency_metric(OperationKind::Seek, |this| {
+                    // encode key and decode it after.
+                    let key: Vec<u8> = key.encode().into();
+                    Ok::<_, DatabaseError>(
+                        this.inner
+                            .get_both_range(key.as_ref(), subkey.encode().as_ref())
+                            .map_err(|e| DatabaseError::Read(e.into()))?
+                            .map(|val| decoder::<T>((Cow::Owned(key), val))),
+                    )
+                })?
             }
-            (Some(key), None) => {
+            (Some(key), None) => self.execute_with_latency_metric(OperationKind::Seek, |this| {
                 let key: Vec<u8> = key.encode().into();
-                self.inner
-                    .set(key.as_ref())
-                    .map_err(|e| DatabaseError::Read(e.into()))?
-                    .map(|val| decoder::<T>((Cow::Owned(key), val)))
-            }
+                Ok::<_, DatabaseError>(
+                    this.inner
+                        .set(key.as_ref())
+                        .map_err(|e| DatabaseError::Read(e.into()))?
+                        .map(|val| decoder::<T>((Cow::Owned(key), val))),
+                )
+            })?,
             (None, Some(subkey)) => {
                 if let Some((key, _)) = self.first()? {
                     let key: Vec<u8> = key.encode().into();
@@ -89,6 +89,11 @@ pub struct DatabaseArguments {
     ///
     /// This flag affects only at environment opening but can't be changed after.
     exclusive: Option<bool>,
+    /// Record the latencies of reads, seeks and writes per table if metrics are enabled with
+    /// [`DatabaseEnv::with_metrics`].
+    latency_metrics: bool,
+    /// Read ahead when loading pages. If [None], reading ahead is disabled.
+    readahead: Option<bool>,
 }
 
 impl DatabaseArguments {
@@ -99,6 +104,8 @@ impl DatabaseArguments {
             log_level: None,
             max_read_transaction_duration: None,
             exclusive: None,
+            latency_metrics: false,
+            readahead: None,
         }
     }
 
@@ -123,6 +130,20 @@ impl DatabaseArguments {
         self
     }
 
+    /// Set whether the latencies of reads, seeks and writes are recorded per table.
+    ///
+    /// Every database operation is timed, so this adds overhead to hot paths.
+    pub const fn with_latency_metrics(mut self, latency_metrics: bool) -> Self {
+        self.latency_metrics = latency_metrics;
+        self
+    }
+
+    /// Set whether pages are read ahead.
+    pub const fn with_readahead(mut self, readahead: Option<bool>) -> Self {
+        self.readahead = readahead;
+        self
+    }
+
     /// Returns the client version if any.
     pub const fn client_version(&self) -> &ClientVersion {
         &self.client_version
@@ -136,6 +157,8 @@ pub struct DatabaseEnv {
     inner: Environment,
     /// Cache for metric handles. If `None`, metrics are not recorded.
     metrics: Option<Arc<DatabaseEnvMetrics>>,
+    /// If `true`, the latencies of operations are recorded once metrics are enabled.
+    latency_metrics: bool,
     /// Write lock for when dealing with a read-write environment.
     _lock_file: Option<StorageLock>,
 }
@@ -336,9 +359,9 @@ impl DatabaseEnv {
 
         inner_env.set_flags(EnvironmentFlags {
             mode,
-            // We disable readahead because it improves performance for linear scans, but
-            // worsens it for random access (which is our access pattern outside of sync)
-            no_rdahead: true,
+            // We disable readahead by default because it improves performance for linear scans,
+            // but worsens it for random access (which is our access pattern outside of sync)
+            no_rdahead: !args.readahead.unwrap_or_default(),
             coalesce: true,
             exclusive: args.exclusive.unwrap_or_default(),
             ..Default::default()
@@ -405,6 +428,7 @@ impl DatabaseEnv {
         let env = Self {
             inner: inner_env.open(path).map_err(|e| DatabaseError::Open(e.into()))?,
             metrics: None,
+            latency_metrics: args.latency_metrics,
             _lock_file,
         };
 
@@ -413,7 +437,7 @@ impl DatabaseEnv {
 
     /// Enables metrics on the database.
     pub fn with_metrics(mut self) -> Self {
-        self.metrics = Some(DatabaseEnvMetrics::new().into());
+        self.metrics = Some(DatabaseEnvMetrics::new(self.latency_metrics).into());
         self
     }
 
@@ -603,6 +627,39 @@ mod tests {
         assert_eq!(first.1, value, "First next should be put value");
     }
 
+    #[test]
+    fn db_cursor_with_latency_metrics() {
+        let args = DatabaseArguments::new(ClientVersion::default()).with_latency_metrics(true);
+        let env = DatabaseEnv::open(
+            &tempfile::TempDir::new().expect(ERROR_TEMPDIR).into_path(),
+            DatabaseEnvKind::RW,
+            args,
+        )
+        .expect(ERROR_DB_CREATION)
+        .with_metrics();
+        env.create_tables().expect(ERROR_TABLE_CREATION);
+        assert!(env.metrics.as_ref().unwrap().records_latencies());
+
+        let value = Header::default();
+
+        // PUT
+        let tx = env.tx_mut().expect(ERROR_INIT_TX);
+        tx.put::<Headers>(1, value.clone()).expect(ERROR_PUT);
+        tx.put::<Headers>(2, value.clone()).expect(ERROR_PUT);
+        tx.commit().expect(ERROR_COMMIT);
+
+        // Cursor
+        let tx = env.tx().expect(ERROR_INIT_TX);
+        assert_eq!(tx.get::<Headers>(1).expect(ERROR_GET), Some(value.clone()));
+        let mut cursor = tx.cursor_read::<Headers>().unwrap();
+        assert_eq!(cursor.seek_exact(2).unwrap(), Some((2, value.clone())));
+        assert_eq!(cursor.prev().unwrap(), Some((1, value)));
+
+        // Walk
+        let walk = cursor.walk_range(1..).unwrap();
+        assert_eq!(walk.map(|entry| entry.unwrap().0).collect::<Vec<_>>(), vec![1, 2]);
+    }
+
     #[test]
     fn db_cursor_walk_range() {
         let db: Arc<DatabaseEnv> = create_test_db(DatabaseEnvKind::RW);
@@ -12,7 +12,7 @@ use reth_db_api::{
 };
 use reth_libmdbx::{ffi::MDBX_dbi, CommitLatency, Transaction, TransactionKind, WriteFlags, RW};
 use reth_storage_errors::db::{DatabaseWriteError, DatabaseWriteOperation};
-use reth_tracing::tracing::{debug, trace, warn};
+use reth_tracing::tracing::{debug, debug_span, trace, warn};
 use std::{
     backtrace::Backtrace,
     marker::PhantomData,
@@ -274,6 +274,7 @@ impl<K: TransactionKind> DbTx for Tx<K> {
     }
 
     fn commit(self) -> Result<bool, DatabaseError> {
+        let _span = debug_span!(target: "storage::db::mdbx", "commit").entered();
         self.execute_with_close_transaction_metric(TransactionOutcome::Commit, |this| {
             match this.inner.commit().map_err(|e| DatabaseError::Commit(e.into())) {
                 Ok((v, latency)) => (Ok(v), Some(latency)),
@@ -56,6 +56,36 @@ impl StorageLock {
             Ok(Self(Arc::new(StorageLockInner::new(file_path)?)))
         }
     }
+
+    /// Returns the status of the lock on the target directory, without acquiring it.
+    pub fn status(path: &Path) -> Result<StorageLockStatus, StorageLockError> {
+        let file_path = path.join(LOCKFILE_NAME);
+        if !file_path.exists() {
+            return Ok(StorageLockStatus::Unlocked)
+        }
+
+        match ProcessUID::parse(&file_path)? {
+            Some(process_lock)
+                if process_lock.pid != (process::id() as usize) && process_lock.is_active() =>
+            {
+                Ok(StorageLockStatus::Taken(process_lock.pid))
+            }
+            _ => Ok(StorageLockStatus::Stale),
+        }
+    }
+}
+
+/// Status of the lock on a storage directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageLockStatus {
+    /// There is no lock file.
+    Unlocked,
+    /// The lock file was left behind by a process that is no longer running, e.g. after a crash.
+    ///
+    /// It is replaced the next time the storage is opened with read-write access.
+    Stale,
+    /// The lock is held by the running process with the given PID.
+    Taken(usize),
 }
 
 impl Drop for StorageLock {
@@ -162,6 +192,7 @@ mod tests {
             fake_pid += 1;
         }
         ProcessUID { pid: fake_pid, start_time: u64::MAX }.write(&lock_file).unwrap();
+        assert_eq!(StorageLock::status(temp_dir.path()), Ok(StorageLockStatus::Stale));
         assert_eq!(Ok(lock.clone()), StorageLock::try_acquire(temp_dir.path()));
 
         let mut pid_1 = ProcessUID::new(1).unwrap();
@@ -169,6 +200,7 @@ mod tests {
         // If a parsed `ProcessUID` exists, the lock can NOT be acquired.
         pid_1.write(&lock_file).unwrap();
         assert_eq!(Err(StorageLockError::Taken(1)), StorageLock::try_acquire(temp_dir.path()));
+        assert_eq!(StorageLock::status(temp_dir.path()), Ok(StorageLockStatus::Taken(1)));
 
         // A lock of a different but existing PID can be acquired ONLY IF the start_time differs.
         pid_1.start_time += 1;
@@ -186,5 +218,6 @@ mod tests {
         assert!(lock_file.exists());
         drop(lock);
         assert!(!lock_file.exists());
+        assert_eq!(StorageLock::status(temp_dir.path()), Ok(StorageLockStatus::Unlocked));
     }
 }
@@ -23,16 +23,22 @@ pub(crate) struct DatabaseEnvMetrics {
     /// outcome. Can only be updated at tx close, as outcome is only known at that point.
     transaction_outcomes:
         FxHashMap<(TransactionMode, TransactionOutcome), TransactionOutcomeMetrics>,
+    /// Caches `LatencyMetrics` handles for each table and operation kind tuple. If `None`, the
+    /// latencies of operations are not recorded.
+    latencies: Option<FxHashMap<(&'static str, OperationKind), LatencyMetrics>>,
 }
 
 impl DatabaseEnvMetrics {
-    pub(crate) fn new() -> Self {
+    /// Creates the metric handles, including the handles for the latencies of operations if
+    /// `latencies` is `true`.
+    pub(crate) fn new(latencies: bool) -> Self {
         // Pre-populate metric handle maps with all possible combinations of labels
         // to avoid runtime locks on the map when recording metrics.
         Self {
             operations: Self::generate_operation_handles(),
             transactions: Self::generate_transaction_handles(),
             transaction_outcomes: Self::generate_transaction_outcome_handles(),
+            latencies: latencies.then(Self::generate_latency_handles),
         }
     }
 
@@ -95,6 +101,27 @@ impl DatabaseEnvMetrics {
         transaction_outcomes
     }
 
+    /// Generate a map of all possible latency handles for each table and operation kind tuple.
+    /// Used for tracking the latencies of all reads, seeks and writes.
+    fn generate_latency_handles() -> FxHashMap<(&'static str, OperationKind), LatencyMetrics> {
+        let mut latencies = FxHashMap::with_capacity_and_hasher(
+            Tables::COUNT * OperationKind::COUNT,
+            Default::default(),
+        );
+        for table in Tables::ALL {
+            for kind in OperationKind::iter() {
+                latencies.insert(
+                    (table.name(), kind),
+                    LatencyMetrics::new_with_labels(&[
+                        (Labels::Table.as_str(), table.name()),
+                        (Labels::OperationKind.as_str(), kind.as_str()),
+                    ]),
+                );
+            }
+        }
+        latencies
+    }
+
     /// Record a metric for database operation executed in `f`.
     /// Panics if a metric recorder is not found for the given table and operation.
     pub(crate) fn record_operation<R>(
@@ -104,10 +131,40 @@ impl DatabaseEnvMetrics {
         value_size: Option<usize>,
         f: impl FnOnce() -> R,
     ) -> R {
-        self.operations
+        let metrics = self
+            .operations
             .get(&(table, operation))
-            .expect("operation & table metric handle not found")
-            .record(value_size, f)
+            .expect("operation & table metric handle not found");
+        match self.latency_metrics(table, operation.kind()) {
+            Some(latency) => latency.record(|| metrics.record(value_size, f)),
+            None => metrics.record(value_size, f),
+        }
+    }
+
+    /// Returns `true` if the latencies of operations are recorded.
+    pub(crate) const fn records_latencies(&self) -> bool {
+        self.latencies.is_some()
+    }
+
+    /// Record the latency of an operation of the given kind executed in `f`, if the latencies of
+    /// operations are recorded.
+    /// Panics if a metric recorder is not found for the given table and operation kind.
+    pub(crate) fn record_latency<R>(
+        &self,
+        table: &'static str,
+        kind: OperationKind,
+        f: impl FnOnce() -> R,
+    ) -> R {
+        match self.latency_metrics(table, kind) {
+            Some(latency) => latency.record(f),
+            None => f(),
+        }
+    }
+
+    fn latency_metrics(&self, table: &'static str, kind: OperationKind) -> Option<&LatencyMetrics> {
+        self.latencies.as_ref().map(|latencies| {
+            latencies.get(&(table, kind)).expect("operation kind & table metric handle not found")
+        })
     }
 
     /// Record metrics for opening a database transaction.
@@ -229,6 +286,44 @@ impl Operation {
             Self::CursorDeleteCurrentDuplicates => "cursor-delete-current-duplicates",
         }
     }
+
+    /// Returns the kind of the operation.
+    pub(crate) const fn kind(&self) -> OperationKind {
+        match self {
+            Self::Get => OperationKind::Read,
+            Self::Put |
+            Self::Delete |
+            Self::CursorUpsert |
+            Self::CursorInsert |
+            Self::CursorAppend |
+            Self::CursorAppendDup |
+            Self::CursorDeleteCurrent |
+            Self::CursorDeleteCurrentDuplicates => OperationKind::Write,
+        }
+    }
+}
+
+/// Kinds of operations conducted on the database, which latencies are recorded for.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, EnumCount, EnumIter)]
+pub(crate) enum OperationKind {
+    /// Reads of a value by key, or of the next, previous, first, last or current entry of a
+    /// cursor.
+    Read,
+    /// Cursor positioning at a key, or at the first entry greater than or equal to a key.
+    Seek,
+    /// Puts, inserts, appends and deletes.
+    Write,
+}
+
+impl OperationKind {
+    /// Returns the operation kind as a string.
+    pub(crate) const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Read => "read",
+            Self::Seek => "seek",
+            Self::Write => "write",
+        }
+    }
 }
 
 /// Enum defining labels for various aspects used in metrics.
@@ -241,6 +336,8 @@ enum Labels {
     TransactionOutcome,
     /// Label representing a database operation.
     Operation,
+    /// Label representing a kind of database operations.
+    OperationKind,
 }
 
 impl Labels {
@@ -251,6 +348,7 @@ impl Labels {
             Self::TransactionMode => "mode",
             Self::TransactionOutcome => "outcome",
             Self::Operation => "operation",
+            Self::OperationKind => "kind",
         }
     }
 }
@@ -357,3 +455,23 @@ impl OperationMetrics {
         }
     }
 }
+
+#[derive(Metrics, Clone)]
+#[metrics(scope = "database.table")]
+pub(crate) struct LatencyMetrics {
+    /// Total number of database operations of the kind made on the table
+    calls_total: Counter,
+    /// The time it took to execute a database operation of the kind on the table
+    duration_seconds: Histogram,
+}
+
+impl LatencyMetrics {
+    /// Record the latency of the operation executed in `f`.
+    pub(crate) fn record<R>(&self, f: impl FnOnce() -> R) -> R {
+        self.calls_total.increment(1);
+        let start = Instant::now();
+        let result = f();
+        self.duration_seconds.record(start.elapsed());
+        result
+    }
+}
@@ -1,22 +1,40 @@
-use super::mask::{ColumnSelectorOne, ColumnSelectorThree, ColumnSelectorTwo};
+use super::{
+    mask::{ColumnSelectorOne, ColumnSelectorThree, ColumnSelectorTwo},
+    ReceiptMask,
+};
 use derive_more::{Deref, DerefMut};
 use reth_db_api::table::Decompress;
 use reth_nippy_jar::{DataReader, NippyJar, NippyJarCursor};
-use reth_primitives::{static_file::SegmentHeader, B256};
+use reth_primitives::{
+    receipt_compaction::TopicDictionary, static_file::SegmentHeader, Receipt, B256,
+};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use std::sync::Arc;
 
 /// Cursor of a static file segment.
 #[derive(Debug, Deref, DerefMut)]
-pub struct StaticFileCursor<'a>(NippyJarCursor<'a, SegmentHeader>);
+pub struct StaticFileCursor<'a> {
+    #[deref]
+    #[deref_mut]
+    cursor: NippyJarCursor<'a, SegmentHeader>,
+    /// Topic dictionary of a compacted receipts segment.
+    receipt_topics: Option<Arc<TopicDictionary>>,
+}
 
 impl<'a> StaticFileCursor<'a> {
     /// Returns a new [`StaticFileCursor`].
     pub fn new(jar: &'a NippyJar<SegmentHeader>, reader: Arc<DataReader>) -> ProviderResult<Self> {
-        Ok(Self(
-            NippyJarCursor::with_reader(jar, reader)
+        Ok(Self {
+            cursor: NippyJarCursor::with_reader(jar, reader)
                 .map_err(|err| ProviderError::NippyJar(err.to_string()))?,
-        ))
+            receipt_topics: None,
+        })
+    }
+
+    /// Sets the topic dictionary used to decode receipts of a compacted receipts segment.
+    pub fn with_receipt_topics(mut self, receipt_topics: Option<Arc<TopicDictionary>>) -> Self {
+        self.receipt_topics = receipt_topics;
+        self
     }
 
     /// Returns the current `BlockNumber` or `TxNumber` of the cursor depending on the kind of
@@ -78,6 +96,23 @@ impl<'a> StaticFileCursor<'a> {
         }
     }
 
+    /// Gets a receipt from a receipts segment, decoding it with the segment topic dictionary if
+    /// the segment has been compacted.
+    pub fn get_receipt(&mut self, key_or_num: KeyOrNumber<'_>) -> ProviderResult<Option<Receipt>> {
+        let Some(topics) = self.receipt_topics.clone() else {
+            return self.get_one::<ReceiptMask<Receipt>>(key_or_num)
+        };
+
+        match self.get(key_or_num, <ReceiptMask<Receipt>>::MASK)? {
+            Some(row) => Ok(Some(
+                topics
+                    .decode_receipt(row[0])
+                    .map_err(|err| ProviderError::NippyJar(err.to_string()))?,
+            )),
+            None => Ok(None),
+        }
+    }
+
     /// Gets three column values from a row.
     #[allow(clippy::type_complexity)]
     pub fn get_three<M: ColumnSelectorThree>(
@@ -3,7 +3,7 @@
 mod generation;
 use std::{
     collections::{hash_map::Entry, HashMap},
-    path::Path,
+    path::{Path, PathBuf},
 };
 
 pub use generation::*;
@@ -15,12 +15,16 @@ mod mask;
 pub use mask::*;
 use reth_nippy_jar::{NippyJar, NippyJarError};
 use reth_primitives::{
+    receipt_compaction::TopicDictionary,
     static_file::{SegmentHeader, SegmentRangeInclusive},
     StaticFileSegment,
 };
 
 mod masks;
 
+/// File extension of the topic dictionary of a compacted receipts static file.
+const RECEIPT_TOPICS_FILE_EXTENSION: &str = "topics";
+
 /// Alias type for a map of [`StaticFileSegment`] and sorted lists of existing static file ranges.
 type SortedStaticFiles =
     HashMap<StaticFileSegment, Vec<(SegmentRangeInclusive, Option<SegmentRangeInclusive>)>>;
@@ -73,3 +77,25 @@ pub fn iter_static_files(path: impl AsRef<Path>) -> Result<SortedStaticFiles, Ni
 
     Ok(static_files)
 }
+
+/// Returns the path of the topic dictionary belonging to the receipts static file at `data_path`.
+///
+/// Its presence marks the static file as compacted: every receipt is encoded with
+/// [`TopicDictionary::encode_receipt`] instead of its `Compact` encoding.
+pub fn receipt_topics_path(data_path: &Path) -> PathBuf {
+    data_path.with_extension(RECEIPT_TOPICS_FILE_EXTENSION)
+}
+
+/// Loads the topic dictionary of the receipts static file at `data_path`, if it has been
+/// compacted.
+pub fn load_receipt_topics(data_path: &Path) -> Result<Option<TopicDictionary>, NippyJarError> {
+    let path = receipt_topics_path(data_path);
+    if !path.exists() {
+        return Ok(None)
+    }
+
+    let bytes = reth_fs_util::read(&path).map_err(|err| NippyJarError::Custom(err.to_string()))?;
+    TopicDictionary::from_bytes(&bytes)
+        .map(Some)
+        .map_err(|err| NippyJarError::Custom(format!("{}: {err}", path.display())))
+}
@@ -23,14 +23,17 @@ use reth_db_api::{
         accounts::{AccountBeforeTx, BlockNumberAddress},
         blocks::{HeaderHash, StoredBlockOmmers},
         client_version::ClientVersion,
+        legacy_tx_meta::{StoredLegacyL1Fee, StoredLegacyTxMeta},
+        reorg::StoredReorg,
         storage_sharded_key::StorageShardedKey,
+        sync_history::SyncSnapshot,
         CompactU256, ShardedKey, StoredBlockBodyIndices, StoredBlockWithdrawals,
     },
     table::{Decode, DupSort, Encode, Table},
 };
 use reth_primitives::{
-    Account, Address, BlockHash, BlockNumber, Bytecode, Header, Receipt, Requests, StorageEntry,
-    TransactionSignedNoHash, TxHash, TxNumber, B256,
+    AccessList, Account, Address, BlockHash, BlockNumber, Bytecode, Header, Receipt, Requests,
+    StorageEntry, TransactionSignedNoHash, TxHash, TxNumber, B256,
 };
 use reth_primitives_traits::IntegerList;
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
@@ -306,10 +309,8 @@ tables! {
     /// Canonical only Stores transaction receipts.
     table Receipts<Key = TxNumber, Value = Receipt>;
 
-    /// Stores all smart contract bytecodes.
-    /// There will be multiple accounts that have same bytecode
-    /// So we would need to introduce reference counter.
-    /// This will be small optimization on state.
+    /// Stores all smart contract bytecodes, once per code hash.
+    /// Bytecodes are deleted once they are no longer referenced, see [`BytecodeReferences`].
     table Bytecodes<Key = B256, Value = Bytecode>;
 
     /// Stores the current state of an [`Account`].
@@ -408,6 +409,40 @@ tables! {
 
     /// Stores generic chain state info, like the last finalized block.
     table ChainState<Key = ChainStateKey, Value = BlockNumber>;
+
+    /// Stores the journal of reorgs of the canonical chain, by sequence number.
+    table Reorgs<Key = u64, Value = StoredReorg>;
+
+    /// Stores periodic snapshots of the sync progress by unix timestamp in seconds.
+    table SyncHistory<Key = u64, Value = SyncSnapshot>;
+
+    /// Stores the root of each epoch of the pre-merge header accumulator, by epoch number.
+    table HeaderAccumulators<Key = u64, Value = B256>;
+
+    /// Stores the metadata of the transactions of the OP Mainnet legacy chain, by transaction
+    /// number.
+    table LegacyTxMeta<Key = TxNumber, Value = StoredLegacyTxMeta>;
+
+    /// Stores the L1 fees of the transactions of the OP Mainnet legacy chain, by transaction
+    /// number.
+    table LegacyL1Fees<Key = TxNumber, Value = StoredLegacyL1Fee>;
+
+    /// Stores the access lists of the canonical blocks recorded during execution, by block number.
+    table BlockAccessLists<Key = BlockNumber, Value = AccessList>;
+
+    /// Stores the accounts of the chain the node is forked from, fetched when they're first read.
+    /// Accounts that don't exist on the forked chain are stored empty.
+    table ForkAccounts<Key = Address, Value = Account>;
+
+    /// Stores the storage of the chain the node is forked from, fetched when it's first read.
+    table ForkStorages<Key = Address, Value = StorageEntry, SubKey = B256>;
+
+    /// Stores the bytecodes of the accounts of the chain the node is forked from.
+    table ForkBytecodes<Key = B256, Value = Bytecode>;
+
+    /// Stores the number of entries in [`PlainAccountState`] and [`AccountChangeSets`] that
+    /// reference each bytecode in [`Bytecodes`].
+    table BytecodeReferences<Key = B256, Value = u64>;
 }
 
 /// Keys for the `ChainState` table.
@@ -415,6 +450,13 @@ tables! {
 pub enum ChainStateKey {
     /// Last finalized block key
     LastFinalizedBlock,
+    /// Fork block of a reorg deeper than the configured maximum, canonicalization is halted while
+    /// it's set
+    ReorgHalt,
+    /// Block of the chain the node is forked from that the state in the fork tables belongs to
+    ForkBlock,
+    /// Target block of a `reth unwind` that was interrupted before all stages were unwound
+    PendingUnwind,
 }
 
 impl Encode for ChainStateKey {
@@ -423,16 +465,21 @@ impl Encode for ChainStateKey {
     fn encode(self) -> Self::Encoded {
         match self {
             Self::LastFinalizedBlock => [0],
+            Self::ReorgHalt => [1],
+            Self::ForkBlock => [2],
+            Self::PendingUnwind => [3],
         }
     }
 }
 
 impl Decode for ChainStateKey {
     fn decode<B: AsRef<[u8]>>(value: B) -> Result<Self, reth_db_api::DatabaseError> {
-        if value.as_ref() == [0] {
-            Ok(Self::LastFinalizedBlock)
-        } else {
-            Err(reth_db_api::DatabaseError::Decode)
+        match value.as_ref() {
+            [0] => Ok(Self::LastFinalizedBlock),
+            [1] => Ok(Self::ReorgHalt),
+            [2] => Ok(Self::ForkBlock),
+            [3] => Ok(Self::PendingUnwind),
+            _ => Err(reth_db_api::DatabaseError::Decode),
         }
     }
 }
@@ -1,6 +1,6 @@
 use reth_primitives::{
-    Address, BlockHash, BlockHashOrNumber, BlockNumber, GotExpected, StaticFileSegment,
-    TxHashOrNumber, TxNumber, B256, U256,
+    storage_layout::StorageLayoutError, Address, BlockHash, BlockHashOrNumber, BlockNumber,
+    GotExpected, StaticFileSegment, TxHashOrNumber, TxNumber, B256, U256,
 };
 
 #[cfg(feature = "std")]
@@ -142,6 +142,12 @@ pub enum ProviderError {
     /// Storage lock error.
     #[error(transparent)]
     StorageLockError(#[from] crate::lockfile::StorageLockError),
+    /// The state of the chain the node is forked from couldn't be fetched.
+    #[error("failed to fetch the state of the forked chain: {0}")]
+    ForkState(String),
+    /// A storage variable couldn't be decoded with a storage layout.
+    #[error(transparent)]
+    StorageLayout(#[from] StorageLayoutError),
 }
 
 impl From<reth_fs_util::FsPathError> for ProviderError {
@@ -43,6 +43,9 @@ pub use cursor::NippyJarCursor;
 mod writer;
 pub use writer::{ConsistencyFailStrategy, NippyJarWriter};
 
+#[cfg(all(target_os = "linux", feature = "io-uring"))]
+pub mod uring;
+
 const NIPPY_JAR_VERSION: usize = 1;
 
 const INDEX_FILE_EXTENSION: &str = "idx";
@@ -484,7 +487,6 @@ impl<H: NippyJarHeader> PerfectHashingFunction for NippyJar<H> {
 #[derive(Debug)]
 pub struct DataReader {
     /// Data file descriptor. Needs to be kept alive as long as `data_mmap` handle.
-    #[allow(dead_code)]
     data_file: File,
     /// Mmap handle for data.
     data_mmap: Mmap,
@@ -576,6 +578,11 @@ impl DataReader {
     pub fn size(&self) -> usize {
         self.data_mmap.len()
     }
+
+    /// Returns the data file descriptor.
+    pub const fn data_file(&self) -> &File {
+        &self.data_file
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,212 @@
+//! Batched reads of [`NippyJar`] values through `io_uring`.
+//!
+//! Reading values through the mmap handle of a [`DataReader`] page faults on every cold page, one
+//! at a time. [`UringReader`] instead reads the requested rows of several jars with a single
+//! submission, so the reads are served by the device in parallel.
+
+use crate::{DataReader, NippyJar, NippyJarError, NippyJarHeader};
+use io_uring::{opcode, types, IoUring};
+use std::{
+    ops::Range,
+    os::{fd::AsRawFd, unix::fs::FileExt},
+    sync::Mutex,
+};
+
+/// Default number of reads submitted at once.
+pub const DEFAULT_QUEUE_DEPTH: u32 = 64;
+
+/// A request to read a range of rows of a [`NippyJar`].
+#[derive(Debug)]
+pub struct RowsRequest<'a, H> {
+    /// The jar to read from.
+    pub jar: &'a NippyJar<H>,
+    /// The data reader of the jar, used to look up the value offsets.
+    pub reader: &'a DataReader,
+    /// The rows to read.
+    pub rows: Range<usize>,
+}
+
+/// Reads rows of [`NippyJar`]s through an `io_uring` instance.
+#[derive(Debug)]
+pub struct UringReader {
+    ring: Mutex<IoUring>,
+    queue_depth: u32,
+}
+
+impl UringReader {
+    /// Creates a new reader, submitting at most `queue_depth` reads at once.
+    pub fn new(queue_depth: u32) -> Result<Self, NippyJarError> {
+        Ok(Self { ring: Mutex::new(IoUring::new(queue_depth)?), queue_depth })
+    }
+
+    /// Reads the values of `column` for the rows of every request.
+    ///
+    /// Returns one list of values per request. Only jars without compression are supported, since
+    /// values are returned as they are stored.
+    pub fn read_rows<H: NippyJarHeader>(
+        &self,
+        requests: &[RowsRequest<'_, H>],
+        column: usize,
+    ) -> Result<Vec<Vec<Vec<u8>>>, NippyJarError> {
+        // The rows of a request are stored next to each other, so each request is a single read.
+        let mut reads = Vec::with_capacity(requests.len());
+        for request in requests {
+            if request.jar.compressor().is_some() {
+                return Err(NippyJarError::Custom(
+                    "io_uring reads of compressed jars are not supported".to_string(),
+                ))
+            }
+            if request.rows.is_empty() {
+                reads.push((0..0, Vec::new()));
+                continue
+            }
+
+            let mut values = Vec::with_capacity(request.rows.len());
+            for row in request.rows.clone() {
+                values.push(value_range(request.jar, request.reader, row, column)?);
+            }
+            let start = value_range(request.jar, request.reader, request.rows.start, 0)?.start;
+            let end = value_range(
+                request.jar,
+                request.reader,
+                request.rows.end - 1,
+                request.jar.columns - 1,
+            )?
+            .end;
+            reads.push((start..end, values));
+        }
+
+        let buffers = self.read_ranges(requests, &reads)?;
+
+        Ok(reads
+            .into_iter()
+            .zip(buffers)
+            .map(|((range, values), buffer)| {
+                values
+                    .into_iter()
+                    .map(|value| {
+                        buffer[value.start - range.start..value.end - range.start].to_vec()
+                    })
+                    .collect()
+            })
+            .collect())
+    }
+
+    /// Reads the byte ranges of the data files, `queue_depth` reads at a time.
+    fn read_ranges<H>(
+        &self,
+        requests: &[RowsRequest<'_, H>],
+        reads: &[(Range<usize>, Vec<Range<usize>>)],
+    ) -> Result<Vec<Vec<u8>>, NippyJarError> {
+        let mut buffers = reads.iter().map(|(range, _)| vec![0u8; range.len()]).collect::<Vec<_>>();
+        let mut ring = self.ring.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let indices =
+            (0..reads.len()).filter(|index| !reads[*index].0.is_empty()).collect::<Vec<_>>();
+        for batch in indices.chunks(self.queue_depth as usize) {
+            for &index in batch {
+                let file = requests[index].reader.data_file();
+                let buffer = &mut buffers[index];
+                let entry = opcode::Read::new(
+                    types::Fd(file.as_raw_fd()),
+                    buffer.as_mut_ptr(),
+                    buffer.len() as u32,
+                )
+                .offset(reads[index].0.start as u64)
+                .build()
+                .user_data(index as u64);
+                // SAFETY: the buffer and the file outlive the read, since all submitted reads are
+                // completed before this function returns.
+                unsafe { ring.submission().push(&entry) }
+                    .expect("batches are not larger than the submission queue");
+            }
+            ring.submit_and_wait(batch.len())?;
+
+            let completed = ring
+                .completion()
+                .map(|entry| (entry.user_data(), entry.result()))
+                .collect::<Vec<_>>();
+            for (index, result) in completed {
+                let index = index as usize;
+                if result < 0 {
+                    return Err(std::io::Error::from_raw_os_error(-result).into())
+                }
+
+                // Complete short reads synchronously.
+                let read = result as usize;
+                if read < buffers[index].len() {
+                    requests[index].reader.data_file().read_exact_at(
+                        &mut buffers[index][read..],
+                        (reads[index].0.start + read) as u64,
+                    )?;
+                }
+            }
+        }
+
+        Ok(buffers)
+    }
+}
+
+/// Returns the byte range of a value in the data file of the jar.
+fn value_range<H>(
+    jar: &NippyJar<H>,
+    reader: &DataReader,
+    row: usize,
+    column: usize,
+) -> Result<Range<usize>, NippyJarError> {
+    let offset_pos = row * jar.columns + column;
+    let start = reader.offset(offset_pos)? as usize;
+    let end = if jar.rows * jar.columns == offset_pos + 1 {
+        // It's the last column of the last row
+        reader.size()
+    } else {
+        reader.offset(offset_pos + 1)? as usize
+    };
+    Ok(start..end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_rows_of_multiple_jars() {
+        let dir = tempfile::tempdir().unwrap();
+        let columns = |jar: usize| -> Vec<Vec<Vec<u8>>> {
+            (0..2usize)
+                .map(|column| {
+                    (0..10usize)
+                        .map(|row| vec![(jar * 100 + column * 10 + row) as u8; row])
+                        .collect()
+                })
+                .collect()
+        };
+
+        let mut jars = Vec::new();
+        for index in 0..2 {
+            let path = dir.path().join(format!("jar{index}"));
+            let values = columns(index);
+            NippyJar::new_without_header(2, &path)
+                .freeze(
+                    values.iter().map(|column| column.iter().cloned().map(Ok).collect()).collect(),
+                    10,
+                )
+                .unwrap();
+            let jar = NippyJar::<()>::load_without_header(&path).unwrap();
+            let reader = jar.open_data_reader().unwrap();
+            jars.push((jar, reader));
+        }
+
+        let uring = UringReader::new(1).unwrap();
+        let requests = vec![
+            RowsRequest { jar: &jars[0].0, reader: &jars[0].1, rows: 3..10 },
+            RowsRequest { jar: &jars[1].0, reader: &jars[1].1, rows: 0..0 },
+            RowsRequest { jar: &jars[1].0, reader: &jars[1].1, rows: 0..2 },
+        ];
+        let values = uring.read_rows(&requests, 1).unwrap();
+        assert_eq!(
+            values,
+            vec![columns(0)[1][3..10].to_vec(), vec![], columns(1)[1][0..2].to_vec()]
+        );
+    }
+}
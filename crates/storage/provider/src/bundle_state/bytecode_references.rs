@@ -0,0 +1,302 @@
+use reth_db::tables;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW},
+    transaction::{DbTx, DbTxMut},
+};
+use reth_primitives::{Account, Address, BlockNumber, B256, KECCAK_EMPTY};
+use reth_storage_errors::db::DatabaseError;
+use std::collections::HashMap;
+
+/// Changes to the reference counts of contract bytecodes.
+///
+/// Bytecodes are stored once per code hash in [`tables::Bytecodes`] and referenced by the accounts
+/// in [`tables::PlainAccountState`] and [`tables::AccountChangeSets`]. The number of references is
+/// tracked in [`tables::BytecodeReferences`], and a bytecode is deleted once nothing references it
+/// anymore.
+///
+/// Changes are collected first and written at once, so that a reference moving from one entry to
+/// another never deletes the bytecode in between.
+#[derive(Debug, Default)]
+pub struct BytecodeReferenceChanges(HashMap<B256, i64>);
+
+impl BytecodeReferenceChanges {
+    /// Adds a reference to the bytecode of the account, if it has any.
+    pub fn add(&mut self, account: Option<&Account>) {
+        if let Some(hash) = bytecode_hash(account) {
+            *self.0.entry(hash).or_default() += 1;
+        }
+    }
+
+    /// Removes a reference to the bytecode of the account, if it has any.
+    pub fn remove(&mut self, account: Option<&Account>) {
+        if let Some(hash) = bytecode_hash(account) {
+            *self.0.entry(hash).or_default() -= 1;
+        }
+    }
+
+    /// Marks a newly written bytecode, so that it's deleted if nothing references it once the
+    /// changes are written.
+    pub fn touch(&mut self, hash: B256) {
+        self.0.entry(hash).or_default();
+    }
+
+    /// Returns `true` if there are no changes.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Writes the reference counts to the database, and deletes the bytecodes that are no longer
+    /// referenced.
+    ///
+    /// Returns the number of deleted bytecodes.
+    pub fn write_to_db<TX: DbTxMut + DbTx>(self, tx: &TX) -> Result<usize, DatabaseError> {
+        let mut changes = self.0.into_iter().collect::<Vec<_>>();
+        changes.sort_unstable_by_key(|(hash, _)| *hash);
+
+        let mut references_cursor = tx.cursor_write::<tables::BytecodeReferences>()?;
+        let mut bytecodes_cursor = tx.cursor_write::<tables::Bytecodes>()?;
+        let mut deleted = 0;
+        for (hash, change) in changes {
+            let references = references_cursor.seek_exact(hash)?.map_or(0, |(_, count)| count);
+            let references = references.saturating_add_signed(change);
+            if references > 0 {
+                references_cursor.upsert(hash, references)?;
+                continue
+            }
+
+            tracing::trace!(target: "provider::bundle_state", ?hash, "Deleting unreferenced bytecode");
+            if references_cursor.seek_exact(hash)?.is_some() {
+                references_cursor.delete_current()?;
+            }
+            if bytecodes_cursor.seek_exact(hash)?.is_some() {
+                bytecodes_cursor.delete_current()?;
+                deleted += 1;
+            }
+        }
+
+        Ok(deleted)
+    }
+}
+
+/// The key of the [`BytecodeReferencesCheckpoint`] in [`tables::StageCheckpointProgresses`].
+const CHECKPOINT_KEY: &str = "BytecodeReferences";
+
+/// Where an interrupted [`rebuild_bytecode_references`] continues from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BytecodeReferencesCheckpoint {
+    /// The references from [`tables::PlainAccountState`] are counted, starting at the address.
+    Accounts(Address),
+    /// The references from [`tables::AccountChangeSets`] are counted, starting at the block.
+    ChangeSets(BlockNumber),
+}
+
+impl BytecodeReferencesCheckpoint {
+    fn encode(self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(21);
+        match self {
+            Self::Accounts(address) => {
+                buf.push(0);
+                buf.extend_from_slice(address.as_slice());
+            }
+            Self::ChangeSets(block) => {
+                buf.push(1);
+                buf.extend_from_slice(&block.to_be_bytes());
+            }
+        }
+        buf
+    }
+
+    fn decode(buf: &[u8]) -> Result<Self, DatabaseError> {
+        match buf.split_first() {
+            Some((0, address)) if address.len() == 20 => {
+                Ok(Self::Accounts(Address::from_slice(address)))
+            }
+            Some((1, block)) => Ok(Self::ChangeSets(BlockNumber::from_be_bytes(
+                block.try_into().map_err(|_| DatabaseError::Decode)?,
+            ))),
+            _ => Err(DatabaseError::Decode),
+        }
+    }
+}
+
+/// Rebuilds [`tables::BytecodeReferences`] from the accounts in [`tables::PlainAccountState`] and
+/// [`tables::AccountChangeSets`], replacing the existing reference counts.
+///
+/// Databases created before bytecodes were reference counted have no references, and must be
+/// backfilled before any bytecode is deleted.
+///
+/// The tables are walked in batches of about `batch_size` entries, so that the transaction can be
+/// committed in between. Every call counts one batch and saves a checkpoint to continue from,
+/// which is returned. Returns `None` once all references are counted and the checkpoint is
+/// removed.
+pub fn rebuild_bytecode_references<TX: DbTxMut + DbTx>(
+    tx: &TX,
+    batch_size: usize,
+) -> Result<Option<BytecodeReferencesCheckpoint>, DatabaseError> {
+    let checkpoint = tx.get::<tables::StageCheckpointProgresses>(CHECKPOINT_KEY.to_string())?;
+    let checkpoint = match checkpoint {
+        Some(buf) => BytecodeReferencesCheckpoint::decode(&buf)?,
+        None => {
+            // a new rebuild replaces the existing counts
+            tx.clear::<tables::BytecodeReferences>()?;
+            BytecodeReferencesCheckpoint::Accounts(Address::ZERO)
+        }
+    };
+
+    let mut references = HashMap::<B256, u64>::new();
+    let mut processed = 0;
+    let next = match checkpoint {
+        BytecodeReferencesCheckpoint::Accounts(start) => {
+            let mut accounts_cursor = tx.cursor_read::<tables::PlainAccountState>()?;
+            let mut walker = accounts_cursor.walk(Some(start))?;
+            let mut next = Some(BytecodeReferencesCheckpoint::ChangeSets(0));
+            while let Some((address, account)) = walker.next().transpose()? {
+                if processed == batch_size {
+                    next = Some(BytecodeReferencesCheckpoint::Accounts(address));
+                    break
+                }
+                if let Some(hash) = bytecode_hash(Some(&account)) {
+                    *references.entry(hash).or_default() += 1;
+                }
+                processed += 1;
+            }
+            next
+        }
+        BytecodeReferencesCheckpoint::ChangeSets(start) => {
+            let mut changesets_cursor = tx.cursor_read::<tables::AccountChangeSets>()?;
+            let mut walker = changesets_cursor.walk(Some(start))?;
+            let mut next = None;
+            let mut current = start;
+            while let Some((block, account_before)) = walker.next().transpose()? {
+                // batches end between blocks, the changes of a block are counted at once
+                if processed >= batch_size && block != current {
+                    next = Some(BytecodeReferencesCheckpoint::ChangeSets(block));
+                    break
+                }
+                if let Some(hash) = bytecode_hash(account_before.info.as_ref()) {
+                    *references.entry(hash).or_default() += 1;
+                }
+                current = block;
+                processed += 1;
+            }
+            next
+        }
+    };
+
+    // add the counts of this batch to the ones of the previous batches
+    let mut references = references.into_iter().collect::<Vec<_>>();
+    references.sort_unstable_by_key(|(hash, _)| *hash);
+    let mut references_cursor = tx.cursor_write::<tables::BytecodeReferences>()?;
+    for (hash, count) in references {
+        let counted = references_cursor.seek_exact(hash)?.map_or(0, |(_, count)| count);
+        references_cursor.upsert(hash, counted + count)?;
+    }
+
+    match next {
+        Some(checkpoint) => {
+            tx.put::<tables::StageCheckpointProgresses>(
+                CHECKPOINT_KEY.to_string(),
+                checkpoint.encode(),
+            )?;
+        }
+        None => {
+            tx.delete::<tables::StageCheckpointProgresses>(CHECKPOINT_KEY.to_string(), None)?;
+        }
+    }
+
+    Ok(next)
+}
+
+/// Returns the hash of the bytecode of the account, if it has any.
+fn bytecode_hash(account: Option<&Account>) -> Option<B256> {
+    account?.bytecode_hash.filter(|hash| *hash != KECCAK_EMPTY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_provider_factory;
+    use reth_db_api::models::AccountBeforeTx;
+    use reth_primitives::{Address, Bytecode};
+
+    #[test]
+    fn delete_unreferenced_bytecode() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        let tx = provider.tx_ref();
+
+        let hash = B256::with_last_byte(1);
+        let account = Account { bytecode_hash: Some(hash), ..Default::default() };
+        tx.put::<tables::Bytecodes>(hash, Bytecode::new_raw(vec![0x00].into())).unwrap();
+
+        // two accounts reference the bytecode
+        let mut changes = BytecodeReferenceChanges::default();
+        changes.add(Some(&account));
+        changes.add(Some(&account));
+        changes.add(Some(&Account::default()));
+        assert_eq!(changes.write_to_db(tx), Ok(0));
+        assert_eq!(tx.get::<tables::BytecodeReferences>(hash), Ok(Some(2)));
+
+        // the reference moves to another entry, the bytecode is kept
+        let mut changes = BytecodeReferenceChanges::default();
+        changes.remove(Some(&account));
+        changes.add(Some(&account));
+        assert_eq!(changes.write_to_db(tx), Ok(0));
+        assert_eq!(tx.get::<tables::BytecodeReferences>(hash), Ok(Some(2)));
+
+        // the last references are removed
+        let mut changes = BytecodeReferenceChanges::default();
+        changes.remove(Some(&account));
+        changes.remove(Some(&account));
+        assert_eq!(changes.write_to_db(tx), Ok(1));
+        assert_eq!(tx.get::<tables::BytecodeReferences>(hash), Ok(None));
+        assert_eq!(tx.get::<tables::Bytecodes>(hash), Ok(None));
+    }
+
+    #[test]
+    fn rebuild_references() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        let tx = provider.tx_ref();
+
+        let (first, second) = (B256::with_last_byte(1), B256::with_last_byte(2));
+        let account = |hash| Account { bytecode_hash: Some(hash), ..Default::default() };
+        tx.put::<tables::PlainAccountState>(Address::with_last_byte(1), account(first)).unwrap();
+        tx.put::<tables::PlainAccountState>(Address::with_last_byte(2), account(first)).unwrap();
+        tx.put::<tables::PlainAccountState>(Address::with_last_byte(3), Account::default())
+            .unwrap();
+        tx.put::<tables::AccountChangeSets>(
+            1,
+            AccountBeforeTx { address: Address::with_last_byte(1), info: Some(account(second)) },
+        )
+        .unwrap();
+        tx.put::<tables::AccountChangeSets>(
+            2,
+            AccountBeforeTx { address: Address::with_last_byte(1), info: None },
+        )
+        .unwrap();
+        // stale counts are replaced
+        tx.put::<tables::BytecodeReferences>(B256::with_last_byte(3), 1).unwrap();
+
+        // one account or block per batch
+        let checkpoints =
+            std::iter::from_fn(|| rebuild_bytecode_references(tx, 1).unwrap()).collect::<Vec<_>>();
+        assert_eq!(
+            checkpoints,
+            vec![
+                BytecodeReferencesCheckpoint::Accounts(Address::with_last_byte(2)),
+                BytecodeReferencesCheckpoint::Accounts(Address::with_last_byte(3)),
+                BytecodeReferencesCheckpoint::ChangeSets(0),
+                BytecodeReferencesCheckpoint::ChangeSets(2),
+            ]
+        );
+        assert_eq!(
+            tx.get::<tables::StageCheckpointProgresses>(CHECKPOINT_KEY.to_string()),
+            Ok(None)
+        );
+        assert_eq!(tx.entries::<tables::BytecodeReferences>(), Ok(2));
+        assert_eq!(tx.get::<tables::BytecodeReferences>(first), Ok(Some(2)));
+        assert_eq!(tx.get::<tables::BytecodeReferences>(second), Ok(Some(1)));
+        assert_eq!(tx.get::<tables::BytecodeReferences>(B256::with_last_byte(3)), Ok(None));
+    }
+}
@@ -1,6 +1,7 @@
 //! Bundle state module.
 //! This module contains all the logic related to bundle state.
 mod bundle_state_with_receipts;
+mod bytecode_references;
 mod hashed_state_changes;
 mod state_changes;
 mod state_reverts;
@@ -8,6 +9,9 @@ mod state_reverts;
 pub use bundle_state_with_receipts::{
     AccountRevertInit, BundleStateInit, OriginalValuesKnown, RevertsInit,
 };
+pub use bytecode_references::{
+    rebuild_bytecode_references, BytecodeReferenceChanges, BytecodeReferencesCheckpoint,
+};
 pub use hashed_state_changes::HashedStateChanges;
 pub use state_changes::StateChanges;
 pub use state_reverts::{StateReverts, StorageRevertsIter};
@@ -1,3 +1,4 @@
+use super::BytecodeReferenceChanges;
 use rayon::slice::ParallelSliceMut;
 use reth_db::tables;
 use reth_db_api::{
@@ -30,12 +31,17 @@ impl StateChanges {
         // Write new account state
         tracing::trace!(target: "provider::bundle_state", len = self.0.accounts.len(), "Writing new account state");
         let mut accounts_cursor = tx.cursor_write::<tables::PlainAccountState>()?;
+        let mut bytecode_references = BytecodeReferenceChanges::default();
         // write account to database.
         for (address, account) in self.0.accounts {
+            let existing = accounts_cursor.seek_exact(address)?.map(|(_, account)| account);
+            bytecode_references.remove(existing.as_ref());
             if let Some(account) = account {
                 tracing::trace!(target: "provider::bundle_state", ?address, "Updating plain state account");
-                accounts_cursor.upsert(address, account.into())?;
-            } else if accounts_cursor.seek_exact(address)?.is_some() {
+                let account = account.into();
+                bytecode_references.add(Some(&account));
+                accounts_cursor.upsert(address, account)?;
+            } else if existing.is_some() {
                 tracing::trace!(target: "provider::bundle_state", ?address, "Deleting plain state account");
                 accounts_cursor.delete_current()?;
             }
@@ -45,8 +51,14 @@ impl StateChanges {
         tracing::trace!(target: "provider::bundle_state", len = self.0.contracts.len(), "Writing bytecodes");
         let mut bytecodes_cursor = tx.cursor_write::<tables::Bytecodes>()?;
         for (hash, bytecode) in self.0.contracts {
+            bytecode_references.touch(hash);
             bytecodes_cursor.upsert(hash, Bytecode(bytecode))?;
         }
+        drop(bytecodes_cursor);
+
+        // Update the bytecode reference counts, deleting the bytecodes that are no longer
+        // referenced.
+        bytecode_references.write_to_db(tx)?;
 
         // Write new storage state and wipe storage if needed.
         tracing::trace!(target: "provider::bundle_state", len = self.0.storage.len(), "Writing new storage state");
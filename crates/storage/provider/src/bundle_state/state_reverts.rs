@@ -1,3 +1,4 @@
+use super::BytecodeReferenceChanges;
 use rayon::slice::ParallelSliceMut;
 use reth_db::tables;
 use reth_db_api::{
@@ -73,6 +74,7 @@ impl StateReverts {
         // Write account changes
         tracing::trace!(target: "provider::reverts", "Writing account changes");
         let mut account_changeset_cursor = tx.cursor_dup_write::<tables::AccountChangeSets>()?;
+        let mut bytecode_references = BytecodeReferenceChanges::default();
 
         for (block_index, mut account_block_reverts) in self.0.accounts.into_iter().enumerate() {
             let block_number = first_block + block_index as BlockNumber;
@@ -80,13 +82,16 @@ impl StateReverts {
             account_block_reverts.par_sort_by_key(|a| a.0);
 
             for (address, info) in account_block_reverts {
-                account_changeset_cursor.append_dup(
-                    block_number,
-                    AccountBeforeTx { address, info: info.map(Into::into) },
-                )?;
+                let info = info.map(Into::into);
+                bytecode_references.add(info.as_ref());
+                account_changeset_cursor
+                    .append_dup(block_number, AccountBeforeTx { address, info })?;
             }
         }
 
+        // Account changesets reference the bytecodes of the accounts they store.
+        bytecode_references.write_to_db(tx)?;
+
         Ok(())
     }
 }
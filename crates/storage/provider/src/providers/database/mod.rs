@@ -1,29 +1,39 @@
 use crate::{
-    providers::{state::latest::LatestStateProvider, StaticFileProvider},
+    providers::{
+        state::{fork::ForkStateProvider, latest::LatestStateProvider},
+        ForkState, ForkStateSource, StaticFileProvider,
+    },
     to_range,
     traits::{BlockSource, ReceiptProvider},
-    BlockHashReader, BlockNumReader, BlockReader, ChainSpecProvider, DatabaseProviderFactory,
-    EvmEnvProvider, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider, ProviderError,
-    PruneCheckpointReader, RequestsProvider, StageCheckpointReader, StateProviderBox,
-    StaticFileProviderFactory, TransactionVariant, TransactionsProvider, WithdrawalsProvider,
+    BlockAccessListReader, BlockHashReader, BlockNumReader, BlockReader, ChainSpecProvider,
+    DatabaseProviderFactory, EvmEnvProvider, HeaderAccumulatorReader, HeaderProvider,
+    HeaderSyncGap, HeaderSyncGapProvider, LegacyTxMetaReader, ProviderError, PruneCheckpointReader,
+    ReorgJournalReader, RequestsProvider, StageCheckpointReader, StateProviderBox,
+    StaticFileProviderFactory, SyncHistoryReader, TransactionVariant, TransactionsProvider,
+    WithdrawalsProvider,
 };
 use reth_chainspec::{ChainInfo, ChainSpec};
 use reth_db::{init_db, mdbx::DatabaseArguments, DatabaseEnv};
-use reth_db_api::{database::Database, models::StoredBlockBodyIndices};
+use reth_db_api::{
+    database::Database,
+    models::{
+        StoredBlockBodyIndices, StoredLegacyL1Fee, StoredLegacyTxMeta, StoredReorg, SyncSnapshot,
+    },
+};
 use reth_errors::{RethError, RethResult};
 use reth_evm::ConfigureEvmEnv;
 use reth_primitives::{
-    Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders, Header, Receipt,
-    SealedBlock, SealedBlockWithSenders, SealedHeader, StaticFileSegment, TransactionMeta,
-    TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256,
-    U256,
+    AccessList, Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders,
+    Header, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader, StaticFileSegment,
+    TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal,
+    Withdrawals, B256, U256,
 };
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_errors::provider::ProviderResult;
 use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg};
 use std::{
-    ops::{RangeBounds, RangeInclusive},
+    ops::{Range, RangeBounds, RangeInclusive},
     path::Path,
     sync::Arc,
 };
@@ -46,6 +56,19 @@ pub struct ProviderFactory<DB> {
     chain_spec: Arc<ChainSpec>,
     /// Static File Provider
     static_file_provider: StaticFileProvider,
+    /// State of the chain the node is forked from, read if it's missing locally
+    fork_state: Option<ForkState>,
+    /// The block the chain is served up to, if it's pinned to a historical block
+    pinned: Option<PinnedBlock>,
+}
+
+/// A historical block a [`ProviderFactory`] serves the chain up to, as if it was the tip.
+#[derive(Debug, Clone, Copy)]
+struct PinnedBlock {
+    number: BlockNumber,
+    hash: B256,
+    /// The number of the first transaction after the block.
+    next_tx_num: TxNumber,
 }
 
 impl<DB> ProviderFactory<DB> {
@@ -55,7 +78,7 @@ impl<DB> ProviderFactory<DB> {
         chain_spec: Arc<ChainSpec>,
         static_file_provider: StaticFileProvider,
     ) -> Self {
-        Self { db: Arc::new(db), chain_spec, static_file_provider }
+        Self { db: Arc::new(db), chain_spec, static_file_provider, fork_state: None, pinned: None }
     }
 
     /// Enables metrics on the static file provider.
@@ -64,6 +87,22 @@ impl<DB> ProviderFactory<DB> {
         self
     }
 
+    /// Returns the state of the chain the node is forked from, if it's forked.
+    pub const fn fork_state(&self) -> Option<&ForkState> {
+        self.fork_state.as_ref()
+    }
+
+    /// Wraps the state provider so it reads the state of the chain the node is forked from, if
+    /// the state is missing locally.
+    ///
+    /// Returns the state provider unchanged if the node isn't forked.
+    pub fn with_fork_overlay(&self, state_provider: StateProviderBox) -> StateProviderBox {
+        match &self.fork_state {
+            Some(fork) => Box::new(ForkStateProvider::new(state_provider, fork.clone())),
+            None => state_provider,
+        }
+    }
+
     /// Returns reference to the underlying database.
     pub fn db_ref(&self) -> &DB {
         &self.db
@@ -89,11 +128,124 @@ impl ProviderFactory<DatabaseEnv> {
             db: Arc::new(init_db(path, args).map_err(RethError::msg)?),
             chain_spec,
             static_file_provider,
+            fork_state: None,
+            pinned: None,
         })
     }
 }
 
 impl<DB: Database> ProviderFactory<DB> {
+    /// Reads the state of the chain the node is forked from, if it's missing locally.
+    ///
+    /// The state is fetched from the source when it's first read and cached in the database.
+    pub fn with_fork_state(mut self, source: Arc<dyn ForkStateSource>) -> Self
+    where
+        DB: 'static,
+    {
+        self.fork_state = Some(ForkState::new(self.db.clone(), source));
+        self
+    }
+
+    /// Serves the chain as if the given block was its tip.
+    ///
+    /// Blocks, transactions and receipts after the block aren't returned, and the latest state is
+    /// the state after the block, reconstructed from the history indices. Fails if the block isn't
+    /// stored or its state history is pruned.
+    pub fn pinned_at(mut self, number: BlockNumber) -> ProviderResult<Self> {
+        let provider = self.provider()?;
+        if number > provider.best_block_number()? {
+            return Err(ProviderError::HeaderNotFound(number.into()))
+        }
+        for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+            if provider
+                .get_prune_checkpoint(segment)?
+                .and_then(|checkpoint| checkpoint.block_number)
+                .is_some_and(|pruned| pruned >= number)
+            {
+                return Err(ProviderError::StateAtBlockPruned(number))
+            }
+        }
+        let hash =
+            provider.block_hash(number)?.ok_or(ProviderError::HeaderNotFound(number.into()))?;
+        let next_tx_num = provider
+            .block_body_indices(number)?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(number))?
+            .next_tx_num();
+        drop(provider);
+
+        self.pinned = Some(PinnedBlock { number, hash, next_tx_num });
+        Ok(self)
+    }
+
+    /// Returns the block the chain is served up to, if it's pinned to a historical block.
+    pub fn pinned_block(&self) -> Option<BlockNumber> {
+        self.pinned.map(|pinned| pinned.number)
+    }
+
+    /// Returns `true` if the block is served, i.e. it's not after the pinned block.
+    fn serves_block(&self, number: BlockNumber) -> bool {
+        self.pinned.map_or(true, |pinned| number <= pinned.number)
+    }
+
+    /// Returns `true` if the block is served. Unknown block hashes are considered served.
+    fn serves_block_id(&self, id: BlockHashOrNumber) -> ProviderResult<bool> {
+        if self.pinned.is_none() {
+            return Ok(true)
+        }
+        let number = match id {
+            BlockHashOrNumber::Hash(hash) => self.provider()?.block_number(hash)?,
+            BlockHashOrNumber::Number(number) => Some(number),
+        };
+        Ok(number.map_or(true, |number| self.serves_block(number)))
+    }
+
+    /// Returns `true` if the transaction is served, i.e. it's not after the pinned block.
+    fn serves_tx(&self, id: TxNumber) -> bool {
+        self.pinned.map_or(true, |pinned| id < pinned.next_tx_num)
+    }
+
+    /// Limits the range of blocks to the served blocks.
+    fn served_blocks(&self, range: impl RangeBounds<BlockNumber>) -> Range<BlockNumber> {
+        let range = to_range(range);
+        match self.pinned {
+            Some(pinned) => {
+                let end = range.end.min(pinned.number + 1);
+                range.start.min(end)..end
+            }
+            None => range,
+        }
+    }
+
+    /// Limits the inclusive range of blocks to the served blocks.
+    fn served_blocks_inclusive(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Option<RangeInclusive<BlockNumber>> {
+        let range = self.served_blocks(range);
+        (!range.is_empty()).then(|| range.start..=range.end - 1)
+    }
+
+    /// Limits the range of transactions to the served transactions.
+    fn served_txs(&self, range: impl RangeBounds<TxNumber>) -> Range<TxNumber> {
+        let range = to_range(range);
+        match self.pinned {
+            Some(pinned) => {
+                let end = range.end.min(pinned.next_tx_num);
+                range.start.min(end)..end
+            }
+            None => range,
+        }
+    }
+
+    /// Returns an error if the block isn't served.
+    fn ensure_served(&self, id: BlockHashOrNumber) -> ProviderResult<()> {
+        if self.serves_block_id(id)? {
+            Ok(())
+        } else {
+            Err(ProviderError::HeaderNotFound(id))
+        }
+    }
+
     /// Returns a provider with a created `DbTx` inside, which allows fetching data from the
     /// database using different types of providers. Example: [`HeaderProvider`]
     /// [`BlockHashReader`]. This may fail if the inner read database transaction fails to open.
@@ -120,10 +272,17 @@ impl<DB: Database> ProviderFactory<DB> {
     }
 
     /// State provider for latest block
+    ///
+    /// This is the state after the pinned block, if the chain is pinned to a historical block.
     #[track_caller]
     pub fn latest(&self) -> ProviderResult<StateProviderBox> {
+        if let Some(pinned) = self.pinned {
+            return self.history_by_block_number(pinned.number)
+        }
         trace!(target: "providers::db", "Returning latest state provider");
-        Ok(Box::new(LatestStateProvider::new(self.db.tx()?, self.static_file_provider())))
+        let state_provider =
+            Box::new(LatestStateProvider::new(self.db.tx()?, self.static_file_provider()));
+        Ok(self.with_fork_overlay(state_provider))
     }
 
     /// Storage provider for state at that given block
@@ -131,9 +290,10 @@ impl<DB: Database> ProviderFactory<DB> {
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<StateProviderBox> {
+        self.ensure_served(block_number.into())?;
         let state_provider = self.provider()?.state_provider_by_block_number(block_number)?;
         trace!(target: "providers::db", ?block_number, "Returning historical state provider for block number");
-        Ok(state_provider)
+        Ok(self.with_fork_overlay(state_provider))
     }
 
     /// Storage provider for state at that given block hash
@@ -143,10 +303,11 @@ impl<DB: Database> ProviderFactory<DB> {
         let block_number = provider
             .block_number(block_hash)?
             .ok_or(ProviderError::BlockHashNotFound(block_hash))?;
+        self.ensure_served(block_number.into())?;
 
         let state_provider = self.provider()?.state_provider_by_block_number(block_number)?;
         trace!(target: "providers::db", ?block_number, %block_hash, "Returning historical state provider for block hash");
-        Ok(state_provider)
+        Ok(self.with_fork_overlay(state_provider))
     }
 }
 
@@ -175,10 +336,13 @@ impl<DB: Database> HeaderSyncGapProvider for ProviderFactory<DB> {
 
 impl<DB: Database> HeaderProvider for ProviderFactory<DB> {
     fn header(&self, block_hash: &BlockHash) -> ProviderResult<Option<Header>> {
-        self.provider()?.header(block_hash)
+        Ok(self.provider()?.header(block_hash)?.filter(|header| self.serves_block(header.number)))
     }
 
     fn header_by_number(&self, num: BlockNumber) -> ProviderResult<Option<Header>> {
+        if !self.serves_block(num) {
+            return Ok(None)
+        }
         self.static_file_provider.get_with_static_file_or_database(
             StaticFileSegment::Headers,
             num,
@@ -188,10 +352,16 @@ impl<DB: Database> HeaderProvider for ProviderFactory<DB> {
     }
 
     fn header_td(&self, hash: &BlockHash) -> ProviderResult<Option<U256>> {
+        if !self.serves_block_id((*hash).into())? {
+            return Ok(None)
+        }
         self.provider()?.header_td(hash)
     }
 
     fn header_td_by_number(&self, number: BlockNumber) -> ProviderResult<Option<U256>> {
+        if !self.serves_block(number) {
+            return Ok(None)
+        }
         if let Some(td) = self.chain_spec.final_paris_total_difficulty(number) {
             // if this block is higher than the final paris(merge) block, return the final paris
             // difficulty
@@ -209,7 +379,7 @@ impl<DB: Database> HeaderProvider for ProviderFactory<DB> {
     fn headers_range(&self, range: impl RangeBounds<BlockNumber>) -> ProviderResult<Vec<Header>> {
         self.static_file_provider.get_range_with_static_file_or_database(
             StaticFileSegment::Headers,
-            to_range(range),
+            self.served_blocks(range),
             |static_file, range, _| static_file.headers_range(range),
             |range, _| self.provider()?.headers_range(range),
             |_| true,
@@ -217,6 +387,9 @@ impl<DB: Database> HeaderProvider for ProviderFactory<DB> {
     }
 
     fn sealed_header(&self, number: BlockNumber) -> ProviderResult<Option<SealedHeader>> {
+        if !self.serves_block(number) {
+            return Ok(None)
+        }
         self.static_file_provider.get_with_static_file_or_database(
             StaticFileSegment::Headers,
             number,
@@ -239,7 +412,7 @@ impl<DB: Database> HeaderProvider for ProviderFactory<DB> {
     ) -> ProviderResult<Vec<SealedHeader>> {
         self.static_file_provider.get_range_with_static_file_or_database(
             StaticFileSegment::Headers,
-            to_range(range),
+            self.served_blocks(range),
             |static_file, range, predicate| static_file.sealed_headers_while(range, predicate),
             |range, predicate| self.provider()?.sealed_headers_while(range, predicate),
             predicate,
@@ -249,6 +422,9 @@ impl<DB: Database> HeaderProvider for ProviderFactory<DB> {
 
 impl<DB: Database> BlockHashReader for ProviderFactory<DB> {
     fn block_hash(&self, number: u64) -> ProviderResult<Option<B256>> {
+        if !self.serves_block(number) {
+            return Ok(None)
+        }
         self.static_file_provider.get_with_static_file_or_database(
             StaticFileSegment::Headers,
             number,
@@ -264,7 +440,7 @@ impl<DB: Database> BlockHashReader for ProviderFactory<DB> {
     ) -> ProviderResult<Vec<B256>> {
         self.static_file_provider.get_range_with_static_file_or_database(
             StaticFileSegment::Headers,
-            start..end,
+            self.served_blocks(start..end),
             |static_file, range, _| static_file.canonical_hashes_range(range.start, range.end),
             |range, _| self.provider()?.canonical_hashes_range(range.start, range.end),
             |_| true,
@@ -274,29 +450,41 @@ impl<DB: Database> BlockHashReader for ProviderFactory<DB> {
 
 impl<DB: Database> BlockNumReader for ProviderFactory<DB> {
     fn chain_info(&self) -> ProviderResult<ChainInfo> {
+        if let Some(pinned) = self.pinned {
+            return Ok(ChainInfo { best_hash: pinned.hash, best_number: pinned.number })
+        }
         self.provider()?.chain_info()
     }
 
     fn best_block_number(&self) -> ProviderResult<BlockNumber> {
+        if let Some(pinned) = self.pinned {
+            return Ok(pinned.number)
+        }
         self.provider()?.best_block_number()
     }
 
     fn last_block_number(&self) -> ProviderResult<BlockNumber> {
+        if let Some(pinned) = self.pinned {
+            return Ok(pinned.number)
+        }
         self.provider()?.last_block_number()
     }
 
     fn block_number(&self, hash: B256) -> ProviderResult<Option<BlockNumber>> {
-        self.provider()?.block_number(hash)
+        Ok(self.provider()?.block_number(hash)?.filter(|number| self.serves_block(*number)))
     }
 }
 
 impl<DB: Database> BlockReader for ProviderFactory<DB> {
     fn find_block_by_hash(&self, hash: B256, source: BlockSource) -> ProviderResult<Option<Block>> {
-        self.provider()?.find_block_by_hash(hash, source)
+        Ok(self
+            .provider()?
+            .find_block_by_hash(hash, source)?
+            .filter(|block| self.serves_block(block.number)))
     }
 
     fn block(&self, id: BlockHashOrNumber) -> ProviderResult<Option<Block>> {
-        self.provider()?.block(id)
+        Ok(self.provider()?.block(id)?.filter(|block| self.serves_block(block.number)))
     }
 
     fn pending_block(&self) -> ProviderResult<Option<SealedBlock>> {
@@ -312,6 +500,9 @@ impl<DB: Database> BlockReader for ProviderFactory<DB> {
     }
 
     fn ommers(&self, id: BlockHashOrNumber) -> ProviderResult<Option<Vec<Header>>> {
+        if !self.serves_block_id(id)? {
+            return Ok(None)
+        }
         self.provider()?.ommers(id)
     }
 
@@ -319,6 +510,9 @@ impl<DB: Database> BlockReader for ProviderFactory<DB> {
         &self,
         number: BlockNumber,
     ) -> ProviderResult<Option<StoredBlockBodyIndices>> {
+        if !self.serves_block(number) {
+            return Ok(None)
+        }
         self.provider()?.block_body_indices(number)
     }
 
@@ -327,10 +521,14 @@ impl<DB: Database> BlockReader for ProviderFactory<DB> {
         id: BlockHashOrNumber,
         transaction_kind: TransactionVariant,
     ) -> ProviderResult<Option<BlockWithSenders>> {
-        self.provider()?.block_with_senders(id, transaction_kind)
+        Ok(self
+            .provider()?
+            .block_with_senders(id, transaction_kind)?
+            .filter(|block| self.serves_block(block.number)))
     }
 
     fn block_range(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<Vec<Block>> {
+        let Some(range) = self.served_blocks_inclusive(range) else { return Ok(Vec::new()) };
         self.provider()?.block_range(range)
     }
 
@@ -338,6 +536,7 @@ impl<DB: Database> BlockReader for ProviderFactory<DB> {
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<Vec<BlockWithSenders>> {
+        let Some(range) = self.served_blocks_inclusive(range) else { return Ok(Vec::new()) };
         self.provider()?.block_with_senders_range(range)
     }
 
@@ -345,16 +544,20 @@ impl<DB: Database> BlockReader for ProviderFactory<DB> {
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<Vec<SealedBlockWithSenders>> {
+        let Some(range) = self.served_blocks_inclusive(range) else { return Ok(Vec::new()) };
         self.provider()?.sealed_block_with_senders_range(range)
     }
 }
 
 impl<DB: Database> TransactionsProvider for ProviderFactory<DB> {
     fn transaction_id(&self, tx_hash: TxHash) -> ProviderResult<Option<TxNumber>> {
-        self.provider()?.transaction_id(tx_hash)
+        Ok(self.provider()?.transaction_id(tx_hash)?.filter(|id| self.serves_tx(*id)))
     }
 
     fn transaction_by_id(&self, id: TxNumber) -> ProviderResult<Option<TransactionSigned>> {
+        if !self.serves_tx(id) {
+            return Ok(None)
+        }
         self.static_file_provider.get_with_static_file_or_database(
             StaticFileSegment::Transactions,
             id,
@@ -367,6 +570,9 @@ impl<DB: Database> TransactionsProvider for ProviderFactory<DB> {
         &self,
         id: TxNumber,
     ) -> ProviderResult<Option<TransactionSignedNoHash>> {
+        if !self.serves_tx(id) {
+            return Ok(None)
+        }
         self.static_file_provider.get_with_static_file_or_database(
             StaticFileSegment::Transactions,
             id,
@@ -376,6 +582,9 @@ impl<DB: Database> TransactionsProvider for ProviderFactory<DB> {
     }
 
     fn transaction_by_hash(&self, hash: TxHash) -> ProviderResult<Option<TransactionSigned>> {
+        if self.pinned.is_some() && self.transaction_id(hash)?.is_none() {
+            return Ok(None)
+        }
         self.provider()?.transaction_by_hash(hash)
     }
 
@@ -383,10 +592,16 @@ impl<DB: Database> TransactionsProvider for ProviderFactory<DB> {
         &self,
         tx_hash: TxHash,
     ) -> ProviderResult<Option<(TransactionSigned, TransactionMeta)>> {
-        self.provider()?.transaction_by_hash_with_meta(tx_hash)
+        Ok(self
+            .provider()?
+            .transaction_by_hash_with_meta(tx_hash)?
+            .filter(|(_, meta)| self.serves_block(meta.block_number)))
     }
 
     fn transaction_block(&self, id: TxNumber) -> ProviderResult<Option<BlockNumber>> {
+        if !self.serves_tx(id) {
+            return Ok(None)
+        }
         self.provider()?.transaction_block(id)
     }
 
@@ -394,6 +609,9 @@ impl<DB: Database> TransactionsProvider for ProviderFactory<DB> {
         &self,
         id: BlockHashOrNumber,
     ) -> ProviderResult<Option<Vec<TransactionSigned>>> {
+        if !self.serves_block_id(id)? {
+            return Ok(None)
+        }
         self.provider()?.transactions_by_block(id)
     }
 
@@ -401,30 +619,36 @@ impl<DB: Database> TransactionsProvider for ProviderFactory<DB> {
         &self,
         range: impl RangeBounds<BlockNumber>,
     ) -> ProviderResult<Vec<Vec<TransactionSigned>>> {
-        self.provider()?.transactions_by_block_range(range)
+        self.provider()?.transactions_by_block_range(self.served_blocks(range))
     }
 
     fn transactions_by_tx_range(
         &self,
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<TransactionSignedNoHash>> {
-        self.provider()?.transactions_by_tx_range(range)
+        self.provider()?.transactions_by_tx_range(self.served_txs(range))
     }
 
     fn senders_by_tx_range(
         &self,
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<Address>> {
-        self.provider()?.senders_by_tx_range(range)
+        self.provider()?.senders_by_tx_range(self.served_txs(range))
     }
 
     fn transaction_sender(&self, id: TxNumber) -> ProviderResult<Option<Address>> {
+        if !self.serves_tx(id) {
+            return Ok(None)
+        }
         self.provider()?.transaction_sender(id)
     }
 }
 
 impl<DB: Database> ReceiptProvider for ProviderFactory<DB> {
     fn receipt(&self, id: TxNumber) -> ProviderResult<Option<Receipt>> {
+        if !self.serves_tx(id) {
+            return Ok(None)
+        }
         self.static_file_provider.get_with_static_file_or_database(
             StaticFileSegment::Receipts,
             id,
@@ -434,10 +658,16 @@ impl<DB: Database> ReceiptProvider for ProviderFactory<DB> {
     }
 
     fn receipt_by_hash(&self, hash: TxHash) -> ProviderResult<Option<Receipt>> {
+        if self.pinned.is_some() && self.transaction_id(hash)?.is_none() {
+            return Ok(None)
+        }
         self.provider()?.receipt_by_hash(hash)
     }
 
     fn receipts_by_block(&self, block: BlockHashOrNumber) -> ProviderResult<Option<Vec<Receipt>>> {
+        if !self.serves_block_id(block)? {
+            return Ok(None)
+        }
         self.provider()?.receipts_by_block(block)
     }
 
@@ -447,12 +677,19 @@ impl<DB: Database> ReceiptProvider for ProviderFactory<DB> {
     ) -> ProviderResult<Vec<Receipt>> {
         self.static_file_provider.get_range_with_static_file_or_database(
             StaticFileSegment::Receipts,
-            to_range(range),
+            self.served_txs(range),
             |static_file, range, _| static_file.receipts_by_tx_range(range),
             |range, _| self.provider()?.receipts_by_tx_range(range),
             |_| true,
         )
     }
+
+    fn legacy_l1_fee(&self, id: TxNumber) -> ProviderResult<Option<StoredLegacyL1Fee>> {
+        if !self.serves_tx(id) {
+            return Ok(None)
+        }
+        self.provider()?.legacy_l1_fee(id)
+    }
 }
 
 impl<DB: Database> WithdrawalsProvider for ProviderFactory<DB> {
@@ -461,6 +698,9 @@ impl<DB: Database> WithdrawalsProvider for ProviderFactory<DB> {
         id: BlockHashOrNumber,
         timestamp: u64,
     ) -> ProviderResult<Option<Withdrawals>> {
+        if !self.serves_block_id(id)? {
+            return Ok(None)
+        }
         self.provider()?.withdrawals_by_block(id, timestamp)
     }
 
@@ -478,6 +718,9 @@ where
         id: BlockHashOrNumber,
         timestamp: u64,
     ) -> ProviderResult<Option<reth_primitives::Requests>> {
+        if !self.serves_block_id(id)? {
+            return Ok(None)
+        }
         self.provider()?.requests_by_block(id, timestamp)
     }
 }
@@ -492,6 +735,43 @@ impl<DB: Database> StageCheckpointReader for ProviderFactory<DB> {
     }
 }
 
+impl<DB: Database> LegacyTxMetaReader for ProviderFactory<DB> {
+    fn legacy_tx_meta(&self, id: TxNumber) -> ProviderResult<Option<StoredLegacyTxMeta>> {
+        self.provider()?.legacy_tx_meta(id)
+    }
+}
+
+impl<DB: Database> BlockAccessListReader for ProviderFactory<DB> {
+    fn block_access_list(&self, number: BlockNumber) -> ProviderResult<Option<AccessList>> {
+        if !self.serves_block(number) {
+            return Ok(None)
+        }
+        self.provider()?.block_access_list(number)
+    }
+}
+
+impl<DB: Database> HeaderAccumulatorReader for ProviderFactory<DB> {
+    fn header_accumulator_epochs(&self) -> ProviderResult<Vec<B256>> {
+        self.provider()?.header_accumulator_epochs()
+    }
+}
+
+impl<DB: Database> ReorgJournalReader for ProviderFactory<DB> {
+    fn reorgs(&self, from: u64, limit: usize) -> ProviderResult<Vec<(u64, StoredReorg)>> {
+        self.provider()?.reorgs(from, limit)
+    }
+
+    fn last_reorg_id(&self) -> ProviderResult<Option<u64>> {
+        self.provider()?.last_reorg_id()
+    }
+}
+
+impl<DB: Database> SyncHistoryReader for ProviderFactory<DB> {
+    fn sync_history(&self, from: u64) -> ProviderResult<Vec<(u64, SyncSnapshot)>> {
+        self.provider()?.sync_history(from)
+    }
+}
+
 impl<DB: Database> EvmEnvProvider for ProviderFactory<DB> {
     fn fill_env_at<EvmConfig>(
         &self,
@@ -503,6 +783,7 @@ impl<DB: Database> EvmEnvProvider for ProviderFactory<DB> {
     where
         EvmConfig: ConfigureEvmEnv,
     {
+        self.ensure_served(at)?;
         self.provider()?.fill_env_at(cfg, block_env, at, evm_config)
     }
 
@@ -524,6 +805,7 @@ impl<DB: Database> EvmEnvProvider for ProviderFactory<DB> {
         block_env: &mut BlockEnv,
         at: BlockHashOrNumber,
     ) -> ProviderResult<()> {
+        self.ensure_served(at)?;
         self.provider()?.fill_block_env_at(block_env, at)
     }
 
@@ -544,6 +826,7 @@ impl<DB: Database> EvmEnvProvider for ProviderFactory<DB> {
     where
         EvmConfig: ConfigureEvmEnv,
     {
+        self.ensure_served(at)?;
         self.provider()?.fill_cfg_env_at(cfg, at, evm_config)
     }
 
@@ -584,6 +867,8 @@ impl<DB> Clone for ProviderFactory<DB> {
             db: Arc::clone(&self.db),
             chain_spec: self.chain_spec.clone(),
             static_file_provider: self.static_file_provider.clone(),
+            fork_state: self.fork_state.clone(),
+            pinned: self.pinned,
         }
     }
 }
@@ -593,7 +878,8 @@ mod tests {
     use crate::{
         providers::{StaticFileProvider, StaticFileWriter},
         test_utils::create_test_provider_factory,
-        BlockHashReader, BlockNumReader, BlockWriter, HeaderSyncGapProvider, TransactionsProvider,
+        BlockHashReader, BlockNumReader, BlockRangeIter, BlockWriter, HeaderSyncGapProvider,
+        StageCheckpointWriter, SyncHistoryWriter, TransactionsProvider,
     };
     use alloy_rlp::Decodable;
     use assert_matches::assert_matches;
@@ -604,12 +890,13 @@ mod tests {
         tables,
         test_utils::{create_test_static_files_dir, ERROR_TEMPDIR},
     };
+    use reth_db_api::transaction::DbTxMut;
     use reth_primitives::{hex_literal::hex, SealedBlock, StaticFileSegment, TxNumber, B256, U256};
     use reth_prune_types::{PruneMode, PruneModes};
     use reth_storage_errors::provider::ProviderError;
     use reth_testing_utils::{
         generators,
-        generators::{random_block, random_header},
+        generators::{random_block, random_block_range, random_header},
     };
     use std::{ops::RangeInclusive, sync::Arc};
     use tokio::sync::watch;
@@ -697,6 +984,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn ommers_of_blocks_with_and_without_uncles() {
+        let factory = create_test_provider_factory();
+        let mut rng = generators::rng();
+        let genesis = random_block(&mut rng, 0, None, Some(0), Some(0));
+        let without_uncles = random_block(&mut rng, 1, Some(genesis.hash()), Some(0), Some(0));
+        let with_uncles = random_block(&mut rng, 2, Some(without_uncles.hash()), Some(0), Some(2));
+
+        let provider = factory.provider_rw().unwrap();
+        for block in [&genesis, &without_uncles, &with_uncles] {
+            provider.insert_block(block.clone().try_seal_with_senders().unwrap(), None).unwrap();
+        }
+
+        assert_eq!(provider.ommers(1.into()).unwrap(), Some(Vec::new()));
+        assert_eq!(provider.ommers(with_uncles.hash().into()).unwrap(), Some(with_uncles.ommers));
+        assert_eq!(provider.ommers(3.into()).unwrap(), None);
+    }
+
+    #[test]
+    fn sync_history_keeps_latest_snapshots() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        for timestamp in 1..=5 {
+            let snapshot = SyncSnapshot { block_number: timestamp * 10, ..Default::default() };
+            provider.record_sync_snapshot(timestamp, snapshot, 3).unwrap();
+        }
+        provider.commit().unwrap();
+
+        let history = factory.sync_history(0).unwrap();
+        assert_eq!(history.iter().map(|(timestamp, _)| *timestamp).collect::<Vec<_>>(), [3, 4, 5]);
+        assert_eq!(history[2].1.block_number, 50);
+        assert_eq!(factory.sync_history(5).unwrap().len(), 1);
+    }
+
     #[test]
     fn get_take_block_transaction_range_recover_senders() {
         let factory = create_test_provider_factory();
@@ -739,6 +1060,82 @@ mod tests {
         }
     }
 
+    #[test]
+    fn block_range_iter() {
+        let factory = create_test_provider_factory();
+        let mut rng = generators::rng();
+        let blocks = random_block_range(&mut rng, 0..=4, B256::ZERO, 0..3);
+
+        let provider = factory.provider_rw().unwrap();
+        let mut tx_num = 0;
+        for block in &blocks {
+            provider.insert_block(block.clone().try_seal_with_senders().unwrap(), None).unwrap();
+            for _ in &block.body {
+                let receipt = Receipt { cumulative_gas_used: tx_num, ..Default::default() };
+                provider.tx_ref().put::<tables::Receipts>(tx_num, receipt).unwrap();
+                tx_num += 1;
+            }
+        }
+        provider.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        let iter = BlockRangeIter::new(&provider, 1..=10).with_batch_size(2);
+        let read = iter.collect::<ProviderResult<Vec<_>>>().unwrap();
+        assert_eq!(read.len(), 4);
+
+        let mut tx_num = blocks[0].body.len() as u64;
+        for (block, read) in blocks[1..].iter().zip(read) {
+            assert_eq!(read.block.block, *block);
+            let receipts = read.receipts.unwrap();
+            assert_eq!(receipts.len(), block.body.len());
+            for receipt in receipts {
+                assert_eq!(receipt.cumulative_gas_used, tx_num);
+                tx_num += 1;
+            }
+        }
+
+        let mut iter = BlockRangeIter::new(&provider, 0..=0).without_receipts();
+        assert_matches!(iter.next(), Some(Ok(read)) if read.receipts.is_none());
+        assert_matches!(iter.next(), None);
+    }
+
+    #[test]
+    fn pinned_to_historical_block() {
+        let factory = create_test_provider_factory();
+        let mut rng = generators::rng();
+        let blocks = random_block_range(&mut rng, 0..=4, B256::ZERO, 1..3);
+
+        let provider = factory.provider_rw().unwrap();
+        for block in &blocks {
+            provider.insert_block(block.clone().try_seal_with_senders().unwrap(), None).unwrap();
+        }
+        provider.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(4)).unwrap();
+        provider.commit().unwrap();
+
+        assert_matches!(
+            factory.clone().pinned_at(5),
+            Err(ProviderError::HeaderNotFound(BlockHashOrNumber::Number(5)))
+        );
+
+        let factory = factory.pinned_at(2).unwrap();
+        assert_eq!(factory.best_block_number().unwrap(), 2);
+        assert_eq!(factory.chain_info().unwrap().best_hash, blocks[2].hash());
+        assert!(factory.header_by_number(2).unwrap().is_some());
+        assert!(factory.header_by_number(3).unwrap().is_none());
+        assert!(factory.header(&blocks[3].hash()).unwrap().is_none());
+        assert!(factory.block(blocks[3].hash().into()).unwrap().is_none());
+        assert_eq!(factory.block_range(1..=4).unwrap().len(), 2);
+        assert_eq!(factory.headers_range(..).unwrap().len(), 3);
+
+        let served = &blocks[2].body[0];
+        let hidden = &blocks[3].body[0];
+        assert!(factory.transaction_by_hash(served.hash()).unwrap().is_some());
+        assert!(factory.transaction_by_hash(hidden.hash()).unwrap().is_none());
+        assert!(factory.transaction_by_hash_with_meta(hidden.hash()).unwrap().is_none());
+        let tx_count = blocks[..=2].iter().map(|block| block.body.len()).sum::<usize>();
+        assert_eq!(factory.transactions_by_tx_range(..).unwrap().len(), tx_count);
+    }
+
     #[test]
     fn header_sync_gap_lookup() {
         let factory = create_test_provider_factory();
@@ -1,16 +1,20 @@
 use crate::{
-    bundle_state::{BundleStateInit, HashedStateChanges, RevertsInit},
+    bundle_state::{BundleStateInit, BytecodeReferenceChanges, HashedStateChanges, RevertsInit},
     providers::{database::metrics, static_file::StaticFileWriter, StaticFileProvider},
     to_range,
     traits::{
         AccountExtReader, BlockSource, ChangeSetReader, ReceiptProvider, StageCheckpointWriter,
     },
-    AccountReader, BlockExecutionWriter, BlockHashReader, BlockNumReader, BlockReader, BlockWriter,
-    EvmEnvProvider, FinalizedBlockReader, FinalizedBlockWriter, HashingWriter, HeaderProvider,
-    HeaderSyncGap, HeaderSyncGapProvider, HistoricalStateProvider, HistoryWriter,
-    LatestStateProvider, OriginalValuesKnown, ProviderError, PruneCheckpointReader,
-    PruneCheckpointWriter, RequestsProvider, StageCheckpointReader, StateProviderBox, StateWriter,
-    StatsReader, StorageReader, TransactionVariant, TransactionsProvider, TransactionsProviderExt,
+    AccountReader, BlockAccessListReader, BlockAccessListWriter, BlockExecutionWriter,
+    BlockHashReader, BlockNumReader, BlockReader, BlockWriter, EvmEnvProvider,
+    FinalizedBlockReader, FinalizedBlockWriter, ForkBlockReader, ForkBlockWriter, HashingWriter,
+    HeaderAccumulatorReader, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider,
+    HistoricalStateProvider, HistoryWriter, LatestStateProvider, LegacyTxMetaReader,
+    LegacyTxMetaWriter, OriginalValuesKnown, PendingUnwindReader, PendingUnwindWriter,
+    ProviderError, PruneCheckpointReader, PruneCheckpointWriter, ReorgHaltReader, ReorgHaltWriter,
+    ReorgJournalReader, ReorgJournalWriter, RequestsProvider, StageCheckpointReader,
+    StateProviderBox, StateWriter, StatsReader, StorageReader, SyncHistoryReader,
+    SyncHistoryWriter, TransactionVariant, TransactionsProvider, TransactionsProviderExt,
     WithdrawalsProvider,
 };
 use itertools::{izip, Itertools};
@@ -23,6 +27,7 @@ use reth_db_api::{
     models::{
         sharded_key, storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress,
         ShardedKey, StoredBlockBodyIndices, StoredBlockOmmers, StoredBlockWithdrawals,
+        StoredLegacyL1Fee, StoredLegacyTxMeta, StoredReorg, SyncSnapshot,
     },
     table::{Table, TableRow},
     transaction::{DbTx, DbTxMut},
@@ -34,11 +39,11 @@ use reth_network_p2p::headers::downloader::SyncTarget;
 use reth_primitives::{
     keccak256,
     revm::{config::revm_spec, env::fill_block_env},
-    Account, Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders,
-    GotExpected, Head, Header, Receipt, Requests, SealedBlock, SealedBlockWithSenders,
-    SealedHeader, StaticFileSegment, StorageEntry, TransactionMeta, TransactionSigned,
-    TransactionSignedEcRecovered, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal,
-    Withdrawals, B256, U256,
+    AccessList, Account, Address, Block, BlockHash, BlockHashOrNumber, BlockNumber,
+    BlockWithSenders, GotExpected, Head, Header, Receipt, Requests, SealedBlock,
+    SealedBlockWithSenders, SealedHeader, StaticFileSegment, StorageEntry, TransactionMeta,
+    TransactionSigned, TransactionSignedEcRecovered, TransactionSignedNoHash, TxHash, TxNumber,
+    Withdrawal, Withdrawals, B256, U256,
 };
 use reth_prune_types::{PruneCheckpoint, PruneLimiter, PruneModes, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
@@ -567,6 +572,11 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         let to_transaction_num =
             block_bodies.last().expect("already checked if there are blocks").1.last_tx_num();
 
+        // the access lists were recorded by the execution of the blocks
+        if TAKE {
+            self.get_or_take::<tables::BlockAccessLists, true>(range.clone())?;
+        }
+
         let storage_range = BlockNumberAddress::range(range.clone());
 
         let storage_changeset =
@@ -587,10 +597,15 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         let mut plain_storage_cursor = self.tx.cursor_dup_write::<tables::PlainStorageState>()?;
 
         let mut reverts: RevertsInit = HashMap::new();
+        let mut bytecode_references = BytecodeReferenceChanges::default();
 
         // add account changeset changes
         for (block_number, account_before) in account_changeset.into_iter().rev() {
             let AccountBeforeTx { info: old_info, address } = account_before;
+            if TAKE {
+                // the changeset is removed, and with it the reference to the bytecode
+                bytecode_references.remove(old_info.as_ref());
+            }
             match state.entry(address) {
                 hash_map::Entry::Vacant(entry) => {
                     let new_info = plain_accounts_cursor.seek_exact(address)?.map(|kv| kv.1);
@@ -646,6 +661,8 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
                 // revert account if needed.
                 if old_account != new_account {
                     let existing_entry = plain_accounts_cursor.seek_exact(*address)?;
+                    bytecode_references.remove(existing_entry.as_ref().map(|(_, account)| account));
+                    bytecode_references.add(old_account.as_ref());
                     if let Some(account) = old_account {
                         plain_accounts_cursor.upsert(*address, *account)?;
                     } else if existing_entry.is_some() {
@@ -673,6 +690,9 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
                     }
                 }
             }
+
+            // delete the bytecodes that were only referenced by the unwound blocks
+            bytecode_references.write_to_db(&self.tx)?;
         }
 
         // iterate over block body and create ExecutionResult
@@ -1270,6 +1290,21 @@ impl<TX: DbTx> ChangeSetReader for DatabaseProvider<TX> {
             })
             .collect()
     }
+
+    fn storage_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(Address, StorageEntry)>> {
+        let range = BlockNumberAddress::range(block_number..=block_number);
+        self.tx
+            .cursor_read::<tables::StorageChangeSets>()?
+            .walk_range(range)?
+            .map(|result| -> ProviderResult<_> {
+                let (block_address, storage_entry) = result?;
+                Ok((block_address.address(), storage_entry))
+            })
+            .collect()
+    }
 }
 
 impl<TX: DbTx> HeaderSyncGapProvider for DatabaseProvider<TX> {
@@ -1528,8 +1563,14 @@ impl<TX: DbTx> BlockReader for DatabaseProvider<TX> {
                 return Ok(Some(Vec::new()))
             }
 
-            let ommers = self.tx.get::<tables::BlockOmmers>(number)?.map(|o| o.ommers);
-            return Ok(ommers)
+            if let Some(ommers) = self.tx.get::<tables::BlockOmmers>(number)? {
+                return Ok(Some(ommers.ommers))
+            }
+
+            // only blocks with ommers have an entry, any other block with a stored body has none
+            if self.block_body_indices(number)?.is_some() {
+                return Ok(Some(Vec::new()))
+            }
         }
 
         Ok(None)
@@ -1918,6 +1959,10 @@ impl<TX: DbTx> ReceiptProvider for DatabaseProvider<TX> {
             |_| true,
         )
     }
+
+    fn legacy_l1_fee(&self, id: TxNumber) -> ProviderResult<Option<StoredLegacyL1Fee>> {
+        Ok(self.tx.get::<tables::LegacyL1Fees>(id)?)
+    }
 }
 
 impl<TX: DbTx> WithdrawalsProvider for DatabaseProvider<TX> {
@@ -2829,17 +2874,10 @@ impl<TX: DbTx> StatsReader for DatabaseProvider<TX> {
 
 impl<TX: DbTx> FinalizedBlockReader for DatabaseProvider<TX> {
     fn last_finalized_block_number(&self) -> ProviderResult<BlockNumber> {
-        let mut finalized_blocks = self
+        Ok(self
             .tx
-            .cursor_read::<tables::ChainState>()?
-            .walk(Some(tables::ChainStateKey::LastFinalizedBlock))?
-            .take(1)
-            .collect::<Result<BTreeMap<tables::ChainStateKey, BlockNumber>, _>>()?;
-
-        let last_finalized_block_number = finalized_blocks
-            .pop_first()
-            .unwrap_or((tables::ChainStateKey::LastFinalizedBlock, 0_u64));
-        Ok(last_finalized_block_number.1)
+            .get::<tables::ChainState>(tables::ChainStateKey::LastFinalizedBlock)?
+            .unwrap_or_default())
     }
 }
 
@@ -2851,6 +2889,152 @@ impl<TX: DbTxMut> FinalizedBlockWriter for DatabaseProvider<TX> {
     }
 }
 
+impl<TX: DbTx> ReorgHaltReader for DatabaseProvider<TX> {
+    fn reorg_halt(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(self.tx.get::<tables::ChainState>(tables::ChainStateKey::ReorgHalt)?)
+    }
+}
+
+impl<TX: DbTxMut + DbTx> ReorgHaltWriter for DatabaseProvider<TX> {
+    fn save_reorg_halt(&self, fork_block: BlockNumber) -> ProviderResult<()> {
+        Ok(self.tx.put::<tables::ChainState>(tables::ChainStateKey::ReorgHalt, fork_block)?)
+    }
+
+    fn clear_reorg_halt(&self) -> ProviderResult<Option<BlockNumber>> {
+        let fork_block = self.reorg_halt()?;
+        if fork_block.is_some() {
+            self.tx.delete::<tables::ChainState>(tables::ChainStateKey::ReorgHalt, None)?;
+        }
+        Ok(fork_block)
+    }
+}
+
+impl<TX: DbTx> ForkBlockReader for DatabaseProvider<TX> {
+    fn fork_block(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(self.tx.get::<tables::ChainState>(tables::ChainStateKey::ForkBlock)?)
+    }
+}
+
+impl<TX: DbTxMut> ForkBlockWriter for DatabaseProvider<TX> {
+    fn save_fork_block(&self, block: BlockNumber) -> ProviderResult<()> {
+        Ok(self.tx.put::<tables::ChainState>(tables::ChainStateKey::ForkBlock, block)?)
+    }
+}
+
+impl<TX: DbTx> PendingUnwindReader for DatabaseProvider<TX> {
+    fn pending_unwind(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(self.tx.get::<tables::ChainState>(tables::ChainStateKey::PendingUnwind)?)
+    }
+}
+
+impl<TX: DbTxMut + DbTx> PendingUnwindWriter for DatabaseProvider<TX> {
+    fn save_pending_unwind(&self, target: BlockNumber) -> ProviderResult<()> {
+        Ok(self.tx.put::<tables::ChainState>(tables::ChainStateKey::PendingUnwind, target)?)
+    }
+
+    fn clear_pending_unwind(&self) -> ProviderResult<Option<BlockNumber>> {
+        let target = self.pending_unwind()?;
+        if target.is_some() {
+            self.tx.delete::<tables::ChainState>(tables::ChainStateKey::PendingUnwind, None)?;
+        }
+        Ok(target)
+    }
+}
+
+impl<TX: DbTx> ReorgJournalReader for DatabaseProvider<TX> {
+    fn reorgs(&self, from: u64, limit: usize) -> ProviderResult<Vec<(u64, StoredReorg)>> {
+        Ok(self
+            .tx
+            .cursor_read::<tables::Reorgs>()?
+            .walk(Some(from))?
+            .take(limit)
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+
+    fn last_reorg_id(&self) -> ProviderResult<Option<u64>> {
+        Ok(self.tx.cursor_read::<tables::Reorgs>()?.last()?.map(|(id, _)| id))
+    }
+}
+
+impl<TX: DbTx> SyncHistoryReader for DatabaseProvider<TX> {
+    fn sync_history(&self, from: u64) -> ProviderResult<Vec<(u64, SyncSnapshot)>> {
+        Ok(self
+            .tx
+            .cursor_read::<tables::SyncHistory>()?
+            .walk(Some(from))?
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+}
+
+impl<TX: DbTxMut + DbTx> SyncHistoryWriter for DatabaseProvider<TX> {
+    fn record_sync_snapshot(
+        &self,
+        timestamp: u64,
+        snapshot: SyncSnapshot,
+        max_snapshots: usize,
+    ) -> ProviderResult<()> {
+        self.tx.put::<tables::SyncHistory>(timestamp, snapshot)?;
+
+        let excess = self.tx.entries::<tables::SyncHistory>()?.saturating_sub(max_snapshots);
+        let mut cursor = self.tx.cursor_write::<tables::SyncHistory>()?;
+        for _ in 0..excess {
+            if cursor.first()?.is_none() {
+                break
+            }
+            cursor.delete_current()?;
+        }
+        Ok(())
+    }
+}
+
+impl<TX: DbTx> LegacyTxMetaReader for DatabaseProvider<TX> {
+    fn legacy_tx_meta(&self, id: TxNumber) -> ProviderResult<Option<StoredLegacyTxMeta>> {
+        Ok(self.tx.get::<tables::LegacyTxMeta>(id)?)
+    }
+}
+
+impl<TX: DbTxMut + DbTx> LegacyTxMetaWriter for DatabaseProvider<TX> {
+    fn insert_legacy_tx_meta(&self, id: TxNumber, meta: StoredLegacyTxMeta) -> ProviderResult<()> {
+        Ok(self.tx.put::<tables::LegacyTxMeta>(id, meta)?)
+    }
+}
+
+impl<TX: DbTx> BlockAccessListReader for DatabaseProvider<TX> {
+    fn block_access_list(&self, number: BlockNumber) -> ProviderResult<Option<AccessList>> {
+        Ok(self.tx.get::<tables::BlockAccessLists>(number)?)
+    }
+}
+
+impl<TX: DbTxMut + DbTx> BlockAccessListWriter for DatabaseProvider<TX> {
+    fn insert_block_access_list(
+        &self,
+        number: BlockNumber,
+        access_list: AccessList,
+    ) -> ProviderResult<()> {
+        Ok(self.tx.put::<tables::BlockAccessLists>(number, access_list)?)
+    }
+}
+
+impl<TX: DbTx> HeaderAccumulatorReader for DatabaseProvider<TX> {
+    fn header_accumulator_epochs(&self) -> ProviderResult<Vec<B256>> {
+        Ok(self
+            .tx
+            .cursor_read::<tables::HeaderAccumulators>()?
+            .walk(None)?
+            .map(|entry| entry.map(|(_, root)| root))
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+}
+
+impl<TX: DbTxMut + DbTx> ReorgJournalWriter for DatabaseProvider<TX> {
+    fn append_reorg(&self, reorg: StoredReorg) -> ProviderResult<u64> {
+        let mut cursor = self.tx.cursor_write::<tables::Reorgs>()?;
+        let id = cursor.last()?.map_or(0, |(id, _)| id + 1);
+        cursor.append(id, reorg)?;
+        Ok(id)
+    }
+}
+
 fn range_size_hint(range: &impl RangeBounds<TxNumber>) -> Option<usize> {
     let start = match range.start_bound().cloned() {
         Bound::Included(start) => start,
@@ -1,11 +1,13 @@
 use crate::{
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    BlockSource, BlockchainTreePendingStateProvider, CanonChainTracker, CanonStateNotifications,
-    CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader, DatabaseProviderFactory,
-    EvmEnvProvider, FullExecutionDataProvider, HeaderProvider, ProviderError,
-    PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt, RequestsProvider,
-    StageCheckpointReader, StateProviderBox, StateProviderFactory, StaticFileProviderFactory,
-    TransactionVariant, TransactionsProvider, TreeViewer, WithdrawalsProvider,
+    AccountReader, BlockAccessListReader, BlockHashReader, BlockIdReader, BlockNumReader,
+    BlockReader, BlockReaderIdExt, BlockSource, BlockchainTreePendingStateProvider,
+    CanonChainTracker, CanonStateNotifications, CanonStateSubscriptions, ChainSpecProvider,
+    ChangeSetReader, DatabaseProviderFactory, EvmEnvProvider, FullExecutionDataProvider,
+    HeaderAccumulatorReader, HeaderProvider, LegacyTxMetaReader, ProviderError,
+    PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt, ReorgJournalReader,
+    RequestsProvider, StageCheckpointReader, StateProviderBox, StateProviderFactory,
+    StaticFileProviderFactory, TransactionVariant, TransactionsProvider, TreeViewer,
+    WithdrawalsProvider,
 };
 use reth_blockchain_tree_api::{
     error::{CanonicalError, InsertBlockError},
@@ -15,14 +17,16 @@ use reth_blockchain_tree_api::{
 use reth_chainspec::{ChainInfo, ChainSpec};
 use reth_db_api::{
     database::Database,
-    models::{AccountBeforeTx, StoredBlockBodyIndices},
+    models::{
+        AccountBeforeTx, StoredBlockBodyIndices, StoredLegacyL1Fee, StoredLegacyTxMeta, StoredReorg,
+    },
 };
 use reth_evm::ConfigureEvmEnv;
 use reth_primitives::{
-    Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumHash, BlockNumber,
-    BlockNumberOrTag, BlockWithSenders, Header, Receipt, SealedBlock, SealedBlockWithSenders,
-    SealedHeader, TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber,
-    Withdrawal, Withdrawals, B256, U256,
+    AccessList, Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumHash,
+    BlockNumber, BlockNumberOrTag, BlockWithSenders, Header, Receipt, SealedBlock,
+    SealedBlockWithSenders, SealedHeader, StorageEntry, TransactionMeta, TransactionSigned,
+    TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
 };
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
@@ -47,6 +51,7 @@ pub use static_file::{
 
 mod state;
 pub use state::{
+    fork::{ForkState, ForkStateProvider, ForkStateSource},
     historical::{HistoricalStateProvider, HistoricalStateProviderRef},
     latest::{LatestStateProvider, LatestStateProviderRef},
 };
@@ -112,13 +117,10 @@ where
     /// Create a new provider using only the database and the tree, fetching the latest header from
     /// the database to initialize the provider.
     pub fn new(database: ProviderFactory<DB>, tree: Arc<dyn TreeViewer>) -> ProviderResult<Self> {
-        let provider = database.provider()?;
-        let best: ChainInfo = provider.chain_info()?;
-        match provider.header_by_number(best.best_number)? {
-            Some(header) => {
-                drop(provider);
-                Ok(Self::with_latest(database, tree, header.seal(best.best_hash)))
-            }
+        // read through the factory, so the chain is initialized at its pinned block
+        let best: ChainInfo = database.chain_info()?;
+        match database.header_by_number(best.best_number)? {
+            Some(header) => Ok(Self::with_latest(database, tree, header.seal(best.best_hash))),
             None => Err(ProviderError::HeaderNotFound(best.best_number.into())),
         }
     }
@@ -436,6 +438,10 @@ where
     ) -> ProviderResult<Vec<Receipt>> {
         self.database.receipts_by_tx_range(range)
     }
+
+    fn legacy_l1_fee(&self, id: TxNumber) -> ProviderResult<Option<StoredLegacyL1Fee>> {
+        self.database.legacy_l1_fee(id)
+    }
 }
 
 impl<DB> ReceiptProviderIdExt for BlockchainProvider<DB>
@@ -522,7 +528,7 @@ where
     where
         EvmConfig: ConfigureEvmEnv,
     {
-        self.database.provider()?.fill_env_at(cfg, block_env, at, evm_config)
+        self.database.fill_env_at(cfg, block_env, at, evm_config)
     }
 
     fn fill_env_with_header<EvmConfig>(
@@ -543,7 +549,7 @@ where
         block_env: &mut BlockEnv,
         at: BlockHashOrNumber,
     ) -> ProviderResult<()> {
-        self.database.provider()?.fill_block_env_at(block_env, at)
+        self.database.fill_block_env_at(block_env, at)
     }
 
     fn fill_block_env_with_header(
@@ -563,7 +569,7 @@ where
     where
         EvmConfig: ConfigureEvmEnv,
     {
-        self.database.provider()?.fill_cfg_env_at(cfg, at, evm_config)
+        self.database.fill_cfg_env_at(cfg, at, evm_config)
     }
 
     fn fill_cfg_env_with_header<EvmConfig>(
@@ -908,6 +914,53 @@ where
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
         self.database.provider()?.account_block_changeset(block_number)
     }
+
+    fn storage_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(Address, StorageEntry)>> {
+        self.database.provider()?.storage_block_changeset(block_number)
+    }
+}
+
+impl<DB> LegacyTxMetaReader for BlockchainProvider<DB>
+where
+    DB: Database,
+{
+    fn legacy_tx_meta(&self, id: TxNumber) -> ProviderResult<Option<StoredLegacyTxMeta>> {
+        self.database.legacy_tx_meta(id)
+    }
+}
+
+impl<DB> BlockAccessListReader for BlockchainProvider<DB>
+where
+    DB: Database,
+{
+    fn block_access_list(&self, number: BlockNumber) -> ProviderResult<Option<AccessList>> {
+        self.database.block_access_list(number)
+    }
+}
+
+impl<DB> HeaderAccumulatorReader for BlockchainProvider<DB>
+where
+    DB: Database,
+{
+    fn header_accumulator_epochs(&self) -> ProviderResult<Vec<B256>> {
+        self.database.header_accumulator_epochs()
+    }
+}
+
+impl<DB> ReorgJournalReader for BlockchainProvider<DB>
+where
+    DB: Database,
+{
+    fn reorgs(&self, from: u64, limit: usize) -> ProviderResult<Vec<(u64, StoredReorg)>> {
+        self.database.reorgs(from, limit)
+    }
+
+    fn last_reorg_id(&self) -> ProviderResult<Option<u64>> {
+        self.database.last_reorg_id()
+    }
 }
 
 impl<DB> AccountReader for BlockchainProvider<DB>
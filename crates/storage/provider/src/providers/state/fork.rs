@@ -0,0 +1,361 @@
+use crate::{AccountReader, BlockHashReader, StateProvider, StateRootProvider};
+use reth_db::tables;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbDupCursorRO, DbDupCursorRW},
+    database::Database,
+    models::{storage_sharded_key::StorageShardedKey, ShardedKey},
+    transaction::{DbTx, DbTxMut},
+};
+use reth_primitives::{
+    Account, Address, BlockNumber, Bytecode, Bytes, StorageEntry, StorageKey, StorageValue, B256,
+};
+use reth_storage_errors::provider::ProviderResult;
+use reth_trie::{updates::TrieUpdates, AccountProof};
+use revm::db::BundleState;
+use std::{collections::HashMap, fmt, sync::Arc};
+
+/// Source of the state of the chain a node is forked from, at the block it's forked at.
+pub trait ForkStateSource: Send + Sync + fmt::Debug {
+    /// Returns the block of the forked chain the state is read at.
+    fn block(&self) -> BlockNumber;
+
+    /// Returns the account and its bytecode, `None` if the account doesn't exist.
+    fn account(&self, address: Address) -> ProviderResult<Option<(Account, Option<Bytecode>)>>;
+
+    /// Returns the value of a storage slot of an account.
+    fn storage(&self, address: Address, key: StorageKey) -> ProviderResult<StorageValue>;
+}
+
+/// The state of the chain a node is forked from.
+///
+/// The state is fetched from the [`ForkStateSource`] when it's first read and cached in the
+/// [`tables::ForkAccounts`], [`tables::ForkStorages`] and [`tables::ForkBytecodes`] tables. It
+/// only serves accounts and storage slots that were never changed by a local block, the local
+/// state of all others is authoritative.
+#[derive(Clone)]
+pub struct ForkState {
+    source: Arc<dyn ForkStateSource>,
+    db: Arc<dyn ForkStateDatabase>,
+}
+
+impl ForkState {
+    /// Creates the fork state of the given source, cached in the given database.
+    pub fn new<DB: Database + 'static>(db: Arc<DB>, source: Arc<dyn ForkStateSource>) -> Self {
+        Self { source, db }
+    }
+
+    /// Returns the block of the forked chain the state is read at.
+    pub fn block(&self) -> BlockNumber {
+        self.source.block()
+    }
+
+    /// Returns the account of the forked chain, unless it was changed locally.
+    pub fn basic_account(&self, address: Address) -> ProviderResult<Option<Account>> {
+        if self.db.account_changed(address)? {
+            return Ok(None)
+        }
+        if let Some(account) = self.db.cached_account(address)? {
+            return Ok((!account.is_empty()).then_some(account))
+        }
+
+        let account = self.source.account(address)?;
+        let (info, bytecode) = account.clone().unwrap_or_default();
+        self.db.cache_account(address, info, bytecode)?;
+        Ok(account.map(|(account, _)| account))
+    }
+
+    /// Returns the storage slot of the forked chain, unless it was changed locally.
+    pub fn storage(
+        &self,
+        address: Address,
+        key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        if self.db.storage_changed(address, key)? {
+            return Ok(None)
+        }
+        if let Some(value) = self.db.cached_storage(address, key)? {
+            return Ok(Some(value))
+        }
+
+        let value = self.source.storage(address, key)?;
+        self.db.cache_storage(address, key, value)?;
+        Ok(Some(value))
+    }
+
+    /// Returns a bytecode of the accounts of the forked chain that were read.
+    pub fn bytecode_by_hash(&self, code_hash: B256) -> ProviderResult<Option<Bytecode>> {
+        self.db.cached_bytecode(code_hash)
+    }
+}
+
+impl fmt::Debug for ForkState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ForkState").field("source", &self.source).finish_non_exhaustive()
+    }
+}
+
+/// Access to the cached fork state and the local history, so [`ForkState`] isn't generic over
+/// the database.
+trait ForkStateDatabase: Send + Sync {
+    /// Returns `true` if a local block changed the account.
+    fn account_changed(&self, address: Address) -> ProviderResult<bool>;
+
+    /// Returns `true` if a local block changed the storage slot.
+    fn storage_changed(&self, address: Address, key: StorageKey) -> ProviderResult<bool>;
+
+    fn cached_account(&self, address: Address) -> ProviderResult<Option<Account>>;
+
+    fn cached_storage(
+        &self,
+        address: Address,
+        key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>>;
+
+    fn cached_bytecode(&self, code_hash: B256) -> ProviderResult<Option<Bytecode>>;
+
+    fn cache_account(
+        &self,
+        address: Address,
+        account: Account,
+        bytecode: Option<Bytecode>,
+    ) -> ProviderResult<()>;
+
+    fn cache_storage(
+        &self,
+        address: Address,
+        key: StorageKey,
+        value: StorageValue,
+    ) -> ProviderResult<()>;
+}
+
+impl<DB: Database> ForkStateDatabase for DB {
+    fn account_changed(&self, address: Address) -> ProviderResult<bool> {
+        let tx = self.tx()?;
+        let shard = tx
+            .cursor_read::<tables::AccountsHistory>()?
+            .seek(ShardedKey::new(address, 0))?
+            .filter(|(key, _)| key.key == address);
+        Ok(shard.is_some())
+    }
+
+    fn storage_changed(&self, address: Address, key: StorageKey) -> ProviderResult<bool> {
+        let tx = self.tx()?;
+        let shard = tx
+            .cursor_read::<tables::StoragesHistory>()?
+            .seek(StorageShardedKey::new(address, key, 0))?
+            .filter(|(shard, _)| shard.address == address && shard.sharded_key.key == key);
+        Ok(shard.is_some())
+    }
+
+    fn cached_account(&self, address: Address) -> ProviderResult<Option<Account>> {
+        Ok(self.tx()?.get::<tables::ForkAccounts>(address)?)
+    }
+
+    fn cached_storage(
+        &self,
+        address: Address,
+        key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        let tx = self.tx()?;
+        let entry = tx
+            .cursor_dup_read::<tables::ForkStorages>()?
+            .seek_by_key_subkey(address, key)?
+            .filter(|entry| entry.key == key);
+        Ok(entry.map(|entry| entry.value))
+    }
+
+    fn cached_bytecode(&self, code_hash: B256) -> ProviderResult<Option<Bytecode>> {
+        Ok(self.tx()?.get::<tables::ForkBytecodes>(code_hash)?)
+    }
+
+    fn cache_account(
+        &self,
+        address: Address,
+        account: Account,
+        bytecode: Option<Bytecode>,
+    ) -> ProviderResult<()> {
+        let tx = self.tx_mut()?;
+        if let Some((code_hash, bytecode)) = account.bytecode_hash.zip(bytecode) {
+            tx.put::<tables::ForkBytecodes>(code_hash, bytecode)?;
+        }
+        tx.put::<tables::ForkAccounts>(address, account)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn cache_storage(
+        &self,
+        address: Address,
+        key: StorageKey,
+        value: StorageValue,
+    ) -> ProviderResult<()> {
+        let tx = self.tx_mut()?;
+        let mut cursor = tx.cursor_dup_write::<tables::ForkStorages>()?;
+        // another reader may have cached the slot in the meantime
+        if cursor.seek_by_key_subkey(address, key)?.filter(|entry| entry.key == key).is_none() {
+            cursor.upsert(address, StorageEntry { key, value })?;
+        }
+        drop(cursor);
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+/// A state provider that reads the state of the chain the node is forked from, if the state isn't
+/// available in the inner provider.
+///
+/// See [`ForkState`].
+#[derive(Debug)]
+pub struct ForkStateProvider<SP> {
+    inner: SP,
+    fork: ForkState,
+}
+
+impl<SP: StateProvider> ForkStateProvider<SP> {
+    /// Creates a new provider that reads the forked state missing in the inner provider.
+    pub const fn new(inner: SP, fork: ForkState) -> Self {
+        Self { inner, fork }
+    }
+}
+
+impl<SP: StateProvider> BlockHashReader for ForkStateProvider<SP> {
+    fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+        self.inner.block_hash(number)
+    }
+
+    fn canonical_hashes_range(
+        &self,
+        start: BlockNumber,
+        end: BlockNumber,
+    ) -> ProviderResult<Vec<B256>> {
+        self.inner.canonical_hashes_range(start, end)
+    }
+}
+
+impl<SP: StateProvider> AccountReader for ForkStateProvider<SP> {
+    fn basic_account(&self, address: Address) -> ProviderResult<Option<Account>> {
+        match self.inner.basic_account(address)? {
+            Some(account) => Ok(Some(account)),
+            None => self.fork.basic_account(address),
+        }
+    }
+}
+
+impl<SP: StateProvider> StateRootProvider for ForkStateProvider<SP> {
+    fn state_root(&self, bundle_state: &BundleState) -> ProviderResult<B256> {
+        self.inner.state_root(bundle_state)
+    }
+
+    fn state_root_with_updates(
+        &self,
+        bundle_state: &BundleState,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        self.inner.state_root_with_updates(bundle_state)
+    }
+}
+
+impl<SP: StateProvider> StateProvider for ForkStateProvider<SP> {
+    fn storage(
+        &self,
+        account: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        match self.inner.storage(account, storage_key)? {
+            Some(value) => Ok(Some(value)),
+            None => self.fork.storage(account, storage_key),
+        }
+    }
+
+    fn bytecode_by_hash(&self, code_hash: B256) -> ProviderResult<Option<Bytecode>> {
+        match self.inner.bytecode_by_hash(code_hash)? {
+            Some(bytecode) => Ok(Some(bytecode)),
+            None => self.fork.bytecode_by_hash(code_hash),
+        }
+    }
+
+    fn proof(&self, address: Address, keys: &[B256]) -> ProviderResult<AccountProof> {
+        self.inner.proof(address, keys)
+    }
+
+    fn witness(&self, targets: &HashMap<Address, Vec<B256>>) -> ProviderResult<Vec<Bytes>> {
+        self.inner.witness(targets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::create_test_provider_factory, HistoryWriter};
+    use reth_primitives::{KECCAK_EMPTY, U256};
+    use std::{
+        collections::BTreeMap,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    /// A forked chain with a single contract, counting the reads.
+    #[derive(Debug, Default)]
+    struct TestSource {
+        reads: AtomicUsize,
+    }
+
+    impl ForkStateSource for TestSource {
+        fn block(&self) -> BlockNumber {
+            100
+        }
+
+        fn account(&self, address: Address) -> ProviderResult<Option<(Account, Option<Bytecode>)>> {
+            self.reads.fetch_add(1, Ordering::Relaxed);
+            if address != Address::with_last_byte(1) {
+                return Ok(None)
+            }
+            let bytecode = Bytecode::new_raw(Bytes::from_static(&[0x60, 0x00]));
+            let account = Account {
+                nonce: 1,
+                balance: U256::from(10),
+                bytecode_hash: Some(bytecode.hash_slow()),
+            };
+            Ok(Some((account, Some(bytecode))))
+        }
+
+        fn storage(&self, _address: Address, key: StorageKey) -> ProviderResult<StorageValue> {
+            self.reads.fetch_add(1, Ordering::Relaxed);
+            Ok(U256::from_be_bytes(key.0))
+        }
+    }
+
+    #[test]
+    fn reads_and_caches_forked_state() {
+        let factory = create_test_provider_factory();
+        let source = Arc::new(TestSource::default());
+        let factory = factory.with_fork_state(source.clone());
+        let fork = factory.fork_state().unwrap();
+
+        let contract = Address::with_last_byte(1);
+        let account = fork.basic_account(contract).unwrap().unwrap();
+        assert_eq!(account.nonce, 1);
+        assert!(fork.bytecode_by_hash(account.bytecode_hash.unwrap()).unwrap().is_some());
+        assert_eq!(fork.basic_account(Address::with_last_byte(2)).unwrap(), None);
+        assert_eq!(fork.storage(contract, B256::with_last_byte(3)).unwrap(), Some(U256::from(3)));
+        assert_eq!(source.reads.load(Ordering::Relaxed), 3);
+
+        // cached reads, including of accounts that don't exist
+        assert_eq!(fork.basic_account(contract).unwrap(), Some(account));
+        assert_eq!(fork.basic_account(Address::with_last_byte(2)).unwrap(), None);
+        assert_eq!(fork.storage(contract, B256::with_last_byte(3)).unwrap(), Some(U256::from(3)));
+        assert_eq!(fork.bytecode_by_hash(KECCAK_EMPTY).unwrap(), None);
+        assert_eq!(source.reads.load(Ordering::Relaxed), 3);
+
+        // state changed by a local block is never read from the forked chain
+        let provider = factory.provider_rw().unwrap();
+        provider.insert_account_history_index(BTreeMap::from([(contract, vec![1])])).unwrap();
+        provider
+            .insert_storage_history_index(BTreeMap::from([(
+                (contract, B256::with_last_byte(3)),
+                vec![1],
+            )]))
+            .unwrap();
+        provider.commit().unwrap();
+        assert_eq!(fork.basic_account(contract).unwrap(), None);
+        assert_eq!(fork.storage(contract, B256::with_last_byte(3)).unwrap(), None);
+    }
+}
@@ -10,13 +10,13 @@ use reth_db_api::{
     transaction::DbTx,
 };
 use reth_primitives::{
-    constants::EPOCH_SLOTS, Account, Address, BlockNumber, Bytecode, StaticFileSegment, StorageKey,
-    StorageValue, B256,
+    constants::EPOCH_SLOTS, Account, Address, BlockNumber, Bytecode, Bytes, StaticFileSegment,
+    StorageKey, StorageValue, B256,
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{updates::TrieUpdates, AccountProof, HashedPostState};
 use revm::db::BundleState;
-use std::fmt::Debug;
+use std::{collections::HashMap, fmt::Debug};
 
 /// State provider for a given block number which takes a tx reference.
 ///
@@ -72,8 +72,13 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
 
     /// Lookup an account in the `AccountsHistory` table
     pub fn account_history_lookup(&self, address: Address) -> ProviderResult<HistoryInfo> {
+        let mut lowest_available_block_number =
+            self.lowest_available_blocks.account_history_block_number;
         if !self.lowest_available_blocks.is_account_history_available(self.block_number) {
-            return Err(ProviderError::StateAtBlockPruned(self.block_number))
+            if !self.is_history_retained(address)? {
+                return Err(ProviderError::StateAtBlockPruned(self.block_number))
+            }
+            lowest_available_block_number = None;
         }
 
         // history key to search IntegerList of block number changesets.
@@ -81,7 +86,7 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
         self.history_info::<tables::AccountsHistory, _>(
             history_key,
             |key| key.key == address,
-            self.lowest_available_blocks.account_history_block_number,
+            lowest_available_block_number,
         )
     }
 
@@ -91,8 +96,13 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
         address: Address,
         storage_key: StorageKey,
     ) -> ProviderResult<HistoryInfo> {
+        let mut lowest_available_block_number =
+            self.lowest_available_blocks.storage_history_block_number;
         if !self.lowest_available_blocks.is_storage_history_available(self.block_number) {
-            return Err(ProviderError::StateAtBlockPruned(self.block_number))
+            if !self.is_history_retained(address)? {
+                return Err(ProviderError::StateAtBlockPruned(self.block_number))
+            }
+            lowest_available_block_number = None;
         }
 
         // history key to search IntegerList of block number changesets.
@@ -100,10 +110,40 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
         self.history_info::<tables::StoragesHistory, _>(
             history_key,
             |key| key.address == address && key.sharded_key.key == storage_key,
-            self.lowest_available_blocks.storage_history_block_number,
+            lowest_available_block_number,
         )
     }
 
+    /// Checks whether the account and storage history of the address was retained by the pruner,
+    /// which is the case for the addresses of the history allowlist.
+    ///
+    /// The pruner removes the account history indices below the lowest available block, except
+    /// for the allowlisted addresses, so an address with an index entry below it has its full
+    /// history. An allowlisted address that wasn't changed below the lowest available block can't
+    /// be told apart from a pruned one.
+    fn is_history_retained(&self, address: Address) -> ProviderResult<bool> {
+        let Some(account_history_block_number) =
+            self.lowest_available_blocks.account_history_block_number
+        else {
+            // Without pruned account history, the indices don't tell the retained addresses apart.
+            return Ok(false)
+        };
+        let lowest_available_block_number = self
+            .lowest_available_blocks
+            .storage_history_block_number
+            .map_or(account_history_block_number, |block_number| {
+                block_number.min(account_history_block_number)
+            });
+
+        let first_change = self
+            .tx
+            .cursor_read::<tables::AccountsHistory>()?
+            .seek(ShardedKey::new(address, 0))?
+            .filter(|(key, _)| key.key == address)
+            .and_then(|(_, blocks)| blocks.iter().next());
+        Ok(first_change.is_some_and(|block_number| block_number < lowest_available_block_number))
+    }
+
     /// Retrieve revert hashed state for this history provider.
     fn revert_state(&self) -> ProviderResult<HashedPostState> {
         if !self.lowest_available_blocks.is_account_history_available(self.block_number) ||
@@ -311,6 +351,12 @@ impl<'b, TX: DbTx> StateProvider for HistoricalStateProviderRef<'b, TX> {
     fn proof(&self, _address: Address, _keys: &[B256]) -> ProviderResult<AccountProof> {
         Err(ProviderError::StateRootNotAvailableForHistoricalBlock)
     }
+
+    fn witness(&self, targets: &HashMap<Address, Vec<B256>>) -> ProviderResult<Vec<Bytes>> {
+        self.revert_state()?
+            .multiproof(self.tx, targets)
+            .map_err(|err| ProviderError::Database(err.into()))
+    }
 }
 
 /// State provider for a given block number.
@@ -7,7 +7,7 @@ use crate::{
     TransactionsProvider,
 };
 use reth_chainspec::ChainInfo;
-use reth_db::static_file::{HeaderMask, ReceiptMask, StaticFileCursor, TransactionMask};
+use reth_db::static_file::{HeaderMask, StaticFileCursor, TransactionMask};
 use reth_db_api::models::CompactU256;
 use reth_primitives::{
     Address, BlockHash, BlockHashOrNumber, BlockNumber, Header, Receipt, SealedHeader,
@@ -48,7 +48,8 @@ impl<'a> StaticFileJarProvider<'a> {
     where
         'b: 'a,
     {
-        let result = StaticFileCursor::new(self.value(), self.mmap_handle())?;
+        let result = StaticFileCursor::new(self.value(), self.mmap_handle())?
+            .with_receipt_topics(self.receipt_topics());
 
         if let Some(metrics) = &self.metrics {
             metrics.record_segment_operation(
@@ -291,7 +292,7 @@ impl<'a> TransactionsProvider for StaticFileJarProvider<'a> {
 
 impl<'a> ReceiptProvider for StaticFileJarProvider<'a> {
     fn receipt(&self, num: TxNumber) -> ProviderResult<Option<Receipt>> {
-        self.cursor()?.get_one::<ReceiptMask<Receipt>>(num.into())
+        self.cursor()?.get_receipt(num.into())
     }
 
     fn receipt_by_hash(&self, hash: TxHash) -> ProviderResult<Option<Receipt>> {
@@ -318,7 +319,7 @@ impl<'a> ReceiptProvider for StaticFileJarProvider<'a> {
         let mut receipts = Vec::with_capacity((range.end - range.start) as usize);
 
         for num in range {
-            if let Some(tx) = cursor.get_one::<ReceiptMask<Receipt>>(num.into())? {
+            if let Some(tx) = cursor.get_receipt(num.into())? {
                 receipts.push(tx)
             }
         }
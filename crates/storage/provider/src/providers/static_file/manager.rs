@@ -12,7 +12,9 @@ use parking_lot::RwLock;
 use reth_chainspec::ChainInfo;
 use reth_db::{
     lockfile::StorageLock,
-    static_file::{iter_static_files, HeaderMask, ReceiptMask, StaticFileCursor, TransactionMask},
+    static_file::{
+        iter_static_files, receipt_topics_path, HeaderMask, StaticFileCursor, TransactionMask,
+    },
     tables,
 };
 use reth_db_api::{
@@ -107,10 +109,18 @@ pub struct StaticFileProviderInner {
     map: DashMap<(BlockNumber, StaticFileSegment), LoadedJar>,
     /// Max static file block for each segment
     static_files_max_block: RwLock<HashMap<StaticFileSegment, u64>>,
+    /// Min static file block and transaction for each segment, the lower static files may have
+    /// been archived.
+    static_files_min: RwLock<HashMap<StaticFileSegment, (BlockNumber, Option<TxNumber>)>>,
     /// Available static file block ranges on disk indexed by max transactions.
     static_files_tx_index: RwLock<SegmentRanges>,
     /// Directory where `static_files` are located
     path: PathBuf,
+    /// Additional read-only directories consulted for the static files below the lowest one in
+    /// `path`, e.g. an archive of old history shared by several nodes.
+    read_only_dirs: Vec<PathBuf>,
+    /// The read-only directory of each static file that isn't located in `path`, keyed like `map`.
+    read_only_static_files: RwLock<HashMap<(BlockNumber, StaticFileSegment), PathBuf>>,
     /// Whether [`StaticFileJarProvider`] loads filters into memory. If not, `by_hash` queries
     /// won't be able to be queried directly.
     load_filters: bool,
@@ -121,6 +131,9 @@ pub struct StaticFileProviderInner {
     access: StaticFileAccess,
     /// Write lock for when access is [`StaticFileAccess::RW`].
     _lock_file: Option<StorageLock>,
+    /// Reader for batched reads of transaction and receipt ranges, if enabled.
+    #[cfg(all(target_os = "linux", feature = "io-uring"))]
+    uring: Option<reth_nippy_jar::uring::UringReader>,
 }
 
 impl StaticFileProviderInner {
@@ -136,12 +149,17 @@ impl StaticFileProviderInner {
             map: Default::default(),
             writers: Default::default(),
             static_files_max_block: Default::default(),
+            static_files_min: Default::default(),
             static_files_tx_index: Default::default(),
             path: path.as_ref().to_path_buf(),
+            read_only_dirs: Vec::new(),
+            read_only_static_files: Default::default(),
             load_filters: false,
             metrics: None,
             access,
             _lock_file,
+            #[cfg(all(target_os = "linux", feature = "io-uring"))]
+            uring: None,
         };
 
         Ok(provider)
@@ -169,6 +187,116 @@ impl StaticFileProvider {
         Self(Arc::new(provider))
     }
 
+    /// Consults the read-only directories for the static files of a segment below its lowest static
+    /// file in the directory of the provider.
+    ///
+    /// The directories are never written to, and a static file is read from the first directory
+    /// that contains it.
+    pub fn with_read_only_dirs(self, dirs: Vec<PathBuf>) -> ProviderResult<Self> {
+        if dirs.is_empty() {
+            return Ok(self)
+        }
+        for dir in &dirs {
+            reth_fs_util::metadata(dir)?;
+        }
+        let mut provider =
+            Arc::try_unwrap(self.0).expect("should be called when initializing only");
+        provider.read_only_dirs = dirs;
+        let provider = Self(Arc::new(provider));
+        provider.initialize_index()?;
+        Ok(provider)
+    }
+
+    /// Reads ranges of transactions and receipts through `io_uring`, submitting at most
+    /// `queue_depth` reads at once.
+    ///
+    /// Requires the `io-uring` feature and Linux. Static files with compression are still read
+    /// through their mmap handle.
+    pub fn with_io_uring(self, queue_depth: u32) -> ProviderResult<Self> {
+        #[cfg(all(target_os = "linux", feature = "io-uring"))]
+        {
+            let mut provider =
+                Arc::try_unwrap(self.0).expect("should be called when initializing only");
+            provider.uring = Some(
+                reth_nippy_jar::uring::UringReader::new(queue_depth)
+                    .map_err(|e| ProviderError::NippyJar(e.to_string()))?,
+            );
+            Ok(Self(Arc::new(provider)))
+        }
+
+        #[cfg(not(all(target_os = "linux", feature = "io-uring")))]
+        {
+            let _ = queue_depth;
+            Err(ProviderError::NippyJar(
+                "io_uring reads require Linux and the `io-uring` feature".to_string(),
+            ))
+        }
+    }
+
+    /// Reads the raw values of a range of transactions or receipts with a single batch of
+    /// `io_uring` reads, one per static file, and decodes them with `decode`.
+    ///
+    /// Returns `None` if `io_uring` is disabled, or if the range isn't fully covered by
+    /// uncompressed static files.
+    #[cfg(all(target_os = "linux", feature = "io-uring"))]
+    fn fetch_range_uring<T>(
+        &self,
+        segment: StaticFileSegment,
+        range: Range<TxNumber>,
+        mut decode: impl FnMut(
+            &[u8],
+            Option<&reth_primitives::receipt_compaction::TopicDictionary>,
+        ) -> ProviderResult<T>,
+    ) -> ProviderResult<Option<Vec<T>>> {
+        use reth_nippy_jar::uring::RowsRequest;
+
+        let Some(uring) = &self.uring else { return Ok(None) };
+
+        let mut providers = Vec::new();
+        let mut tx = range.start;
+        while tx < range.end {
+            let Ok(provider) = self.get_segment_provider_from_transaction(segment, tx, None) else {
+                return Ok(None)
+            };
+            let Some(tx_range) = provider.user_header().tx_range().copied() else {
+                return Ok(None)
+            };
+            if provider.compressor().is_some() || tx < tx_range.start() {
+                return Ok(None)
+            }
+            let end = range.end.min(tx_range.end() + 1);
+            let rows = (tx - tx_range.start()) as usize..(end - tx_range.start()) as usize;
+            if rows.end > provider.rows() {
+                return Ok(None)
+            }
+            providers.push((provider, rows));
+            tx = end;
+        }
+
+        let readers =
+            providers.iter().map(|(provider, _)| provider.mmap_handle()).collect::<Vec<_>>();
+        let requests = providers
+            .iter()
+            .zip(&readers)
+            .map(|((provider, rows), reader)| RowsRequest {
+                jar: provider.value(),
+                reader,
+                rows: rows.clone(),
+            })
+            .collect::<Vec<_>>();
+        let values =
+            uring.read_rows(&requests, 0).map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+
+        let mut result = Vec::with_capacity((range.end - range.start) as usize);
+        for ((provider, _), values) in providers.iter().zip(values) {
+            let topics = provider.receipt_topics();
+            for value in values {
+                result.push(decode(&value, topics.as_deref())?);
+            }
+        }
+        Ok(Some(result))
+    }
+
     /// Reports metrics for the static files.
     pub fn report_metrics(&self) -> ProviderResult<()> {
         let Some(metrics) = &self.metrics else { return Ok(()) };
@@ -307,6 +435,13 @@ impl StaticFileProvider {
             jar
         };
 
+        // A new static file for this range must not be mistaken for a compacted one.
+        let receipt_topics = receipt_topics_path(jar.data_path());
+        if receipt_topics.exists() {
+            reth_fs_util::remove_file(receipt_topics)
+                .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+        }
+
         jar.delete().map_err(|e| ProviderError::NippyJar(e.to_string()))?;
 
         let mut segment_max_block = None;
@@ -318,6 +453,43 @@ impl StaticFileProvider {
         Ok(())
     }
 
+    /// Deletes the lowest jar of a segment in the directory of the provider and all files
+    /// associated with it, e.g. after archiving it. The highest jar is never deleted, since it's
+    /// the one the segment is appended to.
+    ///
+    /// Returns the fixed block range of the deleted jar, if any.
+    ///
+    /// CAUTION: destructive. Deletes files on disk.
+    pub fn delete_lowest_jar(
+        &self,
+        segment: StaticFileSegment,
+    ) -> ProviderResult<Option<SegmentRangeInclusive>> {
+        let (Some(fixed_block_range), Some(highest_block)) = (
+            self.get_lowest_local_static_file_range(segment),
+            self.get_highest_static_file_block(segment),
+        ) else {
+            return Ok(None)
+        };
+        if fixed_block_range.end() >= highest_block {
+            return Ok(None)
+        }
+
+        self.remove_cached_provider(segment, fixed_block_range.end());
+        let jar =
+            NippyJar::<SegmentHeader>::load(&self.path.join(segment.filename(&fixed_block_range)))
+                .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+        let receipt_topics = receipt_topics_path(jar.data_path());
+        if receipt_topics.exists() {
+            reth_fs_util::remove_file(receipt_topics)
+                .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+        }
+        jar.delete().map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+
+        self.initialize_index()?;
+
+        Ok(Some(fixed_block_range))
+    }
+
     /// Given a segment and block range it returns a cached
     /// [`StaticFileJarProvider`]. TODO(joshie): we should check the size and pop N if there's too
     /// many.
@@ -332,7 +504,7 @@ impl StaticFileProvider {
         let mut provider: StaticFileJarProvider<'_> = if let Some(jar) = self.map.get(&key) {
             jar.into()
         } else {
-            let path = self.path.join(segment.filename(fixed_block_range));
+            let path = self.jar_path(segment, fixed_block_range);
             let mut jar =
                 NippyJar::load(&path).map_err(|e| ProviderError::NippyJar(e.to_string()))?;
             if self.load_filters {
@@ -348,6 +520,20 @@ impl StaticFileProvider {
         Ok(provider)
     }
 
+    /// Returns the path of the static file of the segment and fixed block range, which is located
+    /// in a read-only directory if it's not in the directory of the provider.
+    fn jar_path(
+        &self,
+        segment: StaticFileSegment,
+        fixed_block_range: &SegmentRangeInclusive,
+    ) -> PathBuf {
+        let filename = segment.filename(fixed_block_range);
+        match self.read_only_static_files.read().get(&(fixed_block_range.end(), segment)) {
+            Some(dir) => dir.join(filename),
+            None => self.path.join(filename),
+        }
+    }
+
     /// Gets a static file segment's block range from the provider inner block
     /// index.
     fn get_segment_ranges_from_block(
@@ -355,6 +541,9 @@ impl StaticFileProvider {
         segment: StaticFileSegment,
         block: u64,
     ) -> Option<SegmentRangeInclusive> {
+        if self.get_lowest_static_file_block(segment).is_some_and(|min| min > block) {
+            return None
+        }
         self.static_files_max_block
             .read()
             .get(&segment)
@@ -381,7 +570,10 @@ impl StaticFileProvider {
                 // request tx is higher than highest static file tx
                 return None
             }
-            let tx_start = static_files_rev_iter.peek().map(|(tx_end, _)| *tx_end + 1).unwrap_or(0);
+            let tx_start = match static_files_rev_iter.peek() {
+                Some((tx_end, _)) => *tx_end + 1,
+                None => self.get_lowest_static_file_tx(segment).unwrap_or_default(),
+            };
             if tx_start <= tx {
                 return Some(find_fixed_range(block_range.end()))
             }
@@ -462,6 +654,7 @@ impl StaticFileProvider {
             None => {
                 tx_index.remove(&segment);
                 max_block.remove(&segment);
+                self.static_files_min.write().remove(&segment);
             }
         };
 
@@ -472,17 +665,55 @@ impl StaticFileProvider {
     pub fn initialize_index(&self) -> ProviderResult<()> {
         let mut max_block = self.static_files_max_block.write();
         let mut tx_index = self.static_files_tx_index.write();
+        let mut min = self.static_files_min.write();
+        let mut read_only_static_files = self.read_only_static_files.write();
 
         tx_index.clear();
+        min.clear();
+        read_only_static_files.clear();
 
-        for (segment, ranges) in
-            iter_static_files(&self.path).map_err(|e| ProviderError::NippyJar(e.to_string()))?
-        {
+        let mut static_files =
+            iter_static_files(&self.path).map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+
+        // Add the static files of the read-only directories below the lowest local static file.
+        // Segments without local static files are not extended, since they are appended to.
+        let lowest_local_blocks = static_files
+            .iter()
+            .filter_map(|(segment, ranges)| Some((*segment, ranges.first()?.0.start())))
+            .collect::<HashMap<_, _>>();
+        for dir in &self.read_only_dirs {
+            for (segment, ranges) in
+                iter_static_files(dir).map_err(|e| ProviderError::NippyJar(e.to_string()))?
+            {
+                let (Some(lowest_local_block), Some(segment_ranges)) =
+                    (lowest_local_blocks.get(&segment), static_files.get_mut(&segment))
+                else {
+                    continue
+                };
+                for (block_range, tx_range) in ranges {
+                    let key = (find_fixed_range(block_range.start()).end(), segment);
+                    if block_range.end() < *lowest_local_block &&
+                        !read_only_static_files.contains_key(&key)
+                    {
+                        read_only_static_files.insert(key, dir.clone());
+                        segment_ranges.push((block_range, tx_range));
+                    }
+                }
+                segment_ranges.sort_by_key(|(block_range, _)| block_range.end());
+            }
+        }
+
+        for (segment, ranges) in static_files {
             // Update last block for each segment
             if let Some((block_range, _)) = ranges.last() {
                 max_block.insert(segment, block_range.end());
             }
 
+            // Update first block and transaction for each segment
+            if let Some((block_range, tx_range)) = ranges.first() {
+                min.insert(segment, (block_range.start(), tx_range.map(|range| range.start())));
+            }
+
             // Update tx -> block_range index
             for (block_range, tx_range) in ranges {
                 if let Some(tx_range) = tx_range {
@@ -740,6 +971,33 @@ impl StaticFileProvider {
         self.static_files_max_block.read().get(&segment).copied()
     }
 
+    /// Gets the lowest static file block if it exists for a static file segment.
+    pub fn get_lowest_static_file_block(&self, segment: StaticFileSegment) -> Option<BlockNumber> {
+        self.static_files_min.read().get(&segment).map(|(block, _)| *block)
+    }
+
+    /// Gets the fixed block range of the lowest static file of a segment in the directory of the
+    /// provider, skipping the static files of the read-only directories.
+    pub fn get_lowest_local_static_file_range(
+        &self,
+        segment: StaticFileSegment,
+    ) -> Option<SegmentRangeInclusive> {
+        let highest_block = self.get_highest_static_file_block(segment)?;
+        let mut fixed_block_range = find_fixed_range(self.get_lowest_static_file_block(segment)?);
+        while !self.path.join(segment.filename(&fixed_block_range)).exists() {
+            if fixed_block_range.end() >= highest_block {
+                return None
+            }
+            fixed_block_range = find_fixed_range(fixed_block_range.end() + 1);
+        }
+        Some(fixed_block_range)
+    }
+
+    /// Gets the lowest static file transaction if it exists for a static file segment.
+    pub fn get_lowest_static_file_tx(&self, segment: StaticFileSegment) -> Option<TxNumber> {
+        self.static_files_min.read().get(&segment).and_then(|(_, tx)| *tx)
+    }
+
     /// Gets the highest static file transaction.
     pub fn get_highest_static_file_tx(&self, segment: StaticFileSegment) -> Option<TxNumber> {
         self.static_files_tx_index
@@ -765,8 +1023,9 @@ impl StaticFileProvider {
         func: impl Fn(StaticFileJarProvider<'_>) -> ProviderResult<Option<T>>,
     ) -> ProviderResult<Option<T>> {
         if let Some(highest_block) = self.get_highest_static_file_block(segment) {
+            let lowest_block = self.get_lowest_static_file_block(segment).unwrap_or_default();
             let mut range = find_fixed_range(highest_block);
-            while range.end() > 0 {
+            while range.end() > 0 && range.end() >= lowest_block {
                 if let Some(res) = func(self.get_or_create_jar_provider(segment, &range)?)? {
                     return Ok(Some(res))
                 }
@@ -1205,10 +1464,26 @@ impl ReceiptProvider for StaticFileProvider {
         &self,
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<Receipt>> {
+        let range = to_range(range);
+
+        #[cfg(all(target_os = "linux", feature = "io-uring"))]
+        if let Some(receipts) =
+            self.fetch_range_uring(StaticFileSegment::Receipts, range.clone(), |value, topics| {
+                match topics {
+                    Some(topics) => topics
+                        .decode_receipt(value)
+                        .map_err(|err| ProviderError::NippyJar(err.to_string())),
+                    None => Ok(<Receipt as reth_db_api::table::Decompress>::decompress(value)?),
+                }
+            })?
+        {
+            return Ok(receipts)
+        }
+
         self.fetch_range_with_predicate(
             StaticFileSegment::Receipts,
-            to_range(range),
-            |cursor, number| cursor.get_one::<ReceiptMask<Receipt>>(number.into()),
+            range,
+            |cursor, number| cursor.get_receipt(number.into()),
             |_| true,
         )
     }
@@ -1360,9 +1635,20 @@ impl TransactionsProvider for StaticFileProvider {
         &self,
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<TransactionSignedNoHash>> {
+        let range = to_range(range);
+
+        #[cfg(all(target_os = "linux", feature = "io-uring"))]
+        if let Some(transactions) =
+            self.fetch_range_uring(StaticFileSegment::Transactions, range.clone(), |value, _| {
+                Ok(<TransactionSignedNoHash as reth_db_api::table::Decompress>::decompress(value)?)
+            })?
+        {
+            return Ok(transactions)
+        }
+
         self.fetch_range_with_predicate(
             StaticFileSegment::Transactions,
-            to_range(range),
+            range,
             |cursor, number| {
                 cursor.get_one::<TransactionMask<TransactionSignedNoHash>>(number.into())
             },
@@ -9,8 +9,11 @@ pub use writer::{StaticFileProviderRW, StaticFileProviderRWRefMut};
 
 mod metrics;
 
+use reth_db::static_file::load_receipt_topics;
 use reth_nippy_jar::NippyJar;
-use reth_primitives::{static_file::SegmentHeader, StaticFileSegment};
+use reth_primitives::{
+    receipt_compaction::TopicDictionary, static_file::SegmentHeader, StaticFileSegment,
+};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use std::{ops::Deref, sync::Arc};
 
@@ -24,14 +27,24 @@ type LoadedJarRef<'a> = dashmap::mapref::one::Ref<'a, (u64, StaticFileSegment),
 pub struct LoadedJar {
     jar: NippyJar<SegmentHeader>,
     mmap_handle: Arc<reth_nippy_jar::DataReader>,
+    /// Topic dictionary if this is a compacted receipts static file.
+    receipt_topics: Option<Arc<TopicDictionary>>,
 }
 
 impl LoadedJar {
     fn new(jar: NippyJar<SegmentHeader>) -> ProviderResult<Self> {
+        let receipt_topics = if jar.user_header().segment().is_receipts() {
+            load_receipt_topics(jar.data_path())
+                .map_err(|e| ProviderError::NippyJar(e.to_string()))?
+                .map(Arc::new)
+        } else {
+            None
+        };
+
         match jar.open_data_reader() {
             Ok(data_reader) => {
                 let mmap_handle = Arc::new(data_reader);
-                Ok(Self { jar, mmap_handle })
+                Ok(Self { jar, mmap_handle, receipt_topics })
             }
             Err(e) => Err(ProviderError::NippyJar(e.to_string())),
         }
@@ -42,6 +55,11 @@ impl LoadedJar {
         self.mmap_handle.clone()
     }
 
+    /// Returns the topic dictionary if this is a compacted receipts static file.
+    fn receipt_topics(&self) -> Option<Arc<TopicDictionary>> {
+        self.receipt_topics.clone()
+    }
+
     const fn segment(&self) -> StaticFileSegment {
         self.jar.user_header().segment()
     }
@@ -5,12 +5,14 @@ use super::{
 };
 use dashmap::mapref::one::RefMut;
 use reth_codecs::Compact;
+use reth_db::static_file::{load_receipt_topics, receipt_topics_path};
 use reth_db_api::models::CompactU256;
 use reth_nippy_jar::{ConsistencyFailStrategy, NippyJar, NippyJarError, NippyJarWriter};
 use reth_primitives::{
+    receipt_compaction::TopicDictionary,
     static_file::{find_fixed_range, SegmentHeader, SegmentRangeInclusive},
-    BlockHash, BlockNumber, Header, Receipt, StaticFileSegment, TransactionSignedNoHash, TxNumber,
-    U256,
+    BlockHash, BlockNumber, Bytes, Header, Receipt, StaticFileSegment, TransactionSignedNoHash,
+    TxNumber, U256,
 };
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use std::{
@@ -36,6 +38,9 @@ pub struct StaticFileProviderRW {
     data_path: PathBuf,
     /// Reusable buffer for encoding appended data.
     buf: Vec<u8>,
+    /// Topic dictionary of the opened file if it is a compacted receipts static file. Appended
+    /// receipts are encoded with it.
+    receipt_topics: Option<TopicDictionary>,
     /// Metrics.
     metrics: Option<Arc<StaticFileProviderMetrics>>,
     /// On commit, does the instructed pruning: number of lines, and if it applies, the last block
@@ -51,11 +56,13 @@ impl StaticFileProviderRW {
         reader: Weak<StaticFileProviderInner>,
         metrics: Option<Arc<StaticFileProviderMetrics>>,
     ) -> ProviderResult<Self> {
-        let (writer, data_path) = Self::open(segment, block, reader.clone(), metrics.clone())?;
+        let (writer, data_path, receipt_topics) =
+            Self::open(segment, block, reader.clone(), metrics.clone())?;
         Ok(Self {
             writer,
             data_path,
             buf: Vec::with_capacity(100),
+            receipt_topics,
             reader,
             metrics,
             prune_on_commit: None,
@@ -67,7 +74,7 @@ impl StaticFileProviderRW {
         block: u64,
         reader: Weak<StaticFileProviderInner>,
         metrics: Option<Arc<StaticFileProviderMetrics>>,
-    ) -> ProviderResult<(NippyJarWriter<SegmentHeader>, PathBuf)> {
+    ) -> ProviderResult<(NippyJarWriter<SegmentHeader>, PathBuf, Option<TopicDictionary>)> {
         let start = Instant::now();
 
         let static_file_provider = Self::upgrade_provider_to_strong_reference(&reader);
@@ -97,8 +104,14 @@ impl StaticFileProviderRW {
             ConsistencyFailStrategy::Heal
         };
 
+        let receipt_topics = if segment.is_receipts() {
+            load_receipt_topics(&path).map_err(|e| ProviderError::NippyJar(e.to_string()))?
+        } else {
+            None
+        };
+
         let result = match NippyJarWriter::new(jar, access) {
-            Ok(writer) => Ok((writer, path)),
+            Ok(writer) => Ok((writer, path, receipt_topics)),
             Err(NippyJarError::FrozenJar) => {
                 // This static file has been frozen, so we should
                 Err(ProviderError::FinalizedStaticFile(segment, block))
@@ -283,10 +296,11 @@ impl StaticFileProviderRW {
                 self.commit()?;
 
                 // Opens the new static file
-                let (writer, data_path) =
+                let (writer, data_path, receipt_topics) =
                     Self::open(segment, last_block + 1, self.reader.clone(), self.metrics.clone())?;
                 self.writer = writer;
                 self.data_path = data_path;
+                self.receipt_topics = receipt_topics;
 
                 *self.writer.user_header_mut() =
                     SegmentHeader::new(find_fixed_range(last_block + 1), None, None, segment);
@@ -411,7 +425,7 @@ impl StaticFileProviderRW {
     /// file.
     fn delete_current_and_open_previous(&mut self) -> Result<(), ProviderError> {
         let current_path = self.data_path.clone();
-        let (previous_writer, data_path) = Self::open(
+        let (previous_writer, data_path, receipt_topics) = Self::open(
             self.user_header().segment(),
             self.writer.user_header().expected_block_start() - 1,
             self.reader.clone(),
@@ -419,6 +433,13 @@ impl StaticFileProviderRW {
         )?;
         self.writer = previous_writer;
         self.data_path = data_path;
+        self.receipt_topics = receipt_topics;
+
+        let current_receipt_topics = receipt_topics_path(&current_path);
+        if current_receipt_topics.exists() {
+            reth_fs_util::remove_file(current_receipt_topics)
+                .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+        }
         NippyJar::<SegmentHeader>::load(&current_path)
             .map_err(|e| ProviderError::NippyJar(e.to_string()))?
             .delete()
@@ -459,6 +480,24 @@ impl StaticFileProviderRW {
         Ok(self.writer.user_header().tx_end().expect("qed"))
     }
 
+    /// Appends receipt to tx number-based static file, encoding it with the topic dictionary if
+    /// the static file has been compacted.
+    ///
+    /// Returns the current [`TxNumber`] as seen in the static file.
+    fn append_receipt_with_tx_number(
+        &mut self,
+        tx_num: TxNumber,
+        receipt: Receipt,
+    ) -> ProviderResult<TxNumber> {
+        let Some(receipt_topics) = &self.receipt_topics else {
+            return self.append_with_tx_number(StaticFileSegment::Receipts, tx_num, receipt)
+        };
+
+        let mut encoded = Vec::with_capacity(128);
+        receipt_topics.encode_receipt(&receipt, &mut encoded);
+        self.append_with_tx_number(StaticFileSegment::Receipts, tx_num, Bytes::from(encoded))
+    }
+
     /// Appends header to static file.
     ///
     /// It **CALLS** `increment_block()` since the number of headers is equal to the number of
@@ -534,7 +573,7 @@ impl StaticFileProviderRW {
         let start = Instant::now();
         self.ensure_no_queued_prune()?;
 
-        let result = self.append_with_tx_number(StaticFileSegment::Receipts, tx_num, receipt)?;
+        let result = self.append_receipt_with_tx_number(tx_num, receipt)?;
 
         if let Some(metrics) = &self.metrics {
             metrics.record_segment_operation(
@@ -569,7 +608,7 @@ impl StaticFileProviderRW {
 
         for receipt_result in receipts_iter {
             let (tx_num, receipt) = receipt_result?;
-            tx_number = self.append_with_tx_number(StaticFileSegment::Receipts, tx_num, receipt)?;
+            tx_number = self.append_receipt_with_tx_number(tx_num, receipt)?;
             count += 1;
         }
 
@@ -1,19 +1,24 @@
 use crate::{
     traits::{BlockSource, ReceiptProvider},
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    ChainSpecProvider, ChangeSetReader, EvmEnvProvider, FullExecutionDataProvider, HeaderProvider,
-    ReceiptProviderIdExt, RequestsProvider, StateProvider, StateProviderBox, StateProviderFactory,
-    StateRootProvider, TransactionVariant, TransactionsProvider, WithdrawalsProvider,
+    AccountReader, BlockAccessListReader, BlockHashReader, BlockIdReader, BlockNumReader,
+    BlockReader, BlockReaderIdExt, ChainSpecProvider, ChangeSetReader, EvmEnvProvider,
+    FullExecutionDataProvider, HeaderAccumulatorReader, HeaderProvider, LegacyTxMetaReader,
+    ReceiptProviderIdExt, ReorgJournalReader, RequestsProvider, StateProvider, StateProviderBox,
+    StateProviderFactory, StateRootProvider, TransactionVariant, TransactionsProvider,
+    WithdrawalsProvider,
 };
 use parking_lot::Mutex;
 use reth_chainspec::{ChainInfo, ChainSpec};
-use reth_db_api::models::{AccountBeforeTx, StoredBlockBodyIndices};
+use reth_db_api::models::{
+    AccountBeforeTx, StoredBlockBodyIndices, StoredLegacyTxMeta, StoredReorg,
+};
 use reth_evm::ConfigureEvmEnv;
 use reth_primitives::{
-    keccak256, Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumber,
-    BlockWithSenders, Bytecode, Bytes, Header, Receipt, SealedBlock, SealedBlockWithSenders,
-    SealedHeader, StorageKey, StorageValue, TransactionMeta, TransactionSigned,
-    TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
+    keccak256, AccessList, Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId,
+    BlockNumber, BlockWithSenders, Bytecode, Bytes, Header, Receipt, SealedBlock,
+    SealedBlockWithSenders, SealedHeader, StorageEntry, StorageKey, StorageValue, TransactionMeta,
+    TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256,
+    U256,
 };
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use reth_trie::{updates::TrieUpdates, AccountProof};
@@ -570,6 +575,10 @@ impl StateProvider for MockEthProvider {
     fn proof(&self, address: Address, _keys: &[B256]) -> ProviderResult<AccountProof> {
         Ok(AccountProof::new(address))
     }
+
+    fn witness(&self, _targets: &HashMap<Address, Vec<B256>>) -> ProviderResult<Vec<Bytes>> {
+        Ok(Vec::new())
+    }
 }
 
 impl EvmEnvProvider for MockEthProvider {
@@ -703,4 +712,39 @@ impl ChangeSetReader for MockEthProvider {
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
         Ok(Vec::default())
     }
+
+    fn storage_block_changeset(
+        &self,
+        _block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(Address, StorageEntry)>> {
+        Ok(Vec::default())
+    }
+}
+
+impl LegacyTxMetaReader for MockEthProvider {
+    fn legacy_tx_meta(&self, _id: TxNumber) -> ProviderResult<Option<StoredLegacyTxMeta>> {
+        Ok(None)
+    }
+}
+
+impl BlockAccessListReader for MockEthProvider {
+    fn block_access_list(&self, _number: BlockNumber) -> ProviderResult<Option<AccessList>> {
+        Ok(None)
+    }
+}
+
+impl HeaderAccumulatorReader for MockEthProvider {
+    fn header_accumulator_epochs(&self) -> ProviderResult<Vec<B256>> {
+        Ok(Vec::default())
+    }
+}
+
+impl ReorgJournalReader for MockEthProvider {
+    fn reorgs(&self, _from: u64, _limit: usize) -> ProviderResult<Vec<(u64, StoredReorg)>> {
+        Ok(Vec::default())
+    }
+
+    fn last_reorg_id(&self) -> ProviderResult<Option<u64>> {
+        Ok(None)
+    }
 }
@@ -1,19 +1,22 @@
 use crate::{
     traits::{BlockSource, ReceiptProvider},
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    ChainSpecProvider, ChangeSetReader, EvmEnvProvider, HeaderProvider, PruneCheckpointReader,
-    ReceiptProviderIdExt, RequestsProvider, StageCheckpointReader, StateProvider, StateProviderBox,
-    StateProviderFactory, StateRootProvider, TransactionVariant, TransactionsProvider,
-    WithdrawalsProvider,
+    AccountReader, BlockAccessListReader, BlockHashReader, BlockIdReader, BlockNumReader,
+    BlockReader, BlockReaderIdExt, ChainSpecProvider, ChangeSetReader, EvmEnvProvider,
+    HeaderAccumulatorReader, HeaderProvider, LegacyTxMetaReader, PruneCheckpointReader,
+    ReceiptProviderIdExt, ReorgJournalReader, RequestsProvider, StageCheckpointReader,
+    StateProvider, StateProviderBox, StateProviderFactory, StateRootProvider, TransactionVariant,
+    TransactionsProvider, WithdrawalsProvider,
 };
 use reth_chainspec::{ChainInfo, ChainSpec, MAINNET};
-use reth_db_api::models::{AccountBeforeTx, StoredBlockBodyIndices};
+use reth_db_api::models::{
+    AccountBeforeTx, StoredBlockBodyIndices, StoredLegacyTxMeta, StoredReorg,
+};
 use reth_evm::ConfigureEvmEnv;
 use reth_primitives::{
-    Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumber, BlockWithSenders,
-    Bytecode, Header, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader, StorageKey,
-    StorageValue, TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber,
-    Withdrawal, Withdrawals, B256, U256,
+    AccessList, Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumber,
+    BlockWithSenders, Bytecode, Bytes, Header, Receipt, SealedBlock, SealedBlockWithSenders,
+    SealedHeader, StorageEntry, StorageKey, StorageValue, TransactionMeta, TransactionSigned,
+    TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
 };
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
@@ -24,6 +27,7 @@ use revm::{
     primitives::{BlockEnv, CfgEnvWithHandlerCfg},
 };
 use std::{
+    collections::HashMap,
     ops::{RangeBounds, RangeInclusive},
     sync::Arc,
 };
@@ -298,6 +302,41 @@ impl ChangeSetReader for NoopProvider {
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
         Ok(Vec::default())
     }
+
+    fn storage_block_changeset(
+        &self,
+        _block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(Address, StorageEntry)>> {
+        Ok(Vec::default())
+    }
+}
+
+impl LegacyTxMetaReader for NoopProvider {
+    fn legacy_tx_meta(&self, _id: TxNumber) -> ProviderResult<Option<StoredLegacyTxMeta>> {
+        Ok(None)
+    }
+}
+
+impl BlockAccessListReader for NoopProvider {
+    fn block_access_list(&self, _number: BlockNumber) -> ProviderResult<Option<AccessList>> {
+        Ok(None)
+    }
+}
+
+impl HeaderAccumulatorReader for NoopProvider {
+    fn header_accumulator_epochs(&self) -> ProviderResult<Vec<B256>> {
+        Ok(Vec::default())
+    }
+}
+
+impl ReorgJournalReader for NoopProvider {
+    fn reorgs(&self, _from: u64, _limit: usize) -> ProviderResult<Vec<(u64, StoredReorg)>> {
+        Ok(Vec::default())
+    }
+
+    fn last_reorg_id(&self) -> ProviderResult<Option<u64>> {
+        Ok(None)
+    }
 }
 
 impl StateRootProvider for NoopProvider {
@@ -329,6 +368,10 @@ impl StateProvider for NoopProvider {
     fn proof(&self, address: Address, _keys: &[B256]) -> ProviderResult<AccountProof> {
         Ok(AccountProof::new(address))
     }
+
+    fn witness(&self, _targets: &HashMap<Address, Vec<B256>>) -> ProviderResult<Vec<Bytes>> {
+        Ok(Vec::new())
+    }
 }
 
 impl EvmEnvProvider for NoopProvider {
@@ -0,0 +1,19 @@
+use reth_errors::ProviderResult;
+use reth_primitives::{AccessList, BlockNumber};
+
+/// Functionality to read the access lists of the blocks recorded during execution.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait BlockAccessListReader: Send + Sync {
+    /// Returns the access list of the canonical block, if it was recorded.
+    fn block_access_list(&self, number: BlockNumber) -> ProviderResult<Option<AccessList>>;
+}
+
+/// Functionality to store the access lists of the blocks recorded during execution.
+pub trait BlockAccessListWriter: Send + Sync {
+    /// Inserts the access list of the canonical block, replacing any previous one.
+    fn insert_block_access_list(
+        &self,
+        number: BlockNumber,
+        access_list: AccessList,
+    ) -> ProviderResult<()>;
+}
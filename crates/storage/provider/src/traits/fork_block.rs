@@ -0,0 +1,15 @@
+use reth_errors::ProviderResult;
+use reth_primitives::BlockNumber;
+
+/// Functionality to read the block of the chain the node is forked from.
+pub trait ForkBlockReader: Send + Sync {
+    /// Returns the block of the forked chain the cached fork state belongs to, if the node was
+    /// forked.
+    fn fork_block(&self) -> ProviderResult<Option<BlockNumber>>;
+}
+
+/// Functionality to save the block of the chain the node is forked from.
+pub trait ForkBlockWriter: Send + Sync {
+    /// Saves the block of the forked chain the cached fork state belongs to.
+    fn save_fork_block(&self, block: BlockNumber) -> ProviderResult<()>;
+}
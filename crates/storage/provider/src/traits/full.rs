@@ -1,9 +1,10 @@
 //! Helper provider traits to encapsulate all provider traits for simplicity.
 
 use crate::{
-    AccountReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
-    DatabaseProviderFactory, EvmEnvProvider, StageCheckpointReader, StateProviderFactory,
-    StaticFileProviderFactory,
+    AccountReader, BlockAccessListReader, BlockReaderIdExt, CanonStateSubscriptions,
+    ChainSpecProvider, ChangeSetReader, DatabaseProviderFactory, EvmEnvProvider,
+    HeaderAccumulatorReader, LegacyTxMetaReader, PruneCheckpointReader, ReorgJournalReader,
+    StageCheckpointReader, StateProviderFactory, StaticFileProviderFactory,
 };
 use reth_db_api::database::Database;
 
@@ -17,6 +18,11 @@ pub trait FullProvider<DB: Database>:
     + EvmEnvProvider
     + ChainSpecProvider
     + ChangeSetReader
+    + ReorgJournalReader
+    + HeaderAccumulatorReader
+    + LegacyTxMetaReader
+    + BlockAccessListReader
+    + PruneCheckpointReader
     + CanonStateSubscriptions
     + StageCheckpointReader
     + Clone
@@ -34,6 +40,12 @@ impl<T, DB: Database> FullProvider<DB> for T where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + ReorgJournalReader
+        + HeaderAccumulatorReader
+        + LegacyTxMetaReader
+        + BlockAccessListReader
+        + PruneCheckpointReader
+    + PruneCheckpointReader
         + CanonStateSubscriptions
         + StageCheckpointReader
         + Clone
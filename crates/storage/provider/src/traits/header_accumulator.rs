@@ -0,0 +1,11 @@
+use reth_errors::ProviderResult;
+use reth_primitives::B256;
+
+/// Functionality to read the pre-merge header accumulator.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait HeaderAccumulatorReader: Send + Sync {
+    /// Returns the roots of the complete epochs of the header accumulator, in epoch order.
+    ///
+    /// See [`reth_primitives::header_accumulator`] for the format of the accumulator.
+    fn header_accumulator_epochs(&self) -> ProviderResult<Vec<B256>>;
+}
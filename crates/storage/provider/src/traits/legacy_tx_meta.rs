@@ -0,0 +1,16 @@
+use reth_db_api::models::StoredLegacyTxMeta;
+use reth_errors::ProviderResult;
+use reth_primitives::TxNumber;
+
+/// Functionality to read the metadata of the transactions of the OP Mainnet legacy chain.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait LegacyTxMetaReader: Send + Sync {
+    /// Returns the legacy metadata of the transaction, if it was imported.
+    fn legacy_tx_meta(&self, id: TxNumber) -> ProviderResult<Option<StoredLegacyTxMeta>>;
+}
+
+/// Functionality to store the metadata of the transactions of the OP Mainnet legacy chain.
+pub trait LegacyTxMetaWriter: Send + Sync {
+    /// Inserts the legacy metadata of the transaction, replacing any previous one.
+    fn insert_legacy_tx_meta(&self, id: TxNumber, meta: StoredLegacyTxMeta) -> ProviderResult<()>;
+}
@@ -50,3 +50,27 @@ pub use tree_viewer::TreeViewer;
 
 mod finalized_block;
 pub use finalized_block::{FinalizedBlockReader, FinalizedBlockWriter};
+
+mod reorg_halt;
+pub use reorg_halt::{ReorgHaltReader, ReorgHaltWriter};
+
+mod reorg_journal;
+pub use reorg_journal::{ReorgJournalReader, ReorgJournalWriter};
+
+mod sync_history;
+pub use sync_history::{SyncHistoryReader, SyncHistoryWriter};
+
+mod header_accumulator;
+pub use header_accumulator::HeaderAccumulatorReader;
+
+mod legacy_tx_meta;
+pub use legacy_tx_meta::{LegacyTxMetaReader, LegacyTxMetaWriter};
+
+mod block_access_list;
+pub use block_access_list::{BlockAccessListReader, BlockAccessListWriter};
+
+mod fork_block;
+pub use fork_block::{ForkBlockReader, ForkBlockWriter};
+
+mod pending_unwind;
+pub use pending_unwind::{PendingUnwindReader, PendingUnwindWriter};
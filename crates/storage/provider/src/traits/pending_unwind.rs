@@ -0,0 +1,18 @@
+use reth_errors::ProviderResult;
+use reth_primitives::BlockNumber;
+
+/// Functionality to read whether an unwind of the datadir was interrupted.
+pub trait PendingUnwindReader: Send + Sync {
+    /// Returns the target block of the interrupted unwind, if any.
+    fn pending_unwind(&self) -> ProviderResult<Option<BlockNumber>>;
+}
+
+/// Functionality to persist the target of an unwind whose stages are unwound in separate
+/// transactions, and to clear it once all of them are.
+pub trait PendingUnwindWriter: Send + Sync {
+    /// Saves the target block of the unwind.
+    fn save_pending_unwind(&self, target: BlockNumber) -> ProviderResult<()>;
+
+    /// Clears the pending unwind, returning its target block, if any.
+    fn clear_pending_unwind(&self) -> ProviderResult<Option<BlockNumber>>;
+}
@@ -0,0 +1,19 @@
+use reth_errors::ProviderResult;
+use reth_primitives::BlockNumber;
+
+/// Functionality to read whether canonicalization is halted by a reorg deeper than the configured
+/// maximum.
+pub trait ReorgHaltReader: Send + Sync {
+    /// Returns the fork block of the reorg that halted canonicalization, if it's halted.
+    fn reorg_halt(&self) -> ProviderResult<Option<BlockNumber>>;
+}
+
+/// Functionality to halt canonicalization because of a reorg deeper than the configured maximum,
+/// and to resume it.
+pub trait ReorgHaltWriter: Send + Sync {
+    /// Saves the fork block of the reorg that halted canonicalization.
+    fn save_reorg_halt(&self, fork_block: BlockNumber) -> ProviderResult<()>;
+
+    /// Clears the halt, returning the fork block of the reorg that caused it, if any.
+    fn clear_reorg_halt(&self) -> ProviderResult<Option<BlockNumber>>;
+}
@@ -0,0 +1,19 @@
+use reth_db_api::models::StoredReorg;
+use reth_errors::ProviderResult;
+
+/// Functionality to read the journal of reorgs of the canonical chain.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait ReorgJournalReader: Send + Sync {
+    /// Returns up to `limit` reorgs with a sequence number of at least `from`, together with
+    /// their sequence number, in the order they happened.
+    fn reorgs(&self, from: u64, limit: usize) -> ProviderResult<Vec<(u64, StoredReorg)>>;
+
+    /// Returns the sequence number of the last reorg in the journal, if any.
+    fn last_reorg_id(&self) -> ProviderResult<Option<u64>>;
+}
+
+/// Functionality to append to the journal of reorgs of the canonical chain.
+pub trait ReorgJournalWriter: Send + Sync {
+    /// Appends the reorg to the journal, returning its sequence number.
+    fn append_reorg(&self, reorg: StoredReorg) -> ProviderResult<u64>;
+}
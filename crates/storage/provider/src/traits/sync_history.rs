@@ -0,0 +1,22 @@
+use reth_db_api::models::SyncSnapshot;
+use reth_errors::ProviderResult;
+
+/// Functionality to read the history of the sync progress.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait SyncHistoryReader: Send + Sync {
+    /// Returns the snapshots of the sync progress recorded at a unix timestamp in seconds of at
+    /// least `from`, together with their timestamp, in the order they were recorded.
+    fn sync_history(&self, from: u64) -> ProviderResult<Vec<(u64, SyncSnapshot)>>;
+}
+
+/// Functionality to record the history of the sync progress.
+pub trait SyncHistoryWriter: Send + Sync {
+    /// Records the snapshot at the unix timestamp in seconds, removing the oldest snapshots so
+    /// that at most `max_snapshots` are kept.
+    fn record_sync_snapshot(
+        &self,
+        timestamp: u64,
+        snapshot: SyncSnapshot,
+        max_snapshots: usize,
+    ) -> ProviderResult<()>;
+}
@@ -0,0 +1,151 @@
+use crate::BlockReader;
+use reth_primitives::{BlockNumber, Receipt, SealedBlockWithSenders};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use std::ops::RangeInclusive;
+
+/// Default number of blocks read at once by a [`BlockRangeIter`].
+pub const DEFAULT_BLOCK_RANGE_BATCH_SIZE: u64 = 1_000;
+
+/// A canonical block with its senders and receipts, as returned by [`BlockRangeIter`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockWithReceipts {
+    /// The block with the senders of its transactions.
+    pub block: SealedBlockWithSenders,
+    /// The receipts of the transactions of the block.
+    ///
+    /// `None` if receipts were not requested, or if some of them are pruned.
+    pub receipts: Option<Vec<Receipt>>,
+}
+
+/// Iterator over the canonical blocks of a range, with their senders and receipts.
+///
+/// Blocks are read in batches: the headers, transactions and senders of a batch are read with a
+/// single [`BlockReader::sealed_block_with_senders_range`] call and the receipts of all of its
+/// transactions with a single [`ReceiptProvider::receipts_by_tx_range`] call, instead of one
+/// lookup per block.
+///
+/// The iterator ends at the first block of the range that is not available. It's fused after an
+/// error.
+///
+/// [`ReceiptProvider::receipts_by_tx_range`]: crate::ReceiptProvider::receipts_by_tx_range
+#[derive(Debug)]
+pub struct BlockRangeIter<'a, P> {
+    provider: &'a P,
+    /// The blocks that are not read yet.
+    remaining: RangeInclusive<BlockNumber>,
+    batch_size: u64,
+    with_receipts: bool,
+    batch: std::vec::IntoIter<BlockWithReceipts>,
+}
+
+impl<'a, P: BlockReader> BlockRangeIter<'a, P> {
+    /// Creates a new iterator over the blocks of `range`, reading
+    /// [`DEFAULT_BLOCK_RANGE_BATCH_SIZE`] blocks at once.
+    pub fn new(provider: &'a P, range: RangeInclusive<BlockNumber>) -> Self {
+        Self {
+            provider,
+            remaining: range,
+            batch_size: DEFAULT_BLOCK_RANGE_BATCH_SIZE,
+            with_receipts: true,
+            batch: Vec::new().into_iter(),
+        }
+    }
+
+    /// Sets the number of blocks read at once.
+    pub fn with_batch_size(mut self, batch_size: u64) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Skips reading the receipts of the blocks.
+    pub const fn without_receipts(mut self) -> Self {
+        self.with_receipts = false;
+        self
+    }
+
+    /// Reads the next batch of blocks.
+    fn next_batch(&mut self) -> ProviderResult<Vec<BlockWithReceipts>> {
+        let (start, range_end) = (*self.remaining.start(), *self.remaining.end());
+        let end = start.saturating_add(self.batch_size - 1).min(range_end);
+        self.remaining = match end.checked_add(1) {
+            Some(next) => next..=range_end,
+            None => empty_range(),
+        };
+
+        let blocks = self.provider.sealed_block_with_senders_range(start..=end)?;
+        if (blocks.len() as u64) < end - start + 1 {
+            // the range extends past the available blocks
+            self.remaining = empty_range();
+        }
+        if blocks.is_empty() {
+            return Ok(Vec::new())
+        }
+
+        if !self.with_receipts {
+            return Ok(blocks
+                .into_iter()
+                .map(|block| BlockWithReceipts { block, receipts: None })
+                .collect())
+        }
+
+        let first_tx = self
+            .provider
+            .block_body_indices(start)?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(start))?
+            .first_tx_num();
+        let tx_count = blocks.iter().map(|block| block.body.len() as u64).sum::<u64>();
+        let mut receipts = self.provider.receipts_by_tx_range(first_tx..first_tx + tx_count)?;
+
+        if receipts.len() as u64 != tx_count {
+            // some receipts of the batch are pruned, so they can't be split by position
+            return blocks
+                .into_iter()
+                .map(|block| {
+                    let receipts = self
+                        .provider
+                        .receipts_by_block(block.number.into())?
+                        .filter(|receipts| receipts.len() == block.body.len());
+                    Ok(BlockWithReceipts { block, receipts })
+                })
+                .collect()
+        }
+
+        let mut batch = Vec::with_capacity(blocks.len());
+        for block in blocks.into_iter().rev() {
+            let block_receipts = receipts.split_off(receipts.len() - block.body.len());
+            batch.push(BlockWithReceipts { block, receipts: Some(block_receipts) });
+        }
+        batch.reverse();
+        Ok(batch)
+    }
+}
+
+impl<P: BlockReader> Iterator for BlockRangeIter<'_, P> {
+    type Item = ProviderResult<BlockWithReceipts>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(block) = self.batch.next() {
+                return Some(Ok(block))
+            }
+            if self.remaining.is_empty() {
+                return None
+            }
+
+            match self.next_batch() {
+                Ok(batch) => self.batch = batch.into_iter(),
+                Err(err) => {
+                    self.remaining = empty_range();
+                    return Some(Err(err))
+                }
+            }
+        }
+    }
+}
+
+impl<P: BlockReader> std::iter::FusedIterator for BlockRangeIter<'_, P> {}
+
+#[allow(clippy::reversed_empty_ranges)]
+const fn empty_range() -> RangeInclusive<BlockNumber> {
+    1..=0
+}
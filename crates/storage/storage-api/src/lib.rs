@@ -16,6 +16,9 @@ pub use account::*;
 mod block;
 pub use block::*;
 
+mod block_range;
+pub use block_range::*;
+
 mod block_id;
 pub use block_id::*;
 
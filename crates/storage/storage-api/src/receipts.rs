@@ -1,4 +1,5 @@
 use crate::BlockIdReader;
+use reth_db_api::models::StoredLegacyL1Fee;
 use reth_primitives::{BlockHashOrNumber, BlockId, BlockNumberOrTag, Receipt, TxHash, TxNumber};
 use reth_storage_errors::provider::ProviderResult;
 use std::ops::RangeBounds;
@@ -26,6 +27,14 @@ pub trait ReceiptProvider: Send + Sync {
         &self,
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<Receipt>>;
+
+    /// Get the L1 fee of a transaction of the OP Mainnet legacy chain by transaction number, as
+    /// recorded in its receipt.
+    ///
+    /// Returns `None` if no fee was imported for the transaction.
+    fn legacy_l1_fee(&self, _id: TxNumber) -> ProviderResult<Option<StoredLegacyL1Fee>> {
+        Ok(None)
+    }
 }
 
 /// Trait extension for `ReceiptProvider`, for types that implement `BlockId` conversion.
@@ -2,11 +2,13 @@ use super::{AccountReader, BlockHashReader, BlockIdReader, StateRootProvider};
 use auto_impl::auto_impl;
 use reth_execution_types::ExecutionOutcome;
 use reth_primitives::{
-    Address, BlockHash, BlockId, BlockNumHash, BlockNumber, BlockNumberOrTag, Bytecode, StorageKey,
-    StorageValue, B256, KECCAK_EMPTY, U256,
+    storage_layout::{StorageLayout, StorageVariable},
+    Address, BlockHash, BlockId, BlockNumHash, BlockNumber, BlockNumberOrTag, Bytecode, Bytes,
+    StorageKey, StorageValue, B256, KECCAK_EMPTY, U256,
 };
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use reth_trie::AccountProof;
+use std::collections::HashMap;
 
 /// Type alias of boxed [`StateProvider`].
 pub type StateProviderBox = Box<dyn StateProvider>;
@@ -27,6 +29,12 @@ pub trait StateProvider: BlockHashReader + AccountReader + StateRootProvider + S
     /// Get account and storage proofs.
     fn proof(&self, address: Address, keys: &[B256]) -> ProviderResult<AccountProof>;
 
+    /// Get the trie nodes that prove the given accounts and, for each account, the given storage
+    /// slots.
+    ///
+    /// The nodes are deduplicated, see `Proof::multiproof`.
+    fn witness(&self, targets: &HashMap<Address, Vec<B256>>) -> ProviderResult<Vec<Bytes>>;
+
     /// Get account code by its address.
     ///
     /// Returns `None` if the account doesn't exist or account is not a contract
@@ -73,6 +81,19 @@ pub trait StateProvider: BlockHashReader + AccountReader + StateRootProvider + S
             None => Ok(None),
         }
     }
+
+    /// Get the storage variable at the given path of a contract, decoded with the contract's
+    /// storage layout.
+    ///
+    /// See [`StorageLayout::read_variable`] for the paths.
+    fn storage_variable(
+        &self,
+        addr: Address,
+        layout: &StorageLayout,
+        path: &str,
+    ) -> ProviderResult<StorageVariable> {
+        layout.read_variable(path, |slot| Ok(self.storage(addr, slot)?.unwrap_or_default()))
+    }
 }
 
 /// Light wrapper that returns `StateProvider` implementations that correspond to the given
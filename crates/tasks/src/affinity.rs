@@ -0,0 +1,328 @@
+//! Pinning threads to a set of CPUs.
+//!
+//! On machines with several NUMA nodes, threads that are moved between nodes by the scheduler keep
+//! accessing memory allocated on the node they started on. Pinning a worker pool to the cores of a
+//! single node keeps its memory accesses local.
+
+use crate::TaskSpawner;
+use futures_util::future::BoxFuture;
+use std::{
+    fmt::{Display, Formatter},
+    future::Future,
+    io,
+    str::FromStr,
+};
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+/// Maximum number of CPUs a [`CpuSet`] can hold.
+pub const MAX_CPUS: usize = 1024;
+
+const WORDS: usize = MAX_CPUS / 64;
+
+/// Error returned when parsing a [`CpuSet`].
+#[derive(Debug, thiserror::Error)]
+pub enum CpuSetParseError {
+    /// An entry of the list is neither a CPU, a range of CPUs nor a NUMA node.
+    #[error("invalid CPU list entry {0:?}")]
+    InvalidEntry(String),
+    /// A CPU is larger than [`MAX_CPUS`].
+    #[error("CPU {0} is out of range, at most {MAX_CPUS} CPUs are supported")]
+    OutOfRange(usize),
+    /// The list does not contain any CPU.
+    #[error("empty CPU list")]
+    Empty,
+    /// The CPUs of a NUMA node could not be read.
+    #[error("failed to read the CPUs of NUMA node {0}: {1}")]
+    Node(usize, io::Error),
+}
+
+/// A set of CPUs threads can be pinned to.
+///
+/// Parsed from a comma separated list of CPUs, CPU ranges and NUMA nodes, e.g. `0-7,16` or
+/// `node:1`. The CPUs of a NUMA node are read from
+/// `/sys/devices/system/node/node<N>/cpulist`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CpuSet {
+    words: [u64; WORDS],
+}
+
+impl CpuSet {
+    /// Returns the CPUs the calling thread is allowed to run on.
+    #[cfg(target_os = "linux")]
+    pub fn current() -> io::Result<Self> {
+        // SAFETY: `cpu_set_t` is a plain bitmask, all bits unset is a valid value.
+        let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+        // SAFETY: `set` is a valid `cpu_set_t` of the given size.
+        let result =
+            unsafe { libc::sched_getaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &mut set) };
+        if result != 0 {
+            return Err(io::Error::last_os_error())
+        }
+
+        let mut cpus = Self { words: [0; WORDS] };
+        for cpu in 0..MAX_CPUS {
+            // SAFETY: `cpu` is smaller than `MAX_CPUS`, which is the size of `cpu_set_t`.
+            if unsafe { libc::CPU_ISSET(cpu, &set) } {
+                cpus.words[cpu / 64] |= 1 << (cpu % 64);
+            }
+        }
+        Ok(cpus)
+    }
+
+    /// Returns the CPUs the calling thread is allowed to run on.
+    ///
+    /// Thread affinity is only supported on Linux.
+    #[cfg(not(target_os = "linux"))]
+    pub fn current() -> io::Result<Self> {
+        Err(unsupported())
+    }
+
+    /// Returns `true` if `cpu` is part of the set.
+    pub const fn contains(&self, cpu: usize) -> bool {
+        cpu < MAX_CPUS && self.words[cpu / 64] & (1 << (cpu % 64)) != 0
+    }
+
+    /// Returns the number of CPUs in the set.
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /// Returns `true` if the set does not contain any CPU.
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|word| *word == 0)
+    }
+
+    /// Returns the CPUs of the set in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..MAX_CPUS).filter(|cpu| self.contains(*cpu))
+    }
+
+    /// Pins the calling thread to the CPUs of the set.
+    ///
+    /// Threads spawned by the calling thread afterwards inherit the affinity.
+    #[cfg(target_os = "linux")]
+    pub fn pin_current_thread(&self) -> io::Result<()> {
+        // SAFETY: `cpu_set_t` is a plain bitmask, all bits unset is a valid value.
+        let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+        for cpu in self.iter() {
+            // SAFETY: `cpu` is smaller than `MAX_CPUS`, which is the size of `cpu_set_t`.
+            unsafe { libc::CPU_SET(cpu, &mut set) };
+        }
+
+        // SAFETY: `set` is a valid `cpu_set_t` of the given size.
+        let result =
+            unsafe { libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) };
+        if result != 0 {
+            return Err(io::Error::last_os_error())
+        }
+        Ok(())
+    }
+
+    /// Pins the calling thread to the CPUs of the set.
+    ///
+    /// Thread affinity is only supported on Linux.
+    #[cfg(not(target_os = "linux"))]
+    pub fn pin_current_thread(&self) -> io::Result<()> {
+        Err(unsupported())
+    }
+
+    fn insert(&mut self, cpu: usize) -> Result<(), CpuSetParseError> {
+        if cpu >= MAX_CPUS {
+            return Err(CpuSetParseError::OutOfRange(cpu))
+        }
+        self.words[cpu / 64] |= 1 << (cpu % 64);
+        Ok(())
+    }
+
+    /// Adds the CPUs of a list like `0-7,16` to the set.
+    fn insert_list(&mut self, list: &str) -> Result<(), CpuSetParseError> {
+        let parse = |cpu: &str| {
+            cpu.trim().parse::<usize>().map_err(|_| CpuSetParseError::InvalidEntry(cpu.to_string()))
+        };
+
+        for entry in list.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+            match entry.split_once('-') {
+                Some((start, end)) => {
+                    let (start, end) = (parse(start)?, parse(end)?);
+                    if start > end {
+                        return Err(CpuSetParseError::InvalidEntry(entry.to_string()))
+                    }
+                    for cpu in start..=end {
+                        self.insert(cpu)?;
+                    }
+                }
+                None => self.insert(parse(entry)?)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn unsupported() -> io::Error {
+    io::Error::new(io::ErrorKind::Unsupported, "thread affinity is only supported on Linux")
+}
+
+impl FromStr for CpuSet {
+    type Err = CpuSetParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut set = Self { words: [0; WORDS] };
+        for entry in s.split(',').map(str::trim) {
+            match entry.strip_prefix("node:") {
+                Some(node) => {
+                    let node = node
+                        .parse::<usize>()
+                        .map_err(|_| CpuSetParseError::InvalidEntry(entry.to_string()))?;
+                    let cpus = std::fs::read_to_string(format!(
+                        "/sys/devices/system/node/node{node}/cpulist"
+                    ))
+                    .map_err(|err| CpuSetParseError::Node(node, err))?;
+                    set.insert_list(&cpus)?;
+                }
+                None if entry.is_empty() => {}
+                None => set.insert_list(entry)?,
+            }
+        }
+
+        if set.is_empty() {
+            return Err(CpuSetParseError::Empty)
+        }
+        Ok(set)
+    }
+}
+
+impl Display for CpuSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut cpus = self.iter().peekable();
+        let mut first = true;
+        while let Some(start) = cpus.next() {
+            let mut end = start;
+            while cpus.peek() == Some(&(end + 1)) {
+                end = cpus.next().expect("peeked");
+            }
+
+            if !first {
+                write!(f, ",")?;
+            }
+            first = false;
+
+            if start == end {
+                write!(f, "{start}")?;
+            } else {
+                write!(f, "{start}-{end}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Runs `fut` with the polling thread pinned to `cpus`, if any.
+///
+/// The previous affinity of the thread is restored once `fut` completes, so this is meant for
+/// futures that are driven to completion on a single thread, like blocking tasks.
+pub async fn run_pinned<F: Future>(cpus: Option<CpuSet>, fut: F) -> F::Output {
+    let Some(cpus) = cpus else { return fut.await };
+
+    let previous = match CpuSet::current().and_then(|previous| {
+        cpus.pin_current_thread()?;
+        Ok(previous)
+    }) {
+        Ok(previous) => Some(previous),
+        Err(err) => {
+            warn!(target: "tasks", %err, %cpus, "Failed to pin thread");
+            None
+        }
+    };
+
+    let output = fut.await;
+
+    if let Some(previous) = previous {
+        if let Err(err) = previous.pin_current_thread() {
+            warn!(target: "tasks", %err, cpus = %previous, "Failed to restore thread affinity");
+        }
+    }
+    output
+}
+
+/// A [`TaskSpawner`] that pins the threads running its blocking tasks to a [`CpuSet`].
+///
+/// Non-blocking tasks are spawned onto the runtime as usual, since they may move between the
+/// worker threads of the runtime.
+#[derive(Debug, Clone)]
+pub struct PinnedTaskSpawner<S> {
+    inner: S,
+    cpus: CpuSet,
+}
+
+impl<S> PinnedTaskSpawner<S> {
+    /// Creates a new spawner, pinning the blocking tasks spawned by `inner` to `cpus`.
+    pub const fn new(inner: S, cpus: CpuSet) -> Self {
+        Self { inner, cpus }
+    }
+}
+
+impl<S: TaskSpawner + Clone> TaskSpawner for PinnedTaskSpawner<S> {
+    fn spawn(&self, fut: BoxFuture<'static, ()>) -> JoinHandle<()> {
+        self.inner.spawn(fut)
+    }
+
+    fn spawn_critical(&self, name: &'static str, fut: BoxFuture<'static, ()>) -> JoinHandle<()> {
+        self.inner.spawn_critical(name, fut)
+    }
+
+    fn spawn_blocking(&self, fut: BoxFuture<'static, ()>) -> JoinHandle<()> {
+        self.inner.spawn_blocking(Box::pin(run_pinned(Some(self.cpus), fut)))
+    }
+
+    fn spawn_critical_blocking(
+        &self,
+        name: &'static str,
+        fut: BoxFuture<'static, ()>,
+    ) -> JoinHandle<()> {
+        self.inner.spawn_critical_blocking(name, Box::pin(run_pinned(Some(self.cpus), fut)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_cpu_list() {
+        let set: CpuSet = "0-3, 8,10-11".parse().unwrap();
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0, 1, 2, 3, 8, 10, 11]);
+        assert_eq!(set.len(), 7);
+        assert_eq!(set.to_string(), "0-3,8,10-11");
+
+        let set: CpuSet = "1023".parse().unwrap();
+        assert!(set.contains(1023));
+        assert_eq!(set.to_string(), "1023");
+    }
+
+    #[test]
+    fn parse_invalid_cpu_list() {
+        assert!(matches!("".parse::<CpuSet>(), Err(CpuSetParseError::Empty)));
+        assert!(matches!("4-2".parse::<CpuSet>(), Err(CpuSetParseError::InvalidEntry(_))));
+        assert!(matches!("a".parse::<CpuSet>(), Err(CpuSetParseError::InvalidEntry(_))));
+        assert!(matches!("node:x".parse::<CpuSet>(), Err(CpuSetParseError::InvalidEntry(_))));
+        assert!(matches!("1024".parse::<CpuSet>(), Err(CpuSetParseError::OutOfRange(1024))));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn pins_blocking_tasks() {
+        let spawner =
+            PinnedTaskSpawner::new(crate::TokioTaskExecutor::default(), "0".parse().unwrap());
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        spawner
+            .spawn_blocking(Box::pin(async move {
+                let pinned = CpuSet::current().unwrap();
+                let _ = tx.send(pinned);
+            }))
+            .await
+            .unwrap();
+        assert_eq!(rx.await.unwrap().iter().collect::<Vec<_>>(), vec![0]);
+    }
+}
@@ -39,6 +39,7 @@ use tokio::{
 use tracing::{debug, error};
 use tracing_futures::Instrument;
 
+pub mod affinity;
 pub mod metrics;
 pub mod shutdown;
 
@@ -0,0 +1,124 @@
+use std::{
+    collections::VecDeque,
+    io::{self, Write},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+};
+use tracing_subscriber::fmt::MakeWriter;
+
+/// The default number of log lines kept by a [`LogBuffer`].
+const DEFAULT_LOG_BUFFER_CAPACITY: usize = 1000;
+
+/// A buffer of the most recent log lines.
+///
+/// Used as the writer of the stdout layer when the output of the terminal is taken over, e.g. by a
+/// dashboard, which then shows the lines itself. Once the terminal is released again,
+/// [`LogBuffer::print_to_stdout`] restores the regular output.
+#[derive(Debug, Clone)]
+pub struct LogBuffer {
+    lines: Arc<Mutex<VecDeque<String>>>,
+    capacity: usize,
+    stdout: Arc<AtomicBool>,
+}
+
+impl LogBuffer {
+    /// Creates a new buffer that keeps up to `capacity` lines.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            lines: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity,
+            stdout: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Returns the last `count` lines, oldest first.
+    pub fn last_lines(&self, count: usize) -> Vec<String> {
+        let lines = self.lines.lock().unwrap_or_else(PoisonError::into_inner);
+        lines.iter().skip(lines.len().saturating_sub(count)).cloned().collect()
+    }
+
+    /// Writes all following log lines to stdout instead of keeping them in the buffer.
+    pub fn print_to_stdout(&self) {
+        self.stdout.store(true, Ordering::Relaxed);
+    }
+
+    /// Appends complete lines of the given output to the buffer, dropping the oldest lines if the
+    /// buffer is full.
+    fn push(&self, output: &[u8]) {
+        let output = String::from_utf8_lossy(output);
+        let mut lines = self.lines.lock().unwrap_or_else(PoisonError::into_inner);
+        for line in output.lines().filter(|line| !line.trim().is_empty()) {
+            if lines.len() == self.capacity {
+                lines.pop_front();
+            }
+            lines.push_back(line.to_string());
+        }
+    }
+}
+
+impl Default for LogBuffer {
+    fn default() -> Self {
+        Self::new(DEFAULT_LOG_BUFFER_CAPACITY)
+    }
+}
+
+impl<'a> MakeWriter<'a> for LogBuffer {
+    type Writer = LogBufferWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        LogBufferWriter { buffer: self.clone(), output: Vec::new() }
+    }
+}
+
+/// Writer of a single log event into a [`LogBuffer`].
+///
+/// The event is added to the buffer when the writer is dropped.
+#[derive(Debug)]
+pub struct LogBufferWriter {
+    buffer: LogBuffer,
+    output: Vec<u8>,
+}
+
+impl Write for LogBufferWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.output.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for LogBufferWriter {
+    fn drop(&mut self) {
+        if self.buffer.stdout.load(Ordering::Relaxed) {
+            let _ = io::stdout().write_all(&self.output);
+        } else {
+            self.buffer.push(&self.output);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_last_lines() {
+        let buffer = LogBuffer::new(3);
+        for i in 0..5 {
+            let mut writer = buffer.make_writer();
+            write!(writer, "line {i}\n\n").unwrap();
+        }
+
+        assert_eq!(buffer.last_lines(2), ["line 3", "line 4"]);
+        assert_eq!(buffer.last_lines(10), ["line 2", "line 3", "line 4"]);
+
+        buffer.print_to_stdout();
+        write!(buffer.make_writer(), "line 5").unwrap();
+        assert_eq!(buffer.last_lines(1), ["line 4"]);
+    }
+}
@@ -1,8 +1,18 @@
-use std::path::{Path, PathBuf};
+use std::{
+    fmt,
+    fmt::Display,
+    path::{Path, PathBuf},
+};
 
+use clap::ValueEnum;
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{trace::SdkTracerProvider, Resource};
 use rolling_file::{RollingConditionBasic, RollingFileAppender};
 use tracing_appender::non_blocking::WorkerGuard;
-use tracing_subscriber::{filter::Directive, EnvFilter, Layer, Registry};
+use tracing_subscriber::{
+    filter::Directive, fmt::writer::BoxMakeWriter, EnvFilter, Layer, Registry,
+};
 
 use crate::formatter::LogFormat;
 
@@ -17,6 +27,9 @@ pub(crate) type BoxedLayer<S> = Box<dyn Layer<S> + Send + Sync>;
 
 const RETH_LOG_FILE_NAME: &str = "reth.log";
 
+/// The service name the spans exported over OTLP are reported under.
+const OTLP_SERVICE_NAME: &str = "reth";
+
 /// Default [directives](Directive) for [`EnvFilter`] which disables high-frequency debug logs from
 /// `hyper`, `trust-dns`, `jsonrpsee-server`, and `discv5`.
 const DEFAULT_ENV_FILTER_DIRECTIVES: [&str; 5] = [
@@ -60,6 +73,32 @@ impl Layers {
         Ok(())
     }
 
+    /// Adds a layer that exports spans to an OpenTelemetry collector over OTLP/HTTP.
+    ///
+    /// Spans are exported in batches from a background thread.
+    ///
+    /// # Arguments
+    /// * `endpoint` - The OTLP/HTTP traces endpoint of the collector.
+    /// * `filter` - A string containing additional filter directives for this layer.
+    ///
+    /// # Returns
+    /// An `eyre::Result<()>` indicating the success or failure of the operation.
+    pub(crate) fn otlp(&mut self, endpoint: &str, filter: &str) -> eyre::Result<()> {
+        let otlp_filter = build_env_filter(None, filter)?;
+        let exporter = opentelemetry_otlp::SpanExporter::builder()
+            .with_http()
+            .with_endpoint(endpoint)
+            .build()?;
+        let provider = SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_resource(Resource::builder().with_service_name(OTLP_SERVICE_NAME).build())
+            .build();
+        let tracer = provider.tracer(OTLP_SERVICE_NAME);
+        let layer = tracing_opentelemetry::layer().with_tracer(tracer).with_filter(otlp_filter);
+        self.inner.push(layer.boxed());
+        Ok(())
+    }
+
     /// Adds a stdout layer with specified formatting and filtering.
     ///
     /// # Type Parameters
@@ -70,6 +109,7 @@ impl Layers {
     /// * `directive` - Directive for the default logging level.
     /// * `filter` - Additional filter directives as a string.
     /// * `color` - Optional color configuration for the log messages.
+    /// * `writer` - Optional writer that replaces stdout, e.g. a [`LogBuffer`](crate::LogBuffer).
     ///
     /// # Returns
     /// An `eyre::Result<()>` indicating the success or failure of the operation.
@@ -79,9 +119,10 @@ impl Layers {
         default_directive: Directive,
         filters: &str,
         color: Option<String>,
+        writer: Option<BoxMakeWriter>,
     ) -> eyre::Result<()> {
         let filter = build_env_filter(Some(default_directive), filters)?;
-        let layer = format.apply(filter, color, None);
+        let layer = format.apply(filter, color, writer);
         self.inner.push(layer.boxed());
         Ok(())
     }
@@ -103,12 +144,34 @@ impl Layers {
     ) -> eyre::Result<FileWorkerGuard> {
         let (writer, guard) = file_info.create_log_writer();
         let file_filter = build_env_filter(None, filter)?;
-        let layer = format.apply(file_filter, None, Some(writer));
+        let layer = format.apply(file_filter, None, Some(BoxMakeWriter::new(writer)));
         self.inner.push(layer);
         Ok(guard)
     }
 }
 
+/// The interval at which log files are rotated, in addition to the rotation by size.
+#[derive(Debug, Copy, Clone, Default, ValueEnum, Eq, PartialEq)]
+pub enum LogRotation {
+    /// Log files are only rotated by size.
+    #[default]
+    Never,
+    /// Log files are rotated at the start of every hour.
+    Hourly,
+    /// Log files are rotated at the start of every day.
+    Daily,
+}
+
+impl Display for LogRotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Never => write!(f, "never"),
+            Self::Hourly => write!(f, "hourly"),
+            Self::Daily => write!(f, "daily"),
+        }
+    }
+}
+
 /// Holds configuration information for file logging.
 ///
 /// Contains details about the log file's path, name, size, and rotation strategy.
@@ -118,12 +181,25 @@ pub struct FileInfo {
     file_name: String,
     max_size_bytes: u64,
     max_files: usize,
+    rotation: LogRotation,
 }
 
 impl FileInfo {
     /// Creates a new `FileInfo` instance.
     pub fn new(dir: PathBuf, max_size_bytes: u64, max_files: usize) -> Self {
-        Self { dir, file_name: RETH_LOG_FILE_NAME.to_string(), max_size_bytes, max_files }
+        Self {
+            dir,
+            file_name: RETH_LOG_FILE_NAME.to_string(),
+            max_size_bytes,
+            max_files,
+            rotation: LogRotation::Never,
+        }
+    }
+
+    /// Sets the interval at which the log file is rotated, in addition to the rotation by size.
+    pub const fn with_rotation(mut self, rotation: LogRotation) -> Self {
+        self.rotation = rotation;
+        self
     }
 
     /// Creates the log directory if it doesn't exist.
@@ -144,13 +220,15 @@ impl FileInfo {
     /// A tuple containing the non-blocking writer and its associated worker guard.
     fn create_log_writer(&self) -> (tracing_appender::non_blocking::NonBlocking, WorkerGuard) {
         let log_dir = self.create_log_dir();
+        let condition = RollingConditionBasic::new().max_size(self.max_size_bytes);
+        let condition = match self.rotation {
+            LogRotation::Never => condition,
+            LogRotation::Hourly => condition.hourly(),
+            LogRotation::Daily => condition.daily(),
+        };
         let (writer, guard) = tracing_appender::non_blocking(
-            RollingFileAppender::new(
-                log_dir.join(&self.file_name),
-                RollingConditionBasic::new().max_size(self.max_size_bytes),
-                self.max_files,
-            )
-            .expect("Could not initialize file logging"),
+            RollingFileAppender::new(log_dir.join(&self.file_name), condition, self.max_files)
+                .expect("Could not initialize file logging"),
         );
         (writer, guard)
     }
@@ -1,7 +1,7 @@
 //!  The `tracing` module provides functionalities for setting up and configuring logging.
 //!
 //!  It includes structures and functions to create and manage various logging layers: stdout,
-//!  file, or journald. The module's primary entry point is the `Tracer` struct, which can be
+//!  file, journald, or span export over OTLP. The module's primary entry point is the `Tracer` struct, which can be
 //!  configured to use different logging formats and destinations. If no layer is specified, it will
 //!  default to stdout.
 //!
@@ -48,11 +48,13 @@ pub use tracing;
 pub use tracing_subscriber;
 
 // Re-export LogFormat
+pub use buffer::{LogBuffer, LogBufferWriter};
 pub use formatter::LogFormat;
-pub use layers::{FileInfo, FileWorkerGuard};
+pub use layers::{FileInfo, FileWorkerGuard, LogRotation};
 
 pub use test_tracer::TestTracer;
 
+mod buffer;
 mod formatter;
 mod layers;
 mod test_tracer;
@@ -60,16 +62,22 @@ mod test_tracer;
 use crate::layers::Layers;
 use tracing::level_filters::LevelFilter;
 use tracing_appender::non_blocking::WorkerGuard;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use tracing_subscriber::{
+    fmt::writer::BoxMakeWriter, layer::SubscriberExt, util::SubscriberInitExt,
+};
 
 ///  Tracer for application logging.
 ///
 ///  Manages the configuration and initialization of logging layers,
-/// including standard output, optional journald, and optional file logging.
+/// including standard output, optional journald, optional file logging and optional span
+/// export over OTLP.
 #[derive(Debug, Clone)]
 pub struct RethTracer {
     stdout: LayerInfo,
+    stdout_buffer: Option<LogBuffer>,
+    recent_logs: Option<LogBuffer>,
     journald: Option<String>,
+    otlp: Option<(String, String)>,
     file: Option<(LayerInfo, FileInfo)>,
 }
 
@@ -79,7 +87,14 @@ impl RethTracer {
     ///  Initializes with default stdout layer configuration.
     ///  Journald and file layers are not set by default.
     pub fn new() -> Self {
-        Self { stdout: LayerInfo::default(), journald: None, file: None }
+        Self {
+            stdout: LayerInfo::default(),
+            stdout_buffer: None,
+            recent_logs: None,
+            journald: None,
+            otlp: None,
+            file: None,
+        }
     }
 
     ///  Sets a custom configuration for the stdout layer.
@@ -91,6 +106,29 @@ impl RethTracer {
         self
     }
 
+    ///  Writes the logs of the stdout layer into the given buffer instead of stdout.
+    ///
+    ///  The lines are written without colors.
+    ///
+    ///  # Arguments
+    ///  * `buffer` - The `LogBuffer` to write the stdout logs into.
+    pub fn with_stdout_buffer(mut self, buffer: LogBuffer) -> Self {
+        self.stdout_buffer = Some(buffer);
+        self
+    }
+
+    ///  Also keeps the logs of the stdout layer in the given buffer, e.g. to include the most
+    ///  recent lines in a crash report.
+    ///
+    ///  The lines are written in terminal format without colors.
+    ///
+    ///  # Arguments
+    ///  * `buffer` - The `LogBuffer` to keep the recent logs in.
+    pub fn with_recent_logs(mut self, buffer: LogBuffer) -> Self {
+        self.recent_logs = Some(buffer);
+        self
+    }
+
     ///  Sets the journald layer filter.
     ///
     ///  # Arguments
@@ -100,6 +138,16 @@ impl RethTracer {
         self
     }
 
+    ///  Exports spans to an OpenTelemetry collector.
+    ///
+    ///  # Arguments
+    ///  * `endpoint` - The OTLP/HTTP traces endpoint of the collector.
+    ///  * `filter` - The `filter` to use for the exported spans.
+    pub fn with_otlp(mut self, endpoint: String, filter: String) -> Self {
+        self.otlp = Some((endpoint, filter));
+        self
+    }
+
     ///  Sets the file layer configuration and associated file info.
     ///
     ///  # Arguments
@@ -193,17 +241,36 @@ impl Tracer for RethTracer {
     fn init(self) -> eyre::Result<Option<WorkerGuard>> {
         let mut layers = Layers::new();
 
+        if let Some(buffer) = self.recent_logs {
+            layers.stdout(
+                LogFormat::Terminal,
+                self.stdout.default_directive.parse()?,
+                &self.stdout.filters,
+                None,
+                Some(BoxMakeWriter::new(buffer)),
+            )?;
+        }
+
+        let (color, writer) = match self.stdout_buffer {
+            Some(buffer) => (None, Some(BoxMakeWriter::new(buffer))),
+            None => (self.stdout.color, None),
+        };
         layers.stdout(
             self.stdout.format,
             self.stdout.default_directive.parse()?,
             &self.stdout.filters,
-            self.stdout.color,
+            color,
+            writer,
         )?;
 
         if let Some(config) = self.journald {
             layers.journald(&config)?;
         }
 
+        if let Some((endpoint, filter)) = self.otlp {
+            layers.otlp(&endpoint, &filter)?;
+        }
+
         let file_guard = if let Some((config, file_info)) = self.file {
             Some(layers.file(config.format, &config.filters, file_info)?)
         } else {
@@ -3,7 +3,10 @@
 pub use disk::{DiskFileBlobStore, DiskFileBlobStoreConfig, OpenDiskFileBlobStore};
 pub use mem::InMemoryBlobStore;
 pub use noop::NoopBlobStore;
-use reth_primitives::{BlobTransactionSidecar, B256};
+use reth_primitives::{
+    eip4844::{Blob, Bytes48},
+    BlobTransactionSidecar, B256,
+};
 use std::{
     fmt,
     sync::atomic::{AtomicUsize, Ordering},
@@ -71,6 +74,15 @@ pub trait BlobStore: fmt::Debug + Send + Sync + 'static {
     fn blobs_len(&self) -> usize;
 }
 
+/// A blob and its KZG proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlobAndProof {
+    /// The blob.
+    pub blob: Blob,
+    /// The KZG proof of the blob.
+    pub proof: Bytes48,
+}
+
 /// Error variants that can occur when interacting with a blob store.
 #[derive(Debug, thiserror::Error)]
 pub enum BlobStoreError {
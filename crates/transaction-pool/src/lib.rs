@@ -27,6 +27,12 @@
 //! The order in which they're returned are determined by a `Priority` value returned by the
 //! `TransactionOrdering` type this pool is configured with.
 //!
+//! The default `CoinbaseTipOrdering` prefers transactions with the highest effective tip,
+//! `FirstComeOrdering` yields transactions in the order they were submitted, and
+//! `ClosureOrdering` allows using a custom priority function. Since the payload builder requests
+//! the best transactions from the pending pool, this determines the order in which transactions
+//! are included in built payloads.
+//!
 //! This is only used in the _pending_ pool to yield the best transactions for block production. The
 //! _base pool_ is ordered by base fee, and the _queued pool_ by current distance.
 //!
@@ -153,21 +159,26 @@
 use crate::{identifier::TransactionId, pool::PoolInner};
 use aquamarine as _;
 use reth_eth_wire_types::HandleMempoolData;
-use reth_primitives::{Address, BlobTransactionSidecar, PooledTransactionsElement, TxHash, U256};
+use reth_primitives::{
+    Address, BlobTransactionSidecar, IntoRecoveredTransaction, PooledTransactionsElement, TxHash,
+    B256, U256,
+};
 use reth_provider::StateProviderFactory;
 use std::{collections::HashSet, sync::Arc};
 use tokio::sync::mpsc::Receiver;
 use tracing::{instrument, trace};
 
 pub use crate::{
-    blobstore::{BlobStore, BlobStoreError},
+    blobstore::{BlobAndProof, BlobStore, BlobStoreError},
     config::{
         LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
         REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
         TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
     },
     error::PoolResult,
-    ordering::{CoinbaseTipOrdering, Priority, TransactionOrdering},
+    ordering::{
+        ClosureOrdering, CoinbaseTipOrdering, FirstComeOrdering, Priority, TransactionOrdering,
+    },
     pool::{
         blob_tx_priority, fee_delta, state::SubPool, AllTransactionsEvents, FullTransactionEvent,
         TransactionEvent, TransactionEvents,
@@ -522,6 +533,45 @@ where
     ) -> Result<Vec<BlobTransactionSidecar>, BlobStoreError> {
         self.pool.blob_store().get_exact(tx_hashes)
     }
+    fn get_blobs_for_versioned_hashes(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProof>>, BlobStoreError> {
+        let mut blobs = vec![None; versioned_hashes.len()];
+        let mut missing = versioned_hashes.len();
+
+        let AllPoolTransactions { pending, queued } = self.all_transactions();
+        for tx in pending.into_iter().chain(queued).filter(|tx| tx.is_eip4844()) {
+            if missing == 0 {
+                break
+            }
+            let Some(tx_versioned_hashes) =
+                tx.transaction.to_recovered_transaction().blob_versioned_hashes()
+            else {
+                continue
+            };
+            if !tx_versioned_hashes.iter().any(|hash| versioned_hashes.contains(hash)) {
+                continue
+            }
+            let Some(sidecar) = self.pool.blob_store().get(*tx.hash())? else { continue };
+
+            for (blob_index, versioned_hash) in tx_versioned_hashes.iter().enumerate() {
+                for (index, _) in
+                    versioned_hashes.iter().enumerate().filter(|(_, hash)| *hash == versioned_hash)
+                {
+                    if blobs[index].is_none() {
+                        blobs[index] = Some(BlobAndProof {
+                            blob: sidecar.blobs[blob_index],
+                            proof: sidecar.proofs[blob_index],
+                        });
+                        missing -= 1;
+                    }
+                }
+            }
+        }
+
+        Ok(blobs)
+    }
 }
 
 impl<V, T, S> TransactionPoolExt for Pool<V, T, S>
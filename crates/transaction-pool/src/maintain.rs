@@ -7,6 +7,7 @@ use crate::{
     traits::{CanonicalStateUpdate, ChangedAccount, TransactionPool, TransactionPoolExt},
     BlockInfo,
 };
+use alloy_rlp::{RlpDecodable, RlpEncodable};
 use futures_util::{
     future::{BoxFuture, Fuse, FusedFuture},
     FutureExt, Stream, StreamExt,
@@ -16,7 +17,7 @@ use reth_fs_util::FsPathError;
 use reth_primitives::{
     Address, BlockHash, BlockNumber, BlockNumberOrTag, FromRecoveredPooledTransaction,
     IntoRecoveredTransaction, PooledTransactionsElementEcRecovered, TransactionSigned,
-    TryFromRecoveredTransaction,
+    TryFromRecoveredTransaction, TxHash,
 };
 use reth_provider::{
     BlockReaderIdExt, CanonStateNotification, ChainSpecProvider, ProviderError,
@@ -25,9 +26,10 @@ use reth_provider::{
 use reth_tasks::TaskSpawner;
 use std::{
     borrow::Borrow,
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     hash::{Hash, Hasher},
     path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 use tokio::sync::oneshot;
 use tracing::{debug, error, info, trace, warn};
@@ -44,28 +46,74 @@ pub struct MaintainPoolConfig {
     ///
     /// Default: 100
     pub max_reload_accounts: usize,
+    /// Number of blocks the blobs of mined transactions are kept in the blob store after the
+    /// block was finalized.
+    ///
+    /// Default: 0
+    pub blob_retention_blocks: u64,
 }
 
 impl Default for MaintainPoolConfig {
     fn default() -> Self {
-        Self { max_update_depth: 64, max_reload_accounts: 100 }
+        Self { max_update_depth: 64, max_reload_accounts: 100, blob_retention_blocks: 0 }
     }
 }
 
+/// Default interval at which local transactions are written to the backup file.
+pub const DEFAULT_REJOURNAL_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Default time after which local transactions from the backup file are no longer reinserted.
+pub const DEFAULT_LOCAL_TRANSACTIONS_LIFETIME: Duration = Duration::from_secs(3 * 60 * 60);
+
 /// Settings for local transaction backup task
 #[derive(Debug, Clone, Default)]
 pub struct LocalTransactionBackupConfig {
     /// Path to transactions backup file
     pub transactions_path: Option<PathBuf>,
+    /// Interval at which local transactions are written to the backup file, in addition to on
+    /// shutdown.
+    ///
+    /// If `None`, the backup is only written on shutdown.
+    pub rejournal_interval: Option<Duration>,
+    /// Maximum time since a transaction was submitted, after which it is no longer reinserted
+    /// from the backup file.
+    ///
+    /// If `None`, transactions are reinserted regardless of their age.
+    pub lifetime: Option<Duration>,
 }
 
 impl LocalTransactionBackupConfig {
     /// Receive path to transactions backup and return initialized config
     pub const fn with_local_txs_backup(transactions_path: PathBuf) -> Self {
-        Self { transactions_path: Some(transactions_path) }
+        Self {
+            transactions_path: Some(transactions_path),
+            rejournal_interval: None,
+            lifetime: None,
+        }
+    }
+
+    /// Sets the interval at which local transactions are written to the backup file.
+    pub const fn with_rejournal_interval(mut self, rejournal_interval: Duration) -> Self {
+        self.rejournal_interval = Some(rejournal_interval);
+        self
+    }
+
+    /// Sets the maximum age of transactions that are reinserted from the backup file.
+    pub const fn with_lifetime(mut self, lifetime: Duration) -> Self {
+        self.lifetime = Some(lifetime);
+        self
     }
 }
 
+/// A local transaction in the backup file.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+struct LocalTransactionEntry {
+    /// Unix timestamp in seconds of when the transaction was first submitted to the node.
+    submitted_at: u64,
+    /// The signed transaction.
+    transaction: TransactionSigned,
+}
+
 /// Returns a spawnable future for maintaining the state of the transaction pool.
 pub fn maintain_transaction_pool_future<Client, P, St, Tasks>(
     client: Client,
@@ -102,7 +150,8 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
     Tasks: TaskSpawner + 'static,
 {
     let metrics = MaintainPoolMetrics::default();
-    let MaintainPoolConfig { max_update_depth, max_reload_accounts, .. } = config;
+    let MaintainPoolConfig { max_update_depth, max_reload_accounts, blob_retention_blocks } =
+        config;
     // ensure the pool points to latest state
     if let Ok(Some(latest)) = client.header_by_number_or_tag(BlockNumberOrTag::Latest) {
         let latest = latest.seal_slow();
@@ -188,7 +237,9 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
         if let Some(finalized) =
             last_finalized_block.update(client.finalized_block_number().ok().flatten())
         {
-            match blob_store_tracker.on_finalized_block(finalized) {
+            // blobs are kept for the retention window after their block was finalized
+            let prunable = finalized.saturating_sub(blob_retention_blocks);
+            match blob_store_tracker.on_finalized_block(prunable) {
                 BlobStoreUpdates::None => {}
                 BlobStoreUpdates::Finalized(blobs) => {
                     metrics.inc_deleted_tracked_blobs(blobs.len());
@@ -565,32 +616,71 @@ fn changed_accounts_iter(
         .map(|(address, acc)| ChangedAccount { address, nonce: acc.nonce, balance: acc.balance })
 }
 
+/// Returns the current unix timestamp in seconds.
+fn unix_timestamp() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
 /// Loads transactions from a file, decodes them from the RLP format, and inserts them
 /// into the transaction pool on node boot up.
 /// The file is removed after the transactions have been successfully processed.
+///
+/// Transactions submitted longer than `lifetime` ago are dropped. Returns the submission times of
+/// the reinserted transactions.
 async fn load_and_reinsert_transactions<P>(
     pool: P,
     file_path: &Path,
-) -> Result<(), TransactionsBackupError>
+    lifetime: Option<Duration>,
+) -> Result<HashMap<TxHash, u64>, TransactionsBackupError>
 where
     P: TransactionPool,
 {
     if !file_path.exists() {
-        return Ok(())
+        return Ok(HashMap::new())
     }
 
     debug!(target: "txpool", txs_file =?file_path, "Check local persistent storage for saved transactions");
     let data = reth_fs_util::read(file_path)?;
 
     if data.is_empty() {
-        return Ok(())
+        return Ok(HashMap::new())
     }
 
-    let txs_signed: Vec<TransactionSigned> = alloy_rlp::Decodable::decode(&mut data.as_slice())?;
+    let now = unix_timestamp();
+    let entries: Vec<LocalTransactionEntry> =
+        match alloy_rlp::Decodable::decode(&mut data.as_slice()) {
+            Ok(entries) => entries,
+            Err(_) => {
+                // backups written before submission times were recorded only contain the
+                // transactions
+                let txs_signed: Vec<TransactionSigned> =
+                    alloy_rlp::Decodable::decode(&mut data.as_slice())?;
+                txs_signed
+                    .into_iter()
+                    .map(|transaction| LocalTransactionEntry { submitted_at: now, transaction })
+                    .collect()
+            }
+        };
+
+    let num_entries = entries.len();
+    let entries = entries
+        .into_iter()
+        .filter(|entry| {
+            lifetime.map_or(true, |lifetime| {
+                now.saturating_sub(entry.submitted_at) <= lifetime.as_secs()
+            })
+        })
+        .collect::<Vec<_>>();
+    let num_expired = num_entries - entries.len();
 
-    let pool_transactions = txs_signed
+    let mut submitted_at = HashMap::with_capacity(entries.len());
+    let pool_transactions = entries
         .into_iter()
-        .filter_map(|tx| tx.try_ecrecovered())
+        .filter_map(|entry| {
+            let tx = entry.transaction.try_ecrecovered()?;
+            submitted_at.insert(tx.hash(), entry.submitted_at);
+            Some(tx)
+        })
         .filter_map(|tx| {
             // Filter out errors
             <P as TransactionPool>::Transaction::try_from_recovered_transaction(tx).ok()
@@ -599,33 +689,55 @@ where
 
     let outcome = pool.add_transactions(crate::TransactionOrigin::Local, pool_transactions).await;
 
-    info!(target: "txpool", txs_file =?file_path, num_txs=%outcome.len(), "Successfully reinserted local transactions from file");
+    info!(target: "txpool", txs_file =?file_path, num_txs=%outcome.len(), num_expired, "Successfully reinserted local transactions from file");
     reth_fs_util::remove_file(file_path)?;
-    Ok(())
+    Ok(submitted_at)
 }
 
-fn save_local_txs_backup<P>(pool: P, file_path: &Path)
+/// Writes the local transactions of the pool to the backup file.
+///
+/// The submission time of transactions that were reinserted from a previous backup is taken from
+/// `submitted_at`, so that they still expire after their lifetime.
+fn save_local_txs_backup<P>(pool: P, file_path: &Path, submitted_at: &HashMap<TxHash, u64>)
 where
     P: TransactionPool,
 {
     let local_transactions = pool.get_local_transactions();
     if local_transactions.is_empty() {
         trace!(target: "txpool", "no local transactions to save");
+        if file_path.exists() {
+            // the transactions of a previous backup are no longer in the pool
+            if let Err(err) = reth_fs_util::remove_file(file_path) {
+                warn!(target: "txpool", %err, txs_file=?file_path, "Failed to remove local transactions file");
+            }
+        }
         return
     }
 
+    let now = unix_timestamp();
     let local_transactions = local_transactions
         .into_iter()
-        .map(|tx| tx.to_recovered_transaction().into_signed())
+        .map(|tx| LocalTransactionEntry {
+            submitted_at: submitted_at
+                .get(tx.hash())
+                .copied()
+                .unwrap_or_else(|| now.saturating_sub(tx.timestamp.elapsed().as_secs())),
+            transaction: tx.to_recovered_transaction().into_signed(),
+        })
         .collect::<Vec<_>>();
 
     let num_txs = local_transactions.len();
     let mut buf = Vec::new();
     alloy_rlp::encode_list(&local_transactions, &mut buf);
     info!(target: "txpool", txs_file =?file_path, num_txs=%num_txs, "Saving current local transactions");
-    let parent_dir = file_path.parent().map(std::fs::create_dir_all).transpose();
-
-    match parent_dir.map(|_| reth_fs_util::write(file_path, buf)) {
+    let parent_dir = file_path.parent().map(reth_fs_util::create_dir_all).transpose();
+
+    // write to a temporary file first, so that a crash while writing doesn't corrupt the backup
+    let tmp_path = file_path.with_extension("tmp");
+    match parent_dir
+        .and_then(|_| reth_fs_util::write(&tmp_path, buf))
+        .and_then(|_| reth_fs_util::rename(&tmp_path, file_path))
+    {
         Ok(_) => {
             info!(target: "txpool", txs_file=?file_path, "Wrote local transactions to file");
         }
@@ -649,7 +761,8 @@ pub enum TransactionsBackupError {
     Pool(#[from] PoolError),
 }
 
-/// Task which manages saving local transactions to the persistent file in case of shutdown.
+/// Task which manages saving local transactions to the persistent file in case of shutdown, and
+/// periodically if a [`LocalTransactionBackupConfig::rejournal_interval`] is configured.
 /// Reloads the transactions from the file on the boot up and inserts them into the pool.
 pub async fn backup_local_transactions_task<P>(
     shutdown: reth_tasks::shutdown::GracefulShutdown,
@@ -663,14 +776,36 @@ pub async fn backup_local_transactions_task<P>(
         return
     };
 
-    if let Err(err) = load_and_reinsert_transactions(pool.clone(), &transactions_path).await {
-        error!(target: "txpool", "{}", err)
-    }
+    let submitted_at =
+        match load_and_reinsert_transactions(pool.clone(), &transactions_path, config.lifetime)
+            .await
+        {
+            Ok(submitted_at) => submitted_at,
+            Err(err) => {
+                error!(target: "txpool", "{}", err);
+                HashMap::new()
+            }
+        };
 
-    let graceful_guard = shutdown.await;
+    let graceful_guard = if let Some(rejournal_interval) = config.rejournal_interval {
+        let mut shutdown = std::pin::pin!(shutdown);
+        let mut interval = tokio::time::interval(rejournal_interval);
+        // the first tick completes immediately
+        interval.tick().await;
+        loop {
+            tokio::select! {
+                guard = &mut shutdown => break guard,
+                _ = interval.tick() => {
+                    save_local_txs_backup(pool.clone(), &transactions_path, &submitted_at);
+                }
+            }
+        }
+    } else {
+        shutdown.await
+    };
 
     // write transactions to disk
-    save_local_txs_backup(pool, &transactions_path);
+    save_local_txs_backup(pool, &transactions_path, &submitted_at);
 
     drop(graceful_guard)
 }
@@ -742,9 +877,51 @@ mod tests {
 
         let data = fs::read(transactions_path).unwrap();
 
-        let txs: Vec<TransactionSigned> =
+        let txs: Vec<LocalTransactionEntry> =
             alloy_rlp::Decodable::decode(&mut data.as_slice()).unwrap();
         assert_eq!(txs.len(), 1);
+        assert!(txs[0].submitted_at > 0);
+
+        temp_dir.close().unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_reinsert_skips_expired_transactions() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let transactions_path = temp_dir.path().join(FILENAME).with_extension(EXTENSION);
+        let tx_bytes = hex!("02f87201830655c2808505ef61f08482565f94388c818ca8b9251b393131c08a736a67ccb192978801049e39c4b5b1f580c001a01764ace353514e8abdfb92446de356b260e3c1225b73fc4c8876a6258d12a129a04f02294aa61ca7676061cd99f29275491218b4754b46a0248e5e42bc5091f507");
+        let tx = PooledTransactionsElement::decode_enveloped(&mut &tx_bytes[..]).unwrap();
+        let transaction = tx.into_transaction();
+        let provider = MockEthProvider::default();
+        let sender = hex!("1f9090aaE28b8a3dCeaDf281B0F12828e676c326").into();
+        provider.add_account(sender, ExtendedAccount::new(42, U256::MAX));
+        let blob_store = InMemoryBlobStore::default();
+        let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+            .build(provider, blob_store.clone());
+        let txpool =
+            Pool::new(validator, CoinbaseTipOrdering::default(), blob_store, Default::default());
+
+        let mut buf = Vec::new();
+        let expired = LocalTransactionEntry { submitted_at: 1, transaction: transaction.clone() };
+        alloy_rlp::encode_list(&[expired], &mut buf);
+        fs::write(&transactions_path, &buf).unwrap();
+
+        let lifetime = Some(DEFAULT_LOCAL_TRANSACTIONS_LIFETIME);
+        load_and_reinsert_transactions(txpool.clone(), &transactions_path, lifetime).await.unwrap();
+        assert!(txpool.get_local_transactions().is_empty());
+
+        // backups without submission times are reinserted
+        let mut buf = Vec::new();
+        alloy_rlp::encode_list(&[transaction], &mut buf);
+        fs::write(&transactions_path, &buf).unwrap();
+
+        let submitted_at =
+            load_and_reinsert_transactions(txpool.clone(), &transactions_path, lifetime)
+                .await
+                .unwrap();
+        assert_eq!(txpool.get_local_transactions().len(), 1);
+        assert_eq!(submitted_at.len(), 1);
+        assert!(!transactions_path.exists());
 
         temp_dir.close().unwrap();
     }
@@ -79,3 +79,126 @@ impl<T> Clone for CoinbaseTipOrdering<T> {
         Self::default()
     }
 }
+
+/// First-come-first-served ordering.
+///
+/// All transactions have the same priority, so the pending pool yields them in the order they
+/// were submitted to the pool.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct FirstComeOrdering<T>(PhantomData<T>);
+
+impl<T> TransactionOrdering for FirstComeOrdering<T>
+where
+    T: PoolTransaction + 'static,
+{
+    type PriorityValue = U256;
+    type Transaction = T;
+
+    fn priority(
+        &self,
+        _transaction: &Self::Transaction,
+        _base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        Priority::Value(U256::ZERO)
+    }
+}
+
+impl<T> Default for FirstComeOrdering<T> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<T> Clone for FirstComeOrdering<T> {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+/// Ordering determined by a custom closure.
+///
+/// The closure is called with the transaction and the current base fee and returns the priority
+/// of the transaction. Transactions with the same priority are yielded in the order they were
+/// submitted to the pool.
+pub struct ClosureOrdering<T, F> {
+    priority: F,
+    _marker: PhantomData<T>,
+}
+
+impl<T, F> ClosureOrdering<T, F> {
+    /// Creates a new ordering that uses the given closure to determine the priority.
+    pub const fn new(priority: F) -> Self {
+        Self { priority, _marker: PhantomData }
+    }
+}
+
+impl<T, F> TransactionOrdering for ClosureOrdering<T, F>
+where
+    T: PoolTransaction + 'static,
+    F: Fn(&T, u64) -> Priority<U256> + Send + Sync + 'static,
+{
+    type PriorityValue = U256;
+    type Transaction = T;
+
+    fn priority(
+        &self,
+        transaction: &Self::Transaction,
+        base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        (self.priority)(transaction, base_fee)
+    }
+}
+
+impl<T, F: Clone> Clone for ClosureOrdering<T, F> {
+    fn clone(&self) -> Self {
+        Self::new(self.priority.clone())
+    }
+}
+
+impl<T, F> fmt::Debug for ClosureOrdering<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClosureOrdering").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        pool::pending::PendingPool,
+        test_utils::{MockTransaction, MockTransactionFactory},
+    };
+
+    #[test]
+    fn first_come_ordering_yields_in_submission_order() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = PendingPool::new(FirstComeOrdering::<MockTransaction>::default());
+
+        let low = f.validated_arc(MockTransaction::eip1559().with_priority_fee(1));
+        let high = f.validated_arc(MockTransaction::eip1559().with_priority_fee(100));
+        pool.add_transaction(low.clone(), 0);
+        pool.add_transaction(high.clone(), 0);
+
+        let best = pool.best().map(|tx| *tx.hash()).collect::<Vec<_>>();
+        assert_eq!(best, vec![*low.hash(), *high.hash()]);
+    }
+
+    #[test]
+    fn closure_ordering_uses_closure_priority() {
+        let mut f = MockTransactionFactory::default();
+        // prefer transactions with the lowest gas limit
+        let ordering = ClosureOrdering::new(|tx: &MockTransaction, _base_fee: u64| {
+            Priority::Value(U256::MAX - U256::from(tx.gas_limit()))
+        });
+        let mut pool = PendingPool::new(ordering);
+
+        let large = f.validated_arc(MockTransaction::eip1559().with_gas_limit(100_000));
+        let small = f.validated_arc(MockTransaction::eip1559().with_gas_limit(21_000));
+        pool.add_transaction(large.clone(), 0);
+        pool.add_transaction(small.clone(), 0);
+
+        let best = pool.best().map(|tx| *tx.hash()).collect::<Vec<_>>();
+        assert_eq!(best, vec![*small.hash(), *large.hash()]);
+    }
+}
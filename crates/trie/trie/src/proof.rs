@@ -1,7 +1,7 @@
 use crate::{
     hashed_cursor::{HashedCursorFactory, HashedStorageCursor},
     node_iter::{TrieElement, TrieNodeIter},
-    prefix_set::PrefixSetMut,
+    prefix_set::{PrefixSet, PrefixSetMut, TriePrefixSets},
     trie_cursor::{DatabaseAccountTrieCursor, DatabaseStorageTrieCursor},
     walker::TrieWalker,
     HashBuilder, Nibbles,
@@ -10,8 +10,10 @@ use alloy_rlp::{BufMut, Encodable};
 use reth_db::tables;
 use reth_db_api::transaction::DbTx;
 use reth_execution_errors::{StateRootError, StorageRootError};
-use reth_primitives::{constants::EMPTY_ROOT_HASH, keccak256, Address, B256};
+use reth_primitives::{constants::EMPTY_ROOT_HASH, keccak256, Address, Bytes, B256};
 use reth_trie_common::{proof::ProofRetainer, AccountProof, StorageProof, TrieAccount};
+use std::collections::{BTreeSet, HashMap};
+
 /// A struct for generating merkle proofs.
 ///
 /// Proof generator adds the target address and slots to the prefix set, enables the proof retainer
@@ -23,12 +25,28 @@ pub struct Proof<'a, TX, H> {
     tx: &'a TX,
     /// The factory for hashed cursors.
     hashed_cursor_factory: H,
+    /// The prefixes of the trie nodes that changed in the hashed state of the cursor factory and
+    /// must be recomputed.
+    prefix_sets: TriePrefixSets,
 }
 
 impl<'a, TX> Proof<'a, TX, &'a TX> {
     /// Create a new [Proof] instance.
-    pub const fn new(tx: &'a TX) -> Self {
-        Self { tx, hashed_cursor_factory: tx }
+    pub fn new(tx: &'a TX) -> Self {
+        Self { tx, hashed_cursor_factory: tx, prefix_sets: TriePrefixSets::default() }
+    }
+}
+
+impl<'a, TX, H> Proof<'a, TX, H> {
+    /// Set the hashed cursor factory, e.g. to generate proofs for a state on top of the database.
+    pub fn with_hashed_cursor_factory<HF>(self, hashed_cursor_factory: HF) -> Proof<'a, TX, HF> {
+        Proof { tx: self.tx, hashed_cursor_factory, prefix_sets: self.prefix_sets }
+    }
+
+    /// Set the prefix sets of the hashed state that differs from the database.
+    pub fn with_prefix_sets(mut self, prefix_sets: TriePrefixSets) -> Self {
+        self.prefix_sets = prefix_sets;
+        self
     }
 }
 
@@ -52,9 +70,9 @@ where
             DatabaseAccountTrieCursor::new(self.tx.cursor_read::<tables::AccountsTrie>()?);
 
         // Create the walker.
-        let mut prefix_set = PrefixSetMut::default();
-        prefix_set.insert(target_nibbles.clone());
-        let walker = TrieWalker::new(trie_cursor, prefix_set.freeze());
+        let prefix_set =
+            with_targets(&self.prefix_sets.account_prefix_set, [target_nibbles.clone()]);
+        let walker = TrieWalker::new(trie_cursor, prefix_set);
 
         // Create a hash builder to rebuild the root node since it is not available in the database.
         let retainer = ProofRetainer::from_iter([target_nibbles]);
@@ -94,6 +112,64 @@ where
         Ok(account_proof)
     }
 
+    /// Generate the trie nodes that prove the given accounts and, for each account, the given
+    /// storage slots.
+    ///
+    /// This walks the account trie only once, so it is considerably cheaper than generating an
+    /// [`AccountProof`] for each account. The nodes are deduplicated and sorted.
+    pub fn multiproof(
+        &self,
+        targets: &HashMap<Address, Vec<B256>>,
+    ) -> Result<Vec<Bytes>, StateRootError> {
+        let targets = targets
+            .iter()
+            .map(|(address, slots)| (keccak256(address), slots.as_slice()))
+            .collect::<HashMap<_, _>>();
+        let target_nibbles = targets.keys().map(Nibbles::unpack).collect::<Vec<_>>();
+
+        let hashed_account_cursor = self.hashed_cursor_factory.hashed_account_cursor()?;
+        let trie_cursor =
+            DatabaseAccountTrieCursor::new(self.tx.cursor_read::<tables::AccountsTrie>()?);
+        let prefix_set =
+            with_targets(&self.prefix_sets.account_prefix_set, target_nibbles.iter().cloned());
+        let walker = TrieWalker::new(trie_cursor, prefix_set);
+
+        let retainer = ProofRetainer::from_iter(target_nibbles);
+        let mut hash_builder = HashBuilder::default().with_proof_retainer(retainer);
+
+        let mut nodes = BTreeSet::new();
+        let mut account_rlp = Vec::with_capacity(128);
+        let mut account_node_iter = TrieNodeIter::new(walker, hashed_account_cursor);
+        while let Some(account_node) = account_node_iter.try_next()? {
+            match account_node {
+                TrieElement::Branch(node) => {
+                    hash_builder.add_branch(node.key, node.value, node.children_are_in_trie);
+                }
+                TrieElement::Leaf(hashed_address, account) => {
+                    let storage_root = if let Some(slots) = targets.get(&hashed_address) {
+                        let (storage_root, storage_proofs) =
+                            self.storage_root_with_proofs(hashed_address, slots)?;
+                        nodes.extend(storage_proofs.into_iter().flat_map(|proof| proof.proof));
+                        storage_root
+                    } else {
+                        self.storage_root(hashed_address)?
+                    };
+
+                    account_rlp.clear();
+                    let account = TrieAccount::from((account, storage_root));
+                    account.encode(&mut account_rlp as &mut dyn BufMut);
+
+                    hash_builder.add_leaf(Nibbles::unpack(hashed_address), &account_rlp);
+                }
+            }
+        }
+
+        let _ = hash_builder.root();
+        nodes.extend(hash_builder.take_proofs().into_values());
+
+        Ok(nodes.into_iter().collect())
+    }
+
     /// Compute storage root.
     pub fn storage_root(&self, hashed_address: B256) -> Result<B256, StorageRootError> {
         let (storage_root, _) = self.storage_root_with_proofs(hashed_address, &[])?;
@@ -117,7 +193,10 @@ where
         }
 
         let target_nibbles = proofs.iter().map(|p| p.nibbles.clone()).collect::<Vec<_>>();
-        let prefix_set = PrefixSetMut::from(target_nibbles.clone()).freeze();
+        let prefix_set = match self.prefix_sets.storage_prefix_sets.get(&hashed_address) {
+            Some(prefix_set) => with_targets(prefix_set, target_nibbles.iter().cloned()),
+            None => PrefixSetMut::from(target_nibbles.clone()).freeze(),
+        };
         let trie_cursor = DatabaseStorageTrieCursor::new(
             self.tx.cursor_dup_read::<tables::StoragesTrie>()?,
             hashed_address,
@@ -159,6 +238,15 @@ where
     }
 }
 
+/// Returns the given prefix set extended by the target keys.
+fn with_targets(prefix_set: &PrefixSet, targets: impl IntoIterator<Item = Nibbles>) -> PrefixSet {
+    let mut prefix_set = PrefixSetMut::from(prefix_set.iter().cloned());
+    for target in targets {
+        prefix_set.insert(target);
+    }
+    prefix_set.freeze()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -446,4 +534,32 @@ mod tests {
         similar_asserts::assert_eq!(account_proof, expected);
         assert_eq!(account_proof.verify(root), Ok(()));
     }
+
+    #[test]
+    fn holesky_multiproof() {
+        // Create test database and insert genesis accounts.
+        let factory = create_test_provider_factory();
+        insert_genesis(&factory, HOLESKY.clone()).unwrap();
+
+        let deposit_contract =
+            Address::from_str("0x4242424242424242424242424242424242424242").unwrap();
+        let slots = Vec::from([B256::with_last_byte(0x22), B256::with_last_byte(0x99)]);
+        let nonexistent = Address::from_str("0x000d836201318ec6899a67540690382780743281").unwrap();
+
+        let provider = factory.provider().unwrap();
+        let proof = Proof::new(provider.tx_ref());
+
+        // the multiproof contains exactly the nodes of the individual proofs
+        let mut expected = BTreeSet::new();
+        let deposit_contract_proof = proof.account_proof(deposit_contract, &slots).unwrap();
+        expected.extend(deposit_contract_proof.proof);
+        for storage_proof in deposit_contract_proof.storage_proofs {
+            expected.extend(storage_proof.proof);
+        }
+        expected.extend(proof.account_proof(nonexistent, &[]).unwrap().proof);
+
+        let targets = HashMap::from([(deposit_contract, slots), (nonexistent, Vec::new())]);
+        let multiproof = proof.multiproof(&targets).unwrap();
+        similar_asserts::assert_eq!(multiproof, expected.into_iter().collect::<Vec<_>>());
+    }
 }
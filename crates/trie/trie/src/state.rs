@@ -1,6 +1,7 @@
 use crate::{
     hashed_cursor::HashedPostStateCursorFactory,
     prefix_set::{PrefixSetMut, TriePrefixSets},
+    proof::Proof,
     updates::TrieUpdates,
     Nibbles, StateRoot,
 };
@@ -12,7 +13,7 @@ use reth_db_api::{
     transaction::DbTx,
 };
 use reth_execution_errors::StateRootError;
-use reth_primitives::{keccak256, Account, Address, BlockNumber, B256, U256};
+use reth_primitives::{keccak256, Account, Address, BlockNumber, Bytes, B256, U256};
 use revm::db::BundleAccount;
 use std::{
     collections::{hash_map, HashMap, HashSet},
@@ -255,6 +256,21 @@ impl HashedPostState {
             .with_prefix_sets(prefix_sets)
             .root_with_updates()
     }
+
+    /// Generates the trie nodes that prove the given accounts and storage slots in the state of
+    /// the database with this [`HashedPostState`] applied. See [`Proof::multiproof`] for more info.
+    pub fn multiproof<TX: DbTx>(
+        &self,
+        tx: &TX,
+        targets: &HashMap<Address, Vec<B256>>,
+    ) -> Result<Vec<Bytes>, StateRootError> {
+        let sorted = self.clone().into_sorted();
+        let prefix_sets = self.construct_prefix_sets();
+        Proof::new(tx)
+            .with_hashed_cursor_factory(HashedPostStateCursorFactory::new(tx, &sorted))
+            .with_prefix_sets(prefix_sets)
+            .multiproof(targets)
+    }
 }
 
 /// Representation of in-memory hashed storage.
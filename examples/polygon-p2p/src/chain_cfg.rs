@@ -26,6 +26,9 @@ pub(crate) fn polygon_chain_spec() -> Arc<ChainSpec> {
         deposit_contract: None,
         base_fee_params: reth_chainspec::BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
         prune_delete_limit: 0,
+        block_rewards: Default::default(),
+        block_hash_history: Default::default(),
+        scheduled_hardforks: Default::default(),
     }
     .into()
 }
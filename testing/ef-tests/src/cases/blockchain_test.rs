@@ -2,10 +2,10 @@
 
 use crate::{
     models::{BlockchainTest, ForkSpec},
-    Case, Error, Suite,
+    Case, Error, Suite, TestResult,
 };
 use alloy_rlp::Decodable;
-use rayon::iter::{ParallelBridge, ParallelIterator};
+use rayon::iter::{IntoParallelRefIterator, ParallelBridge, ParallelIterator};
 use reth_db::test_utils::{create_test_rw_db, create_test_static_files_dir};
 use reth_primitives::{BlockBody, SealedBlock, StaticFileSegment};
 use reth_provider::{
@@ -69,110 +69,127 @@ impl Case for BlockchainTestCase {
         // Iterate through test cases, filtering by the network type to exclude specific forks.
         self.tests
             .values()
-            .filter(|case| {
-                !matches!(
-                    case.network,
-                    ForkSpec::ByzantiumToConstantinopleAt5 |
-                        ForkSpec::Constantinople |
-                        ForkSpec::ConstantinopleFix |
-                        ForkSpec::MergeEOF |
-                        ForkSpec::MergeMeterInitCode |
-                        ForkSpec::MergePush0 |
-                        ForkSpec::Unknown
-                )
-            })
+            .filter(|case| !is_skipped_fork(&case.network))
             .par_bridge()
-            .try_for_each(|case| {
-                // Create a new test database and initialize a provider for the test case.
-                let db = create_test_rw_db();
-                let (_static_files_dir, static_files_dir_path) = create_test_static_files_dir();
-                let provider = ProviderFactory::new(
-                    db.as_ref(),
-                    Arc::new(case.network.clone().into()),
-                    StaticFileProvider::read_write(static_files_dir_path).unwrap(),
-                )
-                .provider_rw()
-                .unwrap();
-
-                // Insert initial test state into the provider.
-                provider.insert_historical_block(
-                    SealedBlock::new(
-                        case.genesis_block_header.clone().into(),
-                        BlockBody::default(),
-                    )
-                    .try_seal_with_senders()
-                    .unwrap(),
-                    None,
-                )?;
-                case.pre.write_to_db(provider.tx_ref())?;
-
-                // Initialize receipts static file with genesis
-                {
-                    let mut receipts_writer = provider
-                        .static_file_provider()
-                        .latest_writer(StaticFileSegment::Receipts)
-                        .unwrap();
-                    receipts_writer.increment_block(StaticFileSegment::Receipts, 0).unwrap();
-                    receipts_writer.commit_without_sync_all().unwrap();
-                }
-
-                // Decode and insert blocks, creating a chain of blocks for the test case.
-                let last_block = case.blocks.iter().try_fold(None, |_, block| {
-                    let decoded = SealedBlock::decode(&mut block.rlp.as_ref())?;
-                    provider.insert_historical_block(
-                        decoded.clone().try_seal_with_senders().unwrap(),
-                        None,
-                    )?;
-                    Ok::<Option<SealedBlock>, Error>(Some(decoded))
-                })?;
-                provider
-                    .static_file_provider()
-                    .latest_writer(StaticFileSegment::Headers)
-                    .unwrap()
-                    .commit_without_sync_all()
-                    .unwrap();
-
-                // Execute the execution stage using the EVM processor factory for the test case
-                // network.
-                let _ = ExecutionStage::new_with_executor(
-                    reth_evm_ethereum::execute::EthExecutorProvider::ethereum(Arc::new(
-                        case.network.clone().into(),
-                    )),
-                )
-                .execute(
-                    &provider,
-                    ExecInput { target: last_block.as_ref().map(|b| b.number), checkpoint: None },
-                );
-
-                // Validate the post-state for the test case.
-                match (&case.post_state, &case.post_state_hash) {
-                    (Some(state), None) => {
-                        // Validate accounts in the state against the provider's database.
-                        for (&address, account) in state {
-                            account.assert_db(address, provider.tx_ref())?;
-                        }
-                    }
-                    (None, Some(expected_state_root)) => {
-                        // Insert state hashes into the provider based on the expected state root.
-                        let last_block = last_block.unwrap_or_default();
-                        provider.insert_hashes(
-                            0..=last_block.number,
-                            last_block.hash(),
-                            *expected_state_root,
-                        )?;
-                    }
-                    _ => return Err(Error::MissingPostState),
-                }
-
-                // Drop the provider without committing to the database.
-                drop(provider);
-                Ok(())
-            })?;
+            .try_for_each(run_test)?;
 
         Ok(())
     }
 }
 
+impl BlockchainTestCase {
+    /// Runs every test of the case and returns the result of each test.
+    ///
+    /// Unlike [`Case::run`], this does not stop at the first failing test.
+    pub fn run_each(&self) -> Vec<TestResult> {
+        self.tests
+            .par_iter()
+            .map(|(name, case)| {
+                let result = if self.skip || is_skipped_fork(&case.network) {
+                    Err(Error::Skipped)
+                } else {
+                    run_test(case)
+                };
+                TestResult { name: name.clone(), fork: case.network.clone(), result }
+            })
+            .collect()
+    }
+}
+
+/// Returns whether tests for the given fork are skipped.
+pub(crate) const fn is_skipped_fork(fork: &ForkSpec) -> bool {
+    matches!(
+        fork,
+        ForkSpec::ByzantiumToConstantinopleAt5 |
+            ForkSpec::Constantinople |
+            ForkSpec::ConstantinopleFix |
+            ForkSpec::MergeEOF |
+            ForkSpec::MergeMeterInitCode |
+            ForkSpec::MergePush0 |
+            ForkSpec::Unknown
+    )
+}
+
+/// Runs a single blockchain test.
+fn run_test(case: &BlockchainTest) -> Result<(), Error> {
+    // Create a new test database and initialize a provider for the test case.
+    let db = create_test_rw_db();
+    let (_static_files_dir, static_files_dir_path) = create_test_static_files_dir();
+    let provider = ProviderFactory::new(
+        db.as_ref(),
+        Arc::new(case.network.clone().into()),
+        StaticFileProvider::read_write(static_files_dir_path).unwrap(),
+    )
+    .provider_rw()
+    .unwrap();
+
+    // Insert initial test state into the provider.
+    provider.insert_historical_block(
+        SealedBlock::new(case.genesis_block_header.clone().into(), BlockBody::default())
+            .try_seal_with_senders()
+            .unwrap(),
+        None,
+    )?;
+    case.pre.write_to_db(provider.tx_ref())?;
+
+    // Initialize receipts static file with genesis
+    {
+        let mut receipts_writer =
+            provider.static_file_provider().latest_writer(StaticFileSegment::Receipts).unwrap();
+        receipts_writer.increment_block(StaticFileSegment::Receipts, 0).unwrap();
+        receipts_writer.commit_without_sync_all().unwrap();
+    }
+
+    // Decode and insert blocks, creating a chain of blocks for the test case.
+    let last_block = case.blocks.iter().try_fold(None, |_, block| {
+        let decoded = SealedBlock::decode(&mut block.rlp.as_ref())?;
+        provider.insert_historical_block(decoded.clone().try_seal_with_senders().unwrap(), None)?;
+        Ok::<Option<SealedBlock>, Error>(Some(decoded))
+    })?;
+    provider
+        .static_file_provider()
+        .latest_writer(StaticFileSegment::Headers)
+        .unwrap()
+        .commit_without_sync_all()
+        .unwrap();
+
+    // Execute the execution stage using the EVM processor factory for the test case
+    // network.
+    let _ = ExecutionStage::new_with_executor(
+        reth_evm_ethereum::execute::EthExecutorProvider::ethereum(Arc::new(
+            case.network.clone().into(),
+        )),
+    )
+    .execute(
+        &provider,
+        ExecInput { target: last_block.as_ref().map(|b| b.number), checkpoint: None },
+    );
+
+    // Validate the post-state for the test case.
+    match (&case.post_state, &case.post_state_hash) {
+        (Some(state), None) => {
+            // Validate accounts in the state against the provider's database.
+            for (&address, account) in state {
+                account.assert_db(address, provider.tx_ref())?;
+            }
+        }
+        (None, Some(expected_state_root)) => {
+            // Insert state hashes into the provider based on the expected state root.
+            let last_block = last_block.unwrap_or_default();
+            provider.insert_hashes(
+                0..=last_block.number,
+                last_block.hash(),
+                *expected_state_root,
+            )?;
+        }
+        _ => return Err(Error::MissingPostState),
+    }
+
+    // Drop the provider without committing to the database.
+    drop(provider);
+    Ok(())
+}
+
 /// Returns whether the test at the given path should be skipped.
 ///
 /// Some tests are edge cases that cannot happen on mainnet, while others are skipped for
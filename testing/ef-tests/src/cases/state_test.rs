@@ -0,0 +1,170 @@
+//! Test runners for `GeneralStateTests` in <https://github.com/ethereum/tests> and the state tests
+//! of <https://github.com/ethereum/execution-spec-tests>
+
+use crate::{
+    cases::blockchain_test::{is_skipped_fork, should_skip},
+    models::{ForkSpec, StateTest, StateTestPost},
+    Case, Error, TestResult,
+};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use reth_chainspec::ChainSpec;
+use reth_db::test_utils::{create_test_rw_db, create_test_static_files_dir};
+use reth_evm::ConfigureEvm;
+use reth_evm_ethereum::EthEvmConfig;
+use reth_primitives::{keccak256, TransactionSigned, U256};
+use reth_provider::{providers::StaticFileProvider, ProviderFactory, StateRootProvider};
+use reth_revm::{
+    database::StateProviderDatabase,
+    db::{states::bundle_state::BundleRetention, State},
+    primitives::{BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, TxEnv},
+};
+use std::{collections::BTreeMap, fs, path::Path, sync::Arc};
+
+/// An Ethereum state test.
+#[derive(Debug, PartialEq, Eq)]
+pub struct StateTestCase {
+    tests: BTreeMap<String, StateTest>,
+    skip: bool,
+}
+
+impl Case for StateTestCase {
+    fn load(path: &Path) -> Result<Self, Error> {
+        Ok(Self {
+            tests: {
+                let s = fs::read_to_string(path)
+                    .map_err(|error| Error::Io { path: path.into(), error })?;
+                serde_json::from_str(&s)
+                    .map_err(|error| Error::CouldNotDeserialize { path: path.into(), error })?
+            },
+            skip: should_skip(path),
+        })
+    }
+
+    /// Runs every transaction variant of every test on every fork it has a post state for.
+    ///
+    /// # Errors
+    /// Returns an error if the test is flagged for skipping or a post state does not match.
+    fn run(&self) -> Result<(), Error> {
+        if self.skip {
+            return Err(Error::Skipped)
+        }
+
+        for result in self.run_each() {
+            match result.result {
+                Ok(()) | Err(Error::Skipped) => {}
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl StateTestCase {
+    /// Runs every test of the case and returns the result of each transaction variant.
+    ///
+    /// Unlike [`Case::run`], this does not stop at the first failing test.
+    pub fn run_each(&self) -> Vec<TestResult> {
+        self.tests
+            .iter()
+            .flat_map(|(name, test)| {
+                test.post.iter().flat_map(move |(fork, posts)| {
+                    posts.iter().enumerate().map(move |(idx, post)| (name, test, fork, idx, post))
+                })
+            })
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .map(|(name, test, fork, idx, post)| {
+                let result = if self.skip || is_skipped_fork(fork) {
+                    Err(Error::Skipped)
+                } else {
+                    run_test(test, fork, post)
+                };
+                TestResult { name: format!("{name}[{idx}]"), fork: fork.clone(), result }
+            })
+            .collect()
+    }
+}
+
+/// Executes a single transaction variant of a state test and compares the resulting state root
+/// and logs hash with the expected post state.
+fn run_test(test: &StateTest, fork: &ForkSpec, post: &StateTestPost) -> Result<(), Error> {
+    let Some(txbytes) = &post.txbytes else { return Err(Error::Skipped) };
+
+    // Create a new test database with the pre-state.
+    let chain_spec: Arc<ChainSpec> = Arc::new(fork.clone().into());
+    let db = create_test_rw_db();
+    let (_static_files_dir, static_files_dir_path) = create_test_static_files_dir();
+    let factory = ProviderFactory::new(
+        db.as_ref(),
+        chain_spec.clone(),
+        StaticFileProvider::read_write(static_files_dir_path)?,
+    );
+    let provider = factory.provider_rw()?;
+    test.pre.write_to_db(provider.tx_ref())?;
+    provider.commit()?;
+
+    // Transactions that can't be decoded or recovered are invalid.
+    let Some((transaction, sender)) = TransactionSigned::decode_enveloped(&mut txbytes.as_ref())
+        .ok()
+        .and_then(|tx| tx.recover_signer().map(|sender| (tx, sender)))
+    else {
+        return expect_exception(post, "transaction could not be decoded")
+    };
+
+    let mut cfg = CfgEnvWithHandlerCfg::new(Default::default(), Default::default());
+    let mut block_env = BlockEnv::default();
+    EthEvmConfig::fill_cfg_and_block_env(
+        &mut cfg,
+        &mut block_env,
+        &chain_spec,
+        &test.env.clone().into(),
+        U256::ZERO,
+    );
+    let mut tx_env = TxEnv::default();
+    EthEvmConfig::fill_tx_env(&mut tx_env, &transaction, sender);
+
+    let mut state = State::builder()
+        .with_database(StateProviderDatabase::new(factory.latest()?))
+        .with_bundle_update()
+        .build();
+    let evm_config = EthEvmConfig::default();
+    let result = evm_config
+        .evm_with_env(&mut state, EnvWithHandlerCfg::new_with_cfg_env(cfg, block_env, tx_env))
+        .transact_commit();
+    let result = match result {
+        Ok(result) => result,
+        Err(err) => return expect_exception(post, &err.to_string()),
+    };
+    if let Some(exception) = &post.expect_exception {
+        return Err(Error::Assertion(format!("expected exception {exception}, got none")))
+    }
+
+    state.merge_transitions(BundleRetention::PlainState);
+    let state_root = factory.latest()?.state_root(&state.take_bundle())?;
+    if state_root != post.hash {
+        return Err(Error::Assertion(format!(
+            "state root mismatch: expected {}, got {state_root}",
+            post.hash
+        )))
+    }
+
+    let logs_hash = keccak256(alloy_rlp::encode(result.logs()));
+    if logs_hash != post.logs {
+        return Err(Error::Assertion(format!(
+            "logs hash mismatch: expected {}, got {logs_hash}",
+            post.logs
+        )))
+    }
+
+    Ok(())
+}
+
+/// Checks that the post state expects the transaction to be rejected.
+fn expect_exception(post: &StateTestPost, error: &str) -> Result<(), Error> {
+    if post.expect_exception.is_some() {
+        Ok(())
+    } else {
+        Err(Error::Assertion(format!("transaction unexpectedly failed: {error}")))
+    }
+}
@@ -16,5 +16,5 @@ pub mod cases;
 pub mod models;
 
 pub use case::{Case, Cases};
-pub use result::{CaseResult, Error};
+pub use result::{CaseResult, Error, TestResult};
 pub use suite::Suite;
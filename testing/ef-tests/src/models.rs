@@ -40,6 +40,74 @@ pub struct BlockchainTest {
     pub seal_engine: SealEngine,
 }
 
+/// The definition of a state test.
+///
+/// A state test executes a single transaction on top of a pre-state, for every fork and every
+/// variant of the transaction listed in the expected post states.
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateTest {
+    /// The block environment the transaction is executed in.
+    pub env: StateTestEnv,
+    /// The test pre-state.
+    pub pre: State,
+    /// The expected post states, by fork.
+    pub post: BTreeMap<ForkSpec, Vec<StateTestPost>>,
+}
+
+/// The block environment of a state test.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateTestEnv {
+    /// Coinbase.
+    pub current_coinbase: Address,
+    /// Difficulty.
+    #[serde(default)]
+    pub current_difficulty: U256,
+    /// Gas limit.
+    pub current_gas_limit: U256,
+    /// Block number.
+    pub current_number: U256,
+    /// Timestamp.
+    pub current_timestamp: U256,
+    /// Base fee per gas.
+    pub current_base_fee: Option<U256>,
+    /// Prevrandao.
+    pub current_random: Option<B256>,
+    /// Excess blob gas.
+    pub current_excess_blob_gas: Option<U256>,
+}
+
+impl From<StateTestEnv> for RethHeader {
+    fn from(value: StateTestEnv) -> Self {
+        Self {
+            beneficiary: value.current_coinbase,
+            difficulty: value.current_difficulty,
+            gas_limit: value.current_gas_limit.to::<u64>(),
+            number: value.current_number.to::<u64>(),
+            timestamp: value.current_timestamp.to::<u64>(),
+            base_fee_per_gas: value.current_base_fee.map(|v| v.to::<u64>()),
+            mix_hash: value.current_random.unwrap_or_default(),
+            excess_blob_gas: value.current_excess_blob_gas.map(|v| v.to::<u64>()),
+            ..Default::default()
+        }
+    }
+}
+
+/// The expected post state of a single transaction variant of a state test.
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateTestPost {
+    /// The expected state root.
+    pub hash: B256,
+    /// The expected hash of the RLP encoded logs.
+    pub logs: B256,
+    /// The RLP encoded transaction.
+    pub txbytes: Option<Bytes>,
+    /// The exception the transaction is expected to be rejected with.
+    pub expect_exception: Option<String>,
+}
+
 /// A block header in an Ethereum blockchain test.
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -294,6 +362,7 @@ pub enum ForkSpec {
     /// London
     London,
     /// Paris aka The Merge
+    #[serde(alias = "Paris")]
     Merge,
     /// Shanghai
     Shanghai,
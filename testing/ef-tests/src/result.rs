@@ -1,6 +1,6 @@
 //! Test results and errors
 
-use crate::Case;
+use crate::{models::ForkSpec, Case};
 use reth_db::DatabaseError;
 use reth_provider::ProviderError;
 use std::path::{Path, PathBuf};
@@ -70,6 +70,17 @@ impl CaseResult {
     }
 }
 
+/// The result of running a single test of a test case on a single fork.
+#[derive(Debug)]
+pub struct TestResult {
+    /// The name of the test.
+    pub name: String,
+    /// The fork the test was run on.
+    pub fork: ForkSpec,
+    /// The result of the test.
+    pub result: Result<(), Error>,
+}
+
 /// Assert that all the given tests passed and print the results to stdout.
 pub(crate) fn assert_tests_pass(suite_name: &str, path: &Path, results: &[CaseResult]) {
     let (passed, failed, skipped) = categorize_results(results);